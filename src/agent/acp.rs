@@ -0,0 +1,1024 @@
+//! Shared Agent Client Protocol (ACP) plumbing.
+//!
+//! `GeminiCliRunner` and the configurable `AcpAgentRunner` both speak ACP over
+//! the agent's stdio; this module owns the client side of that connection.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use agent_client_protocol as acp;
+use agent_client_protocol::Agent as _;
+use async_trait::async_trait;
+use futures::StreamExt;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
+use tokio_util::io::ReaderStream;
+
+use crate::agent::acp_workspace::AcpWorkspace;
+use crate::agent::error::AgentError;
+use crate::agent::events::{
+    AgentEvent, AssistantMessageEvent, ControlRequestEvent, ErrorEvent, ReasoningEvent,
+    SessionInitEvent, ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent,
+};
+use crate::agent::runner::{
+    AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType, ApprovalPolicy,
+};
+use crate::agent::session::SessionId;
+use crate::agent::tool_policy::{PolicyDecision, ToolPolicy};
+
+const INIT_TIMEOUT: Duration = Duration::from_secs(10);
+const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+const SESSION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Permission requests waiting on a user decision, keyed by control request id.
+/// The sender resolves with the selected option id (None cancels the request).
+pub(crate) type PendingPermissions = Arc<Mutex<HashMap<String, oneshot::Sender<Option<String>>>>>;
+
+pub(crate) struct AcpClient {
+    event_tx: mpsc::Sender<AgentEvent>,
+    saw_message: Rc<Cell<bool>>,
+    tool_titles: RefCell<HashMap<String, String>>,
+    approval_policy: ApprovalPolicy,
+    tool_policy: ToolPolicy,
+    pending_permissions: PendingPermissions,
+    working_dir: PathBuf,
+    workspace: AcpWorkspace,
+}
+
+impl AcpClient {
+    pub(crate) fn new(
+        event_tx: mpsc::Sender<AgentEvent>,
+        saw_message: Rc<Cell<bool>>,
+        approval_policy: ApprovalPolicy,
+        tool_policy: ToolPolicy,
+        pending_permissions: PendingPermissions,
+        working_dir: PathBuf,
+    ) -> Self {
+        Self {
+            workspace: AcpWorkspace::new(working_dir.clone(), event_tx.clone()),
+            event_tx,
+            saw_message,
+            tool_titles: RefCell::new(HashMap::new()),
+            approval_policy,
+            tool_policy,
+            pending_permissions,
+            working_dir,
+        }
+    }
+
+    async fn send_event(&self, event: AgentEvent) {
+        if self.event_tx.send(event).await.is_err() {
+            tracing::debug!("Failed to send ACP agent event");
+        }
+    }
+
+    fn store_tool_title(&self, tool_id: &str, title: String) {
+        self.tool_titles
+            .borrow_mut()
+            .insert(tool_id.to_string(), title);
+    }
+
+    /// Pick the most permissive allow option, falling back to the first option.
+    fn auto_allow_option(options: &[acp::PermissionOption]) -> Option<&acp::PermissionOption> {
+        options
+            .iter()
+            .find(|o| matches!(o.kind, acp::PermissionOptionKind::AllowAlways))
+            .or_else(|| {
+                options
+                    .iter()
+                    .find(|o| matches!(o.kind, acp::PermissionOptionKind::AllowOnce))
+            })
+            .or_else(|| options.first())
+    }
+
+    fn is_read_only_kind(kind: Option<&acp::ToolKind>) -> bool {
+        matches!(
+            kind,
+            Some(
+                acp::ToolKind::Read
+                    | acp::ToolKind::Search
+                    | acp::ToolKind::Think
+                    | acp::ToolKind::Fetch
+            )
+        )
+    }
+
+    /// Policy tool name for an ACP tool kind, so rules written for Claude
+    /// tools (`Bash`, `Edit`, `Read`, ...) apply to ACP agents too
+    fn policy_tool_name(kind: Option<&acp::ToolKind>) -> Option<&'static str> {
+        match kind? {
+            acp::ToolKind::Execute => Some("Bash"),
+            acp::ToolKind::Edit | acp::ToolKind::Delete | acp::ToolKind::Move => Some("Edit"),
+            acp::ToolKind::Read => Some("Read"),
+            acp::ToolKind::Search => Some("Grep"),
+            acp::ToolKind::Fetch => Some("WebFetch"),
+            _ => None,
+        }
+    }
+
+    /// Check a permission request against the repository policy rules
+    fn policy_decision(&self, args: &acp::RequestPermissionRequest) -> Option<PolicyDecision> {
+        let fields = &args.tool_call.fields;
+        let tool_name = Self::policy_tool_name(fields.kind.as_ref())?;
+        let mut input = fields
+            .raw_input
+            .clone()
+            .filter(serde_json::Value::is_object)
+            .unwrap_or_else(|| serde_json::json!({}));
+        // Fall back to the first reported location for file tools
+        if input.get("file_path").is_none() {
+            if let Some(location) = fields.locations.as_ref().and_then(|l| l.first()) {
+                input["file_path"] = serde_json::json!(location.path.to_string_lossy());
+            }
+        }
+        self.tool_policy
+            .decide(tool_name, &input, &self.working_dir)
+    }
+
+    fn should_auto_allow(&self, kind: Option<&acp::ToolKind>) -> bool {
+        match self.approval_policy {
+            ApprovalPolicy::AllowAll => true,
+            ApprovalPolicy::AllowReads => Self::is_read_only_kind(kind),
+            ApprovalPolicy::Ask => false,
+        }
+    }
+
+    /// Surface a permission request as a control request and wait for the reply.
+    async fn ask_permission(&self, args: &acp::RequestPermissionRequest) -> Option<String> {
+        let tool_id = args.tool_call.tool_call_id.0.to_string();
+        let tool_name = args
+            .tool_call
+            .fields
+            .title
+            .clone()
+            .or_else(|| self.tool_titles.borrow().get(&tool_id).cloned())
+            .unwrap_or_else(|| "tool".to_string());
+        let options: Vec<serde_json::Value> = args
+            .options
+            .iter()
+            .map(|option| {
+                serde_json::json!({
+                    "option_id": option.option_id.0.to_string(),
+                    "name": option.name,
+                    "kind": option.kind,
+                })
+            })
+            .collect();
+        let input = serde_json::json!({
+            "options": options,
+            "kind": args.tool_call.fields.kind,
+            "raw_input": args.tool_call.fields.raw_input,
+        });
+
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let (reply_tx, reply_rx) = oneshot::channel();
+        if let Ok(mut pending) = self.pending_permissions.lock() {
+            pending.insert(request_id.clone(), reply_tx);
+        } else {
+            tracing::warn!("ACP permission map poisoned; cancelling request");
+            return None;
+        }
+
+        self.send_event(AgentEvent::ControlRequest(ControlRequestEvent {
+            request_id,
+            tool_name,
+            tool_use_id: Some(tool_id),
+            input,
+        }))
+        .await;
+
+        // A dropped sender (e.g. the UI went away) is treated as a cancellation.
+        reply_rx.await.ok().flatten()
+    }
+
+    fn content_to_string(&self, content: &[acp::ToolCallContent]) -> Option<String> {
+        let mut parts = Vec::new();
+        for item in content {
+            match item {
+                acp::ToolCallContent::Content(content) => {
+                    if let acp::ContentBlock::Text(text) = &content.content {
+                        if !text.text.is_empty() {
+                            parts.push(text.text.clone());
+                        }
+                    }
+                }
+                acp::ToolCallContent::Diff(diff) => {
+                    parts.push(format!("Diff applied: {}", diff.path.display()));
+                }
+                acp::ToolCallContent::Terminal(terminal) => {
+                    match self.workspace.terminal_output_text(&terminal.terminal_id) {
+                        Some(output) => parts.push(output),
+                        None => parts.push(format!(
+                            "Terminal output available (id: {})",
+                            terminal.terminal_id.0
+                        )),
+                    }
+                }
+                _ => {}
+            }
+        }
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join("\n"))
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl acp::Client for AcpClient {
+    async fn request_permission(
+        &self,
+        args: acp::RequestPermissionRequest,
+    ) -> Result<acp::RequestPermissionResponse, acp::Error> {
+        let chosen = if let Some(decision) = self.policy_decision(&args) {
+            // Policy answers are one-off so the agent never remembers them;
+            // a denial without a reject option cancels the request instead.
+            let once = match decision {
+                PolicyDecision::Deny(rule) => {
+                    tracing::info!(rule = %rule, "ACP tool call denied by policy");
+                    args.options
+                        .iter()
+                        .find(|o| matches!(o.kind, acp::PermissionOptionKind::RejectOnce))
+                }
+                PolicyDecision::Allow => args
+                    .options
+                    .iter()
+                    .find(|o| matches!(o.kind, acp::PermissionOptionKind::AllowOnce))
+                    .or_else(|| Self::auto_allow_option(&args.options)),
+            };
+            once.map(|option| option.option_id.clone())
+        } else if self.should_auto_allow(args.tool_call.fields.kind.as_ref()) {
+            Self::auto_allow_option(&args.options).map(|option| option.option_id.clone())
+        } else {
+            self.ask_permission(&args).await.and_then(|option_id| {
+                args.options
+                    .iter()
+                    .find(|option| option.option_id.0.as_ref() == option_id)
+                    .map(|option| option.option_id.clone())
+            })
+        };
+
+        let outcome = if let Some(option_id) = chosen {
+            acp::RequestPermissionOutcome::Selected(acp::SelectedPermissionOutcome::new(option_id))
+        } else {
+            acp::RequestPermissionOutcome::Cancelled
+        };
+
+        Ok(acp::RequestPermissionResponse::new(outcome))
+    }
+
+    async fn session_notification(&self, args: acp::SessionNotification) -> Result<(), acp::Error> {
+        match args.update {
+            acp::SessionUpdate::AgentMessageChunk(chunk) => {
+                if let acp::ContentBlock::Text(text) = chunk.content {
+                    self.saw_message.set(true);
+                    self.send_event(AgentEvent::AssistantMessage(AssistantMessageEvent {
+                        text: text.text,
+                        is_final: false,
+                    }))
+                    .await;
+                }
+            }
+            acp::SessionUpdate::AgentThoughtChunk(chunk) => {
+                if let acp::ContentBlock::Text(text) = chunk.content {
+                    self.send_event(AgentEvent::AssistantReasoning(ReasoningEvent {
+                        text: text.text,
+                    }))
+                    .await;
+                }
+            }
+            acp::SessionUpdate::ToolCall(tool_call) => {
+                let tool_id = tool_call.tool_call_id.0.to_string();
+                let title = if tool_call.title.is_empty() {
+                    "tool".to_string()
+                } else {
+                    tool_call.title.clone()
+                };
+                self.store_tool_title(&tool_id, title.clone());
+                let arguments = tool_call.raw_input.unwrap_or(serde_json::Value::Null);
+                self.send_event(AgentEvent::ToolStarted(ToolStartedEvent {
+                    tool_name: title.clone(),
+                    tool_id,
+                    arguments,
+                }))
+                .await;
+            }
+            acp::SessionUpdate::ToolCallUpdate(update) => {
+                let tool_id = update.tool_call_id.0.to_string();
+                if let Some(title) = update.fields.title.clone() {
+                    self.store_tool_title(&tool_id, title);
+                }
+                if let Some(acp::ToolCallStatus::Completed) = update.fields.status {
+                    let result = update
+                        .fields
+                        .content
+                        .as_ref()
+                        .and_then(|content| self.content_to_string(content))
+                        .or_else(|| update.fields.raw_output.as_ref().map(|v| v.to_string()));
+                    self.send_event(AgentEvent::ToolCompleted(ToolCompletedEvent {
+                        tool_id,
+                        success: true,
+                        result,
+                        error: None,
+                    }))
+                    .await;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn write_text_file(
+        &self,
+        args: acp::WriteTextFileRequest,
+    ) -> Result<acp::WriteTextFileResponse, acp::Error> {
+        self.workspace.write_text_file(args).await
+    }
+
+    async fn read_text_file(
+        &self,
+        args: acp::ReadTextFileRequest,
+    ) -> Result<acp::ReadTextFileResponse, acp::Error> {
+        self.workspace.read_text_file(args).await
+    }
+
+    async fn create_terminal(
+        &self,
+        args: acp::CreateTerminalRequest,
+    ) -> Result<acp::CreateTerminalResponse, acp::Error> {
+        self.workspace.create_terminal(args).await
+    }
+
+    async fn terminal_output(
+        &self,
+        args: acp::TerminalOutputRequest,
+    ) -> Result<acp::TerminalOutputResponse, acp::Error> {
+        self.workspace.terminal_output(args).await
+    }
+
+    async fn release_terminal(
+        &self,
+        args: acp::ReleaseTerminalRequest,
+    ) -> Result<acp::ReleaseTerminalResponse, acp::Error> {
+        self.workspace.release_terminal(args).await
+    }
+
+    async fn wait_for_terminal_exit(
+        &self,
+        args: acp::WaitForTerminalExitRequest,
+    ) -> Result<acp::WaitForTerminalExitResponse, acp::Error> {
+        self.workspace.wait_for_terminal_exit(args).await
+    }
+
+    async fn kill_terminal_command(
+        &self,
+        args: acp::KillTerminalCommandRequest,
+    ) -> Result<acp::KillTerminalCommandResponse, acp::Error> {
+        self.workspace.kill_terminal_command(args).await
+    }
+
+    async fn ext_method(&self, _: acp::ExtRequest) -> Result<acp::ExtResponse, acp::Error> {
+        Err(acp::Error::method_not_found())
+    }
+
+    async fn ext_notification(&self, _: acp::ExtNotification) -> Result<(), acp::Error> {
+        Ok(())
+    }
+}
+
+/// Picks the auth method to use from those advertised by the agent.
+pub(crate) type AuthSelector = Box<dyn Fn(&[acp::AuthMethod]) -> Option<String> + Send>;
+
+/// Agent-specific details for an ACP session.
+pub(crate) struct AcpLaunch {
+    /// Human-readable agent name used in error messages.
+    pub label: String,
+    /// Chooses which advertised auth method to call `authenticate` with.
+    pub select_auth: AuthSelector,
+    /// Appended to the authentication timeout error.
+    pub auth_hint: String,
+}
+
+/// Spawn `cmd` and drive a single-prompt ACP session over its stdio.
+///
+/// The returned handle accepts `AgentInput::AcpPermission` replies for
+/// permission requests surfaced as `ControlRequest` events.
+pub(crate) async fn start_acp_agent(
+    mut cmd: Command,
+    config: AgentStartConfig,
+    launch: AcpLaunch,
+) -> Result<AgentHandle, AgentError> {
+    let mut child = cmd.spawn()?;
+
+    let pid = child.id().ok_or(AgentError::ProcessSpawnFailed)?;
+    let stdout = child.stdout.take().ok_or(AgentError::StdoutCaptureFailed)?;
+    let stderr = child.stderr.take();
+    let child_stdin = child.stdin.take().ok_or_else(|| {
+        AgentError::Config(format!("Failed to capture stdin for {}", launch.label))
+    })?;
+
+    let (tx, rx) = mpsc::channel::<AgentEvent>(256);
+    let tx_for_monitor = tx.clone();
+
+    let (mut to_acp_writer, acp_incoming_reader) = tokio::io::duplex(64 * 1024);
+    let (acp_out_writer, acp_out_reader) = tokio::io::duplex(64 * 1024);
+
+    tokio::spawn(async move {
+        let mut stdout_stream = ReaderStream::new(stdout);
+        while let Some(res) = stdout_stream.next().await {
+            match res {
+                Ok(data) => {
+                    if to_acp_writer.write_all(&data).await.is_err() {
+                        break;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+    });
+
+    tokio::spawn(async move {
+        let mut child_stdin = child_stdin;
+        let mut reader = ReaderStream::new(acp_out_reader);
+        while let Some(res) = reader.next().await {
+            match res {
+                Ok(data) => {
+                    if child_stdin.write_all(&data).await.is_err() {
+                        break;
+                    }
+                    let _ = child_stdin.flush().await;
+                }
+                Err(_) => break,
+            }
+        }
+    });
+
+    let outgoing = acp_out_writer.compat_write();
+    let incoming = acp_incoming_reader.compat();
+    let prompt = config.prompt.clone();
+    let working_dir = config.working_dir.clone();
+    let tx_for_session = tx.clone();
+    let approval_policy = config.approval_policy;
+    let tool_policy = config.tool_policy.clone();
+    let pending_permissions: PendingPermissions = Arc::new(Mutex::new(HashMap::new()));
+
+    let (input_tx, mut input_rx) = mpsc::channel::<AgentInput>(16);
+    let pending_for_input = pending_permissions.clone();
+    tokio::spawn(async move {
+        while let Some(input) = input_rx.recv().await {
+            match input {
+                AgentInput::AcpPermission {
+                    request_id,
+                    option_id,
+                } => {
+                    let sender = pending_for_input
+                        .lock()
+                        .ok()
+                        .and_then(|mut pending| pending.remove(&request_id));
+                    match sender {
+                        Some(sender) => {
+                            let _ = sender.send(option_id);
+                        }
+                        None => {
+                            tracing::warn!(
+                                request_id = %request_id,
+                                "No pending ACP permission request"
+                            );
+                        }
+                    }
+                }
+                AgentInput::ClaudeJsonl(_)
+                | AgentInput::CodexPrompt { .. }
+                | AgentInput::OpencodeQuestion { .. }
+                | AgentInput::CodexApproval { .. } => {
+                    tracing::warn!("Ignored unsupported input sent to ACP input channel");
+                }
+            }
+        }
+    });
+
+    let AcpLaunch {
+        label,
+        select_auth,
+        auth_hint,
+    } = launch;
+    let label_for_monitor = label.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to build ACP runtime");
+        rt.block_on(async move {
+            let local = tokio::task::LocalSet::new();
+            local
+                .run_until(async move {
+                    let saw_message = Rc::new(Cell::new(false));
+                    let client = AcpClient::new(
+                        tx_for_session.clone(),
+                        saw_message.clone(),
+                        approval_policy,
+                        tool_policy,
+                        pending_permissions,
+                        working_dir.clone(),
+                    );
+                    let (conn, io_fut) =
+                        acp::ClientSideConnection::new(client, outgoing, incoming, |fut| {
+                            tokio::task::spawn_local(fut);
+                        });
+                    let conn = Rc::new(conn);
+
+                    tokio::task::spawn_local(async move {
+                        let _ = io_fut.await;
+                    });
+
+                    let init_response = match tokio::time::timeout(
+                        INIT_TIMEOUT,
+                        conn.initialize(
+                            acp::InitializeRequest::new(acp::ProtocolVersion::V1)
+                                .client_capabilities(
+                                    acp::ClientCapabilities::new()
+                                        .fs(acp::FileSystemCapability::new()
+                                            .read_text_file(true)
+                                            .write_text_file(true))
+                                        .terminal(true),
+                                ),
+                        ),
+                    )
+                    .await
+                    {
+                        Ok(Ok(response)) => response,
+                        Ok(Err(err)) => {
+                            let _ = tx_for_session
+                                .send(AgentEvent::Error(ErrorEvent {
+                                    message: format!(
+                                        "Failed to initialize {} ACP: {}",
+                                        label, err
+                                    ),
+                                    is_fatal: true,
+                                    code: None,
+                                    details: None,
+                                }))
+                                .await;
+                            return;
+                        }
+                        Err(_) => {
+                            let _ = tx_for_session
+                                .send(AgentEvent::Error(ErrorEvent {
+                                    message: format!(
+                                        "Timed out initializing {} ACP. Ensure it is installed and authenticated.",
+                                        label
+                                    ),
+                                    is_fatal: true,
+                                    code: None,
+                                    details: None,
+                                }))
+                                .await;
+                            return;
+                        }
+                    };
+
+                    if !init_response.auth_methods.is_empty() {
+                        if let Some(method_id) =
+                            select_auth(&init_response.auth_methods)
+                        {
+                            match tokio::time::timeout(
+                                AUTH_TIMEOUT,
+                                conn.authenticate(acp::AuthenticateRequest::new(
+                                    method_id.clone(),
+                                )),
+                            )
+                            .await
+                            {
+                                Ok(Ok(_)) => {}
+                                Ok(Err(err)) => {
+                                    let _ = tx_for_session
+                                        .send(AgentEvent::Error(ErrorEvent {
+                                            message: format!(
+                                                "{} authentication failed ({}): {}",
+                                                label, method_id, err
+                                            ),
+                                            is_fatal: true,
+                                            code: None,
+                                            details: None,
+                                        }))
+                                        .await;
+                                    return;
+                                }
+                                Err(_) => {
+                                    let _ = tx_for_session
+                                        .send(AgentEvent::Error(ErrorEvent {
+                                            message: format!(
+                                                "{} authentication timed out. {}",
+                                                label, auth_hint
+                                            )
+                                            .trim_end()
+                                            .to_string(),
+                                            is_fatal: true,
+                                            code: None,
+                                            details: None,
+                                        }))
+                                        .await;
+                                    return;
+                                }
+                            }
+                        }
+                    }
+
+                    let session_id = match tokio::time::timeout(
+                        SESSION_TIMEOUT,
+                        conn.new_session(acp::NewSessionRequest::new(working_dir)),
+                    )
+                    .await
+                    {
+                        Ok(Ok(response)) => response.session_id,
+                        Ok(Err(err)) => {
+                            let _ = tx_for_session
+                                .send(AgentEvent::Error(ErrorEvent {
+                                    message: format!(
+                                        "Failed to create {} session: {}",
+                                        label, err
+                                    ),
+                                    is_fatal: true,
+                                    code: None,
+                                    details: None,
+                                }))
+                                .await;
+                            return;
+                        }
+                        Err(_) => {
+                            let _ = tx_for_session
+                                .send(AgentEvent::Error(ErrorEvent {
+                                    message: format!(
+                                        "Timed out creating {} session. Ensure it is authenticated.",
+                                        label
+                                    ),
+                                    is_fatal: true,
+                                    code: None,
+                                    details: None,
+                                }))
+                                .await;
+                            return;
+                        }
+                    };
+
+                    let _ = tx_for_session
+                        .send(AgentEvent::SessionInit(SessionInitEvent {
+                            session_id: SessionId::from_string(session_id.0.to_string()),
+                            model: None,
+                        }))
+                        .await;
+
+                    let _ = tx_for_session.send(AgentEvent::TurnStarted).await;
+
+                    if !prompt.is_empty() {
+                        let req = acp::PromptRequest::new(
+                            session_id.clone(),
+                            vec![acp::ContentBlock::Text(acp::TextContent::new(prompt))],
+                        );
+
+                        let prompt_result = conn.prompt(req).await;
+
+                        if let Err(err) = prompt_result {
+                            let _ = tx_for_session
+                                .send(AgentEvent::Error(ErrorEvent {
+                                    message: format!("{} prompt failed: {}", label, err),
+                                    is_fatal: true,
+                                    code: None,
+                                    details: None,
+                                }))
+                                .await;
+                            return;
+                        }
+                    }
+
+                    if saw_message.get() {
+                        let _ = tx_for_session
+                            .send(AgentEvent::AssistantMessage(AssistantMessageEvent {
+                                text: String::new(),
+                                is_final: true,
+                            }))
+                            .await;
+                    }
+
+                    let _ = tx_for_session
+                        .send(AgentEvent::TurnCompleted(TurnCompletedEvent {
+                            usage: Default::default(),
+                        }))
+                        .await;
+
+                    let _ = conn.cancel(acp::CancelNotification::new(session_id)).await;
+                })
+                .await;
+        });
+    });
+
+    tokio::spawn(async move {
+        use tokio::io::AsyncReadExt;
+
+        let label = label_for_monitor;
+        let status = child.wait().await;
+        let stderr_content = if let Some(mut stderr) = stderr {
+            let mut buf = String::new();
+            if let Err(err) = stderr.read_to_string(&mut buf).await {
+                tracing::debug!(error = %err, agent = %label, "Failed to read ACP agent stderr");
+            }
+            buf
+        } else {
+            String::new()
+        };
+
+        match status {
+            Ok(exit_status) if !exit_status.success() => {
+                let message = if stderr_content.is_empty() {
+                    format!("{} process exited with status: {}", label, exit_status)
+                } else {
+                    format!(
+                        "{} process failed ({}): {}",
+                        label,
+                        exit_status,
+                        stderr_content.trim()
+                    )
+                };
+                if let Err(send_err) = tx_for_monitor
+                    .send(AgentEvent::Error(ErrorEvent {
+                        message,
+                        is_fatal: true,
+                        code: None,
+                        details: None,
+                    }))
+                    .await
+                {
+                    tracing::debug!(
+                        error = ?send_err,
+                        "Failed to send ACP agent process failure"
+                    );
+                }
+            }
+            Err(err) => {
+                let message = format!("Failed to wait for {} process: {}", label, err);
+                if let Err(send_err) = tx_for_monitor
+                    .send(AgentEvent::Error(ErrorEvent {
+                        message,
+                        is_fatal: true,
+                        code: None,
+                        details: None,
+                    }))
+                    .await
+                {
+                    tracing::debug!(
+                        error = ?send_err,
+                        "Failed to send ACP agent wait error"
+                    );
+                }
+            }
+            Ok(_) => {}
+        }
+    });
+
+    Ok(AgentHandle::new(rx, pid, Some(input_tx)))
+}
+
+/// Forward a permission reply to a running ACP session.
+pub(crate) async fn send_acp_input(
+    handle: &AgentHandle,
+    input: AgentInput,
+    label: &str,
+) -> Result<(), AgentError> {
+    if !matches!(input, AgentInput::AcpPermission { .. }) {
+        return Err(AgentError::NotSupported(format!(
+            "{} only accepts permission responses",
+            label
+        )));
+    }
+    let Some(ref input_tx) = handle.input_tx else {
+        return Err(AgentError::ChannelClosed);
+    };
+    input_tx
+        .send(input)
+        .await
+        .map_err(|_| AgentError::ChannelClosed)
+}
+
+/// An ACP-speaking agent declared in `config.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcpAgentConfig {
+    /// Executable name or path.
+    pub command: String,
+    /// Arguments passed before any per-session arguments.
+    pub args: Vec<String>,
+    /// Extra environment variables for the agent process.
+    pub env: HashMap<String, String>,
+    /// Name shown in the provider and model selectors.
+    pub display_name: String,
+    /// Auth method id to authenticate with, if the agent requires one.
+    pub auth_method: Option<String>,
+    /// Whether the agent can read workspace files; when not, files mentioned
+    /// with `@` are inlined into prompts.
+    pub read_files: bool,
+}
+
+impl AcpAgentConfig {
+    /// Default display name when the config does not set one.
+    pub const DEFAULT_DISPLAY_NAME: &'static str = "ACP Agent";
+
+    pub fn new(command: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+            args: Vec::new(),
+            env: HashMap::new(),
+            display_name: Self::DEFAULT_DISPLAY_NAME.to_string(),
+            auth_method: None,
+            read_files: true,
+        }
+    }
+
+    /// Resolve the configured command to an executable path.
+    pub fn resolve_binary(&self) -> Option<PathBuf> {
+        let path = PathBuf::from(&self.command);
+        if path.components().count() > 1 {
+            return path.exists().then_some(path);
+        }
+        which::which(&self.command).ok()
+    }
+}
+
+/// Runner for any agent that speaks the Agent Client Protocol over stdio.
+pub struct AcpAgentRunner {
+    config: Option<AcpAgentConfig>,
+}
+
+impl AcpAgentRunner {
+    pub fn new(config: Option<AcpAgentConfig>) -> Self {
+        Self { config }
+    }
+
+    /// Name of the configured agent
+    pub fn display_name(&self) -> &str {
+        self.config
+            .as_ref()
+            .map(|agent| agent.display_name.as_str())
+            .unwrap_or(AcpAgentConfig::DEFAULT_DISPLAY_NAME)
+    }
+
+    fn agent_config(&self) -> Result<&AcpAgentConfig, AgentError> {
+        self.config.as_ref().ok_or_else(|| {
+            AgentError::Config("No ACP agent configured; add an [acp_agent] section".to_string())
+        })
+    }
+
+    fn build_command(agent: &AcpAgentConfig, bin: PathBuf, config: &AgentStartConfig) -> Command {
+        let mut cmd = Command::new(bin);
+        cmd.args(&agent.args);
+        for arg in &config.additional_args {
+            cmd.arg(arg);
+        }
+        cmd.envs(&agent.env);
+        cmd.current_dir(&config.working_dir);
+        cmd.stdin(Stdio::piped());
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+        cmd
+    }
+}
+
+#[async_trait]
+impl AgentRunner for AcpAgentRunner {
+    fn agent_type(&self) -> AgentType {
+        AgentType::Acp
+    }
+
+    async fn start(&self, config: AgentStartConfig) -> Result<AgentHandle, AgentError> {
+        let agent = self.agent_config()?;
+        if !config.images.is_empty() {
+            return Err(AgentError::NotSupported(format!(
+                "{} does not support image attachments",
+                agent.display_name
+            )));
+        }
+        if config.resume_session.is_some() {
+            tracing::debug!(
+                agent = %agent.display_name,
+                "ACP agent runner does not support session resume; starting new session"
+            );
+        }
+
+        let bin = agent
+            .resolve_binary()
+            .ok_or_else(|| AgentError::BinaryNotFound(agent.command.clone()))?;
+        let cmd = Self::build_command(agent, bin, &config);
+        let auth_method = agent.auth_method.clone();
+        let launch = AcpLaunch {
+            label: agent.display_name.clone(),
+            select_auth: Box::new(move |methods| {
+                let wanted = auth_method.as_deref()?;
+                methods
+                    .iter()
+                    .any(|method| method.id.0.as_ref() == wanted)
+                    .then(|| wanted.to_string())
+            }),
+            auth_hint: String::new(),
+        };
+        start_acp_agent(cmd, config, launch).await
+    }
+
+    async fn send_input(&self, handle: &AgentHandle, input: AgentInput) -> Result<(), AgentError> {
+        send_acp_input(handle, input, self.display_name()).await
+    }
+
+    async fn stop(&self, handle: &AgentHandle) -> Result<(), AgentError> {
+        #[cfg(unix)]
+        {
+            let result = unsafe { libc::kill(handle.pid as i32, libc::SIGTERM) };
+            if result == -1 {
+                return Err(AgentError::Io(std::io::Error::last_os_error()));
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = handle;
+            return Err(AgentError::NotSupported(
+                "Stop not implemented on this platform".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn kill(&self, handle: &AgentHandle) -> Result<(), AgentError> {
+        #[cfg(unix)]
+        {
+            let result = unsafe { libc::kill(handle.pid as i32, libc::SIGKILL) };
+            if result == -1 {
+                return Err(AgentError::Io(std::io::Error::last_os_error()));
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = handle;
+            return Err(AgentError::NotSupported(
+                "Kill not implemented on this platform".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn is_available(&self) -> bool {
+        self.binary_path().is_some()
+    }
+
+    fn binary_path(&self) -> Option<PathBuf> {
+        self.config
+            .as_ref()
+            .and_then(AcpAgentConfig::resolve_binary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_only_tool_kinds() {
+        assert!(AcpClient::is_read_only_kind(Some(&acp::ToolKind::Read)));
+        assert!(AcpClient::is_read_only_kind(Some(&acp::ToolKind::Search)));
+        assert!(!AcpClient::is_read_only_kind(Some(&acp::ToolKind::Edit)));
+        assert!(!AcpClient::is_read_only_kind(Some(&acp::ToolKind::Execute)));
+        assert!(!AcpClient::is_read_only_kind(None));
+    }
+
+    #[test]
+    fn test_build_command_applies_config() {
+        let mut agent = AcpAgentConfig::new("my-agent");
+        agent.args = vec!["--acp".to_string()];
+        agent
+            .env
+            .insert("AGENT_TOKEN".to_string(), "secret".to_string());
+
+        let config = AgentStartConfig::new("hi", PathBuf::from("/tmp"));
+        let cmd = AcpAgentRunner::build_command(&agent, PathBuf::from("/bin/my-agent"), &config);
+        let std_cmd = cmd.as_std();
+
+        assert_eq!(std_cmd.get_program(), "/bin/my-agent");
+        let args: Vec<_> = std_cmd.get_args().collect();
+        assert_eq!(args, vec!["--acp"]);
+        assert!(std_cmd
+            .get_envs()
+            .any(|(key, value)| key == "AGENT_TOKEN" && value == Some("secret".as_ref())));
+    }
+
+    #[test]
+    fn test_unconfigured_runner_is_unavailable() {
+        let runner = AcpAgentRunner::new(None);
+        assert!(!runner.is_available());
+        assert!(runner.binary_path().is_none());
+    }
+}
@@ -0,0 +1,485 @@
+//! Workspace-scoped file system and terminal services for ACP clients.
+//!
+//! ACP agents can ask the client to read/write files and run commands instead of
+//! doing it themselves. Serving those requests from Conduit keeps every access
+//! inside the session's workspace and lets the UI see the resulting changes.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+use std::rc::Rc;
+
+use agent_client_protocol as acp;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Command;
+use tokio::sync::{mpsc, oneshot, watch};
+
+use crate::agent::events::{AgentEvent, CommandOutputEvent, FileChangedEvent, FileOperation};
+
+/// Default cap on retained terminal output when the agent does not set one.
+const DEFAULT_OUTPUT_BYTE_LIMIT: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TerminalExit {
+    exit_code: Option<u32>,
+    signal: Option<String>,
+}
+
+impl TerminalExit {
+    fn to_acp(&self) -> acp::TerminalExitStatus {
+        acp::TerminalExitStatus::new()
+            .exit_code(self.exit_code)
+            .signal(self.signal.clone())
+    }
+}
+
+struct Terminal {
+    command: String,
+    output: RefCell<String>,
+    truncated: Cell<bool>,
+    byte_limit: usize,
+    exit_rx: watch::Receiver<Option<TerminalExit>>,
+    kill_tx: RefCell<Option<oneshot::Sender<()>>>,
+}
+
+impl Terminal {
+    fn append(&self, chunk: &str) {
+        let mut output = self.output.borrow_mut();
+        output.push_str(chunk);
+        if truncate_front(&mut output, self.byte_limit) {
+            self.truncated.set(true);
+        }
+    }
+
+    fn exit(&self) -> Option<TerminalExit> {
+        self.exit_rx.borrow().clone()
+    }
+
+    fn kill(&self) {
+        if let Some(kill_tx) = self.kill_tx.borrow_mut().take() {
+            let _ = kill_tx.send(());
+        }
+    }
+}
+
+/// File system and terminal access confined to a single workspace directory.
+pub(crate) struct AcpWorkspace {
+    root: PathBuf,
+    event_tx: mpsc::Sender<AgentEvent>,
+    terminals: RefCell<HashMap<String, Rc<Terminal>>>,
+    next_terminal_id: Cell<u64>,
+}
+
+impl AcpWorkspace {
+    pub(crate) fn new(root: PathBuf, event_tx: mpsc::Sender<AgentEvent>) -> Self {
+        let root = root.canonicalize().unwrap_or(root);
+        Self {
+            root,
+            event_tx,
+            terminals: RefCell::new(HashMap::new()),
+            next_terminal_id: Cell::new(1),
+        }
+    }
+
+    async fn send_event(&self, event: AgentEvent) {
+        if self.event_tx.send(event).await.is_err() {
+            tracing::debug!("Failed to send ACP workspace event");
+        }
+    }
+
+    /// Resolve `path` and reject anything that lands outside the workspace.
+    fn resolve(&self, path: &Path) -> Result<PathBuf, acp::Error> {
+        resolve_in_root(&self.root, path).ok_or_else(|| {
+            invalid_params(format!("Path is outside the workspace: {}", path.display()))
+        })
+    }
+
+    fn display_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+
+    pub(crate) async fn read_text_file(
+        &self,
+        args: acp::ReadTextFileRequest,
+    ) -> Result<acp::ReadTextFileResponse, acp::Error> {
+        let path = self.resolve(&args.path)?;
+        let content = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|err| internal_error(format!("Failed to read {}: {}", path.display(), err)))?;
+        Ok(acp::ReadTextFileResponse::new(slice_lines(
+            &content, args.line, args.limit,
+        )))
+    }
+
+    pub(crate) async fn write_text_file(
+        &self,
+        args: acp::WriteTextFileRequest,
+    ) -> Result<acp::WriteTextFileResponse, acp::Error> {
+        let path = self.resolve(&args.path)?;
+        let existed = tokio::fs::try_exists(&path).await.unwrap_or(false);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|err| {
+                internal_error(format!("Failed to create {}: {}", parent.display(), err))
+            })?;
+        }
+        tokio::fs::write(&path, args.content.as_bytes())
+            .await
+            .map_err(|err| {
+                internal_error(format!("Failed to write {}: {}", path.display(), err))
+            })?;
+
+        self.send_event(AgentEvent::FileChanged(FileChangedEvent {
+            path: self.display_path(&path),
+            operation: if existed {
+                FileOperation::Update
+            } else {
+                FileOperation::Create
+            },
+        }))
+        .await;
+        Ok(acp::WriteTextFileResponse::new())
+    }
+
+    pub(crate) async fn create_terminal(
+        &self,
+        args: acp::CreateTerminalRequest,
+    ) -> Result<acp::CreateTerminalResponse, acp::Error> {
+        let cwd = match args.cwd.as_ref() {
+            Some(cwd) => self.resolve(cwd)?,
+            None => self.root.clone(),
+        };
+        let display_command = std::iter::once(args.command.as_str())
+            .chain(args.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        // Agents commonly send a whole shell line with no separate arguments.
+        let mut cmd = if args.args.is_empty() && args.command.contains(char::is_whitespace) {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg(&args.command);
+            cmd
+        } else {
+            let mut cmd = Command::new(&args.command);
+            cmd.args(&args.args);
+            cmd
+        };
+        cmd.current_dir(&cwd)
+            .envs(
+                args.env
+                    .iter()
+                    .map(|var| (var.name.clone(), var.value.clone())),
+            )
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        let mut child = cmd.spawn().map_err(|err| {
+            internal_error(format!("Failed to run `{}`: {}", display_command, err))
+        })?;
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+
+        let (exit_tx, exit_rx) = watch::channel(None);
+        let (kill_tx, kill_rx) = oneshot::channel();
+        let byte_limit = args
+            .output_byte_limit
+            .and_then(|limit| usize::try_from(limit).ok())
+            .unwrap_or(DEFAULT_OUTPUT_BYTE_LIMIT);
+        let terminal = Rc::new(Terminal {
+            command: display_command,
+            output: RefCell::new(String::new()),
+            truncated: Cell::new(false),
+            byte_limit,
+            exit_rx,
+            kill_tx: RefCell::new(Some(kill_tx)),
+        });
+
+        let id = self.next_terminal_id.get();
+        self.next_terminal_id.set(id + 1);
+        let terminal_id = format!("term-{}", id);
+        self.terminals
+            .borrow_mut()
+            .insert(terminal_id.clone(), terminal.clone());
+
+        let stdout_task =
+            stdout.map(|stdout| tokio::task::spawn_local(pump_output(stdout, terminal.clone())));
+        let stderr_task =
+            stderr.map(|stderr| tokio::task::spawn_local(pump_output(stderr, terminal.clone())));
+
+        let event_tx = self.event_tx.clone();
+        tokio::task::spawn_local(async move {
+            let status = tokio::select! {
+                status = child.wait() => status,
+                _ = kill_rx => {
+                    let _ = child.start_kill();
+                    child.wait().await
+                }
+            };
+            for task in [stdout_task, stderr_task].into_iter().flatten() {
+                let _ = task.await;
+            }
+
+            let exit = match status {
+                Ok(status) => exit_from_status(status),
+                Err(err) => {
+                    tracing::debug!(error = %err, "Failed to wait for ACP terminal");
+                    TerminalExit {
+                        exit_code: None,
+                        signal: None,
+                    }
+                }
+            };
+            let exit_code = exit.exit_code.and_then(|code| i32::try_from(code).ok());
+            let _ = exit_tx.send(Some(exit));
+
+            // Gemini reports completion through the tool call, so this stays a
+            // streaming update that carries the output and final exit code.
+            let output = terminal.output.borrow().clone();
+            let _ = event_tx
+                .send(AgentEvent::CommandOutput(CommandOutputEvent {
+                    command: terminal.command.clone(),
+                    output,
+                    exit_code,
+                    is_streaming: true,
+                }))
+                .await;
+        });
+
+        Ok(acp::CreateTerminalResponse::new(acp::TerminalId::new(
+            terminal_id,
+        )))
+    }
+
+    fn terminal(&self, terminal_id: &acp::TerminalId) -> Result<Rc<Terminal>, acp::Error> {
+        self.terminals
+            .borrow()
+            .get(terminal_id.0.as_ref())
+            .cloned()
+            .ok_or_else(|| invalid_params(format!("Unknown terminal: {}", terminal_id.0)))
+    }
+
+    /// Current output of a terminal, used when rendering terminal tool content.
+    pub(crate) fn terminal_output_text(&self, terminal_id: &acp::TerminalId) -> Option<String> {
+        self.terminals
+            .borrow()
+            .get(terminal_id.0.as_ref())
+            .map(|terminal| terminal.output.borrow().clone())
+    }
+
+    pub(crate) async fn terminal_output(
+        &self,
+        args: acp::TerminalOutputRequest,
+    ) -> Result<acp::TerminalOutputResponse, acp::Error> {
+        let terminal = self.terminal(&args.terminal_id)?;
+        let output = terminal.output.borrow().clone();
+        Ok(
+            acp::TerminalOutputResponse::new(output, terminal.truncated.get())
+                .exit_status(terminal.exit().map(|exit| exit.to_acp())),
+        )
+    }
+
+    pub(crate) async fn wait_for_terminal_exit(
+        &self,
+        args: acp::WaitForTerminalExitRequest,
+    ) -> Result<acp::WaitForTerminalExitResponse, acp::Error> {
+        let terminal = self.terminal(&args.terminal_id)?;
+        let mut exit_rx = terminal.exit_rx.clone();
+        let exit = exit_rx
+            .wait_for(Option::is_some)
+            .await
+            .map_err(|_| internal_error("Terminal exited without a status".to_string()))?
+            .clone()
+            .unwrap_or(TerminalExit {
+                exit_code: None,
+                signal: None,
+            });
+        Ok(acp::WaitForTerminalExitResponse::new(exit.to_acp()))
+    }
+
+    pub(crate) async fn kill_terminal_command(
+        &self,
+        args: acp::KillTerminalCommandRequest,
+    ) -> Result<acp::KillTerminalCommandResponse, acp::Error> {
+        self.terminal(&args.terminal_id)?.kill();
+        Ok(acp::KillTerminalCommandResponse::new())
+    }
+
+    pub(crate) async fn release_terminal(
+        &self,
+        args: acp::ReleaseTerminalRequest,
+    ) -> Result<acp::ReleaseTerminalResponse, acp::Error> {
+        let terminal = self
+            .terminals
+            .borrow_mut()
+            .remove(args.terminal_id.0.as_ref())
+            .ok_or_else(|| invalid_params(format!("Unknown terminal: {}", args.terminal_id.0)))?;
+        terminal.kill();
+        Ok(acp::ReleaseTerminalResponse::new())
+    }
+}
+
+/// Append a stream to the terminal's shared buffer. The output goes out once
+/// the command exits; until then the agent polls it with `terminal/output`.
+async fn pump_output(mut reader: impl AsyncRead + Unpin, terminal: Rc<Terminal>) {
+    let mut buf = [0u8; 8192];
+    loop {
+        match reader.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => terminal.append(&String::from_utf8_lossy(&buf[..n])),
+        }
+    }
+}
+
+fn exit_from_status(status: std::process::ExitStatus) -> TerminalExit {
+    #[cfg(unix)]
+    let signal = {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal().map(|signal| signal.to_string())
+    };
+    #[cfg(not(unix))]
+    let signal = None;
+
+    TerminalExit {
+        exit_code: status.code().and_then(|code| u32::try_from(code).ok()),
+        signal,
+    }
+}
+
+fn invalid_params(message: String) -> acp::Error {
+    acp::Error::invalid_params().with_data(message)
+}
+
+fn internal_error(message: String) -> acp::Error {
+    acp::Error::internal_error().with_data(message)
+}
+
+/// Lexically resolve `path` against `root`, returning `None` if it escapes.
+///
+/// Existing ancestors are canonicalized so symlinks cannot point outside the root.
+fn resolve_in_root(root: &Path, path: &Path) -> Option<PathBuf> {
+    let joined = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        root.join(path)
+    };
+
+    let mut normalized = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    return None;
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    if !normalized.starts_with(root) {
+        return None;
+    }
+
+    // `symlink_metadata` sees a dangling link, which `exists` would skip over;
+    // such a link fails to canonicalize and is rejected rather than written
+    // through
+    let existing = normalized
+        .ancestors()
+        .find(|ancestor| std::fs::symlink_metadata(ancestor).is_ok())?;
+    let canonical = existing.canonicalize().ok()?;
+    if !canonical.starts_with(root) {
+        return None;
+    }
+    Some(normalized)
+}
+
+/// Apply ACP's 1-based `line` offset and `limit` to file content.
+fn slice_lines(content: &str, line: Option<u32>, limit: Option<u32>) -> String {
+    if line.is_none() && limit.is_none() {
+        return content.to_string();
+    }
+    let skip = line
+        .map(|line| line.saturating_sub(1) as usize)
+        .unwrap_or(0);
+    let take = limit.map(|limit| limit as usize).unwrap_or(usize::MAX);
+    content
+        .split_inclusive('\n')
+        .skip(skip)
+        .take(take)
+        .collect()
+}
+
+/// Drop bytes from the front so `output` fits in `limit`, keeping char boundaries.
+fn truncate_front(output: &mut String, limit: usize) -> bool {
+    if output.len() <= limit {
+        return false;
+    }
+    let mut cut = output.len() - limit;
+    while !output.is_char_boundary(cut) {
+        cut += 1;
+    }
+    output.drain(..cut);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_in_root_rejects_escapes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+
+        assert_eq!(
+            resolve_in_root(&root, Path::new("src/main.rs")),
+            Some(root.join("src/main.rs"))
+        );
+        assert_eq!(
+            resolve_in_root(&root, &root.join("a/../b.txt")),
+            Some(root.join("b.txt"))
+        );
+        assert_eq!(resolve_in_root(&root, Path::new("../outside.txt")), None);
+        assert_eq!(resolve_in_root(&root, Path::new("/etc/passwd")), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_in_root_rejects_symlink_escape() {
+        let outside = tempfile::tempdir().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.join("link")).unwrap();
+
+        assert_eq!(resolve_in_root(&root, Path::new("link/file.txt")), None);
+
+        // A dangling link would create its target outside the root on write
+        std::os::unix::fs::symlink(outside.path().join("new.txt"), root.join("dangling.txt"))
+            .unwrap();
+        assert_eq!(resolve_in_root(&root, Path::new("dangling.txt")), None);
+    }
+
+    #[test]
+    fn test_slice_lines() {
+        let content = "one\ntwo\nthree\nfour\n";
+        assert_eq!(slice_lines(content, None, None), content);
+        assert_eq!(slice_lines(content, Some(2), Some(2)), "two\nthree\n");
+        assert_eq!(slice_lines(content, Some(4), None), "four\n");
+        assert_eq!(slice_lines(content, None, Some(1)), "one\n");
+    }
+
+    #[test]
+    fn test_truncate_front_keeps_char_boundaries() {
+        let mut output = "héllo".to_string();
+        assert!(truncate_front(&mut output, 4));
+        assert_eq!(output, "llo");
+
+        let mut short = "ok".to_string();
+        assert!(!truncate_front(&mut short, 4));
+        assert_eq!(short, "ok");
+    }
+}
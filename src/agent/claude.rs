@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 
 use async_trait::async_trait;
@@ -11,9 +11,15 @@ use crate::agent::events::{
     AgentEvent, AssistantMessageEvent, ControlRequestEvent, ErrorEvent, SessionInitEvent,
     TokenUsage, ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent, TurnFailedEvent,
 };
-use crate::agent::runner::{AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType};
+use crate::agent::provider_error::classified_error;
+use crate::agent::runner::{
+    AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType, ApprovalPolicy,
+};
 use crate::agent::session::SessionId;
 use crate::agent::stream::{ClaudeRawEvent, JsonlStreamParser};
+use crate::agent::supervisor::ProcessExit;
+use crate::agent::tool_approval::{self, ToolApprovalChoice};
+use crate::agent::tool_policy::{PolicyDecision, ToolPolicy};
 
 pub struct ClaudeCodeRunner {
     binary_path: PathBuf,
@@ -66,6 +72,12 @@ impl ClaudeCodeRunner {
                 .arg(config.allowed_tools.join(","));
         }
 
+        // Deny rules from the repository policy
+        let disallowed_tools = config.tool_policy.claude_disallowed_tools();
+        if !disallowed_tools.is_empty() {
+            cmd.arg("--disallowedTools").arg(disallowed_tools.join(","));
+        }
+
         // Resume session if provided
         if let Some(session_id) = &config.resume_session {
             cmd.arg("--resume").arg(session_id.as_str());
@@ -141,12 +153,10 @@ impl ClaudeCodeRunner {
                 // Check for authentication failure or other errors
                 if let Some(ref error) = assistant.error {
                     if error == "authentication_failed" {
-                        return vec![AgentEvent::Error(ErrorEvent {
-                            message: "Authentication failed. Please run `claude /login` in your terminal to authenticate.".to_string(),
-                            is_fatal: true,
-                            code: None,
-                            details: None,
-                        })];
+                        return vec![AgentEvent::Error(classified_error(
+                            "Authentication failed. Please run `claude /login` in your terminal to authenticate.".to_string(),
+                            true,
+                        ))];
                     }
                     let detail = assistant
                         .extract_text()
@@ -162,12 +172,7 @@ impl ClaudeCodeRunner {
                         "Claude assistant error"
                     );
                     // Handle other error types as fatal errors
-                    return vec![AgentEvent::Error(ErrorEvent {
-                        message,
-                        is_fatal: true,
-                        code: None,
-                        details: None,
-                    })];
+                    return vec![AgentEvent::Error(classified_error(message, true))];
                 }
 
                 // Extract text content
@@ -235,12 +240,10 @@ impl ClaudeCodeRunner {
                         .unwrap_or_else(|| "Unknown error".to_string());
                     tracing::warn!(error = %detail, "Claude result error");
                     return vec![
-                        AgentEvent::Error(ErrorEvent {
-                            message: format!("Claude error: {}", detail),
-                            is_fatal: true,
-                            code: None,
-                            details: None,
-                        }),
+                        AgentEvent::Error(classified_error(
+                            format!("Claude error: {}", detail),
+                            true,
+                        )),
                         AgentEvent::TurnFailed(TurnFailedEvent { error: detail }),
                     ];
                 }
@@ -286,6 +289,26 @@ impl ClaudeCodeRunner {
         }
     }
 
+    /// Claude's stream has no turn start; add `TurnStarted` before the first
+    /// event of each turn after the session init
+    fn mark_turn_start(events: Vec<AgentEvent>, turn_open: &mut bool) -> Vec<AgentEvent> {
+        let mut marked = Vec::with_capacity(events.len() + 1);
+        for event in events {
+            if !*turn_open && !matches!(event, AgentEvent::SessionInit(_)) {
+                marked.push(AgentEvent::TurnStarted);
+                *turn_open = true;
+            }
+            if matches!(
+                event,
+                AgentEvent::TurnCompleted(_) | AgentEvent::TurnFailed(_)
+            ) {
+                *turn_open = false;
+            }
+            marked.push(event);
+        }
+        marked
+    }
+
     fn build_control_initialize_jsonl() -> String {
         let payload = json!({
             "type": "control_request",
@@ -314,8 +337,52 @@ impl ClaudeCodeRunner {
         Ok(format!("{json}\n"))
     }
 
-    fn is_interactive_tool(tool_name: &str) -> bool {
-        matches!(tool_name, "AskUserQuestion" | "ExitPlanMode")
+    /// Tool calls that need an answer from the user: interactive tools
+    /// always, everything else unless the approval policy allows it.
+    fn needs_user_answer(
+        tool_name: &str,
+        policy: ApprovalPolicy,
+        allowed_tools: &[String],
+    ) -> bool {
+        tool_approval::is_interactive_tool(tool_name)
+            || !tool_approval::should_auto_allow(policy, allowed_tools, tool_name)
+    }
+
+    /// Answer for a `can_use_tool` request that Conduit settles without the
+    /// user: policy rules first, then the approval policy.
+    fn auto_answer(
+        request: &ControlRequestEvent,
+        policy: ApprovalPolicy,
+        allowed_tools: &[String],
+        tool_policy: &ToolPolicy,
+        working_dir: &Path,
+    ) -> Option<serde_json::Value> {
+        let tool_name = request.tool_name.as_str();
+        if tool_approval::is_interactive_tool(tool_name) {
+            return None;
+        }
+        match tool_policy.decide(tool_name, &request.input, working_dir) {
+            Some(PolicyDecision::Deny(rule)) => {
+                tracing::info!(tool_name, rule = %rule, "Tool call denied by policy");
+                let mut response = json!({
+                    "behavior": "deny",
+                    "message": PolicyDecision::deny_message(&rule),
+                });
+                if let Some(tool_use_id) = &request.tool_use_id {
+                    response["toolUseID"] = json!(tool_use_id);
+                }
+                Some(response)
+            }
+            Some(PolicyDecision::Allow) => Some(tool_approval::permission_response(
+                request,
+                ToolApprovalChoice::AllowOnce,
+                None,
+            )),
+            None if !Self::needs_user_answer(tool_name, policy, allowed_tools) => Some(
+                tool_approval::permission_response(request, ToolApprovalChoice::AllowOnce, None),
+            ),
+            None => None,
+        }
     }
 }
 
@@ -389,6 +456,16 @@ impl AgentRunner for ClaudeCodeRunner {
                                     "Ignored OpenCode question response sent to Claude input channel"
                                 );
                             }
+                            AgentInput::AcpPermission { .. } => {
+                                tracing::warn!(
+                                    "Ignored ACP permission response sent to Claude input channel"
+                                );
+                            }
+                            AgentInput::CodexApproval { .. } => {
+                                tracing::warn!(
+                                    "Ignored Codex approval response sent to Claude input channel"
+                                );
+                            }
                         }
                     }
                     if let Err(err) = stdin.shutdown().await {
@@ -417,6 +494,10 @@ impl AgentRunner for ClaudeCodeRunner {
         let (tx, rx) = mpsc::channel::<AgentEvent>(256);
         let tx_for_monitor = tx.clone();
         let control_tx = input_tx.clone();
+        let approval_policy = config.approval_policy;
+        let allowed_tools = config.allowed_tools.clone();
+        let tool_policy = config.tool_policy.clone();
+        let working_dir = config.working_dir.clone();
 
         // Spawn JSONL parser task
         tokio::spawn(async move {
@@ -444,6 +525,7 @@ impl AgentRunner for ClaudeCodeRunner {
             });
 
             // Convert and forward events
+            let mut turn_open = false;
             'outer: while let Some(raw_event) = raw_rx.recv().await {
                 if let ClaudeRawEvent::ControlRequest(request) = &raw_event {
                     match &request.request {
@@ -452,43 +534,49 @@ impl AgentRunner for ClaudeCodeRunner {
                             input,
                             tool_use_id,
                         } => {
-                            if Self::is_interactive_tool(tool_name) {
-                                let event = AgentEvent::ControlRequest(ControlRequestEvent {
-                                    request_id: request.request_id.clone(),
-                                    tool_name: tool_name.clone(),
-                                    tool_use_id: tool_use_id.clone(),
-                                    input: input.clone(),
-                                });
-                                if tx.send(event).await.is_err() {
-                                    break 'outer;
-                                }
-                                if control_tx.is_none() {
-                                    tracing::warn!(
-                                        tool_name = tool_name,
-                                        "Control request for interactive tool received without stdin channel"
-                                    );
-                                }
-                            } else if let Some(ref tx) = control_tx {
-                                let mut response_payload = serde_json::Map::new();
-                                response_payload.insert("behavior".to_string(), json!("allow"));
-                                response_payload.insert("updatedInput".to_string(), input.clone());
-                                if let Some(tool_use_id) = tool_use_id.as_ref() {
-                                    response_payload
-                                        .insert("toolUseID".to_string(), json!(tool_use_id));
-                                }
-                                if let Ok(response) = Self::build_control_response_jsonl(
-                                    &request.request_id,
-                                    serde_json::Value::Object(response_payload),
-                                ) {
-                                    if let Err(err) =
-                                        tx.send(AgentInput::ClaudeJsonl(response)).await
-                                    {
+                            let control_request = ControlRequestEvent {
+                                request_id: request.request_id.clone(),
+                                tool_name: tool_name.clone(),
+                                tool_use_id: tool_use_id.clone(),
+                                input: input.clone(),
+                            };
+                            let response_payload = Self::auto_answer(
+                                &control_request,
+                                approval_policy,
+                                &allowed_tools,
+                                &tool_policy,
+                                &working_dir,
+                            );
+                            match response_payload {
+                                None => {
+                                    let event = AgentEvent::ControlRequest(control_request);
+                                    if tx.send(event).await.is_err() {
+                                        break 'outer;
+                                    }
+                                    if control_tx.is_none() {
                                         tracing::warn!(
-                                            "Failed to respond to control request: {}",
-                                            err
+                                            tool_name = tool_name,
+                                            "Control request received without stdin channel"
                                         );
                                     }
                                 }
+                                Some(response_payload) => {
+                                    if let Some(ref tx) = control_tx {
+                                        if let Ok(response) = Self::build_control_response_jsonl(
+                                            &request.request_id,
+                                            response_payload,
+                                        ) {
+                                            if let Err(err) =
+                                                tx.send(AgentInput::ClaudeJsonl(response)).await
+                                            {
+                                                tracing::warn!(
+                                                    "Failed to respond to control request: {}",
+                                                    err
+                                                );
+                                            }
+                                        }
+                                    }
+                                }
                             }
                         }
                         crate::agent::stream::ClaudeControlRequestType::HookCallback { .. } => {
@@ -512,7 +600,7 @@ impl AgentRunner for ClaudeCodeRunner {
                     continue 'outer;
                 }
 
-                for event in Self::convert_event(raw_event) {
+                for event in Self::mark_turn_start(Self::convert_event(raw_event), &mut turn_open) {
                     if tx.send(event).await.is_err() {
                         break 'outer;
                     }
@@ -544,22 +632,9 @@ impl AgentRunner for ClaudeCodeRunner {
             // Check if process failed
             match status {
                 Ok(exit_status) if !exit_status.success() => {
-                    let error_msg = if stderr_content.is_empty() {
-                        format!("Claude process exited with status: {}", exit_status)
-                    } else {
-                        format!(
-                            "Claude process failed ({}): {}",
-                            exit_status,
-                            stderr_content.trim()
-                        )
-                    };
+                    let exit = ProcessExit::from_status(&exit_status, &stderr_content);
                     if let Err(send_err) = tx_for_monitor
-                        .send(AgentEvent::Error(ErrorEvent {
-                            message: error_msg,
-                            is_fatal: true,
-                            code: None,
-                            details: None,
-                        }))
+                        .send(AgentEvent::Error(exit.error_event("Claude")))
                         .await
                     {
                         tracing::debug!(
@@ -660,6 +735,67 @@ mod tests {
         ClaudeResultEvent, ClaudeSystemEvent, ClaudeUsage,
     };
 
+    #[test]
+    fn test_interactive_tools_always_need_an_answer() {
+        let allowed = vec!["Edit".to_string()];
+        for policy in [ApprovalPolicy::Ask, ApprovalPolicy::AllowAll] {
+            assert!(ClaudeCodeRunner::needs_user_answer(
+                "AskUserQuestion",
+                policy,
+                &allowed
+            ));
+        }
+        assert!(ClaudeCodeRunner::needs_user_answer(
+            "Bash",
+            ApprovalPolicy::Ask,
+            &allowed
+        ));
+        assert!(!ClaudeCodeRunner::needs_user_answer(
+            "Edit",
+            ApprovalPolicy::Ask,
+            &allowed
+        ));
+        assert!(!ClaudeCodeRunner::needs_user_answer(
+            "Bash",
+            ApprovalPolicy::AllowAll,
+            &allowed
+        ));
+    }
+
+    #[test]
+    fn test_policy_rules_answer_before_approval_policy() {
+        let tool_policy = ToolPolicy::from_toml(
+            crate::agent::tool_policy::TomlToolPolicy {
+                allow: vec!["Bash(cargo test*)".to_string()],
+                deny: vec!["Bash(git push*)".to_string()],
+                codex: None,
+            },
+            "test",
+        );
+        let request = |command: &str| ControlRequestEvent {
+            request_id: "req-1".to_string(),
+            tool_name: "Bash".to_string(),
+            tool_use_id: Some("toolu_1".to_string()),
+            input: json!({ "command": command }),
+        };
+        let answer = |command: &str, policy: ApprovalPolicy| {
+            ClaudeCodeRunner::auto_answer(
+                &request(command),
+                policy,
+                &[],
+                &tool_policy,
+                Path::new("/repo"),
+            )
+        };
+
+        let denied = answer("git push --force", ApprovalPolicy::AllowAll).unwrap();
+        assert_eq!(denied["behavior"], "deny");
+        assert_eq!(denied["toolUseID"], "toolu_1");
+        let allowed = answer("cargo test", ApprovalPolicy::Ask).unwrap();
+        assert_eq!(allowed["behavior"], "allow");
+        assert!(answer("cargo build", ApprovalPolicy::Ask).is_none());
+    }
+
     /// Test that a system init event is correctly converted to SessionInit
     #[test]
     fn test_convert_system_init_event() {
@@ -805,6 +941,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_turn_start_is_added_before_each_turn() {
+        let completed = || {
+            AgentEvent::TurnCompleted(TurnCompletedEvent {
+                usage: TokenUsage::default(),
+            })
+        };
+        let init = AgentEvent::SessionInit(SessionInitEvent {
+            session_id: SessionId::from_string("test-session"),
+            model: None,
+        });
+        let mut turn_open = false;
+        let first = ClaudeCodeRunner::mark_turn_start(vec![init, completed()], &mut turn_open);
+        assert!(matches!(first[0], AgentEvent::SessionInit(_)));
+        assert!(matches!(first[1], AgentEvent::TurnStarted));
+        assert!(matches!(first[2], AgentEvent::TurnCompleted(_)));
+        assert!(!turn_open);
+
+        let second = ClaudeCodeRunner::mark_turn_start(vec![completed()], &mut turn_open);
+        assert!(matches!(second[0], AgentEvent::TurnStarted));
+        assert_eq!(second.len(), 2);
+    }
+
     /// Test that auth failure assistant event produces Error instead of AssistantMessage
     #[test]
     fn test_convert_auth_failure_produces_error_event() {
@@ -840,11 +999,41 @@ mod tests {
                     "Should tell user how to fix: {}",
                     err.message
                 );
+                assert_eq!(err.code.as_deref(), Some("auth_expired"));
             }
             other => panic!("Expected Error event, got {:?}", other),
         }
     }
 
+    #[test]
+    fn test_convert_rate_limit_error_is_classified() {
+        let raw = ClaudeRawEvent::Assistant(ClaudeAssistantEvent {
+            message: Some(ClaudeMessageObject {
+                model: Some("<synthetic>".to_string()),
+                id: Some("test-id".to_string()),
+                role: Some("assistant".to_string()),
+                content: Some(vec![ClaudeContentBlock::Text {
+                    text: "API Error: 429 rate limit exceeded, retry after 20 seconds".to_string(),
+                }]),
+                stop_reason: Some("stop_sequence".to_string()),
+                usage: None,
+            }),
+            text: None,
+            session_id: Some("test-session".to_string()),
+            error: Some("rate_limit".to_string()),
+        });
+
+        let events = ClaudeCodeRunner::convert_event(raw);
+        let AgentEvent::Error(err) = &events[0] else {
+            panic!("Expected Error event, got {:?}", events[0]);
+        };
+        assert_eq!(err.code.as_deref(), Some("rate_limited"));
+        assert_eq!(
+            crate::agent::provider_error::retry_after(err),
+            Some(std::time::Duration::from_secs(20))
+        );
+    }
+
     /// Test that normal assistant events (no error field) still work
     #[test]
     fn test_normal_assistant_event_still_produces_message() {
@@ -27,12 +27,20 @@ use tokio::sync::{mpsc, oneshot, Mutex};
 
 use crate::agent::error::AgentError;
 use crate::agent::events::{
-    AgentEvent, AssistantMessageEvent, CommandOutputEvent, ContextCompactionEvent, ErrorEvent,
-    FileChangedEvent, FileOperation, ReasoningEvent, SessionInitEvent, TokenUsage, TokenUsageEvent,
-    ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent, TurnFailedEvent,
+    AgentEvent, AssistantMessageEvent, CommandOutputEvent, ContextCompactionEvent,
+    ControlRequestEvent, ErrorEvent, FileChangedEvent, FileOperation, ReasoningEvent,
+    SessionInitEvent, TokenUsage, TokenUsageEvent, ToolCompletedEvent, ToolStartedEvent,
+    TurnCompletedEvent, TurnFailedEvent,
+};
+use crate::agent::provider_error::classified_error;
+use crate::agent::runner::{
+    AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType, CodexApprovalMode,
+    CodexSandboxMode,
 };
-use crate::agent::runner::{AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType};
 use crate::agent::session::SessionId;
+use crate::agent::supervisor::ProcessExit;
+use crate::agent::tool_approval::ToolApprovalChoice;
+use crate::agent::tool_policy::{argv_command, shell_join, PolicyDecision, ToolPolicy};
 
 const CODEX_NPX_PACKAGE: &str = "@openai/codex";
 const CODEX_NPX_VERSION_ENV: &str = "CODEX_NPX_VERSION";
@@ -103,11 +111,25 @@ impl JsonRpcPeer {
     }
 }
 
+/// Which response type a Codex approval request expects
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CodexApprovalKind {
+    Exec,
+    Patch,
+}
+
+/// Approval requests waiting on the user, keyed by the id sent with the
+/// `ControlRequest` event
+type PendingApprovals = Arc<Mutex<HashMap<String, (RequestId, CodexApprovalKind)>>>;
+
 #[derive(Default)]
 struct CodexEventState {
     exec_command_by_id: HashMap<String, String>,
     exec_output_by_id: HashMap<String, String>,
     last_usage: Option<TokenUsage>,
+    /// Cumulative usage at the end of the previous turn. Codex reports running
+    /// totals, so per-turn usage is the difference from this baseline.
+    turn_usage_baseline: TokenUsage,
     last_total_tokens: Option<i64>,
     pending_compaction: bool,
     message_stream_source: Option<MessageStreamSource>,
@@ -159,28 +181,168 @@ impl CodexCliRunner {
         }
     }
 
-    fn approval_policy() -> AskForApproval {
-        match std::env::var("CODEX_APPROVAL_POLICY")
-            .unwrap_or_default()
-            .to_lowercase()
-            .as_str()
-        {
-            "untrusted" => AskForApproval::UnlessTrusted,
-            "on-failure" => AskForApproval::OnFailure,
-            "on-request" => AskForApproval::OnRequest,
-            _ => AskForApproval::Never,
+    /// Approval policy: the repository policy file, then the session setting,
+    /// then `CODEX_APPROVAL_POLICY`. The returned flag is true when the mode
+    /// was chosen explicitly, in which case approval requests that the policy
+    /// rules don't answer go to the user. Deny rules alone need Codex to ask
+    /// before running commands so they can be applied; the rest is approved.
+    fn approval_settings(config: &AgentStartConfig) -> (AskForApproval, bool) {
+        let mode = config
+            .tool_policy
+            .codex_approval_policy()
+            .or(config.codex_approval)
+            .or_else(|| {
+                std::env::var("CODEX_APPROVAL_POLICY")
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+            });
+        match mode {
+            Some(mode) => (
+                Self::ask_for_approval(mode),
+                mode != CodexApprovalMode::Never,
+            ),
+            None if config.tool_policy.has_deny_rules() => (AskForApproval::UnlessTrusted, false),
+            None => (AskForApproval::Never, false),
         }
     }
 
-    fn sandbox_mode() -> SandboxMode {
-        match std::env::var("CODEX_SANDBOX_MODE")
-            .unwrap_or_default()
-            .to_lowercase()
-            .as_str()
-        {
-            "read-only" => SandboxMode::ReadOnly,
-            "workspace-write" => SandboxMode::WorkspaceWrite,
-            _ => SandboxMode::DangerFullAccess,
+    fn ask_for_approval(mode: CodexApprovalMode) -> AskForApproval {
+        match mode {
+            CodexApprovalMode::Untrusted => AskForApproval::UnlessTrusted,
+            CodexApprovalMode::OnFailure => AskForApproval::OnFailure,
+            CodexApprovalMode::OnRequest => AskForApproval::OnRequest,
+            CodexApprovalMode::Never => AskForApproval::Never,
+        }
+    }
+
+    /// Sandbox mode: the repository policy file, then the session setting,
+    /// then `CODEX_SANDBOX_MODE`. Deny rules on file changes keep writes
+    /// inside the workspace.
+    fn sandbox_mode(config: &AgentStartConfig) -> SandboxMode {
+        let mode = config
+            .tool_policy
+            .codex_sandbox_mode()
+            .or(config.codex_sandbox)
+            .or_else(|| {
+                std::env::var("CODEX_SANDBOX_MODE")
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+            });
+        match mode {
+            Some(CodexSandboxMode::ReadOnly) => SandboxMode::ReadOnly,
+            Some(CodexSandboxMode::WorkspaceWrite) => SandboxMode::WorkspaceWrite,
+            Some(CodexSandboxMode::DangerFullAccess) => SandboxMode::DangerFullAccess,
+            None if config.tool_policy.restricts_writes() => SandboxMode::WorkspaceWrite,
+            None => SandboxMode::DangerFullAccess,
+        }
+    }
+
+    /// Map a user's approval choice onto a Codex review decision. "Always
+    /// allow" is not offered for Codex; it is refused rather than quietly
+    /// narrowed to the session.
+    fn review_decision(choice: ToolApprovalChoice) -> ReviewDecision {
+        match choice {
+            ToolApprovalChoice::AllowOnce => ReviewDecision::Approved,
+            ToolApprovalChoice::AllowSession => ReviewDecision::ApprovedForSession,
+            ToolApprovalChoice::AllowAlways | ToolApprovalChoice::Deny => ReviewDecision::Denied,
+        }
+    }
+
+    /// Review decision for a command Codex wants to run, if the policy
+    /// rules answer it. Rules see the script inside a `bash -lc` wrapper.
+    fn exec_decision(tool_policy: &ToolPolicy, command: &[String]) -> Option<ReviewDecision> {
+        match tool_policy.decide_command(&argv_command(command))? {
+            PolicyDecision::Deny(rule) => {
+                tracing::info!(rule = %rule, "Codex command denied by policy");
+                Some(ReviewDecision::Denied)
+            }
+            PolicyDecision::Allow => Some(ReviewDecision::Approved),
+        }
+    }
+
+    /// Review decision for a patch: denied if any changed file is denied,
+    /// approved if every changed file is allowed
+    fn patch_decision<'a>(
+        tool_policy: &ToolPolicy,
+        paths: impl IntoIterator<Item = &'a PathBuf>,
+        working_dir: &Path,
+    ) -> Option<ReviewDecision> {
+        let mut all_allowed = true;
+        let mut any = false;
+        for path in paths {
+            any = true;
+            let input = serde_json::json!({ "file_path": path.to_string_lossy() });
+            match tool_policy.decide("Edit", &input, working_dir) {
+                Some(PolicyDecision::Deny(rule)) => {
+                    tracing::info!(rule = %rule, path = %path.display(), "Codex patch denied by policy");
+                    return Some(ReviewDecision::Denied);
+                }
+                Some(PolicyDecision::Allow) => {}
+                None => all_allowed = false,
+            }
+        }
+        (any && all_allowed).then_some(ReviewDecision::Approved)
+    }
+
+    /// Send the answer to a Codex approval request
+    async fn send_review_decision(
+        peer: &JsonRpcPeer,
+        request_id: RequestId,
+        kind: CodexApprovalKind,
+        decision: ReviewDecision,
+    ) {
+        let result = match kind {
+            CodexApprovalKind::Exec => {
+                serde_json::to_value(ExecCommandApprovalResponse { decision })
+            }
+            CodexApprovalKind::Patch => {
+                serde_json::to_value(ApplyPatchApprovalResponse { decision })
+            }
+        };
+        let response = JSONRPCResponse {
+            id: request_id,
+            result: result.unwrap_or(Value::Null),
+        };
+        if let Err(err) = peer.send(&response).await {
+            tracing::warn!(error = %err, "Failed to send Codex approval response");
+        }
+    }
+
+    /// Answer an approval request from the policy rules, or forward it to the
+    /// user as a `ControlRequest` when an explicit approval mode is set
+    #[allow(clippy::too_many_arguments)]
+    async fn handle_approval_request(
+        peer: &JsonRpcPeer,
+        pending: &PendingApprovals,
+        events: &mpsc::Sender<AgentEvent>,
+        rpc_id: RequestId,
+        kind: CodexApprovalKind,
+        decision: Option<ReviewDecision>,
+        prompts_user: bool,
+        (tool_name, call_id, input): (&str, String, Value),
+    ) {
+        if let Some(decision) = decision {
+            Self::send_review_decision(peer, rpc_id, kind, decision).await;
+            return;
+        }
+        if !prompts_user {
+            Self::send_review_decision(peer, rpc_id, kind, ReviewDecision::Approved).await;
+            return;
+        }
+
+        let request_id = uuid::Uuid::new_v4().to_string();
+        pending
+            .lock()
+            .await
+            .insert(request_id.clone(), (rpc_id, kind));
+        let event = AgentEvent::ControlRequest(ControlRequestEvent {
+            request_id,
+            tool_name: tool_name.to_string(),
+            tool_use_id: Some(call_id),
+            input,
+        });
+        if events.send(event).await.is_err() {
+            tracing::debug!("Dropping Codex approval request; event receiver closed");
         }
     }
 
@@ -272,7 +434,14 @@ impl CodexCliRunner {
                 vec![AgentEvent::TurnStarted]
             }
             EventMsg::TurnComplete(_) => {
-                let usage = state.last_usage.take().unwrap_or_default();
+                let usage = match state.last_usage.take() {
+                    Some(total) => {
+                        let turn = total.since(&state.turn_usage_baseline);
+                        state.turn_usage_baseline = total;
+                        turn
+                    }
+                    None => TokenUsage::default(),
+                };
                 state.message_stream_source = None;
                 state.reasoning_stream_source = None;
                 vec![AgentEvent::TurnCompleted(TurnCompletedEvent { usage })]
@@ -560,12 +729,7 @@ impl CodexCliRunner {
                 Vec::new()
             }
             EventMsg::Error(err) => vec![
-                AgentEvent::Error(ErrorEvent {
-                    message: err.message.clone(),
-                    is_fatal: true,
-                    code: None,
-                    details: None,
-                }),
+                AgentEvent::Error(classified_error(err.message.clone(), true)),
                 AgentEvent::TurnFailed(TurnFailedEvent {
                     error: err.message.clone(),
                 }),
@@ -576,12 +740,10 @@ impl CodexCliRunner {
                 code: None,
                 details: None,
             })],
-            EventMsg::StreamError(err) => vec![AgentEvent::Error(ErrorEvent {
-                message: format!("Stream error: {}", err.message),
-                is_fatal: false,
-                code: None,
-                details: None,
-            })],
+            EventMsg::StreamError(err) => vec![AgentEvent::Error(classified_error(
+                format!("Stream error: {}", err.message),
+                false,
+            ))],
             _ => serde_json::to_value(event)
                 .ok()
                 .map(|data| vec![AgentEvent::Raw { data }])
@@ -652,6 +814,11 @@ impl AgentRunner for CodexCliRunner {
 
         // Spawn JSON-RPC read loop
         let reader_peer = peer.clone();
+        let tool_policy = config.tool_policy.clone();
+        let policy_working_dir = config.working_dir.clone();
+        let (approval_policy, prompts_user) = Self::approval_settings(&config);
+        let pending_approvals: PendingApprovals = Arc::new(Mutex::new(HashMap::new()));
+        let reader_approvals = pending_approvals.clone();
         tokio::spawn(async move {
             let mut reader = BufReader::new(stdout);
             let mut buffer = String::new();
@@ -704,42 +871,56 @@ impl AgentRunner for CodexCliRunner {
                                 if let Ok(server_req) = ServerRequest::try_from(request) {
                                     match server_req {
                                         ServerRequest::ApplyPatchApproval {
-                                            request_id, ..
+                                            request_id,
+                                            params,
                                         } => {
-                                            let response = JSONRPCResponse {
-                                                id: request_id,
-                                                result: serde_json::to_value(
-                                                    ApplyPatchApprovalResponse {
-                                                        decision: ReviewDecision::Approved,
-                                                    },
-                                                )
-                                                .unwrap_or(Value::Null),
-                                            };
-                                            if let Err(err) = reader_peer.send(&response).await {
-                                                tracing::warn!(
-                                                    error = %err,
-                                                    "Failed to send patch approval response"
-                                                );
-                                            }
+                                            let decision = Self::patch_decision(
+                                                &tool_policy,
+                                                params.file_changes.keys(),
+                                                &policy_working_dir,
+                                            );
+                                            let files: Vec<String> = params
+                                                .file_changes
+                                                .keys()
+                                                .map(|path| path.display().to_string())
+                                                .collect();
+                                            let input = serde_json::json!({
+                                                "files": files,
+                                                "description": params.reason,
+                                            });
+                                            Self::handle_approval_request(
+                                                &reader_peer,
+                                                &reader_approvals,
+                                                &tx_for_events,
+                                                request_id,
+                                                CodexApprovalKind::Patch,
+                                                decision,
+                                                prompts_user,
+                                                ("ApplyPatch", params.call_id, input),
+                                            )
+                                            .await;
                                         }
                                         ServerRequest::ExecCommandApproval {
-                                            request_id, ..
+                                            request_id,
+                                            params,
                                         } => {
-                                            let response = JSONRPCResponse {
-                                                id: request_id,
-                                                result: serde_json::to_value(
-                                                    ExecCommandApprovalResponse {
-                                                        decision: ReviewDecision::Approved,
-                                                    },
-                                                )
-                                                .unwrap_or(Value::Null),
-                                            };
-                                            if let Err(err) = reader_peer.send(&response).await {
-                                                tracing::warn!(
-                                                    error = %err,
-                                                    "Failed to send exec approval response"
-                                                );
-                                            }
+                                            let decision =
+                                                Self::exec_decision(&tool_policy, &params.command);
+                                            let input = serde_json::json!({
+                                                "command": shell_join(&params.command),
+                                                "description": params.reason,
+                                            });
+                                            Self::handle_approval_request(
+                                                &reader_peer,
+                                                &reader_approvals,
+                                                &tx_for_events,
+                                                request_id,
+                                                CodexApprovalKind::Exec,
+                                                decision,
+                                                prompts_user,
+                                                ("Bash", params.call_id, input),
+                                            )
+                                            .await;
                                         }
                                         _ => {}
                                     }
@@ -808,8 +989,8 @@ impl AgentRunner for CodexCliRunner {
                         model_provider: None,
                         profile: None,
                         cwd: Some(config.working_dir.to_string_lossy().to_string()),
-                        approval_policy: Some(Self::approval_policy()),
-                        sandbox: Some(Self::sandbox_mode()),
+                        approval_policy: Some(approval_policy),
+                        sandbox: Some(Self::sandbox_mode(&config)),
                         config: Self::conversation_config(&config),
                         base_instructions: None,
                         developer_instructions: None,
@@ -830,8 +1011,8 @@ impl AgentRunner for CodexCliRunner {
                     model: config.model.clone(),
                     profile: None,
                     cwd: Some(config.working_dir.to_string_lossy().to_string()),
-                    approval_policy: Some(Self::approval_policy()),
-                    sandbox: Some(Self::sandbox_mode()),
+                    approval_policy: Some(approval_policy),
+                    sandbox: Some(Self::sandbox_mode(&config)),
                     config: Self::conversation_config(&config),
                     base_instructions: None,
                     include_apply_patch_tool: None,
@@ -896,6 +1077,28 @@ impl AgentRunner for CodexCliRunner {
                             "Ignored OpenCode question response sent to Codex input channel"
                         );
                     }
+                    AgentInput::AcpPermission { .. } => {
+                        tracing::warn!(
+                            "Ignored ACP permission response sent to Codex input channel"
+                        );
+                    }
+                    AgentInput::CodexApproval { request_id, choice } => {
+                        let pending = pending_approvals.lock().await.remove(&request_id);
+                        let Some((rpc_id, kind)) = pending else {
+                            tracing::warn!(
+                                request_id = %request_id,
+                                "Codex approval answered for unknown request"
+                            );
+                            continue;
+                        };
+                        Self::send_review_decision(
+                            &input_peer,
+                            rpc_id,
+                            kind,
+                            Self::review_decision(choice),
+                        )
+                        .await;
+                    }
                 }
             }
         });
@@ -923,22 +1126,9 @@ impl AgentRunner for CodexCliRunner {
 
             match status {
                 Ok(exit_status) if !exit_status.success() => {
-                    let error_msg = if stderr_content.is_empty() {
-                        format!("Codex process exited with status: {}", exit_status)
-                    } else {
-                        format!(
-                            "Codex process failed ({}): {}",
-                            exit_status,
-                            stderr_content.trim()
-                        )
-                    };
+                    let exit = ProcessExit::from_status(&exit_status, &stderr_content);
                     if let Err(err) = tx_for_monitor
-                        .send(AgentEvent::Error(ErrorEvent {
-                            message: error_msg,
-                            is_fatal: true,
-                            code: None,
-                            details: None,
-                        }))
+                        .send(AgentEvent::Error(exit.error_event("Codex")))
                         .await
                     {
                         tracing::debug!(
@@ -1073,4 +1263,81 @@ mod tests {
             Some(&serde_json::Value::String("xhigh".to_string()))
         );
     }
+    #[test]
+    fn test_policy_settings_and_decisions() {
+        let policy = ToolPolicy::from_toml(
+            crate::agent::tool_policy::TomlToolPolicy {
+                allow: Vec::new(),
+                deny: vec!["Bash(git push*)".to_string(), "Edit(!src/**)".to_string()],
+                codex: Some(crate::agent::tool_policy::TomlCodexPolicy {
+                    approval_policy: Some(CodexApprovalMode::OnRequest),
+                    sandbox_mode: Some(CodexSandboxMode::ReadOnly),
+                }),
+            },
+            "test",
+        );
+        let config = AgentStartConfig::new("hello", PathBuf::from("/tmp"))
+            .with_tool_policy(policy.clone())
+            .with_codex_permissions(
+                Some(CodexApprovalMode::Never),
+                Some(CodexSandboxMode::DangerFullAccess),
+            );
+        assert_eq!(
+            CodexCliRunner::approval_settings(&config),
+            (AskForApproval::OnRequest, true)
+        );
+        assert_eq!(CodexCliRunner::sandbox_mode(&config), SandboxMode::ReadOnly);
+
+        let command = vec!["git".to_string(), "push".to_string()];
+        assert_eq!(
+            CodexCliRunner::exec_decision(&policy, &command),
+            Some(ReviewDecision::Denied)
+        );
+        let wrapped = ["bash", "-lc", "git push origin main"].map(String::from);
+        assert_eq!(
+            CodexCliRunner::exec_decision(&policy, &wrapped),
+            Some(ReviewDecision::Denied)
+        );
+        assert_eq!(shell_join(&wrapped), "bash -lc 'git push origin main'");
+        let other = vec!["ls".to_string()];
+        assert_eq!(CodexCliRunner::exec_decision(&policy, &other), None);
+        let quoted = ["sh", "-c", "echo 'git push'"].map(String::from);
+        assert_eq!(CodexCliRunner::exec_decision(&policy, &quoted), None);
+        assert_eq!(shell_join(&quoted), "sh -c 'echo '\\''git push'\\'''");
+        let dir = Path::new("/repo");
+        let inside = [PathBuf::from("/repo/src/lib.rs")];
+        let outside = [
+            PathBuf::from("/repo/src/lib.rs"),
+            PathBuf::from("/repo/build.rs"),
+        ];
+        assert_eq!(CodexCliRunner::patch_decision(&policy, &inside, dir), None);
+        assert_eq!(
+            CodexCliRunner::patch_decision(&policy, &outside, dir),
+            Some(ReviewDecision::Denied)
+        );
+    }
+
+    #[test]
+    fn test_session_codex_permissions() {
+        let config = AgentStartConfig::new("hello", PathBuf::from("/tmp")).with_codex_permissions(
+            Some(CodexApprovalMode::Untrusted),
+            Some(CodexSandboxMode::WorkspaceWrite),
+        );
+        assert_eq!(
+            CodexCliRunner::approval_settings(&config),
+            (AskForApproval::UnlessTrusted, true)
+        );
+        assert_eq!(
+            CodexCliRunner::sandbox_mode(&config),
+            SandboxMode::WorkspaceWrite
+        );
+        assert_eq!(
+            CodexCliRunner::review_decision(ToolApprovalChoice::AllowSession),
+            ReviewDecision::ApprovedForSession
+        );
+        assert_eq!(
+            CodexCliRunner::review_decision(ToolApprovalChoice::AllowAlways),
+            ReviewDecision::Denied
+        );
+    }
 }
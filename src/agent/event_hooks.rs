@@ -0,0 +1,495 @@
+//! Agent event hooks
+//!
+//! User scripts run on the unified agent event stream, so they work the same
+//! for every agent. Hooks are configured globally under `[[hooks.event]]` in
+//! `config.toml` and per repository under `[[event]]` in
+//! `.conduit/hooks.toml`. Each run gets the event as JSON on stdin, runs in
+//! the session's working directory, and can have its output sent back to the
+//! agent as a follow-up prompt (e.g. "run `cargo clippy` after each turn and
+//! feed errors back").
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use super::events::AgentEvent;
+use crate::git::HOOKS_FILE;
+
+/// Events hooks can run on
+pub const HOOK_EVENTS: &[&str] = &[
+    "TurnStarted",
+    "TurnCompleted",
+    "TurnFailed",
+    "ToolCompleted",
+    "FileChanged",
+];
+
+/// Error code of web notices carrying hook output meant for the agent
+pub const EVENT_HOOK_FEEDBACK: &str = "event_hook_feedback";
+/// Error code of web notices reporting a failed hook
+pub const EVENT_HOOK_FAILED: &str = "event_hook_failed";
+
+const DEFAULT_TIMEOUT_SECS: u64 = 120;
+const DEFAULT_MAX_FEEDBACK_ROUNDS: u32 = 3;
+/// Output kept per run; longer output is cut from the front
+const MAX_OUTPUT_BYTES: usize = 16 * 1024;
+
+/// When a hook's output is sent back to the agent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookFeedback {
+    #[default]
+    Never,
+    OnFailure,
+    Always,
+}
+
+/// One hook: a command run whenever an event of the given type arrives
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct EventHook {
+    /// Event type name, e.g. `TurnCompleted`
+    pub event: String,
+    pub command: String,
+    #[serde(default)]
+    pub feedback: HookFeedback,
+    pub timeout_secs: Option<u64>,
+}
+
+impl EventHook {
+    pub fn matches(&self, event: &AgentEvent) -> bool {
+        self.event == event.event_type_name()
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS))
+    }
+
+    /// Whether a run's output goes back to the agent
+    pub fn feeds_back(&self, run: &EventHookRun) -> bool {
+        match self.feedback {
+            HookFeedback::Never => false,
+            HookFeedback::OnFailure => !run.success,
+            HookFeedback::Always => true,
+        }
+    }
+
+    fn validate(&self, source: &str) -> Result<(), String> {
+        if !HOOK_EVENTS.contains(&self.event.as_str()) {
+            return Err(format!(
+                "Unknown hook event '{}' in {} (expected one of {})",
+                self.event,
+                source,
+                HOOK_EVENTS.join(", ")
+            ));
+        }
+        if self.command.trim().is_empty() {
+            return Err(format!(
+                "Empty hook command for {} in {}",
+                self.event, source
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The hooks that apply to a session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventHooks {
+    pub hooks: Vec<EventHook>,
+    /// Follow-up prompts hooks may send before the user speaks again
+    pub max_feedback_rounds: u32,
+}
+
+impl Default for EventHooks {
+    fn default() -> Self {
+        Self {
+            hooks: Vec::new(),
+            max_feedback_rounds: DEFAULT_MAX_FEEDBACK_ROUNDS,
+        }
+    }
+}
+
+/// `[hooks]` in config.toml
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlEventHooks {
+    pub max_feedback_rounds: Option<u32>,
+    #[serde(default)]
+    pub event: Vec<EventHook>,
+}
+
+impl EventHooks {
+    /// Build from config, dropping (and logging) invalid hooks
+    pub fn from_toml(toml: TomlEventHooks, source: &str) -> Self {
+        let hooks = toml
+            .event
+            .into_iter()
+            .filter(|hook| match hook.validate(source) {
+                Ok(()) => true,
+                Err(err) => {
+                    tracing::error!("Ignoring event hook: {}", err);
+                    false
+                }
+            })
+            .collect();
+        Self {
+            hooks,
+            max_feedback_rounds: toml
+                .max_feedback_rounds
+                .unwrap_or(DEFAULT_MAX_FEEDBACK_ROUNDS),
+        }
+    }
+
+    /// Read the `[[event]]` hooks in `.conduit/hooks.toml` from `repo_root`;
+    /// `Ok(None)` when there is no hooks file
+    pub fn load(repo_root: &Path) -> Result<Option<Self>, String> {
+        #[derive(Deserialize)]
+        struct HooksFile {
+            #[serde(default)]
+            event: Vec<EventHook>,
+        }
+
+        let path = repo_root.join(HOOKS_FILE);
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(format!("Failed to read {}: {}", path.display(), err)),
+        };
+        let file: HooksFile = toml::from_str(&contents)
+            .map_err(|err| format!("Failed to parse {}: {}", path.display(), err))?;
+        let source = path.display().to_string();
+        for hook in &file.event {
+            hook.validate(&source)?;
+        }
+        Ok(Some(Self {
+            hooks: file.event,
+            ..Self::default()
+        }))
+    }
+
+    /// Add a repository's hooks after these, keeping this feedback limit
+    pub fn merge(mut self, repo: EventHooks) -> Self {
+        self.hooks.extend(repo.hooks);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Hooks to run for an event
+    pub fn matching<'a>(&'a self, event: &'a AgentEvent) -> impl Iterator<Item = &'a EventHook> {
+        self.hooks.iter().filter(move |hook| hook.matches(event))
+    }
+}
+
+/// Where a hook runs
+#[derive(Debug, Clone)]
+pub struct EventHookContext {
+    pub working_dir: PathBuf,
+    pub session_id: String,
+}
+
+/// Outcome of one hook run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventHookRun {
+    pub success: bool,
+    /// Combined stdout and stderr, trimmed
+    pub output: String,
+    /// Why the run failed, e.g. "exit status 1"
+    pub failure: Option<String>,
+}
+
+impl EventHookRun {
+    fn failed(failure: String, output: String) -> Self {
+        Self {
+            success: false,
+            output,
+            failure: Some(failure),
+        }
+    }
+
+    /// Follow-up prompt telling the agent what the hook reported
+    pub fn feedback_prompt(&self, hook: &EventHook) -> String {
+        let heading = match &self.failure {
+            Some(failure) => format!(
+                "The `{}` hook failed after {} ({}).",
+                hook.command, hook.event, failure
+            ),
+            None => format!(
+                "Output of the `{}` hook after {}:",
+                hook.command, hook.event
+            ),
+        };
+        if self.output.is_empty() {
+            heading
+        } else {
+            format!("{}\n\n```\n{}\n```", heading, self.output)
+        }
+    }
+}
+
+/// Run a hook with the event's JSON on stdin, waiting up to its timeout
+pub fn run_event_hook(hook: &EventHook, event_json: &str, ctx: &EventHookContext) -> EventHookRun {
+    let mut command = shell_command(&hook.command, &ctx.working_dir);
+    command
+        .env("CONDUIT_EVENT", &hook.event)
+        .env("CONDUIT_SESSION_ID", &ctx.session_id)
+        .env("CONDUIT_WORKSPACE_PATH", &ctx.working_dir);
+    run_shell(command, Some(event_json.to_string()), hook.timeout())
+}
+
+/// Run a shell command in a directory, waiting up to `timeout`. Used for
+/// checks such as a goal loop's verification command.
+pub fn run_command(command: &str, working_dir: &Path, timeout: Duration) -> EventHookRun {
+    run_shell(shell_command(command, working_dir), None, timeout)
+}
+
+fn shell_command(command: &str, working_dir: &Path) -> Command {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut cmd = Command::new(shell);
+    cmd.arg(flag).arg(command).current_dir(working_dir);
+    // Its own process group, so a timeout can stop everything it started
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    cmd
+}
+
+/// Kill a shell command and the processes it started
+fn kill_process_group(child: &mut Child) {
+    #[cfg(unix)]
+    unsafe {
+        libc::killpg(child.id() as libc::pid_t, libc::SIGKILL);
+    }
+    let _ = child.kill();
+}
+
+fn run_shell(mut command: Command, stdin: Option<String>, timeout: Duration) -> EventHookRun {
+    let stdin_mode = if stdin.is_some() {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    };
+    let mut child = match command
+        .stdin(stdin_mode)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(err) => {
+            return EventHookRun::failed(format!("failed to start: {}", err), String::new())
+        }
+    };
+
+    // Feed stdin and drain output on threads so a chatty script can't block
+    if let (Some(mut pipe), Some(payload)) = (child.stdin.take(), stdin) {
+        std::thread::spawn(move || {
+            // Scripts that ignore stdin close it early; that's fine
+            let _ = pipe.write_all(payload.as_bytes());
+        });
+    }
+    let readers: Vec<_> = [
+        child
+            .stdout
+            .take()
+            .map(|out| Box::new(out) as Box<dyn Read + Send>),
+        child
+            .stderr
+            .take()
+            .map(|err| Box::new(err) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|mut stream| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = stream.read_to_end(&mut buf);
+            buf
+        })
+    })
+    .collect();
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                kill_process_group(&mut child);
+                let _ = child.wait();
+                // Processes that left the group may still hold the pipes
+                // open, so don't wait on the readers
+                return EventHookRun::failed(
+                    format!("timed out after {}s", timeout.as_secs()),
+                    String::new(),
+                );
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(err) => return EventHookRun::failed(err.to_string(), String::new()),
+        }
+    };
+
+    let mut output = Vec::new();
+    for reader in readers {
+        if let Ok(buf) = reader.join() {
+            output.extend(buf);
+        }
+    }
+    let output = truncate_output(String::from_utf8_lossy(&output).trim());
+    if status.success() {
+        EventHookRun {
+            success: true,
+            output,
+            failure: None,
+        }
+    } else {
+        let failure = match status.code() {
+            Some(code) => format!("exit status {}", code),
+            None => "terminated".to_string(),
+        };
+        EventHookRun::failed(failure, output)
+    }
+}
+
+/// Keep the end of long output, where errors usually are
+fn truncate_output(output: &str) -> String {
+    if output.len() <= MAX_OUTPUT_BYTES {
+        return output.to_string();
+    }
+    let mut start = output.len() - MAX_OUTPUT_BYTES;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("[… output truncated]\n{}", &output[start..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::events::{TokenUsage, TurnCompletedEvent};
+    use tempfile::TempDir;
+
+    fn hook(event: &str, command: &str, feedback: HookFeedback) -> EventHook {
+        EventHook {
+            event: event.to_string(),
+            command: command.to_string(),
+            feedback,
+            timeout_secs: None,
+        }
+    }
+
+    #[test]
+    fn test_load_repo_hooks_and_merge() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(EventHooks::load(dir.path()), Ok(None));
+
+        std::fs::create_dir_all(dir.path().join(".conduit")).unwrap();
+        std::fs::write(
+            dir.path().join(HOOKS_FILE),
+            "setup = \"npm ci\"\n\n[[event]]\nevent = \"TurnCompleted\"\ncommand = \"cargo clippy\"\nfeedback = \"on_failure\"\n",
+        )
+        .unwrap();
+        let repo = EventHooks::load(dir.path()).unwrap().unwrap();
+        assert_eq!(
+            repo.hooks,
+            vec![hook(
+                "TurnCompleted",
+                "cargo clippy",
+                HookFeedback::OnFailure
+            )]
+        );
+
+        let global = EventHooks {
+            hooks: vec![hook("FileChanged", "true", HookFeedback::Never)],
+            max_feedback_rounds: 1,
+        };
+        let merged = global.merge(repo);
+        assert_eq!(merged.hooks.len(), 2);
+        assert_eq!(merged.max_feedback_rounds, 1);
+        let event = AgentEvent::TurnCompleted(TurnCompletedEvent {
+            usage: TokenUsage::default(),
+        });
+        let matching: Vec<_> = merged.matching(&event).collect();
+        assert_eq!(matching.len(), 1);
+        assert_eq!(matching[0].command, "cargo clippy");
+
+        std::fs::write(
+            dir.path().join(HOOKS_FILE),
+            "[[event]]\nevent = \"TurnDone\"\ncommand = \"true\"\n",
+        )
+        .unwrap();
+        assert!(EventHooks::load(dir.path()).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_event_hook_reads_event_and_reports_failure() {
+        let dir = TempDir::new().unwrap();
+        let ctx = EventHookContext {
+            working_dir: dir.path().to_path_buf(),
+            session_id: "session-1".to_string(),
+        };
+        let echo = hook(
+            "TurnCompleted",
+            "cat; echo \" $CONDUIT_EVENT\"",
+            HookFeedback::Always,
+        );
+        let run = run_event_hook(&echo, "{\"type\":\"TurnCompleted\"}", &ctx);
+        assert!(run.success);
+        assert_eq!(run.output, "{\"type\":\"TurnCompleted\"} TurnCompleted");
+        assert!(echo.feeds_back(&run));
+
+        let failing = hook(
+            "TurnCompleted",
+            "echo 'error: unused variable' >&2; exit 1",
+            HookFeedback::OnFailure,
+        );
+        let run = run_event_hook(&failing, "{}", &ctx);
+        assert!(!run.success);
+        assert!(failing.feeds_back(&run));
+        let prompt = run.feedback_prompt(&failing);
+        assert!(prompt.contains("exit status 1"));
+        assert!(prompt.contains("error: unused variable"));
+
+        let slow = EventHook {
+            timeout_secs: Some(0),
+            ..hook("TurnCompleted", "sleep 5", HookFeedback::Never)
+        };
+        let run = run_event_hook(&slow, "{}", &ctx);
+        assert_eq!(run.failure.as_deref(), Some("timed out after 0s"));
+        assert!(!slow.feeds_back(&run));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_timeout_kills_processes_the_command_started() {
+        let dir = TempDir::new().unwrap();
+        let run = run_command(
+            "sleep 30 & echo $! > child.pid; wait",
+            dir.path(),
+            Duration::from_secs(1),
+        );
+        assert_eq!(run.failure.as_deref(), Some("timed out after 1s"));
+
+        let pid: libc::pid_t = std::fs::read_to_string(dir.path().join("child.pid"))
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+        // Reaped by init once killed; give it a moment
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while unsafe { libc::kill(pid, 0) } == 0 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        assert_ne!(unsafe { libc::kill(pid, 0) }, 0);
+    }
+}
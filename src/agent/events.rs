@@ -155,6 +155,22 @@ pub struct TokenUsage {
     pub total_tokens: i64,
 }
 
+impl TokenUsage {
+    /// Usage accrued since `earlier`, for agents that only report running totals.
+    pub fn since(&self, earlier: &TokenUsage) -> TokenUsage {
+        TokenUsage {
+            input_tokens: (self.input_tokens - earlier.input_tokens).max(0),
+            output_tokens: (self.output_tokens - earlier.output_tokens).max(0),
+            cached_tokens: (self.cached_tokens - earlier.cached_tokens).max(0),
+            total_tokens: (self.total_tokens - earlier.total_tokens).max(0),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.input_tokens == 0 && self.output_tokens == 0 && self.total_tokens == 0
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenUsageEvent {
     pub usage: TokenUsage,
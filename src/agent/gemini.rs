@@ -1,34 +1,20 @@
-use std::cell::{Cell, RefCell};
-use std::collections::HashMap;
 use std::env;
 use std::path::PathBuf;
 use std::process::Stdio;
-use std::rc::Rc;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use agent_client_protocol as acp;
-use agent_client_protocol::Agent as _;
 use async_trait::async_trait;
-use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
-use tokio::sync::mpsc;
-use tokio_util::compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
-use tokio_util::io::ReaderStream;
 
+use crate::agent::acp::{send_acp_input, start_acp_agent, AcpLaunch};
 use crate::agent::error::AgentError;
-use crate::agent::events::{
-    AgentEvent, AssistantMessageEvent, ErrorEvent, ReasoningEvent, SessionInitEvent,
-    ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent,
+use crate::agent::runner::{
+    AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType, ApprovalPolicy,
 };
-use crate::agent::runner::{AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType};
-use crate::agent::session::SessionId;
 
 const CACHE_TTL_SECS: u64 = 60 * 60 * 24 * 7;
-const INIT_TIMEOUT: Duration = Duration::from_secs(10);
-const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
-const SESSION_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub struct GeminiCliRunner {
     binary_path: Option<PathBuf>,
@@ -46,212 +32,6 @@ struct ModelCache {
     models: Vec<String>,
 }
 
-struct GeminiAcpClient {
-    event_tx: mpsc::Sender<AgentEvent>,
-    saw_message: Rc<Cell<bool>>,
-    tool_titles: RefCell<HashMap<String, String>>,
-}
-
-impl GeminiAcpClient {
-    fn new(event_tx: mpsc::Sender<AgentEvent>, saw_message: Rc<Cell<bool>>) -> Self {
-        Self {
-            event_tx,
-            saw_message,
-            tool_titles: RefCell::new(HashMap::new()),
-        }
-    }
-
-    async fn send_event(&self, event: AgentEvent) {
-        if self.event_tx.send(event).await.is_err() {
-            tracing::debug!("Failed to send Gemini agent event");
-        }
-    }
-
-    fn store_tool_title(&self, tool_id: &str, title: String) {
-        self.tool_titles
-            .borrow_mut()
-            .insert(tool_id.to_string(), title);
-    }
-
-    fn content_to_string(content: &[acp::ToolCallContent]) -> Option<String> {
-        let mut parts = Vec::new();
-        for item in content {
-            match item {
-                acp::ToolCallContent::Content(content) => {
-                    if let acp::ContentBlock::Text(text) = &content.content {
-                        if !text.text.is_empty() {
-                            parts.push(text.text.clone());
-                        }
-                    }
-                }
-                acp::ToolCallContent::Diff(diff) => {
-                    parts.push(format!("Diff applied: {}", diff.path.display()));
-                }
-                acp::ToolCallContent::Terminal(terminal) => {
-                    parts.push(format!(
-                        "Terminal output available (id: {})",
-                        terminal.terminal_id.0
-                    ));
-                }
-                _ => {}
-            }
-        }
-        if parts.is_empty() {
-            None
-        } else {
-            Some(parts.join("\n"))
-        }
-    }
-}
-
-#[async_trait(?Send)]
-impl acp::Client for GeminiAcpClient {
-    async fn request_permission(
-        &self,
-        args: acp::RequestPermissionRequest,
-    ) -> Result<acp::RequestPermissionResponse, acp::Error> {
-        let chosen = args
-            .options
-            .iter()
-            .find(|o| matches!(o.kind, acp::PermissionOptionKind::AllowAlways))
-            .or_else(|| {
-                args.options
-                    .iter()
-                    .find(|o| matches!(o.kind, acp::PermissionOptionKind::AllowOnce))
-            })
-            .or_else(|| args.options.first());
-
-        let outcome = if let Some(option) = chosen {
-            acp::RequestPermissionOutcome::Selected(acp::SelectedPermissionOutcome::new(
-                option.option_id.clone(),
-            ))
-        } else {
-            acp::RequestPermissionOutcome::Cancelled
-        };
-
-        Ok(acp::RequestPermissionResponse::new(outcome))
-    }
-
-    async fn session_notification(&self, args: acp::SessionNotification) -> Result<(), acp::Error> {
-        match args.update {
-            acp::SessionUpdate::AgentMessageChunk(chunk) => {
-                if let acp::ContentBlock::Text(text) = chunk.content {
-                    self.saw_message.set(true);
-                    self.send_event(AgentEvent::AssistantMessage(AssistantMessageEvent {
-                        text: text.text,
-                        is_final: false,
-                    }))
-                    .await;
-                }
-            }
-            acp::SessionUpdate::AgentThoughtChunk(chunk) => {
-                if let acp::ContentBlock::Text(text) = chunk.content {
-                    self.send_event(AgentEvent::AssistantReasoning(ReasoningEvent {
-                        text: text.text,
-                    }))
-                    .await;
-                }
-            }
-            acp::SessionUpdate::ToolCall(tool_call) => {
-                let tool_id = tool_call.tool_call_id.0.to_string();
-                let title = if tool_call.title.is_empty() {
-                    "tool".to_string()
-                } else {
-                    tool_call.title.clone()
-                };
-                self.store_tool_title(&tool_id, title.clone());
-                let arguments = tool_call.raw_input.unwrap_or(serde_json::Value::Null);
-                self.send_event(AgentEvent::ToolStarted(ToolStartedEvent {
-                    tool_name: title.clone(),
-                    tool_id,
-                    arguments,
-                }))
-                .await;
-            }
-            acp::SessionUpdate::ToolCallUpdate(update) => {
-                let tool_id = update.tool_call_id.0.to_string();
-                if let Some(title) = update.fields.title.clone() {
-                    self.store_tool_title(&tool_id, title);
-                }
-                if let Some(acp::ToolCallStatus::Completed) = update.fields.status {
-                    let result = update
-                        .fields
-                        .content
-                        .as_ref()
-                        .and_then(|content| Self::content_to_string(content))
-                        .or_else(|| update.fields.raw_output.as_ref().map(|v| v.to_string()));
-                    self.send_event(AgentEvent::ToolCompleted(ToolCompletedEvent {
-                        tool_id,
-                        success: true,
-                        result,
-                        error: None,
-                    }))
-                    .await;
-                }
-            }
-            _ => {}
-        }
-        Ok(())
-    }
-
-    async fn write_text_file(
-        &self,
-        _: acp::WriteTextFileRequest,
-    ) -> Result<acp::WriteTextFileResponse, acp::Error> {
-        Err(acp::Error::method_not_found())
-    }
-
-    async fn read_text_file(
-        &self,
-        _: acp::ReadTextFileRequest,
-    ) -> Result<acp::ReadTextFileResponse, acp::Error> {
-        Err(acp::Error::method_not_found())
-    }
-
-    async fn create_terminal(
-        &self,
-        _: acp::CreateTerminalRequest,
-    ) -> Result<acp::CreateTerminalResponse, acp::Error> {
-        Err(acp::Error::method_not_found())
-    }
-
-    async fn terminal_output(
-        &self,
-        _: acp::TerminalOutputRequest,
-    ) -> Result<acp::TerminalOutputResponse, acp::Error> {
-        Err(acp::Error::method_not_found())
-    }
-
-    async fn release_terminal(
-        &self,
-        _: acp::ReleaseTerminalRequest,
-    ) -> Result<acp::ReleaseTerminalResponse, acp::Error> {
-        Err(acp::Error::method_not_found())
-    }
-
-    async fn wait_for_terminal_exit(
-        &self,
-        _: acp::WaitForTerminalExitRequest,
-    ) -> Result<acp::WaitForTerminalExitResponse, acp::Error> {
-        Err(acp::Error::method_not_found())
-    }
-
-    async fn kill_terminal_command(
-        &self,
-        _: acp::KillTerminalCommandRequest,
-    ) -> Result<acp::KillTerminalCommandResponse, acp::Error> {
-        Err(acp::Error::method_not_found())
-    }
-
-    async fn ext_method(&self, _: acp::ExtRequest) -> Result<acp::ExtResponse, acp::Error> {
-        Err(acp::Error::method_not_found())
-    }
-
-    async fn ext_notification(&self, _: acp::ExtNotification) -> Result<(), acp::Error> {
-        Ok(())
-    }
-}
-
 impl GeminiCliRunner {
     pub fn new() -> Self {
         Self { binary_path: None }
@@ -302,7 +82,13 @@ impl GeminiCliRunner {
         let mut cmd = Command::new(&resolved.bin);
         cmd.args(&resolved.base_args);
         cmd.arg("--experimental-acp");
-        cmd.arg("--yolo");
+        // Without --yolo the CLI routes tool calls through request_permission,
+        // which is where the approval policy and deny rules are applied.
+        if config.approval_policy == ApprovalPolicy::AllowAll
+            && !config.tool_policy.has_deny_rules()
+        {
+            cmd.arg("--yolo");
+        }
 
         if let Some(model) = &config.model {
             cmd.arg("--model").arg(model);
@@ -474,315 +260,17 @@ impl AgentRunner for GeminiCliRunner {
         })
         .await;
 
-        let mut cmd = self.build_command(&config, &resolved);
-        let mut child = cmd.spawn()?;
-
-        let pid = child.id().ok_or(AgentError::ProcessSpawnFailed)?;
-        let stdout = child.stdout.take().ok_or(AgentError::StdoutCaptureFailed)?;
-        let stderr = child.stderr.take();
-        let child_stdin = child.stdin.take().ok_or_else(|| {
-            AgentError::Config("Failed to capture stdin for Gemini CLI".to_string())
-        })?;
-
-        let (tx, rx) = mpsc::channel::<AgentEvent>(256);
-        let tx_for_monitor = tx.clone();
-
-        let (mut to_acp_writer, acp_incoming_reader) = tokio::io::duplex(64 * 1024);
-        let (acp_out_writer, acp_out_reader) = tokio::io::duplex(64 * 1024);
-
-        tokio::spawn(async move {
-            let mut stdout_stream = ReaderStream::new(stdout);
-            while let Some(res) = stdout_stream.next().await {
-                match res {
-                    Ok(data) => {
-                        if to_acp_writer.write_all(&data).await.is_err() {
-                            break;
-                        }
-                    }
-                    Err(_) => break,
-                }
-            }
-        });
-
-        tokio::spawn(async move {
-            let mut child_stdin = child_stdin;
-            let mut reader = ReaderStream::new(acp_out_reader);
-            while let Some(res) = reader.next().await {
-                match res {
-                    Ok(data) => {
-                        if child_stdin.write_all(&data).await.is_err() {
-                            break;
-                        }
-                        let _ = child_stdin.flush().await;
-                    }
-                    Err(_) => break,
-                }
-            }
-        });
-
-        let outgoing = acp_out_writer.compat_write();
-        let incoming = acp_incoming_reader.compat();
-        let prompt = config.prompt.clone();
-        let working_dir = config.working_dir.clone();
-        let tx_for_session = tx.clone();
-
-        tokio::task::spawn_blocking(move || {
-            let rt = tokio::runtime::Builder::new_current_thread()
-                .enable_all()
-                .build()
-                .expect("Failed to build Gemini ACP runtime");
-            rt.block_on(async move {
-                let local = tokio::task::LocalSet::new();
-                local
-                    .run_until(async move {
-                        let saw_message = Rc::new(Cell::new(false));
-                        let client =
-                            GeminiAcpClient::new(tx_for_session.clone(), saw_message.clone());
-                        let (conn, io_fut) =
-                            acp::ClientSideConnection::new(client, outgoing, incoming, |fut| {
-                                tokio::task::spawn_local(fut);
-                            });
-                        let conn = Rc::new(conn);
-
-                        tokio::task::spawn_local(async move {
-                            let _ = io_fut.await;
-                        });
-
-                        let init_response = match tokio::time::timeout(
-                            INIT_TIMEOUT,
-                            conn.initialize(acp::InitializeRequest::new(
-                                acp::ProtocolVersion::V1,
-                            )),
-                        )
-                        .await
-                        {
-                            Ok(Ok(response)) => response,
-                            Ok(Err(err)) => {
-                                let _ = tx_for_session
-                                    .send(AgentEvent::Error(ErrorEvent {
-                                        message: format!(
-                                            "Failed to initialize Gemini ACP: {}",
-                                            err
-                                        ),
-                                        is_fatal: true,
-                                        code: None,
-                                        details: None,
-                                    }))
-                                    .await;
-                                return;
-                            }
-                            Err(_) => {
-                                let _ = tx_for_session
-                                    .send(AgentEvent::Error(ErrorEvent {
-                                        message: "Timed out initializing Gemini ACP. Ensure gemini CLI is installed and authenticated.".to_string(),
-                                        is_fatal: true,
-                                        code: None,
-                                        details: None,
-                                    }))
-                                    .await;
-                                return;
-                            }
-                        };
-
-                        if !init_response.auth_methods.is_empty() {
-                            if let Some(method_id) =
-                                Self::select_auth_method(&init_response.auth_methods)
-                            {
-                                match tokio::time::timeout(
-                                    AUTH_TIMEOUT,
-                                    conn.authenticate(acp::AuthenticateRequest::new(
-                                        method_id.clone(),
-                                    )),
-                                )
-                                .await
-                                {
-                                    Ok(Ok(_)) => {}
-                                    Ok(Err(err)) => {
-                                        let _ = tx_for_session
-                                            .send(AgentEvent::Error(ErrorEvent {
-                                                message: format!(
-                                                    "Gemini CLI authentication failed ({}): {}",
-                                                    method_id, err
-                                                ),
-                                                is_fatal: true,
-                                                code: None,
-                                                details: None,
-                                            }))
-                                            .await;
-                                        return;
-                                    }
-                                    Err(_) => {
-                                        let _ = tx_for_session
-                                            .send(AgentEvent::Error(ErrorEvent {
-                                                message: "Gemini CLI authentication timed out. Run `gemini` once to log in or set GEMINI_API_KEY.".to_string(),
-                                                is_fatal: true,
-                                                code: None,
-                                                details: None,
-                                            }))
-                                            .await;
-                                        return;
-                                    }
-                                }
-                            }
-                        }
-
-                        let session_id = match tokio::time::timeout(
-                            SESSION_TIMEOUT,
-                            conn.new_session(acp::NewSessionRequest::new(working_dir)),
-                        )
-                        .await
-                        {
-                            Ok(Ok(response)) => response.session_id,
-                            Ok(Err(err)) => {
-                                let _ = tx_for_session
-                                    .send(AgentEvent::Error(ErrorEvent {
-                                        message: format!(
-                                            "Failed to create Gemini session: {}",
-                                            err
-                                        ),
-                                        is_fatal: true,
-                                        code: None,
-                                        details: None,
-                                    }))
-                                    .await;
-                                return;
-                            }
-                            Err(_) => {
-                                let _ = tx_for_session
-                                    .send(AgentEvent::Error(ErrorEvent {
-                                        message: "Timed out creating Gemini session. Ensure gemini CLI is authenticated.".to_string(),
-                                        is_fatal: true,
-                                        code: None,
-                                        details: None,
-                                    }))
-                                    .await;
-                                return;
-                            }
-                        };
-
-                        let _ = tx_for_session
-                            .send(AgentEvent::SessionInit(SessionInitEvent {
-                                session_id: SessionId::from_string(session_id.0.to_string()),
-                                model: None,
-                            }))
-                            .await;
-
-                        let _ = tx_for_session.send(AgentEvent::TurnStarted).await;
-
-                        if !prompt.is_empty() {
-                            let req = acp::PromptRequest::new(
-                                session_id.clone(),
-                                vec![acp::ContentBlock::Text(acp::TextContent::new(prompt))],
-                            );
-
-                            let prompt_result = conn.prompt(req).await;
-
-                            if let Err(err) = prompt_result {
-                                let _ = tx_for_session
-                                    .send(AgentEvent::Error(ErrorEvent {
-                                        message: format!("Gemini prompt failed: {}", err),
-                                        is_fatal: true,
-                                        code: None,
-                                        details: None,
-                                    }))
-                                    .await;
-                                return;
-                            }
-                        }
-
-                        if saw_message.get() {
-                            let _ = tx_for_session
-                                .send(AgentEvent::AssistantMessage(AssistantMessageEvent {
-                                    text: String::new(),
-                                    is_final: true,
-                                }))
-                                .await;
-                        }
-
-                        let _ = tx_for_session
-                            .send(AgentEvent::TurnCompleted(TurnCompletedEvent {
-                                usage: Default::default(),
-                            }))
-                            .await;
-
-                        let _ = conn.cancel(acp::CancelNotification::new(session_id)).await;
-                    })
-                    .await;
-            });
-        });
-
-        tokio::spawn(async move {
-            use tokio::io::AsyncReadExt;
-
-            let status = child.wait().await;
-            let stderr_content = if let Some(mut stderr) = stderr {
-                let mut buf = String::new();
-                if let Err(err) = stderr.read_to_string(&mut buf).await {
-                    tracing::debug!(error = %err, "Failed to read Gemini stderr");
-                }
-                buf
-            } else {
-                String::new()
-            };
-
-            match status {
-                Ok(exit_status) if !exit_status.success() => {
-                    let message = if stderr_content.is_empty() {
-                        format!("Gemini process exited with status: {}", exit_status)
-                    } else {
-                        format!(
-                            "Gemini process failed ({}): {}",
-                            exit_status,
-                            stderr_content.trim()
-                        )
-                    };
-                    if let Err(send_err) = tx_for_monitor
-                        .send(AgentEvent::Error(ErrorEvent {
-                            message,
-                            is_fatal: true,
-                            code: None,
-                            details: None,
-                        }))
-                        .await
-                    {
-                        tracing::debug!(
-                            error = ?send_err,
-                            "Failed to send Gemini process failure"
-                        );
-                    }
-                }
-                Err(err) => {
-                    let message = format!("Failed to wait for Gemini process: {}", err);
-                    if let Err(send_err) = tx_for_monitor
-                        .send(AgentEvent::Error(ErrorEvent {
-                            message,
-                            is_fatal: true,
-                            code: None,
-                            details: None,
-                        }))
-                        .await
-                    {
-                        tracing::debug!(
-                            error = ?send_err,
-                            "Failed to send Gemini wait error"
-                        );
-                    }
-                }
-                Ok(_) => {}
-            }
-        });
-
-        Ok(AgentHandle::new(rx, pid, None))
+        let cmd = self.build_command(&config, &resolved);
+        let launch = AcpLaunch {
+            label: "Gemini CLI".to_string(),
+            select_auth: Box::new(Self::select_auth_method),
+            auth_hint: "Run `gemini` once to log in or set GEMINI_API_KEY.".to_string(),
+        };
+        start_acp_agent(cmd, config, launch).await
     }
 
-    async fn send_input(
-        &self,
-        _handle: &AgentHandle,
-        _input: AgentInput,
-    ) -> Result<(), AgentError> {
-        Err(AgentError::NotSupported(
-            "Gemini CLI runner does not support interactive input".into(),
-        ))
+    async fn send_input(&self, handle: &AgentHandle, input: AgentInput) -> Result<(), AgentError> {
+        send_acp_input(handle, input, "Gemini CLI runner").await
     }
 
     async fn stop(&self, handle: &AgentHandle) -> Result<(), AgentError> {
@@ -837,3 +325,59 @@ impl AgentRunner for GeminiCliRunner {
         Self::find_binary().or_else(Self::find_npx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command_args(cmd: &Command) -> Vec<String> {
+        cmd.as_std()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_build_command_only_passes_yolo_for_allow_all() {
+        let runner = GeminiCliRunner::with_path(PathBuf::from("/usr/bin/gemini"));
+        let resolved = ResolvedCommand {
+            bin: PathBuf::from("/usr/bin/gemini"),
+            base_args: Vec::new(),
+        };
+
+        let ask = AgentStartConfig::new("hi", PathBuf::from("/tmp"));
+        let args = command_args(&runner.build_command(&ask, &resolved));
+        assert!(args.contains(&"--experimental-acp".to_string()));
+        assert!(!args.contains(&"--yolo".to_string()));
+
+        let allow_all = AgentStartConfig::new("hi", PathBuf::from("/tmp"))
+            .with_approval_policy(ApprovalPolicy::AllowAll);
+        let args = command_args(&runner.build_command(&allow_all, &resolved));
+        assert!(args.contains(&"--yolo".to_string()));
+
+        // Deny rules are only applied when the CLI asks for permission
+        let policy = crate::agent::ToolPolicy::from_toml(
+            crate::agent::tool_policy::TomlToolPolicy {
+                allow: Vec::new(),
+                deny: vec!["Bash(git push*)".to_string()],
+                codex: None,
+            },
+            "test",
+        );
+        let args =
+            command_args(&runner.build_command(&allow_all.with_tool_policy(policy), &resolved));
+        assert!(!args.contains(&"--yolo".to_string()));
+    }
+
+    #[test]
+    fn test_approval_policy_round_trip() {
+        for policy in [
+            ApprovalPolicy::Ask,
+            ApprovalPolicy::AllowReads,
+            ApprovalPolicy::AllowAll,
+        ] {
+            assert_eq!(policy.as_str().parse::<ApprovalPolicy>(), Ok(policy));
+        }
+        assert!("sometimes".parse::<ApprovalPolicy>().is_err());
+    }
+}
@@ -1,21 +1,30 @@
+pub mod acp;
+pub mod acp_workspace;
 pub mod claude;
 pub mod codex;
 pub mod display;
 pub mod error;
+pub mod event_hooks;
 pub mod events;
 pub mod gemini;
 pub mod history;
 pub mod mock;
 pub mod models;
 pub mod opencode;
+pub mod provider_error;
 pub mod runner;
 pub mod session;
 pub mod stream;
+pub mod supervisor;
+pub mod tool_approval;
+pub mod tool_policy;
 
+pub use acp::{AcpAgentConfig, AcpAgentRunner};
 pub use claude::ClaudeCodeRunner;
 pub use codex::CodexCliRunner;
 pub use display::MessageDisplay;
 pub use error::AgentError;
+pub use event_hooks::{EventHook, EventHooks};
 pub use events::*;
 pub use gemini::GeminiCliRunner;
 pub use history::{
@@ -24,9 +33,14 @@ pub use history::{
     HistoryError,
 };
 pub use mock::{MockAgentRunner, MockConfig, MockEventBuilder, MockStartError};
-pub use models::{ModelInfo, ModelRegistry};
+pub use models::{ModelInfo, ModelPricing, ModelRegistry};
 pub use opencode::OpencodeRunner;
+pub use provider_error::ProviderErrorKind;
 pub use runner::{
-    AgentHandle, AgentInput, AgentMode, AgentRunner, AgentStartConfig, AgentType, ReasoningEffort,
+    AgentHandle, AgentInput, AgentMode, AgentRunner, AgentStartConfig, AgentType, ApprovalPolicy,
+    CodexApprovalMode, CodexSandboxMode, ReasoningEffort,
 };
 pub use session::{SessionId, SessionMetadata, SessionStatus};
+pub use supervisor::{ExitKind, ProcessExit, RestartPolicy};
+pub use tool_approval::ToolApprovalChoice;
+pub use tool_policy::{PolicyDecision, ToolPolicy};
@@ -4,9 +4,52 @@ use std::sync::{OnceLock, RwLock};
 
 use tracing::error;
 
+use crate::agent::events::TokenUsage;
 use crate::agent::opencode::load_opencode_models;
 use crate::agent::AgentType;
 
+/// List prices for a model, in US dollars per million tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+    /// Price for cache reads; billed at the input rate when absent
+    pub cached_input_per_million: Option<f64>,
+}
+
+impl ModelPricing {
+    pub const fn new(input_per_million: f64, output_per_million: f64) -> Self {
+        Self {
+            input_per_million,
+            output_per_million,
+            cached_input_per_million: None,
+        }
+    }
+
+    pub const fn with_cached_input(self, cached_input_per_million: f64) -> Self {
+        Self {
+            cached_input_per_million: Some(cached_input_per_million),
+            ..self
+        }
+    }
+
+    /// Estimate the cost of `usage` in US dollars.
+    ///
+    /// Cached tokens are treated as a subset of the input tokens.
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        let input = usage.input_tokens.max(0) as f64;
+        let cached = (usage.cached_tokens.max(0) as f64).min(input);
+        let output = usage.output_tokens.max(0) as f64;
+        let cached_rate = self
+            .cached_input_per_million
+            .unwrap_or(self.input_per_million);
+        ((input - cached) * self.input_per_million
+            + cached * cached_rate
+            + output * self.output_per_million)
+            / 1_000_000.0
+    }
+}
+
 /// Information about a model
 #[derive(Debug, Clone)]
 pub struct ModelInfo {
@@ -24,6 +67,8 @@ pub struct ModelInfo {
     pub agent_type: AgentType,
     /// Maximum context window in tokens
     pub context_window: i64,
+    /// List prices, when known
+    pub pricing: Option<ModelPricing>,
 }
 
 impl ModelInfo {
@@ -43,6 +88,7 @@ impl ModelInfo {
             is_default: false,
             agent_type,
             context_window,
+            pricing: None,
         }
     }
 
@@ -50,6 +96,11 @@ impl ModelInfo {
         self.is_default = true;
         self
     }
+
+    pub fn with_pricing(mut self, pricing: ModelPricing) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
 }
 
 /// Registry of available models for each agent type
@@ -77,6 +128,27 @@ impl ModelRegistry {
 
     const OPENCODE_DEFAULT_MODEL_ID: &'static str = "default";
 
+    /// Default context window for the configured ACP agent (approximate)
+    pub const ACP_CONTEXT_WINDOW: i64 = 200_000;
+
+    const ACP_DEFAULT_MODEL_ID: &'static str = "default";
+
+    const CLAUDE_OPUS_PRICING: ModelPricing = ModelPricing::new(5.0, 25.0).with_cached_input(0.5);
+    const CLAUDE_SONNET_PRICING: ModelPricing = ModelPricing::new(3.0, 15.0).with_cached_input(0.3);
+    const CLAUDE_HAIKU_PRICING: ModelPricing = ModelPricing::new(1.0, 5.0).with_cached_input(0.1);
+    const GPT52_PRICING: ModelPricing = ModelPricing::new(1.75, 14.0).with_cached_input(0.175);
+    const GPT51_PRICING: ModelPricing = ModelPricing::new(1.25, 10.0).with_cached_input(0.125);
+    const GPT51_MINI_PRICING: ModelPricing = ModelPricing::new(0.25, 2.0).with_cached_input(0.025);
+    const GEMINI_25_PRO_PRICING: ModelPricing =
+        ModelPricing::new(1.25, 10.0).with_cached_input(0.125);
+    const GEMINI_25_FLASH_PRICING: ModelPricing =
+        ModelPricing::new(0.3, 2.5).with_cached_input(0.03);
+    const GEMINI_25_FLASH_LITE_PRICING: ModelPricing =
+        ModelPricing::new(0.1, 0.4).with_cached_input(0.01);
+    const GEMINI_3_PRO_PRICING: ModelPricing = ModelPricing::new(2.0, 12.0).with_cached_input(0.2);
+    const GEMINI_3_FLASH_PRICING: ModelPricing =
+        ModelPricing::new(0.5, 3.0).with_cached_input(0.05);
+
     fn opencode_store() -> &'static RwLock<Vec<ModelInfo>> {
         static OPENCODE_MODELS: OnceLock<RwLock<Vec<ModelInfo>>> = OnceLock::new();
         OPENCODE_MODELS.get_or_init(|| RwLock::new(Vec::new()))
@@ -187,6 +259,7 @@ impl ModelRegistry {
                 "Most capable for complex work",
                 Self::CLAUDE_CONTEXT_WINDOW,
             )
+            .with_pricing(Self::CLAUDE_OPUS_PRICING)
             .as_default(),
             ModelInfo::new(
                 AgentType::Claude,
@@ -195,7 +268,8 @@ impl ModelRegistry {
                 "opus[1m]",
                 "Opus 4.6 with 1M context",
                 Self::CLAUDE_1M_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(Self::CLAUDE_OPUS_PRICING),
             ModelInfo::new(
                 AgentType::Claude,
                 "sonnet",
@@ -203,7 +277,8 @@ impl ModelRegistry {
                 "sonnet",
                 "Sonnet 4.6, best for everyday tasks",
                 Self::CLAUDE_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(Self::CLAUDE_SONNET_PRICING),
             ModelInfo::new(
                 AgentType::Claude,
                 "sonnet[1m]",
@@ -211,7 +286,8 @@ impl ModelRegistry {
                 "sonnet[1m]",
                 "Sonnet 4.6 with 1M context",
                 Self::CLAUDE_1M_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(Self::CLAUDE_SONNET_PRICING),
             ModelInfo::new(
                 AgentType::Claude,
                 "haiku",
@@ -219,7 +295,8 @@ impl ModelRegistry {
                 "haiku",
                 "Haiku 4.5, fastest for quick answers",
                 Self::CLAUDE_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(Self::CLAUDE_HAIKU_PRICING),
         ]
     }
 
@@ -234,6 +311,8 @@ impl ModelRegistry {
                 "Latest frontier agentic coding model",
                 Self::CODEX_GPT53_CONTEXT_WINDOW,
             )
+            // Estimated at GPT-5.2 rates
+            .with_pricing(Self::GPT52_PRICING)
             .as_default(),
             ModelInfo::new(
                 AgentType::Codex,
@@ -250,7 +329,8 @@ impl ModelRegistry {
                 "gpt-5.2-codex",
                 "Frontier agentic coding model",
                 Self::CODEX_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(Self::GPT52_PRICING),
             ModelInfo::new(
                 AgentType::Codex,
                 "gpt-5.1-codex-max",
@@ -258,7 +338,8 @@ impl ModelRegistry {
                 "gpt-5.1-codex-max",
                 "Codex-optimized flagship for deep and fast reasoning",
                 Self::CODEX_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(Self::GPT51_PRICING),
             ModelInfo::new(
                 AgentType::Codex,
                 "gpt-5.2",
@@ -266,7 +347,8 @@ impl ModelRegistry {
                 "gpt-5.2",
                 "Latest frontier model with improvements across knowledge, reasoning and coding",
                 Self::CODEX_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(Self::GPT52_PRICING),
             ModelInfo::new(
                 AgentType::Codex,
                 "gpt-5.1-codex-mini",
@@ -274,7 +356,8 @@ impl ModelRegistry {
                 "gpt-5.1-codex-mini",
                 "Optimized for Codex: cheaper and faster, but less capable",
                 Self::CODEX_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(Self::GPT51_MINI_PRICING),
         ]
     }
 
@@ -289,6 +372,7 @@ impl ModelRegistry {
                 "Highest quality Gemini model",
                 Self::GEMINI_CONTEXT_WINDOW,
             )
+            .with_pricing(Self::GEMINI_25_PRO_PRICING)
             .as_default(),
             ModelInfo::new(
                 AgentType::Gemini,
@@ -297,7 +381,8 @@ impl ModelRegistry {
                 "gemini-2.5-flash",
                 "Fast and capable Gemini model",
                 Self::GEMINI_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(Self::GEMINI_25_FLASH_PRICING),
             ModelInfo::new(
                 AgentType::Gemini,
                 "gemini-2.5-flash-lite",
@@ -305,7 +390,8 @@ impl ModelRegistry {
                 "gemini-2.5-flash-lite",
                 "Lowest-latency Gemini model",
                 Self::GEMINI_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(Self::GEMINI_25_FLASH_LITE_PRICING),
             ModelInfo::new(
                 AgentType::Gemini,
                 "gemini-3-pro-preview",
@@ -313,7 +399,8 @@ impl ModelRegistry {
                 "gemini-3-pro-preview",
                 "Preview Gemini 3 model",
                 Self::GEMINI_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(Self::GEMINI_3_PRO_PRICING),
             ModelInfo::new(
                 AgentType::Gemini,
                 "gemini-3-flash-preview",
@@ -321,16 +408,33 @@ impl ModelRegistry {
                 "gemini-3-flash-preview",
                 "Preview Gemini 3 flash model",
                 Self::GEMINI_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(Self::GEMINI_3_FLASH_PRICING),
         ]
     }
 
+    /// Get available models for the configured ACP agent.
+    ///
+    /// ACP agents pick their own model, so there is a single placeholder entry.
+    pub fn acp_models() -> Vec<ModelInfo> {
+        vec![ModelInfo::new(
+            AgentType::Acp,
+            Self::ACP_DEFAULT_MODEL_ID,
+            "Agent default",
+            Self::ACP_DEFAULT_MODEL_ID,
+            "Model chosen by the agent",
+            Self::ACP_CONTEXT_WINDOW,
+        )
+        .as_default()]
+    }
+
     /// Get all models grouped by agent type
     pub fn all_models() -> Vec<ModelInfo> {
         let mut models = Self::claude_models();
         models.extend(Self::codex_models());
         models.extend(Self::gemini_models());
         models.extend(Self::opencode_models());
+        models.extend(Self::acp_models());
         models
     }
 
@@ -341,6 +445,7 @@ impl ModelRegistry {
             AgentType::Codex => Self::codex_models(),
             AgentType::Gemini => Self::gemini_models(),
             AgentType::Opencode => Self::opencode_models(),
+            AgentType::Acp => Self::acp_models(),
         }
     }
 
@@ -351,6 +456,7 @@ impl ModelRegistry {
             AgentType::Codex => "gpt-5.3-codex".to_string(),
             AgentType::Gemini => "gemini-2.5-pro".to_string(),
             AgentType::Opencode => Self::OPENCODE_DEFAULT_MODEL_ID.to_string(),
+            AgentType::Acp => Self::ACP_DEFAULT_MODEL_ID.to_string(),
         }
     }
 
@@ -389,6 +495,7 @@ impl ModelRegistry {
             AgentType::Codex => "◎",
             AgentType::Gemini => "◆",
             AgentType::Opencode => "◍",
+            AgentType::Acp => "◇",
         }
     }
 
@@ -399,6 +506,7 @@ impl ModelRegistry {
             AgentType::Codex => "Codex",
             AgentType::Gemini => "Gemini",
             AgentType::Opencode => "OpenCode",
+            AgentType::Acp => AgentType::Acp.display_name(),
         }
     }
 
@@ -409,6 +517,11 @@ impl ModelRegistry {
             .unwrap_or_else(|| Self::default_context_window(agent_type))
     }
 
+    /// Get list pricing for a specific model, if known
+    pub fn pricing(agent_type: AgentType, model_id: &str) -> Option<ModelPricing> {
+        Self::find_model(agent_type, model_id).and_then(|m| m.pricing)
+    }
+
     /// Default context window when model not found
     pub fn default_context_window(agent_type: AgentType) -> i64 {
         match agent_type {
@@ -416,6 +529,7 @@ impl ModelRegistry {
             AgentType::Codex => Self::CODEX_CONTEXT_WINDOW,
             AgentType::Gemini => Self::GEMINI_CONTEXT_WINDOW,
             AgentType::Opencode => Self::OPENCODE_CONTEXT_WINDOW,
+            AgentType::Acp => Self::ACP_CONTEXT_WINDOW,
         }
     }
 }
@@ -2176,6 +2176,16 @@ impl AgentRunner for OpencodeRunner {
                                 return;
                             }
                         }
+                        AgentInput::AcpPermission { .. } => {
+                            tracing::warn!(
+                                "Ignored ACP permission response sent to OpenCode input channel"
+                            );
+                        }
+                        AgentInput::CodexApproval { .. } => {
+                            tracing::warn!(
+                                "Ignored Codex approval response sent to OpenCode input channel"
+                            );
+                        }
                         AgentInput::OpencodeQuestion {
                             request_id,
                             answers,
@@ -0,0 +1,344 @@
+//! Classification of provider errors reported by the agent CLIs
+//!
+//! Claude and Codex report rate limits, overloads, expired logins and
+//! oversized prompts as free-form error text. Runners pass that text through
+//! [`classified_error`], which tags the [`ErrorEvent`] with a stable code and,
+//! when the message says how long to wait, a `retry_after_secs` detail.
+
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::agent::events::ErrorEvent;
+
+/// Provider error categories that frontends react to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProviderErrorKind {
+    RateLimited,
+    Overloaded,
+    AuthExpired,
+    ContextExceeded,
+}
+
+impl ProviderErrorKind {
+    pub const ALL: [ProviderErrorKind; 4] = [
+        ProviderErrorKind::RateLimited,
+        ProviderErrorKind::Overloaded,
+        ProviderErrorKind::AuthExpired,
+        ProviderErrorKind::ContextExceeded,
+    ];
+
+    /// Code stored on the error event
+    pub fn code(&self) -> &'static str {
+        match self {
+            ProviderErrorKind::RateLimited => "rate_limited",
+            ProviderErrorKind::Overloaded => "overloaded",
+            ProviderErrorKind::AuthExpired => "auth_expired",
+            ProviderErrorKind::ContextExceeded => "context_exceeded",
+        }
+    }
+
+    /// Short label for the status bar
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProviderErrorKind::RateLimited => "Rate limited",
+            ProviderErrorKind::Overloaded => "Overloaded",
+            ProviderErrorKind::AuthExpired => "Login expired",
+            ProviderErrorKind::ContextExceeded => "Context full",
+        }
+    }
+
+    /// Whether waiting and sending the prompt again can succeed
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            ProviderErrorKind::RateLimited | ProviderErrorKind::Overloaded
+        )
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.code() == code)
+    }
+
+    pub fn from_error(error: &ErrorEvent) -> Option<Self> {
+        error.code.as_deref().and_then(Self::from_code)
+    }
+
+    /// Category of an error message, if it matches one. Status codes only
+    /// count in a recognizable form ("status 429", "API Error: 529",
+    /// "429 Too Many Requests"), never as a bare number.
+    pub fn classify(message: &str) -> Option<Self> {
+        let message = message.to_lowercase();
+        let matches = |markers: &[&str]| markers.iter().any(|marker| message.contains(marker));
+        let status = http_status(&message);
+        if matches(&[
+            "authentication_error",
+            "authentication_failed",
+            "invalid api key",
+            "invalid x-api-key",
+            "oauth token has expired",
+            "please run /login",
+            "401 unauthorized",
+        ]) || status == Some(401)
+        {
+            Some(ProviderErrorKind::AuthExpired)
+        } else if matches(&[
+            "prompt is too long",
+            "context_length_exceeded",
+            "maximum context length",
+            "exceeds the context window",
+            "input is too long",
+        ]) {
+            Some(ProviderErrorKind::ContextExceeded)
+        } else if matches(&[
+            "rate_limit_error",
+            "rate limit exceeded",
+            "rate limit reached",
+            "rate limited",
+            "(rate_limit)",
+            "too many requests",
+            "usage limit",
+        ]) || status == Some(429)
+        {
+            Some(ProviderErrorKind::RateLimited)
+        } else if matches(&[
+            "overloaded_error",
+            "service unavailable",
+            "529 overloaded",
+            "is overloaded",
+            "at capacity",
+        ]) || matches!(status, Some(503 | 529))
+        {
+            Some(ProviderErrorKind::Overloaded)
+        } else {
+            None
+        }
+    }
+}
+
+/// Status code of a lowercased message that names one, as in "status 429",
+/// "status code: 503", "http 429" or Claude's "api error: 529"
+fn http_status(message: &str) -> Option<u16> {
+    const PREFIXES: [&str; 5] = ["status code", "status", "http/1.1", "http", "api error"];
+    PREFIXES.iter().find_map(|prefix| {
+        message.match_indices(prefix).find_map(|(start, _)| {
+            let rest = message[start + prefix.len()..]
+                .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, ':' | '=' | '"'));
+            let digits = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            (digits == 3).then(|| rest[..3].parse().ok()).flatten()
+        })
+    })
+}
+
+/// Error event for a provider error message, tagged with its category and
+/// retry-after delay when they can be recognized
+pub fn classified_error(message: String, is_fatal: bool) -> ErrorEvent {
+    let kind = ProviderErrorKind::classify(&message);
+    let retry_after = kind
+        .filter(ProviderErrorKind::is_transient)
+        .and_then(|_| parse_retry_after(&message, chrono::Utc::now().timestamp()));
+    ErrorEvent {
+        message,
+        is_fatal,
+        code: kind.map(|kind| kind.code().to_string()),
+        details: retry_after.map(|delay| json!({ "retry_after_secs": delay.as_secs() })),
+    }
+}
+
+/// Wait requested by a classified error
+pub fn retry_after(error: &ErrorEvent) -> Option<Duration> {
+    error
+        .details
+        .as_ref()?
+        .get("retry_after_secs")?
+        .as_u64()
+        .map(Duration::from_secs)
+}
+
+/// Read how long to wait from messages such as "retry after 30 seconds",
+/// "Retry-After: 30", "try again in 1m30s" or Claude's
+/// "usage limit reached|<unix reset time>".
+pub fn parse_retry_after(message: &str, now_unix: i64) -> Option<Duration> {
+    let lower = message.to_lowercase();
+
+    if lower.contains("limit reached|") {
+        let reset_at = lower
+            .rsplit('|')
+            .next()
+            .and_then(|tail| tail.trim().parse::<i64>().ok())?;
+        return Some(Duration::from_secs(
+            reset_at.saturating_sub(now_unix).max(0) as u64,
+        ));
+    }
+
+    const MARKERS: [&str; 5] = [
+        "retry-after:",
+        "retry after",
+        "try again in",
+        "retry in",
+        "resets in",
+    ];
+    MARKERS.iter().find_map(|marker| {
+        let start = lower.find(marker)? + marker.len();
+        parse_duration_phrase(&lower[start..])
+    })
+}
+
+/// Parse a phrase like "2 hours 5 minutes", "1m30s", "20.5s" or a bare
+/// number of seconds at the start of `text`
+fn parse_duration_phrase(text: &str) -> Option<Duration> {
+    let mut rest = text;
+    let mut total = 0.0f64;
+    let mut parsed_any = false;
+
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if parsed_any {
+            rest = rest.strip_prefix("and").unwrap_or(rest).trim_start();
+        }
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        if number_len == 0 {
+            break;
+        }
+        let Ok(value) = rest[..number_len].parse::<f64>() else {
+            break;
+        };
+        rest = rest[number_len..].trim_start();
+        let unit_len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let seconds_per_unit = match &rest[..unit_len] {
+            "ms" | "millisecond" | "milliseconds" => 0.001,
+            "s" | "sec" | "secs" | "second" | "seconds" => 1.0,
+            "m" | "min" | "mins" | "minute" | "minutes" => 60.0,
+            "h" | "hr" | "hrs" | "hour" | "hours" => 3600.0,
+            // A bare number (as in a Retry-After header) counts as seconds
+            "" if !parsed_any => 1.0,
+            _ => break,
+        };
+        total += value * seconds_per_unit;
+        parsed_any = true;
+        rest = &rest[unit_len..];
+    }
+
+    parsed_any.then(|| Duration::from_secs(total.ceil() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_messages() {
+        let cases = [
+            (
+                "Claude error (rate_limit): Too many requests",
+                Some(ProviderErrorKind::RateLimited),
+            ),
+            (
+                "Claude error: API Error: 529 {\"type\":\"overloaded_error\"}",
+                Some(ProviderErrorKind::Overloaded),
+            ),
+            (
+                "OAuth token has expired. Please run /login",
+                Some(ProviderErrorKind::AuthExpired),
+            ),
+            (
+                "Claude error: Prompt is too long",
+                Some(ProviderErrorKind::ContextExceeded),
+            ),
+            (
+                "Request failed with status 429",
+                Some(ProviderErrorKind::RateLimited),
+            ),
+            (
+                "HTTP 503 Service Unavailable",
+                Some(ProviderErrorKind::Overloaded),
+            ),
+            (
+                "{\"type\":\"error\",\"error\":{\"type\":\"rate_limit_error\"}}",
+                Some(ProviderErrorKind::RateLimited),
+            ),
+            (
+                "Codex error: Your input exceeds the context window of this model",
+                Some(ProviderErrorKind::ContextExceeded),
+            ),
+            ("Tool failed: file not found", None),
+        ];
+        for (message, expected) in cases {
+            assert_eq!(
+                ProviderErrorKind::classify(message),
+                expected,
+                "{}",
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn test_classify_ignores_incidental_matches() {
+        for message in [
+            "Error: src/lib.rs:429:5: mismatched types",
+            "Build failed after 503 tests",
+            "Process exited with code 1 (pid 15290)",
+            "Error reading context length from config",
+            "git push: authentication required for origin",
+            "Error: status 4290 not recognized",
+        ] {
+            assert_eq!(ProviderErrorKind::classify(message), None, "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_code_round_trip() {
+        for kind in ProviderErrorKind::ALL {
+            assert_eq!(ProviderErrorKind::from_code(kind.code()), Some(kind));
+        }
+        assert_eq!(ProviderErrorKind::from_code("model_not_found"), None);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let parse = |message: &str| parse_retry_after(message, 1_000);
+        assert_eq!(
+            parse("Rate limit exceeded, retry after 30 seconds"),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse("429 Too Many Requests; Retry-After: 12"),
+            Some(Duration::from_secs(12))
+        );
+        assert_eq!(
+            parse("Please try again in 1m30s."),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            parse("usage limit. Try again in 2 hours and 5 minutes"),
+            Some(Duration::from_secs(2 * 3600 + 5 * 60))
+        );
+        assert_eq!(
+            parse("Please try again in 20.5s"),
+            Some(Duration::from_secs(21))
+        );
+        assert_eq!(
+            parse("Claude AI usage limit reached|1600"),
+            Some(Duration::from_secs(600))
+        );
+        assert_eq!(parse("Rate limit exceeded"), None);
+    }
+
+    #[test]
+    fn test_classified_error_details() {
+        let event = classified_error("Rate limited. Retry after 45s".to_string(), true);
+        assert_eq!(event.code.as_deref(), Some("rate_limited"));
+        assert_eq!(retry_after(&event), Some(Duration::from_secs(45)));
+
+        let plain = classified_error("Something else broke".to_string(), true);
+        assert!(plain.code.is_none());
+        assert!(retry_after(&plain).is_none());
+    }
+}
@@ -1,11 +1,14 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::str::FromStr;
 use tokio::sync::mpsc;
 
 use crate::agent::error::AgentError;
 use crate::agent::events::AgentEvent;
 use crate::agent::session::SessionId;
+use crate::agent::tool_approval::ToolApprovalChoice;
+use crate::agent::tool_policy::ToolPolicy;
 
 /// Agent type identifier
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -14,6 +17,8 @@ pub enum AgentType {
     Codex,
     Gemini,
     Opencode,
+    /// Any Agent Client Protocol agent declared in config.toml
+    Acp,
 }
 
 /// Agent mode (Build vs Plan)
@@ -27,6 +32,199 @@ pub enum AgentMode {
     Plan,
 }
 
+/// How tool permission requests from the agent are answered.
+///
+/// Only agents that surface permission requests (Claude, Gemini and other ACP
+/// agents) honor this; other agents keep their own permission handling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApprovalPolicy {
+    /// Ask the user for every tool call.
+    #[default]
+    Ask,
+    /// Auto-approve read-only tools, ask for everything else.
+    AllowReads,
+    /// Auto-approve every tool call.
+    AllowAll,
+}
+
+impl ApprovalPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApprovalPolicy::Ask => "ask",
+            ApprovalPolicy::AllowReads => "allow-reads",
+            ApprovalPolicy::AllowAll => "allow-all",
+        }
+    }
+
+    /// Next policy in cycle order (Ask -> AllowReads -> AllowAll -> Ask)
+    pub fn next(&self) -> Self {
+        match self {
+            ApprovalPolicy::Ask => ApprovalPolicy::AllowReads,
+            ApprovalPolicy::AllowReads => ApprovalPolicy::AllowAll,
+            ApprovalPolicy::AllowAll => ApprovalPolicy::Ask,
+        }
+    }
+
+    /// Display name for the UI
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ApprovalPolicy::Ask => "Always ask",
+            ApprovalPolicy::AllowReads => "Allow reads",
+            ApprovalPolicy::AllowAll => "Allow all",
+        }
+    }
+}
+
+impl FromStr for ApprovalPolicy {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "ask" => Ok(ApprovalPolicy::Ask),
+            "allow-reads" => Ok(ApprovalPolicy::AllowReads),
+            "allow-all" => Ok(ApprovalPolicy::AllowAll),
+            _ => Err(()),
+        }
+    }
+}
+
+impl std::fmt::Display for ApprovalPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// When Codex stops to ask before running a command or applying a patch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CodexApprovalMode {
+    /// Ask for everything except known read-only commands.
+    Untrusted,
+    /// Ask only when a sandboxed command fails.
+    OnFailure,
+    /// Let the model decide when to ask.
+    OnRequest,
+    /// Never ask.
+    Never,
+}
+
+impl CodexApprovalMode {
+    pub const ALL: [CodexApprovalMode; 4] = [
+        CodexApprovalMode::Untrusted,
+        CodexApprovalMode::OnFailure,
+        CodexApprovalMode::OnRequest,
+        CodexApprovalMode::Never,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CodexApprovalMode::Untrusted => "untrusted",
+            CodexApprovalMode::OnFailure => "on-failure",
+            CodexApprovalMode::OnRequest => "on-request",
+            CodexApprovalMode::Never => "never",
+        }
+    }
+
+    /// Display name for the UI
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CodexApprovalMode::Untrusted => "Untrusted",
+            CodexApprovalMode::OnFailure => "On failure",
+            CodexApprovalMode::OnRequest => "On request",
+            CodexApprovalMode::Never => "Never",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            CodexApprovalMode::Untrusted => "Ask before anything but safe reads",
+            CodexApprovalMode::OnFailure => "Ask when a sandboxed command fails",
+            CodexApprovalMode::OnRequest => "Codex decides when to ask",
+            CodexApprovalMode::Never => "Never ask",
+        }
+    }
+}
+
+impl FromStr for CodexApprovalMode {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        CodexApprovalMode::ALL
+            .into_iter()
+            .find(|mode| mode.as_str().eq_ignore_ascii_case(value.trim()))
+            .ok_or(())
+    }
+}
+
+impl std::fmt::Display for CodexApprovalMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What Codex commands may touch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CodexSandboxMode {
+    /// Read files only.
+    ReadOnly,
+    /// Write inside the workspace only.
+    WorkspaceWrite,
+    /// No sandbox.
+    DangerFullAccess,
+}
+
+impl CodexSandboxMode {
+    pub const ALL: [CodexSandboxMode; 3] = [
+        CodexSandboxMode::ReadOnly,
+        CodexSandboxMode::WorkspaceWrite,
+        CodexSandboxMode::DangerFullAccess,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CodexSandboxMode::ReadOnly => "read-only",
+            CodexSandboxMode::WorkspaceWrite => "workspace-write",
+            CodexSandboxMode::DangerFullAccess => "danger-full-access",
+        }
+    }
+
+    /// Display name for the UI
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CodexSandboxMode::ReadOnly => "Read only",
+            CodexSandboxMode::WorkspaceWrite => "Workspace write",
+            CodexSandboxMode::DangerFullAccess => "Full access",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            CodexSandboxMode::ReadOnly => "Commands can read but not write",
+            CodexSandboxMode::WorkspaceWrite => "Writes stay inside the workspace",
+            CodexSandboxMode::DangerFullAccess => "No sandbox at all",
+        }
+    }
+}
+
+impl FromStr for CodexSandboxMode {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        CodexSandboxMode::ALL
+            .into_iter()
+            .find(|mode| mode.as_str().eq_ignore_ascii_case(value.trim()))
+            .ok_or(())
+    }
+}
+
+impl std::fmt::Display for CodexSandboxMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Provider-agnostic reasoning effort profile.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ReasoningEffort {
@@ -116,12 +314,13 @@ impl AgentMode {
 
 impl AgentType {
     /// Preferred provider priority order used for defaults and UI listing.
-    pub const fn preferred_order() -> [AgentType; 4] {
+    pub const fn preferred_order() -> [AgentType; 5] {
         [
             AgentType::Codex,
             AgentType::Claude,
             AgentType::Gemini,
             AgentType::Opencode,
+            AgentType::Acp,
         ]
     }
 
@@ -132,12 +331,18 @@ impl AgentType {
         )
     }
 
+    /// Whether the agent is driven over the Agent Client Protocol.
+    pub fn uses_acp(&self) -> bool {
+        matches!(self, AgentType::Gemini | AgentType::Acp)
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             AgentType::Claude => "claude",
             AgentType::Codex => "codex",
             AgentType::Gemini => "gemini",
             AgentType::Opencode => "opencode",
+            AgentType::Acp => "acp",
         }
     }
 
@@ -146,6 +351,7 @@ impl AgentType {
             "codex" => AgentType::Codex,
             "gemini" => AgentType::Gemini,
             "opencode" => AgentType::Opencode,
+            "acp" => AgentType::Acp,
             _ => AgentType::Claude,
         }
     }
@@ -157,15 +363,19 @@ impl AgentType {
             AgentType::Codex => "Codex",
             AgentType::Gemini => "Gemini",
             AgentType::Opencode => "OpenCode",
+            AgentType::Acp => crate::agent::acp::AcpAgentConfig::DEFAULT_DISPLAY_NAME,
         }
     }
 
+    /// Generic name of the agent. The configured ACP agent's own name comes
+    /// from [`Config::agent_display_name`](crate::config::Config::agent_display_name).
     pub fn display_name(&self) -> &'static str {
         match self {
             AgentType::Claude => "Claude Code",
             AgentType::Codex => "Codex CLI",
             AgentType::Gemini => "Gemini CLI",
             AgentType::Opencode => "OpenCode",
+            AgentType::Acp => crate::agent::acp::AcpAgentConfig::DEFAULT_DISPLAY_NAME,
         }
     }
 }
@@ -197,6 +407,14 @@ pub struct AgentStartConfig {
     pub input_format: Option<String>,
     /// Optional stdin payload for structured input (e.g. JSONL)
     pub stdin_payload: Option<String>,
+    /// How tool permission requests are answered
+    pub approval_policy: ApprovalPolicy,
+    /// Allow and deny rules from the config and the repository policy file
+    pub tool_policy: ToolPolicy,
+    /// Codex approval mode chosen for the session (None = default)
+    pub codex_approval: Option<CodexApprovalMode>,
+    /// Codex sandbox mode chosen for the session (None = default)
+    pub codex_sandbox: Option<CodexSandboxMode>,
 }
 
 impl AgentStartConfig {
@@ -214,6 +432,10 @@ impl AgentStartConfig {
             agent_mode: AgentMode::default(),
             input_format: None,
             stdin_payload: None,
+            approval_policy: ApprovalPolicy::default(),
+            tool_policy: ToolPolicy::default(),
+            codex_approval: None,
+            codex_sandbox: None,
         }
     }
 
@@ -261,6 +483,26 @@ impl AgentStartConfig {
         self.stdin_payload = Some(payload.into());
         self
     }
+
+    pub fn with_approval_policy(mut self, policy: ApprovalPolicy) -> Self {
+        self.approval_policy = policy;
+        self
+    }
+
+    pub fn with_tool_policy(mut self, policy: ToolPolicy) -> Self {
+        self.tool_policy = policy;
+        self
+    }
+
+    pub fn with_codex_permissions(
+        mut self,
+        approval: Option<CodexApprovalMode>,
+        sandbox: Option<CodexSandboxMode>,
+    ) -> Self {
+        self.codex_approval = approval;
+        self.codex_sandbox = sandbox;
+        self
+    }
 }
 
 /// Input payload for running agents.
@@ -279,6 +521,16 @@ pub enum AgentInput {
         request_id: String,
         answers: Option<Vec<Vec<String>>>,
     },
+    /// ACP permission response (None means cancel).
+    AcpPermission {
+        request_id: String,
+        option_id: Option<String>,
+    },
+    /// Answer to a Codex command or patch approval request.
+    CodexApproval {
+        request_id: String,
+        choice: ToolApprovalChoice,
+    },
 }
 
 /// Handle to a running agent process
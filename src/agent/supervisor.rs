@@ -0,0 +1,348 @@
+//! Crash detection and restart policy for agent processes
+//!
+//! The Claude and Codex monitors report a non-zero exit as a fatal
+//! [`ErrorEvent`] with the `process_exited` code and a [`ProcessExit`] in its
+//! details. Frontends classify that exit and, when the process crashed in the
+//! middle of a turn, start the agent again with `resume_session` after a
+//! backoff delay so the conversation picks up where it stopped.
+
+use std::process::ExitStatus;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::events::ErrorEvent;
+use crate::agent::runner::AgentType;
+
+/// Error code carried by process exit errors
+pub const PROCESS_EXITED: &str = "process_exited";
+
+/// Error code for the notice posted before an automatic restart
+pub const AGENT_RESTARTING: &str = "agent_restarting";
+
+/// Prompt sent to the resumed session after a crash
+pub const RESUME_PROMPT: &str = "Your previous process exited unexpectedly in the middle of this \
+turn. Continue the task from where you left off; re-check any command or edit whose result you \
+did not see.";
+
+/// Lines of stderr kept with a process exit
+const STDERR_TAIL_LINES: usize = 20;
+
+/// Characters of stderr kept with a process exit
+const STDERR_TAIL_CHARS: usize = 2000;
+
+// POSIX signal numbers; the monitors only see them on unix.
+const SIGHUP: i32 = 1;
+const SIGINT: i32 = 2;
+const SIGABRT: i32 = 6;
+const SIGKILL: i32 = 9;
+const SIGSEGV: i32 = 11;
+const SIGTERM: i32 = 15;
+
+/// Stderr fragments that mean another attempt would fail the same way
+const UNRECOVERABLE_MARKERS: [&str; 9] = [
+    "not logged in",
+    "please run /login",
+    "invalid api key",
+    "authentication",
+    "unauthorized",
+    "unknown option",
+    "unexpected argument",
+    "no conversation found",
+    "command not found",
+];
+
+/// How an agent process ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitKind {
+    /// Exited with status 0
+    Clean,
+    /// Stopped by SIGTERM/SIGINT/SIGHUP, usually on request
+    Terminated,
+    /// Failed in a way a restart will not fix (auth, bad flags, missing session)
+    Unrecoverable,
+    /// Anything else: non-zero status, abort, segfault, OOM kill
+    Crash,
+}
+
+impl ExitKind {
+    pub fn should_restart(&self) -> bool {
+        matches!(self, ExitKind::Crash)
+    }
+}
+
+/// Exit status and stderr tail of a finished agent process
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessExit {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signal: Option<i32>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub stderr_tail: String,
+}
+
+impl ProcessExit {
+    pub fn from_status(status: &ExitStatus, stderr: &str) -> Self {
+        #[cfg(unix)]
+        let signal = std::os::unix::process::ExitStatusExt::signal(status);
+        #[cfg(not(unix))]
+        let signal = None;
+        Self {
+            exit_code: status.code(),
+            signal,
+            stderr_tail: stderr_tail(stderr),
+        }
+    }
+
+    /// Exit recorded on a `process_exited` error, if this is one
+    pub fn from_error(error: &ErrorEvent) -> Option<Self> {
+        if error.code.as_deref() != Some(PROCESS_EXITED) {
+            return None;
+        }
+        error
+            .details
+            .clone()
+            .and_then(|details| serde_json::from_value(details).ok())
+    }
+
+    pub fn classify(&self) -> ExitKind {
+        if self.exit_code == Some(0) {
+            return ExitKind::Clean;
+        }
+        let stderr = self.stderr_tail.to_lowercase();
+        if UNRECOVERABLE_MARKERS
+            .iter()
+            .any(|marker| stderr.contains(marker))
+        {
+            return ExitKind::Unrecoverable;
+        }
+        // Node and shells exit with 128 + signal after handling the signal
+        let signal = self.signal.or_else(|| {
+            self.exit_code
+                .filter(|code| *code > 128)
+                .map(|code| code - 128)
+        });
+        match signal {
+            Some(SIGHUP | SIGINT | SIGTERM) => ExitKind::Terminated,
+            _ => ExitKind::Crash,
+        }
+    }
+
+    /// Short description such as `exit code 1` or `SIGSEGV`
+    pub fn describe(&self) -> String {
+        match (self.signal, self.exit_code) {
+            (Some(signal), _) => signal_name(signal),
+            (None, Some(code)) => format!("exit code {}", code),
+            (None, None) => "unknown status".to_string(),
+        }
+    }
+
+    /// Fatal error event reported by a runner's process monitor
+    pub fn error_event(&self, agent_name: &str) -> ErrorEvent {
+        let message = if self.stderr_tail.is_empty() {
+            format!("{} process exited ({})", agent_name, self.describe())
+        } else {
+            format!(
+                "{} process failed ({}): {}",
+                agent_name,
+                self.describe(),
+                self.stderr_tail
+            )
+        };
+        ErrorEvent {
+            message,
+            is_fatal: true,
+            code: Some(PROCESS_EXITED.to_string()),
+            details: serde_json::to_value(self).ok(),
+        }
+    }
+}
+
+fn signal_name(signal: i32) -> String {
+    match signal {
+        SIGHUP => "SIGHUP".to_string(),
+        SIGINT => "SIGINT".to_string(),
+        SIGABRT => "SIGABRT".to_string(),
+        SIGKILL => "SIGKILL".to_string(),
+        SIGSEGV => "SIGSEGV".to_string(),
+        SIGTERM => "SIGTERM".to_string(),
+        other => format!("signal {}", other),
+    }
+}
+
+/// Last lines of stderr, trimmed to a bounded size
+pub fn stderr_tail(stderr: &str) -> String {
+    let lines: Vec<&str> = stderr.trim().lines().collect();
+    let start = lines.len().saturating_sub(STDERR_TAIL_LINES);
+    let tail = lines[start..].join("\n");
+    let char_count = tail.chars().count();
+    if char_count <= STDERR_TAIL_CHARS {
+        return tail;
+    }
+    tail.chars().skip(char_count - STDERR_TAIL_CHARS).collect()
+}
+
+/// How often and how quickly crashed agents are restarted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestartPolicy {
+    pub enabled: bool,
+    /// Restarts allowed per turn before giving up
+    pub max_restarts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_restarts: 3,
+            initial_backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Delay before restart number `attempt` (1-based), or `None` once the
+    /// restarts are used up
+    pub fn next_delay(&self, attempt: u32) -> Option<Duration> {
+        if !self.enabled || attempt == 0 || attempt > self.max_restarts {
+            return None;
+        }
+        let factor = 1u32.checked_shl(attempt - 1).unwrap_or(u32::MAX);
+        Some(
+            self.initial_backoff
+                .saturating_mul(factor)
+                .min(self.max_backoff),
+        )
+    }
+}
+
+/// Chat notice posted when a crashed agent is about to be restarted
+pub fn restart_notice(
+    agent_type: AgentType,
+    exit: &ProcessExit,
+    attempt: u32,
+    policy: &RestartPolicy,
+    delay: Duration,
+) -> String {
+    format!(
+        "{} crashed ({}). Resuming the session in {}s (restart {} of {}).",
+        agent_type.display_name(),
+        exit.describe(),
+        delay.as_secs_f32().ceil() as u64,
+        attempt,
+        policy.max_restarts
+    )
+}
+
+/// Chat notice posted when a crash is not followed by a restart
+pub fn give_up_notice(agent_type: AgentType, kind: ExitKind, restarts: u32) -> String {
+    let name = agent_type.display_name();
+    match kind {
+        ExitKind::Unrecoverable => format!(
+            "{} exited with an error that a restart will not fix; not restarting.",
+            name
+        ),
+        _ if restarts > 0 => format!(
+            "{} crashed again after {} restart{}; giving up. Send a prompt to resume manually.",
+            name,
+            restarts,
+            if restarts == 1 { "" } else { "s" }
+        ),
+        _ => format!(
+            "{} crashed before the session started, so it cannot be resumed.",
+            name
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exit(code: Option<i32>, signal: Option<i32>, stderr: &str) -> ProcessExit {
+        ProcessExit {
+            exit_code: code,
+            signal,
+            stderr_tail: stderr.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_classify_exits() {
+        assert_eq!(exit(Some(0), None, "").classify(), ExitKind::Clean);
+        assert_eq!(exit(Some(1), None, "panic").classify(), ExitKind::Crash);
+        assert_eq!(exit(None, Some(SIGSEGV), "").classify(), ExitKind::Crash);
+        assert_eq!(exit(None, Some(SIGKILL), "").classify(), ExitKind::Crash);
+        assert_eq!(
+            exit(None, Some(SIGTERM), "").classify(),
+            ExitKind::Terminated
+        );
+        assert_eq!(exit(Some(130), None, "").classify(), ExitKind::Terminated);
+        assert_eq!(
+            exit(Some(1), None, "Error: Invalid API key · Please run /login").classify(),
+            ExitKind::Unrecoverable
+        );
+        assert_eq!(
+            exit(Some(1), None, "No conversation found with session ID: abc").classify(),
+            ExitKind::Unrecoverable
+        );
+    }
+
+    #[test]
+    fn test_error_event_round_trip() {
+        let crash = exit(Some(3), None, "boom");
+        let event = crash.error_event("Claude");
+        assert!(event.is_fatal);
+        assert_eq!(event.message, "Claude process failed (exit code 3): boom");
+        assert_eq!(ProcessExit::from_error(&event), Some(crash));
+
+        let other = ErrorEvent {
+            message: "model missing".to_string(),
+            is_fatal: true,
+            code: Some("model_not_found".to_string()),
+            details: None,
+        };
+        assert_eq!(ProcessExit::from_error(&other), None);
+    }
+
+    #[test]
+    fn test_stderr_tail_keeps_last_lines() {
+        let stderr = (0..50)
+            .map(|i| format!("line {}", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let tail = stderr_tail(&stderr);
+        assert_eq!(tail.lines().count(), STDERR_TAIL_LINES);
+        assert!(tail.starts_with("line 30"));
+        assert!(tail.ends_with("line 49"));
+
+        let long = "x".repeat(STDERR_TAIL_CHARS * 2);
+        assert_eq!(stderr_tail(&long).len(), STDERR_TAIL_CHARS);
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let policy = RestartPolicy::default();
+        assert_eq!(policy.next_delay(0), None);
+        assert_eq!(policy.next_delay(1), Some(Duration::from_secs(2)));
+        assert_eq!(policy.next_delay(2), Some(Duration::from_secs(4)));
+        assert_eq!(policy.next_delay(3), Some(Duration::from_secs(8)));
+        assert_eq!(policy.next_delay(4), None);
+
+        let capped = RestartPolicy {
+            max_restarts: 10,
+            ..RestartPolicy::default()
+        };
+        assert_eq!(capped.next_delay(10), Some(Duration::from_secs(30)));
+
+        let disabled = RestartPolicy {
+            enabled: false,
+            ..RestartPolicy::default()
+        };
+        assert_eq!(disabled.next_delay(1), None);
+    }
+}
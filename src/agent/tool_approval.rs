@@ -0,0 +1,294 @@
+//! Approval cards for Claude tool calls
+//!
+//! Claude asks for permission through `can_use_tool` control requests. Every
+//! request that the approval policy does not answer on its own is shown to the
+//! user with the same four choices in the TUI and the web UI.
+
+use std::str::FromStr;
+
+use serde_json::{json, Value};
+
+use crate::agent::events::ControlRequestEvent;
+use crate::agent::runner::{AgentType, ApprovalPolicy};
+
+/// Lines of tool input shown on an approval card before it is cut off
+const MAX_PREVIEW_LINES: usize = 40;
+
+/// How the user answered an approval card
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToolApprovalChoice {
+    /// Run this call only
+    AllowOnce,
+    /// Run this tool without asking for the rest of the session
+    AllowSession,
+    /// Run this tool without asking in this repository from now on
+    AllowAlways,
+    /// Refuse the call, optionally telling the agent why
+    Deny,
+}
+
+impl ToolApprovalChoice {
+    /// Choices in the order they are offered
+    pub const ALL: [ToolApprovalChoice; 4] = [
+        ToolApprovalChoice::AllowOnce,
+        ToolApprovalChoice::AllowSession,
+        ToolApprovalChoice::AllowAlways,
+        ToolApprovalChoice::Deny,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ToolApprovalChoice::AllowOnce => "allow_once",
+            ToolApprovalChoice::AllowSession => "allow_session",
+            ToolApprovalChoice::AllowAlways => "allow_always",
+            ToolApprovalChoice::Deny => "deny",
+        }
+    }
+
+    /// Label shown on the approval card
+    pub fn label(&self) -> &'static str {
+        match self {
+            ToolApprovalChoice::AllowOnce => "Allow once",
+            ToolApprovalChoice::AllowSession => "Allow for this session",
+            ToolApprovalChoice::AllowAlways => "Always allow in this repository",
+            ToolApprovalChoice::Deny => "Deny with reason...",
+        }
+    }
+
+    /// Whether an agent's approval cards offer this choice. Codex has no
+    /// repository-wide allow list, so it is not offered "always allow".
+    pub fn is_offered(&self, agent_type: AgentType) -> bool {
+        agent_type != AgentType::Codex || *self != ToolApprovalChoice::AllowAlways
+    }
+
+    pub fn is_allow(&self) -> bool {
+        !matches!(self, ToolApprovalChoice::Deny)
+    }
+}
+
+impl FromStr for ToolApprovalChoice {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ToolApprovalChoice::ALL
+            .into_iter()
+            .find(|choice| choice.as_str() == value)
+            .ok_or(())
+    }
+}
+
+/// Tools that ask the user something themselves and never get an approval card
+pub fn is_interactive_tool(tool_name: &str) -> bool {
+    matches!(tool_name, "AskUserQuestion" | "ExitPlanMode")
+}
+
+/// Claude tools that only read the workspace or the web
+pub fn is_read_only_tool(tool_name: &str) -> bool {
+    matches!(
+        tool_name,
+        "Read" | "Glob" | "Grep" | "LS" | "NotebookRead" | "WebFetch" | "WebSearch" | "TodoWrite"
+    )
+}
+
+/// Whether a Claude tool call can run without showing an approval card
+pub fn should_auto_allow(
+    policy: ApprovalPolicy,
+    allowed_tools: &[String],
+    tool_name: &str,
+) -> bool {
+    if allowed_tools.iter().any(|tool| tool == tool_name) {
+        return true;
+    }
+    match policy {
+        ApprovalPolicy::AllowAll => true,
+        ApprovalPolicy::AllowReads => is_read_only_tool(tool_name),
+        ApprovalPolicy::Ask => false,
+    }
+}
+
+/// What the tool is about to do: the command for shell tools, a `-`/`+` diff
+/// for edits and the new contents for writes.
+pub fn preview(tool_name: &str, input: &Value) -> Option<String> {
+    let field = |key: &str| input.get(key).and_then(Value::as_str);
+    let lines = match tool_name {
+        "Bash" => {
+            let mut lines = vec![format!("$ {}", field("command")?)];
+            if let Some(description) = field("description") {
+                lines.insert(0, format!("# {}", description));
+            }
+            lines
+        }
+        "Edit" => {
+            let mut lines = vec![field("file_path")?.to_string()];
+            push_diff(
+                &mut lines,
+                field("old_string").unwrap_or(""),
+                field("new_string").unwrap_or(""),
+            );
+            lines
+        }
+        "MultiEdit" => {
+            let mut lines = vec![field("file_path")?.to_string()];
+            for edit in input
+                .get("edits")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                push_diff(
+                    &mut lines,
+                    edit.get("old_string").and_then(Value::as_str).unwrap_or(""),
+                    edit.get("new_string").and_then(Value::as_str).unwrap_or(""),
+                );
+            }
+            lines
+        }
+        "Write" => {
+            let mut lines = vec![field("file_path")?.to_string()];
+            push_diff(&mut lines, "", field("content").unwrap_or(""));
+            lines
+        }
+        "ApplyPatch" => {
+            let mut lines: Vec<String> = input
+                .get("files")?
+                .as_array()?
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect();
+            if let Some(description) = field("description") {
+                lines.insert(0, format!("# {}", description));
+            }
+            lines
+        }
+        _ => {
+            if input.is_null() {
+                return None;
+            }
+            match ["file_path", "path", "url", "pattern", "query"]
+                .iter()
+                .find_map(|key| field(key))
+            {
+                Some(value) => vec![value.to_string()],
+                None => serde_json::to_string_pretty(input)
+                    .ok()?
+                    .lines()
+                    .map(str::to_string)
+                    .collect(),
+            }
+        }
+    };
+    Some(truncate_lines(lines))
+}
+
+fn push_diff(lines: &mut Vec<String>, old: &str, new: &str) {
+    lines.extend(old.lines().map(|line| format!("- {}", line)));
+    lines.extend(new.lines().map(|line| format!("+ {}", line)));
+}
+
+fn truncate_lines(mut lines: Vec<String>) -> String {
+    if lines.len() > MAX_PREVIEW_LINES {
+        let hidden = lines.len() - MAX_PREVIEW_LINES;
+        lines.truncate(MAX_PREVIEW_LINES);
+        lines.push(format!("… {} more lines", hidden));
+    }
+    lines.join("\n")
+}
+
+/// `can_use_tool` response for an approval card answer
+pub fn permission_response(
+    request: &ControlRequestEvent,
+    choice: ToolApprovalChoice,
+    reason: Option<&str>,
+) -> Value {
+    let mut response = if choice.is_allow() {
+        json!({ "behavior": "allow", "updatedInput": request.input })
+    } else {
+        let message = match reason.map(str::trim).filter(|reason| !reason.is_empty()) {
+            Some(reason) => format!("The user denied this tool call: {}", reason),
+            None => "The user denied this tool call.".to_string(),
+        };
+        json!({ "behavior": "deny", "message": message })
+    };
+    if let Some(tool_use_id) = &request.tool_use_id {
+        response["toolUseID"] = json!(tool_use_id);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(tool_name: &str, input: Value) -> ControlRequestEvent {
+        ControlRequestEvent {
+            request_id: "req-1".to_string(),
+            tool_name: tool_name.to_string(),
+            tool_use_id: Some("toolu_1".to_string()),
+            input,
+        }
+    }
+
+    #[test]
+    fn test_choice_round_trip() {
+        for choice in ToolApprovalChoice::ALL {
+            assert_eq!(choice.as_str().parse::<ToolApprovalChoice>(), Ok(choice));
+        }
+        assert!("maybe".parse::<ToolApprovalChoice>().is_err());
+    }
+
+    #[test]
+    fn test_codex_is_not_offered_always_allow() {
+        assert!(ToolApprovalChoice::AllowAlways.is_offered(AgentType::Claude));
+        assert!(!ToolApprovalChoice::AllowAlways.is_offered(AgentType::Codex));
+        assert!(ToolApprovalChoice::AllowSession.is_offered(AgentType::Codex));
+    }
+
+    #[test]
+    fn test_should_auto_allow_follows_policy() {
+        let allowed = vec!["Bash".to_string()];
+        assert!(should_auto_allow(ApprovalPolicy::Ask, &allowed, "Bash"));
+        assert!(!should_auto_allow(ApprovalPolicy::Ask, &allowed, "Read"));
+        assert!(should_auto_allow(ApprovalPolicy::AllowReads, &[], "Grep"));
+        assert!(!should_auto_allow(ApprovalPolicy::AllowReads, &[], "Write"));
+        assert!(should_auto_allow(ApprovalPolicy::AllowAll, &[], "Write"));
+    }
+
+    #[test]
+    fn test_preview_shows_command_and_diff() {
+        let bash = json!({ "command": "cargo test", "description": "Run tests" });
+        assert_eq!(preview("Bash", &bash).unwrap(), "# Run tests\n$ cargo test");
+
+        let edit = json!({
+            "file_path": "src/lib.rs",
+            "old_string": "let a = 1;",
+            "new_string": "let a = 2;\nlet b = 3;",
+        });
+        assert_eq!(
+            preview("Edit", &edit).unwrap(),
+            "src/lib.rs\n- let a = 1;\n+ let a = 2;\n+ let b = 3;"
+        );
+
+        let content = (0..100)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let write = json!({ "file_path": "big.txt", "content": content });
+        let preview = preview("Write", &write).unwrap();
+        assert_eq!(preview.lines().count(), MAX_PREVIEW_LINES + 1);
+        assert!(preview.ends_with("… 61 more lines"));
+    }
+
+    #[test]
+    fn test_permission_response_payloads() {
+        let request = request("Bash", json!({ "command": "ls" }));
+        let allow = permission_response(&request, ToolApprovalChoice::AllowSession, None);
+        assert_eq!(allow["behavior"], "allow");
+        assert_eq!(allow["updatedInput"]["command"], "ls");
+        assert_eq!(allow["toolUseID"], "toolu_1");
+
+        let deny = permission_response(&request, ToolApprovalChoice::Deny, Some(" wrong dir "));
+        assert_eq!(deny["behavior"], "deny");
+        assert_eq!(deny["message"], "The user denied this tool call: wrong dir");
+    }
+}
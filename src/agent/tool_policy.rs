@@ -0,0 +1,565 @@
+//! Declarative tool permission policy
+//!
+//! A policy is a list of allow and deny rules written as `Tool` or
+//! `Tool(pattern)`, for example `Bash(cargo test*)` or `Edit(!src/**)`. Rules
+//! come from the `[policy]` section of the global config and from
+//! `.conduit/policy.toml` in a repository. Each runner applies them through its
+//! own permission mechanism: Claude tool flags and `can_use_tool` answers,
+//! Gemini/ACP permission answers, and the Codex sandbox and approval answers.
+//!
+//! Patterns are matched against the command for `Bash` and against the path
+//! (relative to the working directory) for file tools. `*` matches any run of
+//! characters, `?` a single one, and a leading `!` inverts the match. Deny
+//! rules win over allow rules; calls matching neither fall back to the
+//! approval policy.
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::agent::runner::{CodexApprovalMode, CodexSandboxMode};
+
+/// Policy file looked up in a repository's main checkout
+pub const POLICY_FILE: &str = ".conduit/policy.toml";
+
+/// Tools that change files; an `Edit` rule covers all of them
+const WRITE_TOOLS: [&str; 4] = ["Edit", "MultiEdit", "Write", "NotebookEdit"];
+
+/// Input fields holding a path, in lookup order
+const PATH_FIELDS: [&str; 4] = ["file_path", "notebook_path", "absolute_path", "path"];
+
+/// Shell operators that chain several commands into one `Bash` call
+const COMMAND_SEPARATORS: [&str; 6] = ["&&", "||", ";", "|", "\n", "$("];
+
+/// Shells whose `-c` script is matched in place of the wrapper, as in
+/// `bash -lc "git push"`
+const SHELL_WRAPPERS: [&str; 3] = ["bash", "sh", "zsh"];
+
+/// On-disk shape of a policy, shared by `.conduit/policy.toml` and `[policy]`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlToolPolicy {
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+    pub codex: Option<TomlCodexPolicy>,
+}
+
+/// Codex settings that have no rule equivalent
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlCodexPolicy {
+    pub approval_policy: Option<CodexApprovalMode>,
+    pub sandbox_mode: Option<CodexSandboxMode>,
+}
+
+/// One allow or deny rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolRule {
+    tool: String,
+    pattern: Option<String>,
+    negated: bool,
+}
+
+impl ToolRule {
+    /// Whether the rule names this tool (`Edit` also names every write tool)
+    fn covers(&self, tool_name: &str) -> bool {
+        self.tool == tool_name || (self.tool == "Edit" && WRITE_TOOLS.contains(&tool_name))
+    }
+
+    fn matches(&self, tool_name: &str, subject: Option<&str>) -> bool {
+        if !self.covers(tool_name) {
+            return false;
+        }
+        let Some(pattern) = &self.pattern else {
+            return true;
+        };
+        subject.is_some_and(|subject| wildcard_match(pattern, subject) != self.negated)
+    }
+
+    /// The rule in Claude's `--allowedTools` syntax, when Claude can express it
+    fn claude_syntax(&self) -> Option<String> {
+        let Some(pattern) = &self.pattern else {
+            return Some(self.tool.clone());
+        };
+        if self.negated {
+            return None;
+        }
+        if self.tool != "Bash" {
+            return Some(format!("{}({})", self.tool, pattern));
+        }
+        // Claude only understands exact commands and `prefix:*`
+        let prefix = pattern.strip_suffix('*').unwrap_or(pattern);
+        if prefix.contains(['*', '?']) {
+            return None;
+        }
+        if prefix.len() == pattern.len() {
+            Some(format!("Bash({})", pattern))
+        } else {
+            Some(format!("Bash({}:*)", prefix.trim_end()))
+        }
+    }
+}
+
+impl FromStr for ToolRule {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let (tool, pattern) = match value.split_once('(') {
+            Some((tool, rest)) => {
+                let pattern = rest
+                    .strip_suffix(')')
+                    .ok_or_else(|| format!("Missing ')' in rule '{}'", value))?;
+                (tool.trim(), Some(pattern.trim()))
+            }
+            None => (value, None),
+        };
+        if tool.is_empty()
+            || !tool
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(format!("Invalid tool name in rule '{}'", value));
+        }
+        let (pattern, negated) = match pattern {
+            Some(pattern) => {
+                let (pattern, negated) = match pattern.strip_prefix('!') {
+                    Some(pattern) => (pattern, true),
+                    None => (pattern, false),
+                };
+                // Accept Claude's `prefix:*` form as well as `prefix*`
+                let pattern = match pattern.strip_suffix(":*") {
+                    Some(prefix) => format!("{}*", prefix),
+                    None => pattern.to_string(),
+                };
+                if pattern.is_empty() {
+                    return Err(format!("Empty pattern in rule '{}'", value));
+                }
+                (Some(pattern), negated)
+            }
+            None => (None, false),
+        };
+        Ok(ToolRule {
+            tool: tool.to_string(),
+            pattern,
+            negated,
+        })
+    }
+}
+
+impl fmt::Display for ToolRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.pattern {
+            Some(pattern) if self.negated => write!(f, "{}(!{})", self.tool, pattern),
+            Some(pattern) => write!(f, "{}({})", self.tool, pattern),
+            None => f.write_str(&self.tool),
+        }
+    }
+}
+
+/// Outcome of checking a tool call against the policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyDecision {
+    Allow,
+    /// Denied by the rule shown
+    Deny(String),
+}
+
+impl PolicyDecision {
+    /// Message returned to the agent for a denied call
+    pub fn deny_message(rule: &str) -> String {
+        format!("Blocked by repository policy (deny rule {})", rule)
+    }
+}
+
+/// Allow and deny rules for one repository
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolPolicy {
+    allow: Vec<ToolRule>,
+    deny: Vec<ToolRule>,
+    codex_approval_policy: Option<CodexApprovalMode>,
+    codex_sandbox_mode: Option<CodexSandboxMode>,
+}
+
+impl ToolPolicy {
+    /// Build a policy from its TOML form; invalid rules are skipped with a warning
+    pub fn from_toml(policy: TomlToolPolicy, source: &str) -> Self {
+        let parse = |rules: Vec<String>| -> Vec<ToolRule> {
+            rules
+                .iter()
+                .filter_map(|rule| match rule.parse::<ToolRule>() {
+                    Ok(rule) => Some(rule),
+                    Err(err) => {
+                        tracing::warn!(source, "Ignoring policy rule: {}", err);
+                        None
+                    }
+                })
+                .collect()
+        };
+        let codex = policy.codex.unwrap_or_default();
+        Self {
+            allow: parse(policy.allow),
+            deny: parse(policy.deny),
+            codex_approval_policy: codex.approval_policy,
+            codex_sandbox_mode: codex.sandbox_mode,
+        }
+    }
+
+    /// Load `.conduit/policy.toml` from a repository checkout, if present
+    pub fn load(repo_root: &Path) -> Result<Option<Self>, String> {
+        let path = repo_root.join(POLICY_FILE);
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(format!("Failed to read {}: {}", path.display(), err)),
+        };
+        let policy: TomlToolPolicy = toml::from_str(&contents)
+            .map_err(|err| format!("Failed to parse {}: {}", path.display(), err))?;
+        Ok(Some(Self::from_toml(policy, &path.display().to_string())))
+    }
+
+    /// Combine two policies: rules add up, `other`'s Codex settings win
+    pub fn merge(mut self, other: ToolPolicy) -> Self {
+        self.allow.extend(other.allow);
+        self.deny.extend(other.deny);
+        if other.codex_approval_policy.is_some() {
+            self.codex_approval_policy = other.codex_approval_policy;
+        }
+        if other.codex_sandbox_mode.is_some() {
+            self.codex_sandbox_mode = other.codex_sandbox_mode;
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty()
+            && self.deny.is_empty()
+            && self.codex_approval_policy.is_none()
+            && self.codex_sandbox_mode.is_none()
+    }
+
+    pub fn has_deny_rules(&self) -> bool {
+        !self.deny.is_empty()
+    }
+
+    /// Whether any deny rule limits file changes
+    pub fn restricts_writes(&self) -> bool {
+        WRITE_TOOLS
+            .iter()
+            .any(|tool| self.deny.iter().any(|rule| rule.covers(tool)))
+    }
+
+    /// Check a tool call; `None` when no rule applies
+    pub fn decide(
+        &self,
+        tool_name: &str,
+        input: &Value,
+        working_dir: &Path,
+    ) -> Option<PolicyDecision> {
+        if tool_name == "Bash" {
+            return self.decide_command(input_command(input)?.as_str());
+        }
+        let subject = input_path(input, working_dir).or_else(|| {
+            ["url", "pattern", "query"]
+                .iter()
+                .find_map(|key| input.get(key).and_then(Value::as_str))
+                .map(str::to_string)
+        });
+        if let Some(rule) = self
+            .deny
+            .iter()
+            .find(|rule| rule.matches(tool_name, subject.as_deref()))
+        {
+            return Some(PolicyDecision::Deny(rule.to_string()));
+        }
+        self.allow
+            .iter()
+            .any(|rule| rule.matches(tool_name, subject.as_deref()))
+            .then_some(PolicyDecision::Allow)
+    }
+
+    /// Check a shell command. Deny rules see every chained command; allow
+    /// rules with a pattern only apply to a single, unchained command.
+    pub fn decide_command(&self, command: &str) -> Option<PolicyDecision> {
+        let command = command.trim();
+        let segments = command_segments(command);
+        for rule in &self.deny {
+            let denied = std::iter::once(command)
+                .chain(segments.iter().copied())
+                .any(|segment| rule.matches("Bash", Some(segment)));
+            if denied {
+                return Some(PolicyDecision::Deny(rule.to_string()));
+            }
+        }
+        let chained = segments.len() > 1;
+        self.allow
+            .iter()
+            .any(|rule| rule.matches("Bash", Some(command)) && (rule.pattern.is_none() || !chained))
+            .then_some(PolicyDecision::Allow)
+    }
+
+    /// Claude `--allowedTools` entries: `base` plus the allow rules Claude can
+    /// express. Bare tools that a deny rule Claude cannot express would
+    /// restrict are dropped so those calls still reach `can_use_tool`.
+    pub fn claude_allowed_tools(&self, base: Vec<String>) -> Vec<String> {
+        let mut tools = base;
+        tools.extend(self.allow.iter().filter_map(ToolRule::claude_syntax));
+        let guarded: Vec<&ToolRule> = self
+            .deny
+            .iter()
+            .filter(|rule| rule.claude_syntax().is_none())
+            .collect();
+        tools.retain(|tool| !guarded.iter().any(|rule| rule.covers(tool)));
+        tools
+    }
+
+    /// Claude `--disallowedTools` entries for the deny rules Claude can express
+    pub fn claude_disallowed_tools(&self) -> Vec<String> {
+        self.deny
+            .iter()
+            .filter_map(ToolRule::claude_syntax)
+            .collect()
+    }
+
+    /// Codex approval policy set in the policy file
+    pub fn codex_approval_policy(&self) -> Option<CodexApprovalMode> {
+        self.codex_approval_policy
+    }
+
+    /// Codex sandbox mode set in the policy file
+    pub fn codex_sandbox_mode(&self) -> Option<CodexSandboxMode> {
+        self.codex_sandbox_mode
+    }
+}
+
+/// Command of a shell tool call, from a string or an argv array
+fn input_command(input: &Value) -> Option<String> {
+    match input.get("command")? {
+        Value::String(command) => Some(command.clone()),
+        Value::Array(args) => Some(argv_command(
+            &args.iter().filter_map(Value::as_str).collect::<Vec<_>>(),
+        )),
+        _ => None,
+    }
+}
+
+/// Command an argv runs: the script of a shell wrapper such as
+/// `bash -lc <script>`, otherwise the arguments quoted for a shell
+pub fn argv_command<S: AsRef<str>>(args: &[S]) -> String {
+    wrapped_script(args)
+        .map(str::to_string)
+        .unwrap_or_else(|| shell_join(args))
+}
+
+/// Join arguments into one shell command line, quoting where needed
+pub fn shell_join<S: AsRef<str>>(args: &[S]) -> String {
+    args.iter()
+        .map(|arg| shell_quote(arg.as_ref()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Script passed to `bash`, `sh` or `zsh` with `-c` (or `-lc` and the like)
+fn wrapped_script<S: AsRef<str>>(args: &[S]) -> Option<&str> {
+    let (program, rest) = args.split_first()?;
+    let program = Path::new(program.as_ref()).file_name()?.to_str()?;
+    if !SHELL_WRAPPERS.contains(&program) {
+        return None;
+    }
+    let mut rest = rest.iter().map(|arg| arg.as_ref());
+    loop {
+        let flag = rest.next()?;
+        if flag.starts_with("--") {
+            continue;
+        }
+        if flag.strip_prefix('-')?.contains('c') {
+            return rest.next();
+        }
+    }
+}
+
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || "-_./=:@%+,".contains(ch));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Path of a file tool call, relative to the working directory when inside it
+fn input_path(input: &Value, working_dir: &Path) -> Option<String> {
+    let path = PATH_FIELDS
+        .iter()
+        .find_map(|key| input.get(key).and_then(Value::as_str))?;
+    Some(
+        Path::new(path)
+            .strip_prefix(working_dir)
+            .map(|relative| relative.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| path.to_string()),
+    )
+}
+
+/// Split a shell command on chaining operators
+fn command_segments(command: &str) -> Vec<&str> {
+    let mut segments = vec![command];
+    for separator in COMMAND_SEPARATORS {
+        segments = segments
+            .into_iter()
+            .flat_map(|segment| segment.split(separator))
+            .collect();
+    }
+    segments
+        .into_iter()
+        .map(|segment| segment.trim().trim_end_matches(')').trim())
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// Match `text` against a pattern where `*` is any run and `?` any character
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn policy(allow: &[&str], deny: &[&str]) -> ToolPolicy {
+        ToolPolicy::from_toml(
+            TomlToolPolicy {
+                allow: allow.iter().map(|rule| rule.to_string()).collect(),
+                deny: deny.iter().map(|rule| rule.to_string()).collect(),
+                codex: None,
+            },
+            "test",
+        )
+    }
+
+    #[test]
+    fn test_rule_parsing() {
+        let rule: ToolRule = "Bash(cargo test:*)".parse().unwrap();
+        assert_eq!(rule.to_string(), "Bash(cargo test*)");
+        let rule: ToolRule = "Edit(!src/**)".parse().unwrap();
+        assert!(rule.negated);
+        assert_eq!(rule.to_string(), "Edit(!src/**)");
+        assert!("Bash(cargo test".parse::<ToolRule>().is_err());
+        assert!("(foo)".parse::<ToolRule>().is_err());
+        assert!("Bash()".parse::<ToolRule>().is_err());
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("cargo test*", "cargo test --workspace"));
+        assert!(wildcard_match("src/**", "src/agent/mod.rs"));
+        assert!(wildcard_match("*.rs", "main.rs"));
+        assert!(wildcard_match("a?c", "abc"));
+        assert!(!wildcard_match("src/**", "tests/lib.rs"));
+        assert!(!wildcard_match("cargo test*", "cargo build"));
+    }
+
+    #[test]
+    fn test_commands_deny_wins_and_chains_are_checked() {
+        let policy = policy(&["Bash(cargo test*)", "Bash(git *)"], &["Bash(git push*)"]);
+        assert_eq!(
+            policy.decide_command("cargo test -p conduit"),
+            Some(PolicyDecision::Allow)
+        );
+        assert_eq!(
+            policy.decide_command("git push origin main"),
+            Some(PolicyDecision::Deny("Bash(git push*)".to_string()))
+        );
+        assert_eq!(
+            policy.decide_command("cargo test && git push"),
+            Some(PolicyDecision::Deny("Bash(git push*)".to_string()))
+        );
+        // Chained commands never ride on a pattern allow rule
+        assert_eq!(policy.decide_command("cargo test; rm -rf target"), None);
+        assert_eq!(policy.decide_command("npm install"), None);
+    }
+
+    #[test]
+    fn test_writes_outside_src_are_denied() {
+        let policy = policy(&[], &["Edit(!src/**)"]);
+        let dir = Path::new("/repo");
+        let inside = json!({ "file_path": "/repo/src/main.rs", "content": "" });
+        let outside = json!({ "file_path": "/repo/Cargo.toml", "content": "" });
+        assert_eq!(policy.decide("Write", &inside, dir), None);
+        assert_eq!(
+            policy.decide("Write", &outside, dir),
+            Some(PolicyDecision::Deny("Edit(!src/**)".to_string()))
+        );
+        assert_eq!(policy.decide("Read", &outside, dir), None);
+        assert!(policy.restricts_writes());
+    }
+
+    #[test]
+    fn test_claude_translation() {
+        let policy = policy(
+            &["Bash(cargo test*)", "Read"],
+            &["Bash(git push*)", "Edit(!src/**)"],
+        );
+        let allowed = policy.claude_allowed_tools(vec![
+            "Bash".to_string(),
+            "Write".to_string(),
+            "Grep".to_string(),
+        ]);
+        assert_eq!(allowed, vec!["Bash", "Grep", "Bash(cargo test:*)", "Read"]);
+        assert_eq!(policy.claude_disallowed_tools(), vec!["Bash(git push:*)"]);
+    }
+
+    #[test]
+    fn test_load_and_merge() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(ToolPolicy::load(dir.path()), Ok(None));
+
+        std::fs::create_dir_all(dir.path().join(".conduit")).unwrap();
+        std::fs::write(
+            dir.path().join(POLICY_FILE),
+            "allow = [\"Bash(cargo test*)\"]\ndeny = [\"Bash(git push*)\"]\n\n[codex]\nsandbox_mode = \"workspace-write\"\n",
+        )
+        .unwrap();
+        let repo = ToolPolicy::load(dir.path()).unwrap().unwrap();
+        let merged = policy(&[], &["Bash(rm -rf*)"]).merge(repo);
+        assert_eq!(merged.deny.len(), 2);
+        assert_eq!(
+            merged.codex_sandbox_mode(),
+            Some(CodexSandboxMode::WorkspaceWrite)
+        );
+        assert!(!merged.is_empty());
+
+        std::fs::write(dir.path().join(POLICY_FILE), "allow = 3").unwrap();
+        assert!(ToolPolicy::load(dir.path()).is_err());
+    }
+}
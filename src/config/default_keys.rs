@@ -78,6 +78,8 @@ pub fn default_keybindings() -> KeybindingConfig {
     bind(&mut config.global, "M-n", Action::NewWorkspaceUnderCursor);
     bind(&mut config.global, "M-q", Action::OpenQueueEditor);
     bind(&mut config.global, "M-e", Action::EditPromptExternal);
+    bind(&mut config.global, "M-S-z", Action::RollbackLastTurn); // Alt+Shift+Z to undo a turn
+    bind(&mut config.global, "M-/", Action::SearchChat);
 
     // Agent mode toggle (Build/Plan) - Ctrl+\
     //
@@ -289,6 +291,19 @@ pub fn default_keybindings() -> KeybindingConfig {
     );
     bind(file_viewer, "q", Action::CloseTab);
 
+    // ========== Diff Review ==========
+    // Scrolling, tab switching and closing behave as in the file viewer
+    let mut diff_viewer = file_viewer.clone();
+    bind(&mut diff_viewer, "n", Action::DiffNextHunk);
+    bind(&mut diff_viewer, "]", Action::DiffNextHunk);
+    bind(&mut diff_viewer, "p", Action::DiffPrevHunk);
+    bind(&mut diff_viewer, "[", Action::DiffPrevHunk);
+    bind(&mut diff_viewer, "s", Action::DiffStageHunk);
+    bind(&mut diff_viewer, "x", Action::DiffRevertHunk);
+    bind(&mut diff_viewer, "c", Action::DiffCommentHunk);
+    bind(&mut diff_viewer, "r", Action::DiffRefresh);
+    config.context.insert(KeyContext::DiffViewer, diff_viewer);
+
     // ========== Queue Editing Mode ==========
     let queue = config.context.entry(KeyContext::QueueEditing).or_default();
 
@@ -372,6 +387,7 @@ pub fn default_keybindings() -> KeybindingConfig {
     );
     bind(sidebar, "r", Action::AddRepository);
     bind(sidebar, "s", Action::OpenSettings);
+    bind(sidebar, "t", Action::ShowCheckpoints);
     bind(sidebar, "x", Action::ArchiveOrRemove);
 
     // ========== Dialog Context ==========
@@ -524,6 +540,54 @@ pub fn default_keybindings() -> KeybindingConfig {
         Action::MoveCursorEnd,
     );
 
+    // ========== Chat Search Bar ==========
+    let chat_search = config.context.entry(KeyContext::ChatSearch).or_default();
+
+    chat_search.insert(
+        KeyCombo::new(KeyCode::Enter, KeyModifiers::NONE),
+        Action::SearchNext,
+    );
+    chat_search.insert(
+        KeyCombo::new(KeyCode::Enter, KeyModifiers::SHIFT),
+        Action::SearchPrev,
+    );
+    chat_search.insert(
+        KeyCombo::new(KeyCode::Down, KeyModifiers::NONE),
+        Action::SearchNext,
+    );
+    chat_search.insert(
+        KeyCombo::new(KeyCode::Up, KeyModifiers::NONE),
+        Action::SearchPrev,
+    );
+    chat_search.insert(
+        KeyCombo::new(KeyCode::Esc, KeyModifiers::NONE),
+        Action::Cancel,
+    );
+    chat_search.insert(
+        KeyCombo::new(KeyCode::Backspace, KeyModifiers::NONE),
+        Action::Backspace,
+    );
+    chat_search.insert(
+        KeyCombo::new(KeyCode::Delete, KeyModifiers::NONE),
+        Action::Delete,
+    );
+    chat_search.insert(
+        KeyCombo::new(KeyCode::Left, KeyModifiers::NONE),
+        Action::MoveCursorLeft,
+    );
+    chat_search.insert(
+        KeyCombo::new(KeyCode::Right, KeyModifiers::NONE),
+        Action::MoveCursorRight,
+    );
+    chat_search.insert(
+        KeyCombo::new(KeyCode::Home, KeyModifiers::NONE),
+        Action::MoveCursorStart,
+    );
+    chat_search.insert(
+        KeyCombo::new(KeyCode::End, KeyModifiers::NONE),
+        Action::MoveCursorEnd,
+    );
+
     // ========== Base Directory Dialog ==========
     let base_dir = config.context.entry(KeyContext::BaseDir).or_default();
 
@@ -855,4 +919,56 @@ mod tests {
 
         assert!(matches!(context.get(&key_combo), Some(Action::CloseTab)));
     }
+
+    #[test]
+    fn test_diff_viewer_hunk_actions_and_close_tab() {
+        let config = default_keybindings();
+        let context = config
+            .context
+            .get(&KeyContext::DiffViewer)
+            .expect("diff viewer context missing");
+        let parse = |key: &str| -> KeyCombo { key.parse().expect("Should parse key") };
+
+        assert!(matches!(
+            context.get(&parse("n")),
+            Some(Action::DiffNextHunk)
+        ));
+        assert!(matches!(
+            context.get(&parse("s")),
+            Some(Action::DiffStageHunk)
+        ));
+        assert!(matches!(
+            context.get(&parse("x")),
+            Some(Action::DiffRevertHunk)
+        ));
+        assert!(matches!(
+            context.get(&parse("c")),
+            Some(Action::DiffCommentHunk)
+        ));
+        assert!(matches!(context.get(&parse("q")), Some(Action::CloseTab)));
+    }
+
+    #[test]
+    fn test_chat_search_bindings() {
+        let config = default_keybindings();
+        let parse = |key: &str| -> KeyCombo { key.parse().expect("Should parse key") };
+        assert!(matches!(
+            config.global.get(&parse("M-/")),
+            Some(Action::SearchChat)
+        ));
+
+        let context = config
+            .context
+            .get(&KeyContext::ChatSearch)
+            .expect("chat search context missing");
+        assert!(matches!(
+            context.get(&parse("<CR>")),
+            Some(Action::SearchNext)
+        ));
+        assert!(matches!(
+            context.get(&parse("<Up>")),
+            Some(Action::SearchPrev)
+        ));
+        assert!(matches!(context.get(&parse("<Esc>")), Some(Action::Cancel)));
+    }
 }
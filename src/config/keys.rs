@@ -98,6 +98,8 @@ pub enum KeyContext {
     Chat,
     /// File viewer tab mode
     FileViewer,
+    /// Diff review tab mode
+    DiffViewer,
     /// Scrolling through chat history
     Scrolling,
     /// Sidebar navigation
@@ -126,6 +128,8 @@ pub enum KeyContext {
     ThemePicker,
     /// Queue editor (inline)
     QueueEditing,
+    /// Chat transcript search bar
+    ChatSearch,
 }
 
 impl KeyContext {
@@ -134,6 +138,7 @@ impl KeyContext {
         &[
             KeyContext::Chat,
             KeyContext::FileViewer,
+            KeyContext::DiffViewer,
             KeyContext::Scrolling,
             KeyContext::Sidebar,
             KeyContext::Dialog,
@@ -148,6 +153,7 @@ impl KeyContext {
             KeyContext::CommandPalette,
             KeyContext::ThemePicker,
             KeyContext::QueueEditing,
+            KeyContext::ChatSearch,
         ]
     }
 
@@ -166,16 +172,31 @@ impl KeyContext {
             InputMode::AddingRepository => return KeyContext::AddRepository,
             InputMode::SelectingModel => return KeyContext::ModelSelector,
             InputMode::SelectingReasoning => return KeyContext::ModelSelector,
+            InputMode::SelectingCodexPermissions => return KeyContext::ModelSelector,
             InputMode::SelectingProviders => return KeyContext::ModelSelector,
+            InputMode::SelectingRaceContenders => return KeyContext::ModelSelector,
+            InputMode::ShowingCheckpoints => return KeyContext::ModelSelector,
+            InputMode::ShowingSchedules => return KeyContext::ModelSelector,
             InputMode::SettingBaseDir => return KeyContext::BaseDir,
+            // Single-line text entry shares the add-repository bindings
+            InputMode::CommentingHunk => return KeyContext::AddRepository,
+            InputMode::SettingGoal => return KeyContext::AddRepository,
+            InputMode::FillingPromptVariables => return KeyContext::AddRepository,
+            InputMode::SearchingChat => return KeyContext::ChatSearch,
             InputMode::PickingProject => return KeyContext::ProjectPicker,
             InputMode::Confirming => return KeyContext::Dialog,
             InputMode::RemovingProject => return KeyContext::Dialog,
             InputMode::ShowingError => return KeyContext::Dialog,
+            InputMode::ShowingUsage => return KeyContext::Dialog,
+            InputMode::ShowingRaceResults => return KeyContext::Dialog,
             InputMode::Command => return KeyContext::Command,
             InputMode::ShowingHelp => return KeyContext::HelpDialog,
             InputMode::ImportingSession => return KeyContext::SessionImport,
-            InputMode::CommandPalette | InputMode::SlashMenu => return KeyContext::CommandPalette,
+            // Type-to-search list, same keys as the palette
+            InputMode::SearchingHistory => return KeyContext::CommandPalette,
+            InputMode::CommandPalette | InputMode::SlashMenu | InputMode::PickingMention => {
+                return KeyContext::CommandPalette
+            }
             InputMode::MissingTool => return KeyContext::Dialog,
             InputMode::SelectingTheme => return KeyContext::ThemePicker,
             InputMode::QueueEditing => return KeyContext::QueueEditing,
@@ -1,10 +1,17 @@
 pub mod default_keys;
 pub mod keys;
 mod settings;
+mod user_commands;
 
 pub use default_keys::default_keybindings;
 pub use keys::{parse_key_notation, KeyCombo, KeyContext, KeyParseError, KeybindingConfig};
 pub use settings::{
     parse_action, save_default_model, save_enabled_providers, save_theme_config, save_tool_path,
-    Config, QueueDelivery, QueueMode, SteerBehavior, SteerFallback, COMMAND_NAMES, EXAMPLE_CONFIG,
+    BudgetAction, BudgetConfig, BudgetLimit, Config, GoalConfig, NotificationEvent,
+    NotificationsConfig, QueueDelivery, QueueMode, RateLimitConfig, SteerBehavior, SteerFallback,
+    TerminalNotification, COMMAND_NAMES, EXAMPLE_CONFIG,
+};
+pub use user_commands::{
+    expand_user_command, find_user_command, CommandOutput, CommandStep, UserCommand,
+    UserCommandKind, USER_COMMAND_PREFIX,
 };
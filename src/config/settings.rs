@@ -1,18 +1,26 @@
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use toml_edit::{Array, DocumentMut, Item, Table};
 
-use crate::agent::{AgentType, ModelRegistry};
+use crate::agent::event_hooks::TomlEventHooks;
+use crate::agent::tool_policy::TomlToolPolicy;
+use crate::agent::{
+    AcpAgentConfig, AgentType, ApprovalPolicy, EventHooks, ModelPricing, ModelRegistry,
+    RestartPolicy, TokenUsage, ToolPolicy,
+};
 use crate::git::WorkspaceMode;
+use crate::session::TranscriptFormat;
 use crate::ui::action::Action;
 use crate::util::paths::config_path;
 use crate::util::tools::{Tool, ToolAvailability, ToolPaths};
 
 use super::default_keys::default_keybindings;
 use super::keys::{parse_key_notation, KeyContext, KeybindingConfig};
+use super::user_commands::{TomlUserCommand, UserCommand, USER_COMMAND_PREFIX};
 
 /// Example configuration file contents (bundled with the binary)
 pub const EXAMPLE_CONFIG: &str = include_str!("config.toml.example");
@@ -60,6 +68,26 @@ pub struct Config {
     pub web_status: WebStatusConfig,
     /// Workspace defaults
     pub workspaces: WorkspacesConfig,
+    /// Tool permission defaults
+    pub permissions: PermissionsConfig,
+    /// Allow and deny rules applied in every repository
+    pub tool_policy: ToolPolicy,
+    /// Generic ACP agent (None = not configured)
+    pub acp_agent: Option<AcpAgentConfig>,
+    /// Spend limits per session, repository and day
+    pub budget: BudgetConfig,
+    /// Automatic restart of crashed agent processes
+    pub supervisor: RestartPolicy,
+    /// Retries and fallback after rate limits and overloads
+    pub rate_limit: RateLimitConfig,
+    /// Scripts run on agent events in every repository
+    pub event_hooks: EventHooks,
+    /// Alerts when an agent finishes, fails or waits for input
+    pub notifications: NotificationsConfig,
+    /// Limits for goal loops started with /goal
+    pub goal: GoalConfig,
+    /// Slash commands defined under `[[commands]]`
+    pub user_commands: Vec<UserCommand>,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
@@ -167,6 +195,250 @@ pub struct TomlWorkspacesConfig {
     pub archive_remote_prompt: Option<bool>,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct PermissionsConfig {
+    pub approval_policy: ApprovalPolicy,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlPermissionsConfig {
+    pub approval_policy: Option<ApprovalPolicy>,
+}
+
+/// What happens once a hard budget limit is reached
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BudgetAction {
+    /// Let the running turn finish but refuse to submit further prompts
+    #[default]
+    Block,
+    /// Also interrupt the running turn as soon as the limit is crossed
+    Interrupt,
+}
+
+/// A hard limit expressed in tokens, estimated dollars, or both
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+pub struct BudgetLimit {
+    pub tokens: Option<i64>,
+    pub usd: Option<f64>,
+}
+
+impl BudgetLimit {
+    pub fn is_set(&self) -> bool {
+        self.tokens.is_some() || self.usd.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BudgetConfig {
+    /// Lifetime limit for a single session
+    pub session: BudgetLimit,
+    /// Limit per repository for the current calendar month
+    pub repository: BudgetLimit,
+    /// Limit across all sessions since local midnight
+    pub daily: BudgetLimit,
+    /// Soft threshold, as a percentage of each hard limit
+    pub warn_at_percent: u8,
+    pub on_hard_limit: BudgetAction,
+}
+
+impl BudgetConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.session.is_set() || self.repository.is_set() || self.daily.is_set()
+    }
+
+    /// Whether running turns are interrupted once a hard limit is crossed
+    pub fn interrupts_running_turn(&self) -> bool {
+        self.is_enabled() && self.on_hard_limit == BudgetAction::Interrupt
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlBudgetConfig {
+    pub session: Option<BudgetLimit>,
+    pub repository: Option<BudgetLimit>,
+    pub daily: Option<BudgetLimit>,
+    pub warn_at_percent: Option<u8>,
+    pub on_hard_limit: Option<BudgetAction>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    /// Send the last prompt again once a rate limit or overload clears
+    pub auto_retry: bool,
+    /// Retries per prompt before giving up
+    pub max_retries: u32,
+    /// Wait before the first retry when the error gives no retry-after
+    pub initial_backoff: Duration,
+    /// Longest wait that is retried automatically
+    pub max_wait: Duration,
+    /// Agent offered to take over the conversation while limited
+    pub fallback_agent: Option<AgentType>,
+    /// Model for the fallback agent (None = its default model)
+    pub fallback_model: Option<String>,
+}
+
+impl RateLimitConfig {
+    /// Wait before retry number `attempt` (1-based): the provider's
+    /// retry-after when given, else exponential backoff. `None` when the
+    /// retry should not happen automatically.
+    pub fn retry_delay(&self, retry_after: Option<Duration>, attempt: u32) -> Option<Duration> {
+        if !self.auto_retry || attempt == 0 || attempt > self.max_retries {
+            return None;
+        }
+        let delay = retry_after.unwrap_or_else(|| {
+            let factor = 1u32.checked_shl(attempt - 1).unwrap_or(u32::MAX);
+            self.initial_backoff
+                .saturating_mul(factor)
+                .min(self.max_wait)
+        });
+        (delay <= self.max_wait).then_some(delay)
+    }
+}
+
+/// Agent moments worth a notification
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    TurnCompleted,
+    TurnFailed,
+    /// Waiting on a question, plan approval or tool approval
+    InputNeeded,
+}
+
+/// How the terminal is told about a notification
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TerminalNotification {
+    #[default]
+    Off,
+    /// The terminal bell
+    Bell,
+    /// An OSC 9 desktop notification (iTerm2, WezTerm, kitty, Ghostty)
+    Osc9,
+}
+
+#[derive(Debug, Clone)]
+pub struct NotificationsConfig {
+    /// Events that notify
+    pub events: Vec<NotificationEvent>,
+    /// Only notify for tabs you aren't looking at (in the terminal UI)
+    pub background_only: bool,
+    pub terminal: TerminalNotification,
+    /// Command run per notification, e.g. `["notify-send", "{title}", "{summary}"]`
+    pub command: Option<Vec<String>>,
+    /// URL the notification is POSTed to as JSON
+    pub webhook_url: Option<String>,
+}
+
+impl NotificationsConfig {
+    /// Whether an event notifies through at least one sink
+    pub fn notifies(&self, event: NotificationEvent) -> bool {
+        self.events.contains(&event)
+            && (self.terminal != TerminalNotification::Off
+                || self.command.is_some()
+                || self.webhook_url.is_some())
+    }
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            events: vec![
+                NotificationEvent::TurnCompleted,
+                NotificationEvent::TurnFailed,
+                NotificationEvent::InputNeeded,
+            ],
+            background_only: true,
+            terminal: TerminalNotification::Off,
+            command: None,
+            webhook_url: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlNotificationsConfig {
+    pub events: Option<Vec<NotificationEvent>>,
+    pub background_only: Option<bool>,
+    pub terminal: Option<TerminalNotification>,
+    pub command: Option<Vec<String>>,
+    pub webhook_url: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GoalConfig {
+    /// Agent turns before a goal loop gives up
+    pub max_iterations: u32,
+    /// Spend before a goal loop gives up (None = no limit)
+    pub max_cost_usd: Option<f64>,
+    /// Longest a verification command may run
+    pub check_timeout: Duration,
+}
+
+impl Default for GoalConfig {
+    fn default() -> Self {
+        Self {
+            max_iterations: 5,
+            max_cost_usd: None,
+            check_timeout: Duration::from_secs(600),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlGoalConfig {
+    pub max_iterations: Option<u32>,
+    pub max_cost_usd: Option<f64>,
+    pub check_timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlRateLimitConfig {
+    pub auto_retry: Option<bool>,
+    pub max_retries: Option<u32>,
+    pub initial_backoff_secs: Option<u64>,
+    pub max_wait_secs: Option<u64>,
+    pub fallback_agent: Option<AgentType>,
+    pub fallback_model: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlSupervisorConfig {
+    pub enabled: Option<bool>,
+    pub max_restarts: Option<u32>,
+    pub initial_backoff_ms: Option<u64>,
+    pub max_backoff_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlAcpAgentConfig {
+    pub command: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub env: Option<HashMap<String, String>>,
+    pub display_name: Option<String>,
+    pub auth_method: Option<String>,
+    pub read_files: Option<bool>,
+}
+
+impl TomlAcpAgentConfig {
+    /// Build the runtime config; `None` when no command is set.
+    fn into_config(self) -> Option<AcpAgentConfig> {
+        let command = self.command.filter(|command| !command.trim().is_empty())?;
+        let mut config = AcpAgentConfig::new(command);
+        config.args = self.args.unwrap_or_default();
+        config.env = self.env.unwrap_or_default();
+        if let Some(name) = self.display_name.filter(|name| !name.trim().is_empty()) {
+            config.display_name = name;
+        }
+        config.auth_method = self.auth_method;
+        if let Some(read_files) = self.read_files {
+            config.read_files = read_files;
+        }
+        Some(config)
+    }
+}
+
 /// TOML representation of default model
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct TomlDefaultModelConfig {
@@ -198,7 +470,7 @@ impl Default for Config {
                 "Glob".into(),
                 "Grep".into(),
             ],
-            // Claude Sonnet 3.5 pricing
+            // Fallback for Claude models without list pricing
             claude_input_cost_per_million: 3.0,
             claude_output_cost_per_million: 15.0,
             keybindings: default_keybindings(),
@@ -231,6 +503,31 @@ impl Default for Config {
                 archive_delete_branch: true,
                 archive_remote_prompt: true,
             },
+            permissions: PermissionsConfig {
+                approval_policy: ApprovalPolicy::Ask,
+            },
+            tool_policy: ToolPolicy::default(),
+            acp_agent: None,
+            budget: BudgetConfig {
+                session: BudgetLimit::default(),
+                repository: BudgetLimit::default(),
+                daily: BudgetLimit::default(),
+                warn_at_percent: 80,
+                on_hard_limit: BudgetAction::Block,
+            },
+            supervisor: RestartPolicy::default(),
+            rate_limit: RateLimitConfig {
+                auto_retry: true,
+                max_retries: 3,
+                initial_backoff: Duration::from_secs(30),
+                max_wait: Duration::from_secs(600),
+                fallback_agent: None,
+                fallback_model: None,
+            },
+            event_hooks: EventHooks::default(),
+            notifications: NotificationsConfig::default(),
+            goal: GoalConfig::default(),
+            user_commands: Vec::new(),
         }
     }
 }
@@ -307,6 +604,26 @@ pub struct TomlConfig {
     pub web_status: Option<TomlWebStatusConfig>,
     /// Workspace defaults
     pub workspaces: Option<TomlWorkspacesConfig>,
+    /// Tool permission defaults
+    pub permissions: Option<TomlPermissionsConfig>,
+    /// Global allow and deny rules
+    pub policy: Option<TomlToolPolicy>,
+    /// Generic ACP agent
+    pub acp_agent: Option<TomlAcpAgentConfig>,
+    /// Budget limits
+    pub budget: Option<TomlBudgetConfig>,
+    /// Crashed agent restarts
+    pub supervisor: Option<TomlSupervisorConfig>,
+    /// Rate limit retries and fallback
+    pub rate_limit: Option<TomlRateLimitConfig>,
+    /// Agent event hooks
+    pub hooks: Option<TomlEventHooks>,
+    /// Attention notifications
+    pub notifications: Option<TomlNotificationsConfig>,
+    /// Goal loop limits
+    pub goal: Option<TomlGoalConfig>,
+    /// User-defined slash commands
+    pub commands: Option<Vec<TomlUserCommand>>,
 }
 
 impl TomlKeybindings {
@@ -393,6 +710,10 @@ fn parse_context_bindings(
 
 /// Parse an action name string into an Action
 pub fn parse_action(name: &str) -> Option<Action> {
+    if let Some(command) = name.strip_prefix(USER_COMMAND_PREFIX) {
+        let command = command.trim().trim_start_matches('/');
+        return (!command.is_empty()).then(|| Action::RunCommand(command.to_string()));
+    }
     match name {
         // Global
         "quit" => Some(Action::Quit),
@@ -401,22 +722,58 @@ pub fn parse_action(name: &str) -> Option<Action> {
         "open_pr" => Some(Action::OpenPr),
         "fork_session" => Some(Action::ForkSession),
         "handoff_session" => Some(Action::HandoffSession),
+        "start_race" | "race" => Some(Action::StartRace),
+        "show_race_results" => Some(Action::ShowRaceResults),
         "interrupt_agent" => Some(Action::InterruptAgent),
         "toggle_view_mode" => Some(Action::ToggleViewMode),
         "show_model_selector" => Some(Action::ShowModelSelector),
         "show_reasoning_selector" => Some(Action::ShowReasoningSelector),
+        "show_codex_permissions" => Some(Action::ShowCodexPermissions),
         "show_theme_picker" => Some(Action::ShowThemePicker),
         "show_providers_selector" => Some(Action::ShowProvidersSelector),
         "toggle_metrics" => Some(Action::ToggleMetrics),
         "dump_debug_state" => Some(Action::DumpDebugState),
         "suspend" => Some(Action::Suspend),
         "copy_selection" => Some(Action::CopySelection),
+        "cycle_approval_policy" => Some(Action::CycleApprovalPolicy),
+        "export_transcript_markdown" => Some(Action::ExportTranscript(TranscriptFormat::Markdown)),
+        "export_transcript_html" => Some(Action::ExportTranscript(TranscriptFormat::Html)),
+        "export_transcript_jsonl" => Some(Action::ExportTranscript(TranscriptFormat::Jsonl)),
+        "show_usage" | "usage" => Some(Action::ShowUsageDashboard),
 
         // Tab management
         "close_tab" => Some(Action::CloseTab),
         "next_tab" => Some(Action::NextTab),
         "prev_tab" => Some(Action::PrevTab),
 
+        // Diff review
+        "open_diff_review" | "diff" => Some(Action::OpenDiffReview),
+        "diff_next_hunk" => Some(Action::DiffNextHunk),
+        "diff_prev_hunk" => Some(Action::DiffPrevHunk),
+        "diff_stage_hunk" => Some(Action::DiffStageHunk),
+        "diff_revert_hunk" => Some(Action::DiffRevertHunk),
+        "diff_comment_hunk" => Some(Action::DiffCommentHunk),
+        "diff_refresh" => Some(Action::DiffRefresh),
+
+        // Checkpoints
+        "show_checkpoints" | "checkpoints" => Some(Action::ShowCheckpoints),
+        "rollback_last_turn" | "rollback" => Some(Action::RollbackLastTurn),
+
+        // Schedules
+        "show_schedules" | "schedules" => Some(Action::ShowSchedules),
+
+        // Workspace setup
+        "run_workspace_setup" | "setup" => Some(Action::RunWorkspaceSetup),
+
+        // Goal loop
+        "start_goal" | "goal" => Some(Action::StartGoal),
+
+        // Chat search
+        "search_chat" | "search" => Some(Action::SearchChat),
+        "search_next" => Some(Action::SearchNext),
+        "search_prev" => Some(Action::SearchPrev),
+        "search_history" | "history" => Some(Action::SearchHistory),
+
         // Scrolling
         "scroll_up" => Some(Action::ScrollUp(1)),
         "scroll_down" => Some(Action::ScrollDown(1)),
@@ -514,20 +871,50 @@ pub const COMMAND_NAMES: &[&str] = &[
     "open_pr",
     "fork_session",
     "handoff_session",
+    "start_race",
+    "show_race_results",
     "interrupt_agent",
     "toggle_view_mode",
     "show_model_selector",
     "show_reasoning_selector",
+    "show_codex_permissions",
     "show_theme_picker",
     "show_providers_selector",
     "toggle_metrics",
     "dump_debug_state",
     "suspend",
     "copy_selection",
+    "cycle_approval_policy",
+    "export_transcript_markdown",
+    "export_transcript_html",
+    "export_transcript_jsonl",
+    "show_usage",
     // Tab management
     "close_tab",
     "next_tab",
     "prev_tab",
+    // Diff review
+    "open_diff_review",
+    "diff_next_hunk",
+    "diff_prev_hunk",
+    "diff_stage_hunk",
+    "diff_revert_hunk",
+    "diff_comment_hunk",
+    "diff_refresh",
+    // Checkpoints
+    "show_checkpoints",
+    "rollback_last_turn",
+    // Schedules
+    "show_schedules",
+    // Workspace setup
+    "run_workspace_setup",
+    // Goal loop
+    "start_goal",
+    // Chat search
+    "search_chat",
+    "search_next",
+    "search_prev",
+    "search_history",
     // Scrolling
     "scroll_up",
     "scroll_down",
@@ -614,10 +1001,28 @@ impl Config {
             "codex" => Some(AgentType::Codex),
             "gemini" => Some(AgentType::Gemini),
             "opencode" => Some(AgentType::Opencode),
+            "acp" => Some(AgentType::Acp),
             _ => None,
         }
     }
 
+    /// Whether an agent opens the files mentioned in a prompt itself.
+    /// Only a configured ACP agent may not; its mentions are inlined.
+    pub fn agent_reads_files(&self, agent_type: AgentType) -> bool {
+        match agent_type {
+            AgentType::Acp => self.acp_agent.as_ref().is_none_or(|agent| agent.read_files),
+            _ => true,
+        }
+    }
+
+    /// Name shown for an agent; the ACP agent goes by its configured name
+    pub fn agent_display_name(&self, agent_type: AgentType) -> &str {
+        match (agent_type, &self.acp_agent) {
+            (AgentType::Acp, Some(agent)) => &agent.display_name,
+            _ => agent_type.display_name(),
+        }
+    }
+
     /// Load configuration from file, merging with defaults
     pub fn load() -> Self {
         let mut config = Config::default();
@@ -747,6 +1152,123 @@ impl Config {
                             config.workspaces.archive_remote_prompt = remote_prompt;
                         }
                     }
+                    // Load tool permission defaults
+                    if let Some(permissions) = toml_config.permissions {
+                        if let Some(approval_policy) = permissions.approval_policy {
+                            config.permissions.approval_policy = approval_policy;
+                        }
+                    }
+                    // Load global allow and deny rules
+                    if let Some(policy) = toml_config.policy {
+                        config.tool_policy = ToolPolicy::from_toml(policy, "config [policy]");
+                    }
+
+                    // Load generic ACP agent
+                    if let Some(acp_agent) = toml_config.acp_agent.and_then(|a| a.into_config()) {
+                        config.tool_paths.acp = acp_agent.resolve_binary();
+                        config.acp_agent = Some(acp_agent);
+                    }
+
+                    // Load budget limits
+                    if let Some(budget) = toml_config.budget {
+                        if let Some(session) = budget.session {
+                            config.budget.session = session;
+                        }
+                        if let Some(repository) = budget.repository {
+                            config.budget.repository = repository;
+                        }
+                        if let Some(daily) = budget.daily {
+                            config.budget.daily = daily;
+                        }
+                        if let Some(warn_at_percent) = budget.warn_at_percent {
+                            config.budget.warn_at_percent = warn_at_percent.min(100);
+                        }
+                        if let Some(on_hard_limit) = budget.on_hard_limit {
+                            config.budget.on_hard_limit = on_hard_limit;
+                        }
+                    }
+
+                    // Load crashed agent restart policy
+                    if let Some(supervisor) = toml_config.supervisor {
+                        if let Some(enabled) = supervisor.enabled {
+                            config.supervisor.enabled = enabled;
+                        }
+                        if let Some(max_restarts) = supervisor.max_restarts {
+                            config.supervisor.max_restarts = max_restarts;
+                        }
+                        if let Some(ms) = supervisor.initial_backoff_ms {
+                            config.supervisor.initial_backoff = Duration::from_millis(ms);
+                        }
+                        if let Some(ms) = supervisor.max_backoff_ms {
+                            config.supervisor.max_backoff = Duration::from_millis(ms);
+                        }
+                    }
+
+                    // Load rate limit retries and fallback
+                    if let Some(rate_limit) = toml_config.rate_limit {
+                        if let Some(auto_retry) = rate_limit.auto_retry {
+                            config.rate_limit.auto_retry = auto_retry;
+                        }
+                        if let Some(max_retries) = rate_limit.max_retries {
+                            config.rate_limit.max_retries = max_retries;
+                        }
+                        if let Some(secs) = rate_limit.initial_backoff_secs {
+                            config.rate_limit.initial_backoff = Duration::from_secs(secs);
+                        }
+                        if let Some(secs) = rate_limit.max_wait_secs {
+                            config.rate_limit.max_wait = Duration::from_secs(secs);
+                        }
+                        if rate_limit.fallback_agent.is_some() {
+                            config.rate_limit.fallback_agent = rate_limit.fallback_agent;
+                        }
+                        if rate_limit.fallback_model.is_some() {
+                            config.rate_limit.fallback_model = rate_limit.fallback_model;
+                        }
+                    }
+                    // Load agent event hooks
+                    if let Some(hooks) = toml_config.hooks {
+                        config.event_hooks = EventHooks::from_toml(hooks, "config [hooks]");
+                    }
+                    // Load attention notifications
+                    if let Some(notifications) = toml_config.notifications {
+                        if let Some(events) = notifications.events {
+                            config.notifications.events = events;
+                        }
+                        if let Some(background_only) = notifications.background_only {
+                            config.notifications.background_only = background_only;
+                        }
+                        if let Some(terminal) = notifications.terminal {
+                            config.notifications.terminal = terminal;
+                        }
+                        config.notifications.command =
+                            notifications.command.filter(|command| !command.is_empty());
+                        config.notifications.webhook_url = notifications
+                            .webhook_url
+                            .filter(|url| !url.trim().is_empty());
+                    }
+                    // Load goal loop limits
+                    if let Some(goal) = toml_config.goal {
+                        if let Some(max_iterations) = goal.max_iterations {
+                            config.goal.max_iterations = max_iterations.max(1);
+                        }
+                        config.goal.max_cost_usd = goal.max_cost_usd.filter(|cost| *cost > 0.0);
+                        if let Some(secs) = goal.check_timeout_secs {
+                            config.goal.check_timeout = Duration::from_secs(secs);
+                        }
+                    }
+                    // Load user-defined commands
+                    if let Some(commands) = toml_config.commands {
+                        config.user_commands = commands
+                            .into_iter()
+                            .filter_map(|command| match UserCommand::from_toml(command) {
+                                Ok(command) => Some(command),
+                                Err(err) => {
+                                    tracing::error!("Ignoring command: {}", err);
+                                    None
+                                }
+                            })
+                            .collect();
+                    }
                 }
             }
         }
@@ -788,6 +1310,7 @@ impl Config {
             AgentType::Codex => Tool::Codex,
             AgentType::Gemini => Tool::Gemini,
             AgentType::Opencode => Tool::Opencode,
+            AgentType::Acp => Tool::Acp,
         }
     }
 
@@ -844,19 +1367,36 @@ impl Config {
         self.default_model = Some(model_id);
     }
 
-    /// Calculate cost for given token usage
-    pub fn calculate_cost(&self, input_tokens: i64, output_tokens: i64) -> f64 {
-        let input_cost = (input_tokens as f64 / 1_000_000.0) * self.claude_input_cost_per_million;
-        let output_cost =
-            (output_tokens as f64 / 1_000_000.0) * self.claude_output_cost_per_million;
-        input_cost + output_cost
+    /// Estimate the dollar cost of token usage for a model.
+    ///
+    /// Uses the model's list pricing; Claude models without known pricing fall
+    /// back to the configured Claude rates. Returns `None` when no price is known.
+    pub fn calculate_cost(
+        &self,
+        agent_type: AgentType,
+        model: Option<&str>,
+        usage: &TokenUsage,
+    ) -> Option<f64> {
+        let model = model
+            .map(str::to_string)
+            .unwrap_or_else(|| ModelRegistry::default_model(agent_type));
+        let pricing = ModelRegistry::pricing(agent_type, &model).or_else(|| {
+            (agent_type == AgentType::Claude).then(|| {
+                ModelPricing::new(
+                    self.claude_input_cost_per_million,
+                    self.claude_output_cost_per_million,
+                )
+            })
+        })?;
+        Some(pricing.cost(usage))
     }
 }
 
 /// Save a tool path to the config file
 ///
 /// This function reads the existing config.toml, adds or updates the tool path
-/// in the [tools] section, and writes it back while preserving all other content.
+/// in the [tools] section (or `[acp_agent].command` for the ACP agent), and writes
+/// it back while preserving all other content.
 pub fn save_tool_path(tool: Tool, path: &Path) -> std::io::Result<()> {
     let config_file = config_path();
 
@@ -872,14 +1412,26 @@ pub fn save_tool_path(tool: Tool, path: &Path) -> std::io::Result<()> {
         .parse()
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
 
-    // Ensure [tools] section exists
-    if !doc.contains_key("tools") {
-        doc["tools"] = Item::Table(Table::new());
+    // The ACP agent's binary lives in its own section
+    let section = if tool == Tool::Acp {
+        "acp_agent"
+    } else {
+        "tools"
+    };
+    let key = if tool == Tool::Acp {
+        "command"
+    } else {
+        tool.binary_name()
+    };
+
+    // Ensure the section exists
+    if !doc.contains_key(section) {
+        doc[section] = Item::Table(Table::new());
     }
 
     // Set the tool path
     let path_str = path.to_string_lossy().to_string();
-    doc["tools"][tool.binary_name()] = toml_edit::value(path_str);
+    doc[section][key] = toml_edit::value(path_str);
 
     // Ensure parent directory exists
     if let Some(parent) = config_file.parent() {
@@ -1040,6 +1592,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_user_commands_are_bindable() {
+        let toml_config: TomlConfig = toml::from_str(
+            r#"
+            [keys]
+            "command:ship" = "C-M-s"
+
+            [[commands]]
+            name = "ship"
+            steps = ["open_pr"]
+            "#,
+        )
+        .unwrap();
+        let ship = Action::RunCommand("ship".to_string());
+        let keybindings = toml_config.keys.unwrap().to_keybinding_config();
+        assert!(keybindings.global.values().any(|action| *action == ship));
+        assert_eq!(toml_config.commands.unwrap().len(), 1);
+        assert_eq!(parse_action("command:/ship"), Some(ship));
+        assert_eq!(parse_action("command:"), None);
+    }
+
     #[test]
     fn test_command_names_include_handoff_session() {
         assert!(
@@ -1047,4 +1620,209 @@ mod tests {
             "handoff_session should be present in COMMAND_NAMES"
         );
     }
+
+    #[test]
+    fn test_calculate_cost_uses_model_pricing() {
+        let config = Config::default();
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            cached_tokens: 400_000,
+            total_tokens: 1_100_000,
+        };
+
+        // 600k uncached at $1.75 + 400k cached at $0.175 + 100k output at $14
+        let cost = config
+            .calculate_cost(AgentType::Codex, Some("gpt-5.2"), &usage)
+            .unwrap();
+        assert!((cost - 2.52).abs() < 1e-9);
+
+        // Unknown Claude models fall back to the configured rates
+        let cost = config
+            .calculate_cost(AgentType::Claude, Some("claude-unknown"), &usage)
+            .unwrap();
+        assert!((cost - 4.5).abs() < 1e-9);
+
+        assert!(config
+            .calculate_cost(AgentType::Acp, None, &usage)
+            .is_none());
+    }
+
+    #[test]
+    fn test_acp_agent_config_requires_command() {
+        let toml_config: TomlConfig = toml::from_str(
+            r#"
+            [acp_agent]
+            command = "my-agent"
+            args = ["--acp"]
+            display_name = "My Agent"
+
+            [acp_agent.env]
+            MY_AGENT_TOKEN = "abc"
+            "#,
+        )
+        .unwrap();
+        let agent = toml_config.acp_agent.unwrap().into_config().unwrap();
+        assert_eq!(agent.command, "my-agent");
+        assert_eq!(agent.args, vec!["--acp".to_string()]);
+        assert_eq!(agent.display_name, "My Agent");
+        assert_eq!(
+            agent.env.get("MY_AGENT_TOKEN").map(String::as_str),
+            Some("abc")
+        );
+
+        let missing = TomlAcpAgentConfig {
+            display_name: Some("No Command".to_string()),
+            ..Default::default()
+        };
+        assert!(missing.into_config().is_none());
+    }
+
+    #[test]
+    fn test_acp_agent_read_files_controls_mention_inlining() {
+        let toml_config: TomlConfig = toml::from_str(
+            r#"
+            [acp_agent]
+            command = "chat-only-agent"
+            read_files = false
+            "#,
+        )
+        .unwrap();
+        let mut config = Config::default();
+        assert!(config.agent_reads_files(AgentType::Acp));
+
+        config.acp_agent = toml_config.acp_agent.unwrap().into_config();
+        assert!(!config.agent_reads_files(AgentType::Acp));
+        assert!(config.agent_reads_files(AgentType::Claude));
+    }
+
+    #[test]
+    fn test_budget_config_parses_limits() {
+        let toml_config: TomlConfig = toml::from_str(
+            r#"
+            [budget]
+            on_hard_limit = "interrupt"
+
+            [budget.session]
+            usd = 5.0
+
+            [budget.daily]
+            tokens = 1000000
+            usd = 50.0
+            "#,
+        )
+        .unwrap();
+        let budget = toml_config.budget.unwrap();
+        assert_eq!(budget.on_hard_limit, Some(BudgetAction::Interrupt));
+        assert_eq!(budget.session.unwrap().usd, Some(5.0));
+        assert!(budget.repository.is_none());
+        let daily = budget.daily.unwrap();
+        assert_eq!(daily.tokens, Some(1_000_000));
+        assert!(daily.is_set());
+        assert!(!Config::default().budget.is_enabled());
+    }
+
+    #[test]
+    fn test_supervisor_config_parses() {
+        let toml_config: TomlConfig = toml::from_str(
+            r#"
+            [supervisor]
+            max_restarts = 5
+            initial_backoff_ms = 500
+            "#,
+        )
+        .unwrap();
+        let supervisor = toml_config.supervisor.unwrap();
+        assert_eq!(supervisor.max_restarts, Some(5));
+        assert_eq!(supervisor.initial_backoff_ms, Some(500));
+        assert!(supervisor.enabled.is_none());
+        assert!(Config::default().supervisor.enabled);
+    }
+
+    #[test]
+    fn test_rate_limit_retry_delay() {
+        let toml_config: TomlConfig = toml::from_str(
+            r#"
+            [rate_limit]
+            max_retries = 2
+            initial_backoff_secs = 10
+            max_wait_secs = 60
+            fallback_agent = "codex"
+            "#,
+        )
+        .unwrap();
+        let rate_limit = toml_config.rate_limit.unwrap();
+        assert_eq!(rate_limit.fallback_agent, Some(AgentType::Codex));
+
+        let config = RateLimitConfig {
+            auto_retry: true,
+            max_retries: 2,
+            initial_backoff: Duration::from_secs(10),
+            max_wait: Duration::from_secs(60),
+            fallback_agent: None,
+            fallback_model: None,
+        };
+        assert_eq!(config.retry_delay(None, 1), Some(Duration::from_secs(10)));
+        assert_eq!(config.retry_delay(None, 2), Some(Duration::from_secs(20)));
+        assert_eq!(config.retry_delay(None, 3), None);
+        assert_eq!(
+            config.retry_delay(Some(Duration::from_secs(45)), 1),
+            Some(Duration::from_secs(45))
+        );
+        // Waits past max_wait are left to the user
+        assert_eq!(config.retry_delay(Some(Duration::from_secs(3600)), 1), None);
+    }
+    #[test]
+    fn test_policy_section_parses_rules() {
+        let toml_config: TomlConfig = toml::from_str(
+            r#"
+            [policy]
+            allow = ["Bash(cargo test*)"]
+            deny = ["Bash(git push*)"]
+
+            [policy.codex]
+            sandbox_mode = "workspace-write"
+            "#,
+        )
+        .unwrap();
+        let policy = ToolPolicy::from_toml(toml_config.policy.unwrap(), "test");
+        assert!(policy.has_deny_rules());
+        assert_eq!(
+            policy.codex_sandbox_mode(),
+            Some(crate::agent::CodexSandboxMode::WorkspaceWrite)
+        );
+        assert!(Config::default().tool_policy.is_empty());
+    }
+
+    #[test]
+    fn test_notifications_section_parses() {
+        let toml_config: TomlConfig = toml::from_str(
+            r#"
+            [notifications]
+            events = ["turn_failed", "input_needed"]
+            terminal = "osc9"
+            command = ["notify-send", "{title}", "{summary}"]
+            "#,
+        )
+        .unwrap();
+        let notifications = toml_config.notifications.unwrap();
+        assert_eq!(
+            notifications.events,
+            Some(vec![
+                NotificationEvent::TurnFailed,
+                NotificationEvent::InputNeeded
+            ])
+        );
+        assert_eq!(notifications.terminal, Some(TerminalNotification::Osc9));
+        assert!(notifications.background_only.is_none());
+
+        // Nothing is sent until a sink is configured
+        let defaults = NotificationsConfig::default();
+        assert!(!defaults.notifies(NotificationEvent::TurnCompleted));
+        let bell = NotificationsConfig {
+            terminal: TerminalNotification::Bell,
+            ..defaults
+        };
+        assert!(bell.notifies(NotificationEvent::InputNeeded));
+    }
 }
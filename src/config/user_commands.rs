@@ -0,0 +1,255 @@
+//! User-defined slash commands
+//!
+//! Each `[[commands]]` entry in the config adds a command to the `/` menu and
+//! the command palette; `[keys]` binds one as `"command:<name>"`. A command
+//! either runs a shell script in the session's workspace, with `output`
+//! deciding what happens to what it prints, or chains other actions as
+//! `steps`, where `command:<name>` runs another user command. A step after a
+//! script only runs once the script succeeds.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use super::settings::parse_action;
+use crate::ui::action::Action;
+
+/// Prefix naming a user command where an action name is expected
+pub const USER_COMMAND_PREFIX: &str = "command:";
+
+/// How long a script may run unless the command says otherwise
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How deeply macros may call other macros
+const MAX_MACRO_DEPTH: usize = 8;
+
+/// What happens to a script's output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandOutput {
+    /// Show it in the chat
+    #[default]
+    Show,
+    /// Insert it into the prompt being typed
+    Insert,
+    /// Send it to the agent
+    Submit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UserCommandKind {
+    /// Shell script run in the workspace
+    Script {
+        run: String,
+        output: CommandOutput,
+        timeout: Duration,
+    },
+    /// Actions run one after another
+    Macro { steps: Vec<Action> },
+}
+
+/// A command from `[[commands]]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserCommand {
+    pub name: String,
+    pub description: String,
+    pub kind: UserCommandKind,
+}
+
+/// `[[commands]]` in config.toml
+#[derive(Debug, Clone, Deserialize)]
+pub struct TomlUserCommand {
+    pub name: String,
+    pub description: Option<String>,
+    pub run: Option<String>,
+    pub output: Option<CommandOutput>,
+    pub timeout_secs: Option<u64>,
+    pub steps: Option<Vec<String>>,
+}
+
+/// One step of a running command, with macros already expanded
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandStep {
+    Script(UserCommand),
+    Action(Action),
+}
+
+impl UserCommand {
+    pub fn from_toml(toml: TomlUserCommand) -> Result<Self, String> {
+        let name = toml.name.trim().trim_start_matches('/').to_string();
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(format!("Invalid command name '{}'", toml.name));
+        }
+
+        let kind = match (toml.run, toml.steps) {
+            (Some(run), None) if !run.trim().is_empty() => UserCommandKind::Script {
+                run,
+                output: toml.output.unwrap_or_default(),
+                timeout: toml
+                    .timeout_secs
+                    .map_or(DEFAULT_TIMEOUT, Duration::from_secs),
+            },
+            (None, Some(steps)) if !steps.is_empty() => {
+                let steps = steps
+                    .iter()
+                    .map(|step| {
+                        parse_action(step)
+                            .ok_or_else(|| format!("/{}: unknown step '{}'", name, step))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                UserCommandKind::Macro { steps }
+            }
+            _ => {
+                return Err(format!(
+                    "/{}: set either `run` or a non-empty `steps`",
+                    name
+                ))
+            }
+        };
+
+        Ok(Self {
+            name,
+            description: toml.description.unwrap_or_default().trim().to_string(),
+            kind,
+        })
+    }
+
+    /// The description, or what the command does when it has none
+    pub fn summary(&self) -> String {
+        if !self.description.is_empty() {
+            return self.description.clone();
+        }
+        match &self.kind {
+            UserCommandKind::Script { run, .. } => format!("Run `{}`", run),
+            UserCommandKind::Macro { steps } => steps
+                .iter()
+                .map(|step| match step {
+                    Action::RunCommand(name) => format!("/{}", name),
+                    other => other.description().to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(", then "),
+        }
+    }
+}
+
+/// Find a command by name, ignoring case
+pub fn find_user_command<'a>(commands: &'a [UserCommand], name: &str) -> Option<&'a UserCommand> {
+    commands
+        .iter()
+        .find(|command| command.name.eq_ignore_ascii_case(name))
+}
+
+/// The steps `name` runs, with nested macros expanded in place
+pub fn expand_user_command(
+    commands: &[UserCommand],
+    name: &str,
+) -> Result<Vec<CommandStep>, String> {
+    let mut steps = Vec::new();
+    expand_into(commands, name, &mut Vec::new(), &mut steps)?;
+    Ok(steps)
+}
+
+fn expand_into(
+    commands: &[UserCommand],
+    name: &str,
+    stack: &mut Vec<String>,
+    steps: &mut Vec<CommandStep>,
+) -> Result<(), String> {
+    let command =
+        find_user_command(commands, name).ok_or_else(|| format!("No command named /{}", name))?;
+    if stack.contains(&command.name) {
+        return Err(format!("/{} calls itself", command.name));
+    }
+    if stack.len() >= MAX_MACRO_DEPTH {
+        return Err(format!("/{} nests too many commands", stack[0]));
+    }
+
+    match &command.kind {
+        UserCommandKind::Script { .. } => steps.push(CommandStep::Script(command.clone())),
+        UserCommandKind::Macro { steps: actions } => {
+            stack.push(command.name.clone());
+            for action in actions {
+                match action {
+                    Action::RunCommand(nested) => expand_into(commands, nested, stack, steps)?,
+                    other => steps.push(CommandStep::Action(other.clone())),
+                }
+            }
+            stack.pop();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(toml: &str) -> Vec<UserCommand> {
+        #[derive(Deserialize)]
+        struct Commands {
+            commands: Vec<TomlUserCommand>,
+        }
+        let parsed: Commands = toml::from_str(toml).unwrap();
+        parsed
+            .commands
+            .into_iter()
+            .map(|command| UserCommand::from_toml(command).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_macro_expands_scripts_and_actions() {
+        let commands = parse(
+            r#"
+            [[commands]]
+            name = "test"
+            run = "cargo test"
+
+            [[commands]]
+            name = "ship"
+            description = "Test, then open a PR"
+            steps = ["command:test", "open_pr"]
+            "#,
+        );
+        assert_eq!(commands[0].summary(), "Run `cargo test`");
+
+        let steps = expand_user_command(&commands, "SHIP").unwrap();
+        assert_eq!(steps.len(), 2);
+        assert!(matches!(&steps[0], CommandStep::Script(command) if command.name == "test"));
+        assert_eq!(steps[1], CommandStep::Action(Action::OpenPr));
+    }
+
+    #[test]
+    fn test_invalid_commands_are_rejected() {
+        let commands = parse(
+            r#"
+            [[commands]]
+            name = "loop"
+            steps = ["command:loop"]
+            "#,
+        );
+        assert!(expand_user_command(&commands, "loop").is_err());
+        assert!(expand_user_command(&commands, "missing").is_err());
+
+        let both = TomlUserCommand {
+            name: "both".to_string(),
+            description: None,
+            run: Some("true".to_string()),
+            output: None,
+            timeout_secs: None,
+            steps: Some(vec!["open_pr".to_string()]),
+        };
+        assert!(UserCommand::from_toml(both).is_err());
+
+        let unknown_step = TomlUserCommand {
+            name: "oops".to_string(),
+            description: None,
+            run: None,
+            output: None,
+            timeout_secs: None,
+            steps: Some(vec!["not_an_action".to_string()]),
+        };
+        assert!(UserCommand::from_toml(unknown_step).is_err());
+    }
+}
@@ -2,21 +2,30 @@
 
 use std::sync::Arc;
 
+use uuid::Uuid;
+
 use crate::agent::{
-    ClaudeCodeRunner, CodexCliRunner, GeminiCliRunner, ModelRegistry, OpencodeRunner,
+    AcpAgentConfig, AcpAgentRunner, AgentRunner, AgentType, ApprovalPolicy, ClaudeCodeRunner,
+    CodexApprovalMode, CodexCliRunner, CodexSandboxMode, EventHooks, GeminiCliRunner,
+    ModelRegistry, OpencodeRunner, ToolPolicy,
 };
 use crate::config::Config;
 use crate::data::{
-    AppStateStore, Database, ForkSeedStore, RepositoryStore, SessionTabStore, WorkspaceStore,
+    AppStateStore, CheckpointStore, Database, ForkSeedStore, HistoryIndexStore, Repository,
+    RepositoryStore, ScheduledPromptStore, SessionTabStore, UsageLedgerStore, WorkspaceStore,
 };
 use crate::git::WorkspaceRepoManager;
 use crate::util::{Tool, ToolAvailability};
 
+use super::repo_settings::{
+    resolve_repo_approval_policy, resolve_repo_event_hooks, resolve_repo_tool_policy,
+};
+
 /// Core infrastructure for Conduit, shared between TUI and web interfaces.
 ///
 /// This struct owns all the foundational components:
 /// - Database connection and DAO stores for persistent data
-/// - Agent runners for Claude, Codex, Gemini, and OpenCode
+/// - Agent runners for Claude, Codex, Gemini, OpenCode, and a configured ACP agent
 /// - Configuration and tool availability
 /// - Worktree manager for git workspace operations
 pub struct ConduitCore {
@@ -36,6 +45,14 @@ pub struct ConduitCore {
     session_tab_store: Option<SessionTabStore>,
     /// Fork seed DAO (for persisting fork metadata)
     fork_seed_store: Option<ForkSeedStore>,
+    /// Usage ledger DAO (for per-turn token and cost records)
+    usage_ledger_store: Option<UsageLedgerStore>,
+    /// Checkpoint DAO (for per-turn worktree snapshots)
+    checkpoint_store: Option<CheckpointStore>,
+    /// Scheduled prompt DAO (for cron-driven prompts)
+    scheduled_prompt_store: Option<ScheduledPromptStore>,
+    /// History index DAO (for cross-session search)
+    history_index_store: Option<HistoryIndexStore>,
     /// Claude Code runner
     claude_runner: Arc<ClaudeCodeRunner>,
     /// Codex CLI runner
@@ -44,6 +61,8 @@ pub struct ConduitCore {
     gemini_runner: Arc<GeminiCliRunner>,
     /// OpenCode runner
     opencode_runner: Arc<OpencodeRunner>,
+    /// Generic ACP agent runner
+    acp_runner: Arc<AcpAgentRunner>,
     /// Worktree manager
     worktree_manager: WorkspaceRepoManager,
 }
@@ -59,6 +78,10 @@ impl ConduitCore {
             app_state_store,
             session_tab_store,
             fork_seed_store,
+            usage_ledger_store,
+            checkpoint_store,
+            scheduled_prompt_store,
+            history_index_store,
         ) = match Database::open_default() {
             Ok(db) => {
                 let repo_store = RepositoryStore::new(db.connection());
@@ -66,6 +89,10 @@ impl ConduitCore {
                 let app_state_store = AppStateStore::new(db.connection());
                 let session_tab_store = SessionTabStore::new(db.connection());
                 let fork_seed_store = ForkSeedStore::new(db.connection());
+                let usage_ledger_store = UsageLedgerStore::new(db.connection());
+                let checkpoint_store = CheckpointStore::new(db.connection());
+                let scheduled_prompt_store = ScheduledPromptStore::new(db.connection());
+                let history_index_store = HistoryIndexStore::new(db.connection());
                 (
                     Some(db),
                     Some(repo_store),
@@ -73,11 +100,15 @@ impl ConduitCore {
                     Some(app_state_store),
                     Some(session_tab_store),
                     Some(fork_seed_store),
+                    Some(usage_ledger_store),
+                    Some(checkpoint_store),
+                    Some(scheduled_prompt_store),
+                    Some(history_index_store),
                 )
             }
             Err(e) => {
                 tracing::warn!(error = %e, "Failed to open database");
-                (None, None, None, None, None, None)
+                (None, None, None, None, None, None, None, None, None, None)
             }
         };
 
@@ -106,6 +137,8 @@ impl ConduitCore {
             None => Arc::new(OpencodeRunner::new()),
         };
 
+        let acp_runner = Arc::new(AcpAgentRunner::new(config.acp_agent.clone()));
+
         if tools.is_available(Tool::Opencode) {
             let models = crate::agent::opencode::load_opencode_models(
                 tools.get_path(Tool::Opencode).cloned(),
@@ -124,10 +157,15 @@ impl ConduitCore {
             app_state_store,
             session_tab_store,
             fork_seed_store,
+            usage_ledger_store,
+            checkpoint_store,
+            scheduled_prompt_store,
+            history_index_store,
             claude_runner,
             codex_runner,
             gemini_runner,
             opencode_runner,
+            acp_runner,
             worktree_manager,
         }
     }
@@ -192,6 +230,46 @@ impl ConduitCore {
         self.fork_seed_store.clone()
     }
 
+    /// Get the usage ledger store.
+    pub fn usage_ledger_store(&self) -> Option<&UsageLedgerStore> {
+        self.usage_ledger_store.as_ref()
+    }
+
+    /// Get a clone of the usage ledger store.
+    pub fn usage_ledger_store_clone(&self) -> Option<UsageLedgerStore> {
+        self.usage_ledger_store.clone()
+    }
+
+    /// Get the checkpoint store.
+    pub fn checkpoint_store(&self) -> Option<&CheckpointStore> {
+        self.checkpoint_store.as_ref()
+    }
+
+    /// Get a clone of the checkpoint store.
+    pub fn checkpoint_store_clone(&self) -> Option<CheckpointStore> {
+        self.checkpoint_store.clone()
+    }
+
+    /// Get the scheduled prompt store.
+    pub fn scheduled_prompt_store(&self) -> Option<&ScheduledPromptStore> {
+        self.scheduled_prompt_store.as_ref()
+    }
+
+    /// Get a clone of the scheduled prompt store.
+    pub fn scheduled_prompt_store_clone(&self) -> Option<ScheduledPromptStore> {
+        self.scheduled_prompt_store.clone()
+    }
+
+    /// Get the history index store.
+    pub fn history_index_store(&self) -> Option<&HistoryIndexStore> {
+        self.history_index_store.as_ref()
+    }
+
+    /// Get a clone of the history index store.
+    pub fn history_index_store_clone(&self) -> Option<HistoryIndexStore> {
+        self.history_index_store.clone()
+    }
+
     /// Get the Claude runner.
     pub fn claude_runner(&self) -> &Arc<ClaudeCodeRunner> {
         &self.claude_runner
@@ -212,6 +290,22 @@ impl ConduitCore {
         &self.opencode_runner
     }
 
+    /// Get the generic ACP agent runner.
+    pub fn acp_runner(&self) -> &Arc<AcpAgentRunner> {
+        &self.acp_runner
+    }
+
+    /// Get the runner for an agent type.
+    pub fn runner_for(&self, agent_type: AgentType) -> Arc<dyn AgentRunner> {
+        match agent_type {
+            AgentType::Claude => self.claude_runner.clone(),
+            AgentType::Codex => self.codex_runner.clone(),
+            AgentType::Gemini => self.gemini_runner.clone(),
+            AgentType::Opencode => self.opencode_runner.clone(),
+            AgentType::Acp => self.acp_runner.clone(),
+        }
+    }
+
     /// Get the worktree manager.
     pub fn worktree_manager(&self) -> &WorkspaceRepoManager {
         &self.worktree_manager
@@ -232,6 +326,97 @@ impl ConduitCore {
         &mut self.config
     }
 
+    /// Resolve the tool approval policy for a workspace, honoring the owning
+    /// repository's override when one is set.
+    pub fn approval_policy_for_workspace(&self, workspace_id: Option<Uuid>) -> ApprovalPolicy {
+        let repo = self.repository_for_workspace(workspace_id);
+        resolve_repo_approval_policy(&self.config, repo.as_ref())
+    }
+
+    /// Codex approval and sandbox defaults set on the workspace's repository.
+    pub fn codex_permissions_for_workspace(
+        &self,
+        workspace_id: Option<Uuid>,
+    ) -> (Option<CodexApprovalMode>, Option<CodexSandboxMode>) {
+        self.repository_for_workspace(workspace_id)
+            .map_or((None, None), |repo| {
+                (repo.codex_approval_mode, repo.codex_sandbox_mode)
+            })
+    }
+
+    /// Allow and deny rules for a workspace: the global rules plus the owning
+    /// repository's policy file.
+    pub fn tool_policy_for_workspace(&self, workspace_id: Option<Uuid>) -> ToolPolicy {
+        let repo = self.repository_for_workspace(workspace_id);
+        resolve_repo_tool_policy(&self.config, repo.as_ref())
+    }
+
+    /// Agent event hooks for a workspace: the global hooks plus the owning
+    /// repository's hooks file.
+    pub fn event_hooks_for_workspace(&self, workspace_id: Option<Uuid>) -> EventHooks {
+        let repo = self.repository_for_workspace(workspace_id);
+        resolve_repo_event_hooks(&self.config, repo.as_ref())
+    }
+
+    /// Tools Claude may run without asking in a workspace: the tools the
+    /// owning repository always allows and the policy's allow rules, plus the
+    /// configured list under `AllowAll`. Entries a policy deny rule must still
+    /// see are left out; the approval policy decides calls outside the list.
+    pub fn claude_allowed_tools_for_workspace(
+        &self,
+        workspace_id: Option<Uuid>,
+        approval_policy: ApprovalPolicy,
+    ) -> Vec<String> {
+        let repo = self.repository_for_workspace(workspace_id);
+        let mut tools = match approval_policy {
+            ApprovalPolicy::AllowAll => self.config.claude_allowed_tools.clone(),
+            ApprovalPolicy::AllowReads | ApprovalPolicy::Ask => Vec::new(),
+        };
+        if let (Some(repo), Some(store)) = (repo.as_ref(), self.repo_store.as_ref()) {
+            match store.allowed_tools(repo.id) {
+                Ok(repo_tools) => tools.extend(repo_tools),
+                Err(err) => {
+                    tracing::warn!(error = %err, repo_id = %repo.id, "Failed to load allowed tools")
+                }
+            }
+        }
+        let mut tools =
+            resolve_repo_tool_policy(&self.config, repo.as_ref()).claude_allowed_tools(tools);
+        tools.sort();
+        tools.dedup();
+        tools
+    }
+
+    /// Always allow a tool in the repository that owns a workspace.
+    pub fn allow_tool_for_workspace(
+        &self,
+        workspace_id: Option<Uuid>,
+        tool_name: &str,
+    ) -> Result<(), String> {
+        let repo = self
+            .repository_for_workspace(workspace_id)
+            .ok_or_else(|| "Session has no repository".to_string())?;
+        let store = self
+            .repo_store
+            .as_ref()
+            .ok_or_else(|| "Database not available".to_string())?;
+        store
+            .allow_tool(repo.id, tool_name)
+            .map_err(|err| format!("Failed to save tool permission: {}", err))
+    }
+
+    fn repository_for_workspace(&self, workspace_id: Option<Uuid>) -> Option<Repository> {
+        workspace_id
+            .and_then(|id| self.workspace_store.as_ref()?.get_by_id(id).ok().flatten())
+            .and_then(|workspace| {
+                self.repo_store
+                    .as_ref()?
+                    .get_by_id(workspace.repository_id)
+                    .ok()
+                    .flatten()
+            })
+    }
+
     /// Refresh agent runners using the latest tool configuration.
     ///
     /// This should be called after updating tool paths (e.g., when the user
@@ -253,6 +438,20 @@ impl ConduitCore {
             Some(path) => Arc::new(OpencodeRunner::with_path(path.clone())),
             None => Arc::new(OpencodeRunner::new()),
         };
+        let acp_agent = match self.tools.get_path(Tool::Acp) {
+            Some(path) => {
+                let command = path.to_string_lossy().into_owned();
+                let mut agent = self
+                    .config
+                    .acp_agent
+                    .clone()
+                    .unwrap_or_else(|| AcpAgentConfig::new(command.clone()));
+                agent.command = command;
+                Some(agent)
+            }
+            None => self.config.acp_agent.clone(),
+        };
+        self.acp_runner = Arc::new(AcpAgentRunner::new(acp_agent));
 
         if self.tools.is_available(Tool::Opencode) {
             let models = crate::agent::opencode::load_opencode_models(
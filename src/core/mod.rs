@@ -5,11 +5,16 @@
 //! - Agent runners (Claude, Codex, Gemini)
 //! - Configuration and tool availability
 //! - Worktree management
+//! - Attention notifications
 
 mod conduit_core;
 pub mod dto;
+pub mod notifications;
 mod repo_settings;
 pub mod services;
 
 pub use conduit_core::ConduitCore;
-pub use repo_settings::{resolve_repo_workspace_settings, RepoWorkspaceSettings};
+pub use repo_settings::{
+    resolve_repo_approval_policy, resolve_repo_event_hooks, resolve_repo_tool_policy,
+    resolve_repo_workspace_settings, RepoWorkspaceSettings,
+};
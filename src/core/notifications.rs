@@ -0,0 +1,198 @@
+//! Attention notifications
+//!
+//! Tells you, outside Conduit, when an agent finishes a turn, fails, or waits
+//! for input. Sinks are configured under `[notifications]`: a terminal bell or
+//! OSC 9 escape (terminal UI only), a command such as `notify-send`, and an
+//! HTTP webhook that receives the notification as JSON.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::config::{NotificationEvent, NotificationsConfig, TerminalNotification};
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Longest summary sent to a sink
+const MAX_SUMMARY_CHARS: usize = 200;
+
+/// One notification, also the webhook's JSON payload
+#[derive(Debug, Clone, Serialize)]
+pub struct Notification {
+    pub event: NotificationEvent,
+    pub session_id: Uuid,
+    pub session_title: Option<String>,
+    pub workspace_id: Option<Uuid>,
+    pub workspace_name: Option<String>,
+    pub project_name: Option<String>,
+    pub agent: String,
+    pub summary: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Notification {
+    pub fn new(event: NotificationEvent, session_id: Uuid, agent: impl Into<String>) -> Self {
+        Self {
+            event,
+            session_id,
+            session_title: None,
+            workspace_id: None,
+            workspace_name: None,
+            project_name: None,
+            agent: agent.into(),
+            summary: String::new(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    pub fn with_summary(mut self, summary: &str) -> Self {
+        // First line only; errors and answers can run long
+        let line = summary.lines().next().unwrap_or_default().trim();
+        self.summary = if line.chars().count() > MAX_SUMMARY_CHARS {
+            let cut: String = line.chars().take(MAX_SUMMARY_CHARS - 1).collect();
+            format!("{}…", cut)
+        } else {
+            line.to_string()
+        };
+        self
+    }
+
+    /// Short heading, e.g. "bold-fox: Turn finished"
+    pub fn title(&self) -> String {
+        let label = match self.event {
+            NotificationEvent::TurnCompleted => "Turn finished",
+            NotificationEvent::TurnFailed => "Turn failed",
+            NotificationEvent::InputNeeded => "Waiting for input",
+        };
+        let place = self
+            .workspace_name
+            .as_deref()
+            .or(self.session_title.as_deref())
+            .unwrap_or(self.agent.as_str());
+        format!("{}: {}", place, label)
+    }
+
+    /// Title and summary on one line
+    pub fn message(&self) -> String {
+        if self.summary.is_empty() {
+            self.title()
+        } else {
+            format!("{} · {}", self.title(), self.summary)
+        }
+    }
+
+    fn expand(&self, arg: &str) -> String {
+        let event = match self.event {
+            NotificationEvent::TurnCompleted => "turn_completed",
+            NotificationEvent::TurnFailed => "turn_failed",
+            NotificationEvent::InputNeeded => "input_needed",
+        };
+        arg.replace("{title}", &self.title())
+            .replace("{summary}", &self.summary)
+            .replace("{message}", &self.message())
+            .replace("{event}", event)
+            .replace("{session_id}", &self.session_id.to_string())
+            .replace("{workspace}", self.workspace_name.as_deref().unwrap_or(""))
+    }
+}
+
+/// Escape sequence to write to the terminal, if that sink is on
+pub fn terminal_sequence(
+    kind: TerminalNotification,
+    notification: &Notification,
+) -> Option<String> {
+    match kind {
+        TerminalNotification::Off => None,
+        TerminalNotification::Bell => Some("\x07".to_string()),
+        TerminalNotification::Osc9 => {
+            // Control characters would end the sequence early
+            let message: String = notification
+                .message()
+                .chars()
+                .filter(|c| !c.is_control())
+                .collect();
+            Some(format!("\x1b]9;{}\x07", message))
+        }
+    }
+}
+
+/// Deliver through the command and webhook sinks. Failures are logged; a
+/// broken sink must never get in the way of the session.
+pub async fn send_external(config: &NotificationsConfig, notification: &Notification) {
+    if let Some((program, args)) = config.command.as_ref().and_then(|c| c.split_first()) {
+        let result = tokio::process::Command::new(notification.expand(program))
+            .args(args.iter().map(|arg| notification.expand(arg)))
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .await;
+        match result {
+            Ok(status) if status.success() => {}
+            Ok(status) => tracing::warn!(%status, "Notification command failed"),
+            Err(err) => tracing::warn!(error = %err, "Failed to run notification command"),
+        }
+    }
+
+    if let Some(url) = &config.webhook_url {
+        let result = reqwest::Client::new()
+            .post(url)
+            .timeout(WEBHOOK_TIMEOUT)
+            .json(notification)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        if let Err(err) = result {
+            tracing::warn!(error = %err, "Failed to send notification webhook");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification() -> Notification {
+        let mut notification =
+            Notification::new(NotificationEvent::InputNeeded, Uuid::nil(), "Claude Code")
+                .with_summary("AskUserQuestion is waiting\nsecond line");
+        notification.workspace_name = Some("bold-fox".to_string());
+        notification
+    }
+
+    #[test]
+    fn test_notification_text_and_placeholders() {
+        let notification = notification();
+        assert_eq!(notification.title(), "bold-fox: Waiting for input");
+        assert_eq!(
+            notification.message(),
+            "bold-fox: Waiting for input · AskUserQuestion is waiting"
+        );
+        assert_eq!(
+            notification.expand("{event} in {workspace}: {summary}"),
+            "input_needed in bold-fox: AskUserQuestion is waiting"
+        );
+
+        let payload = serde_json::to_value(&notification).unwrap();
+        assert_eq!(payload["event"], "input_needed");
+        assert_eq!(payload["workspace_name"], "bold-fox");
+    }
+
+    #[test]
+    fn test_terminal_sequences() {
+        let notification = notification();
+        assert_eq!(
+            terminal_sequence(TerminalNotification::Off, &notification),
+            None
+        );
+        assert_eq!(
+            terminal_sequence(TerminalNotification::Bell, &notification).as_deref(),
+            Some("\x07")
+        );
+        assert_eq!(
+            terminal_sequence(TerminalNotification::Osc9, &notification).as_deref(),
+            Some("\x1b]9;bold-fox: Waiting for input · AskUserQuestion is waiting\x07")
+        );
+    }
+}
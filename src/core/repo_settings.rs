@@ -1,3 +1,4 @@
+use crate::agent::{ApprovalPolicy, EventHooks, ToolPolicy};
 use crate::config::Config;
 use crate::data::Repository;
 use crate::git::WorkspaceMode;
@@ -21,3 +22,45 @@ pub fn resolve_repo_workspace_settings(
             .archive_remote_prompt_or(config.workspaces.archive_remote_prompt),
     }
 }
+
+/// Resolve the tool approval policy, falling back to the config default when
+/// the repository is unknown or has no override.
+pub fn resolve_repo_approval_policy(config: &Config, repo: Option<&Repository>) -> ApprovalPolicy {
+    let default = config.permissions.approval_policy;
+    repo.map_or(default, |repo| repo.approval_policy_or(default))
+}
+
+/// Resolve the allow and deny rules for a repository: the global `[policy]`
+/// rules plus the repository's `.conduit/policy.toml`, read from its main
+/// checkout so an agent cannot loosen the policy from inside a workspace.
+pub fn resolve_repo_tool_policy(config: &Config, repo: Option<&Repository>) -> ToolPolicy {
+    let global = config.tool_policy.clone();
+    let Some(base_path) = repo.and_then(|repo| repo.base_path.as_deref()) else {
+        return global;
+    };
+    match ToolPolicy::load(base_path) {
+        Ok(Some(policy)) => global.merge(policy),
+        Ok(None) => global,
+        Err(err) => {
+            tracing::error!("Ignoring repository policy: {}", err);
+            global
+        }
+    }
+}
+
+/// Resolve the agent event hooks for a repository: the global `[hooks]`
+/// plus the `[[event]]` hooks in the repository's `.conduit/hooks.toml`.
+pub fn resolve_repo_event_hooks(config: &Config, repo: Option<&Repository>) -> EventHooks {
+    let global = config.event_hooks.clone();
+    let Some(base_path) = repo.and_then(|repo| repo.base_path.as_deref()) else {
+        return global;
+    };
+    match EventHooks::load(base_path) {
+        Ok(Some(hooks)) => global.merge(hooks),
+        Ok(None) => global,
+        Err(err) => {
+            tracing::error!("Ignoring repository event hooks: {}", err);
+            global
+        }
+    }
+}
@@ -0,0 +1,332 @@
+use chrono::{DateTime, Datelike, Local, NaiveDate, Utc};
+
+use crate::agent::events::ContextWindowState;
+use crate::agent::TokenUsage;
+use crate::config::{BudgetConfig, BudgetLimit};
+use crate::core::services::error::ServiceError;
+use crate::core::services::usage_service::{TurnUsageContext, UsageService};
+use crate::core::ConduitCore;
+use crate::data::{UsageScope, UsageTotals};
+
+/// Which budget window a limit applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetScope {
+    /// Everything recorded for one session
+    Session,
+    /// The session's repository, for the current calendar month
+    Repository,
+    /// All sessions since local midnight
+    Daily,
+}
+
+impl BudgetScope {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            BudgetScope::Session => "Session",
+            BudgetScope::Repository => "Repository",
+            BudgetScope::Daily => "Daily",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetMetric {
+    Tokens,
+    Usd,
+}
+
+/// A budget that has crossed its soft or hard threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BudgetBreach {
+    pub scope: BudgetScope,
+    pub metric: BudgetMetric,
+    pub used: f64,
+    pub limit: f64,
+}
+
+impl BudgetBreach {
+    fn ratio(&self) -> f64 {
+        if self.limit > 0.0 {
+            self.used / self.limit
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    fn amount(&self) -> String {
+        match self.metric {
+            BudgetMetric::Tokens => format!(
+                "{} of {} tokens used",
+                ContextWindowState::format_tokens(self.used as i64),
+                ContextWindowState::format_tokens(self.limit as i64)
+            ),
+            BudgetMetric::Usd => format!("${:.2} of ${:.2} spent", self.used, self.limit),
+        }
+    }
+}
+
+/// Result of checking a session against the configured budgets
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BudgetStatus {
+    Ok,
+    Warning(BudgetBreach),
+    Exceeded(BudgetBreach),
+}
+
+impl BudgetStatus {
+    pub fn is_exceeded(&self) -> bool {
+        matches!(self, BudgetStatus::Exceeded(_))
+    }
+
+    /// User-facing explanation, `None` when within budget
+    pub fn message(&self) -> Option<String> {
+        match self {
+            BudgetStatus::Ok => None,
+            BudgetStatus::Warning(breach) => Some(format!(
+                "{} budget at {:.0}%: {}",
+                breach.scope.display_name(),
+                breach.ratio() * 100.0,
+                breach.amount()
+            )),
+            BudgetStatus::Exceeded(breach) => Some(format!(
+                "{} budget exceeded: {}. New prompts are held until the budget resets or is raised.",
+                breach.scope.display_name(),
+                breach.amount()
+            )),
+        }
+    }
+}
+
+/// Tracks the running turn's usage from the cumulative totals agents report
+/// mid-turn, so hard limits can interrupt before the turn completes.
+#[derive(Debug, Clone, Default)]
+pub struct InFlightUsage {
+    baseline: TokenUsage,
+    latest: TokenUsage,
+}
+
+impl InFlightUsage {
+    pub fn start_turn(&mut self) {
+        self.baseline = self.latest.clone();
+    }
+
+    /// Record a running total and return the usage accrued by the current turn
+    pub fn observe(&mut self, total: &TokenUsage) -> TokenUsage {
+        // Totals going backwards means a fresh agent process
+        if total.total_tokens < self.latest.total_tokens {
+            self.baseline = TokenUsage::default();
+        }
+        self.latest = total.clone();
+        total.since(&self.baseline)
+    }
+}
+
+pub struct BudgetService;
+
+impl BudgetService {
+    /// Check a session against the configured budgets.
+    ///
+    /// `in_flight` is usage from the running turn that has not reached the
+    /// ledger yet.
+    pub fn check(
+        core: &ConduitCore,
+        context: &TurnUsageContext,
+        in_flight: Option<&TokenUsage>,
+    ) -> Result<BudgetStatus, ServiceError> {
+        let config = core.config().budget;
+        if !config.is_enabled() {
+            return Ok(BudgetStatus::Ok);
+        }
+        let store = core
+            .usage_ledger_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+        let map_err = |e: rusqlite::Error| {
+            ServiceError::Internal(format!("Failed to load usage totals: {}", e))
+        };
+
+        let mut pending = UsageTotals::default();
+        if let Some(usage) = in_flight {
+            pending.total_tokens = usage.total_tokens;
+            pending.cost_usd = core
+                .config()
+                .calculate_cost(context.agent_type, context.model.as_deref(), usage)
+                .unwrap_or(0.0);
+        }
+
+        let now = Local::now();
+        let mut windows = Vec::new();
+        if config.session.is_set() {
+            if let Some(session_id) = context.session_id {
+                let totals = store
+                    .totals(UsageScope::Session(session_id), None)
+                    .map_err(map_err)?;
+                windows.push((BudgetScope::Session, config.session, totals));
+            }
+        }
+        if config.repository.is_set() {
+            if let Some(repository_id) =
+                UsageService::repository_for_workspace(core, context.workspace_id)?
+            {
+                let totals = store
+                    .totals(
+                        UsageScope::Repository(repository_id),
+                        Some(Self::month_start(now)),
+                    )
+                    .map_err(map_err)?;
+                windows.push((BudgetScope::Repository, config.repository, totals));
+            }
+        }
+        if config.daily.is_set() {
+            let totals = store
+                .totals(UsageScope::All, Some(Self::day_start(now)))
+                .map_err(map_err)?;
+            windows.push((BudgetScope::Daily, config.daily, totals));
+        }
+
+        for (_, _, totals) in &mut windows {
+            totals.total_tokens += pending.total_tokens;
+            totals.cost_usd += pending.cost_usd;
+        }
+        Ok(Self::evaluate(&config, &windows))
+    }
+
+    /// Like [`check`](Self::check), but logs failures and reports them as
+    /// within budget so a ledger problem never blocks the agent.
+    pub fn status(
+        core: &ConduitCore,
+        context: &TurnUsageContext,
+        in_flight: Option<&TokenUsage>,
+    ) -> BudgetStatus {
+        Self::check(core, context, in_flight).unwrap_or_else(|err| {
+            tracing::warn!(error = %err, session_id = ?context.session_id, "Failed to check budget");
+            BudgetStatus::Ok
+        })
+    }
+
+    /// Pick the most severe breach among the given windows
+    fn evaluate(
+        config: &BudgetConfig,
+        windows: &[(BudgetScope, BudgetLimit, UsageTotals)],
+    ) -> BudgetStatus {
+        let mut worst: Option<BudgetBreach> = None;
+        for (scope, limit, totals) in windows {
+            let metrics = [
+                (
+                    BudgetMetric::Tokens,
+                    limit.tokens.map(|t| t as f64),
+                    totals.total_tokens as f64,
+                ),
+                (BudgetMetric::Usd, limit.usd, totals.cost_usd),
+            ];
+            for (metric, limit, used) in metrics {
+                let Some(limit) = limit else {
+                    continue;
+                };
+                let breach = BudgetBreach {
+                    scope: *scope,
+                    metric,
+                    used,
+                    limit,
+                };
+                if worst.is_none_or(|w| breach.ratio() > w.ratio()) {
+                    worst = Some(breach);
+                }
+            }
+        }
+
+        let Some(breach) = worst else {
+            return BudgetStatus::Ok;
+        };
+        if breach.used >= breach.limit {
+            BudgetStatus::Exceeded(breach)
+        } else if config.warn_at_percent > 0
+            && breach.ratio() * 100.0 >= f64::from(config.warn_at_percent)
+        {
+            BudgetStatus::Warning(breach)
+        } else {
+            BudgetStatus::Ok
+        }
+    }
+
+    fn day_start(now: DateTime<Local>) -> DateTime<Utc> {
+        Self::local_midnight(now.date_naive(), now)
+    }
+
+    fn month_start(now: DateTime<Local>) -> DateTime<Utc> {
+        let first = now.date_naive().with_day(1).unwrap_or(now.date_naive());
+        Self::local_midnight(first, now)
+    }
+
+    fn local_midnight(date: NaiveDate, fallback: DateTime<Local>) -> DateTime<Utc> {
+        date.and_hms_opt(0, 0, 0)
+            .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+            .unwrap_or(fallback)
+            .with_timezone(&Utc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BudgetAction, Config};
+
+    fn budget(daily: BudgetLimit) -> BudgetConfig {
+        BudgetConfig {
+            daily,
+            on_hard_limit: BudgetAction::Block,
+            ..Config::default().budget
+        }
+    }
+
+    fn totals(tokens: i64, cost_usd: f64) -> UsageTotals {
+        UsageTotals {
+            total_tokens: tokens,
+            cost_usd,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_evaluate_thresholds() {
+        let limit = BudgetLimit {
+            tokens: Some(1_000_000),
+            usd: Some(10.0),
+        };
+        let config = budget(limit);
+        let window = |t| [(BudgetScope::Daily, limit, t)];
+
+        assert_eq!(
+            BudgetService::evaluate(&config, &window(totals(100_000, 1.0))),
+            BudgetStatus::Ok
+        );
+        let status = BudgetService::evaluate(&config, &window(totals(100_000, 8.5)));
+        assert!(matches!(
+            status,
+            BudgetStatus::Warning(BudgetBreach {
+                metric: BudgetMetric::Usd,
+                ..
+            })
+        ));
+        let status = BudgetService::evaluate(&config, &window(totals(1_200_000, 8.5)));
+        assert!(status.is_exceeded());
+        assert!(status
+            .message()
+            .unwrap()
+            .starts_with("Daily budget exceeded: 1.2M of 1.0M tokens used"));
+    }
+
+    #[test]
+    fn test_in_flight_usage_tracks_running_totals() {
+        let total = |t| TokenUsage {
+            total_tokens: t,
+            ..Default::default()
+        };
+        let mut in_flight = InFlightUsage::default();
+        assert_eq!(in_flight.observe(&total(100)).total_tokens, 100);
+        in_flight.start_turn();
+        assert_eq!(in_flight.observe(&total(250)).total_tokens, 150);
+        // A restarted agent reports totals from zero again
+        in_flight.start_turn();
+        assert_eq!(in_flight.observe(&total(40)).total_tokens, 40);
+    }
+}
@@ -75,6 +75,7 @@ impl ConfigService {
                     AgentType::Codex => crate::util::Tool::Codex,
                     AgentType::Gemini => crate::util::Tool::Gemini,
                     AgentType::Opencode => crate::util::Tool::Opencode,
+                    AgentType::Acp => crate::util::Tool::Acp,
                 })
             })
             .count();
@@ -1,11 +1,21 @@
+pub mod budget_service;
 pub mod config_service;
 pub mod context_window_service;
 pub mod error;
 pub mod model_service;
+pub mod schedule_service;
 pub mod session_service;
+pub mod usage_service;
+pub mod workspace_service;
 
+pub use budget_service::{BudgetService, BudgetStatus, InFlightUsage};
 pub use config_service::ConfigService;
 pub use context_window_service::{ContextWindowService, ResolvedContextWindow};
 pub use error::ServiceError;
 pub use model_service::ModelService;
+pub use schedule_service::{
+    CreateScheduleParams, ScheduleDelivery, ScheduleService, UpdateScheduleParams,
+};
 pub use session_service::{CreateSessionParams, SessionService, UpdateSessionParams};
+pub use usage_service::{TurnUsageContext, UsageService};
+pub use workspace_service::WorkspaceService;
@@ -1,4 +1,4 @@
-use crate::agent::ModelRegistry;
+use crate::agent::{AgentType, ModelRegistry};
 use crate::core::dto::{ListModelsDto, ModelGroupDto, ModelInfoDto};
 use crate::core::services::config_service::ConfigService;
 use crate::core::services::ContextWindowService;
@@ -40,7 +40,11 @@ impl ModelService {
 
                 Some(ModelGroupDto {
                     agent_type: agent_type_str,
-                    section_title: ModelRegistry::agent_section_title(agent_type).to_string(),
+                    section_title: match agent_type {
+                        AgentType::Acp => core.config().agent_display_name(agent_type),
+                        _ => ModelRegistry::agent_section_title(agent_type),
+                    }
+                    .to_string(),
                     icon: ModelRegistry::agent_icon(agent_type).to_string(),
                     models: model_entries,
                 })
@@ -0,0 +1,307 @@
+use chrono::{DateTime, Local, Utc};
+use uuid::Uuid;
+
+use crate::core::services::error::ServiceError;
+use crate::core::services::session_service::SessionService;
+use crate::core::services::workspace_service::WorkspaceService;
+use crate::core::ConduitCore;
+use crate::data::{QueuedMessageMode, ScheduledPrompt, ScheduledPromptStore, Workspace};
+use crate::util::CronSchedule;
+
+#[derive(Debug, Clone)]
+pub struct CreateScheduleParams {
+    pub repository_id: Uuid,
+    /// Workspace to reuse; None creates a new workspace for every run
+    pub workspace_id: Option<Uuid>,
+    pub name: Option<String>,
+    pub schedule: String,
+    pub prompt: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UpdateScheduleParams {
+    pub name: Option<String>,
+    pub schedule: Option<String>,
+    pub prompt: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+/// Where a scheduled prompt was queued
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduleDelivery {
+    pub workspace_id: Uuid,
+    pub session_id: Uuid,
+    /// The workspace was created for this run and still needs its setup hooks
+    pub created_workspace: bool,
+}
+
+pub struct ScheduleService;
+
+impl ScheduleService {
+    pub fn list(core: &ConduitCore) -> Result<Vec<ScheduledPrompt>, ServiceError> {
+        Self::store(core)?
+            .get_all()
+            .map_err(|e| ServiceError::Internal(format!("Failed to list schedules: {}", e)))
+    }
+
+    pub fn get(core: &ConduitCore, id: Uuid) -> Result<ScheduledPrompt, ServiceError> {
+        Self::store(core)?
+            .get_by_id(id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to get schedule: {}", e)))?
+            .ok_or_else(|| ServiceError::NotFound(format!("Schedule {} not found", id)))
+    }
+
+    pub fn create(
+        core: &ConduitCore,
+        params: CreateScheduleParams,
+    ) -> Result<ScheduledPrompt, ServiceError> {
+        let store = Self::store(core)?;
+        let schedule = Self::parse_schedule(&params.schedule)?;
+        let prompt = Self::validate_prompt(&params.prompt)?;
+
+        let repo_store = core
+            .repo_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+        repo_store
+            .get_by_id(params.repository_id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to get repository: {}", e)))?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Repository {} not found", params.repository_id))
+            })?;
+        if let Some(workspace_id) = params.workspace_id {
+            let workspace = Self::workspace(core, workspace_id)?;
+            if workspace.repository_id != params.repository_id {
+                return Err(ServiceError::InvalidInput(format!(
+                    "Workspace {} does not belong to repository {}",
+                    workspace_id, params.repository_id
+                )));
+            }
+        }
+
+        let name = params
+            .name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| default_name(&prompt));
+        let mut entry = ScheduledPrompt::new(
+            params.repository_id,
+            params.workspace_id,
+            name,
+            params.schedule.trim(),
+            prompt,
+        );
+        entry.enabled = params.enabled;
+        entry.next_run_at = Self::next_run(&schedule, Utc::now());
+        store
+            .create(&entry)
+            .map_err(|e| ServiceError::Internal(format!("Failed to create schedule: {}", e)))?;
+        Ok(entry)
+    }
+
+    pub fn update(
+        core: &ConduitCore,
+        id: Uuid,
+        params: UpdateScheduleParams,
+    ) -> Result<ScheduledPrompt, ServiceError> {
+        let store = Self::store(core)?;
+        let mut entry = Self::get(core, id)?;
+
+        if let Some(name) = params.name {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(ServiceError::InvalidInput(
+                    "Name must not be empty".to_string(),
+                ));
+            }
+            entry.name = name.to_string();
+        }
+        if let Some(prompt) = params.prompt {
+            entry.prompt = Self::validate_prompt(&prompt)?;
+        }
+        let reschedule = params.schedule.is_some() || params.enabled == Some(true);
+        if let Some(schedule) = params.schedule {
+            Self::parse_schedule(&schedule)?;
+            entry.schedule = schedule.trim().to_string();
+        }
+        if let Some(enabled) = params.enabled {
+            entry.enabled = enabled;
+        }
+        if reschedule {
+            // Re-enabling starts from now instead of firing every missed run
+            let schedule = Self::parse_schedule(&entry.schedule)?;
+            entry.next_run_at = Self::next_run(&schedule, Utc::now());
+        }
+
+        store
+            .update(&entry)
+            .map_err(|e| ServiceError::Internal(format!("Failed to update schedule: {}", e)))?;
+        Ok(entry)
+    }
+
+    pub fn delete(core: &ConduitCore, id: Uuid) -> Result<(), ServiceError> {
+        Self::get(core, id)?;
+        Self::store(core)?
+            .delete(id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to delete schedule: {}", e)))
+    }
+
+    /// Next run strictly after `after`, evaluated in local time
+    pub fn next_run(schedule: &CronSchedule, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        schedule
+            .next_after(&after.with_timezone(&Local))
+            .map(|next| next.with_timezone(&Utc))
+    }
+
+    /// Claim every entry that is due at `now` and advance it to its next run.
+    ///
+    /// Runs missed while nothing was running are collapsed into one. Entries
+    /// claimed by another process in the meantime are left out.
+    pub fn claim_due(
+        core: &ConduitCore,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<ScheduledPrompt>, ServiceError> {
+        let store = Self::store(core)?;
+        let mut claimed = Vec::new();
+        for entry in Self::list(core)? {
+            let Some(due_at) = entry.next_run_at.filter(|_| entry.is_due(now)) else {
+                continue;
+            };
+            let next = match entry.schedule.parse::<CronSchedule>() {
+                Ok(schedule) => Self::next_run(&schedule, now),
+                Err(err) => {
+                    tracing::warn!(schedule_id = %entry.id, error = %err, "Invalid stored schedule");
+                    None
+                }
+            };
+            match store.claim(entry.id, due_at, next, now) {
+                Ok(true) => claimed.push(entry),
+                Ok(false) => {}
+                Err(e) => {
+                    return Err(ServiceError::Internal(format!(
+                        "Failed to claim schedule: {}",
+                        e
+                    )))
+                }
+            }
+        }
+        Ok(claimed)
+    }
+
+    /// The workspace a run should use, creating a new one when the entry
+    /// does not name one
+    pub fn resolve_workspace(
+        core: &ConduitCore,
+        entry: &ScheduledPrompt,
+    ) -> Result<Workspace, ServiceError> {
+        if let Some(workspace_id) = entry.workspace_id {
+            let workspace = Self::workspace(core, workspace_id)?;
+            if workspace.archived_at.is_some() {
+                return Err(ServiceError::InvalidInput(format!(
+                    "Workspace {} is archived",
+                    workspace.name
+                )));
+            }
+            return Ok(workspace);
+        }
+
+        let repo = core
+            .repo_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?
+            .get_by_id(entry.repository_id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to get repository: {}", e)))?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Repository {} not found", entry.repository_id))
+            })?;
+        WorkspaceService::create_workspace(core, &repo)
+    }
+
+    /// Queue the prompt on the workspace's session, opening one if needed.
+    /// The caller runs setup for a newly created workspace before starting
+    /// the prompt.
+    pub fn deliver(
+        core: &ConduitCore,
+        entry: &ScheduledPrompt,
+    ) -> Result<ScheduleDelivery, ServiceError> {
+        let workspace = Self::resolve_workspace(core, entry)?;
+        let created_workspace = entry.workspace_id.is_none();
+        let session = SessionService::get_or_create_session_for_workspace(core, workspace.id)?;
+        SessionService::add_queue_message(
+            core,
+            session.id,
+            QueuedMessageMode::FollowUp,
+            entry.prompt.clone(),
+            Vec::new(),
+        )?;
+        Ok(ScheduleDelivery {
+            workspace_id: workspace.id,
+            session_id: session.id,
+            created_workspace,
+        })
+    }
+
+    /// Record the outcome of a run; `error` is None when it was queued
+    pub fn record_run(core: &ConduitCore, id: Uuid, ran_at: DateTime<Utc>, error: Option<&str>) {
+        let Ok(store) = Self::store(core) else {
+            return;
+        };
+        if let Err(e) = store.record_run(id, ran_at, error) {
+            tracing::warn!(schedule_id = %id, error = %e, "Failed to record schedule run");
+        }
+    }
+
+    fn store(core: &ConduitCore) -> Result<&ScheduledPromptStore, ServiceError> {
+        core.scheduled_prompt_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))
+    }
+
+    fn workspace(core: &ConduitCore, id: Uuid) -> Result<Workspace, ServiceError> {
+        core.workspace_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?
+            .get_by_id(id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to get workspace: {}", e)))?
+            .ok_or_else(|| ServiceError::NotFound(format!("Workspace {} not found", id)))
+    }
+
+    fn parse_schedule(schedule: &str) -> Result<CronSchedule, ServiceError> {
+        schedule
+            .parse()
+            .map_err(|e| ServiceError::InvalidInput(format!("Invalid schedule: {}", e)))
+    }
+
+    fn validate_prompt(prompt: &str) -> Result<String, ServiceError> {
+        let prompt = prompt.trim();
+        if prompt.is_empty() {
+            return Err(ServiceError::InvalidInput(
+                "Prompt must not be empty".to_string(),
+            ));
+        }
+        Ok(prompt.to_string())
+    }
+}
+
+/// First line of the prompt, shortened for lists
+fn default_name(prompt: &str) -> String {
+    const MAX_CHARS: usize = 40;
+    let first_line = prompt.lines().next().unwrap_or_default().trim();
+    if first_line.chars().count() <= MAX_CHARS {
+        first_line.to_string()
+    } else {
+        let truncated: String = first_line.chars().take(MAX_CHARS - 1).collect();
+        format!("{}…", truncated.trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_name_uses_first_line() {
+        assert_eq!(default_name("run tests\nthen fix"), "run tests");
+        let long = "update every dependency in the workspace and open a pull request";
+        let name = default_name(long);
+        assert!(name.ends_with('…'));
+        assert!(name.chars().count() <= 40);
+    }
+}
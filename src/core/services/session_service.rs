@@ -2,7 +2,7 @@ use chrono::Utc;
 use rusqlite::{params, Error as SqliteError, ErrorCode, Result as SqliteResult};
 use uuid::Uuid;
 
-use crate::agent::{AgentMode, AgentType, ModelRegistry};
+use crate::agent::{AgentMode, AgentType, CodexApprovalMode, CodexSandboxMode, ModelRegistry};
 use crate::core::services::error::ServiceError;
 use crate::core::ConduitCore;
 use crate::data::{
@@ -23,6 +23,10 @@ pub struct UpdateSessionParams {
     pub model: Option<String>,
     pub agent_type: Option<AgentType>,
     pub agent_mode: Option<AgentMode>,
+    /// Codex approval mode; applies when the agent next starts or resumes
+    pub codex_approval: Option<CodexApprovalMode>,
+    /// Codex sandbox mode; applies when the agent next starts or resumes
+    pub codex_sandbox: Option<CodexSandboxMode>,
 }
 
 pub struct SessionService;
@@ -166,6 +170,20 @@ impl SessionService {
             session.model_invalid = false;
         }
 
+        if params.codex_approval.is_some() || params.codex_sandbox.is_some() {
+            if session.agent_type != AgentType::Codex {
+                return Err(ServiceError::InvalidInput(
+                    "Sandbox and approval modes are only supported for Codex sessions".to_string(),
+                ));
+            }
+            if let Some(mode) = params.codex_approval {
+                session.codex_approval = Some(mode);
+            }
+            if let Some(mode) = params.codex_sandbox {
+                session.codex_sandbox = Some(mode);
+            }
+        }
+
         store
             .update(&session)
             .map_err(|e| ServiceError::Internal(format!("Failed to update session: {}", e)))?;
@@ -0,0 +1,150 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::agent::{AgentType, TokenUsage};
+use crate::core::services::error::ServiceError;
+use crate::core::ConduitCore;
+use crate::data::{UsageGroupBy, UsageRecord, UsageScope, UsageSummaryRow, UsageTotals};
+
+/// Where a completed turn ran, for attributing its usage in the ledger.
+#[derive(Debug, Clone)]
+pub struct TurnUsageContext {
+    pub session_id: Option<Uuid>,
+    pub agent_session_id: Option<String>,
+    pub agent_type: AgentType,
+    pub model: Option<String>,
+    pub workspace_id: Option<Uuid>,
+}
+
+pub struct UsageService;
+
+impl UsageService {
+    /// Append a completed turn's usage to the ledger.
+    ///
+    /// Turns that report no tokens are skipped. Returns the stored record.
+    pub fn record_turn(
+        core: &ConduitCore,
+        context: TurnUsageContext,
+        usage: &TokenUsage,
+    ) -> Result<Option<UsageRecord>, ServiceError> {
+        if usage.is_empty() {
+            return Ok(None);
+        }
+        let store = core
+            .usage_ledger_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+
+        let repository_id = Self::repository_for_workspace(core, context.workspace_id)?;
+
+        let mut record = UsageRecord::new(context.agent_type, context.model, usage);
+        record.cost_usd =
+            core.config()
+                .calculate_cost(context.agent_type, record.model.as_deref(), usage);
+        record.session_id = context.session_id;
+        record.agent_session_id = context.agent_session_id;
+        record.workspace_id = context.workspace_id;
+        record.repository_id = repository_id;
+
+        store
+            .record(&record)
+            .map_err(|e| ServiceError::Internal(format!("Failed to record usage: {}", e)))?;
+        Ok(Some(record))
+    }
+
+    /// Resolve the repository a workspace belongs to, for attributing usage
+    pub(crate) fn repository_for_workspace(
+        core: &ConduitCore,
+        workspace_id: Option<Uuid>,
+    ) -> Result<Option<Uuid>, ServiceError> {
+        match (workspace_id, core.workspace_store()) {
+            (Some(workspace_id), Some(workspace_store)) => Ok(workspace_store
+                .get_by_id(workspace_id)
+                .map_err(|e| ServiceError::Internal(format!("Failed to load workspace: {}", e)))?
+                .map(|workspace| workspace.repository_id)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Parse a `since` filter: an RFC 3339 timestamp, a `YYYY-MM-DD` date
+    /// (midnight UTC) or a relative number of days such as `30d`.
+    pub fn parse_since(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, ServiceError> {
+        let value = value.trim();
+        if let Some(days) = value.strip_suffix('d') {
+            if let Ok(days) = days.parse::<i64>() {
+                return Duration::try_days(days.max(0))
+                    .and_then(|days| now.checked_sub_signed(days))
+                    .ok_or_else(|| {
+                        ServiceError::InvalidInput(format!("Invalid since: {value}. Too many days"))
+                    });
+            }
+        }
+        if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+            return Ok(at.with_timezone(&Utc));
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            if let Some(midnight) = date.and_hms_opt(0, 0, 0) {
+                return Ok(midnight.and_utc());
+            }
+        }
+        Err(ServiceError::InvalidInput(format!(
+            "Invalid since: {value}. Use an RFC 3339 timestamp, YYYY-MM-DD or a day count like 30d"
+        )))
+    }
+
+    /// Summarize ledger entries recorded since `since`.
+    pub fn summarize(
+        core: &ConduitCore,
+        since: Option<DateTime<Utc>>,
+        group_by: UsageGroupBy,
+    ) -> Result<Vec<UsageSummaryRow>, ServiceError> {
+        let store = core
+            .usage_ledger_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+        store
+            .summarize(since, group_by)
+            .map_err(|e| ServiceError::Internal(format!("Failed to summarize usage: {}", e)))
+    }
+
+    /// Everything recorded for one Conduit session.
+    pub fn session_totals(
+        core: &ConduitCore,
+        session_id: Uuid,
+    ) -> Result<UsageTotals, ServiceError> {
+        let store = core
+            .usage_ledger_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+        store
+            .totals(UsageScope::Session(session_id), None)
+            .map_err(|e| ServiceError::Internal(format!("Failed to load usage totals: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_since_formats() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 31, 12, 0, 0).unwrap();
+
+        assert_eq!(
+            UsageService::parse_since("7d", now).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 24, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            UsageService::parse_since("2026-03-01", now).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            UsageService::parse_since("2026-03-01T08:30:00+02:00", now).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 1, 6, 30, 0).unwrap()
+        );
+        assert!(UsageService::parse_since("last month", now).is_err());
+        assert!(matches!(
+            UsageService::parse_since("9223372036854775807d", now),
+            Err(ServiceError::InvalidInput(_))
+        ));
+        assert!(UsageService::parse_since("100000000d", now).is_err());
+    }
+}
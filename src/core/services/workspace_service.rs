@@ -0,0 +1,91 @@
+use std::path::Path;
+
+use crate::core::resolve_repo_workspace_settings;
+use crate::core::services::error::ServiceError;
+use crate::core::ConduitCore;
+use crate::data::{Repository, Workspace};
+use crate::util::names::{generate_branch_name, generate_workspace_name, get_git_username};
+
+pub struct WorkspaceService;
+
+impl WorkspaceService {
+    /// Look up the repository registered at `path`, registering it first if
+    /// Conduit has not seen it before.
+    pub fn ensure_repository(core: &ConduitCore, path: &Path) -> Result<Repository, ServiceError> {
+        let path = path.canonicalize().map_err(|e| {
+            ServiceError::InvalidInput(format!("Invalid repository path {}: {}", path.display(), e))
+        })?;
+        if !core.worktree_manager().is_git_repo(&path) {
+            return Err(ServiceError::InvalidInput(format!(
+                "{} is not a git repository",
+                path.display()
+            )));
+        }
+
+        let store = core
+            .repo_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+        if let Some(existing) = store
+            .get_by_path(&path)
+            .map_err(|e| ServiceError::Internal(format!("Failed to query repository: {}", e)))?
+        {
+            return Ok(existing);
+        }
+
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("Unknown")
+            .to_string();
+        let repo = Repository::from_local_path(name, path);
+        store
+            .create(&repo)
+            .map_err(|e| ServiceError::Internal(format!("Failed to create repository: {}", e)))?;
+        Ok(repo)
+    }
+
+    /// Create a new workspace with a generated name and branch, mirroring the
+    /// TUI's workspace creation flow.
+    pub fn create_workspace(
+        core: &ConduitCore,
+        repo: &Repository,
+    ) -> Result<Workspace, ServiceError> {
+        let store = core
+            .workspace_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+        let repo_path = repo
+            .base_path
+            .clone()
+            .ok_or_else(|| ServiceError::InvalidInput("Repository has no base path".to_string()))?;
+
+        let existing_names = store
+            .get_all_names_by_repository(repo.id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to get workspace names: {}", e)))?;
+        let workspace_name = generate_workspace_name(&existing_names);
+        let branch_name = generate_branch_name(&get_git_username(), &workspace_name);
+
+        let settings = resolve_repo_workspace_settings(core.config(), repo);
+        let workspace_path = core
+            .worktree_manager()
+            .create_workspace(settings.mode, &repo_path, &branch_name, &workspace_name)
+            .map_err(|e| ServiceError::Internal(format!("Failed to create workspace: {}", e)))?;
+
+        let workspace = Workspace::new(repo.id, &workspace_name, &branch_name, workspace_path);
+        store.create(&workspace).map_err(|e| {
+            if let Err(err) =
+                core.worktree_manager()
+                    .remove_workspace(settings.mode, &repo_path, &workspace.path)
+            {
+                tracing::warn!(
+                    error = %err,
+                    repo_path = %repo_path.display(),
+                    workspace_path = %workspace.path.display(),
+                    "Failed to remove workspace after workspace save failure"
+                );
+            }
+            ServiceError::Internal(format!("Failed to save workspace: {}", e))
+        })?;
+
+        Ok(workspace)
+    }
+}
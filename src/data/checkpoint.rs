@@ -0,0 +1,200 @@
+//! Checkpoint data access object
+
+use super::models::Checkpoint;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Result as SqliteResult};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Data access object for per-turn checkpoint operations
+#[derive(Clone)]
+pub struct CheckpointStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl CheckpointStore {
+    /// Create a new CheckpointStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Insert a new checkpoint
+    pub fn create(&self, checkpoint: &Checkpoint) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO checkpoints (id, workspace_id, session_id, turn, ref_name, commit_sha, created_at, prompt, files_changed)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                checkpoint.id.to_string(),
+                checkpoint.workspace_id.to_string(),
+                checkpoint.session_id.map(|id| id.to_string()),
+                checkpoint.turn,
+                checkpoint.ref_name,
+                checkpoint.commit_sha,
+                checkpoint.created_at.to_rfc3339(),
+                checkpoint.prompt,
+                encode_files(&checkpoint.files_changed),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Record the snapshot commit once it has been written
+    pub fn set_commit(&self, id: Uuid, commit_sha: &str) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE checkpoints SET commit_sha = ?2 WHERE id = ?1",
+            params![id.to_string(), commit_sha],
+        )?;
+        Ok(())
+    }
+
+    /// Record the files changed by the turn that followed the checkpoint
+    pub fn set_files_changed(&self, id: Uuid, files: &[String]) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE checkpoints SET files_changed = ?2 WHERE id = ?1",
+            params![id.to_string(), encode_files(files)],
+        )?;
+        Ok(())
+    }
+
+    /// Get a checkpoint by ID
+    pub fn get_by_id(&self, id: Uuid) -> SqliteResult<Option<Checkpoint>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, workspace_id, session_id, turn, ref_name, commit_sha, created_at, prompt, files_changed
+             FROM checkpoints WHERE id = ?1",
+        )?;
+
+        let mut rows = stmt.query(params![id.to_string()])?;
+        if let Some(row) = rows.next()? {
+            Ok(Some(Self::row_to_checkpoint(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Completed checkpoints for a workspace, newest first
+    pub fn list_for_workspace(&self, workspace_id: Uuid) -> SqliteResult<Vec<Checkpoint>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, workspace_id, session_id, turn, ref_name, commit_sha, created_at, prompt, files_changed
+             FROM checkpoints
+             WHERE workspace_id = ?1 AND commit_sha IS NOT NULL
+             ORDER BY created_at DESC",
+        )?;
+
+        let checkpoints = stmt
+            .query_map(params![workspace_id.to_string()], Self::row_to_checkpoint)?
+            .collect::<SqliteResult<Vec<_>>>()?;
+        Ok(checkpoints)
+    }
+
+    /// Delete a checkpoint by ID
+    pub fn delete(&self, id: Uuid) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM checkpoints WHERE id = ?1",
+            params![id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Convert a database row to a Checkpoint
+    fn row_to_checkpoint(row: &rusqlite::Row) -> SqliteResult<Checkpoint> {
+        let id_str: String = row.get(0)?;
+        let workspace_id_str: String = row.get(1)?;
+        let session_id_str: Option<String> = row.get(2)?;
+        let created_at_str: String = row.get(6)?;
+        let files_changed: String = row.get(8)?;
+
+        Ok(Checkpoint {
+            id: Uuid::parse_str(&id_str).unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Invalid UUID in checkpoints table: {}, error: {}",
+                    id_str,
+                    e
+                );
+                Uuid::new_v4()
+            }),
+            workspace_id: Uuid::parse_str(&workspace_id_str).unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Invalid workspace_id UUID in checkpoints table: {}, error: {}",
+                    workspace_id_str,
+                    e
+                );
+                Uuid::nil()
+            }),
+            session_id: session_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+            turn: row.get(3)?,
+            ref_name: row.get(4)?,
+            commit_sha: row.get(5)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|e| {
+                    tracing::warn!(
+                        "Invalid DateTime in checkpoints table: {}, error: {}",
+                        created_at_str,
+                        e
+                    );
+                    Utc::now()
+                }),
+            prompt: row.get(7)?,
+            files_changed: serde_json::from_str(&files_changed).unwrap_or_else(|e| {
+                tracing::warn!("Invalid files_changed in checkpoints table: {}", e);
+                Vec::new()
+            }),
+        })
+    }
+}
+
+fn encode_files(files: &[String]) -> String {
+    serde_json::to_string(files).unwrap_or_else(|_| "[]".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Database;
+    use chrono::Duration;
+    use tempfile::tempdir;
+
+    fn setup_db() -> (tempfile::TempDir, Database, CheckpointStore) {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let dao = CheckpointStore::new(db.connection());
+        (dir, db, dao)
+    }
+
+    #[test]
+    fn test_list_skips_pending_and_orders_newest_first() {
+        let (_dir, _db, dao) = setup_db();
+        let workspace_id = Uuid::new_v4();
+
+        let mut first = Checkpoint::new(workspace_id, None, 1);
+        first.created_at = Utc::now() - Duration::minutes(5);
+        dao.create(&first).unwrap();
+        dao.set_commit(first.id, "abc123").unwrap();
+        dao.set_files_changed(first.id, &["src/lib.rs".to_string()])
+            .unwrap();
+
+        let second = Checkpoint::new(workspace_id, None, 2);
+        dao.create(&second).unwrap();
+        dao.set_commit(second.id, "def456").unwrap();
+
+        // Snapshot still running: not restorable yet
+        dao.create(&Checkpoint::new(workspace_id, None, 3)).unwrap();
+        dao.create(&Checkpoint::new(Uuid::new_v4(), None, 1))
+            .unwrap();
+
+        let listed = dao.list_for_workspace(workspace_id).unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].id, second.id);
+        assert_eq!(listed[1].commit_sha.as_deref(), Some("abc123"));
+        assert_eq!(listed[1].files_changed, vec!["src/lib.rs".to_string()]);
+
+        dao.delete(second.id).unwrap();
+        assert!(dao.get_by_id(second.id).unwrap().is_none());
+    }
+}
@@ -16,8 +16,11 @@ CREATE TABLE IF NOT EXISTS repositories (
     workspace_mode TEXT,
     archive_delete_branch INTEGER,
     archive_remote_prompt INTEGER,
+    approval_policy TEXT,
     created_at TEXT NOT NULL,
-    updated_at TEXT NOT NULL
+    updated_at TEXT NOT NULL,
+    codex_approval_mode TEXT,
+    codex_sandbox_mode TEXT
 );
 
 CREATE TABLE IF NOT EXISTS workspaces (
@@ -36,6 +39,14 @@ CREATE TABLE IF NOT EXISTS workspaces (
 
 CREATE INDEX IF NOT EXISTS idx_workspaces_repository ON workspaces(repository_id);
 
+CREATE TABLE IF NOT EXISTS repository_allowed_tools (
+    repository_id TEXT NOT NULL,
+    tool_name TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (repository_id, tool_name),
+    FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+);
+
 CREATE TABLE IF NOT EXISTS app_state (
     key TEXT PRIMARY KEY,
     value TEXT NOT NULL,
@@ -60,6 +71,8 @@ CREATE TABLE IF NOT EXISTS session_tabs (
     fork_seed_id TEXT,
     title TEXT,
     title_generated INTEGER NOT NULL DEFAULT 0,
+    codex_approval_mode TEXT,
+    codex_sandbox_mode TEXT,
     FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE SET NULL
 );
 
@@ -82,6 +95,74 @@ CREATE TABLE IF NOT EXISTS fork_seeds (
 );
 
 CREATE INDEX IF NOT EXISTS idx_fork_seeds_parent_session ON fork_seeds(parent_session_id);
+
+CREATE TABLE IF NOT EXISTS usage_ledger (
+    id TEXT PRIMARY KEY,
+    recorded_at TEXT NOT NULL,
+    session_id TEXT,
+    agent_session_id TEXT,
+    agent_type TEXT NOT NULL,
+    model TEXT,
+    workspace_id TEXT,
+    repository_id TEXT,
+    input_tokens INTEGER NOT NULL DEFAULT 0,
+    output_tokens INTEGER NOT NULL DEFAULT 0,
+    cached_tokens INTEGER NOT NULL DEFAULT 0,
+    total_tokens INTEGER NOT NULL DEFAULT 0,
+    cost_usd REAL
+);
+
+CREATE INDEX IF NOT EXISTS idx_usage_ledger_recorded_at ON usage_ledger(recorded_at);
+
+CREATE TABLE IF NOT EXISTS checkpoints (
+    id TEXT PRIMARY KEY,
+    workspace_id TEXT NOT NULL,
+    session_id TEXT,
+    turn INTEGER NOT NULL,
+    ref_name TEXT NOT NULL,
+    commit_sha TEXT,
+    created_at TEXT NOT NULL,
+    prompt TEXT,
+    files_changed TEXT NOT NULL DEFAULT '[]'
+);
+
+CREATE INDEX IF NOT EXISTS idx_checkpoints_workspace ON checkpoints(workspace_id, created_at);
+
+CREATE TABLE IF NOT EXISTS scheduled_prompts (
+    id TEXT PRIMARY KEY,
+    repository_id TEXT NOT NULL,
+    workspace_id TEXT,
+    name TEXT NOT NULL,
+    schedule TEXT NOT NULL,
+    prompt TEXT NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    next_run_at TEXT,
+    last_run_at TEXT,
+    last_error TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS history_sources (
+    key TEXT PRIMARY KEY,
+    agent_type TEXT NOT NULL,
+    agent_session_id TEXT NOT NULL,
+    session_tab_id TEXT,
+    workspace_id TEXT,
+    title TEXT,
+    project TEXT,
+    file_path TEXT NOT NULL,
+    fingerprint TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS history_fts USING fts5(
+    content,
+    source_key UNINDEXED,
+    message_index UNINDEXED,
+    role UNINDEXED,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
 "#;
 
 #[derive(Error, Debug)]
@@ -532,6 +613,41 @@ CREATE TABLE IF NOT EXISTS fork_seeds_new (
             [],
         )?;
 
+        // Migration 14: Add tool approval policy override to repositories table
+        let has_approval_policy: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('repositories') WHERE name='approval_policy'",
+                [],
+                |row| row.get::<_, i64>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_approval_policy {
+            conn.execute(
+                "ALTER TABLE repositories ADD COLUMN approval_policy TEXT",
+                [],
+            )?;
+        }
+
+        // Migration 15: Add Codex sandbox/approval modes to repositories and session_tabs
+        for table in ["repositories", "session_tabs"] {
+            for column in ["codex_approval_mode", "codex_sandbox_mode"] {
+                let has_column: bool = conn
+                    .query_row(
+                        &format!(
+                            "SELECT COUNT(*) FROM pragma_table_info('{table}') WHERE name='{column}'"
+                        ),
+                        [],
+                        |row| row.get::<_, i64>(0).map(|c| c > 0),
+                    )
+                    .unwrap_or(false);
+
+                if !has_column {
+                    conn.execute(&format!("ALTER TABLE {table} ADD COLUMN {column} TEXT"), [])?;
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -586,6 +702,12 @@ mod tests {
                 .collect();
             assert!(tables.contains(&"repositories".to_string()));
             assert!(tables.contains(&"workspaces".to_string()));
+            assert!(tables.contains(&"usage_ledger".to_string()));
+            assert!(tables.contains(&"checkpoints".to_string()));
+            assert!(tables.contains(&"repository_allowed_tools".to_string()));
+            assert!(tables.contains(&"scheduled_prompts".to_string()));
+            assert!(tables.contains(&"history_sources".to_string()));
+            assert!(tables.contains(&"history_fts".to_string()));
             Ok(())
         })
         .unwrap();
@@ -0,0 +1,286 @@
+//! Full-text index over session transcripts (SQLite FTS5)
+
+use super::models::{HistorySearchHit, HistorySource};
+use crate::agent::AgentType;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Source columns read back by `row_to_source`
+const SOURCE_COLUMNS: &str = "s.key, s.agent_type, s.agent_session_id, s.session_tab_id, s.workspace_id, s.title, s.project, s.file_path, s.fingerprint, s.updated_at";
+
+/// Data access object for the cross-session history index
+#[derive(Clone)]
+pub struct HistoryIndexStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl HistoryIndexStore {
+    /// Create a new HistoryIndexStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Look up an indexed source by key
+    pub fn get_source(&self, key: &str) -> SqliteResult<Option<HistorySource>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!("SELECT {SOURCE_COLUMNS} FROM history_sources s WHERE s.key = ?1"),
+            params![key],
+            Self::row_to_source,
+        )
+        .optional()
+    }
+
+    /// All indexed sources
+    pub fn get_sources(&self) -> SqliteResult<Vec<HistorySource>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!("SELECT {SOURCE_COLUMNS} FROM history_sources s"))?;
+        let rows = stmt.query_map([], Self::row_to_source)?;
+        rows.collect()
+    }
+
+    /// Replace a source's messages, given as (role, text) in transcript order
+    pub fn replace(
+        &self,
+        source: &HistorySource,
+        messages: &[(String, String)],
+    ) -> SqliteResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM history_fts WHERE source_key = ?1",
+            params![source.key],
+        )?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO history_fts (content, source_key, message_index, role)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (index, (role, text)) in messages.iter().enumerate() {
+                if text.trim().is_empty() {
+                    continue;
+                }
+                insert.execute(params![text, source.key, index as i64, role])?;
+            }
+        }
+        Self::upsert_source(&tx, source)?;
+        tx.commit()
+    }
+
+    /// Update a source's metadata (title, session tab) without reindexing it
+    pub fn update_source(&self, source: &HistorySource) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        Self::upsert_source(&conn, source)
+    }
+
+    /// Drop a source and its messages
+    pub fn remove(&self, key: &str) -> SqliteResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM history_fts WHERE source_key = ?1",
+            params![key],
+        )?;
+        tx.execute("DELETE FROM history_sources WHERE key = ?1", params![key])?;
+        tx.commit()
+    }
+
+    /// Best matches for a free-text query, most relevant first
+    pub fn search(&self, query: &str, limit: usize) -> SqliteResult<Vec<HistorySearchHit>> {
+        let Some(match_expr) = Self::match_expression(query) else {
+            return Ok(Vec::new());
+        };
+        let sql = format!(
+            "SELECT {SOURCE_COLUMNS}, f.message_index, f.role,
+                    snippet(history_fts, 0, ?2, ?3, '…', 16)
+             FROM history_fts f
+             JOIN history_sources s ON s.key = f.source_key
+             WHERE history_fts MATCH ?1
+             ORDER BY bm25(history_fts), s.updated_at DESC
+             LIMIT ?4"
+        );
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(
+            params![
+                match_expr,
+                HistorySearchHit::MATCH_START.to_string(),
+                HistorySearchHit::MATCH_END.to_string(),
+                limit as i64
+            ],
+            |row| {
+                let message_index: i64 = row.get(10)?;
+                Ok(HistorySearchHit {
+                    source: Self::row_to_source(row)?,
+                    message_index: message_index.max(0) as usize,
+                    role: row.get(11)?,
+                    snippet: row.get(12)?,
+                })
+            },
+        )?;
+        rows.collect()
+    }
+
+    /// Turn free text into an FTS5 query: every word must match, the last
+    /// one as a prefix so results show up while typing
+    fn match_expression(query: &str) -> Option<String> {
+        let terms: Vec<String> = query
+            .split_whitespace()
+            .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+            .collect();
+        let (last, rest) = terms.split_last()?;
+        let mut expr = rest.join(" ");
+        if !expr.is_empty() {
+            expr.push(' ');
+        }
+        expr.push_str(last);
+        expr.push('*');
+        Some(expr)
+    }
+
+    fn upsert_source(conn: &Connection, source: &HistorySource) -> SqliteResult<()> {
+        conn.execute(
+            "INSERT INTO history_sources (key, agent_type, agent_session_id, session_tab_id, workspace_id, title, project, file_path, fingerprint, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+             ON CONFLICT(key) DO UPDATE SET
+                session_tab_id = excluded.session_tab_id,
+                workspace_id = excluded.workspace_id,
+                title = excluded.title,
+                project = excluded.project,
+                file_path = excluded.file_path,
+                fingerprint = excluded.fingerprint,
+                updated_at = excluded.updated_at",
+            params![
+                source.key,
+                source.agent_type.as_str(),
+                source.agent_session_id,
+                source.session_tab_id.map(|id| id.to_string()),
+                source.workspace_id.map(|id| id.to_string()),
+                source.title,
+                source.project,
+                source.file_path.to_string_lossy(),
+                source.fingerprint,
+                source.updated_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn row_to_source(row: &rusqlite::Row) -> SqliteResult<HistorySource> {
+        let agent_type: String = row.get(1)?;
+        let session_tab_id: Option<String> = row.get(3)?;
+        let workspace_id: Option<String> = row.get(4)?;
+        let file_path: String = row.get(7)?;
+        let updated_at: String = row.get(9)?;
+        Ok(HistorySource {
+            key: row.get(0)?,
+            agent_type: AgentType::parse(&agent_type),
+            agent_session_id: row.get(2)?,
+            session_tab_id: session_tab_id.and_then(|id| Uuid::parse_str(&id).ok()),
+            workspace_id: workspace_id.and_then(|id| Uuid::parse_str(&id).ok()),
+            title: row.get(5)?,
+            project: row.get(6)?,
+            file_path: PathBuf::from(file_path),
+            fingerprint: row.get(8)?,
+            updated_at: DateTime::parse_from_rfc3339(&updated_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Database;
+    use tempfile::tempdir;
+
+    fn setup_db() -> (tempfile::TempDir, Database, HistoryIndexStore) {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let dao = HistoryIndexStore::new(db.connection());
+        (dir, db, dao)
+    }
+
+    fn source(agent_session_id: &str, title: &str) -> HistorySource {
+        HistorySource {
+            key: HistorySource::key_for(AgentType::Claude, agent_session_id),
+            agent_type: AgentType::Claude,
+            agent_session_id: agent_session_id.to_string(),
+            session_tab_id: None,
+            workspace_id: None,
+            title: Some(title.to_string()),
+            project: Some("/src/app".to_string()),
+            file_path: PathBuf::from(format!("/tmp/{agent_session_id}.jsonl")),
+            fingerprint: "1:1".to_string(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn messages(texts: &[(&str, &str)]) -> Vec<(String, String)> {
+        texts
+            .iter()
+            .map(|(role, text)| (role.to_string(), text.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_search_finds_messages_across_sessions() {
+        let (_dir, _db, dao) = setup_db();
+        dao.replace(
+            &source("a", "Login flow"),
+            &messages(&[
+                ("user", "The OAuth redirect loops forever"),
+                (
+                    "assistant",
+                    "Fixed the redirect URI in the callback handler",
+                ),
+            ]),
+        )
+        .unwrap();
+        dao.replace(
+            &source("b", "Styling"),
+            &messages(&[("user", "Make the header blue")]),
+        )
+        .unwrap();
+
+        let hits = dao.search("oauth redir", 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].source.agent_session_id, "a");
+        assert_eq!(hits[0].message_index, 0);
+        assert_eq!(hits[0].role, "user");
+        assert!(hits[0].snippet.contains("\u{2}OAuth\u{3}"));
+        assert_eq!(hits[0].matched_term(), Some("OAuth"));
+
+        assert_eq!(dao.search("redirect", 10).unwrap().len(), 2);
+        assert!(dao.search("   ", 10).unwrap().is_empty());
+        // Quotes and FTS operators are taken literally
+        assert!(dao.search("\"header\" OR", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_replace_and_remove_source() {
+        let (_dir, _db, dao) = setup_db();
+        let mut src = source("a", "Login flow");
+        dao.replace(&src, &messages(&[("user", "first attempt")]))
+            .unwrap();
+        src.fingerprint = "2:2".to_string();
+        dao.replace(&src, &messages(&[("user", "second attempt")]))
+            .unwrap();
+
+        assert!(dao.search("first", 10).unwrap().is_empty());
+        assert_eq!(dao.search("second", 10).unwrap().len(), 1);
+        assert_eq!(
+            dao.get_source(&src.key).unwrap().unwrap().fingerprint,
+            "2:2"
+        );
+
+        dao.remove(&src.key).unwrap();
+        assert!(dao.get_source(&src.key).unwrap().is_none());
+        assert!(dao.search("second", 10).unwrap().is_empty());
+    }
+}
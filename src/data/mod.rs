@@ -3,20 +3,29 @@
 //! This module provides SQLite-based storage for repositories and workspaces.
 
 mod app_state;
+mod checkpoint;
 mod database;
 mod fork_seed;
+mod history_index;
 mod models;
 mod repository;
+mod scheduled_prompt;
 mod session_tab;
+mod usage_ledger;
 mod workspace;
 
 pub use app_state::AppStateStore;
+pub use checkpoint::CheckpointStore;
 pub use database::Database;
 pub use fork_seed::ForkSeedStore;
+pub use history_index::HistoryIndexStore;
 pub use models::{
-    ForkSeed, QueuedImageAttachment, QueuedMessage, QueuedMessageMode, Repository, SessionTab,
-    Workspace,
+    Checkpoint, ForkSeed, HistorySearchHit, HistorySource, QueuedImageAttachment, QueuedMessage,
+    QueuedMessageMode, Repository, ScheduledPrompt, SessionTab, UsageGroupBy, UsageRecord,
+    UsageSummaryRow, UsageTotals, Workspace,
 };
 pub use repository::RepositoryStore;
+pub use scheduled_prompt::ScheduledPromptStore;
 pub use session_tab::SessionTabStore;
+pub use usage_ledger::{UsageLedgerStore, UsageScope};
 pub use workspace::WorkspaceStore;
@@ -5,8 +5,8 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use uuid::Uuid;
 
-use crate::agent::AgentType;
-use crate::git::WorkspaceMode;
+use crate::agent::{AgentType, ApprovalPolicy, CodexApprovalMode, CodexSandboxMode, TokenUsage};
+use crate::git::{CheckpointManager, WorkspaceMode};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
@@ -56,6 +56,12 @@ pub struct Repository {
     pub archive_delete_branch: Option<bool>,
     /// Whether to prompt for remote deletion on archive (None = config default)
     pub archive_remote_prompt: Option<bool>,
+    /// Tool approval policy override (None = config default)
+    pub approval_policy: Option<ApprovalPolicy>,
+    /// Default Codex approval mode for new sessions (None = config default)
+    pub codex_approval_mode: Option<CodexApprovalMode>,
+    /// Default Codex sandbox mode for new sessions (None = config default)
+    pub codex_sandbox_mode: Option<CodexSandboxMode>,
     /// When the repository was added
     pub created_at: DateTime<Utc>,
     /// Last time the repository was modified
@@ -74,6 +80,9 @@ impl Repository {
             workspace_mode: None,
             archive_delete_branch: None,
             archive_remote_prompt: None,
+            approval_policy: None,
+            codex_approval_mode: None,
+            codex_sandbox_mode: None,
             created_at: now,
             updated_at: now,
         }
@@ -90,6 +99,9 @@ impl Repository {
             workspace_mode: None,
             archive_delete_branch: None,
             archive_remote_prompt: None,
+            approval_policy: None,
+            codex_approval_mode: None,
+            codex_sandbox_mode: None,
             created_at: now,
             updated_at: now,
         }
@@ -106,6 +118,10 @@ impl Repository {
     pub fn archive_remote_prompt_or(&self, default: bool) -> bool {
         self.archive_remote_prompt.unwrap_or(default)
     }
+
+    pub fn approval_policy_or(&self, default: ApprovalPolicy) -> ApprovalPolicy {
+        self.approval_policy.unwrap_or(default)
+    }
 }
 
 /// Represents a workspace (git worktree) within a repository
@@ -216,6 +232,10 @@ pub struct SessionTab {
     pub title: Option<String>,
     /// Whether a title has already been generated and persisted
     pub title_generated: bool,
+    /// Codex approval mode override (None = repository default)
+    pub codex_approval: Option<CodexApprovalMode>,
+    /// Codex sandbox mode override (None = repository default)
+    pub codex_sandbox: Option<CodexSandboxMode>,
 }
 
 impl SessionTab {
@@ -246,6 +266,8 @@ impl SessionTab {
             fork_seed_id: None,
             title: None,
             title_generated: false,
+            codex_approval: None,
+            codex_sandbox: None,
         }
     }
 }
@@ -299,3 +321,289 @@ impl ForkSeed {
         }
     }
 }
+
+/// Token usage and estimated cost for a single completed agent turn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageRecord {
+    /// Unique identifier
+    pub id: Uuid,
+    /// When the turn completed
+    pub recorded_at: DateTime<Utc>,
+    /// Session tab the turn ran in
+    pub session_id: Option<Uuid>,
+    /// Agent's own session ID
+    pub agent_session_id: Option<String>,
+    /// Agent type that ran the turn
+    pub agent_type: AgentType,
+    /// Model used for the turn
+    pub model: Option<String>,
+    /// Workspace the session belongs to
+    pub workspace_id: Option<Uuid>,
+    /// Repository the workspace belongs to
+    pub repository_id: Option<Uuid>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cached_tokens: i64,
+    pub total_tokens: i64,
+    /// Estimated cost in US dollars (None when the model has no known pricing)
+    pub cost_usd: Option<f64>,
+}
+
+impl UsageRecord {
+    pub fn new(agent_type: AgentType, model: Option<String>, usage: &TokenUsage) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            recorded_at: Utc::now(),
+            session_id: None,
+            agent_session_id: None,
+            agent_type,
+            model,
+            workspace_id: None,
+            repository_id: None,
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+            cached_tokens: usage.cached_tokens,
+            total_tokens: usage.total_tokens,
+            cost_usd: None,
+        }
+    }
+}
+
+/// Aggregated usage over a set of ledger entries
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageTotals {
+    pub turns: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cached_tokens: i64,
+    pub total_tokens: i64,
+    pub cost_usd: f64,
+}
+
+impl UsageTotals {
+    /// Combine the totals of several summary rows
+    pub fn sum<'a>(totals: impl IntoIterator<Item = &'a UsageTotals>) -> Self {
+        totals.into_iter().fold(Self::default(), |mut acc, t| {
+            acc.turns += t.turns;
+            acc.input_tokens += t.input_tokens;
+            acc.output_tokens += t.output_tokens;
+            acc.cached_tokens += t.cached_tokens;
+            acc.total_tokens += t.total_tokens;
+            acc.cost_usd += t.cost_usd;
+            acc
+        })
+    }
+}
+
+/// Usage totals for one group of a usage summary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageSummaryRow {
+    /// Group key (repository ID, model ID or UTC date)
+    pub key: String,
+    /// Human-readable group label
+    pub label: String,
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+}
+
+/// How usage summaries are grouped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageGroupBy {
+    Repo,
+    Model,
+    #[default]
+    Day,
+}
+
+impl UsageGroupBy {
+    pub const ALL: [UsageGroupBy; 3] = [UsageGroupBy::Day, UsageGroupBy::Repo, UsageGroupBy::Model];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsageGroupBy::Repo => "repo",
+            UsageGroupBy::Model => "model",
+            UsageGroupBy::Day => "day",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            UsageGroupBy::Repo => "Repository",
+            UsageGroupBy::Model => "Model",
+            UsageGroupBy::Day => "Day",
+        }
+    }
+}
+
+impl std::str::FromStr for UsageGroupBy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "repo" | "repository" => Ok(UsageGroupBy::Repo),
+            "model" => Ok(UsageGroupBy::Model),
+            "day" => Ok(UsageGroupBy::Day),
+            _ => Err(format!(
+                "Invalid group_by: {value}. Must be one of: repo, model, day"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for UsageGroupBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Worktree snapshot taken at the start of an agent turn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Workspace the snapshot was taken in
+    pub workspace_id: Uuid,
+    /// Session tab whose turn triggered the snapshot
+    pub session_id: Option<Uuid>,
+    /// 1-based turn number within the session
+    pub turn: i64,
+    /// Hidden git ref that keeps the snapshot commit alive
+    pub ref_name: String,
+    /// Snapshot commit (None until the snapshot finishes)
+    pub commit_sha: Option<String>,
+    /// When the turn started
+    pub created_at: DateTime<Utc>,
+    /// Prompt that started the turn, if known
+    pub prompt: Option<String>,
+    /// Files the turn changed, filled in when the turn ends
+    pub files_changed: Vec<String>,
+}
+
+impl Checkpoint {
+    pub fn new(workspace_id: Uuid, session_id: Option<Uuid>, turn: i64) -> Self {
+        let id = Uuid::new_v4();
+        Self {
+            id,
+            workspace_id,
+            session_id,
+            turn,
+            ref_name: CheckpointManager::ref_name(workspace_id, id),
+            commit_sha: None,
+            created_at: Utc::now(),
+            prompt: None,
+            files_changed: Vec::new(),
+        }
+    }
+}
+
+/// Prompt submitted to a workspace on a cron schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledPrompt {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Repository the prompt runs against
+    pub repository_id: Uuid,
+    /// Workspace to reuse (None creates a new workspace for every run)
+    pub workspace_id: Option<Uuid>,
+    /// Short label shown in lists
+    pub name: String,
+    /// Five-field cron expression, evaluated in local time
+    pub schedule: String,
+    /// Prompt text queued when the schedule fires
+    pub prompt: String,
+    /// Disabled entries are kept but never run
+    pub enabled: bool,
+    /// Next time the prompt is due (None when the schedule never fires again)
+    pub next_run_at: Option<DateTime<Utc>>,
+    /// When the prompt last ran
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// Why the last run failed, if it did
+    pub last_error: Option<String>,
+    /// When the entry was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl ScheduledPrompt {
+    pub fn new(
+        repository_id: Uuid,
+        workspace_id: Option<Uuid>,
+        name: impl Into<String>,
+        schedule: impl Into<String>,
+        prompt: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            repository_id,
+            workspace_id,
+            name: name.into(),
+            schedule: schedule.into(),
+            prompt: prompt.into(),
+            enabled: true,
+            next_run_at: None,
+            last_run_at: None,
+            last_error: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Whether the prompt should run at `now`
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.enabled && self.next_run_at.is_some_and(|next| next <= now)
+    }
+}
+
+/// A transcript in the cross-session search index
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistorySource {
+    /// Agent type and agent session ID, e.g. "claude:1b2c…"
+    pub key: String,
+    pub agent_type: AgentType,
+    /// Agent session ID the transcript is stored under
+    pub agent_session_id: String,
+    /// Conduit session tab that ran it, if any
+    pub session_tab_id: Option<Uuid>,
+    /// Workspace of that session tab
+    pub workspace_id: Option<Uuid>,
+    /// Session title or first prompt
+    pub title: Option<String>,
+    /// Project directory the session ran in
+    pub project: Option<String>,
+    /// Transcript file the messages were read from
+    pub file_path: PathBuf,
+    /// File modification time and size when indexed
+    pub fingerprint: String,
+    /// Last activity in the session
+    pub updated_at: DateTime<Utc>,
+}
+
+impl HistorySource {
+    pub fn key_for(agent_type: AgentType, agent_session_id: &str) -> String {
+        format!("{}:{}", agent_type.as_str(), agent_session_id)
+    }
+}
+
+/// One message matching a history search
+#[derive(Debug, Clone)]
+pub struct HistorySearchHit {
+    pub source: HistorySource,
+    /// Position of the message in the loaded transcript
+    pub message_index: usize,
+    /// Message role, e.g. "user" or "tool"
+    pub role: String,
+    /// Excerpt around the match; matched terms are wrapped in
+    /// `HistorySearchHit::MATCH_START` and `MATCH_END`
+    pub snippet: String,
+}
+
+impl HistorySearchHit {
+    pub const MATCH_START: char = '\u{2}';
+    pub const MATCH_END: char = '\u{3}';
+
+    /// The first highlighted term of the snippet
+    pub fn matched_term(&self) -> Option<&str> {
+        let start = self.snippet.find(Self::MATCH_START)? + Self::MATCH_START.len_utf8();
+        let len = self.snippet[start..].find(Self::MATCH_END)?;
+        Some(&self.snippet[start..start + len]).filter(|term| !term.trim().is_empty())
+    }
+}
@@ -1,6 +1,7 @@
 //! Repository data access object
 
 use super::models::Repository;
+use crate::agent::{ApprovalPolicy, CodexApprovalMode, CodexSandboxMode};
 use crate::git::WorkspaceMode;
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, Result as SqliteResult};
@@ -25,8 +26,8 @@ impl RepositoryStore {
     pub fn create(&self, repo: &Repository) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
-            "INSERT INTO repositories (id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            "INSERT INTO repositories (id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, approval_policy, created_at, updated_at, codex_approval_mode, codex_sandbox_mode)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
             params![
                 repo.id.to_string(),
                 repo.name,
@@ -37,8 +38,11 @@ impl RepositoryStore {
                 repo.workspace_mode.map(|mode| mode.as_str().to_string()),
                 repo.archive_delete_branch.map(|value| value as i32),
                 repo.archive_remote_prompt.map(|value| value as i32),
+                repo.approval_policy.map(|policy| policy.as_str().to_string()),
                 repo.created_at.to_rfc3339(),
                 repo.updated_at.to_rfc3339(),
+                repo.codex_approval_mode.map(|mode| mode.as_str()),
+                repo.codex_sandbox_mode.map(|mode| mode.as_str()),
             ],
         )?;
         Ok(())
@@ -48,7 +52,7 @@ impl RepositoryStore {
     pub fn get_by_id(&self, id: Uuid) -> SqliteResult<Option<Repository>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at
+            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, approval_policy, created_at, updated_at, codex_approval_mode, codex_sandbox_mode
              FROM repositories WHERE id = ?1",
         )?;
 
@@ -64,7 +68,7 @@ impl RepositoryStore {
     pub fn get_all(&self) -> SqliteResult<Vec<Repository>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at
+            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, approval_policy, created_at, updated_at, codex_approval_mode, codex_sandbox_mode
              FROM repositories ORDER BY name",
         )?;
 
@@ -79,7 +83,7 @@ impl RepositoryStore {
     pub fn update(&self, repo: &Repository) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
-            "UPDATE repositories SET name = ?2, base_path = ?3, repository_url = ?4, workspace_mode = ?5, archive_delete_branch = ?6, archive_remote_prompt = ?7, updated_at = ?8
+            "UPDATE repositories SET name = ?2, base_path = ?3, repository_url = ?4, workspace_mode = ?5, archive_delete_branch = ?6, archive_remote_prompt = ?7, approval_policy = ?8, updated_at = ?9, codex_approval_mode = ?10, codex_sandbox_mode = ?11
              WHERE id = ?1",
             params![
                 repo.id.to_string(),
@@ -89,7 +93,10 @@ impl RepositoryStore {
                 repo.workspace_mode.map(|mode| mode.as_str().to_string()),
                 repo.archive_delete_branch.map(|value| value as i32),
                 repo.archive_remote_prompt.map(|value| value as i32),
+                repo.approval_policy.map(|policy| policy.as_str().to_string()),
                 Utc::now().to_rfc3339(),
+                repo.codex_approval_mode.map(|mode| mode.as_str()),
+                repo.codex_sandbox_mode.map(|mode| mode.as_str()),
             ],
         )?;
         Ok(())
@@ -122,7 +129,7 @@ impl RepositoryStore {
         let conn = self.conn.lock().unwrap();
         let path_str = path.to_string_lossy().to_string();
         let mut stmt = conn.prepare(
-            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at
+            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, approval_policy, created_at, updated_at, codex_approval_mode, codex_sandbox_mode
              FROM repositories WHERE base_path = ?1",
         )?;
 
@@ -141,8 +148,11 @@ impl RepositoryStore {
         let workspace_mode_raw: Option<String> = row.get(4)?;
         let archive_delete_branch_raw: Option<i32> = row.get(5)?;
         let archive_remote_prompt_raw: Option<i32> = row.get(6)?;
-        let created_at_str: String = row.get(7)?;
-        let updated_at_str: String = row.get(8)?;
+        let approval_policy_raw: Option<String> = row.get(7)?;
+        let created_at_str: String = row.get(8)?;
+        let updated_at_str: String = row.get(9)?;
+        let codex_approval_raw: Option<String> = row.get(10)?;
+        let codex_sandbox_raw: Option<String> = row.get(11)?;
 
         let workspace_mode = match workspace_mode_raw {
             None => None,
@@ -162,6 +172,24 @@ impl RepositoryStore {
             }
         };
 
+        let approval_policy = match approval_policy_raw {
+            None => None,
+            Some(value) => {
+                let parsed = ApprovalPolicy::from_str(&value).map_err(|_| {
+                    let err = std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("Invalid approval_policy '{}'", value),
+                    );
+                    rusqlite::Error::FromSqlConversionFailure(
+                        7,
+                        rusqlite::types::Type::Text,
+                        Box::new(err),
+                    )
+                })?;
+                Some(parsed)
+            }
+        };
+
         Ok(Repository {
             id: Uuid::parse_str(&id_str).unwrap_or_else(|_| Uuid::new_v4()),
             name: row.get(1)?,
@@ -170,6 +198,9 @@ impl RepositoryStore {
             workspace_mode,
             archive_delete_branch: archive_delete_branch_raw.map(|value| value != 0),
             archive_remote_prompt: archive_remote_prompt_raw.map(|value| value != 0),
+            approval_policy,
+            codex_approval_mode: codex_approval_raw.and_then(|value| value.parse().ok()),
+            codex_sandbox_mode: codex_sandbox_raw.and_then(|value| value.parse().ok()),
             created_at: DateTime::parse_from_rfc3339(&created_at_str)
                 .map(|dt| dt.with_timezone(&Utc))
                 .unwrap_or_else(|_| Utc::now()),
@@ -186,6 +217,7 @@ impl RepositoryStore {
         workspace_mode: Option<WorkspaceMode>,
         archive_delete_branch: Option<bool>,
         archive_remote_prompt: Option<bool>,
+        approval_policy: Option<ApprovalPolicy>,
     ) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
@@ -193,18 +225,68 @@ impl RepositoryStore {
              SET workspace_mode = COALESCE(?2, workspace_mode),
                  archive_delete_branch = COALESCE(?3, archive_delete_branch),
                  archive_remote_prompt = COALESCE(?4, archive_remote_prompt),
-                 updated_at = ?5
+                 approval_policy = COALESCE(?5, approval_policy),
+                 updated_at = ?6
              WHERE id = ?1",
             params![
                 id.to_string(),
                 workspace_mode.map(|mode| mode.as_str().to_string()),
                 archive_delete_branch.map(|value| value as i32),
                 archive_remote_prompt.map(|value| value as i32),
+                approval_policy.map(|policy| policy.as_str().to_string()),
                 Utc::now().to_rfc3339(),
             ],
         )?;
         Ok(())
     }
+
+    /// Update the Codex sandbox and approval defaults for new sessions.
+    pub fn update_codex_defaults(
+        &self,
+        id: Uuid,
+        approval: Option<CodexApprovalMode>,
+        sandbox: Option<CodexSandboxMode>,
+    ) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE repositories
+             SET codex_approval_mode = COALESCE(?2, codex_approval_mode),
+                 codex_sandbox_mode = COALESCE(?3, codex_sandbox_mode),
+                 updated_at = ?4
+             WHERE id = ?1",
+            params![
+                id.to_string(),
+                approval.map(|mode| mode.as_str()),
+                sandbox.map(|mode| mode.as_str()),
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Tools the user has chosen to always allow in a repository
+    pub fn allowed_tools(&self, id: Uuid) -> SqliteResult<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT tool_name FROM repository_allowed_tools
+             WHERE repository_id = ?1 ORDER BY tool_name",
+        )?;
+        let tools = stmt
+            .query_map(params![id.to_string()], |row| row.get(0))?
+            .collect::<SqliteResult<Vec<String>>>()?;
+        Ok(tools)
+    }
+
+    /// Always allow a tool in a repository
+    pub fn allow_tool(&self, id: Uuid, tool_name: &str) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR IGNORE INTO repository_allowed_tools (repository_id, tool_name, created_at)
+             VALUES (?1, ?2, ?3)",
+            params![id.to_string(), tool_name, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -259,4 +341,66 @@ mod tests {
         dao.delete(repo.id).unwrap();
         assert!(dao.get_by_id(repo.id).unwrap().is_none());
     }
+
+    #[test]
+    fn test_update_settings_approval_policy() {
+        let (_dir, _db, dao) = setup_db();
+        let repo = Repository::from_local_path("policy", PathBuf::from("/tmp/policy"));
+
+        dao.create(&repo).unwrap();
+        assert_eq!(
+            dao.get_by_id(repo.id).unwrap().unwrap().approval_policy,
+            None
+        );
+
+        dao.update_settings(repo.id, None, None, None, Some(ApprovalPolicy::AllowReads))
+            .unwrap();
+        let retrieved = dao.get_by_id(repo.id).unwrap().unwrap();
+        assert_eq!(retrieved.approval_policy, Some(ApprovalPolicy::AllowReads));
+
+        // Leaving the policy unset keeps the existing override.
+        dao.update_settings(repo.id, Some(WorkspaceMode::Checkout), None, None, None)
+            .unwrap();
+        let retrieved = dao.get_by_id(repo.id).unwrap().unwrap();
+        assert_eq!(retrieved.approval_policy, Some(ApprovalPolicy::AllowReads));
+        assert_eq!(retrieved.workspace_mode, Some(WorkspaceMode::Checkout));
+    }
+
+    #[test]
+    fn test_update_codex_defaults() {
+        let (_dir, _db, dao) = setup_db();
+        let repo = Repository::from_local_path("codex", PathBuf::from("/tmp/codex"));
+        dao.create(&repo).unwrap();
+
+        dao.update_codex_defaults(repo.id, None, Some(CodexSandboxMode::ReadOnly))
+            .unwrap();
+        dao.update_codex_defaults(repo.id, Some(CodexApprovalMode::OnRequest), None)
+            .unwrap();
+        let retrieved = dao.get_by_id(repo.id).unwrap().unwrap();
+        assert_eq!(
+            retrieved.codex_approval_mode,
+            Some(CodexApprovalMode::OnRequest)
+        );
+        assert_eq!(
+            retrieved.codex_sandbox_mode,
+            Some(CodexSandboxMode::ReadOnly)
+        );
+    }
+
+    #[test]
+    fn test_allowed_tools() {
+        let (_dir, _db, dao) = setup_db();
+        let repo = Repository::from_local_path("tools", PathBuf::from("/tmp/tools"));
+        dao.create(&repo).unwrap();
+        assert!(dao.allowed_tools(repo.id).unwrap().is_empty());
+
+        dao.allow_tool(repo.id, "Bash").unwrap();
+        dao.allow_tool(repo.id, "Edit").unwrap();
+        dao.allow_tool(repo.id, "Bash").unwrap();
+        assert_eq!(dao.allowed_tools(repo.id).unwrap(), vec!["Bash", "Edit"]);
+
+        // Tools follow the repository when it is removed
+        dao.delete(repo.id).unwrap();
+        assert!(dao.allowed_tools(repo.id).unwrap().is_empty());
+    }
 }
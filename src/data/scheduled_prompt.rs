@@ -0,0 +1,269 @@
+//! Scheduled prompt data access object
+
+use super::models::ScheduledPrompt;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection, Result as SqliteResult};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+const COLUMNS: &str = "id, repository_id, workspace_id, name, schedule, prompt, enabled, next_run_at, last_run_at, last_error, created_at";
+
+/// Data access object for scheduled prompt operations
+#[derive(Clone)]
+pub struct ScheduledPromptStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl ScheduledPromptStore {
+    /// Create a new ScheduledPromptStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Insert a new scheduled prompt
+    pub fn create(&self, entry: &ScheduledPrompt) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            &format!(
+                "INSERT INTO scheduled_prompts ({COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"
+            ),
+            params![
+                entry.id.to_string(),
+                entry.repository_id.to_string(),
+                entry.workspace_id.map(|id| id.to_string()),
+                entry.name,
+                entry.schedule,
+                entry.prompt,
+                entry.enabled,
+                entry.next_run_at.map(format_time),
+                entry.last_run_at.map(format_time),
+                entry.last_error,
+                format_time(entry.created_at),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Update the editable fields and the next run time
+    pub fn update(&self, entry: &ScheduledPrompt) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE scheduled_prompts
+             SET workspace_id = ?2, name = ?3, schedule = ?4, prompt = ?5, enabled = ?6, next_run_at = ?7
+             WHERE id = ?1",
+            params![
+                entry.id.to_string(),
+                entry.workspace_id.map(|id| id.to_string()),
+                entry.name,
+                entry.schedule,
+                entry.prompt,
+                entry.enabled,
+                entry.next_run_at.map(format_time),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Get a scheduled prompt by ID
+    pub fn get_by_id(&self, id: Uuid) -> SqliteResult<Option<ScheduledPrompt>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM scheduled_prompts WHERE id = ?1"
+        ))?;
+
+        let mut rows = stmt.query(params![id.to_string()])?;
+        if let Some(row) = rows.next()? {
+            Ok(Some(Self::row_to_entry(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// All scheduled prompts, oldest first
+    pub fn get_all(&self) -> SqliteResult<Vec<ScheduledPrompt>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM scheduled_prompts ORDER BY created_at"
+        ))?;
+
+        let entries = stmt
+            .query_map([], Self::row_to_entry)?
+            .collect::<SqliteResult<Vec<_>>>()?;
+        Ok(entries)
+    }
+
+    /// Claim a due run by moving `next_run_at` forward.
+    ///
+    /// Returns false when another process (TUI or web server) already
+    /// claimed it, i.e. `next_run_at` no longer holds the value that was read.
+    pub fn claim(
+        &self,
+        id: Uuid,
+        expected_next: DateTime<Utc>,
+        new_next: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> SqliteResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE scheduled_prompts
+             SET next_run_at = ?3, last_run_at = ?4, last_error = NULL
+             WHERE id = ?1 AND enabled = 1 AND next_run_at = ?2",
+            params![
+                id.to_string(),
+                format_time(expected_next),
+                new_next.map(format_time),
+                format_time(now),
+            ],
+        )?;
+        Ok(updated == 1)
+    }
+
+    /// Record when a run was delivered and why it failed, if it did
+    pub fn record_run(
+        &self,
+        id: Uuid,
+        ran_at: DateTime<Utc>,
+        error: Option<&str>,
+    ) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE scheduled_prompts SET last_run_at = ?2, last_error = ?3 WHERE id = ?1",
+            params![id.to_string(), format_time(ran_at), error],
+        )?;
+        Ok(())
+    }
+
+    /// Delete a scheduled prompt by ID
+    pub fn delete(&self, id: Uuid) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM scheduled_prompts WHERE id = ?1",
+            params![id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Convert a database row to a ScheduledPrompt
+    fn row_to_entry(row: &rusqlite::Row) -> SqliteResult<ScheduledPrompt> {
+        let id_str: String = row.get(0)?;
+        let repository_id_str: String = row.get(1)?;
+        let workspace_id_str: Option<String> = row.get(2)?;
+        let next_run_at: Option<String> = row.get(7)?;
+        let last_run_at: Option<String> = row.get(8)?;
+        let created_at_str: String = row.get(10)?;
+
+        Ok(ScheduledPrompt {
+            id: Uuid::parse_str(&id_str).unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Invalid UUID in scheduled_prompts table: {}, error: {}",
+                    id_str,
+                    e
+                );
+                Uuid::new_v4()
+            }),
+            repository_id: Uuid::parse_str(&repository_id_str).unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Invalid repository_id UUID in scheduled_prompts table: {}, error: {}",
+                    repository_id_str,
+                    e
+                );
+                Uuid::nil()
+            }),
+            workspace_id: workspace_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+            name: row.get(3)?,
+            schedule: row.get(4)?,
+            prompt: row.get(5)?,
+            enabled: row.get(6)?,
+            next_run_at: next_run_at.as_deref().and_then(parse_time),
+            last_run_at: last_run_at.as_deref().and_then(parse_time),
+            last_error: row.get(9)?,
+            created_at: parse_time(&created_at_str).unwrap_or_else(Utc::now),
+        })
+    }
+}
+
+/// Whole-second RFC 3339, so a stored time round-trips to the same string
+/// and can be compared in `claim`
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| {
+            tracing::warn!(
+                "Invalid DateTime in scheduled_prompts table: {}, error: {}",
+                value,
+                e
+            );
+        })
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{Database, Repository, RepositoryStore};
+    use chrono::{Duration, TimeZone};
+    use std::path::PathBuf;
+    use tempfile::tempdir;
+
+    fn setup_db() -> (tempfile::TempDir, Database, ScheduledPromptStore, Uuid) {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let repo = Repository::from_local_path("repo", PathBuf::from("/tmp/repo"));
+        RepositoryStore::new(db.connection()).create(&repo).unwrap();
+        let dao = ScheduledPromptStore::new(db.connection());
+        (dir, db, dao, repo.id)
+    }
+
+    #[test]
+    fn test_claim_only_succeeds_once() {
+        let (_dir, _db, dao, repository_id) = setup_db();
+        let now = Utc.with_ymd_and_hms(2024, 6, 10, 8, 0, 30).unwrap();
+        let due = Utc.with_ymd_and_hms(2024, 6, 10, 8, 0, 0).unwrap();
+
+        let mut entry =
+            ScheduledPrompt::new(repository_id, None, "tests", "0 8 * * 1-5", "run tests");
+        entry.next_run_at = Some(due);
+        dao.create(&entry).unwrap();
+
+        let stored = dao.get_by_id(entry.id).unwrap().unwrap();
+        assert!(stored.is_due(now));
+        assert_eq!(stored.next_run_at, Some(due));
+
+        let next = due + Duration::days(1);
+        assert!(dao.claim(entry.id, due, Some(next), now).unwrap());
+        // A second process read the same due time but lost the race
+        assert!(!dao.claim(entry.id, due, Some(next), now).unwrap());
+
+        let stored = dao.get_by_id(entry.id).unwrap().unwrap();
+        assert_eq!(stored.next_run_at, Some(next));
+        assert_eq!(stored.last_run_at, Some(now));
+        assert!(!stored.is_due(now));
+    }
+
+    #[test]
+    fn test_update_and_delete() {
+        let (_dir, _db, dao, repository_id) = setup_db();
+        let mut entry = ScheduledPrompt::new(repository_id, None, "deps", "@daily", "update deps");
+        dao.create(&entry).unwrap();
+
+        entry.enabled = false;
+        entry.prompt = "update dependencies".to_string();
+        dao.update(&entry).unwrap();
+        dao.record_run(entry.id, Utc::now(), Some("repository missing"))
+            .unwrap();
+
+        let all = dao.get_all().unwrap();
+        assert_eq!(all.len(), 1);
+        assert!(!all[0].enabled);
+        assert_eq!(all[0].prompt, "update dependencies");
+        assert_eq!(all[0].last_error.as_deref(), Some("repository missing"));
+
+        dao.delete(entry.id).unwrap();
+        assert!(dao.get_all().unwrap().is_empty());
+    }
+}
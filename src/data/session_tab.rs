@@ -50,8 +50,8 @@ impl SessionTabStore {
         let queued_messages = serialize_queued_messages(&tab.queued_messages);
         let input_history = serialize_input_history(&tab.input_history);
         conn.execute(
-            "INSERT INTO session_tabs (id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
+            "INSERT INTO session_tabs (id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated, codex_approval_mode, codex_sandbox_mode)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)
              ON CONFLICT(id) DO UPDATE SET
                tab_index = excluded.tab_index,
                is_open = excluded.is_open,
@@ -67,7 +67,9 @@ impl SessionTabStore {
                input_history = excluded.input_history,
                fork_seed_id = excluded.fork_seed_id,
                title = excluded.title,
-               title_generated = excluded.title_generated",
+               title_generated = excluded.title_generated,
+               codex_approval_mode = excluded.codex_approval_mode,
+               codex_sandbox_mode = excluded.codex_sandbox_mode",
             params![
                 tab.id.to_string(),
                 tab.tab_index,
@@ -86,6 +88,8 @@ impl SessionTabStore {
                 tab.fork_seed_id.map(|id| id.to_string()),
                 tab.title,
                 if tab.title_generated { 1 } else { 0 },
+                tab.codex_approval.map(|mode| mode.as_str()),
+                tab.codex_sandbox.map(|mode| mode.as_str()),
             ],
         )?;
         Ok(())
@@ -95,8 +99,8 @@ impl SessionTabStore {
         let queued_messages = serialize_queued_messages(&tab.queued_messages);
         let input_history = serialize_input_history(&tab.input_history);
         conn.execute(
-            "INSERT INTO session_tabs (id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+            "INSERT INTO session_tabs (id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated, codex_approval_mode, codex_sandbox_mode)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)",
             params![
                 tab.id.to_string(),
                 tab.tab_index,
@@ -115,6 +119,8 @@ impl SessionTabStore {
                 tab.fork_seed_id.map(|id| id.to_string()),
                 tab.title,
                 if tab.title_generated { 1 } else { 0 },
+                tab.codex_approval.map(|mode| mode.as_str()),
+                tab.codex_sandbox.map(|mode| mode.as_str()),
             ],
         )?;
         Ok(())
@@ -125,7 +131,8 @@ impl SessionTabStore {
         let input_history = serialize_input_history(&tab.input_history);
         conn.execute(
             "UPDATE session_tabs SET tab_index = ?2, is_open = ?3, workspace_id = ?4, agent_type = ?5, agent_mode = ?6,
-             agent_session_id = ?7, model = ?8, model_invalid = ?9, pr_number = ?10, pending_user_message = ?11, queued_messages = ?12, input_history = ?13, fork_seed_id = ?14, title = ?15 WHERE id = ?1",
+             agent_session_id = ?7, model = ?8, model_invalid = ?9, pr_number = ?10, pending_user_message = ?11, queued_messages = ?12, input_history = ?13, fork_seed_id = ?14, title = ?15,
+             codex_approval_mode = ?16, codex_sandbox_mode = ?17 WHERE id = ?1",
             params![
                 tab.id.to_string(),
                 tab.tab_index,
@@ -142,6 +149,8 @@ impl SessionTabStore {
                 input_history,
                 tab.fork_seed_id.map(|id| id.to_string()),
                 tab.title,
+                tab.codex_approval.map(|mode| mode.as_str()),
+                tab.codex_sandbox.map(|mode| mode.as_str()),
             ],
         )?;
         Ok(())
@@ -154,7 +163,7 @@ impl SessionTabStore {
         // sessions closed, but older DBs may still contain "open" sessions pointing at archived
         // workspaces.)
         let mut stmt = conn.prepare(
-            "SELECT st.id, st.tab_index, st.is_open, st.workspace_id, st.agent_type, st.agent_mode, st.agent_session_id, st.model, st.model_invalid, st.pr_number, st.created_at, st.pending_user_message, st.queued_messages, st.input_history, st.fork_seed_id, st.title, st.title_generated
+            "SELECT st.id, st.tab_index, st.is_open, st.workspace_id, st.agent_type, st.agent_mode, st.agent_session_id, st.model, st.model_invalid, st.pr_number, st.created_at, st.pending_user_message, st.queued_messages, st.input_history, st.fork_seed_id, st.title, st.title_generated, st.codex_approval_mode, st.codex_sandbox_mode
              FROM session_tabs st
              LEFT JOIN workspaces w ON st.workspace_id = w.id
              WHERE st.is_open = 1
@@ -177,7 +186,7 @@ impl SessionTabStore {
     pub fn get_by_id(&self, id: Uuid) -> SqliteResult<Option<SessionTab>> {
         let conn = self.lock_conn()?;
         let mut stmt = conn.prepare(
-            "SELECT id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated
+            "SELECT id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated, codex_approval_mode, codex_sandbox_mode
              FROM session_tabs WHERE id = ?1",
         )?;
 
@@ -196,7 +205,8 @@ impl SessionTabStore {
         let input_history = serialize_input_history(&tab.input_history);
         conn.execute(
             "UPDATE session_tabs SET tab_index = ?2, is_open = ?3, workspace_id = ?4, agent_type = ?5, agent_mode = ?6,
-             agent_session_id = ?7, model = ?8, model_invalid = ?9, pr_number = ?10, pending_user_message = ?11, queued_messages = ?12, input_history = ?13, fork_seed_id = ?14, title = ?15, title_generated = ?16 WHERE id = ?1",
+             agent_session_id = ?7, model = ?8, model_invalid = ?9, pr_number = ?10, pending_user_message = ?11, queued_messages = ?12, input_history = ?13, fork_seed_id = ?14, title = ?15, title_generated = ?16,
+             codex_approval_mode = ?17, codex_sandbox_mode = ?18 WHERE id = ?1",
             params![
                 tab.id.to_string(),
                 tab.tab_index,
@@ -214,6 +224,8 @@ impl SessionTabStore {
                 tab.fork_seed_id.map(|id| id.to_string()),
                 tab.title,
                 if tab.title_generated { 1 } else { 0 },
+                tab.codex_approval.map(|mode| mode.as_str()),
+                tab.codex_sandbox.map(|mode| mode.as_str()),
             ],
         )?;
         Ok(())
@@ -255,7 +267,7 @@ impl SessionTabStore {
         workspace_id: Uuid,
     ) -> SqliteResult<Option<SessionTab>> {
         let mut stmt = conn.prepare(
-            "SELECT id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated
+            "SELECT id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated, codex_approval_mode, codex_sandbox_mode
              FROM session_tabs WHERE workspace_id = ?1 ORDER BY is_open DESC, created_at DESC LIMIT 1",
         )?;
 
@@ -277,7 +289,7 @@ impl SessionTabStore {
         workspace_id: Uuid,
     ) -> SqliteResult<Option<SessionTab>> {
         let mut stmt = conn.prepare(
-            "SELECT id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated
+            "SELECT id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated, codex_approval_mode, codex_sandbox_mode
              FROM session_tabs WHERE workspace_id = ?1 AND is_open = 1 ORDER BY created_at DESC LIMIT 1",
         )?;
 
@@ -368,6 +380,8 @@ impl SessionTabStore {
         let fork_seed_id_str: Option<String> = row.get("fork_seed_id")?;
         let title_generated: i64 = row.get("title_generated")?;
         let model_invalid: i64 = row.get("model_invalid")?;
+        let codex_approval: Option<String> = row.get("codex_approval_mode")?;
+        let codex_sandbox: Option<String> = row.get("codex_sandbox_mode")?;
 
         Ok(SessionTab {
             id: Uuid::parse_str(&id_str).map_err(|e| {
@@ -401,6 +415,8 @@ impl SessionTabStore {
             fork_seed_id: fork_seed_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
             title: row.get("title")?,
             title_generated: title_generated != 0,
+            codex_approval: codex_approval.and_then(|value| value.parse().ok()),
+            codex_sandbox: codex_sandbox.and_then(|value| value.parse().ok()),
         })
     }
 }
@@ -442,6 +458,7 @@ fn deserialize_input_history(raw: Option<&str>) -> Vec<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::agent::{CodexApprovalMode, CodexSandboxMode};
     use crate::data::{
         Database, QueuedImageAttachment, QueuedMessageMode, Repository, RepositoryStore, Workspace,
         WorkspaceStore,
@@ -575,6 +592,25 @@ mod tests {
         assert_eq!(retrieved.pr_number, Some(42));
         assert_eq!(retrieved.queued_messages.len(), 1);
         assert_eq!(retrieved.input_history, tab.input_history);
+        assert_eq!(retrieved.codex_sandbox, None);
+    }
+
+    #[test]
+    fn test_codex_permissions_roundtrip() {
+        let (_dir, _db, dao) = setup_db();
+        let mut tab = SessionTab::new(0, AgentType::Codex, None, None, None, None);
+        tab.codex_approval = Some(CodexApprovalMode::OnRequest);
+        dao.create(&tab).unwrap();
+
+        tab.codex_sandbox = Some(CodexSandboxMode::WorkspaceWrite);
+        dao.update(&tab).unwrap();
+
+        let retrieved = dao.get_by_id(tab.id).unwrap().unwrap();
+        assert_eq!(retrieved.codex_approval, Some(CodexApprovalMode::OnRequest));
+        assert_eq!(
+            retrieved.codex_sandbox,
+            Some(CodexSandboxMode::WorkspaceWrite)
+        );
     }
 
     #[test]
@@ -0,0 +1,261 @@
+//! Usage ledger data access object
+
+use super::models::{UsageGroupBy, UsageRecord, UsageSummaryRow, UsageTotals};
+use crate::agent::AgentType;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, params_from_iter, Connection, Result as SqliteResult};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Aggregate columns read back by `row_to_totals`
+const TOTALS_COLUMNS: &str = "COUNT(*), COALESCE(SUM(u.input_tokens), 0), COALESCE(SUM(u.output_tokens), 0), COALESCE(SUM(u.cached_tokens), 0), COALESCE(SUM(u.total_tokens), 0), COALESCE(SUM(u.cost_usd), 0.0)";
+
+/// Restricts which ledger entries are aggregated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UsageScope {
+    #[default]
+    All,
+    Session(Uuid),
+    Repository(Uuid),
+}
+
+/// Data access object for the per-turn usage ledger
+#[derive(Clone)]
+pub struct UsageLedgerStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl UsageLedgerStore {
+    /// Create a new UsageLedgerStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Timestamps are stored in a fixed UTC format so they compare lexically.
+    fn format_timestamp(at: DateTime<Utc>) -> String {
+        at.to_rfc3339_opts(SecondsFormat::Secs, true)
+    }
+
+    /// Append a usage record
+    pub fn record(&self, record: &UsageRecord) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO usage_ledger (id, recorded_at, session_id, agent_session_id, agent_type, model, workspace_id, repository_id, input_tokens, output_tokens, cached_tokens, total_tokens, cost_usd)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![
+                record.id.to_string(),
+                Self::format_timestamp(record.recorded_at),
+                record.session_id.map(|id| id.to_string()),
+                record.agent_session_id,
+                record.agent_type.as_str(),
+                record.model,
+                record.workspace_id.map(|id| id.to_string()),
+                record.repository_id.map(|id| id.to_string()),
+                record.input_tokens,
+                record.output_tokens,
+                record.cached_tokens,
+                record.total_tokens,
+                record.cost_usd,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Aggregate usage recorded since `since` (or ever) within `scope`
+    pub fn totals(
+        &self,
+        scope: UsageScope,
+        since: Option<DateTime<Utc>>,
+    ) -> SqliteResult<UsageTotals> {
+        let mut clauses = Vec::new();
+        let mut values = Vec::new();
+        match scope {
+            UsageScope::All => {}
+            UsageScope::Session(id) => {
+                clauses.push("u.session_id = ?");
+                values.push(id.to_string());
+            }
+            UsageScope::Repository(id) => {
+                clauses.push("u.repository_id = ?");
+                values.push(id.to_string());
+            }
+        }
+        if let Some(since) = since {
+            clauses.push("u.recorded_at >= ?");
+            values.push(Self::format_timestamp(since));
+        }
+
+        let mut sql = format!("SELECT {TOTALS_COLUMNS} FROM usage_ledger u");
+        if !clauses.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&clauses.join(" AND "));
+        }
+
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(&sql, params_from_iter(values), |row| {
+            Self::row_to_totals(row, 0)
+        })
+    }
+
+    /// Summarize usage recorded since `since`, grouped by repository, model or day
+    pub fn summarize(
+        &self,
+        since: Option<DateTime<Utc>>,
+        group_by: UsageGroupBy,
+    ) -> SqliteResult<Vec<UsageSummaryRow>> {
+        // Biggest spenders first for repos and models, newest first for days
+        let (key, label, order) = match group_by {
+            UsageGroupBy::Repo => (
+                "COALESCE(u.repository_id, '')",
+                "COALESCE(r.name, 'No repository')",
+                "8 DESC, 7 DESC",
+            ),
+            UsageGroupBy::Model => (
+                "u.agent_type || ':' || COALESCE(u.model, '')",
+                "COALESCE(u.model, 'default')",
+                "8 DESC, 7 DESC",
+            ),
+            UsageGroupBy::Day => (
+                "substr(u.recorded_at, 1, 10)",
+                "substr(u.recorded_at, 1, 10)",
+                "1 DESC",
+            ),
+        };
+        let sql = format!(
+            "SELECT {key}, {label}, {TOTALS_COLUMNS}
+             FROM usage_ledger u
+             LEFT JOIN repositories r ON r.id = u.repository_id
+             WHERE ?1 IS NULL OR u.recorded_at >= ?1
+             GROUP BY 1
+             ORDER BY {order}"
+        );
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params![since.map(Self::format_timestamp)], |row| {
+            let key: String = row.get(0)?;
+            let label: String = row.get(1)?;
+            Ok(UsageSummaryRow {
+                label: match group_by {
+                    UsageGroupBy::Model => Self::model_label(&key).unwrap_or(label),
+                    _ => label,
+                },
+                key,
+                totals: Self::row_to_totals(row, 2)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Label model groups with the agent's display name rather than its ID
+    fn model_label(key: &str) -> Option<String> {
+        let (agent, model) = key.split_once(':')?;
+        let agent = AgentType::parse(agent);
+        let model = if model.is_empty() { "default" } else { model };
+        Some(format!("{} {}", agent.short_name(), model))
+    }
+
+    fn row_to_totals(row: &rusqlite::Row, offset: usize) -> SqliteResult<UsageTotals> {
+        Ok(UsageTotals {
+            turns: row.get(offset)?,
+            input_tokens: row.get(offset + 1)?,
+            output_tokens: row.get(offset + 2)?,
+            cached_tokens: row.get(offset + 3)?,
+            total_tokens: row.get(offset + 4)?,
+            cost_usd: row.get(offset + 5)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::TokenUsage;
+    use crate::data::{Database, Repository, RepositoryStore};
+    use chrono::Duration;
+    use std::path::PathBuf;
+    use tempfile::tempdir;
+
+    fn setup_db() -> (tempfile::TempDir, Database, UsageLedgerStore) {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let dao = UsageLedgerStore::new(db.connection());
+        (dir, db, dao)
+    }
+
+    fn record(agent_type: AgentType, model: &str, tokens: i64, cost: Option<f64>) -> UsageRecord {
+        let usage = TokenUsage {
+            input_tokens: tokens,
+            output_tokens: tokens / 10,
+            cached_tokens: 0,
+            total_tokens: tokens + tokens / 10,
+        };
+        let mut record = UsageRecord::new(agent_type, Some(model.to_string()), &usage);
+        record.cost_usd = cost;
+        record
+    }
+
+    #[test]
+    fn test_totals_by_scope_and_since() {
+        let (_dir, _db, dao) = setup_db();
+        let session_id = Uuid::new_v4();
+
+        let mut old = record(AgentType::Claude, "opus", 1000, Some(1.0));
+        old.recorded_at = Utc::now() - Duration::days(2);
+        old.session_id = Some(session_id);
+        dao.record(&old).unwrap();
+
+        let mut recent = record(AgentType::Claude, "opus", 500, Some(0.5));
+        recent.session_id = Some(session_id);
+        dao.record(&recent).unwrap();
+
+        dao.record(&record(AgentType::Codex, "gpt-5.2", 200, None))
+            .unwrap();
+
+        let all = dao.totals(UsageScope::All, None).unwrap();
+        assert_eq!(all.turns, 3);
+        assert_eq!(all.input_tokens, 1700);
+        assert!((all.cost_usd - 1.5).abs() < 1e-9);
+
+        let session = dao
+            .totals(
+                UsageScope::Session(session_id),
+                Some(Utc::now() - Duration::days(1)),
+            )
+            .unwrap();
+        assert_eq!(session.turns, 1);
+        assert_eq!(session.input_tokens, 500);
+    }
+
+    #[test]
+    fn test_summarize_by_repo_and_model() {
+        let (_dir, db, dao) = setup_db();
+        let repo = Repository::from_local_path("conduit", PathBuf::from("/tmp/conduit"));
+        RepositoryStore::new(db.connection()).create(&repo).unwrap();
+
+        let mut cheap = record(AgentType::Codex, "gpt-5.1-codex-mini", 100, Some(0.1));
+        cheap.repository_id = Some(repo.id);
+        dao.record(&cheap).unwrap();
+        let mut pricey = record(AgentType::Claude, "opus", 100, Some(2.0));
+        pricey.repository_id = Some(repo.id);
+        dao.record(&pricey).unwrap();
+        dao.record(&record(AgentType::Claude, "opus", 100, Some(1.0)))
+            .unwrap();
+
+        let by_repo = dao.summarize(None, UsageGroupBy::Repo).unwrap();
+        assert_eq!(by_repo.len(), 2);
+        assert_eq!(by_repo[0].label, "conduit");
+        assert_eq!(by_repo[0].key, repo.id.to_string());
+        assert_eq!(by_repo[0].totals.turns, 2);
+        assert_eq!(by_repo[1].label, "No repository");
+
+        let by_model = dao.summarize(None, UsageGroupBy::Model).unwrap();
+        assert_eq!(by_model.len(), 2);
+        assert_eq!(by_model[0].key, "claude:opus");
+        assert!((by_model[0].totals.cost_usd - 3.0).abs() < 1e-9);
+
+        let by_day = dao.summarize(None, UsageGroupBy::Day).unwrap();
+        assert_eq!(by_day.len(), 1);
+        assert_eq!(by_day[0].totals.turns, 3);
+    }
+}
@@ -0,0 +1,306 @@
+//! Per-turn worktree snapshots stored under hidden refs
+//!
+//! A checkpoint is a commit object built from a throwaway index, so taking
+//! one never touches the user's index, branch or stash. Each commit is kept
+//! alive by a ref under `refs/conduit/checkpoints/`.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use uuid::Uuid;
+
+use super::worktree::WorktreeError;
+
+/// Namespace for checkpoint refs
+const CHECKPOINT_REF_PREFIX: &str = "refs/conduit/checkpoints";
+
+/// Captures and restores worktree snapshots
+pub struct CheckpointManager;
+
+impl CheckpointManager {
+    /// Hidden ref that keeps a checkpoint commit reachable
+    pub fn ref_name(workspace_id: Uuid, checkpoint_id: Uuid) -> String {
+        format!(
+            "{}/{}/{}",
+            CHECKPOINT_REF_PREFIX, workspace_id, checkpoint_id
+        )
+    }
+
+    /// Ref holding the worktree as it was just before the last restore
+    pub fn backup_ref_name(workspace_id: Uuid) -> String {
+        format!("{}/{}/pre-restore", CHECKPOINT_REF_PREFIX, workspace_id)
+    }
+
+    /// Snapshot the worktree (tracked and untracked, minus ignored files)
+    /// into a commit pointed to by `ref_name`. Returns the commit SHA.
+    pub fn snapshot(
+        working_dir: &Path,
+        ref_name: &str,
+        message: &str,
+    ) -> Result<String, WorktreeError> {
+        let commit = Self::snapshot_commit(working_dir, message)?;
+        git_stdout(working_dir, None, &["update-ref", ref_name, &commit])?;
+        Ok(commit)
+    }
+
+    /// Put the worktree back to the state captured in `commit`.
+    ///
+    /// The current state is first saved under `backup_ref`. Files created
+    /// after the checkpoint are then removed and captured files rewritten.
+    /// The user's index and HEAD are left alone.
+    pub fn restore(
+        working_dir: &Path,
+        commit: &str,
+        backup_ref: &str,
+    ) -> Result<(), WorktreeError> {
+        let current = Self::snapshot(working_dir, backup_ref, "conduit: pre-restore")?;
+        let added = git_stdout(
+            working_dir,
+            None,
+            &[
+                "diff",
+                "--name-only",
+                "-z",
+                "--no-renames",
+                "--diff-filter=A",
+                commit,
+                &current,
+            ],
+        )?;
+        for path in added.split('\0').filter(|p| !p.is_empty()) {
+            let full = working_dir.join(path);
+            if full.is_file() || full.is_symlink() {
+                std::fs::remove_file(&full)?;
+            }
+        }
+
+        let index = TempIndex::new(working_dir)?;
+        git_stdout(working_dir, Some(&index.path), &["read-tree", commit])?;
+        git_stdout(
+            working_dir,
+            Some(&index.path),
+            &["checkout-index", "--all", "--force"],
+        )?;
+        Ok(())
+    }
+
+    /// Paths that differ between the checkpoint and the current worktree
+    pub fn changed_since(working_dir: &Path, commit: &str) -> Result<Vec<String>, WorktreeError> {
+        let (current, _) = Self::snapshot_tree(working_dir)?;
+        let changed = git_stdout(
+            working_dir,
+            None,
+            &[
+                "diff",
+                "--name-only",
+                "-z",
+                "--no-renames",
+                commit,
+                &current,
+            ],
+        )?;
+        Ok(changed
+            .split('\0')
+            .filter(|path| !path.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Drop the ref for a checkpoint so git can collect its objects
+    pub fn delete_ref(working_dir: &Path, ref_name: &str) -> Result<(), WorktreeError> {
+        git_stdout(working_dir, None, &["update-ref", "-d", ref_name])?;
+        Ok(())
+    }
+
+    /// Build a commit for the current worktree without writing any ref
+    fn snapshot_commit(working_dir: &Path, message: &str) -> Result<String, WorktreeError> {
+        let (tree, head) = Self::snapshot_tree(working_dir)?;
+        let mut args = vec!["commit-tree", "--no-gpg-sign", &tree, "-m", message];
+        if let Some(head) = &head {
+            args.push("-p");
+            args.push(head);
+        }
+        let commit = git_stdout(working_dir, None, &args)?;
+        Ok(commit.trim().to_string())
+    }
+
+    /// Write a tree object for the current worktree; also returns HEAD if any
+    fn snapshot_tree(working_dir: &Path) -> Result<(String, Option<String>), WorktreeError> {
+        let head = git_stdout(working_dir, None, &["rev-parse", "--verify", "-q", "HEAD"])
+            .ok()
+            .map(|sha| sha.trim().to_string())
+            .filter(|sha| !sha.is_empty());
+
+        // Start from the real index so its stat cache spares `add` from
+        // rehashing unchanged files; the index itself is never written
+        let index = TempIndex::new(working_dir)?;
+        let real_index = git_stdout(
+            working_dir,
+            None,
+            &["rev-parse", "--path-format=absolute", "--git-path", "index"],
+        )?;
+        let real_index = PathBuf::from(real_index.trim());
+        if real_index.exists() {
+            std::fs::copy(&real_index, &index.path)?;
+        } else if let Some(head) = &head {
+            git_stdout(working_dir, Some(&index.path), &["read-tree", head])?;
+        }
+        git_stdout(working_dir, Some(&index.path), &["add", "-A"])?;
+        let tree = git_stdout(working_dir, Some(&index.path), &["write-tree"])?;
+        Ok((tree.trim().to_string(), head))
+    }
+}
+
+/// Scratch index file under the repository's git dir, removed on drop
+struct TempIndex {
+    path: PathBuf,
+}
+
+impl TempIndex {
+    fn new(working_dir: &Path) -> Result<Self, WorktreeError> {
+        let git_dir = git_stdout(
+            working_dir,
+            None,
+            &["rev-parse", "--path-format=absolute", "--git-dir"],
+        )?;
+        let path = PathBuf::from(git_dir.trim())
+            .join(format!("conduit-checkpoint-{}.index", Uuid::new_v4()));
+        Ok(Self { path })
+    }
+}
+
+impl Drop for TempIndex {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+fn git_stdout(
+    working_dir: &Path,
+    index_file: Option<&Path>,
+    args: &[&str],
+) -> Result<String, WorktreeError> {
+    let mut command = Command::new("git");
+    command.args(args).current_dir(working_dir);
+    if let Some(index_file) = index_file {
+        command.env("GIT_INDEX_FILE", index_file);
+    }
+    // Snapshots are machine commits; don't depend on the user's identity
+    command
+        .env("GIT_AUTHOR_NAME", "Conduit")
+        .env("GIT_AUTHOR_EMAIL", "conduit@localhost")
+        .env("GIT_COMMITTER_NAME", "Conduit")
+        .env("GIT_COMMITTER_EMAIL", "conduit@localhost");
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(WorktreeError::CommandFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn run_git(path: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn init_git_repo(path: &Path) {
+        run_git(path, &["init"]);
+        run_git(path, &["config", "user.email", "test@test.com"]);
+        run_git(path, &["config", "user.name", "Test"]);
+        std::fs::write(path.join("README.md"), "# Test").unwrap();
+        run_git(path, &["add", "."]);
+        run_git(path, &["commit", "-m", "Initial commit"]);
+    }
+
+    #[test]
+    fn test_snapshot_and_restore_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        init_git_repo(path);
+
+        std::fs::write(path.join("README.md"), "# Edited").unwrap();
+        std::fs::write(path.join("notes.txt"), "untracked").unwrap();
+        let head_before = run_git(path, &["rev-parse", "HEAD"]);
+        let status_before = run_git(path, &["status", "--porcelain"]);
+
+        let ref_name = CheckpointManager::ref_name(Uuid::new_v4(), Uuid::new_v4());
+        let commit = CheckpointManager::snapshot(path, &ref_name, "turn 1").unwrap();
+        assert_eq!(run_git(path, &["rev-parse", &ref_name]), commit);
+        // Snapshotting must not change HEAD or the index
+        assert_eq!(run_git(path, &["rev-parse", "HEAD"]), head_before);
+        assert_eq!(run_git(path, &["status", "--porcelain"]), status_before);
+
+        // Simulate an agent turn
+        std::fs::write(path.join("README.md"), "# Broken").unwrap();
+        std::fs::remove_file(path.join("notes.txt")).unwrap();
+        std::fs::write(path.join("new.rs"), "fn main() {}").unwrap();
+        assert_eq!(
+            CheckpointManager::changed_since(path, &commit).unwrap(),
+            vec!["README.md", "new.rs", "notes.txt"]
+        );
+
+        let backup_ref = CheckpointManager::backup_ref_name(Uuid::new_v4());
+        CheckpointManager::restore(path, &commit, &backup_ref).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(path.join("README.md")).unwrap(),
+            "# Edited"
+        );
+        assert_eq!(
+            std::fs::read_to_string(path.join("notes.txt")).unwrap(),
+            "untracked"
+        );
+        assert!(!path.join("new.rs").exists());
+        assert_eq!(run_git(path, &["rev-parse", "HEAD"]), head_before);
+        assert_eq!(run_git(path, &["status", "--porcelain"]), status_before);
+        // The pre-restore state is kept
+        assert_eq!(
+            run_git(path, &["show", &format!("{}:README.md", backup_ref)]),
+            "# Broken"
+        );
+
+        CheckpointManager::delete_ref(path, &ref_name).unwrap();
+        assert!(Command::new("git")
+            .args(["rev-parse", "--verify", "-q", &ref_name])
+            .current_dir(path)
+            .output()
+            .map(|o| !o.status.success())
+            .unwrap());
+    }
+    #[test]
+    fn test_snapshot_keeps_staged_files_git_ignores() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        init_git_repo(path);
+        std::fs::write(path.join(".gitignore"), "*.lock\n").unwrap();
+        std::fs::write(path.join("deps.lock"), "pinned").unwrap();
+        run_git(path, &["add", "-f", "deps.lock"]);
+
+        let ref_name = CheckpointManager::ref_name(Uuid::new_v4(), Uuid::new_v4());
+        let commit = CheckpointManager::snapshot(path, &ref_name, "turn 1").unwrap();
+        assert_eq!(
+            run_git(path, &["show", &format!("{}:deps.lock", commit)]),
+            "pinned"
+        );
+        assert_eq!(
+            run_git(path, &["diff", "--cached", "--name-only"]),
+            "deps.lock"
+        );
+    }
+}
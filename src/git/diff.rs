@@ -0,0 +1,556 @@
+//! Workspace diff loading, parsing and per-hunk patch application
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use super::worktree::WorktreeError;
+use super::PrManager;
+
+/// Kind of a single line inside a hunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+/// One line of a hunk, with its line number on each side
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub content: String,
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+}
+
+/// A single `@@` hunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffHunk {
+    /// The `@@ -a,b +c,d @@ ...` header line
+    pub header: String,
+    pub lines: Vec<DiffLine>,
+    /// Raw patch lines (header included) used to rebuild a one-hunk patch
+    raw: Vec<String>,
+}
+
+impl DiffHunk {
+    /// The hunk as it appears in the patch, header included
+    pub fn text(&self) -> String {
+        self.raw.join("\n")
+    }
+
+    /// Working tree lines the hunk covers, as a start and a count
+    fn new_range(&self) -> (usize, usize) {
+        let range = self
+            .header
+            .split_whitespace()
+            .find_map(|part| part.strip_prefix('+'))
+            .unwrap_or_default();
+        let mut parts = range.split(',');
+        let start = parts
+            .next()
+            .and_then(|start| start.parse().ok())
+            .unwrap_or(0);
+        let count = parts.next().map_or(1, |count| count.parse().unwrap_or(0));
+        (start, count)
+    }
+
+    /// Whether the two hunks cover a common working tree line. A hunk that
+    /// only deletes covers the line its deletion sits at.
+    fn overlaps(&self, other: &DiffHunk) -> bool {
+        let (start, count) = self.new_range();
+        let (other_start, other_count) = other.new_range();
+        start < other_start + other_count.max(1) && other_start < start + count.max(1)
+    }
+}
+
+/// Changes to one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff {
+    /// Path before the change (`None` for added files)
+    pub old_path: Option<String>,
+    /// Path after the change (`None` for deleted files)
+    pub new_path: Option<String>,
+    pub hunks: Vec<DiffHunk>,
+    pub is_binary: bool,
+    /// Raw `diff --git` .. `+++` header lines
+    header: Vec<String>,
+}
+
+impl FileDiff {
+    /// Path to show for this file, preferring the new side
+    pub fn display_path(&self) -> &str {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or("(unknown)")
+    }
+
+    pub fn additions(&self) -> usize {
+        self.count(DiffLineKind::Added)
+    }
+
+    pub fn deletions(&self) -> usize {
+        self.count(DiffLineKind::Removed)
+    }
+
+    fn count(&self, kind: DiffLineKind) -> usize {
+        self.hunks
+            .iter()
+            .flat_map(|hunk| &hunk.lines)
+            .filter(|line| line.kind == kind)
+            .count()
+    }
+
+    /// Build a patch containing only the given hunk, suitable for `git apply`
+    pub fn hunk_patch(&self, index: usize) -> Option<String> {
+        let hunk = self.hunks.get(index)?;
+        let mut patch = String::new();
+        for line in self.header.iter().chain(&hunk.raw) {
+            patch.push_str(line);
+            patch.push('\n');
+        }
+        Some(patch)
+    }
+}
+
+/// What to do with a single hunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HunkAction {
+    /// Add the hunk to the index
+    Stage,
+    /// Undo the hunk in the working tree
+    Revert,
+}
+
+impl HunkAction {
+    pub fn past_tense(&self) -> &'static str {
+        match self {
+            HunkAction::Stage => "staged",
+            HunkAction::Revert => "reverted",
+        }
+    }
+}
+
+/// The diff of a workspace against the point where it left its base branch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkspaceDiff {
+    /// Branch the workspace is compared against, e.g. `origin/main`
+    pub base_ref: String,
+    /// Merge-base commit of `base_ref` and `HEAD`
+    pub base_commit: String,
+    pub files: Vec<FileDiff>,
+    /// Changes not yet in the index, which staging applies from
+    unstaged: Vec<FileDiff>,
+}
+
+impl WorkspaceDiff {
+    /// Diff the working tree (committed, staged, unstaged and untracked
+    /// changes) against the merge-base with the repository's default branch
+    pub fn load(working_dir: &Path) -> Result<Self, WorktreeError> {
+        let default_branch = PrManager::get_default_branch(working_dir);
+        let (base_ref, base_commit) = [format!("origin/{}", default_branch), default_branch]
+            .into_iter()
+            .find_map(|base_ref| {
+                let commit = git_stdout(working_dir, &["merge-base", "HEAD", &base_ref]).ok()?;
+                Some((base_ref, commit.trim().to_string()))
+            })
+            .map_or_else(
+                || {
+                    git_stdout(working_dir, &["rev-parse", "HEAD"])
+                        .map(|commit| ("HEAD".to_string(), commit.trim().to_string()))
+                },
+                Ok,
+            )?;
+        Self::load_against(working_dir, base_ref, base_commit)
+    }
+
+    fn load_against(
+        working_dir: &Path,
+        base_ref: String,
+        base_commit: String,
+    ) -> Result<Self, WorktreeError> {
+        let diff_args = ["--no-optional-locks", "diff", "--no-color", "--no-ext-diff"];
+        let mut text = git_stdout(
+            working_dir,
+            &[&diff_args[..], &[base_commit.as_str()]].concat(),
+        )?;
+        let mut unstaged_text = git_stdout(working_dir, &diff_args)?;
+
+        let untracked = git_stdout(
+            working_dir,
+            &["ls-files", "--others", "--exclude-standard", "-z"],
+        )?;
+        for path in untracked.split('\0').filter(|path| !path.is_empty()) {
+            // `--no-index` exits with 1 when the files differ, so read stdout directly
+            let output = Command::new("git")
+                .args([
+                    "--no-optional-locks",
+                    "diff",
+                    "--no-color",
+                    "--no-ext-diff",
+                    "--no-index",
+                    "--",
+                    "/dev/null",
+                    path,
+                ])
+                .current_dir(working_dir)
+                .output()?;
+            let untracked_diff = String::from_utf8_lossy(&output.stdout);
+            text.push_str(&untracked_diff);
+            unstaged_text.push_str(&untracked_diff);
+        }
+
+        let mut diff = Self::parse(base_ref, base_commit, &text);
+        diff.unstaged = Self::parse(String::new(), String::new(), &unstaged_text).files;
+        Ok(diff)
+    }
+
+    /// Parse `git diff` output
+    pub fn parse(base_ref: String, base_commit: String, text: &str) -> Self {
+        let mut files: Vec<FileDiff> = Vec::new();
+        let mut old_line = 0;
+        let mut new_line = 0;
+
+        for line in text.lines() {
+            if line.starts_with("diff --git ") {
+                files.push(FileDiff {
+                    old_path: None,
+                    new_path: None,
+                    hunks: Vec::new(),
+                    is_binary: false,
+                    header: vec![line.to_string()],
+                });
+                continue;
+            }
+            let Some(file) = files.last_mut() else {
+                continue;
+            };
+
+            if let Some(header) = line.strip_prefix("@@") {
+                let (old_start, new_start) = parse_hunk_range(header).unwrap_or((0, 0));
+                old_line = old_start;
+                new_line = new_start;
+                file.hunks.push(DiffHunk {
+                    header: line.to_string(),
+                    lines: Vec::new(),
+                    raw: vec![line.to_string()],
+                });
+                continue;
+            }
+
+            let Some(hunk) = file.hunks.last_mut() else {
+                // Still in the file header
+                if let Some(path) = line.strip_prefix("--- ") {
+                    file.old_path = strip_diff_prefix(path);
+                } else if let Some(path) = line.strip_prefix("+++ ") {
+                    file.new_path = strip_diff_prefix(path);
+                } else if line.starts_with("Binary files ") {
+                    file.is_binary = true;
+                }
+                file.header.push(line.to_string());
+                continue;
+            };
+
+            hunk.raw.push(line.to_string());
+            let (kind, content) = match line.chars().next() {
+                Some('+') => (DiffLineKind::Added, &line[1..]),
+                Some('-') => (DiffLineKind::Removed, &line[1..]),
+                Some(' ') => (DiffLineKind::Context, &line[1..]),
+                // "\ No newline at end of file" and anything unexpected
+                _ => continue,
+            };
+            let (old, new) = match kind {
+                DiffLineKind::Added => (None, Some(new_line)),
+                DiffLineKind::Removed => (Some(old_line), None),
+                DiffLineKind::Context => (Some(old_line), Some(new_line)),
+            };
+            if old.is_some() {
+                old_line += 1;
+            }
+            if new.is_some() {
+                new_line += 1;
+            }
+            hunk.lines.push(DiffLine {
+                kind,
+                content: content.to_string(),
+                old_line: old,
+                new_line: new,
+            });
+        }
+
+        // Renames and mode changes without hunks carry the paths in the header only
+        for file in &mut files {
+            if file.old_path.is_none() && file.new_path.is_none() {
+                file.new_path = file.header[0]
+                    .rsplit_once(" b/")
+                    .map(|(_, path)| path.to_string());
+            }
+        }
+
+        Self {
+            base_ref,
+            base_commit,
+            files,
+            unstaged: Vec::new(),
+        }
+    }
+
+    /// Patch staging a hunk of `files`: the not-yet-staged changes it covers,
+    /// taken from the index-to-working-tree diff so `git apply --cached`
+    /// applies. `None` when the hunk is already committed or staged.
+    pub fn stage_patch(&self, file: usize, hunk: usize) -> Option<String> {
+        let file = self.files.get(file)?;
+        let hunk = file.hunks.get(hunk)?;
+        let unstaged = self
+            .unstaged
+            .iter()
+            .find(|unstaged| unstaged.display_path() == file.display_path())?;
+        let hunks: Vec<&DiffHunk> = unstaged
+            .hunks
+            .iter()
+            .filter(|candidate| candidate.overlaps(hunk))
+            .collect();
+        if hunks.is_empty() {
+            return None;
+        }
+        let mut patch = String::new();
+        for line in unstaged
+            .header
+            .iter()
+            .chain(hunks.iter().flat_map(|hunk| &hunk.raw))
+        {
+            patch.push_str(line);
+            patch.push('\n');
+        }
+        Some(patch)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn additions(&self) -> usize {
+        self.files.iter().map(FileDiff::additions).sum()
+    }
+
+    pub fn deletions(&self) -> usize {
+        self.files.iter().map(FileDiff::deletions).sum()
+    }
+
+    /// The whole diff as `git diff` printed it
+    pub fn patch(&self) -> String {
+        let mut patch = String::new();
+        for file in &self.files {
+            let hunk_lines = file.hunks.iter().flat_map(|hunk| &hunk.raw);
+            for line in file.header.iter().chain(hunk_lines) {
+                patch.push_str(line);
+                patch.push('\n');
+            }
+        }
+        patch
+    }
+}
+
+/// Apply a patch from [`WorkspaceDiff::stage_patch`] to the index, or one from
+/// [`FileDiff::hunk_patch`] to the working tree
+pub fn apply_hunk(
+    working_dir: &Path,
+    patch: &str,
+    action: HunkAction,
+) -> Result<(), WorktreeError> {
+    let args: &[&str] = match action {
+        HunkAction::Stage => &["apply", "--cached", "--recount", "-"],
+        HunkAction::Revert => &["apply", "--reverse", "--recount", "-"],
+    };
+    let mut child = Command::new("git")
+        .args(args)
+        .current_dir(working_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(patch.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(WorktreeError::CommandFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn git_stdout(working_dir: &Path, args: &[&str]) -> Result<String, WorktreeError> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(working_dir)
+        .output()?;
+    if !output.status.success() {
+        return Err(WorktreeError::CommandFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parse the start lines out of ` -a,b +c,d @@ ...`
+fn parse_hunk_range(header: &str) -> Option<(usize, usize)> {
+    let mut parts = header.split_whitespace();
+    let old = parts.next()?.strip_prefix('-')?;
+    let new = parts.next()?.strip_prefix('+')?;
+    let start = |range: &str| range.split(',').next()?.parse().ok();
+    Some((start(old)?, start(new)?))
+}
+
+/// Turn `a/src/lib.rs` into `src/lib.rs`; `/dev/null` means the side is absent
+fn strip_diff_prefix(path: &str) -> Option<String> {
+    let path = path.trim_end();
+    if path == "/dev/null" {
+        return None;
+    }
+    let path = path
+        .strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path);
+    Some(path.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,4 @@
+ fn main() {
+-    old();
++    new();
++    extra();
+ }
+@@ -10,2 +11,2 @@ fn other() {
+-    a
++    b
+ }
+diff --git a/notes.txt b/notes.txt
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/notes.txt
+@@ -0,0 +1 @@
++hello
+\\ No newline at end of file
+";
+
+    #[test]
+    fn test_parse_files_and_hunks() {
+        let diff = WorkspaceDiff::parse("origin/main".into(), "abc".into(), SAMPLE);
+        assert_eq!(diff.files.len(), 2);
+
+        let lib = &diff.files[0];
+        assert_eq!(lib.display_path(), "src/lib.rs");
+        assert_eq!(lib.hunks.len(), 2);
+        assert_eq!((lib.additions(), lib.deletions()), (3, 2));
+
+        let first = &lib.hunks[0].lines;
+        assert_eq!(first[1].kind, DiffLineKind::Removed);
+        assert_eq!(first[1].old_line, Some(2));
+        assert_eq!(first[2].new_line, Some(2));
+        assert_eq!(first[4].old_line, Some(3));
+        assert_eq!(first[4].new_line, Some(4));
+
+        let notes = &diff.files[1];
+        assert_eq!(notes.old_path, None);
+        assert_eq!(notes.display_path(), "notes.txt");
+        assert_eq!(notes.hunks[0].lines.len(), 1);
+    }
+
+    #[test]
+    fn test_patch_round_trips() {
+        let diff = WorkspaceDiff::parse("origin/main".into(), "abc".into(), SAMPLE);
+        assert_eq!(diff.patch(), SAMPLE);
+    }
+
+    #[test]
+    fn test_hunk_patch_keeps_file_header() {
+        let diff = WorkspaceDiff::parse("origin/main".into(), "abc".into(), SAMPLE);
+        let patch = diff.files[0].hunk_patch(1).unwrap();
+        assert!(patch.starts_with("diff --git a/src/lib.rs b/src/lib.rs\n"));
+        assert!(patch.contains("+++ b/src/lib.rs\n@@ -10,2 +11,2 @@ fn other() {\n"));
+        assert!(!patch.contains("new();"));
+
+        let new_file = diff.files[1].hunk_patch(0).unwrap();
+        assert!(new_file.ends_with("+hello\n\\ No newline at end of file\n"));
+        assert!(diff.files[1].hunk_patch(1).is_none());
+    }
+
+    fn run_git(path: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn test_adjacent_hunks_do_not_overlap() {
+        let hunk = |header: &str| DiffHunk {
+            header: header.to_string(),
+            lines: Vec::new(),
+            raw: Vec::new(),
+        };
+        let first = hunk("@@ -1,7 +1,7 @@");
+        let second = hunk("@@ -8,7 +8,7 @@");
+        assert!(!first.overlaps(&second));
+        assert!(!second.overlaps(&first));
+        assert!(first.overlaps(&hunk("@@ -5,4 +5,4 @@")));
+        assert!(second.overlaps(&hunk("@@ -10,1 +10,0 @@")));
+    }
+
+    #[test]
+    fn test_stage_hunk_after_committed_change_in_same_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path();
+        run_git(path, &["init"]);
+        run_git(path, &["config", "user.email", "test@test.com"]);
+        run_git(path, &["config", "user.name", "Test"]);
+        let mut lines: Vec<String> = (1..=20).map(|n| format!("line {}", n)).collect();
+        std::fs::write(path.join("file.txt"), lines.join("\n") + "\n").unwrap();
+        run_git(path, &["add", "."]);
+        run_git(path, &["commit", "-m", "base"]);
+        let base = run_git(path, &["rev-parse", "HEAD"]);
+
+        // Committed on the branch, then a later edit in the same file
+        lines[1] = "committed".to_string();
+        std::fs::write(path.join("file.txt"), lines.join("\n") + "\n").unwrap();
+        run_git(path, &["commit", "-am", "branch change"]);
+        lines[17] = "edited".to_string();
+        std::fs::write(path.join("file.txt"), lines.join("\n") + "\n").unwrap();
+
+        let diff = WorkspaceDiff::load_against(path, "base".into(), base.clone()).unwrap();
+        assert_eq!(diff.files[0].hunks.len(), 2);
+        assert_eq!(diff.stage_patch(0, 0), None);
+
+        let patch = diff.stage_patch(0, 1).unwrap();
+        apply_hunk(path, &patch, HunkAction::Stage).unwrap();
+        let staged = run_git(path, &["diff", "--cached"]);
+        assert!(staged.contains("+edited"));
+        assert!(!staged.contains("committed"));
+
+        // Once staged, the hunk has nothing left to stage
+        let diff = WorkspaceDiff::load_against(path, "base".into(), base).unwrap();
+        assert_eq!(diff.stage_patch(0, 1), None);
+    }
+}
@@ -0,0 +1,422 @@
+//! Workspace setup and teardown hooks
+//!
+//! A repository can check in `.conduit/hooks.toml` to bootstrap new
+//! workspaces: files copied or symlinked from the main checkout (`.env`,
+//! `node_modules`, build caches) and a setup script run in the new workspace,
+//! plus a teardown script run before a workspace is archived. Scripts run
+//! with `sh -c` (`cmd /C` on Windows) and get the workspace details in
+//! `CONDUIT_ROOT_PATH`, `CONDUIT_WORKSPACE_PATH`, `CONDUIT_WORKSPACE_NAME`
+//! and `CONDUIT_BRANCH`.
+
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+
+use serde::Deserialize;
+
+/// Hooks file looked up in a repository's main checkout
+pub const HOOKS_FILE: &str = ".conduit/hooks.toml";
+
+/// Repository hooks, as written in `.conduit/hooks.toml`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct WorkspaceHooks {
+    /// Paths copied from the main checkout into each new workspace
+    #[serde(default)]
+    pub copy: Vec<String>,
+    /// Paths symlinked from each new workspace to the main checkout
+    #[serde(default)]
+    pub symlink: Vec<String>,
+    /// Script run in a new workspace once files are in place
+    pub setup: Option<String>,
+    /// Script run in a workspace before it is archived
+    pub teardown: Option<String>,
+}
+
+/// The workspace a hook runs for
+#[derive(Debug, Clone, Copy)]
+pub struct HookContext<'a> {
+    /// The repository's main checkout
+    pub root_path: &'a Path,
+    pub workspace_path: &'a Path,
+    pub workspace_name: &'a str,
+    pub branch: &'a str,
+}
+
+impl WorkspaceHooks {
+    /// Read `.conduit/hooks.toml` from `repo_root`; `Ok(None)` when there is none
+    pub fn load(repo_root: &Path) -> Result<Option<Self>, String> {
+        let path = repo_root.join(HOOKS_FILE);
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(format!("Failed to read {}: {}", path.display(), err)),
+        };
+        let hooks: WorkspaceHooks = toml::from_str(&contents)
+            .map_err(|err| format!("Failed to parse {}: {}", path.display(), err))?;
+        for entry in hooks.copy.iter().chain(&hooks.symlink) {
+            if !is_relative_inside(entry) {
+                return Err(format!(
+                    "Invalid path '{}' in {}: paths must be relative to the repository",
+                    entry,
+                    path.display()
+                ));
+            }
+        }
+        Ok(Some(hooks))
+    }
+
+    /// Whether a new workspace has anything to set up
+    pub fn has_setup(&self) -> bool {
+        !self.copy.is_empty() || !self.symlink.is_empty() || self.setup.is_some()
+    }
+
+    /// Copy and link files, then run the setup script, reporting progress
+    /// and script output one line at a time
+    pub fn run_setup(
+        &self,
+        ctx: HookContext<'_>,
+        mut on_line: impl FnMut(String),
+    ) -> Result<(), String> {
+        for entry in &self.copy {
+            let source = ctx.root_path.join(entry);
+            let target = ctx.workspace_path.join(entry);
+            if !source.exists() {
+                on_line(format!("Skipped {} (not in the main checkout)", entry));
+            } else if target.exists() {
+                on_line(format!("Skipped {} (already in the workspace)", entry));
+            } else {
+                copy_recursive(&source, &target)
+                    .map_err(|err| format!("Failed to copy {}: {}", entry, err))?;
+                on_line(format!("Copied {}", entry));
+            }
+        }
+        for entry in &self.symlink {
+            let source = ctx.root_path.join(entry);
+            let target = ctx.workspace_path.join(entry);
+            if !source.exists() {
+                on_line(format!("Skipped {} (not in the main checkout)", entry));
+            } else if target.symlink_metadata().is_ok() {
+                on_line(format!("Skipped {} (already in the workspace)", entry));
+            } else {
+                symlink(&source, &target)
+                    .map_err(|err| format!("Failed to link {}: {}", entry, err))?;
+                on_line(format!("Linked {}", entry));
+            }
+        }
+        match &self.setup {
+            Some(script) => run_script("setup", script, ctx, on_line),
+            None => Ok(()),
+        }
+    }
+
+    /// Run the teardown script, if any, reporting its output one line at a time
+    pub fn run_teardown(
+        &self,
+        ctx: HookContext<'_>,
+        on_line: impl FnMut(String),
+    ) -> Result<(), String> {
+        match &self.teardown {
+            Some(script) => run_script("teardown", script, ctx, on_line),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Load the repository's hooks and run its teardown script for a workspace
+/// about to be archived. Output goes to the log; the error, if any, is meant
+/// to be reported as an archive warning.
+pub fn run_workspace_teardown(ctx: HookContext<'_>) -> Result<(), String> {
+    // The worktree may already be gone; there is nothing left to tear down
+    if !ctx.workspace_path.exists() {
+        return Ok(());
+    }
+    let Some(hooks) = WorkspaceHooks::load(ctx.root_path)? else {
+        return Ok(());
+    };
+    hooks.run_teardown(ctx, |line| {
+        tracing::info!(workspace = %ctx.workspace_name, "teardown: {}", line);
+    })
+}
+
+fn run_script(
+    hook: &str,
+    script: &str,
+    ctx: HookContext<'_>,
+    mut on_line: impl FnMut(String),
+) -> Result<(), String> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    on_line(format!("$ {}", script));
+    let mut child = Command::new(shell)
+        .arg(flag)
+        .arg(script)
+        .current_dir(ctx.workspace_path)
+        .env("CONDUIT_ROOT_PATH", ctx.root_path)
+        .env("CONDUIT_WORKSPACE_PATH", ctx.workspace_path)
+        .env("CONDUIT_WORKSPACE_NAME", ctx.workspace_name)
+        .env("CONDUIT_BRANCH", ctx.branch)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Failed to run {} script: {}", hook, err))?;
+
+    // Interleave stdout and stderr in the order lines arrive
+    let (line_tx, line_rx) = mpsc::channel();
+    let readers: Vec<_> = [
+        child
+            .stdout
+            .take()
+            .map(|out| Box::new(out) as Box<dyn Read + Send>),
+        child
+            .stderr
+            .take()
+            .map(|err| Box::new(err) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|stream| {
+        let line_tx = line_tx.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stream).lines() {
+                let Ok(line) = line else { break };
+                if line_tx.send(line).is_err() {
+                    break;
+                }
+            }
+        })
+    })
+    .collect();
+    drop(line_tx);
+    for line in line_rx {
+        on_line(line);
+    }
+    for reader in readers {
+        let _ = reader.join();
+    }
+
+    let status = child
+        .wait()
+        .map_err(|err| format!("Failed to run {} script: {}", hook, err))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(match status.code() {
+            Some(code) => format!("{} script exited with status {}", hook, code),
+            None => format!("{} script was terminated", hook),
+        })
+    }
+}
+
+/// Relative paths that stay inside the repository
+fn is_relative_inside(entry: &str) -> bool {
+    let path = Path::new(entry);
+    !entry.trim().is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// Copy a file or directory tree. A listed entry that is a symlink is
+/// followed, so the workspace gets what it points at.
+fn copy_recursive(source: &Path, target: &Path) -> std::io::Result<()> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if !source.is_dir() {
+        std::fs::copy(source, target)?;
+        return Ok(());
+    }
+    copy_tree(source, source, target)
+}
+
+/// Copy the directory `source` inside the copied tree `top`. Symlinks are
+/// recreated rather than followed, so a link to a parent directory can't
+/// recurse forever; a relative link that leaves `top` is pointed at its
+/// original target so it still resolves from the workspace.
+fn copy_tree(top: &Path, source: &Path, target: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(target)?;
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let (path, dest) = (entry.path(), target.join(entry.file_name()));
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            let link = std::fs::read_link(&path)?;
+            let resolved = normalize(&source.join(&link));
+            if link.is_relative() && resolved.starts_with(top) {
+                symlink(&link, &dest)?;
+            } else {
+                symlink(&resolved, &dest)?;
+            }
+        } else if file_type.is_dir() {
+            copy_tree(top, &path, &dest)?;
+        } else {
+            std::fs::copy(&path, &dest)?;
+        }
+    }
+    Ok(())
+}
+
+/// Resolve `.` and `..` components without touching the file system
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+fn symlink(source: &Path, target: &Path) -> std::io::Result<()> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(source, target)
+    }
+    #[cfg(windows)]
+    {
+        if source.is_dir() {
+            std::os::windows::fs::symlink_dir(source, target)
+        } else {
+            std::os::windows::fs::symlink_file(source, target)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn context<'a>(root: &'a Path, workspace: &'a Path) -> HookContext<'a> {
+        HookContext {
+            root_path: root,
+            workspace_path: workspace,
+            workspace_name: "bold-fox",
+            branch: "me/bold-fox",
+        }
+    }
+
+    #[test]
+    fn test_load_hooks_file() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(WorkspaceHooks::load(dir.path()).unwrap(), None);
+
+        std::fs::create_dir_all(dir.path().join(".conduit")).unwrap();
+        std::fs::write(
+            dir.path().join(HOOKS_FILE),
+            "copy = [\".env\"]\nsymlink = [\"node_modules\"]\nsetup = \"npm ci\"\n",
+        )
+        .unwrap();
+        let hooks = WorkspaceHooks::load(dir.path()).unwrap().unwrap();
+        assert_eq!(hooks.copy, vec![".env"]);
+        assert_eq!(hooks.setup.as_deref(), Some("npm ci"));
+        assert!(hooks.teardown.is_none());
+        assert!(hooks.has_setup());
+
+        std::fs::write(dir.path().join(HOOKS_FILE), "copy = [\"../secrets\"]").unwrap();
+        assert!(WorkspaceHooks::load(dir.path()).is_err());
+        std::fs::write(dir.path().join(HOOKS_FILE), "copy = [\"/etc/passwd\"]").unwrap();
+        assert!(WorkspaceHooks::load(dir.path()).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_setup_links_files_and_streams_output() {
+        let root = TempDir::new().unwrap();
+        let workspace = TempDir::new().unwrap();
+        std::fs::write(root.path().join(".env"), "KEY=1\n").unwrap();
+        std::fs::create_dir_all(root.path().join("node_modules/pkg")).unwrap();
+
+        let hooks = WorkspaceHooks {
+            copy: vec![".env".to_string(), "missing.txt".to_string()],
+            symlink: vec!["node_modules".to_string()],
+            setup: Some("echo \"ready on $CONDUIT_BRANCH\"; echo warn >&2".to_string()),
+            teardown: None,
+        };
+        let mut lines = Vec::new();
+        hooks
+            .run_setup(context(root.path(), workspace.path()), |line| {
+                lines.push(line)
+            })
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(workspace.path().join(".env")).unwrap(),
+            "KEY=1\n"
+        );
+        assert!(workspace.path().join("node_modules/pkg").is_dir());
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with("Skipped missing.txt")));
+        assert!(lines.contains(&"ready on me/bold-fox".to_string()));
+        assert!(lines.contains(&"warn".to_string()));
+
+        let failing = WorkspaceHooks {
+            setup: Some("exit 3".to_string()),
+            ..Default::default()
+        };
+        let err = failing
+            .run_setup(context(root.path(), workspace.path()), |_| {})
+            .unwrap_err();
+        assert!(err.contains("status 3"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_recreates_symlinks() {
+        let root = TempDir::new().unwrap();
+        let workspace = TempDir::new().unwrap();
+        let shared = root.path().join("shared");
+        std::fs::create_dir_all(&shared).unwrap();
+        std::fs::write(shared.join(".env"), "KEY=1\n").unwrap();
+        std::os::unix::fs::symlink("shared/.env", root.path().join(".env")).unwrap();
+        let config = root.path().join("config");
+        std::fs::create_dir_all(&config).unwrap();
+        std::fs::write(config.join("app.toml"), "debug = true\n").unwrap();
+        std::os::unix::fs::symlink("app.toml", config.join("current.toml")).unwrap();
+        std::os::unix::fs::symlink("../shared/.env", config.join(".env")).unwrap();
+        // Following this link would copy the tree into itself forever
+        std::os::unix::fs::symlink("..", config.join("parent")).unwrap();
+
+        // A listed link is copied as what it points at
+        copy_recursive(&root.path().join(".env"), &workspace.path().join(".env")).unwrap();
+        let copied_env = workspace.path().join(".env");
+        assert!(!std::fs::symlink_metadata(&copied_env)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(std::fs::read_to_string(copied_env).unwrap(), "KEY=1\n");
+
+        copy_recursive(&config, &workspace.path().join("config")).unwrap();
+        let copied = workspace.path().join("config");
+        assert_eq!(
+            std::fs::read_to_string(copied.join("app.toml")).unwrap(),
+            "debug = true\n"
+        );
+        // Links within the tree stay relative; ones leaving it keep resolving
+        assert_eq!(
+            std::fs::read_link(copied.join("current.toml")).unwrap(),
+            Path::new("app.toml")
+        );
+        assert_eq!(
+            std::fs::read_to_string(copied.join(".env")).unwrap(),
+            "KEY=1\n"
+        );
+        assert_eq!(
+            std::fs::read_link(copied.join("parent")).unwrap(),
+            root.path()
+        );
+    }
+}
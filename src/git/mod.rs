@@ -1,11 +1,17 @@
 //! Git operations module
 
+mod checkpoint;
+mod diff;
+mod hooks;
 mod pr;
 mod status;
 mod workspace_mode;
 mod workspace_repo;
 mod worktree;
 
+pub use checkpoint::CheckpointManager;
+pub use diff::{apply_hunk, DiffHunk, DiffLine, DiffLineKind, FileDiff, HunkAction, WorkspaceDiff};
+pub use hooks::{run_workspace_teardown, HookContext, WorkspaceHooks, HOOKS_FILE};
 pub use pr::{
     CheckState, CheckStatus, MergeReadiness, MergeableStatus, PrManager, PrPreflightResult,
     PrState, PrStatus, ReviewDecision,
@@ -52,6 +52,22 @@ impl GitDiffStats {
         stats
     }
 
+    /// Get diff stats between `base` and the working tree, so commits made
+    /// since `base` count alongside uncommitted changes
+    pub fn since_commit(working_dir: &Path, base: &str) -> Self {
+        let output = Command::new("git")
+            .args(["--no-optional-locks", "diff", "--shortstat", base])
+            .current_dir(working_dir)
+            .output();
+
+        match output {
+            Ok(o) if o.status.success() => {
+                Self::parse_shortstat(&String::from_utf8_lossy(&o.stdout))
+            }
+            _ => GitDiffStats::default(),
+        }
+    }
+
     /// Parse from `git diff --shortstat` output
     /// Format: "1 file changed, 44 insertions(+), 10 deletions(-)"
     fn parse_shortstat(output: &str) -> Self {
@@ -0,0 +1,480 @@
+//! Headless agent runs (`conduit run`).
+//!
+//! Creates an isolated workspace for a repository, drives a single agent turn
+//! to completion and streams the agent's events to a writer, either as JSONL
+//! or as a readable transcript.
+
+use std::fmt;
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::agent::{
+    AgentEvent, AgentInput, AgentStartConfig, AgentType, ApprovalPolicy, ControlRequestEvent,
+    FileOperation, ToolApprovalChoice,
+};
+use crate::core::services::{
+    BudgetService, CreateSessionParams, InFlightUsage, SessionService, TurnUsageContext,
+    UsageService, WorkspaceService,
+};
+use crate::core::ConduitCore;
+use crate::data::Workspace;
+use crate::git::{HookContext, WorkspaceHooks};
+
+/// Longest tool argument summary shown in the text transcript.
+const TOOL_SUMMARY_MAX_CHARS: usize = 120;
+
+/// How agent events are written to stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// One serialized `AgentEvent` per line.
+    #[default]
+    Jsonl,
+    /// Human-readable transcript.
+    Text,
+}
+
+impl OutputFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputFormat::Jsonl => "jsonl",
+            OutputFormat::Text => "text",
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "jsonl" | "json" => Ok(OutputFormat::Jsonl),
+            "text" | "transcript" => Ok(OutputFormat::Text),
+            other => Err(format!(
+                "Invalid output format: {other}. Must be one of: jsonl, text"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Options for a single headless run.
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    /// Repository to create the workspace in
+    pub repo: PathBuf,
+    pub agent_type: AgentType,
+    /// Model override (None = configured default for the agent)
+    pub model: Option<String>,
+    pub prompt: String,
+    pub format: OutputFormat,
+    /// Approval policy override (None = repository/config policy)
+    pub approval_policy: Option<ApprovalPolicy>,
+}
+
+/// Result of a headless run.
+#[derive(Debug, Clone)]
+pub struct RunOutcome {
+    /// Workspace the agent ran in (kept for inspection)
+    pub workspace: Workspace,
+    /// Agent session ID, if the agent reported one
+    pub agent_session_id: Option<String>,
+    /// Failure reason when the turn did not complete
+    pub error: Option<String>,
+}
+
+impl RunOutcome {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Create a workspace, run the agent to completion and stream its events to `out`.
+///
+/// Permission prompts cannot be answered without a terminal, so they are
+/// declined; pass an approval policy that pre-approves the tools the task needs.
+pub async fn run(
+    core: &ConduitCore,
+    options: RunOptions,
+    out: &mut impl Write,
+) -> Result<RunOutcome> {
+    let runner = core.runner_for(options.agent_type);
+    if !runner.is_available() {
+        return Err(anyhow!(
+            "{} is not available",
+            core.config().agent_display_name(options.agent_type)
+        ));
+    }
+
+    let repo = WorkspaceService::ensure_repository(core, &options.repo)?;
+    let workspace = WorkspaceService::create_workspace(core, &repo)?;
+    eprintln!(
+        "conduit: running {} in workspace {} ({}) at {}",
+        core.config().agent_display_name(options.agent_type),
+        workspace.name,
+        workspace.branch,
+        workspace.path.display()
+    );
+    if let Some(root_path) = repo.base_path.clone() {
+        if let Err(err) = run_workspace_setup(root_path, workspace.clone()).await {
+            return Ok(RunOutcome {
+                workspace,
+                agent_session_id: None,
+                error: Some(format!("Workspace setup failed: {}", err)),
+            });
+        }
+    }
+
+    let session = SessionService::create_session(
+        core,
+        CreateSessionParams {
+            workspace_id: Some(workspace.id),
+            agent_type: options.agent_type,
+            model: options.model.clone(),
+        },
+    )?;
+    // Scripted runs stay reachable from the workspace without opening a tab.
+    if let Some(store) = core.session_tab_store() {
+        if let Err(e) = store.set_open(session.id, false) {
+            tracing::warn!(error = %e, "Failed to close headless session tab");
+        }
+    }
+
+    let usage_context = TurnUsageContext {
+        session_id: Some(session.id),
+        agent_session_id: None,
+        agent_type: options.agent_type,
+        model: session.model.clone(),
+        workspace_id: Some(workspace.id),
+    };
+    let budget = BudgetService::status(core, &usage_context, None);
+    if budget.is_exceeded() {
+        return Ok(RunOutcome {
+            workspace,
+            agent_session_id: None,
+            error: budget.message(),
+        });
+    }
+    let interrupt_on_budget = core.config().budget.interrupts_running_turn();
+    let mut in_flight_usage = InFlightUsage::default();
+
+    let approval_policy = options
+        .approval_policy
+        .unwrap_or_else(|| core.approval_policy_for_workspace(Some(workspace.id)));
+    let tool_policy = core.tool_policy_for_workspace(Some(workspace.id));
+    let mut config = AgentStartConfig::new(options.prompt, workspace.path.clone())
+        .with_tools(core.claude_allowed_tools_for_workspace(Some(workspace.id), approval_policy))
+        .with_approval_policy(approval_policy)
+        .with_tool_policy(tool_policy);
+    if let Some(model) = session.model.clone() {
+        config = config.with_model(model);
+    }
+    if options.agent_type == AgentType::Codex {
+        let (approval, sandbox) = core.codex_permissions_for_workspace(Some(workspace.id));
+        config = config.with_codex_permissions(approval, sandbox);
+    }
+
+    let mut handle = runner.start(config).await.with_context(|| {
+        format!(
+            "Failed to start {}",
+            core.config().agent_display_name(options.agent_type)
+        )
+    })?;
+
+    let mut renderer = TranscriptRenderer::default();
+    let mut agent_session_id = None;
+    let mut error = None;
+    let mut finished = false;
+
+    while let Some(event) = handle.events.recv().await {
+        match options.format {
+            OutputFormat::Jsonl => writeln!(out, "{}", serde_json::to_string(&event)?)?,
+            OutputFormat::Text => write!(out, "{}", renderer.render(&event))?,
+        }
+        out.flush()?;
+
+        match &event {
+            AgentEvent::SessionInit(init) => {
+                let id = init.session_id.as_str().to_string();
+                record_agent_session_id(core, session.id, &id);
+                agent_session_id = Some(id);
+            }
+            AgentEvent::ControlRequest(request) => {
+                decline_control_request(options.agent_type, &handle.input_tx, request).await;
+            }
+            AgentEvent::TokenUsage(usage_event) if interrupt_on_budget => {
+                let turn_usage = in_flight_usage.observe(&usage_event.usage);
+                let budget = BudgetService::status(core, &usage_context, Some(&turn_usage));
+                if budget.is_exceeded() {
+                    // The interrupted turn never completes, so record what it used so far
+                    let context = TurnUsageContext {
+                        agent_session_id: agent_session_id.clone(),
+                        ..usage_context.clone()
+                    };
+                    if let Err(e) = UsageService::record_turn(core, context, &turn_usage) {
+                        tracing::warn!(error = %e, "Failed to record headless turn usage");
+                    }
+                    error = budget.message();
+                    finished = true;
+                    break;
+                }
+            }
+            AgentEvent::TurnCompleted(completed) => {
+                let context = TurnUsageContext {
+                    agent_session_id: agent_session_id.clone(),
+                    ..usage_context.clone()
+                };
+                if let Err(e) = UsageService::record_turn(core, context, &completed.usage) {
+                    tracing::warn!(error = %e, "Failed to record headless turn usage");
+                }
+                finished = true;
+                break;
+            }
+            AgentEvent::TurnFailed(failed) => {
+                error = Some(failed.error.clone());
+                finished = true;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    if finished {
+        // Long-lived agents (app servers, ACP) keep running after the turn.
+        if let Err(e) = runner.stop(&handle).await {
+            tracing::debug!(error = %e, "Failed to stop agent after headless run");
+        }
+    } else {
+        error = Some("Agent exited before completing the turn".to_string());
+    }
+
+    Ok(RunOutcome {
+        workspace,
+        agent_session_id,
+        error,
+    })
+}
+
+/// Run the repository's `.conduit/hooks.toml` setup in the new workspace,
+/// echoing its output to stderr.
+async fn run_workspace_setup(root_path: PathBuf, workspace: Workspace) -> Result<(), String> {
+    let hooks = match WorkspaceHooks::load(&root_path)? {
+        Some(hooks) if hooks.has_setup() => hooks,
+        _ => return Ok(()),
+    };
+    eprintln!("conduit: running workspace setup");
+    tokio::task::spawn_blocking(move || {
+        let ctx = HookContext {
+            root_path: &root_path,
+            workspace_path: &workspace.path,
+            workspace_name: &workspace.name,
+            branch: &workspace.branch,
+        };
+        hooks.run_setup(ctx, |line| eprintln!("conduit: setup: {}", line))
+    })
+    .await
+    .map_err(|err| format!("Setup task failed: {}", err))?
+}
+
+fn record_agent_session_id(core: &ConduitCore, session_id: Uuid, agent_session_id: &str) {
+    let Some(store) = core.session_tab_store() else {
+        return;
+    };
+    match store.get_by_id(session_id) {
+        Ok(Some(mut tab)) => {
+            tab.agent_session_id = Some(agent_session_id.to_string());
+            if let Err(e) = store.update(&tab) {
+                tracing::warn!(error = %e, "Failed to record agent session id");
+            }
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!(error = %e, "Failed to load headless session"),
+    }
+}
+
+async fn decline_control_request(
+    agent_type: AgentType,
+    input_tx: &Option<mpsc::Sender<AgentInput>>,
+    request: &ControlRequestEvent,
+) {
+    let input = if agent_type.uses_acp() {
+        AgentInput::AcpPermission {
+            request_id: request.request_id.clone(),
+            option_id: None,
+        }
+    } else if agent_type == AgentType::Opencode {
+        AgentInput::OpencodeQuestion {
+            request_id: request.request_id.clone(),
+            answers: None,
+        }
+    } else if agent_type == AgentType::Codex {
+        AgentInput::CodexApproval {
+            request_id: request.request_id.clone(),
+            choice: ToolApprovalChoice::Deny,
+        }
+    } else {
+        return;
+    };
+    if let Some(tx) = input_tx {
+        if let Err(e) = tx.send(input).await {
+            tracing::debug!(error = %e, "Failed to decline control request");
+        }
+    }
+}
+
+/// Renders agent events as a plain-text transcript.
+#[derive(Debug)]
+pub struct TranscriptRenderer {
+    at_line_start: bool,
+}
+
+impl Default for TranscriptRenderer {
+    fn default() -> Self {
+        Self {
+            at_line_start: true,
+        }
+    }
+}
+
+impl TranscriptRenderer {
+    pub fn render(&mut self, event: &AgentEvent) -> String {
+        match event {
+            AgentEvent::AssistantMessage(msg) => {
+                let mut text = msg.text.clone();
+                if msg.is_final && !text.ends_with('\n') {
+                    text.push('\n');
+                }
+                self.raw(text)
+            }
+            AgentEvent::ToolStarted(tool) => {
+                let summary = summarize_arguments(&tool.arguments);
+                if summary.is_empty() {
+                    self.line(format!("→ {}", tool.tool_name))
+                } else {
+                    self.line(format!("→ {} {}", tool.tool_name, summary))
+                }
+            }
+            AgentEvent::ToolCompleted(tool) if !tool.success => self.line(format!(
+                "  ✗ {}",
+                tool.error.as_deref().unwrap_or("tool failed")
+            )),
+            AgentEvent::CommandOutput(cmd) => match cmd.exit_code {
+                Some(code) => self.line(format!("$ {} (exit {})", cmd.command, code)),
+                None => String::new(),
+            },
+            AgentEvent::FileChanged(change) => {
+                let op = match change.operation {
+                    FileOperation::Create => "created",
+                    FileOperation::Update => "updated",
+                    FileOperation::Delete => "deleted",
+                };
+                self.line(format!("  {} {}", op, change.path))
+            }
+            AgentEvent::ControlRequest(request) => self.line(format!(
+                "? {} needs approval; declined (headless run)",
+                request.tool_name
+            )),
+            AgentEvent::TurnCompleted(done) => self.line(format!(
+                "— done ({} in / {} out tokens)",
+                done.usage.input_tokens, done.usage.output_tokens
+            )),
+            AgentEvent::TurnFailed(failed) => self.line(format!("— failed: {}", failed.error)),
+            AgentEvent::Error(err) => self.line(format!("error: {}", err.message)),
+            _ => String::new(),
+        }
+    }
+
+    fn raw(&mut self, text: String) -> String {
+        if !text.is_empty() {
+            self.at_line_start = text.ends_with('\n');
+        }
+        text
+    }
+
+    fn line(&mut self, text: String) -> String {
+        let prefix = if self.at_line_start { "" } else { "\n" };
+        self.at_line_start = true;
+        format!("{prefix}{text}\n")
+    }
+}
+
+fn summarize_arguments(arguments: &serde_json::Value) -> String {
+    let summary = match arguments {
+        serde_json::Value::Null => return String::new(),
+        serde_json::Value::Object(map) if map.is_empty() => return String::new(),
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    let summary = summary.replace('\n', " ");
+    if summary.chars().count() <= TOOL_SUMMARY_MAX_CHARS {
+        return summary;
+    }
+    let truncated: String = summary.chars().take(TOOL_SUMMARY_MAX_CHARS).collect();
+    format!("{truncated}…")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::{AssistantMessageEvent, TokenUsage, ToolStartedEvent, TurnCompletedEvent};
+
+    fn message(text: &str, is_final: bool) -> AgentEvent {
+        AgentEvent::AssistantMessage(AssistantMessageEvent {
+            text: text.to_string(),
+            is_final,
+        })
+    }
+
+    #[test]
+    fn test_output_format_parse() {
+        assert_eq!("jsonl".parse::<OutputFormat>(), Ok(OutputFormat::Jsonl));
+        assert_eq!("TEXT".parse::<OutputFormat>(), Ok(OutputFormat::Text));
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn test_transcript_breaks_line_before_tool_calls() {
+        let mut renderer = TranscriptRenderer::default();
+        let mut out = String::new();
+        out.push_str(&renderer.render(&message("Looking at ", false)));
+        out.push_str(&renderer.render(&message("the code", false)));
+        out.push_str(&renderer.render(&AgentEvent::ToolStarted(ToolStartedEvent {
+            tool_name: "Read".to_string(),
+            tool_id: "t1".to_string(),
+            arguments: serde_json::json!({"path": "src/lib.rs"}),
+        })));
+        out.push_str(&renderer.render(&message("Done.", true)));
+        out.push_str(
+            &renderer.render(&AgentEvent::TurnCompleted(TurnCompletedEvent {
+                usage: TokenUsage {
+                    input_tokens: 10,
+                    output_tokens: 5,
+                    ..Default::default()
+                },
+            })),
+        );
+
+        assert_eq!(
+            out,
+            "Looking at the code\n→ Read {\"path\":\"src/lib.rs\"}\nDone.\n— done (10 in / 5 out tokens)\n"
+        );
+    }
+
+    #[test]
+    fn test_summarize_arguments_truncates() {
+        assert_eq!(summarize_arguments(&serde_json::Value::Null), "");
+        let long = "x".repeat(TOOL_SUMMARY_MAX_CHARS + 10);
+        let summary = summarize_arguments(&serde_json::Value::String(long));
+        assert_eq!(summary.chars().count(), TOOL_SUMMARY_MAX_CHARS + 1);
+        assert!(summary.ends_with('…'));
+    }
+}
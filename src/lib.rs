@@ -3,6 +3,7 @@ pub mod config;
 pub mod core;
 pub mod data;
 pub mod git;
+pub mod headless;
 pub mod session;
 pub mod ui;
 pub mod util;
@@ -1,10 +1,12 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use conduit::{
+    agent::ApprovalPolicy,
     config::save_tool_path,
+    headless::{self, OutputFormat, RunOptions},
     ui::terminal_guard,
     util::{self, Tool, ToolAvailability},
-    App, Config,
+    AgentType, App, Config,
 };
 use std::fs::{self, OpenOptions};
 use std::path::{Path, PathBuf};
@@ -41,6 +43,41 @@ enum Commands {
         palette: bool,
     },
 
+    /// Run an agent headlessly in a new workspace and stream its events to stdout
+    Run {
+        /// Repository to create the workspace in
+        #[arg(long, value_name = "PATH")]
+        repo: PathBuf,
+
+        /// Agent to run (claude, codex, gemini, opencode, acp)
+        #[arg(long, default_value = "codex")]
+        agent: String,
+
+        /// Model to use (default: configured default for the agent)
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Prompt text
+        #[arg(
+            long,
+            conflicts_with = "prompt_file",
+            required_unless_present = "prompt_file"
+        )]
+        prompt: Option<String>,
+
+        /// Read the prompt from a file ("-" for stdin)
+        #[arg(long, value_name = "PATH")]
+        prompt_file: Option<PathBuf>,
+
+        /// Output format: jsonl or text
+        #[arg(long, default_value = "jsonl")]
+        format: OutputFormat,
+
+        /// Tool approval policy override: ask, allow-reads, allow-all
+        #[arg(long, value_parser = parse_approval_policy)]
+        approval: Option<ApprovalPolicy>,
+    },
+
     /// Start the web server
     Serve {
         /// Host address to bind to
@@ -71,6 +108,33 @@ async fn main() -> Result<()> {
         }) => {
             run_migrate_theme(&input, output.as_deref(), palette)?;
         }
+        Some(Commands::Run {
+            repo,
+            agent,
+            model,
+            prompt,
+            prompt_file,
+            format,
+            approval,
+        }) => {
+            let prompt = match (prompt, prompt_file) {
+                (Some(prompt), _) => prompt,
+                (None, Some(path)) => read_prompt_file(&path)?,
+                (None, None) => anyhow::bail!("Either --prompt or --prompt-file is required"),
+            };
+            let agent_type = parse_agent_type(&agent)?;
+            let options = RunOptions {
+                repo,
+                agent_type,
+                model,
+                prompt,
+                format,
+                approval_policy: approval,
+            };
+            if !run_headless(options).await? {
+                std::process::exit(1);
+            }
+        }
         Some(Commands::Serve { host, port }) => {
             run_web_server(host, port).await?;
         }
@@ -82,12 +146,8 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-/// Run the main application
-async fn run_app() -> Result<()> {
-    // Install panic hook to restore terminal state before printing panic message
-    terminal_guard::install_panic_hook();
-
-    // Initialize logging to file (~/.conduit/logs/conduit.log)
+/// Initialize logging to file (~/.conduit/logs/conduit.log)
+fn init_file_logging() -> Result<()> {
     fs::create_dir_all(util::logs_dir())?;
 
     let log_file = OpenOptions::new()
@@ -105,6 +165,16 @@ async fn run_app() -> Result<()> {
         .with_ansi(false) // Disable ANSI colors in log file
         .init();
 
+    Ok(())
+}
+
+/// Run the main application
+async fn run_app() -> Result<()> {
+    // Install panic hook to restore terminal state before printing panic message
+    terminal_guard::install_panic_hook();
+
+    init_file_logging()?;
+
     // Create config (loads from ~/.conduit/config.toml if present)
     let config = Config::load();
 
@@ -336,6 +406,75 @@ fn run_migrate_theme(input: &Path, output: Option<&Path>, extract_palette: bool)
     Ok(())
 }
 
+fn parse_agent_type(agent: &str) -> Result<AgentType> {
+    match agent.to_lowercase().as_str() {
+        "claude" => Ok(AgentType::Claude),
+        "codex" => Ok(AgentType::Codex),
+        "gemini" => Ok(AgentType::Gemini),
+        "opencode" => Ok(AgentType::Opencode),
+        "acp" => Ok(AgentType::Acp),
+        _ => anyhow::bail!(
+            "Invalid agent type: {}. Must be one of: claude, codex, gemini, opencode, acp",
+            agent
+        ),
+    }
+}
+
+fn parse_approval_policy(value: &str) -> std::result::Result<ApprovalPolicy, String> {
+    value.parse().map_err(|_| {
+        format!(
+            "Invalid approval policy: {}. Must be one of: ask, allow-reads, allow-all",
+            value
+        )
+    })
+}
+
+fn read_prompt_file(path: &Path) -> Result<String> {
+    let prompt = if path == Path::new("-") {
+        let mut buf = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut buf)?;
+        buf
+    } else {
+        fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?
+    };
+    if prompt.trim().is_empty() {
+        anyhow::bail!("Prompt is empty");
+    }
+    Ok(prompt)
+}
+
+/// Run a headless agent session. Returns whether the turn completed.
+async fn run_headless(options: RunOptions) -> Result<bool> {
+    use conduit::core::ConduitCore;
+
+    // Keep stdout free for agent events
+    init_file_logging()?;
+
+    let config = Config::load();
+    let tools = ToolAvailability::detect(&config.tool_paths);
+    if !tools.is_available(Tool::Git) {
+        anyhow::bail!("git is required but was not found");
+    }
+    let core = ConduitCore::new(config, tools);
+
+    let mut stdout = std::io::stdout().lock();
+    let outcome = headless::run(&core, options, &mut stdout).await?;
+
+    if let Some(error) = &outcome.error {
+        eprintln!("conduit: turn failed: {}", error);
+    }
+    if let Some(session_id) = &outcome.agent_session_id {
+        eprintln!("conduit: agent session {}", session_id);
+    }
+    eprintln!(
+        "conduit: workspace kept at {}",
+        outcome.workspace.path.display()
+    );
+
+    Ok(outcome.succeeded())
+}
+
 /// Run the web server
 async fn run_web_server(host: String, port: u16) -> Result<()> {
     use conduit::core::ConduitCore;
@@ -0,0 +1,580 @@
+//! Transcript export.
+//!
+//! Renders a session's chat messages as standalone Markdown, self-contained
+//! HTML, or JSONL of normalized `AgentEvent`s.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::agent::{
+    AgentEvent, AgentType, AssistantMessageEvent, ErrorEvent, ReasoningEvent, TokenUsage,
+    ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent,
+};
+use crate::ui::components::{ChatMessage, MessageRole, TurnSummary};
+use crate::util;
+
+/// Prefix of the hidden seed prompt sent to forked sessions.
+const FORK_SEED_MARKER: &str = "[CONDUIT_FORK_SEED]";
+
+/// Output format for an exported transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    Markdown,
+    Html,
+    Jsonl,
+}
+
+impl TranscriptFormat {
+    pub const ALL: [TranscriptFormat; 3] = [
+        TranscriptFormat::Markdown,
+        TranscriptFormat::Html,
+        TranscriptFormat::Jsonl,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TranscriptFormat::Markdown => "markdown",
+            TranscriptFormat::Html => "html",
+            TranscriptFormat::Jsonl => "jsonl",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            TranscriptFormat::Markdown => "Markdown",
+            TranscriptFormat::Html => "HTML",
+            TranscriptFormat::Jsonl => "JSONL",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TranscriptFormat::Markdown => "md",
+            TranscriptFormat::Html => "html",
+            TranscriptFormat::Jsonl => "jsonl",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            TranscriptFormat::Markdown => "text/markdown; charset=utf-8",
+            TranscriptFormat::Html => "text/html; charset=utf-8",
+            TranscriptFormat::Jsonl => "application/x-ndjson",
+        }
+    }
+}
+
+impl FromStr for TranscriptFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Ok(TranscriptFormat::Markdown),
+            "html" => Ok(TranscriptFormat::Html),
+            "jsonl" => Ok(TranscriptFormat::Jsonl),
+            other => Err(format!(
+                "Invalid export format: {other}. Must be one of: markdown, html, jsonl"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TranscriptFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Header information shown at the top of an exported transcript.
+#[derive(Debug, Clone)]
+pub struct TranscriptMeta {
+    pub title: String,
+    pub agent_type: AgentType,
+    pub model: Option<String>,
+    pub workspace: Option<String>,
+    pub branch: Option<String>,
+    pub exported_at: DateTime<Utc>,
+}
+
+impl TranscriptMeta {
+    pub fn new(title: impl Into<String>, agent_type: AgentType) -> Self {
+        Self {
+            title: title.into(),
+            agent_type,
+            model: None,
+            workspace: None,
+            branch: None,
+            exported_at: Utc::now(),
+        }
+    }
+
+    fn header_fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = vec![("Agent", self.agent_type.display_name().to_string())];
+        if let Some(model) = &self.model {
+            fields.push(("Model", model.clone()));
+        }
+        if let Some(workspace) = &self.workspace {
+            fields.push(("Workspace", workspace.clone()));
+        }
+        if let Some(branch) = &self.branch {
+            fields.push(("Branch", branch.clone()));
+        }
+        fields.push((
+            "Exported",
+            self.exported_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+        ));
+        fields
+    }
+}
+
+/// Render messages in the requested format.
+pub fn render_transcript(
+    messages: &[ChatMessage],
+    meta: &TranscriptMeta,
+    format: TranscriptFormat,
+) -> String {
+    let messages: Vec<&ChatMessage> = messages.iter().filter(|m| !is_hidden(m)).collect();
+    match format {
+        TranscriptFormat::Markdown => render_markdown(&messages, meta),
+        TranscriptFormat::Html => render_html(&messages, meta),
+        TranscriptFormat::Jsonl => transcript_events(&messages)
+            .iter()
+            .filter_map(|event| serde_json::to_string(event).ok())
+            .map(|line| line + "\n")
+            .collect(),
+    }
+}
+
+/// Default location for an export: `<data dir>/exports/<workspace>/<timestamp>-<session>.<ext>`.
+pub fn export_path(workspace: &str, session_label: &str, format: TranscriptFormat) -> PathBuf {
+    let timestamp = Local::now().format("%Y%m%d_%H%M%S");
+    util::data_dir()
+        .join("exports")
+        .join(sanitize_file_component(workspace))
+        .join(format!(
+            "{}-{}.{}",
+            timestamp,
+            sanitize_file_component(session_label),
+            format.extension()
+        ))
+}
+
+fn is_hidden(message: &ChatMessage) -> bool {
+    message.role == MessageRole::User && message.content.trim_start().starts_with(FORK_SEED_MARKER)
+}
+
+fn sanitize_file_component(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let trimmed = sanitized.trim_matches('-');
+    if trimmed.is_empty() {
+        "session".to_string()
+    } else {
+        trimmed.chars().take(60).collect()
+    }
+}
+
+fn summary_line(summary: &TurnSummary) -> String {
+    let mut line = format!(
+        "⏱ {} · ⬇{} ⬆{}",
+        summary.format_duration(),
+        TurnSummary::format_tokens(summary.input_tokens),
+        TurnSummary::format_tokens(summary.output_tokens)
+    );
+    match summary.files_changed.len() {
+        0 => {}
+        1 => line.push_str(" · 1 file changed"),
+        n => line.push_str(&format!(" · {} files changed", n)),
+    }
+    line
+}
+
+fn tool_title(message: &ChatMessage) -> String {
+    let name = message.tool_name.as_deref().unwrap_or("Tool");
+    match message.tool_args.as_deref().map(str::trim) {
+        Some(args) if !args.is_empty() => format!("{} {}", name, args.replace('\n', " ")),
+        _ => name.to_string(),
+    }
+}
+
+// ============================================================================
+// Markdown
+// ============================================================================
+
+fn render_markdown(messages: &[&ChatMessage], meta: &TranscriptMeta) -> String {
+    let mut out = format!("# {}\n\n", meta.title);
+    for (label, value) in meta.header_fields() {
+        out.push_str(&format!("- **{}:** {}\n", label, value));
+    }
+
+    for message in messages {
+        out.push('\n');
+        match message.role {
+            MessageRole::User => {
+                out.push_str("## User\n\n");
+                out.push_str(message.content.trim_end());
+                out.push('\n');
+            }
+            MessageRole::Assistant => {
+                out.push_str("## Assistant\n\n");
+                out.push_str(message.content.trim_end());
+                out.push('\n');
+            }
+            MessageRole::Reasoning => {
+                out.push_str("<details>\n<summary>Reasoning</summary>\n\n");
+                out.push_str(message.content.trim_end());
+                out.push_str("\n\n</details>\n");
+            }
+            MessageRole::Tool => {
+                out.push_str(&format!(
+                    "**Tool:** `{}`",
+                    tool_title(message).replace('`', "'")
+                ));
+                if let Some(code) = message.exit_code {
+                    out.push_str(&format!(" (exit {})", code));
+                }
+                out.push('\n');
+                let content = message.content.trim_end();
+                if !content.is_empty() {
+                    let fence = code_fence(content);
+                    out.push_str(&format!("\n{fence}text\n{content}\n{fence}\n"));
+                }
+            }
+            MessageRole::System => {
+                out.push_str(&format!("_{}_\n", message.content.trim()));
+            }
+            MessageRole::Error => {
+                out.push_str(&format!("> **Error:** {}\n", message.content.trim()));
+            }
+            MessageRole::Summary => {
+                if let Some(summary) = &message.summary {
+                    out.push_str(&format!("> {}\n", summary_line(summary)));
+                    for file in &summary.files_changed {
+                        out.push_str(&format!(
+                            "> - `{}` +{} -{}\n",
+                            file.filename, file.additions, file.deletions
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+/// A backtick fence longer than any backtick run inside `content`.
+fn code_fence(content: &str) -> String {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in content.chars() {
+        if c == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    "`".repeat((longest + 1).max(3))
+}
+
+// ============================================================================
+// HTML
+// ============================================================================
+
+const HTML_STYLE: &str = "\
+body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;max-width:860px;margin:2rem auto;padding:0 1rem;color:#1f2328;line-height:1.5}\
+h1{font-size:1.5rem}\
+dl.meta{display:grid;grid-template-columns:max-content 1fr;gap:.25rem 1rem;color:#59636e}\
+dl.meta dt{font-weight:600}dl.meta dd{margin:0}\
+.msg{margin:1rem 0;padding:.75rem 1rem;border-radius:8px}\
+.role{font-size:.75rem;font-weight:600;text-transform:uppercase;color:#59636e;margin-bottom:.25rem}\
+.user{background:#eef4ff}.assistant{background:#f6f8fa}\
+.error{background:#ffebe9;color:#82071e}.system{color:#59636e;font-style:italic}\
+.summary{color:#59636e;font-size:.875rem;border-top:1px solid #d1d9e0;border-radius:0}\
+pre{background:#f6f8fa;padding:.75rem;border-radius:6px;overflow-x:auto;white-space:pre-wrap}\
+.text{white-space:pre-wrap}\
+details summary{cursor:pointer;color:#59636e}";
+
+fn render_html(messages: &[&ChatMessage], meta: &TranscriptMeta) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{}</title>\n", escape_html(&meta.title)));
+    out.push_str(&format!("<style>{}</style>\n", HTML_STYLE));
+    out.push_str("</head>\n<body>\n");
+    out.push_str(&format!(
+        "<h1>{}</h1>\n<dl class=\"meta\">\n",
+        escape_html(&meta.title)
+    ));
+    for (label, value) in meta.header_fields() {
+        out.push_str(&format!(
+            "<dt>{}</dt><dd>{}</dd>\n",
+            label,
+            escape_html(&value)
+        ));
+    }
+    out.push_str("</dl>\n");
+
+    for message in messages {
+        let block = match message.role {
+            MessageRole::User => html_message("user", "User", &message.content),
+            MessageRole::Assistant => html_message("assistant", "Assistant", &message.content),
+            MessageRole::Reasoning => format!(
+                "<details class=\"msg reasoning\"><summary>Reasoning</summary><div class=\"text\">{}</div></details>",
+                escape_html(message.content.trim_end())
+            ),
+            MessageRole::Tool => {
+                let mut title = escape_html(&tool_title(message));
+                if let Some(code) = message.exit_code {
+                    title.push_str(&format!(" (exit {})", code));
+                }
+                format!(
+                    "<details class=\"msg tool\" open><summary>{}</summary><pre>{}</pre></details>",
+                    title,
+                    escape_html(message.content.trim_end())
+                )
+            }
+            MessageRole::System => format!(
+                "<div class=\"msg system\">{}</div>",
+                escape_html(message.content.trim())
+            ),
+            MessageRole::Error => html_message("error", "Error", &message.content),
+            MessageRole::Summary => match &message.summary {
+                Some(summary) => {
+                    let mut block = format!(
+                        "<div class=\"msg summary\">{}",
+                        escape_html(&summary_line(summary))
+                    );
+                    if !summary.files_changed.is_empty() {
+                        block.push_str("<ul>");
+                        for file in &summary.files_changed {
+                            block.push_str(&format!(
+                                "<li><code>{}</code> +{} -{}</li>",
+                                escape_html(&file.filename),
+                                file.additions,
+                                file.deletions
+                            ));
+                        }
+                        block.push_str("</ul>");
+                    }
+                    block.push_str("</div>");
+                    block
+                }
+                None => continue,
+            },
+        };
+        out.push_str(&block);
+        out.push('\n');
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn html_message(class: &str, label: &str, content: &str) -> String {
+    format!(
+        "<div class=\"msg {}\"><div class=\"role\">{}</div><div class=\"text\">{}</div></div>",
+        class,
+        label,
+        escape_html(content.trim_end())
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// ============================================================================
+// JSONL
+// ============================================================================
+
+/// Convert chat messages back into normalized agent events.
+///
+/// User and system messages have no `AgentEvent` counterpart and are emitted
+/// as `Raw` events with `user_message` / `system_message` types.
+fn transcript_events(messages: &[&ChatMessage]) -> Vec<AgentEvent> {
+    let mut events = Vec::new();
+    let mut tool_index = 0usize;
+    for message in messages {
+        match message.role {
+            MessageRole::User => events.push(AgentEvent::Raw {
+                data: serde_json::json!({ "type": "user_message", "text": message.content }),
+            }),
+            MessageRole::System => events.push(AgentEvent::Raw {
+                data: serde_json::json!({ "type": "system_message", "text": message.content }),
+            }),
+            MessageRole::Assistant => {
+                events.push(AgentEvent::AssistantMessage(AssistantMessageEvent {
+                    text: message.content.clone(),
+                    is_final: true,
+                }))
+            }
+            MessageRole::Reasoning => events.push(AgentEvent::AssistantReasoning(ReasoningEvent {
+                text: message.content.clone(),
+            })),
+            MessageRole::Tool => {
+                tool_index += 1;
+                let tool_id = format!("tool-{}", tool_index);
+                let arguments = message
+                    .tool_args
+                    .as_deref()
+                    .map(|args| {
+                        serde_json::from_str(args)
+                            .unwrap_or_else(|_| serde_json::Value::String(args.to_string()))
+                    })
+                    .unwrap_or(serde_json::Value::Null);
+                let success = message.exit_code.is_none_or(|code| code == 0);
+                events.push(AgentEvent::ToolStarted(ToolStartedEvent {
+                    tool_name: message.tool_name.clone().unwrap_or_default(),
+                    tool_id: tool_id.clone(),
+                    arguments,
+                }));
+                events.push(AgentEvent::ToolCompleted(ToolCompletedEvent {
+                    tool_id,
+                    success,
+                    result: success.then(|| message.content.clone()),
+                    error: (!success).then(|| message.content.clone()),
+                }));
+            }
+            MessageRole::Error => events.push(AgentEvent::Error(ErrorEvent {
+                message: message.content.clone(),
+                is_fatal: false,
+                code: None,
+                details: None,
+            })),
+            MessageRole::Summary => {
+                if let Some(summary) = &message.summary {
+                    let input = summary.input_tokens as i64;
+                    let output = summary.output_tokens as i64;
+                    events.push(AgentEvent::TurnCompleted(TurnCompletedEvent {
+                        usage: TokenUsage {
+                            input_tokens: input,
+                            output_tokens: output,
+                            cached_tokens: 0,
+                            total_tokens: input + output,
+                        },
+                    }));
+                }
+            }
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta() -> TranscriptMeta {
+        let mut meta = TranscriptMeta::new("Fix <login> bug", AgentType::Codex);
+        meta.workspace = Some("brave-otter".to_string());
+        meta
+    }
+
+    fn sample() -> Vec<ChatMessage> {
+        let mut summary = TurnSummary::new().with_duration(75).with_tokens(1200, 300);
+        summary.add_file("src/login.rs", 4, 1);
+        vec![
+            ChatMessage::user("[CONDUIT_FORK_SEED] hidden seed"),
+            ChatMessage::user("Fix the login bug"),
+            ChatMessage::reasoning("Look at the handler first"),
+            ChatMessage::tool_with_exit("Bash", "cargo test", "1 failed ``` oops", Some(1)),
+            ChatMessage::assistant("Fixed in `src/login.rs`."),
+            ChatMessage::turn_summary(summary),
+        ]
+    }
+
+    #[test]
+    fn test_markdown_preserves_tools_reasoning_and_summary() {
+        let out = render_transcript(&sample(), &meta(), TranscriptFormat::Markdown);
+        assert!(out.starts_with("# Fix <login> bug\n"));
+        assert!(!out.contains("hidden seed"));
+        assert!(out.contains("## User\n\nFix the login bug\n"));
+        assert!(out.contains("<summary>Reasoning</summary>"));
+        assert!(out.contains("**Tool:** `Bash cargo test` (exit 1)"));
+        assert!(out.contains("\n````text\n1 failed ``` oops\n````\n"));
+        assert!(out.contains("> ⏱ 1m 15s · ⬇1.2k ⬆300 · 1 file changed"));
+        assert!(out.contains("> - `src/login.rs` +4 -1"));
+    }
+
+    #[test]
+    fn test_html_escapes_content() {
+        let out = render_transcript(&sample(), &meta(), TranscriptFormat::Html);
+        assert!(out.starts_with("<!DOCTYPE html>"));
+        assert!(out.contains("<title>Fix &lt;login&gt; bug</title>"));
+        assert!(out.contains("Bash cargo test (exit 1)"));
+        assert!(!out.contains("hidden seed"));
+    }
+
+    #[test]
+    fn test_jsonl_emits_normalized_events() {
+        let out = render_transcript(&sample(), &meta(), TranscriptFormat::Jsonl);
+        let events: Vec<AgentEvent> = out
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("valid event"))
+            .collect();
+        let names: Vec<&str> = events.iter().map(|e| e.event_type_name()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Raw",
+                "AssistantReasoning",
+                "ToolStarted",
+                "ToolCompleted",
+                "AssistantMessage",
+                "TurnCompleted"
+            ]
+        );
+        match &events[3] {
+            AgentEvent::ToolCompleted(done) => {
+                assert!(!done.success);
+                assert_eq!(done.error.as_deref(), Some("1 failed ``` oops"));
+            }
+            other => panic!("Expected ToolCompleted, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_format_parse_and_paths() {
+        assert_eq!(
+            "md".parse::<TranscriptFormat>(),
+            Ok(TranscriptFormat::Markdown)
+        );
+        assert_eq!(
+            "HTML".parse::<TranscriptFormat>(),
+            Ok(TranscriptFormat::Html)
+        );
+        assert!("pdf".parse::<TranscriptFormat>().is_err());
+        assert_eq!(
+            sanitize_file_component("feat/login page"),
+            "feat-login-page"
+        );
+        assert_eq!(sanitize_file_component("///"), "session");
+    }
+}
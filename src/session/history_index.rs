@@ -0,0 +1,289 @@
+//! Cross-session history index.
+//!
+//! Every session Conduit knows about (open and closed tabs plus sessions
+//! discovered on disk) is loaded with the `agent::history` loaders and its
+//! messages are stored in the SQLite full-text index. Sessions whose
+//! transcript file is unchanged since the last run are skipped.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use tracing::debug;
+
+use crate::agent::{
+    load_claude_history_with_debug, load_codex_history_with_debug,
+    load_opencode_history_with_debug, AgentType, HistoryError,
+};
+use crate::data::{HistoryIndexStore, HistorySource, SessionTab};
+use crate::session::{discover_all_sessions, ExternalSession};
+use crate::ui::components::{ChatMessage, MessageRole};
+
+/// Characters of one message kept in the index
+const MAX_INDEXED_CHARS: usize = 8_000;
+
+/// Outcome of one index refresh
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HistoryIndexStats {
+    /// Sources (re)indexed in this run
+    pub indexed: usize,
+    /// Sources dropped because their transcript is gone
+    pub removed: usize,
+}
+
+/// Bring the index up to date with the transcripts on disk
+pub fn refresh_history_index(
+    store: &HistoryIndexStore,
+    tabs: &[SessionTab],
+) -> rusqlite::Result<HistoryIndexStats> {
+    let indexed: HashMap<String, HistorySource> = store
+        .get_sources()?
+        .into_iter()
+        .map(|source| (source.key.clone(), source))
+        .collect();
+    let sources = collect_sources(discover_all_sessions(), tabs, &indexed);
+
+    let mut stats = HistoryIndexStats::default();
+    let mut live = HashSet::with_capacity(sources.len());
+    for mut source in sources {
+        let fingerprint = file_fingerprint(&source.file_path);
+        if let (Some(fingerprint), Some(previous)) = (&fingerprint, indexed.get(&source.key)) {
+            if *fingerprint == previous.fingerprint {
+                if source.title != previous.title
+                    || source.session_tab_id != previous.session_tab_id
+                    || source.workspace_id != previous.workspace_id
+                {
+                    source.fingerprint = fingerprint.clone();
+                    store.update_source(&source)?;
+                }
+                live.insert(source.key);
+                continue;
+            }
+        }
+
+        let (messages, loaded_from) =
+            match load_messages(source.agent_type, &source.agent_session_id) {
+                Ok(loaded) => loaded,
+                Err(err) => {
+                    debug!(key = %source.key, error = %err, "Skipping unreadable session history");
+                    continue;
+                }
+            };
+        if source.file_path.as_os_str().is_empty() {
+            source.file_path = loaded_from;
+        }
+        source.fingerprint = file_fingerprint(&source.file_path).unwrap_or_default();
+        store.replace(&source, &index_entries(&messages))?;
+        stats.indexed += 1;
+        live.insert(source.key);
+    }
+
+    for key in indexed.keys() {
+        if !live.contains(key) {
+            store.remove(key)?;
+            stats.removed += 1;
+        }
+    }
+    Ok(stats)
+}
+
+/// Merge discovered sessions with session tabs. Tabs name the session and
+/// link it back to Conduit; an open tab wins over closed ones.
+fn collect_sources(
+    discovered: Vec<ExternalSession>,
+    tabs: &[SessionTab],
+    indexed: &HashMap<String, HistorySource>,
+) -> Vec<HistorySource> {
+    let mut sources: HashMap<String, HistorySource> = HashMap::new();
+    for session in discovered {
+        let key = HistorySource::key_for(session.agent_type, &session.id);
+        sources.insert(
+            key.clone(),
+            HistorySource {
+                key,
+                agent_type: session.agent_type,
+                agent_session_id: session.id,
+                session_tab_id: None,
+                workspace_id: None,
+                title: Some(session.display).filter(|display| !display.trim().is_empty()),
+                project: session.project,
+                file_path: session.file_path,
+                fingerprint: String::new(),
+                updated_at: session.timestamp,
+            },
+        );
+    }
+
+    let closed_then_open = tabs
+        .iter()
+        .filter(|tab| !tab.is_open)
+        .chain(tabs.iter().filter(|tab| tab.is_open));
+    for tab in closed_then_open {
+        let Some(agent_session_id) = tab.agent_session_id.as_deref() else {
+            continue;
+        };
+        if matches!(tab.agent_type, AgentType::Gemini | AgentType::Acp) {
+            continue;
+        }
+        let key = HistorySource::key_for(tab.agent_type, agent_session_id);
+        let source = sources.entry(key.clone()).or_insert_with(|| HistorySource {
+            key: key.clone(),
+            agent_type: tab.agent_type,
+            agent_session_id: agent_session_id.to_string(),
+            session_tab_id: None,
+            workspace_id: None,
+            title: None,
+            project: indexed.get(&key).and_then(|source| source.project.clone()),
+            // Not discovered on disk; the loader finds the file
+            file_path: indexed
+                .get(&key)
+                .map(|source| source.file_path.clone())
+                .unwrap_or_default(),
+            fingerprint: String::new(),
+            updated_at: tab.created_at,
+        });
+        source.session_tab_id = Some(tab.id);
+        source.workspace_id = tab.workspace_id;
+        if let Some(title) = tab.title.as_ref().filter(|title| !title.trim().is_empty()) {
+            source.title = Some(title.clone());
+        }
+    }
+
+    sources.into_values().collect()
+}
+
+fn load_messages(
+    agent_type: AgentType,
+    agent_session_id: &str,
+) -> Result<(Vec<ChatMessage>, PathBuf), HistoryError> {
+    let (messages, _debug_entries, file_path) = match agent_type {
+        AgentType::Claude => load_claude_history_with_debug(agent_session_id)?,
+        AgentType::Codex => load_codex_history_with_debug(agent_session_id)?,
+        AgentType::Opencode => load_opencode_history_with_debug(agent_session_id)?,
+        AgentType::Gemini | AgentType::Acp => {
+            return Err(HistoryError::SessionNotFound(agent_session_id.to_string()))
+        }
+    };
+    Ok((messages, file_path))
+}
+
+/// (role, text) per message, in transcript order so positions match the
+/// chat view. Messages that aren't worth searching get empty text.
+fn index_entries(messages: &[ChatMessage]) -> Vec<(String, String)> {
+    messages
+        .iter()
+        .map(|msg| {
+            let role = match msg.role {
+                MessageRole::User => "user",
+                MessageRole::Assistant => "assistant",
+                MessageRole::Reasoning => "reasoning",
+                MessageRole::Tool => "tool",
+                MessageRole::System | MessageRole::Error | MessageRole::Summary => {
+                    return (String::new(), String::new());
+                }
+            };
+            let mut text = String::new();
+            if msg.role == MessageRole::Tool {
+                for part in [msg.tool_name.as_deref(), msg.tool_args.as_deref()]
+                    .into_iter()
+                    .flatten()
+                {
+                    text.push_str(part);
+                    text.push('\n');
+                }
+            }
+            text.push_str(&msg.content);
+            let text = match text.char_indices().nth(MAX_INDEXED_CHARS) {
+                Some((end, _)) => text[..end].to_string(),
+                None => text,
+            };
+            (role.to_string(), text)
+        })
+        .collect()
+}
+
+/// Modification time and size; changes whenever a transcript grows
+fn file_fingerprint(path: &Path) -> Option<String> {
+    if path.as_os_str().is_empty() {
+        return None;
+    }
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default();
+    Some(format!("{}:{}", modified, metadata.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn tab(agent_session_id: &str, is_open: bool, title: &str) -> SessionTab {
+        let mut tab = SessionTab::new(
+            0,
+            AgentType::Claude,
+            None,
+            Some(agent_session_id.to_string()),
+            None,
+            None,
+        );
+        tab.is_open = is_open;
+        tab.title = Some(title.to_string());
+        tab
+    }
+
+    #[test]
+    fn test_collect_sources_prefers_tab_details() {
+        let discovered = vec![ExternalSession {
+            id: "abc".to_string(),
+            agent_type: AgentType::Claude,
+            display: "fix the oauth redirect".to_string(),
+            project: Some("/src/app".to_string()),
+            timestamp: Utc::now(),
+            message_count: 4,
+            file_path: PathBuf::from("/tmp/abc.jsonl"),
+        }];
+        let closed = tab("abc", false, "Old title");
+        let open = tab("abc", true, "OAuth redirect fix");
+        let tab_only = tab("def", false, "Tab only");
+        let tabs = vec![open.clone(), closed, tab_only.clone()];
+
+        let mut sources = collect_sources(discovered, &tabs, &HashMap::new());
+        sources.sort_by(|a, b| a.key.cmp(&b.key));
+
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0].key, "claude:abc");
+        assert_eq!(sources[0].title.as_deref(), Some("OAuth redirect fix"));
+        assert_eq!(sources[0].session_tab_id, Some(open.id));
+        assert_eq!(sources[0].file_path, PathBuf::from("/tmp/abc.jsonl"));
+        assert_eq!(sources[1].key, "claude:def");
+        assert_eq!(sources[1].session_tab_id, Some(tab_only.id));
+        assert!(sources[1].file_path.as_os_str().is_empty());
+    }
+
+    #[test]
+    fn test_index_entries_keep_positions() {
+        let tool = ChatMessage::tool("Bash", "cargo test", "test result: ok");
+        let long = "x".repeat(MAX_INDEXED_CHARS + 10);
+        let messages = vec![
+            ChatMessage::user("where is the redirect?"),
+            ChatMessage::system("Session resumed"),
+            tool,
+            ChatMessage::assistant(long),
+        ];
+
+        let entries = index_entries(&messages);
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0].0, "user");
+        assert!(entries[1].1.is_empty());
+        assert_eq!(entries[2].0, "tool");
+        assert!(entries[2].1.contains("cargo test"));
+        assert!(entries[2].1.contains("test result: ok"));
+        assert_eq!(entries[3].1.chars().count(), MAX_INDEXED_CHARS);
+    }
+}
@@ -0,0 +1,529 @@
+//! `@` mentions of workspace files and symbols.
+//!
+//! Candidates are the files `git ls-files` reports plus, when the workspace
+//! has a ctags `tags` file, the symbols listed in it. A picked candidate is
+//! written into the prompt as a token (`@src/main.rs`, `@src/lib.rs:42`) and
+//! expanded on submit: to the bare path for agents that read files
+//! themselves, or to the path plus the file's content for agents that can't.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Serialize;
+
+/// ctags output file looked up in the working directory
+const TAGS_FILE: &str = "tags";
+/// Files listed per workspace
+const MAX_FILES: usize = 50_000;
+/// Symbols read from the tags file
+const MAX_SYMBOLS: usize = 50_000;
+/// Bytes of one file inlined into a prompt
+const MAX_INLINE_FILE_BYTES: usize = 64 * 1024;
+/// Bytes of file content inlined into one prompt
+const MAX_INLINE_TOTAL_BYTES: usize = 256 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MentionKind {
+    File,
+    Symbol,
+}
+
+/// Something that can be mentioned with `@`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MentionCandidate {
+    pub kind: MentionKind,
+    /// Path relative to the working directory
+    pub path: String,
+    /// Symbol name, for symbols
+    pub symbol: Option<String>,
+    /// 1-based line of the symbol's definition
+    pub line: Option<usize>,
+}
+
+impl MentionCandidate {
+    pub fn file(path: impl Into<String>) -> Self {
+        Self {
+            kind: MentionKind::File,
+            path: path.into(),
+            symbol: None,
+            line: None,
+        }
+    }
+
+    pub fn symbol(name: impl Into<String>, path: impl Into<String>, line: Option<usize>) -> Self {
+        Self {
+            kind: MentionKind::Symbol,
+            path: path.into(),
+            symbol: Some(name.into()),
+            line,
+        }
+    }
+
+    /// Text the picker matches the query against
+    pub fn match_text(&self) -> &str {
+        self.symbol.as_deref().unwrap_or(&self.path)
+    }
+
+    /// Token inserted into the prompt
+    pub fn token(&self) -> String {
+        let target = match self.line {
+            Some(line) => format!("{}:{}", self.path, line),
+            None => self.path.clone(),
+        };
+        if target.contains(char::is_whitespace) {
+            format!("@\"{}\"", target)
+        } else {
+            format!("@{}", target)
+        }
+    }
+}
+
+/// How mention tokens are expanded when a prompt is submitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MentionExpansion {
+    /// Replace the token with the path; the agent opens the file itself
+    Path,
+    /// Also append the file's content, for agents that can't read files
+    Inline,
+}
+
+impl MentionExpansion {
+    pub fn for_agent(reads_files: bool) -> Self {
+        if reads_files {
+            MentionExpansion::Path
+        } else {
+            MentionExpansion::Inline
+        }
+    }
+}
+
+/// Files tracked in `working_dir`'s repository, then symbols from its tags
+/// file if there is one
+pub fn list_mention_candidates(working_dir: &Path) -> Result<Vec<MentionCandidate>, String> {
+    let output = Command::new("git")
+        .args(["--no-optional-locks", "ls-files", "-z"])
+        .current_dir(working_dir)
+        .output()
+        .map_err(|e| format!("Failed to run git ls-files: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git ls-files failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let mut candidates: Vec<MentionCandidate> = output
+        .stdout
+        .split(|byte| *byte == 0)
+        .filter(|path| !path.is_empty())
+        .take(MAX_FILES)
+        .map(|path| MentionCandidate::file(String::from_utf8_lossy(path)))
+        .collect();
+    if let Ok(tags) = std::fs::read_to_string(working_dir.join(TAGS_FILE)) {
+        candidates.extend(parse_tags(&tags));
+    }
+    Ok(candidates)
+}
+
+/// Symbols from a ctags file (`name<TAB>file<TAB>address;"<TAB>fields`)
+fn parse_tags(contents: &str) -> Vec<MentionCandidate> {
+    contents
+        .lines()
+        .filter(|line| !line.starts_with("!_TAG_"))
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.next().filter(|name| !name.is_empty())?;
+            let path = fields.next().filter(|path| !path.is_empty())?;
+            let rest: Vec<&str> = fields.collect();
+            let line_number = rest
+                .iter()
+                .find_map(|field| field.strip_prefix("line:"))
+                .or_else(|| rest.first().map(|address| address.trim_end_matches(";\"")))
+                .and_then(|number| number.parse().ok());
+            Some(MentionCandidate::symbol(name, path, line_number))
+        })
+        .take(MAX_SYMBOLS)
+        .collect()
+}
+
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Fuzzy score of `query` against `candidate`, `None` when the query's
+/// characters don't all appear in order. Matches at word starts, runs of
+/// consecutive matches and matches in the file name score higher.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let chars: Vec<char> = candidate.chars().map(fold).collect();
+    let query: Vec<char> = query
+        .chars()
+        .map(fold)
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let name_start = candidate
+        .rfind('/')
+        .map_or(0, |slash| candidate[..=slash].chars().count());
+
+    // Greedy match from `from`; trying the file name first keeps
+    // `input` from matching the `i` of a `ui/` directory
+    let score_from = |from: usize| -> Option<i64> {
+        let mut score: i64 = 0;
+        let mut next = from;
+        let mut previous: Option<usize> = None;
+        for &q in &query {
+            let index = (next..chars.len()).find(|&i| chars[i] == q)?;
+            score += 1;
+            if index == 0 || matches!(chars[index - 1], '/' | '_' | '-' | '.' | ' ') {
+                score += 8;
+            }
+            if previous.is_some_and(|previous| previous + 1 == index) {
+                score += 5;
+            }
+            if index >= name_start {
+                score += 2;
+            }
+            previous = Some(index);
+            next = index + 1;
+        }
+        Some(score)
+    };
+    let mut score = match (score_from(name_start), score_from(0)) {
+        (Some(name), Some(full)) => name.max(full),
+        (name, full) => name.or(full)?,
+    };
+
+    if !query.is_empty() {
+        let needle: String = query.iter().collect();
+        let name: String = chars[name_start..].iter().collect();
+        if name.contains(&needle) {
+            score += 25;
+        }
+    }
+    Some(score - chars.len() as i64 / 8)
+}
+
+/// Indices of the best matches for `query`, best first
+pub fn rank_mentions(candidates: &[MentionCandidate], query: &str, limit: usize) -> Vec<usize> {
+    let mut scored: Vec<(i64, usize)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(index, candidate)| {
+            fuzzy_score(query, candidate.match_text()).map(|score| (score, index))
+        })
+        .collect();
+    scored.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| {
+                let a_len = candidates[a.1].match_text().len();
+                a_len.cmp(&candidates[b.1].match_text().len())
+            })
+            .then(a.1.cmp(&b.1))
+    });
+    scored.truncate(limit);
+    scored.into_iter().map(|(_, index)| index).collect()
+}
+
+/// An `@` token in prompt text
+struct MentionSpan<'a> {
+    /// Byte range including the `@` (and quotes)
+    start: usize,
+    end: usize,
+    raw: &'a str,
+    quoted: bool,
+}
+
+/// `@` tokens that start the text or follow whitespace, so addresses like
+/// `me@example.com` are left alone
+fn find_mentions(text: &str) -> Vec<MentionSpan<'_>> {
+    let mut spans = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, ch)) = chars.next() {
+        let at_boundary = !matches!(previous, Some(c) if !c.is_whitespace());
+        previous = Some(ch);
+        if ch != '@' || !at_boundary {
+            continue;
+        }
+
+        let rest = &text[start + 1..];
+        let (raw, len, quoted) = if let Some(quoted) = rest.strip_prefix('"') {
+            match quoted.find('"') {
+                Some(end) => (&quoted[..end], end + 2, true),
+                None => continue,
+            }
+        } else {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            (&rest[..end], end, false)
+        };
+        if raw.is_empty() {
+            continue;
+        }
+
+        let end = start + 1 + len;
+        spans.push(MentionSpan {
+            start,
+            end,
+            raw,
+            quoted,
+        });
+        while let Some((_, ch)) = chars.next_if(|(index, _)| *index < end) {
+            previous = Some(ch);
+        }
+    }
+    spans
+}
+
+/// A mention that names a file inside the working directory
+struct ResolvedMention {
+    /// Path as written, with any `:line` suffix
+    target: String,
+    file: PathBuf,
+    /// Trailing punctuation that isn't part of the path
+    trailing: String,
+}
+
+fn resolve_mention(root: &Path, span: &MentionSpan<'_>) -> Option<ResolvedMention> {
+    let trimmed = span
+        .raw
+        .trim_end_matches(['.', ',', ';', ':', '!', '?', ')', '\'', '"']);
+    let variants: &[&str] = if span.quoted || trimmed == span.raw {
+        &[span.raw]
+    } else {
+        &[span.raw, trimmed]
+    };
+
+    variants.iter().find_map(|&variant| {
+        let path = match variant.rsplit_once(':') {
+            Some((path, line))
+                if !line.is_empty() && line.chars().all(|c| c.is_ascii_digit() || c == '-') =>
+            {
+                path
+            }
+            _ => variant,
+        };
+        let file = root.join(path).canonicalize().ok()?;
+        if !file.is_file() || !file.starts_with(root) {
+            return None;
+        }
+        Some(ResolvedMention {
+            target: variant.to_string(),
+            file,
+            trailing: span.raw[variant.len()..].to_string(),
+        })
+    })
+}
+
+/// Path, relative to `working_dir`, of the last `@` mention in `text` that
+/// names a file inside it
+pub fn last_file_mention(text: &str, working_dir: &Path) -> Option<String> {
+    let root = working_dir.canonicalize().ok()?;
+    find_mentions(text).iter().rev().find_map(|span| {
+        let mention = resolve_mention(&root, span)?;
+        let relative = mention.file.strip_prefix(&root).ok()?;
+        Some(relative.to_string_lossy().into_owned())
+    })
+}
+
+/// Expand the `@` mentions in a prompt. Tokens that don't name a file inside
+/// `working_dir` (e.g. `@someone`) are left as typed.
+pub fn expand_mentions(text: &str, working_dir: &Path, expansion: MentionExpansion) -> String {
+    let spans = find_mentions(text);
+    if spans.is_empty() {
+        return text.to_string();
+    }
+    let Ok(root) = working_dir.canonicalize() else {
+        return text.to_string();
+    };
+
+    let mut expanded = String::with_capacity(text.len());
+    let mut inlined: Vec<(String, PathBuf)> = Vec::new();
+    let mut cursor = 0;
+    for span in &spans {
+        let Some(mention) = resolve_mention(&root, span) else {
+            continue;
+        };
+        expanded.push_str(&text[cursor..span.start]);
+        if span.quoted {
+            expanded.push('"');
+            expanded.push_str(&mention.target);
+            expanded.push('"');
+        } else {
+            expanded.push_str(&mention.target);
+        }
+        expanded.push_str(&mention.trailing);
+        cursor = span.end;
+
+        let display = match mention.target.rsplit_once(':') {
+            Some((path, _)) if mention.file.ends_with(path) => path.to_string(),
+            _ => mention.target.clone(),
+        };
+        if !inlined.iter().any(|(_, file)| *file == mention.file) {
+            inlined.push((display, mention.file));
+        }
+    }
+    expanded.push_str(&text[cursor..]);
+
+    if expansion == MentionExpansion::Inline {
+        let mut budget = MAX_INLINE_TOTAL_BYTES;
+        for (display, file) in inlined {
+            let Some(block) = inline_file_block(&display, &file, &mut budget) else {
+                continue;
+            };
+            expanded.push_str("\n\n");
+            expanded.push_str(&block);
+        }
+    }
+    expanded
+}
+
+/// The file wrapped in `<file>` tags, capped per file and by the remaining
+/// prompt budget. Binary files are skipped.
+fn inline_file_block(display: &str, file: &Path, budget: &mut usize) -> Option<String> {
+    let cap = MAX_INLINE_FILE_BYTES.min(*budget);
+    if cap == 0 {
+        return None;
+    }
+    let size = file.metadata().ok()?.len();
+    let mut bytes = Vec::new();
+    File::open(file)
+        .ok()?
+        .take(cap as u64)
+        .read_to_end(&mut bytes)
+        .ok()?;
+    if bytes.contains(&0) {
+        return None;
+    }
+
+    let mut content = String::from_utf8_lossy(&bytes).into_owned();
+    // A cut may split a character; drop the replacement it turns into
+    if (bytes.len() as u64) < size && content.ends_with('\u{FFFD}') {
+        content.pop();
+    }
+    *budget -= bytes.len();
+
+    let mut block = format!("<file path=\"{}\">\n{}", display, content);
+    if !block.ends_with('\n') {
+        block.push('\n');
+    }
+    if (bytes.len() as u64) < size {
+        block.push_str(&format!(
+            "[truncated: first {} of {} bytes]\n",
+            bytes.len(),
+            size
+        ));
+    }
+    block.push_str("</file>");
+    Some(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_rank_mentions_prefers_file_name_matches() {
+        let candidates = vec![
+            MentionCandidate::file("src/ui/components/input_box.rs"),
+            MentionCandidate::file("src/ui/app/app_input.rs"),
+            MentionCandidate::file("docs/src/input-box-notes.md"),
+            MentionCandidate::file("src/main.rs"),
+            MentionCandidate::symbol("InputBox", "src/ui/components/input_box.rs", Some(41)),
+        ];
+
+        let ranked = rank_mentions(&candidates, "inpbox", 10);
+        assert!(!ranked.contains(&3));
+        assert_eq!(candidates[ranked[0]].match_text(), "InputBox");
+
+        let ranked = rank_mentions(&candidates, "app_input", 10);
+        assert_eq!(ranked[0], 1);
+        assert_eq!(rank_mentions(&candidates, "", 2).len(), 2);
+    }
+
+    #[test]
+    fn test_parse_tags_reads_symbols() {
+        let tags = "!_TAG_FILE_FORMAT\t2\t/extended format/\n\
+                    InputBox\tsrc/ui/components/input_box.rs\t/^pub struct InputBox {$/;\"\ts\tline:41\n\
+                    main\tsrc/main.rs\t12;\"\tf\n";
+
+        let symbols = parse_tags(tags);
+        assert_eq!(
+            symbols,
+            vec![
+                MentionCandidate::symbol("InputBox", "src/ui/components/input_box.rs", Some(41)),
+                MentionCandidate::symbol("main", "src/main.rs", Some(12)),
+            ]
+        );
+        assert_eq!(symbols[1].token(), "@src/main.rs:12");
+        assert_eq!(
+            MentionCandidate::file("docs/release notes.md").token(),
+            "@\"docs/release notes.md\""
+        );
+    }
+
+    #[test]
+    fn test_expand_mentions_to_paths() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(dir.path().join("notes one.md"), "notes\n").unwrap();
+
+        let expanded = expand_mentions(
+            "Fix @src/main.rs:1, see @\"notes one.md\". Thanks @alice, mail me@src/main.rs",
+            dir.path(),
+            MentionExpansion::Path,
+        );
+        assert_eq!(
+            expanded,
+            "Fix src/main.rs:1, see \"notes one.md\". Thanks @alice, mail me@src/main.rs"
+        );
+    }
+
+    #[test]
+    fn test_last_file_mention_skips_non_files() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "\n").unwrap();
+
+        assert_eq!(
+            last_file_mention(
+                "Compare @src/lib.rs and @src/main.rs:3, ask @alice",
+                dir.path()
+            ),
+            Some("src/main.rs".to_string())
+        );
+        assert_eq!(last_file_mention("Thanks @alice", dir.path()), None);
+    }
+
+    #[test]
+    fn test_expand_mentions_inlines_content_within_workspace() {
+        let dir = tempdir().unwrap();
+        let workspace = dir.path().join("workspace");
+        std::fs::create_dir(&workspace).unwrap();
+        std::fs::write(workspace.join("small.txt"), "hello").unwrap();
+        std::fs::write(
+            workspace.join("big.txt"),
+            "x".repeat(MAX_INLINE_FILE_BYTES + 10),
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("secret.txt"), "secret").unwrap();
+
+        let expanded = expand_mentions(
+            "Compare @small.txt with @big.txt and @../secret.txt",
+            &workspace,
+            MentionExpansion::Inline,
+        );
+        assert!(expanded.starts_with("Compare small.txt with big.txt and @../secret.txt\n\n"));
+        assert!(expanded.contains("<file path=\"small.txt\">\nhello\n</file>"));
+        assert!(expanded.contains(&format!(
+            "[truncated: first {} of {} bytes]",
+            MAX_INLINE_FILE_BYTES,
+            MAX_INLINE_FILE_BYTES + 10
+        )));
+        assert!(!expanded.contains("secret\n"));
+    }
+}
@@ -1,14 +1,27 @@
 //! Session management module
 //!
 //! This module provides utilities for discovering and importing
-//! sessions from external agents (Claude Code, Codex CLI, Gemini CLI, and OpenCode).
+//! sessions from external agents (Claude Code, Codex CLI, Gemini CLI, and OpenCode),
+//! for exporting session transcripts, and for `@` mentions and reusable
+//! templates in prompts.
 
 pub mod cache;
+pub mod export;
+pub mod history_index;
 pub mod import;
+pub mod mentions;
+pub mod prompts;
 
 pub use cache::{get_file_mtime, SessionCache};
+pub use export::{export_path, render_transcript, TranscriptFormat, TranscriptMeta};
+pub use history_index::{refresh_history_index, HistoryIndexStats};
 pub use import::{
     discover_all_sessions, discover_claude_sessions, discover_codex_sessions,
     discover_opencode_sessions, discover_sessions_incremental, ExternalSession,
     SessionDiscoveryUpdate,
 };
+pub use mentions::{
+    expand_mentions, last_file_mention, list_mention_candidates, rank_mentions, MentionCandidate,
+    MentionExpansion, MentionKind,
+};
+pub use prompts::{load_prompt_templates, session_prompt_values, PromptTemplate};
@@ -0,0 +1,336 @@
+//! Reusable prompt templates
+//!
+//! Templates live in `~/.conduit/prompts/` and in a repository's
+//! `.conduit/prompts/`, where a team can commit them; a repository template
+//! replaces a personal one with the same name. A `.toml` template sets `name`,
+//! `description` and `prompt`. A `.md` template is the prompt itself, optionally
+//! preceded by `name:` and `description:` lines between `---` fences. Either
+//! way the name defaults to the file stem.
+//!
+//! `{{variable}}` placeholders are filled in when a template is used:
+//! `branch`, `diff`, `selection` and `file` come from the session, anything
+//! else is asked for.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::git::{PrManager, WorkspaceDiff};
+
+/// Template directory looked up in a repository
+pub const PROMPTS_DIR: &str = ".conduit/prompts";
+
+/// Current branch of the session's workspace
+pub const BRANCH_VARIABLE: &str = "branch";
+
+/// Workspace changes against its base branch
+pub const DIFF_VARIABLE: &str = "diff";
+
+/// Text selected in the chat view
+pub const SELECTION_VARIABLE: &str = "selection";
+
+/// File selected in the workspace's diff tab, or else the last one mentioned
+/// with `@`
+pub const FILE_VARIABLE: &str = "file";
+
+/// Largest diff substituted for `{{diff}}`
+const MAX_DIFF_BYTES: usize = 128 * 1024;
+
+/// On-disk shape of a `.toml` template
+#[derive(Debug, Deserialize)]
+struct TomlPromptTemplate {
+    name: Option<String>,
+    #[serde(default)]
+    description: String,
+    prompt: String,
+}
+
+/// A named prompt with `{{variable}}` placeholders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptTemplate {
+    pub name: String,
+    pub description: String,
+    pub body: String,
+    /// File the template was read from
+    pub path: PathBuf,
+}
+
+impl PromptTemplate {
+    /// Read a `.toml` or `.md` template
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let (name, description, body) = if has_extension(path, "toml") {
+            let template: TomlPromptTemplate = toml::from_str(&contents)
+                .map_err(|err| format!("Failed to parse {}: {}", path.display(), err))?;
+            (template.name, template.description, template.prompt)
+        } else {
+            parse_markdown(&contents)
+        };
+
+        let name = name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or(stem);
+        if name.is_empty() {
+            return Err(format!("{} has no name", path.display()));
+        }
+        let body = body.trim().to_string();
+        if body.is_empty() {
+            return Err(format!("{} has an empty prompt", path.display()));
+        }
+        Ok(Self {
+            name,
+            description: description.trim().to_string(),
+            body,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Placeholder names, in order of first appearance
+    pub fn variables(&self) -> Vec<String> {
+        let mut variables: Vec<String> = Vec::new();
+        for (_, name) in placeholders(&self.body) {
+            if !variables.iter().any(|existing| existing == name) {
+                variables.push(name.to_string());
+            }
+        }
+        variables
+    }
+
+    /// Substitute placeholders; ones without a value are left as written
+    pub fn render(&self, values: &HashMap<String, String>) -> String {
+        let mut rendered = String::with_capacity(self.body.len());
+        let mut last = 0;
+        for (range, name) in placeholders(&self.body) {
+            if let Some(value) = values.get(name) {
+                rendered.push_str(&self.body[last..range.start]);
+                rendered.push_str(value);
+                last = range.end;
+            }
+        }
+        rendered.push_str(&self.body[last..]);
+        rendered
+    }
+}
+
+/// Personal templates, then the repository's, sorted by name
+pub fn load_prompt_templates(working_dir: Option<&Path>) -> Vec<PromptTemplate> {
+    let mut dirs = vec![crate::util::data_dir().join("prompts")];
+    if let Some(working_dir) = working_dir {
+        dirs.push(working_dir.join(PROMPTS_DIR));
+    }
+
+    let mut templates: BTreeMap<String, PromptTemplate> = BTreeMap::new();
+    for dir in dirs {
+        for template in load_dir(&dir) {
+            templates.insert(template.name.clone(), template);
+        }
+    }
+    templates.into_values().collect()
+}
+
+/// Values for the session placeholders `template` uses; ones that can't be
+/// worked out are left for the user
+pub fn session_prompt_values(
+    template: &PromptTemplate,
+    working_dir: &Path,
+) -> HashMap<String, String> {
+    let variables = template.variables();
+    let mut values = HashMap::new();
+    if variables.iter().any(|name| name == BRANCH_VARIABLE) {
+        if let Some(branch) = PrManager::get_current_branch(working_dir) {
+            values.insert(BRANCH_VARIABLE.to_string(), branch);
+        }
+    }
+    if variables.iter().any(|name| name == DIFF_VARIABLE) {
+        match WorkspaceDiff::load(working_dir) {
+            Ok(diff) if !diff.is_empty() => {
+                values.insert(DIFF_VARIABLE.to_string(), truncate_diff(diff.patch()));
+            }
+            Ok(_) => {}
+            Err(err) => {
+                tracing::debug!(
+                    error = %err,
+                    working_dir = %working_dir.display(),
+                    "Failed to load diff for prompt template"
+                );
+            }
+        }
+    }
+    values
+}
+
+fn load_dir(dir: &Path) -> Vec<PromptTemplate> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            if err.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!(
+                    dir = %dir.display(),
+                    error = %err,
+                    "Failed to read prompt templates"
+                );
+            }
+            return Vec::new();
+        }
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && (has_extension(path, "toml") || has_extension(path, "md")))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .filter_map(|path| match PromptTemplate::load(path) {
+            Ok(template) => Some(template),
+            Err(err) => {
+                tracing::warn!("Ignoring prompt template: {}", err);
+                None
+            }
+        })
+        .collect()
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+/// Split optional `---` front matter from a Markdown template
+fn parse_markdown(contents: &str) -> (Option<String>, String, String) {
+    let mut name = None;
+    let mut description = String::new();
+    let Some(rest) = contents
+        .strip_prefix("---\n")
+        .or_else(|| contents.strip_prefix("---\r\n"))
+    else {
+        return (name, description, contents.to_string());
+    };
+    let Some(end) = rest.find("\n---") else {
+        return (name, description, contents.to_string());
+    };
+
+    for line in rest[..end].lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"').to_string();
+        match key.trim() {
+            "name" => name = Some(value),
+            "description" => description = value,
+            _ => {}
+        }
+    }
+    let body = rest[end + "\n---".len()..]
+        .split_once('\n')
+        .map_or("", |(_, body)| body);
+    (name, description, body.to_string())
+}
+
+/// `{{ name }}` placeholders with their byte ranges
+fn placeholders(text: &str) -> Vec<(std::ops::Range<usize>, &str)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = text[offset..].find("{{").map(|pos| offset + pos) {
+        let Some(len) = text[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + len + 2;
+        let name = text[start + 2..end - 2].trim();
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+        if valid {
+            found.push((start..end, name));
+            offset = end;
+        } else {
+            offset = start + 2;
+        }
+    }
+    found
+}
+
+fn truncate_diff(diff: String) -> String {
+    if diff.len() <= MAX_DIFF_BYTES {
+        return diff;
+    }
+    let mut cut = MAX_DIFF_BYTES;
+    while !diff.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    format!(
+        "{}\n[diff truncated: first {} of {} bytes]",
+        &diff[..cut],
+        cut,
+        diff.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(body: &str) -> PromptTemplate {
+        PromptTemplate {
+            name: "test".to_string(),
+            description: String::new(),
+            body: body.to_string(),
+            path: PathBuf::from("test.md"),
+        }
+    }
+
+    #[test]
+    fn test_variables_and_render() {
+        let template =
+            template("Review {{ diff }} on {{branch}} for {{file}}, then {{diff}}. {{not valid}}");
+        assert_eq!(template.variables(), vec!["diff", "branch", "file"]);
+
+        let values = HashMap::from([
+            ("diff".to_string(), "D".to_string()),
+            ("branch".to_string(), "main".to_string()),
+        ]);
+        assert_eq!(
+            template.render(&values),
+            "Review D on main for {{file}}, then D. {{not valid}}"
+        );
+    }
+
+    #[test]
+    fn test_load_markdown_and_toml_templates() {
+        let dir = tempfile::tempdir().unwrap();
+        let prompts = dir.path().join(PROMPTS_DIR);
+        std::fs::create_dir_all(&prompts).unwrap();
+        std::fs::write(
+            prompts.join("security.md"),
+            "---\nname: security-review\ndescription: Look for vulnerabilities\n---\nAudit {{diff}}\n",
+        )
+        .unwrap();
+        std::fs::write(prompts.join("tests.md"), "Write tests for {{file}}\n").unwrap();
+        std::fs::write(
+            prompts.join("changelog.toml"),
+            "description = \"Draft a changelog entry\"\nprompt = \"Summarize {{branch}}\"\n",
+        )
+        .unwrap();
+        std::fs::write(prompts.join("broken.toml"), "prompt = 3").unwrap();
+        std::fs::write(prompts.join("notes.txt"), "ignored").unwrap();
+
+        let templates = load_dir(&prompts);
+        let names: Vec<&str> = templates.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["changelog", "security-review", "tests"]);
+
+        let security = &templates[1];
+        assert_eq!(security.description, "Look for vulnerabilities");
+        assert_eq!(security.body, "Audit {{diff}}");
+        assert_eq!(templates[0].description, "Draft a changelog entry");
+        assert_eq!(templates[2].body, "Write tests for {{file}}");
+    }
+}
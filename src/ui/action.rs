@@ -7,6 +7,8 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+use crate::session::TranscriptFormat;
+
 /// All mappable UI actions
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -26,6 +28,10 @@ pub enum Action {
     ForkSession,
     /// Handoff current session into a new workspace and tab
     HandoffSession,
+    /// Race one prompt across several agent/model pairs
+    StartRace,
+    /// Show the comparison for the most recent race
+    ShowRaceResults,
     /// Interrupt current agent processing
     InterruptAgent,
     /// Toggle between Chat and RawEvents view
@@ -34,6 +40,8 @@ pub enum Action {
     ShowModelSelector,
     /// Show reasoning selector dialog
     ShowReasoningSelector,
+    /// Show Codex sandbox and approval selector dialog
+    ShowCodexPermissions,
     /// Show theme picker dialog
     ShowThemePicker,
     /// Show providers selector dialog
@@ -48,6 +56,12 @@ pub enum Action {
     CopyWorkspacePath,
     /// Copy active selection to clipboard
     CopySelection,
+    /// Cycle the tool approval policy for the current repository
+    CycleApprovalPolicy,
+    /// Export the active session's transcript
+    ExportTranscript(TranscriptFormat),
+    /// Show recorded token usage and estimated spend
+    ShowUsageDashboard,
 
     // ========== Tab Management ==========
     /// Close current tab
@@ -63,6 +77,54 @@ pub enum Action {
     /// Open a file in a new tab
     OpenFile(PathBuf),
 
+    // ========== Diff Review ==========
+    /// Open the workspace diff against its base branch in a new tab
+    OpenDiffReview,
+    /// Select the next hunk
+    DiffNextHunk,
+    /// Select the previous hunk
+    DiffPrevHunk,
+    /// Stage the selected hunk
+    DiffStageHunk,
+    /// Revert the selected hunk in the working tree
+    DiffRevertHunk,
+    /// Send the selected hunk to the agent with a review remark
+    DiffCommentHunk,
+    /// Reload the diff
+    DiffRefresh,
+
+    // ========== Checkpoints ==========
+    /// Show the checkpoint timeline for the current workspace
+    ShowCheckpoints,
+    /// Restore the workspace to before the most recent agent turn
+    RollbackLastTurn,
+
+    // ========== Schedules ==========
+    /// Show scheduled prompts
+    ShowSchedules,
+
+    // ========== Workspace Setup ==========
+    /// Run the workspace's setup hooks again
+    RunWorkspaceSetup,
+
+    // ========== Goal Loop ==========
+    /// Start a goal loop on the next prompt, or stop the running one
+    StartGoal,
+
+    // ========== Chat Search ==========
+    /// Open the search bar over the chat transcript
+    SearchChat,
+    /// Jump to the next search match
+    SearchNext,
+    /// Jump to the previous search match
+    SearchPrev,
+    /// Search the history of every session
+    SearchHistory,
+
+    // ========== User Commands ==========
+    /// Run a command defined under `[[commands]]`, by name
+    RunCommand(String),
+
     // ========== Chat Scrolling ==========
     /// Scroll chat up by N lines
     ScrollUp(u16),
@@ -244,10 +306,13 @@ impl Action {
             Action::OpenPr => "Open/create PR",
             Action::ForkSession => "Fork session",
             Action::HandoffSession => "Handoff session",
+            Action::StartRace => "Race agents on one prompt",
+            Action::ShowRaceResults => "Show race results",
             Action::InterruptAgent => "Interrupt agent",
             Action::ToggleViewMode => "Toggle view mode",
             Action::ShowModelSelector => "Select model",
             Action::ShowReasoningSelector => "Select reasoning effort",
+            Action::ShowCodexPermissions => "Codex sandbox and approvals",
             Action::ShowThemePicker => "Change theme",
             Action::ShowProvidersSelector => "Select providers",
             Action::ToggleMetrics => "Toggle metrics",
@@ -255,6 +320,13 @@ impl Action {
             Action::Suspend => "Suspend",
             Action::CopyWorkspacePath => "Copy workspace path",
             Action::CopySelection => "Copy selection",
+            Action::CycleApprovalPolicy => "Cycle tool approval policy",
+            Action::ExportTranscript(format) => match format {
+                TranscriptFormat::Markdown => "Export transcript as Markdown",
+                TranscriptFormat::Html => "Export transcript as HTML",
+                TranscriptFormat::Jsonl => "Export transcript as JSONL",
+            },
+            Action::ShowUsageDashboard => "Show usage dashboard",
 
             // Tab management
             Action::CloseTab => "Close tab",
@@ -265,6 +337,37 @@ impl Action {
             // File viewer
             Action::OpenFile(_) => "Open file",
 
+            // Diff review
+            Action::OpenDiffReview => "Review workspace diff",
+            Action::DiffNextHunk => "Next hunk",
+            Action::DiffPrevHunk => "Previous hunk",
+            Action::DiffStageHunk => "Stage hunk",
+            Action::DiffRevertHunk => "Revert hunk",
+            Action::DiffCommentHunk => "Send hunk to agent with comment",
+            Action::DiffRefresh => "Refresh diff",
+
+            // Checkpoints
+            Action::ShowCheckpoints => "Show checkpoints",
+            Action::RollbackLastTurn => "Roll back last agent turn",
+
+            // Schedules
+            Action::ShowSchedules => "Show scheduled prompts",
+
+            // Workspace setup
+            Action::RunWorkspaceSetup => "Run workspace setup again",
+
+            // Goal loop
+            Action::StartGoal => "Loop until a command passes",
+
+            // Chat search
+            Action::SearchChat => "Search chat",
+            Action::SearchNext => "Next match",
+            Action::SearchPrev => "Previous match",
+            Action::SearchHistory => "Search all sessions",
+
+            // User commands
+            Action::RunCommand(_) => "Run user command",
+
             // Scrolling
             Action::ScrollUp(_) => "Scroll up",
             Action::ScrollDown(_) => "Scroll down",
@@ -369,6 +472,7 @@ impl Action {
             Action::NewProject
                 | Action::ShowModelSelector
                 | Action::ShowReasoningSelector
+                | Action::ShowCodexPermissions
                 | Action::ShowThemePicker
                 | Action::ShowProvidersSelector
                 | Action::OpenQueueEditor
@@ -379,6 +483,15 @@ impl Action {
                 | Action::OpenCommandPalette
                 | Action::ForkSession
                 | Action::HandoffSession
+                | Action::StartRace
+                | Action::ShowRaceResults
+                | Action::ShowUsageDashboard
+                | Action::ShowCheckpoints
+                | Action::RollbackLastTurn
+                | Action::ShowSchedules
+                | Action::RunWorkspaceSetup
+                | Action::StartGoal
+                | Action::SearchHistory
         )
     }
 
@@ -394,16 +507,31 @@ impl Action {
                 | Action::OpenPr
                 | Action::ForkSession
                 | Action::HandoffSession
+                | Action::StartRace
+                | Action::ShowRaceResults
                 | Action::InterruptAgent
                 | Action::ToggleViewMode
                 | Action::ShowModelSelector
                 | Action::ShowReasoningSelector
+                | Action::ShowCodexPermissions
                 | Action::ShowThemePicker
                 | Action::ShowProvidersSelector
                 | Action::ToggleMetrics
                 | Action::DumpDebugState
                 | Action::CopyWorkspacePath
                 | Action::CopySelection
+                | Action::CycleApprovalPolicy
+                | Action::ExportTranscript(_)
+                | Action::ShowUsageDashboard
+                | Action::OpenDiffReview
+                | Action::ShowCheckpoints
+                | Action::RollbackLastTurn
+                | Action::ShowSchedules
+                | Action::RunWorkspaceSetup
+                | Action::StartGoal
+                | Action::SearchChat
+                | Action::SearchHistory
+                | Action::RunCommand(_)
                 // Tab management
                 | Action::CloseTab
                 | Action::NextTab
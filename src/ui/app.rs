@@ -10,7 +10,10 @@ use std::time::{Duration, Instant};
 use anyhow::anyhow;
 use chrono::Utc;
 use crossterm::{
-    event::{EnableMouseCapture, Event, EventStream, KeyCode, KeyModifiers, MouseEventKind},
+    event::{
+        EnableFocusChange, EnableMouseCapture, Event, EventStream, KeyCode, KeyModifiers,
+        MouseEventKind,
+    },
     execute,
     terminal::{enable_raw_mode, EnterAlternateScreen},
 };
@@ -27,46 +30,65 @@ use tokio::sync::mpsc;
 use unicode_width::UnicodeWidthStr;
 use uuid::Uuid;
 
+use crate::agent::event_hooks::{run_command, run_event_hook, EventHookContext};
 use crate::agent::events::UserQuestion;
+use crate::agent::provider_error;
+use crate::agent::tool_approval;
 use crate::agent::{
     load_claude_history_with_debug, load_codex_history_with_debug,
-    load_opencode_history_for_dir_with_debug, load_opencode_history_with_debug, AgentEvent,
-    AgentInput, AgentMode, AgentRunner, AgentStartConfig, AgentType, ClaudeCodeRunner,
-    CodexCliRunner, GeminiCliRunner, HistoryDebugEntry, MessageDisplay, ModelRegistry,
-    OpencodeRunner, SessionId,
+    load_opencode_history_for_dir_with_debug, load_opencode_history_with_debug, AcpAgentRunner,
+    AgentEvent, AgentInput, AgentMode, AgentRunner, AgentStartConfig, AgentType, ApprovalPolicy,
+    ClaudeCodeRunner, CodexCliRunner, GeminiCliRunner, HistoryDebugEntry, MessageDisplay,
+    ModelRegistry, OpencodeRunner, ProcessExit, ProviderErrorKind, SessionId, TokenUsage,
+};
+use crate::config::{
+    find_user_command, parse_action, parse_key_notation, Config, KeyContext, NotificationEvent,
+    UserCommandKind, COMMAND_NAMES,
+};
+use crate::core::services::{
+    BudgetService, BudgetStatus, ContextWindowService, TurnUsageContext, UsageService,
 };
-use crate::config::{parse_action, parse_key_notation, Config, KeyContext, COMMAND_NAMES};
-use crate::core::resolve_repo_workspace_settings;
-use crate::core::services::ContextWindowService;
 use crate::core::ConduitCore;
+use crate::core::{resolve_repo_approval_policy, resolve_repo_workspace_settings};
 use crate::data::{
-    AppStateStore, ForkSeed, ForkSeedStore, QueuedImageAttachment, QueuedMessage,
-    QueuedMessageMode, Repository, RepositoryStore, SessionTab, SessionTabStore, WorkspaceStore,
+    AppStateStore, CheckpointStore, ForkSeed, ForkSeedStore, HistoryIndexStore,
+    QueuedImageAttachment, QueuedMessage, QueuedMessageMode, Repository, RepositoryStore,
+    SessionTab, SessionTabStore, UsageGroupBy, UsageSummaryRow, WorkspaceStore,
+};
+use crate::git::{
+    apply_hunk, run_workspace_teardown, CheckpointManager, HookContext, PrManager, PrStatus,
+    WorkspaceDiff, WorkspaceMode, WorkspaceRepoManager,
+};
+use crate::session::{
+    export_path, list_mention_candidates, refresh_history_index, render_transcript,
+    session_prompt_values, TranscriptFormat, TranscriptMeta,
 };
-use crate::git::{PrManager, PrStatus, WorkspaceMode, WorkspaceRepoManager};
 use crate::ui::action::Action;
 use crate::ui::app_prompt;
 use crate::ui::app_queue;
-use crate::ui::app_state::{
-    AppState, ModelPickerContext, NewProjectTarget, PendingForkRequest, PendingHandoffRequest,
-};
+use crate::ui::app_state::{AppState, ModelPickerContext, NewProjectTarget, PendingForkRequest};
 use crate::ui::capabilities::AgentCapabilities;
 use crate::ui::components::{
-    dialog_content_area, AddRepoDialog, AgentSelector, BaseDirDialog, ChatMessage, CommandPalette,
-    ConfirmationContext, ConfirmationDialog, ConfirmationType, DefaultModelSelection, ErrorDialog,
-    EventDirection, GlobalFooter, HelpDialog, InlinePromptState, InlinePromptType, MessageRole,
-    MissingToolDialog, ModelSelector, ProcessingState, ProjectEntry, ProjectPicker, PromptAnswer,
-    ProviderSelector, RawEventsClick, ReasoningSelector, SessionHeader, SessionImportPicker,
-    Sidebar, SidebarData, SlashCommand, SlashMenu, TabBar, TabBarHitTarget, ThemePicker,
-    SIDEBAR_HEADER_ROWS,
+    dialog_content_area, AddRepoDialog, AgentSelector, BaseDirDialog, ChatMessage, ChatSearchBar,
+    CheckpointDialog, CodexPermissionChange, CodexPermissionsSelector, CommandPalette,
+    ConfirmationContext, ConfirmationDialog, ConfirmationType, DefaultModelSelection,
+    DiffCommentDialog, ErrorDialog, EventDirection, GlobalFooter, GoalDialog, HelpDialog,
+    HistorySearchPicker, InlinePromptState, InlinePromptType, MentionPicker, MessageRole,
+    MissingToolDialog, ModelSelector, PermissionChoice, ProcessingState, ProjectEntry,
+    ProjectPicker, PromptAnswer, PromptVariablesDialog, ProviderSelector, RaceDialog, RaceSelector,
+    RawEventsClick, ReasoningSelector, ScheduleDialog, SessionHeader, SessionImportPicker,
+    SetupPane, Sidebar, SidebarData, SlashCommand, SlashMenu, TabBar, TabBarHitTarget, ThemePicker,
+    UsageDialog, SIDEBAR_HEADER_ROWS, USAGE_DASHBOARD_DAYS,
 };
 use crate::ui::effect::Effect;
 use crate::ui::events::{
     AppEvent, ArchiveWorkspaceDialogPreflightResult, ArchiveWorkspacePreflightResult,
     ForkSessionDialogPreflightResult, ForkWorkspaceCreated, InputMode, ProjectDiscoveryEntry,
-    RemoveProjectDialogPreflightResult, RemoveProjectResult, TitleGeneratedResult, ViewMode,
-    WorkspaceArchived, WorkspaceCreated,
+    RaceWorkspacesCreated, RemoveProjectDialogPreflightResult, RemoveProjectResult,
+    TitleGeneratedResult, ViewMode, WorkspaceArchived, WorkspaceCreated,
 };
+use crate::ui::goal::GoalLoop;
+use crate::ui::race::{RaceContender, RaceOutcome};
 use crate::ui::session::AgentSession;
 use crate::ui::terminal_guard::TerminalGuard;
 use crate::util::ToolAvailability;
@@ -85,9 +107,25 @@ mod app_actions_scroll;
 mod app_actions_sidebar;
 mod app_actions_submit;
 mod app_actions_tabs;
+mod app_chat_search;
+mod app_checkpoint;
+mod app_diff;
+mod app_event_hooks;
+mod app_goal;
+mod app_history_search;
 mod app_input;
+mod app_mentions;
+mod app_notifications;
+mod app_prompt_library;
+mod app_race;
+mod app_rate_limit;
+mod app_schedule;
 mod app_scroll;
 mod app_selection;
+mod app_setup;
+mod app_supervisor;
+mod app_tool_approval;
+mod app_user_commands;
 
 #[cfg(target_os = "macos")]
 const PROC_PIDTBSDINFO: libc::c_int = 3;
@@ -248,6 +286,30 @@ impl App {
         self.core.fork_seed_store_clone()
     }
 
+    /// Get the checkpoint DAO.
+    #[inline]
+    fn checkpoint_dao(&self) -> Option<&CheckpointStore> {
+        self.core.checkpoint_store()
+    }
+
+    /// Get a clone of the checkpoint DAO.
+    #[inline]
+    fn checkpoint_dao_clone(&self) -> Option<CheckpointStore> {
+        self.core.checkpoint_store_clone()
+    }
+
+    /// Get the history index DAO.
+    #[inline]
+    fn history_index_dao(&self) -> Option<&HistoryIndexStore> {
+        self.core.history_index_store()
+    }
+
+    /// Get a clone of the history index DAO.
+    #[inline]
+    fn history_index_dao_clone(&self) -> Option<HistoryIndexStore> {
+        self.core.history_index_store_clone()
+    }
+
     /// Get the Claude runner.
     #[inline]
     fn claude_runner(&self) -> &Arc<ClaudeCodeRunner> {
@@ -272,6 +334,12 @@ impl App {
         self.core.opencode_runner()
     }
 
+    /// Get the generic ACP agent runner.
+    #[inline]
+    fn acp_runner(&self) -> &Arc<AcpAgentRunner> {
+        self.core.acp_runner()
+    }
+
     /// Get the worktree manager.
     #[inline]
     fn worktree_manager(&self) -> &WorkspaceRepoManager {
@@ -301,9 +369,12 @@ impl App {
     fn refresh_runners(&mut self) {
         self.core.refresh_runners();
         let tools = self.tools().clone();
+        let acp_name = self.config().agent_display_name(AgentType::Acp).to_string();
         self.state
             .agent_selector_state
-            .update_available_agents(&tools);
+            .update_available_agents(&tools, &acp_name);
+        self.state.tab_manager.set_acp_name(&acp_name);
+        self.state.model_selector_state.set_acp_title(acp_name);
         if self.state.provider_selector_state.is_visible() {
             self.state.provider_selector_state =
                 crate::ui::components::ProviderSelectorState::configure_for(self.config(), &tools);
@@ -369,9 +440,12 @@ impl App {
 
         // Update agent selector based on available tools
         let tools = app.tools().clone();
+        let acp_name = app.config().agent_display_name(AgentType::Acp).to_string();
         app.state
             .agent_selector_state
-            .update_available_agents(&tools);
+            .update_available_agents(&tools, &acp_name);
+        app.state.tab_manager.set_acp_name(&acp_name);
+        app.state.model_selector_state.set_acp_title(acp_name);
 
         // Load sidebar data
         app.refresh_sidebar_data();
@@ -449,6 +523,8 @@ impl App {
             session.model_invalid = tab.model_invalid;
             session.pr_number = tab.pr_number.map(|n| n as u32);
             session.fork_seed_id = tab.fork_seed_id;
+            session.codex_approval = tab.codex_approval;
+            session.codex_sandbox = tab.codex_sandbox;
             // Restore AI-generated session title
             session.title = tab.title.clone();
             // Restore agent mode (defaults to Build if not set)
@@ -514,10 +590,13 @@ impl App {
                             }
                         }
                     }
-                    AgentType::Gemini => {
+                    AgentType::Gemini | AgentType::Acp => {
                         session.chat_view.push(
                             MessageDisplay::System {
-                                content: "Gemini CLI history import isn't supported yet, so previous messages won't be shown.".to_string(),
+                                content: format!(
+                                    "{} history import isn't supported yet, so previous messages won't be shown.",
+                                    tab.agent_type.display_name()
+                                ),
                             }
                             .to_chat_message(),
                         );
@@ -849,6 +928,8 @@ impl App {
                 // Preserve AI-generated session title
                 tab.title = session.title.clone();
                 tab.title_generated = false;
+                tab.codex_approval = session.codex_approval;
+                tab.codex_sandbox = session.codex_sandbox;
                 tab
             })
             .collect();
@@ -954,6 +1035,7 @@ impl App {
     /// Run the application main loop
     pub async fn run(&mut self) -> anyhow::Result<()> {
         self.spawn_shutdown_listeners();
+        self.spawn_schedule_ticker();
 
         // Setup terminal
         enable_raw_mode()?;
@@ -964,7 +1046,12 @@ impl App {
         // Create terminal guard AFTER enabling features - Drop will clean up on any exit path
         let mut guard = TerminalGuard::new(keyboard_enhancement_enabled);
 
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(
+            stdout,
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableFocusChange
+        )?;
 
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
@@ -1224,6 +1311,25 @@ impl App {
                 }
                 Vec::new()
             }
+            // Continuing a macro can run any action, which needs the terminal
+            AppEvent::UserCommandFinished {
+                session_id,
+                command,
+                message_index,
+                run,
+                then,
+            } => {
+                self.handle_user_command_finished(
+                    session_id,
+                    command,
+                    message_index,
+                    run,
+                    then,
+                    terminal,
+                    guard,
+                )
+                .await?
+            }
             _ => self.handle_app_event(event).await?,
         };
 
@@ -1312,6 +1418,10 @@ impl App {
             return state_changed;
         }
 
+        if self.tick_rate_limit_countdowns() {
+            state_changed = true;
+        }
+
         // Advance spinner frame for PR processing indicator
         self.state.spinner_frame = self.state.spinner_frame.wrapping_add(1);
 
@@ -1321,6 +1431,12 @@ impl App {
         // Tick session import spinner (for loading state)
         self.state.session_import_state.tick();
 
+        // Tick history search spinner (while the index refreshes)
+        self.state.history_search_state.tick();
+
+        // Tick mention picker spinner (while files are listed)
+        self.state.mention_picker_state.tick();
+
         // Tick project picker spinner (for loading state)
         self.state.project_picker_state.tick();
 
@@ -1333,12 +1449,19 @@ impl App {
 
     /// Interrupt the current agent processing
     fn interrupt_agent(&mut self) {
+        if let Some(session_id) = self.state.tab_manager.active_session().map(|s| s.id) {
+            self.interrupt_session(session_id);
+        }
+    }
+
+    /// Interrupt agent processing for a specific session
+    fn interrupt_session(&mut self, target_session_id: Uuid) {
         let mut pid = None;
         let mut pid_start_time = None;
         let mut was_processing = false;
         let mut session_id = None;
 
-        if let Some(session) = self.state.tab_manager.active_session_mut() {
+        if let Some(session) = self.state.tab_manager.session_by_id_mut(target_session_id) {
             session_id = Some(session.id);
             pid = session.agent_pid.take();
             pid_start_time = session.agent_pid_start_time.take();
@@ -1347,6 +1470,10 @@ impl App {
             session.inline_prompt = None;
             session.pending_tool_permissions.clear();
             session.pending_tool_permission_responses.clear();
+            session.tool_approval_queue.clear();
+            session.pending_restart = false;
+            session.crash_restarts = 0;
+            session.rate_limit_wait = None;
             if session.is_processing {
                 was_processing = true;
                 session.stop_processing();
@@ -1635,6 +1762,8 @@ impl App {
                 if session.is_processing {
                     session.stop_processing();
                 }
+                session.pending_restart = false;
+                session.rate_limit_wait = None;
                 pid = session.agent_pid.take();
                 pid_start_time = session.agent_pid_start_time.take();
             }
@@ -1773,10 +1902,12 @@ impl App {
             | Action::NewWorkspaceUnderCursor
             | Action::ForkSession
             | Action::HandoffSession
+            | Action::StartRace
             | Action::InterruptAgent
             | Action::ToggleViewMode
             | Action::ShowModelSelector
             | Action::ShowReasoningSelector
+            | Action::ShowCodexPermissions
             | Action::ShowThemePicker
             | Action::ShowProvidersSelector
             | Action::OpenSessionImport
@@ -1786,7 +1917,9 @@ impl App {
             | Action::ToggleAgentMode
             | Action::DumpDebugState
             | Action::CopyWorkspacePath
-            | Action::CopySelection => {
+            | Action::CopySelection
+            | Action::CycleApprovalPolicy
+            | Action::ExportTranscript(_) => {
                 self.handle_global_action(action, &mut effects);
             }
             Action::OpenPr => {
@@ -1814,6 +1947,53 @@ impl App {
                 self.handle_open_file(path, &mut effects);
             }
 
+            // ========== Diff Review ==========
+            Action::OpenDiffReview
+            | Action::DiffNextHunk
+            | Action::DiffPrevHunk
+            | Action::DiffStageHunk
+            | Action::DiffRevertHunk
+            | Action::DiffCommentHunk
+            | Action::DiffRefresh => {
+                self.handle_diff_action(action, &mut effects);
+            }
+
+            // ========== Checkpoints ==========
+            Action::ShowCheckpoints | Action::RollbackLastTurn => {
+                self.handle_checkpoint_action(action);
+            }
+
+            // ========== Schedules ==========
+            Action::ShowSchedules => {
+                self.open_schedules();
+            }
+
+            // ========== Workspace Setup ==========
+            Action::RunWorkspaceSetup => {
+                effects.extend(self.rerun_workspace_setup());
+            }
+
+            // ========== Goal Loop ==========
+            Action::StartGoal => {
+                self.toggle_goal();
+            }
+
+            // ========== Chat Search ==========
+            Action::SearchChat => {
+                self.open_chat_search();
+            }
+            Action::SearchNext | Action::SearchPrev => {
+                self.step_chat_search(matches!(action, Action::SearchNext));
+            }
+            Action::SearchHistory => {
+                effects.extend(self.open_history_search());
+            }
+
+            // ========== User Commands ==========
+            Action::RunCommand(name) => {
+                effects.extend(Box::pin(self.run_user_command(&name, terminal, guard)).await?);
+            }
+
             // ========== Chat Scrolling ==========
             Action::ScrollUp(_)
             | Action::ScrollDown(_)
@@ -1890,14 +2070,30 @@ impl App {
                 if self.state.input_mode == InputMode::SlashMenu {
                     if let Some(entry) = self.state.slash_menu_state.selected_entry() {
                         let command = entry.command;
+                        let template = match command {
+                            SlashCommand::Prompt(index) => {
+                                self.state.slash_menu_state.prompt(index).cloned()
+                            }
+                            _ => None,
+                        };
+                        let user_action = match command {
+                            SlashCommand::User(index) => self
+                                .state
+                                .slash_menu_state
+                                .user_command(index)
+                                .map(|name| Action::RunCommand(name.to_string())),
+                            _ => None,
+                        };
                         self.state.slash_menu_state.hide();
                         self.state.input_mode = InputMode::Normal;
-                        if let Some(action) = Self::slash_command_action(command) {
+                        if let Some(action) = Self::slash_command_action(command).or(user_action) {
                             effects.extend(
                                 Box::pin(self.execute_action(action, terminal, guard)).await?,
                             );
                         } else if matches!(command, SlashCommand::NewSession) {
                             self.start_new_session_in_place();
+                        } else if let Some(template) = template {
+                            effects.extend(self.start_prompt_template(template));
                         }
                     }
                 } else if self.state.input_mode == InputMode::CommandPalette {
@@ -1967,7 +2163,7 @@ impl App {
             }
 
             // ========== Command Mode ==========
-            Action::ShowHelp => {
+            Action::ShowHelp | Action::ShowUsageDashboard | Action::ShowRaceResults => {
                 self.handle_overlay_action(action, &mut effects)?;
             }
             Action::ExecuteCommand => {
@@ -2030,6 +2226,7 @@ impl App {
                         AgentType::Codex => self.codex_runner().clone(),
                         AgentType::Gemini => self.gemini_runner().clone(),
                         AgentType::Opencode => self.opencode_runner().clone(),
+                        AgentType::Acp => self.acp_runner().clone(),
                     };
 
                     let event_tx = self.event_tx.clone();
@@ -2167,6 +2364,63 @@ impl App {
                         );
                     });
                 }
+                Effect::LoadWorkspaceDiff {
+                    tab_id,
+                    working_dir,
+                } => {
+                    let event_tx = self.event_tx.clone();
+                    tokio::task::spawn_blocking(move || {
+                        let result = WorkspaceDiff::load(&working_dir).map_err(|e| e.to_string());
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::WorkspaceDiffLoaded { tab_id, result },
+                            "workspace_diff_loaded",
+                        );
+                    });
+                }
+                Effect::ApplyDiffHunk {
+                    tab_id,
+                    working_dir,
+                    patch,
+                    action,
+                } => {
+                    let event_tx = self.event_tx.clone();
+                    tokio::task::spawn_blocking(move || {
+                        let result =
+                            apply_hunk(&working_dir, &patch, action).map_err(|e| e.to_string());
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::DiffHunkApplied {
+                                tab_id,
+                                action,
+                                result,
+                            },
+                            "diff_hunk_applied",
+                        );
+                    });
+                }
+                Effect::RestoreCheckpoint {
+                    workspace_id,
+                    working_dir,
+                    commit,
+                    turn,
+                } => {
+                    let event_tx = self.event_tx.clone();
+                    tokio::task::spawn_blocking(move || {
+                        let backup_ref = CheckpointManager::backup_ref_name(workspace_id);
+                        let result = CheckpointManager::restore(&working_dir, &commit, &backup_ref)
+                            .map_err(|e| e.to_string());
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::CheckpointRestored {
+                                working_dir,
+                                turn,
+                                result,
+                            },
+                            "checkpoint_restored",
+                        );
+                    });
+                }
                 Effect::DumpDebugState => {
                     let result = self.dump_debug_state();
                     send_app_event(
@@ -2175,6 +2429,14 @@ impl App {
                         "debug_dumped",
                     );
                 }
+                Effect::ExportTranscript(format) => {
+                    let result = self.export_transcript(format);
+                    send_app_event(
+                        &self.event_tx,
+                        AppEvent::TranscriptExported { result },
+                        "transcript_exported",
+                    );
+                }
                 Effect::RunShellCommand {
                     session_id,
                     message_index,
@@ -2338,92 +2600,149 @@ impl App {
                     let event_tx = self.event_tx.clone();
 
                     tokio::task::spawn_blocking(move || {
-                        let result: Result<WorkspaceCreated, String> = (|| {
-                            let repo_dao = repo_dao
-                                .ok_or_else(|| "No repository DAO available".to_string())?;
-                            let workspace_dao = workspace_dao
-                                .ok_or_else(|| "No workspace DAO available".to_string())?;
-
-                            let repo = repo_dao
-                                .get_by_id(repo_id)
-                                .map_err(|e| format!("Failed to load repository: {}", e))?
-                                .ok_or_else(|| "Repository not found".to_string())?;
-
-                            let base_path = repo
-                                .base_path
-                                .clone()
-                                .ok_or_else(|| "Repository has no base path".to_string())?;
-                            let settings = resolve_repo_workspace_settings(&config, &repo);
-
-                            // Get ALL workspace names (including archived) to prevent resurrection
-                            // of old workspace names when creating new ones
-                            let existing_names: Vec<String> = workspace_dao
-                                .get_all_names_by_repository(repo_id)
-                                .unwrap_or_default();
-
-                            let workspace_name =
-                                crate::util::generate_workspace_name(&existing_names);
-                            let username = crate::util::get_git_username();
-                            let branch_name =
-                                crate::util::generate_branch_name(&username, &workspace_name);
+                        let result = create_workspace_blocking(
+                            repo_dao,
+                            workspace_dao,
+                            &worktree_manager,
+                            &config,
+                            repo_id,
+                        );
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::WorkspaceCreated { repo_id, result },
+                            "workspace_created",
+                        );
+                    });
+                }
+                Effect::CreateScheduledWorkspace {
+                    schedule_id,
+                    repo_id,
+                } => {
+                    let repo_dao = self.repo_dao_clone();
+                    let workspace_dao = self.workspace_dao_clone();
+                    let worktree_manager = self.worktree_manager().clone();
+                    let config = self.config().clone();
+                    let event_tx = self.event_tx.clone();
 
-                            let worktree_path = worktree_manager
-                                .create_workspace(
-                                    settings.mode,
-                                    &base_path,
-                                    &branch_name,
-                                    &workspace_name,
-                                )
-                                .map_err(|e| format!("Failed to create workspace: {}", e))?;
+                    tokio::task::spawn_blocking(move || {
+                        let result = create_workspace_blocking(
+                            repo_dao,
+                            workspace_dao,
+                            &worktree_manager,
+                            &config,
+                            repo_id,
+                        );
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::ScheduledWorkspaceCreated {
+                                schedule_id,
+                                result,
+                            },
+                            "scheduled_workspace_created",
+                        );
+                    });
+                }
+                Effect::RunWorkspaceSetup {
+                    workspace_id,
+                    hooks,
+                    root_path,
+                    workspace_path,
+                    workspace_name,
+                    branch,
+                } => {
+                    let event_tx = self.event_tx.clone();
 
-                            let workspace = crate::data::Workspace::new(
-                                repo_id,
-                                &workspace_name,
-                                &branch_name,
-                                worktree_path,
+                    tokio::task::spawn_blocking(move || {
+                        let ctx = HookContext {
+                            root_path: &root_path,
+                            workspace_path: &workspace_path,
+                            workspace_name: &workspace_name,
+                            branch: &branch,
+                        };
+                        let result = hooks.run_setup(ctx, |line| {
+                            send_app_event(
+                                &event_tx,
+                                AppEvent::WorkspaceSetupOutput { workspace_id, line },
+                                "workspace_setup_output",
                             );
-                            let workspace_id = workspace.id;
+                        });
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::WorkspaceSetupFinished {
+                                workspace_id,
+                                result,
+                            },
+                            "workspace_setup_finished",
+                        );
+                    });
+                }
+                Effect::RunEventHook {
+                    session_id,
+                    hook,
+                    event_json,
+                    working_dir,
+                } => {
+                    let event_tx = self.event_tx.clone();
 
-                            if let Err(e) = workspace_dao.create(&workspace) {
-                                if let Err(cleanup_err) = worktree_manager.remove_workspace(
-                                    settings.mode,
-                                    &base_path,
-                                    &workspace.path,
-                                ) {
-                                    tracing::error!(
-                                        error = %cleanup_err,
-                                        base_path = %base_path.display(),
-                                        workspace_path = %workspace.path.display(),
-                                        "Failed to clean up workspace after DB error"
-                                    );
-                                }
-                                if let Err(branch_err) = worktree_manager.delete_branch(
-                                    settings.mode,
-                                    &base_path,
-                                    &workspace.path,
-                                    &branch_name,
-                                ) {
-                                    tracing::error!(
-                                        error = %branch_err,
-                                        base_path = %base_path.display(),
-                                        workspace_path = %workspace.path.display(),
-                                        branch = %branch_name,
-                                        "Failed to delete branch after DB error"
-                                    );
-                                }
-                                return Err(format!("Failed to save workspace to database: {}", e));
-                            }
+                    tokio::task::spawn_blocking(move || {
+                        let ctx = EventHookContext {
+                            working_dir,
+                            session_id: session_id.to_string(),
+                        };
+                        let run = run_event_hook(&hook, &event_json, &ctx);
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::EventHookFinished {
+                                session_id,
+                                hook,
+                                run,
+                            },
+                            "event_hook_finished",
+                        );
+                    });
+                }
+                Effect::RunGoalCheck {
+                    session_id,
+                    command,
+                    working_dir,
+                    timeout,
+                } => {
+                    let event_tx = self.event_tx.clone();
 
-                            Ok(WorkspaceCreated {
-                                repo_id,
-                                workspace_id,
-                            })
-                        })();
+                    tokio::task::spawn_blocking(move || {
+                        let run = run_command(&command, &working_dir, timeout);
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::GoalCheckFinished { session_id, run },
+                            "goal_check_finished",
+                        );
+                    });
+                }
+                Effect::RunUserCommand {
+                    session_id,
+                    command,
+                    working_dir,
+                    message_index,
+                    then,
+                } => {
+                    let UserCommandKind::Script { run, timeout, .. } = &command.kind else {
+                        continue;
+                    };
+                    let (script, timeout) = (run.clone(), *timeout);
+                    let event_tx = self.event_tx.clone();
 
+                    tokio::task::spawn_blocking(move || {
+                        let run = run_command(&script, &working_dir, timeout);
                         send_app_event(
                             &event_tx,
-                            AppEvent::WorkspaceCreated { repo_id, result },
-                            "workspace_created",
+                            AppEvent::UserCommandFinished {
+                                session_id,
+                                command,
+                                message_index,
+                                run,
+                                then,
+                            },
+                            "user_command_finished",
                         );
                     });
                 }
@@ -2540,6 +2859,148 @@ impl App {
                         );
                     });
                 }
+                Effect::CreateRaceWorkspaces {
+                    race_id,
+                    source_workspace_id,
+                    count,
+                } => {
+                    let repo_dao = self.repo_dao_clone();
+                    let workspace_dao = self.workspace_dao_clone();
+                    let worktree_manager = self.worktree_manager().clone();
+                    let config = self.config().clone();
+                    let event_tx = self.event_tx.clone();
+
+                    tokio::task::spawn_blocking(move || {
+                        let result: Result<RaceWorkspacesCreated, String> = (|| {
+                            let workspace_dao = workspace_dao
+                                .ok_or_else(|| "No workspace DAO available".to_string())?;
+                            let repo_dao = repo_dao
+                                .ok_or_else(|| "No repository DAO available".to_string())?;
+
+                            let source = workspace_dao
+                                .get_by_id(source_workspace_id)
+                                .map_err(|e| format!("Failed to load workspace: {}", e))?
+                                .ok_or_else(|| "Workspace not found".to_string())?;
+                            let repo = repo_dao
+                                .get_by_id(source.repository_id)
+                                .map_err(|e| format!("Failed to load repository: {}", e))?
+                                .ok_or_else(|| "Repository not found".to_string())?;
+                            let base_path = repo
+                                .base_path
+                                .clone()
+                                .ok_or_else(|| "Repository has no base path".to_string())?;
+                            let settings = resolve_repo_workspace_settings(&config, &repo);
+
+                            // Every contender starts from the same commit
+                            let base_commit = worktree_manager
+                                .get_branch_sha(
+                                    settings.mode,
+                                    &base_path,
+                                    &source.path,
+                                    &source.branch,
+                                )
+                                .map_err(|e| format!("Failed to resolve base commit: {}", e))?;
+
+                            // Include archived names to prevent resurrecting old workspaces
+                            let mut existing_names: Vec<String> = workspace_dao
+                                .get_all_names_by_repository(source.repository_id)
+                                .unwrap_or_default();
+                            let username = crate::util::get_git_username();
+                            let mut created: Vec<crate::data::Workspace> = Vec::new();
+
+                            let cleanup = |created: &[crate::data::Workspace]| {
+                                for workspace in created {
+                                    if let Err(err) = workspace_dao.delete(workspace.id) {
+                                        tracing::error!(
+                                            error = %err,
+                                            workspace_id = %workspace.id,
+                                            "Failed to delete race workspace record"
+                                        );
+                                    }
+                                    if let Err(err) = worktree_manager.remove_workspace(
+                                        settings.mode,
+                                        &base_path,
+                                        &workspace.path,
+                                    ) {
+                                        tracing::error!(
+                                            error = %err,
+                                            workspace_path = %workspace.path.display(),
+                                            "Failed to remove race workspace"
+                                        );
+                                    }
+                                    if let Err(err) = worktree_manager.delete_branch(
+                                        settings.mode,
+                                        &base_path,
+                                        &workspace.path,
+                                        &workspace.branch,
+                                    ) {
+                                        tracing::error!(
+                                            error = %err,
+                                            branch = %workspace.branch,
+                                            "Failed to delete race workspace branch"
+                                        );
+                                    }
+                                }
+                            };
+
+                            for _ in 0..count {
+                                let workspace_name =
+                                    crate::util::generate_workspace_name(&existing_names);
+                                let branch_name =
+                                    crate::util::generate_branch_name(&username, &workspace_name);
+
+                                let worktree_path = match worktree_manager
+                                    .create_workspace_from_branch(
+                                        settings.mode,
+                                        &base_path,
+                                        &base_commit,
+                                        &branch_name,
+                                        &workspace_name,
+                                    ) {
+                                    Ok(path) => path,
+                                    Err(e) => {
+                                        cleanup(&created);
+                                        return Err(format!("Failed to create workspace: {}", e));
+                                    }
+                                };
+
+                                let workspace = crate::data::Workspace::new(
+                                    source.repository_id,
+                                    &workspace_name,
+                                    &branch_name,
+                                    worktree_path,
+                                );
+                                existing_names.push(workspace_name);
+                                let saved = workspace_dao.create(&workspace);
+                                created.push(workspace);
+                                if let Err(e) = saved {
+                                    cleanup(&created);
+                                    return Err(format!(
+                                        "Failed to save workspace to database: {}",
+                                        e
+                                    ));
+                                }
+                            }
+
+                            Ok(RaceWorkspacesCreated {
+                                repo_id: source.repository_id,
+                                base_commit,
+                                workspace_ids: created.iter().map(|w| w.id).collect(),
+                            })
+                        })(
+                        );
+
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::RaceWorkspacesCreated {
+                                race_id,
+                                source_workspace_id,
+                                result,
+                            },
+                            "race_workspaces_created",
+                        );
+                    });
+                }
                 Effect::ArchiveWorkspacePreflight { workspace_id } => {
                     let repo_dao = self.repo_dao_clone();
                     let workspace_dao = self.workspace_dao_clone();
@@ -2674,6 +3135,15 @@ impl App {
                             let mut warnings = Vec::new();
                             let mut archived_commit_sha = None;
                             if let (Some(base_path), Some(settings)) = (repo_base_path, settings) {
+                                if let Err(e) = run_workspace_teardown(HookContext {
+                                    root_path: &base_path,
+                                    workspace_path: &workspace.path,
+                                    workspace_name: &workspace.name,
+                                    branch: &workspace.branch,
+                                }) {
+                                    warnings.push(format!("Teardown hook failed: {}", e));
+                                }
+
                                 match worktree_manager.get_branch_sha(
                                     settings.mode,
                                     &base_path,
@@ -2833,6 +3303,18 @@ impl App {
                             if let (Some(base_path), Some(settings)) =
                                 (repo_base_path.as_ref(), repo_settings)
                             {
+                                if let Err(e) = run_workspace_teardown(HookContext {
+                                    root_path: base_path,
+                                    workspace_path: &ws.path,
+                                    workspace_name: &ws.name,
+                                    branch: &ws.branch,
+                                }) {
+                                    errors.push(format!(
+                                        "Teardown hook failed for workspace '{}': {}",
+                                        ws.name, e
+                                    ));
+                                }
+
                                 match worktree_manager.get_branch_sha(
                                     settings.mode,
                                     base_path,
@@ -2959,6 +3441,62 @@ impl App {
                     )
                     .await?;
                 }
+                Effect::RefreshHistoryIndex => {
+                    let (Some(store), Some(tab_dao)) =
+                        (self.history_index_dao_clone(), self.session_tab_dao_clone())
+                    else {
+                        continue;
+                    };
+                    let event_tx = self.event_tx.clone();
+                    tokio::task::spawn_blocking(move || {
+                        let result = tab_dao
+                            .get_all()
+                            .and_then(|tabs| refresh_history_index(&store, &tabs))
+                            .map_err(|err| err.to_string());
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::HistoryIndexRefreshed { result },
+                            "history_index_refreshed",
+                        );
+                    });
+                }
+                Effect::OpenHistoryHit(hit) => {
+                    self.open_history_hit(hit).await?;
+                }
+                Effect::LoadMentionCandidates { working_dir } => {
+                    let event_tx = self.event_tx.clone();
+                    tokio::task::spawn_blocking(move || {
+                        let result = list_mention_candidates(&working_dir);
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::MentionCandidatesLoaded {
+                                working_dir,
+                                result,
+                            },
+                            "mention_candidates_loaded",
+                        );
+                    });
+                }
+                Effect::ResolvePromptTemplate {
+                    session_id,
+                    template,
+                    working_dir,
+                    mut values,
+                } => {
+                    let event_tx = self.event_tx.clone();
+                    tokio::task::spawn_blocking(move || {
+                        values.extend(session_prompt_values(&template, &working_dir));
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::PromptTemplateResolved {
+                                session_id,
+                                template,
+                                values,
+                            },
+                            "prompt_template_resolved",
+                        );
+                    });
+                }
                 Effect::GenerateTitleAndBranch {
                     session_id,
                     user_message,
@@ -3023,17 +3561,29 @@ impl App {
                     | InputMode::ShowingHelp
                     | InputMode::AddingRepository
                     | InputMode::SettingBaseDir
+                    | InputMode::CommentingHunk
+                    | InputMode::SettingGoal
+                    | InputMode::FillingPromptVariables
+                    | InputMode::SearchingChat
                     | InputMode::PickingProject
                     | InputMode::ShowingError
+                    | InputMode::ShowingUsage
+                    | InputMode::ShowingRaceResults
+                    | InputMode::ShowingCheckpoints
+                    | InputMode::ShowingSchedules
                     | InputMode::SelectingAgent
                     | InputMode::Confirming
                     | InputMode::ImportingSession
+                    | InputMode::SearchingHistory
                     | InputMode::CommandPalette
                     | InputMode::SlashMenu
+                    | InputMode::PickingMention
                     | InputMode::SelectingTheme
                     | InputMode::SelectingProviders
+                    | InputMode::SelectingRaceContenders
                     | InputMode::SelectingModel
                     | InputMode::SelectingReasoning
+                    | InputMode::SelectingCodexPermissions
             )
     }
 
@@ -3056,14 +3606,42 @@ impl App {
             && input_mode == InputMode::Normal
     }
 
+    /// Helper to check if an `@` keypress should open the mention picker.
+    /// `@` only starts a mention at the start of a word, so addresses like
+    /// `me@example.com` type through.
+    fn should_trigger_mention_picker(
+        key_code: KeyCode,
+        key_modifiers: KeyModifiers,
+        input_mode: InputMode,
+        at_word_start: bool,
+        shell_mode: bool,
+        has_inline_prompt: bool,
+    ) -> bool {
+        key_code == KeyCode::Char('@')
+            && key_modifiers.difference(KeyModifiers::SHIFT).is_empty()
+            && at_word_start
+            && !shell_mode
+            && !has_inline_prompt
+            && input_mode == InputMode::Normal
+    }
+
     fn slash_command_action(command: SlashCommand) -> Option<Action> {
         match command {
             SlashCommand::Model => Some(Action::ShowModelSelector),
             SlashCommand::Reasoning => Some(Action::ShowReasoningSelector),
+            SlashCommand::Sandbox => Some(Action::ShowCodexPermissions),
             SlashCommand::Providers => Some(Action::ShowProvidersSelector),
             SlashCommand::Fork => Some(Action::ForkSession),
             SlashCommand::Handoff => Some(Action::HandoffSession),
-            SlashCommand::NewSession => None,
+            SlashCommand::Race => Some(Action::StartRace),
+            SlashCommand::Diff => Some(Action::OpenDiffReview),
+            SlashCommand::Checkpoints => Some(Action::ShowCheckpoints),
+            SlashCommand::Schedules => Some(Action::ShowSchedules),
+            SlashCommand::Setup => Some(Action::RunWorkspaceSetup),
+            SlashCommand::Goal => Some(Action::StartGoal),
+            SlashCommand::Search => Some(Action::SearchChat),
+            SlashCommand::History => Some(Action::SearchHistory),
+            SlashCommand::NewSession | SlashCommand::Prompt(_) | SlashCommand::User(_) => None,
         }
     }
 
@@ -3253,8 +3831,11 @@ impl App {
             _ => {}
         }
 
-        // Try to parse as an action name
-        parse_action(&command_lower)
+        // Try to parse as an action name, then as a user command
+        parse_action(&command_lower).or_else(|| {
+            find_user_command(&self.config().user_commands, &command_lower)
+                .map(|command| Action::RunCommand(command.name.clone()))
+        })
     }
 
     /// Autocomplete the command buffer
@@ -3503,10 +4084,13 @@ impl App {
                                 }
                             }
                         }
-                        AgentType::Gemini => {
+                        AgentType::Gemini | AgentType::Acp => {
                             session.chat_view.push(
                                 MessageDisplay::System {
-                                    content: "Gemini CLI history import isn't supported yet, so previous messages won't be shown.".to_string(),
+                                    content: format!(
+                                        "{} history import isn't supported yet, so previous messages won't be shown.",
+                                        saved.agent_type.display_name()
+                                    ),
                                 }
                                 .to_chat_message(),
                             );
@@ -3618,6 +4202,7 @@ impl App {
             AgentType::Codex => crate::util::Tool::Codex,
             AgentType::Gemini => crate::util::Tool::Gemini,
             AgentType::Opencode => crate::util::Tool::Opencode,
+            AgentType::Acp => crate::util::Tool::Acp,
         }
     }
 
@@ -4015,12 +4600,23 @@ impl App {
     fn sync_input_mode_for_active_tab(&mut self) {
         match self.state.input_mode {
             InputMode::Normal | InputMode::Scrolling | InputMode::FileViewer => {
-                if self.state.tab_manager.active_is_file() {
+                if self.state.tab_manager.active_is_file()
+                    || self.state.tab_manager.active_is_diff()
+                {
                     self.state.input_mode = InputMode::FileViewer;
                 } else if self.state.input_mode == InputMode::FileViewer {
                     self.state.input_mode = InputMode::Normal;
                 }
             }
+            // The search follows the user to the new tab
+            InputMode::SearchingChat => {
+                if self.state.tab_manager.active_session().is_some() {
+                    self.update_chat_search();
+                } else {
+                    self.close_chat_search();
+                    self.sync_input_mode_for_active_tab();
+                }
+            }
             _ => {}
         }
     }
@@ -4067,7 +4663,8 @@ impl App {
             | Some(ConfirmationContext::OpenExistingPr { .. })
             | Some(ConfirmationContext::ForkSession { .. })
             | Some(ConfirmationContext::ForkSessionPreflightInProgress { .. })
-            | Some(ConfirmationContext::SteerFallback { .. }) => InputMode::Normal,
+            | Some(ConfirmationContext::SteerFallback { .. })
+            | Some(ConfirmationContext::RateLimitFallback { .. }) => InputMode::Normal,
             // Sidebar operations return to sidebar navigation
             Some(ConfirmationContext::ArchiveWorkspace(_))
             | Some(ConfirmationContext::ArchiveWorkspaceRemoteDelete { .. })
@@ -4076,6 +4673,9 @@ impl App {
             | Some(ConfirmationContext::RemoveProject(_))
             | Some(ConfirmationContext::RemoveProjectPreflightInProgress { .. })
             | Some(ConfirmationContext::SelectWorkspaceMode { .. }) => InputMode::SidebarNavigation,
+            // Diff review dialogs return to the diff tab
+            Some(ConfirmationContext::RevertDiffHunk { .. }) => InputMode::FileViewer,
+            Some(ConfirmationContext::RestoreCheckpoint { .. }) => self.checkpoint_return_mode(),
             // No context: return to Normal if tabs exist, otherwise SidebarNavigation
             // (avoids unexpectedly flipping to sidebar when user has active tabs)
             None => {
@@ -4345,12 +4945,45 @@ impl App {
                 Some(mode),
                 repo.archive_delete_branch,
                 repo.archive_remote_prompt,
+                repo.approval_policy,
             )
             .map_err(|e| format!("Failed to update repository settings: {}", e))?;
 
         Ok(())
     }
 
+    /// Advance the active workspace's repository to the next tool approval policy.
+    fn cycle_repo_approval_policy(&mut self) -> Result<(String, ApprovalPolicy), String> {
+        let workspace_id = self
+            .state
+            .tab_manager
+            .active_session()
+            .and_then(|session| session.workspace_id)
+            .ok_or_else(|| "No workspace selected".to_string())?;
+        let workspace_dao = self
+            .workspace_dao()
+            .ok_or_else(|| "Workspace database unavailable".to_string())?;
+        let repo_dao = self
+            .repo_dao()
+            .ok_or_else(|| "Repository database unavailable".to_string())?;
+
+        let workspace = workspace_dao
+            .get_by_id(workspace_id)
+            .map_err(|e| format!("Failed to load workspace: {}", e))?
+            .ok_or_else(|| "Workspace not found".to_string())?;
+        let repo = repo_dao
+            .get_by_id(workspace.repository_id)
+            .map_err(|e| format!("Failed to load repository: {}", e))?
+            .ok_or_else(|| "Repository not found".to_string())?;
+
+        let next = resolve_repo_approval_policy(self.config(), Some(&repo)).next();
+        repo_dao
+            .update_settings(repo.id, None, None, None, Some(next))
+            .map_err(|e| format!("Failed to update repository settings: {}", e))?;
+
+        Ok((repo.name, next))
+    }
+
     /// Initiate project removal - shows confirmation dialog
     fn initiate_remove_project(&mut self, repo_id: uuid::Uuid) {
         self.show_blocking_confirmation_loading(
@@ -4685,12 +5318,15 @@ impl App {
                     }
                 }
             }
-            AgentType::Gemini => {
+            AgentType::Gemini | AgentType::Acp => {
                 session.resume_session_id = None;
                 session.agent_session_id = None;
                 session.chat_view.push(
                     MessageDisplay::System {
-                        content: "Gemini CLI session import isn't supported yet.".to_string(),
+                        content: format!(
+                            "{} session import isn't supported yet.",
+                            agent_type.display_name()
+                        ),
                     }
                     .to_chat_message(),
                 );
@@ -4763,6 +5399,13 @@ impl App {
             return Ok(effects);
         }
 
+        if self.state.input_mode == InputMode::SelectingCodexPermissions
+            && self.state.codex_permissions_state.is_visible()
+        {
+            self.handle_codex_permissions_click(x, y);
+            return Ok(effects);
+        }
+
         if self.state.input_mode == InputMode::SelectingProviders
             && self.state.provider_selector_state.is_visible()
         {
@@ -5135,7 +5778,7 @@ impl App {
             };
             let model_width = model_display.len();
 
-            let agent_display = session.agent_type.display_name();
+            let agent_display = session.agent_name();
             let agent_width = agent_display.len();
             let model = session.model.clone();
             let agent_type = session.agent_type;
@@ -5439,6 +6082,95 @@ impl App {
         effects
     }
 
+    /// Apply the highlighted Codex sandbox/approval option to the active session.
+    pub(super) fn apply_codex_permissions_selection(&mut self) {
+        let change = self
+            .state
+            .codex_permissions_state
+            .selected_option()
+            .map(|option| option.change);
+        self.state.codex_permissions_state.hide();
+        self.state.input_mode = InputMode::Normal;
+        let (Some(change), Some(session)) = (change, self.state.tab_manager.active_session_mut())
+        else {
+            return;
+        };
+
+        // Codex takes these settings when its conversation starts or resumes,
+        // so they can't change under a live process.
+        if session.agent_input_tx.is_some() {
+            let display = MessageDisplay::Error {
+                content: "Changing Codex permissions while the agent is running is not supported. Start a new session/tab."
+                    .to_string(),
+            };
+            session.chat_view.push(display.to_chat_message());
+            return;
+        }
+
+        let msg = match change {
+            CodexPermissionChange::Sandbox(mode) => {
+                session.codex_sandbox = mode;
+                format!(
+                    "Codex sandbox set to: {}",
+                    mode.map_or("Default", |mode| mode.display_name())
+                )
+            }
+            CodexPermissionChange::Approval(mode) => {
+                session.codex_approval = mode;
+                format!(
+                    "Codex approvals set to: {}",
+                    mode.map_or("Default", |mode| mode.display_name())
+                )
+            }
+        };
+        let display = MessageDisplay::System { content: msg };
+        session.chat_view.push(display.to_chat_message());
+    }
+
+    fn handle_codex_permissions_click(&mut self, x: u16, y: u16) {
+        const DIALOG_WIDTH: u16 = 64;
+        const DIALOG_HEIGHT: u16 = 16;
+
+        let terminal_size = crossterm::terminal::size().unwrap_or((80, 24));
+        let screen = Rect::new(0, 0, terminal_size.0, terminal_size.1);
+        let dialog_width = DIALOG_WIDTH.min(screen.width.saturating_sub(4));
+        let dialog_height = DIALOG_HEIGHT.min(screen.height.saturating_sub(2));
+        let dialog_area = Rect {
+            x: (screen.width.saturating_sub(dialog_width)) / 2,
+            y: (screen.height.saturating_sub(dialog_height)) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        if !Self::point_in_rect(x, y, dialog_area) {
+            self.state.codex_permissions_state.hide();
+            self.state.input_mode = InputMode::Normal;
+            return;
+        }
+
+        let inner = dialog_content_area(dialog_area);
+        if inner.height < 4 {
+            return;
+        }
+
+        // Layout: search, separator, list, hint
+        let list_y = inner.y + 2;
+        let list_height = inner.height.saturating_sub(3);
+        self.state
+            .codex_permissions_state
+            .set_max_visible(list_height.saturating_sub(1) as usize);
+
+        if y >= list_y
+            && y < list_y + list_height
+            && self
+                .state
+                .codex_permissions_state
+                .select_at_row((y - list_y) as usize)
+        {
+            self.apply_codex_permissions_selection();
+        }
+    }
+
     fn handle_reasoning_selector_click(&mut self, x: u16, y: u16) {
         const DIALOG_WIDTH: u16 = 58;
         const DIALOG_HEIGHT: u16 = 14;
@@ -5590,6 +6322,8 @@ impl App {
             GlobalFooter::sidebar_hints()
         } else if self.state.tab_manager.active_is_file() {
             GlobalFooter::file_viewer_hints()
+        } else if self.state.tab_manager.active_is_diff() {
+            GlobalFooter::diff_viewer_hints()
         } else {
             match self.state.view_mode {
                 ViewMode::Chat => GlobalFooter::chat_hints(),
@@ -5650,6 +6384,8 @@ impl App {
             InputMode::Normal | InputMode::Scrolling | InputMode::FileViewer => {
                 if self.state.tab_manager.active_is_file() {
                     KeyContext::FileViewer
+                } else if self.state.tab_manager.active_is_diff() {
+                    KeyContext::DiffViewer
                 } else {
                     KeyContext::from_input_mode(self.state.input_mode, self.state.view_mode)
                 }
@@ -5690,6 +6426,23 @@ impl App {
                 );
             }
             AppEvent::OpenPrCompleted { result: Ok(_) } => {}
+            AppEvent::WorkspaceDiffLoaded { tab_id, result } => {
+                self.handle_workspace_diff_loaded(tab_id, result);
+            }
+            AppEvent::DiffHunkApplied {
+                tab_id,
+                action,
+                result,
+            } => {
+                effects.extend(self.handle_diff_hunk_applied(tab_id, action, result));
+            }
+            AppEvent::CheckpointRestored {
+                working_dir,
+                turn,
+                result,
+            } => {
+                effects.extend(self.handle_checkpoint_restored(working_dir, turn, result));
+            }
             AppEvent::DebugDumped { result } => match result {
                 Ok(path) => {
                     self.show_error_with_details(
@@ -5702,6 +6455,18 @@ impl App {
                     self.show_error("Export Failed", &err);
                 }
             },
+            AppEvent::TranscriptExported { result } => match result {
+                Ok(path) => {
+                    self.show_error_with_details(
+                        "Transcript Exported",
+                        "Session transcript has been exported.",
+                        &format!("File saved to:\n{}", path),
+                    );
+                }
+                Err(err) => {
+                    self.show_error("Export Failed", &err);
+                }
+            },
             AppEvent::WorkspaceCreated { repo_id, result } => {
                 self.clear_repo_action_busy(repo_id);
                 match result {
@@ -5711,6 +6476,7 @@ impl App {
                         if let Some(index) = self.find_workspace_index(created.workspace_id) {
                             self.state.sidebar_state.tree_state.selected = index;
                         }
+                        effects.extend(self.start_workspace_setup(created.workspace_id));
                         // Open workspace, close sidebar, and focus prompt box
                         self.open_workspace_with_options(created.workspace_id, true);
                     }
@@ -5731,6 +6497,8 @@ impl App {
                         if let Some(index) = self.find_workspace_index(created.workspace_id) {
                             self.state.sidebar_state.tree_state.selected = index;
                         }
+                        // The fork seed waits for setup like any other prompt
+                        effects.extend(self.start_workspace_setup(created.workspace_id));
                         match self.finish_fork_session(created.workspace_id) {
                             Ok(mut fork_effects) => {
                                 effects.append(&mut fork_effects);
@@ -5779,7 +6547,35 @@ impl App {
                                 }
                             }
                         }
-                        self.show_error("Fork Failed", &err);
+                        self.show_error("Fork Failed", &err);
+                    }
+                }
+            }
+            AppEvent::RaceWorkspacesCreated {
+                race_id,
+                source_workspace_id,
+                result,
+            } => {
+                self.clear_workspace_busy(source_workspace_id);
+                match result {
+                    Ok(created) => {
+                        self.refresh_sidebar_data();
+                        self.state.sidebar_data.expand_repo(created.repo_id);
+                        for workspace_id in created.workspace_ids.clone() {
+                            effects.extend(self.start_workspace_setup(workspace_id));
+                        }
+                        effects.extend(self.finish_race_setup(race_id, created));
+                    }
+                    Err(err) => {
+                        if self
+                            .state
+                            .active_race
+                            .as_ref()
+                            .is_some_and(|race| race.id == race_id)
+                        {
+                            self.state.active_race = None;
+                        }
+                        self.show_error("Race Failed", &err);
                     }
                 }
             }
@@ -6020,6 +6816,7 @@ impl App {
                         }
 
                         self.close_tabs_for_workspace(archived.workspace_id);
+                        self.state.workspace_setups.remove(&archived.workspace_id);
 
                         let current_selection = self.state.sidebar_state.tree_state.selected;
                         self.refresh_sidebar_data();
@@ -6198,6 +6995,42 @@ impl App {
                     }
                 }
             }
+            AppEvent::AgentRestartDue { session_id } => {
+                effects.extend(self.handle_agent_restart_due(session_id)?);
+            }
+            AppEvent::RateLimitRetryDue { session_id } => {
+                effects.extend(self.handle_rate_limit_retry_due(session_id)?);
+            }
+            AppEvent::ScheduleTick => {
+                effects.extend(self.run_due_schedules());
+            }
+            AppEvent::ScheduledWorkspaceCreated {
+                schedule_id,
+                result,
+            } => {
+                effects.extend(self.handle_scheduled_workspace_created(schedule_id, result)?);
+            }
+            AppEvent::WorkspaceSetupOutput { workspace_id, line } => {
+                if let Some(setup) = self.state.workspace_setups.get_mut(&workspace_id) {
+                    setup.push_line(line);
+                }
+            }
+            AppEvent::WorkspaceSetupFinished {
+                workspace_id,
+                result,
+            } => {
+                effects.extend(self.handle_workspace_setup_finished(workspace_id, result)?);
+            }
+            AppEvent::EventHookFinished {
+                session_id,
+                hook,
+                run,
+            } => {
+                effects.extend(self.handle_event_hook_finished(session_id, hook, run)?);
+            }
+            AppEvent::GoalCheckFinished { session_id, run } => {
+                effects.extend(self.handle_goal_check_finished(session_id, run)?);
+            }
             AppEvent::AgentStreamEnded { session_id } => {
                 let Some(tab_index) = self.state.tab_manager.session_index_by_id(session_id) else {
                     tracing::debug!(
@@ -6235,6 +7068,22 @@ impl App {
                     self.state.stop_footer_spinner();
                 }
 
+                // Queued prompts wait for a crashed agent to be resumed or a
+                // rate-limited prompt to be resent
+                if self
+                    .state
+                    .tab_manager
+                    .session(tab_index)
+                    .is_some_and(|session| {
+                        session.pending_restart
+                            || session
+                                .rate_limit_wait
+                                .is_some_and(|wait| wait.retry_at.is_some())
+                    })
+                {
+                    return Ok(effects);
+                }
+
                 match self.drain_queue_for_tab(tab_index) {
                     Ok(mut queued_effects) => effects.append(&mut queued_effects),
                     Err(err) => {
@@ -6261,6 +7110,22 @@ impl App {
                 // Background refresh done - stop spinner
                 self.state.session_import_state.set_loading(false);
             }
+            AppEvent::HistoryIndexRefreshed { result } => {
+                self.handle_history_index_refreshed(result);
+            }
+            AppEvent::MentionCandidatesLoaded {
+                working_dir,
+                result,
+            } => {
+                self.handle_mention_candidates_loaded(working_dir, result);
+            }
+            AppEvent::PromptTemplateResolved {
+                session_id,
+                template,
+                values,
+            } => {
+                effects.extend(self.handle_prompt_template_resolved(session_id, template, values)?);
+            }
             AppEvent::GitTracker(update) => {
                 self.handle_git_tracker_update(update);
             }
@@ -6534,6 +7399,7 @@ impl App {
             );
             return Ok(());
         };
+        let hook_effects = self.event_hook_effects(tab_index, &event);
         // Check if this is a non-active tab receiving content - mark as needing attention
         let is_active_tab = self.state.tab_manager.active_index() == tab_index;
         let is_content_event = matches!(
@@ -6546,6 +7412,12 @@ impl App {
                 | AgentEvent::TurnCompleted(_)
                 | AgentEvent::TurnFailed(_)
         );
+        let mut race_outcome = match &event {
+            AgentEvent::TurnCompleted(_) => Some(RaceOutcome::Finished),
+            AgentEvent::TurnFailed(_) => Some(RaceOutcome::Failed),
+            AgentEvent::Error(err) if err.is_fatal => Some(RaceOutcome::Failed),
+            _ => None,
+        };
 
         // Track whether we need to stop footer spinner (done after session borrow ends)
         let mut should_stop_footer_spinner = false;
@@ -6554,6 +7426,22 @@ impl App {
         let mut pending_model_invalidation = false;
         let mut should_drain_queue = false;
         let mut pending_observed_context_window: Option<(AgentType, String, i64)> = None;
+        let mut pending_turn_usage: Option<(TurnUsageContext, TokenUsage)> = None;
+        let mut pending_in_flight_usage: Option<(TurnUsageContext, TokenUsage)> = None;
+        let mut pending_checkpoint_files: Option<(Uuid, PathBuf, Vec<String>)> = None;
+        let mut pending_restart: Option<Duration> = None;
+        let mut pending_rate_limit_retry: Option<Duration> = None;
+        let mut pending_rate_limit_fallback = false;
+        let mut pending_notification: Option<(NotificationEvent, String)> = None;
+        let mut pending_goal_check = false;
+        let input_tool = match &event {
+            AgentEvent::ToolStarted(tool) => Some(tool.tool_name.clone()),
+            AgentEvent::ControlRequest(request) => Some(request.tool_name.clone()),
+            _ => None,
+        };
+        let interrupt_on_budget = self.config().budget.interrupts_running_turn();
+        let restart_policy = self.config().supervisor;
+        let rate_limit_config = self.config().rate_limit.clone();
 
         {
             let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
@@ -6596,6 +7484,7 @@ impl App {
                 },
             };
             session.record_raw_event(EventDirection::Received, event_type, raw_json);
+            let had_inline_prompt = session.inline_prompt.is_some();
 
             match event {
                 AgentEvent::SessionInit(init) => {
@@ -6606,17 +7495,38 @@ impl App {
                 }
                 AgentEvent::TurnStarted => {
                     session.is_processing = true;
+                    session.in_flight_usage.start_turn();
                     session.update_status();
                 }
                 AgentEvent::TurnCompleted(completed) => {
+                    pending_checkpoint_files = Self::take_checkpoint_files(session);
+                    pending_turn_usage =
+                        Some((Self::turn_usage_context(session), completed.usage.clone()));
                     session.add_usage(completed.usage);
                     session.stop_processing();
+                    session.crash_restarts = 0;
+                    session.rate_limit_retries = 0;
+                    session.rate_limit_wait = None;
                     if session.inline_prompt.is_none() {
                         session.agent_input_tx = None;
                     }
                     if session.inline_prompt.is_none() && !session.queued_messages.is_empty() {
                         should_drain_queue = true;
                     }
+                    // A goal loop checks its command once the agent has nothing left to do
+                    pending_goal_check = !should_drain_queue
+                        && session.inline_prompt.is_none()
+                        && session.goal.as_ref().is_some_and(GoalLoop::is_active);
+                    // Nothing to report while queued prompts or a goal loop keep the agent going
+                    if !should_drain_queue
+                        && !pending_goal_check
+                        && !session.suppress_next_turn_summary
+                    {
+                        pending_notification = Some((
+                            NotificationEvent::TurnCompleted,
+                            Self::turn_notification_summary(&session.current_turn_summary),
+                        ));
+                    }
                     // Safety net: avoid suppressing a future real assistant message
                     // (in case the final assistant message event never arrived)
                     session.suppress_next_assistant_reply = false;
@@ -6640,6 +7550,7 @@ impl App {
                     }
                 }
                 AgentEvent::TurnFailed(failed) => {
+                    pending_checkpoint_files = Self::take_checkpoint_files(session);
                     session.stop_processing();
                     session.chat_view.finalize_streaming();
                     session.tools_in_flight = 0;
@@ -6651,6 +7562,8 @@ impl App {
                     }
                     session.suppress_next_assistant_reply = false;
                     session.suppress_next_turn_summary = false;
+                    pending_notification =
+                        Some((NotificationEvent::TurnFailed, failed.error.clone()));
                     let display = MessageDisplay::Error {
                         content: failed.error,
                     };
@@ -6787,6 +7700,35 @@ impl App {
                         session.chat_view.push(display.to_chat_message());
                     }
                 }
+                AgentEvent::ControlRequest(request) if session.agent_type.uses_acp() => {
+                    // ACP permission requests carry their own option list and are
+                    // answered through the inline prompt rather than Claude's JSONL.
+                    let tool_id = request
+                        .tool_use_id
+                        .clone()
+                        .unwrap_or_else(|| request.request_id.clone());
+                    session
+                        .pending_tool_permissions
+                        .insert(tool_id.clone(), request.request_id.clone());
+                    session.inline_prompt = Some(InlinePromptState::new_tool_permission(
+                        tool_id,
+                        request.request_id.clone(),
+                        request.tool_name.clone(),
+                        Self::tool_permission_detail(&request.input),
+                        Self::tool_permission_choices(&request.input),
+                    ));
+                    session.chat_view.scroll_to_bottom();
+                    should_stop_footer_spinner = true;
+                }
+                AgentEvent::ControlRequest(request)
+                    if !tool_approval::is_interactive_tool(&request.tool_name) =>
+                {
+                    // A Claude tool call the approval policy left to the user
+                    Self::queue_tool_approval(session, request);
+                    if session.inline_prompt.is_some() {
+                        should_stop_footer_spinner = true;
+                    }
+                }
                 AgentEvent::ControlRequest(request) => {
                     if let Some(tool_use_id) = request.tool_use_id.clone() {
                         session
@@ -6904,10 +7846,30 @@ impl App {
                     }
                 }
                 AgentEvent::Error(err) => {
+                    let exit = ProcessExit::from_error(&err);
+                    let provider_error = ProviderErrorKind::from_error(&err)
+                        .filter(|_| err.is_fatal && session.is_processing);
+                    let retry_after = provider_error::retry_after(&err);
+                    if err.is_fatal {
+                        pending_notification =
+                            Some((NotificationEvent::TurnFailed, err.message.clone()));
+                    }
                     let display = MessageDisplay::Error {
                         content: err.message,
                     };
                     session.chat_view.push(display.to_chat_message());
+                    if let Some(exit) = exit {
+                        pending_restart = Self::plan_crash_restart(&restart_policy, session, &exit);
+                    }
+                    if let Some(kind) = provider_error {
+                        pending_rate_limit_retry = Self::plan_rate_limit_retry(
+                            &rate_limit_config,
+                            session,
+                            kind,
+                            retry_after,
+                        );
+                        pending_rate_limit_fallback = kind.is_transient() && is_active_tab;
+                    }
                     if err.code.as_deref() == Some("model_not_found") {
                         session.model = None;
                         session.model_invalid = true;
@@ -6927,6 +7889,11 @@ impl App {
                     }
                 }
                 AgentEvent::TokenUsage(usage_event) => {
+                    let turn_usage = session.in_flight_usage.observe(&usage_event.usage);
+                    if interrupt_on_budget && session.is_processing {
+                        pending_in_flight_usage =
+                            Some((Self::turn_usage_context(session), turn_usage));
+                    }
                     session.update_context_usage(&usage_event);
                     if let Some(context_window) = usage_event.context_window {
                         if context_window > 0 {
@@ -6975,15 +7942,77 @@ impl App {
                     // Clear any pending warning since we just compacted
                     session.pending_context_warning = None;
                 }
+                AgentEvent::FileChanged(change) => {
+                    session.record_file_change(change.path, 0, 0);
+                }
                 _ => {}
             }
+            if !had_inline_prompt && session.inline_prompt.is_some() {
+                let tool = input_tool.unwrap_or_else(|| "The agent".to_string());
+                pending_notification = Some((
+                    NotificationEvent::InputNeeded,
+                    format!("{} is waiting for you", tool),
+                ));
+            }
         } // End session borrow scope
 
+        if let Some(delay) = pending_restart {
+            // The resumed run finishes the turn; hold the race result and the queue until then
+            race_outcome = None;
+            should_drain_queue = false;
+            self.schedule_agent_restart(session_id, delay);
+        }
+        if let Some(delay) = pending_rate_limit_retry {
+            // The resent prompt finishes the turn; hold the race result and the queue until then
+            race_outcome = None;
+            should_drain_queue = false;
+            self.schedule_rate_limit_retry(session_id, delay);
+        }
+        if pending_rate_limit_fallback {
+            self.offer_rate_limit_fallback(session_id);
+        }
+        if let Some((checkpoint_id, working_dir, files)) = pending_checkpoint_files {
+            self.record_checkpoint_files(checkpoint_id, working_dir, files);
+        }
         if let Some((workspace_id, status)) = pending_sidebar_pr_update {
             self.state
                 .sidebar_data
                 .update_workspace_pr_status(workspace_id, Some(status));
         }
+        let mut goal_effects = Vec::new();
+        if pending_goal_check {
+            let cost = pending_turn_usage.as_ref().and_then(|(context, usage)| {
+                self.config()
+                    .calculate_cost(context.agent_type, context.model.as_deref(), usage)
+            });
+            goal_effects = self.start_goal_check(tab_index, cost);
+        }
+        if let Some((context, usage)) = pending_turn_usage {
+            if let Err(err) = UsageService::record_turn(&self.core, context.clone(), &usage) {
+                tracing::warn!(error = %err, %session_id, "Failed to record turn usage");
+            }
+            let status = BudgetService::status(&self.core, &context, None);
+            if status.is_exceeded() {
+                // Hold queued prompts; they stay in the queue until the budget allows them
+                should_drain_queue = false;
+            }
+            self.notify_budget_status(tab_index, status);
+        }
+        if let Some((context, usage)) = pending_in_flight_usage {
+            let status = BudgetService::status(&self.core, &context, Some(&usage));
+            if status.is_exceeded() {
+                // The interrupted turn never completes, so record what it used so far
+                if let Err(err) = UsageService::record_turn(&self.core, context, &usage) {
+                    tracing::warn!(error = %err, %session_id, "Failed to record turn usage");
+                }
+                self.interrupt_session(session_id);
+                self.notify_budget_status(tab_index, status);
+            }
+        }
+        if let Some(outcome) = race_outcome {
+            // After the ledger write above, so the comparison sees this turn's cost
+            self.record_race_turn_end(session_id, outcome);
+        }
         if let Some((agent_type, model_id, context_window)) = pending_observed_context_window {
             ContextWindowService::record_observed(
                 &self.core,
@@ -7024,6 +8053,22 @@ impl App {
                 }
             }
         }
+        if !hook_effects.is_empty() {
+            self.run_effects(hook_effects).await?;
+        }
+        if !goal_effects.is_empty() {
+            self.run_effects(goal_effects).await?;
+        }
+        if let Some((event, summary)) = pending_notification {
+            // A crash restart or rate-limit retry picks the turn back up
+            let resuming = pending_restart.is_some() || pending_rate_limit_retry.is_some();
+            if !(event == NotificationEvent::TurnFailed && resuming) {
+                if event == NotificationEvent::TurnFailed {
+                    self.end_goal_on_failure(tab_index);
+                }
+                self.notify_attention(tab_index, event, &summary);
+            }
+        }
 
         // Stop footer spinner after session borrow is released
         if should_stop_footer_spinner {
@@ -7124,6 +8169,89 @@ impl App {
         }
     }
 
+    /// Extract the selectable options from an ACP permission request payload.
+    fn tool_permission_choices(input: &serde_json::Value) -> Vec<PermissionChoice> {
+        input
+            .get("options")
+            .and_then(|options| options.as_array())
+            .map(|options| {
+                options
+                    .iter()
+                    .filter_map(|option| {
+                        let id = option.get("option_id")?.as_str()?.to_string();
+                        let label = option
+                            .get("name")
+                            .and_then(|name| name.as_str())
+                            .unwrap_or(&id)
+                            .to_string();
+                        Some(PermissionChoice {
+                            id,
+                            label,
+                            requires_reason: false,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Summarize the tool input shown under an ACP permission prompt.
+    fn tool_permission_detail(input: &serde_json::Value) -> Option<String> {
+        let raw_input = input.get("raw_input")?;
+        if raw_input.is_null() {
+            return None;
+        }
+        for key in ["command", "file_path", "path", "url", "pattern"] {
+            if let Some(value) = raw_input.get(key).and_then(|value| value.as_str()) {
+                return Some(value.to_string());
+            }
+        }
+        serde_json::to_string_pretty(raw_input).ok()
+    }
+
+    /// Answer a pending ACP permission request. `None` cancels the request.
+    fn send_acp_permission_response(
+        &mut self,
+        request_id: &str,
+        option_id: Option<String>,
+    ) -> Vec<Effect> {
+        let input_tx = {
+            let Some(session) = self.state.tab_manager.active_session_mut() else {
+                return Vec::new();
+            };
+            if !session.agent_type.uses_acp() {
+                return Vec::new();
+            }
+            let Some(input_tx) = session.agent_input_tx.clone() else {
+                session.chat_view.push(
+                    MessageDisplay::Error {
+                        content: "Permission response failed: session not ready.".to_string(),
+                    }
+                    .to_chat_message(),
+                );
+                return Vec::new();
+            };
+            session.start_processing();
+            session.set_processing_state(ProcessingState::Thinking);
+            input_tx
+        };
+        self.state.start_footer_spinner(None);
+
+        let request_id = request_id.to_string();
+        tokio::spawn(async move {
+            if let Err(err) = input_tx
+                .send(AgentInput::AcpPermission {
+                    request_id,
+                    option_id,
+                })
+                .await
+            {
+                tracing::warn!("Failed to send permission response: {}", err);
+            }
+        });
+        Vec::new()
+    }
+
     fn send_opencode_question_response(
         &mut self,
         request_id: &str,
@@ -7588,8 +8716,34 @@ impl App {
     ) -> anyhow::Result<Vec<Effect>> {
         let mut effects = Vec::new();
 
+        if self.setup_blocks_tab(tab_index) {
+            self.defer_prompt_until_setup(
+                tab_index,
+                prompt,
+                images,
+                image_placeholders,
+                hidden,
+                stdin_payload,
+            );
+            return Ok(effects);
+        }
+
         if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
             Self::flush_pending_agent_output(session);
+            // A new prompt supersedes a scheduled crash restart or rate-limit retry
+            session.pending_restart = false;
+            session.rate_limit_wait = None;
+            if !hidden {
+                session.crash_restarts = 0;
+                session.rate_limit_retries = 0;
+                session.last_prompt = Some(prompt.clone());
+                session.last_prompt_images = images
+                    .iter()
+                    .cloned()
+                    .zip(image_placeholders.iter().cloned())
+                    .map(|(path, placeholder)| QueuedImageAttachment { path, placeholder })
+                    .collect();
+            }
         }
 
         // Extract session info in a limited borrow scope
@@ -7681,6 +8835,53 @@ impl App {
             return Ok(effects);
         }
 
+        let budget_status = match self.state.tab_manager.session(tab_index) {
+            Some(session) => {
+                BudgetService::status(&self.core, &Self::turn_usage_context(session), None)
+            }
+            None => BudgetStatus::Ok,
+        };
+        if budget_status.is_exceeded() {
+            if hidden {
+                // Follow-ups, goal turns and retries aren't the user's to
+                // resend, so say one was dropped rather than go quiet
+                if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
+                    let display = MessageDisplay::System {
+                        content: "Budget limit reached · automatic prompt blocked, not sent"
+                            .to_string(),
+                    };
+                    session.chat_view.push(display.to_chat_message());
+                }
+            } else if !display_prompt.trim().is_empty() || !images.is_empty() {
+                if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
+                    // Keep the prompt at the front of the queue instead of dropping it
+                    let images = images
+                        .into_iter()
+                        .zip(image_placeholders)
+                        .map(|(path, placeholder)| QueuedImageAttachment { path, placeholder })
+                        .collect();
+                    session.queued_messages.insert(
+                        0,
+                        QueuedMessage {
+                            id: Uuid::new_v4(),
+                            mode: QueuedMessageMode::FollowUp,
+                            text: display_prompt,
+                            images,
+                            created_at: Utc::now(),
+                        },
+                    );
+                    let display = MessageDisplay::System {
+                        content: "Budget limit reached · prompt blocked, kept in the queue"
+                            .to_string(),
+                    };
+                    session.chat_view.push(display.to_chat_message());
+                    session.update_status();
+                }
+            }
+            self.notify_budget_status(tab_index, budget_status);
+            return Ok(effects);
+        }
+
         // Capture original user message for title generation BEFORE agent-specific transformations
         // (e.g., Codex placeholder stripping, Claude image-path appends)
         let prompt_for_title = display_prompt.clone();
@@ -7704,7 +8905,11 @@ impl App {
         }
 
         // Start agent
-        if matches!(agent_type, AgentType::Gemini | AgentType::Opencode) && !images.is_empty() {
+        if matches!(
+            agent_type,
+            AgentType::Gemini | AgentType::Opencode | AgentType::Acp
+        ) && !images.is_empty()
+        {
             if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
                 session.stop_processing();
                 session.pending_user_message = None;
@@ -7718,6 +8923,10 @@ impl App {
                             "Image attachments aren't supported for OpenCode in Conduit yet."
                                 .to_string()
                         }
+                        AgentType::Acp => format!(
+                            "Image attachments aren't supported for {} in Conduit yet.",
+                            agent_type.display_name()
+                        ),
                         _ => "Image attachments aren't supported for this agent.".to_string(),
                     },
                 };
@@ -7732,11 +8941,20 @@ impl App {
         // Strip placeholders for agents that send images out-of-band.
         if matches!(
             agent_type,
-            AgentType::Codex | AgentType::Claude | AgentType::Gemini | AgentType::Opencode
+            AgentType::Codex
+                | AgentType::Claude
+                | AgentType::Gemini
+                | AgentType::Opencode
+                | AgentType::Acp
         ) {
             agent_prompt = Self::strip_image_placeholders(agent_prompt, &image_placeholders);
         }
 
+        // `@` mentions show as typed; the agent gets paths or file contents
+        if !hidden {
+            agent_prompt = self.expand_prompt_mentions(agent_type, &working_dir, &agent_prompt);
+        }
+
         if agent_prompt.trim().is_empty() && images.is_empty() && stdin_payload.is_none() {
             if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
                 session.stop_processing();
@@ -7791,6 +9009,18 @@ impl App {
             session.record_raw_event(EventDirection::Sent, "UserPrompt", debug_payload);
         }
 
+        // Snapshot the worktree before the agent can touch it
+        let checkpoint = self
+            .state
+            .tab_manager
+            .session(tab_index)
+            .and_then(|session| {
+                Self::checkpoint_for_turn(session, (!hidden).then(|| display_prompt.clone()))
+            });
+        if let Some(checkpoint) = checkpoint {
+            self.take_checkpoint(session_id, checkpoint);
+        }
+
         let mut use_stream_json = false;
         if agent_type == AgentType::Claude {
             use_stream_json = true;
@@ -7861,8 +9091,22 @@ impl App {
             agent_prompt.clone()
         };
 
+        let workspace_id = self
+            .state
+            .tab_manager
+            .session(tab_index)
+            .and_then(|session| session.workspace_id);
+        let event_hooks = self.core.event_hooks_for_workspace(workspace_id);
+        if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
+            session.event_hooks = event_hooks;
+        }
+        let approval_policy = self.core.approval_policy_for_workspace(workspace_id);
         let mut config = AgentStartConfig::new(prompt_for_agent, working_dir)
-            .with_tools(self.config().claude_allowed_tools.clone())
+            .with_tools(
+                self.core
+                    .claude_allowed_tools_for_workspace(workspace_id, approval_policy),
+            )
+            .with_tool_policy(self.core.tool_policy_for_workspace(workspace_id))
             .with_images(images)
             .with_agent_mode(agent_mode);
 
@@ -7873,6 +9117,18 @@ impl App {
         if let Some(effort) = reasoning_effort {
             config = config.with_reasoning_effort(effort);
         }
+        if agent_type == AgentType::Claude || agent_type.uses_acp() {
+            config = config.with_approval_policy(approval_policy);
+        }
+        if agent_type == AgentType::Codex {
+            let (approval, sandbox) = self.core.codex_permissions_for_workspace(workspace_id);
+            if let Some(session) = self.state.tab_manager.session(tab_index) {
+                config = config.with_codex_permissions(
+                    session.codex_approval.or(approval),
+                    session.codex_sandbox.or(sandbox),
+                );
+            }
+        }
 
         // Structured stdin payload (used for tool results / stream-json input)
         if let Some(payload) = stdin_payload {
@@ -7948,11 +9204,13 @@ impl App {
         let mut footer_message: Option<String> = None;
         let mut shell_command: Option<(Uuid, usize, String, Option<PathBuf>)> = None;
         let mut shell_error: Option<String> = None;
+        let mut race_launch: Option<(String, Vec<RaceContender>, bool)> = None;
         let mut queued_handled = false;
 
         // Extract config values before the mutable borrow
         let steer_behavior = self.config().steer.behavior;
         let steer_fallback = self.config().steer.fallback;
+        let goal_config = self.config().goal.clone();
 
         {
             let Some(session) = self.state.tab_manager.active_session_mut() else {
@@ -7968,6 +9226,8 @@ impl App {
             if submission.text.trim().is_empty() && submission.image_paths.is_empty() {
                 return Ok(effects);
             }
+            // The user is back in the loop; hooks may send follow-ups again
+            session.hook_feedback_rounds = 0;
 
             let submission_text = submission.text;
             let submission_image_paths = submission.image_paths;
@@ -7999,6 +9259,29 @@ impl App {
                 queued_handled = true;
             }
 
+            if !queued_handled {
+                if let Some(contenders) = session.pending_race.take() {
+                    session.update_status();
+                    let prompt = Self::strip_image_placeholders(
+                        submission_text.clone(),
+                        &submission_image_placeholders,
+                    );
+                    race_launch = Some((prompt, contenders, !submission_image_paths.is_empty()));
+                    queued_handled = true;
+                }
+            }
+
+            if !queued_handled {
+                // An armed goal loop takes this prompt as its goal; it is sent as usual
+                if let Some(command) = session.pending_goal.take() {
+                    let goal = Self::strip_image_placeholders(
+                        submission_text.clone(),
+                        &submission_image_placeholders,
+                    );
+                    session.goal = Some(GoalLoop::new(goal, command, &goal_config));
+                }
+            }
+
             if !queued_handled {
                 let effective_mode = if mode == QueuedMessageMode::Steer
                     && steer_behavior == crate::config::SteerBehavior::Soft
@@ -8086,6 +9369,17 @@ impl App {
             return Ok(effects);
         }
 
+        if let Some((prompt, contenders, had_images)) = race_launch {
+            if had_images {
+                self.state.set_timed_footer_message(
+                    "Images are not sent to race contenders".to_string(),
+                    Duration::from_secs(3),
+                );
+            }
+            effects.extend(self.start_race(prompt, contenders));
+            return Ok(effects);
+        }
+
         if let Some(message) = footer_message {
             self.state
                 .set_timed_footer_message(message, Duration::from_secs(3));
@@ -8216,7 +9510,12 @@ impl App {
     ) -> anyhow::Result<()> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(
+            stdout,
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableFocusChange
+        )?;
         terminal.clear()?;
         Ok(())
     }
@@ -8335,17 +9634,7 @@ impl App {
 
     /// Initiate handoff flow - capture source context and open model selection.
     fn initiate_handoff_session(&mut self) {
-        let (
-            source_agent_type,
-            source_agent_mode,
-            source_reasoning_effort,
-            workspace_id,
-            working_dir,
-            project_name,
-            workspace_name,
-            pr_number,
-            handoff_prompt,
-        ) = {
+        let request = {
             let Some(session) = self.state.tab_manager.active_session() else {
                 self.state.set_timed_footer_message(
                     "No active session to hand off".to_string(),
@@ -8359,31 +9648,12 @@ impl App {
                 return;
             }
 
-            (
-                session.agent_type,
-                session.agent_mode,
-                session.reasoning_effort,
-                session.workspace_id,
-                session.working_dir.clone(),
-                session.project_name.clone(),
-                session.workspace_name.clone(),
-                session.pr_number,
-                app_prompt::build_handoff_prompt(session.chat_view.messages()),
-            )
+            Self::handoff_request(session)
         };
+        let source_agent_type = request.source_agent_type;
 
         self.state.close_overlays();
-        self.state.pending_handoff_request = Some(PendingHandoffRequest {
-            source_agent_type,
-            agent_mode: source_agent_mode,
-            reasoning_effort: source_reasoning_effort,
-            workspace_id,
-            working_dir,
-            project_name,
-            workspace_name,
-            pr_number,
-            handoff_prompt: Arc::from(handoff_prompt),
-        });
+        self.state.pending_handoff_request = Some(request);
 
         let mut allowed = self.config().effective_enabled_providers(self.tools());
         if !allowed.contains(&source_agent_type) {
@@ -8809,6 +10079,7 @@ impl App {
         if let Some(ref tracker) = self.git_tracker {
             tracker.untrack_workspace(workspace_id);
         }
+        self.state.workspace_setups.remove(&workspace_id);
 
         let workspace_dao = self.workspace_dao()?;
         let repo_dao = self.repo_dao()?;
@@ -9354,6 +10625,10 @@ impl App {
 
     fn drain_queue_for_tab(&mut self, tab_index: usize) -> anyhow::Result<Vec<Effect>> {
         let mut effects = Vec::new();
+        // Queued prompts start once workspace setup succeeds
+        if self.setup_blocks_tab(tab_index) {
+            return Ok(effects);
+        }
         let mut queued: Vec<QueuedMessage> = Vec::new();
         let (queue_mode, queue_delivery) = (self.config().queue.mode, self.config().queue.delivery);
 
@@ -9490,6 +10765,8 @@ impl App {
         // Check if active tab is a file viewer - render it separately
         if self.state.tab_manager.active_is_file() {
             self.render_file_viewer_tab(content_area, footer_area, f);
+        } else if self.state.tab_manager.active_is_diff() {
+            self.render_diff_viewer_tab(content_area, footer_area, f);
         } else {
             match self.state.view_mode {
                 ViewMode::Chat => {
@@ -9595,6 +10872,9 @@ impl App {
                         } else if self.state.session_import_state.is_visible() {
                             let picker = SessionImportPicker::new();
                             picker.render(size, f.buffer_mut(), &self.state.session_import_state);
+                        } else if self.state.history_search_state.is_visible() {
+                            let picker = HistorySearchPicker::new();
+                            picker.render(size, f.buffer_mut(), &self.state.history_search_state);
                         } else if self.state.model_selector_state.is_visible() {
                             self.state.model_selector_state.update_viewport(size);
                             let selector = ModelSelector::new();
@@ -9606,6 +10886,13 @@ impl App {
                                 f.buffer_mut(),
                                 &self.state.reasoning_selector_state,
                             );
+                        } else if self.state.codex_permissions_state.is_visible() {
+                            let selector = CodexPermissionsSelector::new();
+                            selector.render(
+                                size,
+                                f.buffer_mut(),
+                                &self.state.codex_permissions_state,
+                            );
                         } else if self.state.theme_picker_state.is_visible() {
                             self.render_theme_picker(size, f.buffer_mut());
                         }
@@ -9631,6 +10918,13 @@ impl App {
                             dialog.render(size, f.buffer_mut());
                         }
 
+                        // Draw usage dashboard if open
+                        if self.state.usage_dialog_state.is_visible() {
+                            use ratatui::widgets::Widget;
+                            UsageDialog::new(&self.state.usage_dialog_state)
+                                .render(size, f.buffer_mut());
+                        }
+
                         // Draw missing tool dialog if open
                         if self.state.missing_tool_dialog_state.is_visible() {
                             use ratatui::widgets::Widget;
@@ -9819,19 +11113,55 @@ impl App {
                     tab_bar.render(tab_bar_chunk, f.buffer_mut());
 
                     // Draw session header (below tab bar)
-                    let session_title = self
-                        .state
-                        .tab_manager
-                        .active_session()
-                        .and_then(|s| s.title.as_deref());
-                    SessionHeader::new(session_title).render(header_chunk, f.buffer_mut());
+                    let active_session = self.state.tab_manager.active_session();
+                    let session_title = active_session.and_then(|s| s.title.as_deref());
+                    let goal = active_session.and_then(|s| s.goal.as_ref());
+                    SessionHeader::new(session_title)
+                        .goal(goal)
+                        .render(header_chunk, f.buffer_mut());
 
                     // Draw active session components
                     let is_command_mode = self.state.input_mode == InputMode::Command;
                     let show_chat_scrollbar = self.config().ui.show_chat_scrollbar;
                     if let Some(session) = self.state.tab_manager.active_session_mut() {
-                        // Use full chat area - prompt is now rendered as part of scrollable content
-                        let chat_area = chat_chunk;
+                        // Use full chat area - prompt is now rendered as part of scrollable content.
+                        // Workspace setup output sits below the chat until setup succeeds.
+                        let setup = session
+                            .workspace_id
+                            .and_then(|id| self.state.workspace_setups.get(&id));
+                        let (chat_area, setup_area) = match setup {
+                            Some(setup) if chat_chunk.height > setup.height() + 5 => {
+                                let split = Layout::default()
+                                    .direction(Direction::Vertical)
+                                    .constraints([
+                                        Constraint::Min(5),
+                                        Constraint::Length(setup.height()),
+                                    ])
+                                    .split(chat_chunk);
+                                let setup_area = Rect {
+                                    x: split[1].x + INPUT_MARGIN_LEFT,
+                                    width: split[1].width.saturating_sub(input_total_margin),
+                                    ..split[1]
+                                };
+                                (split[0], Some((setup, setup_area)))
+                            }
+                            _ => (chat_chunk, None),
+                        };
+                        if let Some((setup, area)) = setup_area {
+                            SetupPane::new(setup).render(area, f.buffer_mut());
+                        }
+
+                        // The search bar takes the chat's bottom row
+                        let searching = self.state.input_mode == InputMode::SearchingChat;
+                        let (chat_area, search_area) = if searching && chat_area.height > 3 {
+                            let split = Layout::default()
+                                .direction(Direction::Vertical)
+                                .constraints([Constraint::Min(2), Constraint::Length(1)])
+                                .split(chat_area);
+                            (split[0], Some(split[1]))
+                        } else {
+                            (chat_area, None)
+                        };
 
                         self.state.chat_area = if chat_area.height == 0 {
                             None
@@ -9865,6 +11195,14 @@ impl App {
                             show_chat_scrollbar,
                         );
 
+                        if let Some(area) = search_area {
+                            ChatSearchBar::new(
+                                &self.state.chat_search_input,
+                                session.chat_view.search_status(),
+                            )
+                            .render(area, f.buffer_mut());
+                        }
+
                         // Check if inline prompt is active
                         let has_inline_prompt = session.inline_prompt.is_some();
 
@@ -9922,6 +11260,9 @@ impl App {
                     if self.state.slash_menu_state.is_visible() && !has_inline_prompt {
                         self.render_slash_menu(chat_chunk, input_area_inner, f.buffer_mut());
                     }
+                    if self.state.mention_picker_state.is_visible() && !has_inline_prompt {
+                        self.render_mention_picker(chat_chunk, input_area_inner, f.buffer_mut());
+                    }
 
                     // Draw footer (full width) - context-aware based on input mode
                     let footer = GlobalFooter::from_state(
@@ -9965,12 +11306,12 @@ impl App {
                     tab_bar.render(tab_bar_chunk, f.buffer_mut());
 
                     // Draw session header (below tab bar) - consistent with Chat view
-                    let session_title = self
-                        .state
-                        .tab_manager
-                        .active_session()
-                        .and_then(|s| s.title.as_deref());
-                    SessionHeader::new(session_title).render(header_chunk, f.buffer_mut());
+                    let active_session = self.state.tab_manager.active_session();
+                    let session_title = active_session.and_then(|s| s.title.as_deref());
+                    let goal = active_session.and_then(|s| s.goal.as_ref());
+                    SessionHeader::new(session_title)
+                        .goal(goal)
+                        .render(header_chunk, f.buffer_mut());
 
                     // Draw raw events view
                     if let Some(session) = self.state.tab_manager.active_session_mut() {
@@ -10007,6 +11348,11 @@ impl App {
             );
         }
 
+        if self.state.race_selector_state.is_visible() {
+            let selector = RaceSelector::new();
+            selector.render(size, f.buffer_mut(), &self.state.race_selector_state.dialog);
+        }
+
         // Draw add repository dialog if open
         if self.state.add_repo_dialog_state.is_visible() {
             let dialog = AddRepoDialog::new();
@@ -10025,6 +11371,11 @@ impl App {
             selector.render(size, f.buffer_mut(), &self.state.reasoning_selector_state);
         }
 
+        if self.state.codex_permissions_state.is_visible() {
+            let selector = CodexPermissionsSelector::new();
+            selector.render(size, f.buffer_mut(), &self.state.codex_permissions_state);
+        }
+
         // Draw theme picker dialog if open
         self.render_theme_picker(size, f.buffer_mut());
 
@@ -10046,6 +11397,34 @@ impl App {
             picker.render(size, f.buffer_mut(), &self.state.session_import_state);
         }
 
+        // Draw history search picker if open
+        if self.state.history_search_state.is_visible() {
+            let picker = HistorySearchPicker::new();
+            picker.render(size, f.buffer_mut(), &self.state.history_search_state);
+        }
+
+        // Draw hunk comment dialog if open
+        if self.state.diff_comment_dialog_state.is_visible() {
+            DiffCommentDialog::new().render(
+                size,
+                f.buffer_mut(),
+                &self.state.diff_comment_dialog_state,
+            );
+        }
+
+        // Draw goal dialog if open
+        if self.state.goal_dialog_state.is_visible() {
+            GoalDialog::new().render(size, f.buffer_mut(), &self.state.goal_dialog_state);
+        }
+
+        if self.state.prompt_variables_dialog_state.is_visible() {
+            PromptVariablesDialog::new().render(
+                size,
+                f.buffer_mut(),
+                &self.state.prompt_variables_dialog_state,
+            );
+        }
+
         // Draw confirmation dialog if open
         if self.state.confirmation_dialog_state.visible {
             use ratatui::widgets::Widget;
@@ -10060,6 +11439,30 @@ impl App {
             dialog.render(size, f.buffer_mut());
         }
 
+        // Draw usage dashboard
+        if self.state.usage_dialog_state.is_visible() {
+            use ratatui::widgets::Widget;
+            UsageDialog::new(&self.state.usage_dialog_state).render(size, f.buffer_mut());
+        }
+
+        // Draw race comparison
+        if self.state.race_dialog_state.is_visible() {
+            use ratatui::widgets::Widget;
+            RaceDialog::new(&self.state.race_dialog_state).render(size, f.buffer_mut());
+        }
+
+        // Draw checkpoint timeline
+        if self.state.checkpoint_dialog_state.is_visible() {
+            use ratatui::widgets::Widget;
+            CheckpointDialog::new(&self.state.checkpoint_dialog_state).render(size, f.buffer_mut());
+        }
+
+        // Draw scheduled prompts
+        if self.state.schedule_dialog_state.is_visible() {
+            use ratatui::widgets::Widget;
+            ScheduleDialog::new(&self.state.schedule_dialog_state).render(size, f.buffer_mut());
+        }
+
         // Draw missing tool dialog (on top of everything except spinner)
         if self.state.missing_tool_dialog_state.is_visible() {
             use ratatui::widgets::Widget;
@@ -10265,6 +11668,81 @@ impl App {
         footer.render(footer_area, f.buffer_mut());
     }
 
+    fn render_diff_viewer_tab(
+        &mut self,
+        content_area: Rect,
+        footer_area: Rect,
+        f: &mut ratatui::Frame<'_>,
+    ) {
+        use crate::ui::components::{DiffViewerView, FooterContext, GlobalFooter};
+        use unicode_width::UnicodeWidthStr;
+
+        let is_command_mode = self.state.input_mode == InputMode::Command;
+
+        // Layout: tab bar, diff (header + rows) (+ optional command prompt)
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(if is_command_mode {
+                vec![
+                    Constraint::Length(1), // Tab bar
+                    Constraint::Min(3),    // Diff
+                    Constraint::Length(3), // Command prompt
+                ]
+            } else {
+                vec![
+                    Constraint::Length(1), // Tab bar
+                    Constraint::Min(5),    // Diff
+                ]
+            })
+            .split(content_area);
+
+        let tab_bar_chunk = chunks[0];
+        let content_chunk = chunks[1];
+        let command_chunk = if is_command_mode {
+            Some(chunks[2])
+        } else {
+            None
+        };
+
+        // Store areas for mouse hit-testing
+        self.state.tab_bar_area = Some(tab_bar_chunk);
+        self.state.chat_area = None;
+        self.state.file_viewer_area = Some(content_chunk);
+        self.state.raw_events_area = None;
+        self.state.input_area = command_chunk;
+        self.state.status_bar_area = None;
+        self.state.footer_area = Some(footer_area);
+
+        let tabs_focused = self.state.input_mode != InputMode::SidebarNavigation;
+        self.ensure_tab_bar_scroll(tab_bar_chunk.width, tabs_focused);
+        let tab_bar = self.build_tab_bar(tabs_focused);
+        tab_bar.render(tab_bar_chunk, f.buffer_mut());
+
+        if let Some(viewer) = self.state.tab_manager.active_diff_viewer() {
+            DiffViewerView::new(viewer).render(content_chunk, f.buffer_mut());
+        }
+
+        if let Some(cmd_area) = command_chunk {
+            self.render_command_prompt(cmd_area, f.buffer_mut());
+            let prompt = format!("  cmd › {}", self.state.command_buffer);
+            let prompt_width = UnicodeWidthStr::width(prompt.as_str()) as u16;
+            let max_x = cmd_area.x + cmd_area.width.saturating_sub(1);
+            let cx = (cmd_area.x + prompt_width).min(max_x);
+            let cy = cmd_area.y + 1;
+            f.set_cursor_position((cx, cy));
+        }
+
+        let footer_context = if self.state.input_mode == InputMode::SidebarNavigation {
+            FooterContext::Sidebar
+        } else {
+            FooterContext::DiffViewer
+        };
+        let footer = GlobalFooter::for_context(footer_context)
+            .with_spinner(self.state.footer_spinner.as_ref())
+            .with_message(self.state.footer_message.as_deref());
+        footer.render(footer_area, f.buffer_mut());
+    }
+
     fn render_theme_picker(&mut self, size: Rect, buf: &mut ratatui::buffer::Buffer) {
         if !self.state.theme_picker_state.is_visible() {
             return;
@@ -10385,6 +11863,35 @@ impl App {
         SlashMenu::new().render(menu_area, buf, &self.state.slash_menu_state);
     }
 
+    fn render_mention_picker(
+        &mut self,
+        chat_area: Rect,
+        input_area: Rect,
+        buf: &mut ratatui::buffer::Buffer,
+    ) {
+        let available_height = input_area.y.saturating_sub(chat_area.y);
+        let list_height_max = available_height.saturating_sub(4);
+        if list_height_max == 0 {
+            return;
+        }
+
+        let list_len = self.state.mention_picker_state.filtered_len().clamp(1, 10);
+        let list_height = list_len.min(list_height_max as usize) as u16;
+        self.state
+            .mention_picker_state
+            .set_max_visible(list_height as usize);
+
+        let menu_height = list_height.saturating_add(4);
+        let menu_area = Rect {
+            x: input_area.x,
+            y: input_area.y.saturating_sub(menu_height),
+            width: input_area.width,
+            height: menu_height,
+        };
+
+        MentionPicker::new().render(menu_area, buf, &self.state.mention_picker_state);
+    }
+
     fn find_latest_plan_file(session: &AgentSession) -> Option<std::path::PathBuf> {
         let mut candidates = Vec::new();
         if let Some(home_dir) = dirs::home_dir() {
@@ -10458,6 +11965,89 @@ impl App {
         None
     }
 
+    /// Export the active session's transcript under the data directory.
+    fn export_transcript(&self, format: TranscriptFormat) -> Result<String, String> {
+        let session = self
+            .state
+            .tab_manager
+            .active_session()
+            .ok_or_else(|| "No active session to export".to_string())?;
+        let messages = session.chat_view.messages();
+        if messages.is_empty() {
+            return Err("Session has no messages to export".to_string());
+        }
+
+        let title = session
+            .title
+            .clone()
+            .or_else(|| session.workspace_name.clone())
+            .unwrap_or_else(|| "Conduit session".to_string());
+        let mut meta = TranscriptMeta::new(&title, session.agent_type);
+        meta.model = session.model.clone();
+        meta.workspace = session.workspace_name.clone();
+
+        let content = render_transcript(messages, &meta, format);
+        let workspace = session.workspace_name.as_deref().unwrap_or("no-workspace");
+        let path = export_path(workspace, &title, format);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Could not create export directory: {}", e))?;
+        }
+        std::fs::write(&path, content).map_err(|e| format!("Could not write transcript: {}", e))?;
+
+        Ok(path.display().to_string())
+    }
+
+    fn turn_usage_context(session: &AgentSession) -> TurnUsageContext {
+        TurnUsageContext {
+            session_id: Some(session.id),
+            agent_session_id: session.agent_session_id.as_ref().map(|id| id.to_string()),
+            agent_type: session.agent_type,
+            model: session.model.clone(),
+            workspace_id: session.workspace_id,
+        }
+    }
+
+    /// Surface a budget warning once per session, and every hard-limit stop
+    fn notify_budget_status(&mut self, tab_index: usize, status: BudgetStatus) {
+        let is_active_tab = self.state.tab_manager.active_index() == tab_index;
+        let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
+            return;
+        };
+        match (status, status.message()) {
+            (BudgetStatus::Warning(_), Some(message)) => {
+                if !session.budget_warning_shown {
+                    session.budget_warning_shown = true;
+                    let display = MessageDisplay::System {
+                        content: format!("⚠️ {}", message),
+                    };
+                    session.chat_view.push(display.to_chat_message());
+                }
+            }
+            (BudgetStatus::Exceeded(_), Some(message)) => {
+                let display = MessageDisplay::Error {
+                    content: message.clone(),
+                };
+                session.chat_view.push(display.to_chat_message());
+                if is_active_tab {
+                    self.show_error("Budget limit reached", &message);
+                } else {
+                    session.needs_attention = true;
+                }
+            }
+            _ => session.budget_warning_shown = false,
+        }
+    }
+
+    /// Load the usage dashboard rows for the dashboard's time window.
+    pub(super) fn load_usage_summary(
+        &self,
+        group_by: UsageGroupBy,
+    ) -> Result<Vec<UsageSummaryRow>, String> {
+        let since = Utc::now() - chrono::Duration::days(USAGE_DASHBOARD_DAYS);
+        UsageService::summarize(&self.core, Some(since), group_by).map_err(|e| e.to_string())
+    }
+
     /// Dump complete app state to a JSON file for debugging.
     fn dump_debug_state(&self) -> Result<String, String> {
         use chrono::Local;
@@ -10619,6 +12209,78 @@ impl SessionPersistenceReport {
     }
 }
 
+/// Create a workspace with a generated name and branch for `repo_id`.
+/// Blocking: runs git worktree commands.
+fn create_workspace_blocking(
+    repo_dao: Option<RepositoryStore>,
+    workspace_dao: Option<WorkspaceStore>,
+    worktree_manager: &WorkspaceRepoManager,
+    config: &Config,
+    repo_id: uuid::Uuid,
+) -> Result<WorkspaceCreated, String> {
+    let repo_dao = repo_dao.ok_or_else(|| "No repository DAO available".to_string())?;
+    let workspace_dao = workspace_dao.ok_or_else(|| "No workspace DAO available".to_string())?;
+
+    let repo = repo_dao
+        .get_by_id(repo_id)
+        .map_err(|e| format!("Failed to load repository: {}", e))?
+        .ok_or_else(|| "Repository not found".to_string())?;
+
+    let base_path = repo
+        .base_path
+        .clone()
+        .ok_or_else(|| "Repository has no base path".to_string())?;
+    let settings = resolve_repo_workspace_settings(config, &repo);
+
+    // Get ALL workspace names (including archived) to prevent resurrection
+    // of old workspace names when creating new ones
+    let existing_names: Vec<String> = workspace_dao
+        .get_all_names_by_repository(repo_id)
+        .unwrap_or_default();
+
+    let workspace_name = crate::util::generate_workspace_name(&existing_names);
+    let username = crate::util::get_git_username();
+    let branch_name = crate::util::generate_branch_name(&username, &workspace_name);
+
+    let worktree_path = worktree_manager
+        .create_workspace(settings.mode, &base_path, &branch_name, &workspace_name)
+        .map_err(|e| format!("Failed to create workspace: {}", e))?;
+
+    let workspace =
+        crate::data::Workspace::new(repo_id, &workspace_name, &branch_name, worktree_path);
+    let workspace_id = workspace.id;
+
+    if let Err(e) = workspace_dao.create(&workspace) {
+        if let Err(cleanup_err) =
+            worktree_manager.remove_workspace(settings.mode, &base_path, &workspace.path)
+        {
+            tracing::error!(
+                error = %cleanup_err,
+                base_path = %base_path.display(),
+                workspace_path = %workspace.path.display(),
+                "Failed to clean up workspace after DB error"
+            );
+        }
+        if let Err(branch_err) =
+            worktree_manager.delete_branch(settings.mode, &base_path, &workspace.path, &branch_name)
+        {
+            tracing::error!(
+                error = %branch_err,
+                base_path = %base_path.display(),
+                workspace_path = %workspace.path.display(),
+                branch = %branch_name,
+                "Failed to delete branch after DB error"
+            );
+        }
+        return Err(format!("Failed to save workspace to database: {}", e));
+    }
+
+    Ok(WorkspaceCreated {
+        repo_id,
+        workspace_id,
+    })
+}
+
 /// Async helper for generating title and branch name
 async fn generate_title_and_branch_impl(
     tools: ToolAvailability,
@@ -10811,6 +12473,7 @@ mod tests {
     use crate::agent::{AgentType, ModelRegistry, ReasoningEffort};
     use crate::config::Config;
     use crate::data::{QueuedMessage, QueuedMessageMode};
+    use crate::ui::app_state::PendingHandoffRequest;
     use crate::ui::components::MessageRole;
     use crate::ui::session::AgentSession;
     use crate::util::{Tool, ToolAvailability};
@@ -10894,7 +12557,7 @@ mod tests {
             .map(|m| m.display_name.to_string())
             .unwrap_or(model_id);
         let model_width = model_display.len();
-        let agent_width = session.agent_type.display_name().len();
+        let agent_width = session.agent_name().len();
 
         let leading: usize = 2;
         let relative_x = if show_mode {
@@ -11186,6 +12849,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_at_triggers_mention_picker_at_word_start() {
+        for modifiers in [KeyModifiers::NONE, KeyModifiers::SHIFT] {
+            assert!(App::should_trigger_mention_picker(
+                KeyCode::Char('@'),
+                modifiers,
+                InputMode::Normal,
+                true,
+                false,
+                false,
+            ));
+        }
+        // Mid-word, e.g. an email address
+        assert!(!App::should_trigger_mention_picker(
+            KeyCode::Char('@'),
+            KeyModifiers::NONE,
+            InputMode::Normal,
+            false,
+            false,
+            false,
+        ));
+        // Shell commands take `@` literally
+        assert!(!App::should_trigger_mention_picker(
+            KeyCode::Char('@'),
+            KeyModifiers::NONE,
+            InputMode::Normal,
+            true,
+            true,
+            false,
+        ));
+    }
+
     #[test]
     fn test_slash_command_action_maps_fork_to_fork_session() {
         assert_eq!(
@@ -11194,6 +12889,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_slash_command_action_maps_race_to_start_race() {
+        assert_eq!(
+            App::slash_command_action(SlashCommand::Race),
+            Some(Action::StartRace)
+        );
+    }
+
+    #[test]
+    fn test_slash_command_action_maps_diff_to_open_diff_review() {
+        assert_eq!(
+            App::slash_command_action(SlashCommand::Diff),
+            Some(Action::OpenDiffReview)
+        );
+    }
+
+    #[test]
+    fn test_slash_command_action_maps_checkpoints_to_show_checkpoints() {
+        assert_eq!(
+            App::slash_command_action(SlashCommand::Checkpoints),
+            Some(Action::ShowCheckpoints)
+        );
+    }
+
+    #[test]
+    fn test_slash_command_action_maps_schedules_to_show_schedules() {
+        assert_eq!(
+            App::slash_command_action(SlashCommand::Schedules),
+            Some(Action::ShowSchedules)
+        );
+    }
+
+    #[test]
+    fn test_slash_command_action_maps_setup_to_run_workspace_setup() {
+        assert_eq!(
+            App::slash_command_action(SlashCommand::Setup),
+            Some(Action::RunWorkspaceSetup)
+        );
+    }
+
+    #[test]
+    fn test_slash_command_action_maps_goal_to_start_goal() {
+        assert_eq!(
+            App::slash_command_action(SlashCommand::Goal),
+            Some(Action::StartGoal)
+        );
+    }
+
+    #[test]
+    fn test_slash_command_action_maps_search_to_search_chat() {
+        assert_eq!(
+            App::slash_command_action(SlashCommand::Search),
+            Some(Action::SearchChat)
+        );
+    }
+
+    #[test]
+    fn test_slash_command_action_maps_history_to_search_history() {
+        assert_eq!(
+            App::slash_command_action(SlashCommand::History),
+            Some(Action::SearchHistory)
+        );
+    }
+
+    #[test]
+    fn test_slash_command_action_leaves_prompt_templates_to_the_library() {
+        assert_eq!(App::slash_command_action(SlashCommand::Prompt(0)), None);
+    }
+
+    #[test]
+    fn test_slash_command_action_leaves_user_commands_to_the_config() {
+        assert_eq!(App::slash_command_action(SlashCommand::User(0)), None);
+    }
+
     #[test]
     fn test_slash_command_action_maps_handoff_when_present() {
         let mut slash_state = crate::ui::components::SlashMenuState::new();
@@ -12818,4 +14587,93 @@ mod tests {
             .content
             .contains("Switching agent type after a session has started"));
     }
+
+    #[test]
+    fn test_crash_restart_plan_backs_off_then_gives_up() {
+        let policy = crate::agent::RestartPolicy {
+            max_restarts: 2,
+            ..Default::default()
+        };
+        let crash = ProcessExit {
+            exit_code: Some(1),
+            signal: None,
+            stderr_tail: "panic".to_string(),
+        };
+        let mut session = AgentSession::new(AgentType::Claude);
+        session.agent_session_id = Some(SessionId::from_string("resume-me"));
+
+        // Not mid-turn: nothing to resume
+        assert_eq!(App::plan_crash_restart(&policy, &mut session, &crash), None);
+
+        session.is_processing = true;
+        session.agent_pid = Some(42);
+        assert_eq!(
+            App::plan_crash_restart(&policy, &mut session, &crash),
+            Some(Duration::from_secs(2))
+        );
+        assert!(session.pending_restart);
+        assert_eq!(
+            App::plan_crash_restart(&policy, &mut session, &crash),
+            Some(Duration::from_secs(4))
+        );
+        assert_eq!(App::plan_crash_restart(&policy, &mut session, &crash), None);
+        assert_eq!(session.crash_restarts, 0);
+        let last = session
+            .chat_view
+            .messages()
+            .last()
+            .expect("expected notice");
+        assert!(last.content.contains("giving up"));
+
+        // A user stop clears the PID first, so the exit is not treated as a crash
+        session.agent_pid = None;
+        assert_eq!(App::plan_crash_restart(&policy, &mut session, &crash), None);
+    }
+
+    #[test]
+    fn test_rate_limit_retry_waits_then_gives_up() {
+        let mut config = Config::default().rate_limit;
+        config.max_retries = 1;
+        let mut session = AgentSession::new(AgentType::Claude);
+
+        // Nothing to resend yet
+        assert_eq!(
+            App::plan_rate_limit_retry(&config, &mut session, ProviderErrorKind::RateLimited, None),
+            None
+        );
+
+        session.last_prompt = Some("fix the build".to_string());
+        session.rate_limit_retries = 0;
+        assert_eq!(
+            App::plan_rate_limit_retry(
+                &config,
+                &mut session,
+                ProviderErrorKind::Overloaded,
+                Some(Duration::from_secs(15)),
+            ),
+            Some(Duration::from_secs(15))
+        );
+        let wait = session.rate_limit_wait.expect("expected wait");
+        assert_eq!(wait.kind, ProviderErrorKind::Overloaded);
+        assert!(wait.retry_at.is_some());
+
+        // Retries used up: the wait is shown without a countdown
+        assert_eq!(
+            App::plan_rate_limit_retry(&config, &mut session, ProviderErrorKind::RateLimited, None),
+            None
+        );
+        let wait = session.rate_limit_wait.expect("expected wait");
+        assert!(wait.retry_at.is_none());
+        assert_eq!(session.rate_limit_retries, 0);
+
+        // Auth errors only leave a hint
+        session.rate_limit_wait = None;
+        assert_eq!(
+            App::plan_rate_limit_retry(&config, &mut session, ProviderErrorKind::AuthExpired, None),
+            None
+        );
+        assert!(session.rate_limit_wait.is_none());
+        let last = session.chat_view.messages().last().expect("expected hint");
+        assert!(last.content.contains("log in again"));
+    }
 }
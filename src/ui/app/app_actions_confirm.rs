@@ -1,4 +1,5 @@
 use crate::agent::MessageDisplay;
+use crate::git::HunkAction;
 use crate::ui::app::App;
 use crate::ui::app_state::ModelPickerContext;
 use crate::ui::components::ConfirmationContext;
@@ -137,6 +138,9 @@ impl App {
                 self.state.reasoning_selector_state.hide();
                 self.state.input_mode = InputMode::Normal;
             }
+            InputMode::SelectingCodexPermissions => {
+                self.apply_codex_permissions_selection();
+            }
             InputMode::SelectingTheme => {
                 effects.extend(self.confirm_theme_picker()?);
             }
@@ -172,6 +176,9 @@ impl App {
                     self.state.input_mode = InputMode::Normal;
                 }
             }
+            InputMode::SelectingRaceContenders => {
+                self.confirm_race_contenders();
+            }
             InputMode::PickingProject => {
                 if let Some(project) = self.state.project_picker_state.selected_project() {
                     let repo_id = self.add_project_to_sidebar(project.path.clone());
@@ -318,6 +325,14 @@ impl App {
                                 return Ok(());
                             }
                         }
+                        ConfirmationContext::RateLimitFallback { session_id } => {
+                            if self.state.confirmation_dialog_state.is_confirm_selected() {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::Normal;
+                                effects.extend(self.confirm_rate_limit_fallback(session_id)?);
+                                return Ok(());
+                            }
+                        }
                         ConfirmationContext::ForkSession {
                             parent_workspace_id,
                             base_branch,
@@ -336,6 +351,41 @@ impl App {
                         ConfirmationContext::ForkSessionPreflightInProgress { .. } => {
                             return Ok(());
                         }
+                        ConfirmationContext::RevertDiffHunk {
+                            tab_id,
+                            working_dir,
+                            patch,
+                        } => {
+                            if self.state.confirmation_dialog_state.is_confirm_selected() {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::FileViewer;
+                                effects.push(Effect::ApplyDiffHunk {
+                                    tab_id,
+                                    working_dir,
+                                    patch,
+                                    action: HunkAction::Revert,
+                                });
+                                return Ok(());
+                            }
+                        }
+                        ConfirmationContext::RestoreCheckpoint {
+                            workspace_id,
+                            working_dir,
+                            commit,
+                            turn,
+                        } => {
+                            if self.state.confirmation_dialog_state.is_confirm_selected() {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = self.checkpoint_return_mode();
+                                effects.push(Effect::RestoreCheckpoint {
+                                    workspace_id,
+                                    working_dir,
+                                    commit,
+                                    turn,
+                                });
+                                return Ok(());
+                            }
+                        }
                     }
                 }
                 // Cancel selected - dismiss the confirmation dialog
@@ -345,6 +395,34 @@ impl App {
                 self.state.error_dialog_state.hide();
                 self.state.input_mode = InputMode::Normal;
             }
+            InputMode::ShowingUsage => {
+                self.state.usage_dialog_state.hide();
+                self.state.input_mode = InputMode::Normal;
+            }
+            InputMode::ShowingRaceResults => {
+                effects.extend(self.keep_race_contender());
+            }
+            InputMode::ShowingCheckpoints => {
+                self.confirm_checkpoint_restore();
+            }
+            InputMode::ShowingSchedules => {
+                effects.extend(self.confirm_schedule_dialog()?);
+            }
+            InputMode::CommentingHunk => {
+                effects.extend(self.send_hunk_comment()?);
+            }
+            InputMode::SettingGoal => {
+                self.confirm_goal_command();
+            }
+            InputMode::FillingPromptVariables => {
+                effects.extend(self.confirm_prompt_variable()?);
+            }
+            InputMode::SearchingHistory => {
+                effects.extend(self.confirm_history_search());
+            }
+            InputMode::PickingMention => {
+                self.confirm_mention_picker();
+            }
             InputMode::MissingTool => {
                 // Validate and save the path
                 if let Some(result) = self.state.missing_tool_dialog_state.validate() {
@@ -1,3 +1,4 @@
+use crate::git::HunkAction;
 use crate::ui::action::Action;
 use crate::ui::app::App;
 use crate::ui::components::ConfirmationContext;
@@ -80,6 +81,11 @@ impl App {
                                 self.state.input_mode = InputMode::Normal;
                                 effects.extend(self.confirm_steer_fallback(message_id)?);
                             }
+                            ConfirmationContext::RateLimitFallback { session_id } => {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::Normal;
+                                effects.extend(self.confirm_rate_limit_fallback(session_id)?);
+                            }
                             ConfirmationContext::ForkSession {
                                 parent_workspace_id,
                                 base_branch,
@@ -95,6 +101,35 @@ impl App {
                             ConfirmationContext::ForkSessionPreflightInProgress { .. } => {
                                 return Ok(());
                             }
+                            ConfirmationContext::RevertDiffHunk {
+                                tab_id,
+                                working_dir,
+                                patch,
+                            } => {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::FileViewer;
+                                effects.push(Effect::ApplyDiffHunk {
+                                    tab_id,
+                                    working_dir,
+                                    patch,
+                                    action: HunkAction::Revert,
+                                });
+                            }
+                            ConfirmationContext::RestoreCheckpoint {
+                                workspace_id,
+                                working_dir,
+                                commit,
+                                turn,
+                            } => {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = self.checkpoint_return_mode();
+                                effects.push(Effect::RestoreCheckpoint {
+                                    workspace_id,
+                                    working_dir,
+                                    commit,
+                                    turn,
+                                });
+                            }
                         }
                     }
                 }
@@ -155,6 +190,12 @@ impl App {
                         return Ok(());
                     }
                     self.state.confirmation_dialog_state.toggle_selection();
+                } else if self.state.input_mode == InputMode::ShowingUsage {
+                    self.state.usage_dialog_state.cycle_group_by();
+                    let rows = self.load_usage_summary(self.state.usage_dialog_state.group_by);
+                    self.state.usage_dialog_state.show(rows);
+                } else if self.state.input_mode == InputMode::ShowingRaceResults {
+                    self.state.race_dialog_state.select_next();
                 }
             }
             _ => {}
@@ -29,6 +29,10 @@ impl App {
                     self.state.reasoning_selector_state.hide();
                     self.state.input_mode = InputMode::Normal;
                 }
+                InputMode::SelectingCodexPermissions => {
+                    self.state.codex_permissions_state.hide();
+                    self.state.input_mode = InputMode::Normal;
+                }
                 InputMode::SelectingTheme => {
                     self.state.theme_picker_state.hide(true); // Cancelled - restore original
                     self.state.input_mode = InputMode::Normal;
@@ -42,6 +46,10 @@ impl App {
                     self.state.pending_new_project_target = None;
                     self.state.input_mode = InputMode::Normal;
                 }
+                InputMode::SelectingRaceContenders => {
+                    self.state.race_selector_state.hide();
+                    self.state.input_mode = InputMode::Normal;
+                }
                 InputMode::PickingProject => {
                     self.state.project_picker_state.hide();
                     self.state.input_mode = InputMode::Normal;
@@ -54,6 +62,25 @@ impl App {
                     self.state.base_dir_dialog_state.hide();
                     self.state.input_mode = InputMode::Normal;
                 }
+                InputMode::CommentingHunk => {
+                    self.state.diff_comment_dialog_state.hide();
+                    self.state.input_mode = InputMode::FileViewer;
+                }
+                InputMode::SettingGoal => {
+                    self.state.goal_dialog_state.hide();
+                    self.state.input_mode = InputMode::Normal;
+                }
+                InputMode::FillingPromptVariables => {
+                    self.state.prompt_variables_dialog_state.hide();
+                    self.state.input_mode = InputMode::Normal;
+                }
+                InputMode::SearchingChat => {
+                    self.close_chat_search();
+                }
+                InputMode::SearchingHistory => {
+                    self.state.history_search_state.hide();
+                    self.state.input_mode = InputMode::Normal;
+                }
                 InputMode::Confirming => {
                     if self.is_blocking_confirmation_loading_dialog() {
                         return;
@@ -64,6 +91,26 @@ impl App {
                     self.state.error_dialog_state.hide();
                     self.state.input_mode = InputMode::Normal;
                 }
+                InputMode::ShowingUsage => {
+                    self.state.usage_dialog_state.hide();
+                    self.state.input_mode = InputMode::Normal;
+                }
+                InputMode::ShowingRaceResults => {
+                    self.state.race_dialog_state.hide();
+                    self.state.input_mode = InputMode::Normal;
+                }
+                InputMode::ShowingCheckpoints => {
+                    self.state.checkpoint_dialog_state.hide();
+                    self.state.input_mode = self.checkpoint_return_mode();
+                }
+                InputMode::ShowingSchedules => {
+                    if self.state.schedule_dialog_state.is_adding() {
+                        self.state.schedule_dialog_state.cancel_adding();
+                    } else {
+                        self.state.schedule_dialog_state.hide();
+                        self.state.input_mode = InputMode::Normal;
+                    }
+                }
                 InputMode::MissingTool => {
                     self.state.missing_tool_dialog_state.hide();
                     self.state.input_mode = InputMode::Normal;
@@ -91,6 +138,9 @@ impl App {
                     self.state.slash_menu_state.hide();
                     self.state.input_mode = InputMode::Normal;
                 }
+                InputMode::PickingMention => {
+                    self.cancel_mention_picker();
+                }
                 InputMode::QueueEditing => {
                     self.close_queue_editor();
                 }
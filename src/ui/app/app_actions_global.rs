@@ -1,3 +1,4 @@
+use crate::agent::AgentType;
 use crate::ui::action::Action;
 use crate::ui::app::App;
 use crate::ui::app_state::{ModelPickerContext, SelectionDragTarget};
@@ -140,6 +141,21 @@ impl App {
                     }
                 }
             }
+            Action::ShowCodexPermissions => {
+                if let Some(session) = self.state.tab_manager.active_session() {
+                    if session.agent_type != AgentType::Codex {
+                        self.state.set_timed_footer_message(
+                            "Sandbox and approval modes apply to Codex sessions".to_string(),
+                            Duration::from_secs(3),
+                        );
+                    } else {
+                        let (approval, sandbox) = (session.codex_approval, session.codex_sandbox);
+                        self.state.close_overlays();
+                        self.state.codex_permissions_state.show(approval, sandbox);
+                        self.state.input_mode = InputMode::SelectingCodexPermissions;
+                    }
+                }
+            }
             Action::ShowThemePicker => {
                 self.state.close_overlays();
                 let theme_path = self.config().theme_path.clone();
@@ -157,6 +173,9 @@ impl App {
                 self.state.provider_selector_state.show();
                 self.state.input_mode = InputMode::SelectingProviders;
             }
+            Action::StartRace => {
+                self.show_race_selector();
+            }
             Action::OpenSessionImport => {
                 self.state.close_overlays();
                 self.state.session_import_state.show();
@@ -202,6 +221,9 @@ impl App {
             Action::DumpDebugState => {
                 effects.push(Effect::DumpDebugState);
             }
+            Action::ExportTranscript(format) => {
+                effects.push(Effect::ExportTranscript(format));
+            }
             Action::CopyWorkspacePath => {
                 if let Some(session) = self.state.tab_manager.active_session() {
                     if let Some(working_dir) = &session.working_dir {
@@ -214,6 +236,18 @@ impl App {
                     }
                 }
             }
+            Action::CycleApprovalPolicy => match self.cycle_repo_approval_policy() {
+                Ok((repo_name, policy)) => {
+                    self.state.set_timed_footer_message(
+                        format!("Tool approval for {}: {}", repo_name, policy.display_name()),
+                        Duration::from_secs(5),
+                    );
+                }
+                Err(err) => {
+                    self.state
+                        .set_timed_footer_message(err, Duration::from_secs(5));
+                }
+            },
             Action::CopySelection => {
                 let mut copied = false;
                 let clear_after_copy = self.config().selection.clear_selection_after_copy;
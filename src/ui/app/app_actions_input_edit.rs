@@ -40,12 +40,32 @@ impl App {
                 InputMode::SlashMenu => {
                     self.state.slash_menu_state.delete_char();
                 }
+                InputMode::PickingMention => {
+                    self.mention_picker_backspace();
+                }
                 InputMode::SettingBaseDir => {
                     self.state.base_dir_dialog_state.delete_char();
                 }
                 InputMode::AddingRepository => {
                     self.state.add_repo_dialog_state.delete_char();
                 }
+                InputMode::CommentingHunk => {
+                    self.state.diff_comment_dialog_state.input.delete_char();
+                }
+                InputMode::SettingGoal => {
+                    self.state.goal_dialog_state.input.delete_char();
+                }
+                InputMode::FillingPromptVariables => {
+                    self.state.prompt_variables_dialog_state.input.delete_char();
+                }
+                InputMode::SearchingChat => {
+                    self.state.chat_search_input.delete_char();
+                    self.update_chat_search();
+                }
+                InputMode::SearchingHistory => {
+                    self.state.history_search_state.delete_char();
+                    self.update_history_search();
+                }
                 InputMode::MissingTool => {
                     self.state.missing_tool_dialog_state.backspace();
                 }
@@ -58,9 +78,20 @@ impl App {
                 InputMode::SelectingReasoning => {
                     self.state.reasoning_selector_state.delete_char();
                 }
+                InputMode::SelectingCodexPermissions => {
+                    self.state.codex_permissions_state.delete_char();
+                }
                 InputMode::SelectingProviders => {
                     self.state.provider_selector_state.delete_char();
                 }
+                InputMode::SelectingRaceContenders => {
+                    self.state.race_selector_state.delete_char();
+                }
+                InputMode::ShowingSchedules => {
+                    if let Some(input) = self.state.schedule_dialog_state.input.as_mut() {
+                        input.delete_char();
+                    }
+                }
                 _ => {
                     if let Some(session) = self.state.tab_manager.active_session_mut() {
                         session.input_box.backspace();
@@ -76,14 +107,40 @@ impl App {
                     self.state.model_selector_state.delete_forward();
                 } else if self.state.input_mode == InputMode::SelectingReasoning {
                     self.state.reasoning_selector_state.delete_forward();
+                } else if self.state.input_mode == InputMode::SelectingCodexPermissions {
+                    self.state.codex_permissions_state.delete_forward();
                 } else if self.state.input_mode == InputMode::SelectingProviders {
                     self.state.provider_selector_state.delete_forward();
+                } else if self.state.input_mode == InputMode::SelectingRaceContenders {
+                    self.state.race_selector_state.delete_forward();
                 } else if self.state.input_mode == InputMode::SlashMenu {
                     self.state.slash_menu_state.delete_forward();
+                } else if self.state.input_mode == InputMode::PickingMention {
+                    self.state.mention_picker_state.delete_forward();
                 } else if self.state.input_mode == InputMode::SettingBaseDir {
                     self.state.base_dir_dialog_state.delete_forward();
                 } else if self.state.input_mode == InputMode::AddingRepository {
                     self.state.add_repo_dialog_state.delete_forward();
+                } else if self.state.input_mode == InputMode::CommentingHunk {
+                    self.state.diff_comment_dialog_state.input.delete_forward();
+                } else if self.state.input_mode == InputMode::SettingGoal {
+                    self.state.goal_dialog_state.input.delete_forward();
+                } else if self.state.input_mode == InputMode::FillingPromptVariables {
+                    self.state
+                        .prompt_variables_dialog_state
+                        .input
+                        .delete_forward();
+                } else if self.state.input_mode == InputMode::SearchingChat {
+                    self.state.chat_search_input.delete_forward();
+                    self.update_chat_search();
+                } else if self.state.input_mode == InputMode::SearchingHistory {
+                    self.state.history_search_state.delete_forward();
+                    self.update_history_search();
+                } else if self.state.input_mode == InputMode::ShowingSchedules {
+                    match self.state.schedule_dialog_state.input.as_mut() {
+                        Some(input) => input.delete_forward(),
+                        None => self.delete_selected_schedule(),
+                    }
                 } else if let Some(session) = self.state.tab_manager.active_session_mut() {
                     session.input_box.delete();
                 }
@@ -113,12 +170,26 @@ impl App {
                     self.state.model_selector_state.move_cursor_left();
                 } else if self.state.input_mode == InputMode::SelectingReasoning {
                     self.state.reasoning_selector_state.move_cursor_left();
+                } else if self.state.input_mode == InputMode::SelectingCodexPermissions {
+                    self.state.codex_permissions_state.move_cursor_left();
                 } else if self.state.input_mode == InputMode::SelectingProviders {
                     self.state.provider_selector_state.move_cursor_left();
+                } else if self.state.input_mode == InputMode::SelectingRaceContenders {
+                    self.state.race_selector_state.move_cursor_left();
                 } else if self.state.input_mode == InputMode::AddingRepository {
                     self.state.add_repo_dialog_state.move_left();
                 } else if self.state.input_mode == InputMode::SettingBaseDir {
                     self.state.base_dir_dialog_state.move_left();
+                } else if self.state.input_mode == InputMode::CommentingHunk {
+                    self.state.diff_comment_dialog_state.input.move_left();
+                } else if self.state.input_mode == InputMode::SettingGoal {
+                    self.state.goal_dialog_state.input.move_left();
+                } else if self.state.input_mode == InputMode::FillingPromptVariables {
+                    self.state.prompt_variables_dialog_state.input.move_left();
+                } else if self.state.input_mode == InputMode::SearchingChat {
+                    self.state.chat_search_input.move_left();
+                } else if self.state.input_mode == InputMode::SearchingHistory {
+                    self.state.history_search_state.move_cursor_left();
                 } else if let Some(session) = self.state.tab_manager.active_session_mut() {
                     session.input_box.move_left();
                 }
@@ -130,12 +201,26 @@ impl App {
                     self.state.model_selector_state.move_cursor_right();
                 } else if self.state.input_mode == InputMode::SelectingReasoning {
                     self.state.reasoning_selector_state.move_cursor_right();
+                } else if self.state.input_mode == InputMode::SelectingCodexPermissions {
+                    self.state.codex_permissions_state.move_cursor_right();
                 } else if self.state.input_mode == InputMode::SelectingProviders {
                     self.state.provider_selector_state.move_cursor_right();
+                } else if self.state.input_mode == InputMode::SelectingRaceContenders {
+                    self.state.race_selector_state.move_cursor_right();
                 } else if self.state.input_mode == InputMode::AddingRepository {
                     self.state.add_repo_dialog_state.move_right();
                 } else if self.state.input_mode == InputMode::SettingBaseDir {
                     self.state.base_dir_dialog_state.move_right();
+                } else if self.state.input_mode == InputMode::CommentingHunk {
+                    self.state.diff_comment_dialog_state.input.move_right();
+                } else if self.state.input_mode == InputMode::SettingGoal {
+                    self.state.goal_dialog_state.input.move_right();
+                } else if self.state.input_mode == InputMode::FillingPromptVariables {
+                    self.state.prompt_variables_dialog_state.input.move_right();
+                } else if self.state.input_mode == InputMode::SearchingChat {
+                    self.state.chat_search_input.move_right();
+                } else if self.state.input_mode == InputMode::SearchingHistory {
+                    self.state.history_search_state.move_cursor_right();
                 } else if let Some(session) = self.state.tab_manager.active_session_mut() {
                     session.input_box.move_right();
                 }
@@ -147,12 +232,26 @@ impl App {
                     self.state.model_selector_state.move_cursor_start();
                 } else if self.state.input_mode == InputMode::SelectingReasoning {
                     self.state.reasoning_selector_state.move_cursor_start();
+                } else if self.state.input_mode == InputMode::SelectingCodexPermissions {
+                    self.state.codex_permissions_state.move_cursor_start();
                 } else if self.state.input_mode == InputMode::SelectingProviders {
                     self.state.provider_selector_state.move_cursor_start();
+                } else if self.state.input_mode == InputMode::SelectingRaceContenders {
+                    self.state.race_selector_state.move_cursor_start();
                 } else if self.state.input_mode == InputMode::AddingRepository {
                     self.state.add_repo_dialog_state.move_start();
                 } else if self.state.input_mode == InputMode::SettingBaseDir {
                     self.state.base_dir_dialog_state.move_start();
+                } else if self.state.input_mode == InputMode::CommentingHunk {
+                    self.state.diff_comment_dialog_state.input.move_start();
+                } else if self.state.input_mode == InputMode::SettingGoal {
+                    self.state.goal_dialog_state.input.move_start();
+                } else if self.state.input_mode == InputMode::FillingPromptVariables {
+                    self.state.prompt_variables_dialog_state.input.move_start();
+                } else if self.state.input_mode == InputMode::SearchingChat {
+                    self.state.chat_search_input.move_start();
+                } else if self.state.input_mode == InputMode::SearchingHistory {
+                    self.state.history_search_state.move_cursor_start();
                 } else if let Some(session) = self.state.tab_manager.active_session_mut() {
                     session.input_box.move_start();
                 }
@@ -164,12 +263,26 @@ impl App {
                     self.state.model_selector_state.move_cursor_end();
                 } else if self.state.input_mode == InputMode::SelectingReasoning {
                     self.state.reasoning_selector_state.move_cursor_end();
+                } else if self.state.input_mode == InputMode::SelectingCodexPermissions {
+                    self.state.codex_permissions_state.move_cursor_end();
                 } else if self.state.input_mode == InputMode::SelectingProviders {
                     self.state.provider_selector_state.move_cursor_end();
+                } else if self.state.input_mode == InputMode::SelectingRaceContenders {
+                    self.state.race_selector_state.move_cursor_end();
                 } else if self.state.input_mode == InputMode::AddingRepository {
                     self.state.add_repo_dialog_state.move_end();
                 } else if self.state.input_mode == InputMode::SettingBaseDir {
                     self.state.base_dir_dialog_state.move_end();
+                } else if self.state.input_mode == InputMode::CommentingHunk {
+                    self.state.diff_comment_dialog_state.input.move_end();
+                } else if self.state.input_mode == InputMode::SettingGoal {
+                    self.state.goal_dialog_state.input.move_end();
+                } else if self.state.input_mode == InputMode::FillingPromptVariables {
+                    self.state.prompt_variables_dialog_state.input.move_end();
+                } else if self.state.input_mode == InputMode::SearchingChat {
+                    self.state.chat_search_input.move_end();
+                } else if self.state.input_mode == InputMode::SearchingHistory {
+                    self.state.history_search_state.move_cursor_end();
                 } else if let Some(session) = self.state.tab_manager.active_session_mut() {
                     session.input_box.move_end();
                 }
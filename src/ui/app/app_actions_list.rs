@@ -19,6 +19,9 @@ impl App {
                 InputMode::SelectingReasoning => {
                     self.state.reasoning_selector_state.select_next();
                 }
+                InputMode::SelectingCodexPermissions => {
+                    self.state.codex_permissions_state.select_next();
+                }
                 InputMode::SelectingTheme => {
                     self.state.theme_picker_state.select_next();
                 }
@@ -28,18 +31,33 @@ impl App {
                 InputMode::SelectingProviders => {
                     self.state.provider_selector_state.select_next();
                 }
+                InputMode::SelectingRaceContenders => {
+                    self.state.race_selector_state.select_next();
+                }
+                InputMode::ShowingCheckpoints => {
+                    self.state.checkpoint_dialog_state.select_next();
+                }
+                InputMode::ShowingSchedules => {
+                    self.state.schedule_dialog_state.select_next();
+                }
                 InputMode::PickingProject => {
                     self.state.project_picker_state.select_next();
                 }
                 InputMode::ImportingSession => {
                     self.state.session_import_state.select_next();
                 }
+                InputMode::SearchingHistory => {
+                    self.state.history_search_state.select_next();
+                }
                 InputMode::CommandPalette => {
                     self.state.command_palette_state.select_next();
                 }
                 InputMode::SlashMenu => {
                     self.state.slash_menu_state.select_next();
                 }
+                InputMode::PickingMention => {
+                    self.state.mention_picker_state.select_next();
+                }
                 InputMode::QueueEditing => {
                     if let Some(session) = self.state.tab_manager.active_session_mut() {
                         session.select_queue_next();
@@ -61,6 +79,9 @@ impl App {
                 InputMode::SelectingReasoning => {
                     self.state.reasoning_selector_state.select_previous();
                 }
+                InputMode::SelectingCodexPermissions => {
+                    self.state.codex_permissions_state.select_previous();
+                }
                 InputMode::SelectingTheme => {
                     self.state.theme_picker_state.select_prev();
                 }
@@ -70,18 +91,33 @@ impl App {
                 InputMode::SelectingProviders => {
                     self.state.provider_selector_state.select_previous();
                 }
+                InputMode::SelectingRaceContenders => {
+                    self.state.race_selector_state.select_previous();
+                }
+                InputMode::ShowingCheckpoints => {
+                    self.state.checkpoint_dialog_state.select_previous();
+                }
+                InputMode::ShowingSchedules => {
+                    self.state.schedule_dialog_state.select_previous();
+                }
                 InputMode::PickingProject => {
                     self.state.project_picker_state.select_prev();
                 }
                 InputMode::ImportingSession => {
                     self.state.session_import_state.select_prev();
                 }
+                InputMode::SearchingHistory => {
+                    self.state.history_search_state.select_prev();
+                }
                 InputMode::CommandPalette => {
                     self.state.command_palette_state.select_prev();
                 }
                 InputMode::SlashMenu => {
                     self.state.slash_menu_state.select_prev();
                 }
+                InputMode::PickingMention => {
+                    self.state.mention_picker_state.select_prev();
+                }
                 InputMode::QueueEditing => {
                     if let Some(session) = self.state.tab_manager.active_session_mut() {
                         session.select_queue_prev();
@@ -94,6 +130,10 @@ impl App {
                     self.state.project_picker_state.page_down();
                 } else if self.state.input_mode == InputMode::ImportingSession {
                     self.state.session_import_state.page_down();
+                } else if self.state.input_mode == InputMode::SearchingHistory {
+                    self.state.history_search_state.page_down();
+                } else if self.state.input_mode == InputMode::PickingMention {
+                    self.state.mention_picker_state.page_down();
                 }
             }
             Action::SelectPageUp => {
@@ -101,6 +141,10 @@ impl App {
                     self.state.project_picker_state.page_up();
                 } else if self.state.input_mode == InputMode::ImportingSession {
                     self.state.session_import_state.page_up();
+                } else if self.state.input_mode == InputMode::SearchingHistory {
+                    self.state.history_search_state.page_up();
+                } else if self.state.input_mode == InputMode::PickingMention {
+                    self.state.mention_picker_state.page_up();
                 }
             }
             _ => {}
@@ -29,17 +29,29 @@ impl App {
                 self.state.help_dialog_state.show(&keybindings);
                 self.state.input_mode = InputMode::ShowingHelp;
             }
+            Action::ShowUsageDashboard => {
+                self.state.close_overlays();
+                let rows = self.load_usage_summary(self.state.usage_dialog_state.group_by);
+                self.state.usage_dialog_state.show(rows);
+                self.state.input_mode = InputMode::ShowingUsage;
+            }
+            Action::ShowRaceResults => {
+                self.show_race_results();
+            }
             Action::OpenCommandPalette => {
                 self.state.close_overlays();
                 let keybindings = self.config().keybindings.clone();
+                let user_commands = self.config().user_commands.clone();
                 let supports_plan_mode = self
                     .state
                     .tab_manager
                     .active_session()
                     .is_some_and(|s| s.capabilities.supports_plan_mode);
-                self.state
-                    .command_palette_state
-                    .show(&keybindings, supports_plan_mode);
+                self.state.command_palette_state.show_with_user_commands(
+                    &keybindings,
+                    supports_plan_mode,
+                    &user_commands,
+                );
                 self.state.input_mode = InputMode::CommandPalette;
             }
             _ => {}
@@ -10,6 +10,10 @@ impl App {
             self.handle_file_viewer_scroll(&action);
             return;
         }
+        if self.state.tab_manager.active_is_diff() {
+            self.handle_diff_viewer_scroll(&action);
+            return;
+        }
 
         match action {
             Action::ScrollUp(n) => {
@@ -175,4 +179,22 @@ impl App {
             _ => {}
         }
     }
+
+    /// Handle scroll actions for the diff review tab
+    fn handle_diff_viewer_scroll(&mut self, action: &Action) {
+        let visible_height = self.diff_viewer_visible_height();
+        let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() else {
+            return;
+        };
+
+        match action {
+            Action::ScrollUp(n) => viewer.scroll_up(*n as usize),
+            Action::ScrollDown(n) => viewer.scroll_down_clamped(*n as usize, visible_height),
+            Action::ScrollPageUp => viewer.page_up_exact(visible_height),
+            Action::ScrollPageDown => viewer.page_down_exact(visible_height),
+            Action::ScrollToTop => viewer.scroll_to_top(),
+            Action::ScrollToBottom => viewer.scroll_to_bottom_exact(visible_height),
+            _ => {}
+        }
+    }
 }
@@ -0,0 +1,50 @@
+use crate::ui::app::App;
+use crate::ui::events::InputMode;
+
+impl App {
+    /// Open the search bar over the active session's transcript, or close it
+    pub(super) fn open_chat_search(&mut self) {
+        if self.state.input_mode == InputMode::SearchingChat {
+            self.close_chat_search();
+            return;
+        }
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            return;
+        };
+        session.chat_view.clear_search();
+        self.state.close_overlays();
+        self.state.chat_search_input.clear();
+        self.state.input_mode = InputMode::SearchingChat;
+    }
+
+    /// Re-run the search after the query changed
+    pub(super) fn update_chat_search(&mut self) {
+        let query = self.state.chat_search_input.value().to_string();
+        if let Some(session) = self.state.tab_manager.active_session_mut() {
+            session.chat_view.set_search_query(&query);
+        }
+    }
+
+    /// Move to the next or previous match
+    pub(super) fn step_chat_search(&mut self, forward: bool) {
+        if self.state.input_mode != InputMode::SearchingChat {
+            return;
+        }
+        if let Some(session) = self.state.tab_manager.active_session_mut() {
+            if forward {
+                session.chat_view.search_next();
+            } else {
+                session.chat_view.search_prev();
+            }
+        }
+    }
+
+    /// Close the search bar and drop the highlights
+    pub(super) fn close_chat_search(&mut self) {
+        if let Some(session) = self.state.tab_manager.active_session_mut() {
+            session.chat_view.clear_search();
+        }
+        self.state.chat_search_input.clear();
+        self.state.input_mode = InputMode::Normal;
+    }
+}
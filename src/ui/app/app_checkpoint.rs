@@ -0,0 +1,333 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::data::{Checkpoint, CheckpointStore};
+use crate::git::CheckpointManager;
+use crate::ui::action::Action;
+use crate::ui::app::App;
+use crate::ui::components::{ConfirmationContext, ConfirmationType, NodeType};
+use crate::ui::effect::Effect;
+use crate::ui::events::InputMode;
+use crate::ui::session::AgentSession;
+
+/// Checkpoints kept per workspace; older snapshots and their refs are pruned
+const MAX_CHECKPOINTS_PER_WORKSPACE: usize = 50;
+
+impl App {
+    pub(super) fn handle_checkpoint_action(&mut self, action: Action) {
+        let Some(workspace_id) = self.checkpoint_workspace() else {
+            self.state.set_timed_footer_message(
+                "Checkpoints need a workspace".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        };
+        match action {
+            Action::ShowCheckpoints => self.open_checkpoints(workspace_id),
+            Action::RollbackLastTurn => {
+                let latest = self.load_checkpoints(workspace_id).into_iter().next();
+                match latest {
+                    Some(checkpoint) => self.confirm_restore(workspace_id, &checkpoint),
+                    None => self.state.set_timed_footer_message(
+                        "No checkpoints for this workspace yet".to_string(),
+                        Duration::from_secs(3),
+                    ),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Checkpoint row for a prompt being submitted, if the session has a
+    /// workspace. A prompt sent while a turn is still running shares that
+    /// turn's checkpoint.
+    pub(super) fn checkpoint_for_turn(
+        session: &AgentSession,
+        prompt: Option<String>,
+    ) -> Option<Checkpoint> {
+        let workspace_id = session.workspace_id?;
+        session.working_dir.as_ref()?;
+        if session.current_checkpoint_id.is_some() {
+            return None;
+        }
+        let mut checkpoint = Checkpoint::new(
+            workspace_id,
+            Some(session.id),
+            session.turn_count as i64 + 1,
+        );
+        checkpoint.prompt = prompt;
+        Some(checkpoint)
+    }
+
+    /// Detach the running turn's checkpoint along with the files it reported
+    pub(super) fn take_checkpoint_files(
+        session: &mut AgentSession,
+    ) -> Option<(Uuid, PathBuf, Vec<String>)> {
+        let checkpoint_id = session.current_checkpoint_id.take()?;
+        let working_dir = session.working_dir.clone()?;
+        let files = session
+            .current_turn_summary
+            .files_changed
+            .iter()
+            .map(|change| change.filename.clone())
+            .collect();
+        Some((checkpoint_id, working_dir, files))
+    }
+
+    /// Snapshot the worktree and record the checkpoint. Runs before the agent
+    /// starts so the snapshot can't pick up the turn's own edits.
+    pub(super) fn take_checkpoint(&mut self, session_id: Uuid, mut checkpoint: Checkpoint) {
+        let Some(store) = self.checkpoint_dao_clone() else {
+            return;
+        };
+        let Some(session) = self
+            .state
+            .tab_manager
+            .session_index_by_id(session_id)
+            .and_then(|index| self.state.tab_manager.session_mut(index))
+        else {
+            return;
+        };
+        let Some(working_dir) = session.working_dir.clone() else {
+            return;
+        };
+        let message = format!("conduit: before turn {}", checkpoint.turn);
+        match CheckpointManager::snapshot(&working_dir, &checkpoint.ref_name, &message) {
+            Ok(commit) => checkpoint.commit_sha = Some(commit),
+            Err(err) => {
+                tracing::warn!(
+                    error = %err,
+                    working_dir = %working_dir.display(),
+                    "Failed to snapshot worktree for checkpoint"
+                );
+                return;
+            }
+        }
+        if let Err(err) = store.create(&checkpoint) {
+            tracing::warn!(error = %err, %session_id, "Failed to record checkpoint");
+            let _ = CheckpointManager::delete_ref(&working_dir, &checkpoint.ref_name);
+            return;
+        }
+        session.current_checkpoint_id = Some(checkpoint.id);
+
+        let workspace_id = checkpoint.workspace_id;
+        tokio::task::spawn_blocking(move || {
+            prune_checkpoints(&store, &working_dir, workspace_id);
+        });
+    }
+
+    /// Store the files a turn touched: those the agent reported plus
+    /// anything else that differs from the checkpoint.
+    pub(super) fn record_checkpoint_files(
+        &self,
+        checkpoint_id: Uuid,
+        working_dir: PathBuf,
+        reported: Vec<String>,
+    ) {
+        let Some(store) = self.checkpoint_dao_clone() else {
+            return;
+        };
+        tokio::task::spawn_blocking(move || {
+            let mut files: Vec<String> = reported
+                .into_iter()
+                .map(|path| relative_path(&working_dir, &path))
+                .collect();
+            if let Ok(Some(commit)) = store
+                .get_by_id(checkpoint_id)
+                .map(|checkpoint| checkpoint.and_then(|c| c.commit_sha))
+            {
+                match CheckpointManager::changed_since(&working_dir, &commit) {
+                    Ok(changed) => files.extend(changed),
+                    Err(err) => {
+                        tracing::warn!(error = %err, "Failed to diff worktree against checkpoint")
+                    }
+                }
+            }
+            files.sort();
+            files.dedup();
+            if let Err(err) = store.set_files_changed(checkpoint_id, &files) {
+                tracing::warn!(error = %err, "Failed to save checkpoint files");
+            }
+        });
+    }
+
+    /// Restore the checkpoint highlighted in the timeline (after confirmation)
+    pub(super) fn confirm_checkpoint_restore(&mut self) {
+        let (Some(workspace_id), Some(checkpoint)) = (
+            self.state.checkpoint_dialog_state.workspace_id(),
+            self.state
+                .checkpoint_dialog_state
+                .selected_checkpoint()
+                .cloned(),
+        ) else {
+            return;
+        };
+        self.confirm_restore(workspace_id, &checkpoint);
+    }
+
+    pub(super) fn handle_checkpoint_restored(
+        &mut self,
+        working_dir: PathBuf,
+        turn: i64,
+        result: Result<(), String>,
+    ) -> Option<Effect> {
+        if let Err(err) = result {
+            self.show_error("Restore Failed", &err);
+            return None;
+        }
+        self.state.set_timed_footer_message(
+            format!("Restored files to before turn {}", turn),
+            Duration::from_secs(4),
+        );
+        // Refresh an open diff review of the same worktree
+        let viewer = self
+            .state
+            .tab_manager
+            .tabs()
+            .iter()
+            .filter_map(|tab| tab.as_diff())
+            .find(|viewer| viewer.working_dir == working_dir)?;
+        let tab_id = viewer.id;
+        let viewer = self.state.tab_manager.diff_viewer_by_id_mut(tab_id)?;
+        viewer.loading = true;
+        Some(Effect::LoadWorkspaceDiff {
+            tab_id,
+            working_dir,
+        })
+    }
+
+    /// Where focus goes after the timeline or restore dialog closes
+    pub(super) fn checkpoint_return_mode(&self) -> InputMode {
+        if self.state.tab_manager.is_empty() {
+            InputMode::SidebarNavigation
+        } else if self.state.tab_manager.active_is_file() || self.state.tab_manager.active_is_diff()
+        {
+            InputMode::FileViewer
+        } else {
+            InputMode::Normal
+        }
+    }
+
+    /// Workspace under the sidebar cursor, else the active tab's workspace
+    fn checkpoint_workspace(&self) -> Option<Uuid> {
+        if self.state.input_mode == InputMode::SidebarNavigation {
+            let selected = self.state.sidebar_state.tree_state.selected;
+            return self
+                .state
+                .sidebar_data
+                .get_at(selected)
+                .filter(|node| node.node_type == NodeType::Workspace)
+                .map(|node| node.id);
+        }
+        self.state
+            .tab_manager
+            .active_session()
+            .and_then(|session| session.workspace_id)
+            .or_else(|| {
+                self.state
+                    .tab_manager
+                    .active_diff_viewer()
+                    .and_then(|viewer| viewer.workspace_id)
+            })
+    }
+
+    fn open_checkpoints(&mut self, workspace_id: Uuid) {
+        let name = self
+            .workspace_dao()
+            .and_then(|dao| dao.get_by_id(workspace_id).ok().flatten())
+            .map(|workspace| workspace.name)
+            .unwrap_or_else(|| "workspace".to_string());
+        let checkpoints = self.load_checkpoints(workspace_id);
+        self.state.close_overlays();
+        self.state
+            .checkpoint_dialog_state
+            .show(workspace_id, &name, checkpoints);
+        self.state.input_mode = InputMode::ShowingCheckpoints;
+    }
+
+    fn load_checkpoints(&self, workspace_id: Uuid) -> Vec<Checkpoint> {
+        let Some(store) = self.checkpoint_dao() else {
+            return Vec::new();
+        };
+        store
+            .list_for_workspace(workspace_id)
+            .unwrap_or_else(|err| {
+                tracing::warn!(error = %err, %workspace_id, "Failed to load checkpoints");
+                Vec::new()
+            })
+    }
+
+    fn confirm_restore(&mut self, workspace_id: Uuid, checkpoint: &Checkpoint) {
+        let Some(commit) = checkpoint.commit_sha.clone() else {
+            return;
+        };
+        let busy = self.state.tab_manager.tabs().iter().any(|tab| {
+            tab.as_agent().is_some_and(|session| {
+                session.workspace_id == Some(workspace_id) && session.is_processing
+            })
+        });
+        if busy {
+            self.state.set_timed_footer_message(
+                "Wait for the agent to finish before restoring a checkpoint".to_string(),
+                Duration::from_secs(4),
+            );
+            return;
+        }
+        let Some(workspace) = self
+            .workspace_dao()
+            .and_then(|dao| dao.get_by_id(workspace_id).ok().flatten())
+        else {
+            return;
+        };
+
+        let mut warnings = vec![format!(
+            "Current files are saved to {} first.",
+            CheckpointManager::backup_ref_name(workspace_id)
+        )];
+        if let Some(prompt) = checkpoint.prompt.as_deref().and_then(|p| p.lines().next()) {
+            warnings.insert(0, format!("Turn prompt: {}", prompt));
+        }
+        let context = ConfirmationContext::RestoreCheckpoint {
+            workspace_id,
+            working_dir: workspace.path,
+            commit,
+            turn: checkpoint.turn,
+        };
+        self.state.close_overlays();
+        self.state.confirmation_dialog_state.show(
+            "Restore Checkpoint",
+            format!(
+                "Restore {} to how it was before turn {}?",
+                workspace.name, checkpoint.turn
+            ),
+            warnings,
+            ConfirmationType::Danger,
+            "Restore",
+            Some(context),
+        );
+        self.state.input_mode = InputMode::Confirming;
+    }
+}
+
+/// Drop checkpoints beyond the per-workspace limit, oldest first
+fn prune_checkpoints(store: &CheckpointStore, working_dir: &Path, workspace_id: Uuid) {
+    let Ok(checkpoints) = store.list_for_workspace(workspace_id) else {
+        return;
+    };
+    for checkpoint in checkpoints.into_iter().skip(MAX_CHECKPOINTS_PER_WORKSPACE) {
+        if let Err(err) = CheckpointManager::delete_ref(working_dir, &checkpoint.ref_name) {
+            tracing::debug!(error = %err, "Failed to delete checkpoint ref");
+        }
+        let _ = store.delete(checkpoint.id);
+    }
+}
+
+/// Agents may report absolute paths; keep them relative to the worktree
+fn relative_path(working_dir: &Path, path: &str) -> String {
+    Path::new(path)
+        .strip_prefix(working_dir)
+        .map(|relative| relative.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| path.to_string())
+}
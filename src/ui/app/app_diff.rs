@@ -0,0 +1,250 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::data::{QueuedMessage, QueuedMessageMode};
+use crate::git::{HunkAction, WorkspaceDiff};
+use crate::ui::action::Action;
+use crate::ui::app::App;
+use crate::ui::components::{ConfirmationContext, ConfirmationType, DiffViewerView};
+use crate::ui::diff_viewer::DiffViewerSession;
+use crate::ui::effect::Effect;
+use crate::ui::events::InputMode;
+
+impl App {
+    pub(super) fn handle_diff_action(&mut self, action: Action, effects: &mut Vec<Effect>) {
+        if action == Action::OpenDiffReview {
+            effects.extend(self.open_diff_review());
+            return;
+        }
+
+        let visible_height = self.diff_viewer_visible_height();
+        let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() else {
+            return;
+        };
+        match action {
+            Action::DiffNextHunk => viewer.select_next_hunk(visible_height),
+            Action::DiffPrevHunk => viewer.select_prev_hunk(visible_height),
+            Action::DiffRefresh => {
+                viewer.loading = true;
+                effects.push(Effect::LoadWorkspaceDiff {
+                    tab_id: viewer.id,
+                    working_dir: viewer.working_dir.clone(),
+                });
+            }
+            Action::DiffStageHunk => {
+                if viewer.selected_hunk().is_none() {
+                    return;
+                }
+                let Some(patch) = viewer.selected_stage_patch() else {
+                    self.state.set_timed_footer_message(
+                        "Hunk is already committed or staged".to_string(),
+                        Duration::from_secs(3),
+                    );
+                    return;
+                };
+                effects.push(Effect::ApplyDiffHunk {
+                    tab_id: viewer.id,
+                    working_dir: viewer.working_dir.clone(),
+                    patch,
+                    action: HunkAction::Stage,
+                });
+            }
+            Action::DiffRevertHunk => {
+                let (Some(patch), Some((file, hunk))) =
+                    (viewer.selected_patch(), viewer.selected_hunk())
+                else {
+                    return;
+                };
+                let message = format!(
+                    "Discard this change to {} from the working tree?\n{}",
+                    file.display_path(),
+                    hunk.header
+                );
+                let context = ConfirmationContext::RevertDiffHunk {
+                    tab_id: viewer.id,
+                    working_dir: viewer.working_dir.clone(),
+                    patch,
+                };
+                self.state.confirmation_dialog_state.show(
+                    "Revert Hunk",
+                    message,
+                    vec!["Uncommitted edits in this hunk will be lost.".to_string()],
+                    ConfirmationType::Danger,
+                    "Revert",
+                    Some(context),
+                );
+                self.state.input_mode = InputMode::Confirming;
+            }
+            Action::DiffCommentHunk => {
+                let Some((file, hunk)) = viewer.selected_hunk() else {
+                    return;
+                };
+                let target = format!("{} {}", file.display_path(), hunk.header);
+                let tab_id = viewer.id;
+                self.state.close_overlays();
+                self.state.diff_comment_dialog_state.show(tab_id, target);
+                self.state.input_mode = InputMode::CommentingHunk;
+            }
+            _ => {}
+        }
+    }
+
+    /// Open (or refresh) the diff review tab for the active session's workspace
+    fn open_diff_review(&mut self) -> Option<Effect> {
+        let Some((workspace_id, working_dir)) = self
+            .state
+            .tab_manager
+            .active_session()
+            .and_then(|session| Some((session.workspace_id?, session.working_dir.clone()?)))
+        else {
+            self.state.set_timed_footer_message(
+                "Diff review needs a session in a workspace".to_string(),
+                Duration::from_secs(4),
+            );
+            return None;
+        };
+
+        if let Some(index) = self
+            .state
+            .tab_manager
+            .diff_tab_index_for_workspace(workspace_id)
+        {
+            self.state.tab_manager.switch_to(index);
+            self.sync_input_mode_for_active_tab();
+            let viewer = self.state.tab_manager.active_diff_viewer_mut()?;
+            viewer.loading = true;
+            return Some(Effect::LoadWorkspaceDiff {
+                tab_id: viewer.id,
+                working_dir: viewer.working_dir.clone(),
+            });
+        }
+
+        let name = self
+            .workspace_dao()
+            .and_then(|dao| dao.get_by_id(workspace_id).ok().flatten())
+            .map(|workspace| workspace.name)
+            .unwrap_or_else(|| "workspace".to_string());
+        let viewer = DiffViewerSession::new(Some(workspace_id), working_dir.clone(), name);
+        let tab_id = viewer.id;
+        if self.state.tab_manager.open_diff(viewer).is_none() {
+            self.state.set_timed_footer_message(
+                "Maximum tabs reached".to_string(),
+                Duration::from_secs(3),
+            );
+            return None;
+        }
+        self.sync_input_mode_for_active_tab();
+        Some(Effect::LoadWorkspaceDiff {
+            tab_id,
+            working_dir,
+        })
+    }
+
+    pub(super) fn handle_workspace_diff_loaded(
+        &mut self,
+        tab_id: Uuid,
+        result: Result<WorkspaceDiff, String>,
+    ) {
+        let Some(viewer) = self.state.tab_manager.diff_viewer_by_id_mut(tab_id) else {
+            return;
+        };
+        match result {
+            Ok(diff) => viewer.set_diff(diff),
+            Err(err) => viewer.set_error(err),
+        }
+    }
+
+    pub(super) fn handle_diff_hunk_applied(
+        &mut self,
+        tab_id: Uuid,
+        action: HunkAction,
+        result: Result<(), String>,
+    ) -> Option<Effect> {
+        if let Err(err) = result {
+            self.show_error("Hunk Not Applied", &err);
+            return None;
+        }
+        self.state.set_timed_footer_message(
+            format!("Hunk {}", action.past_tense()),
+            Duration::from_secs(2),
+        );
+        let viewer = self.state.tab_manager.diff_viewer_by_id_mut(tab_id)?;
+        viewer.loading = true;
+        Some(Effect::LoadWorkspaceDiff {
+            tab_id,
+            working_dir: viewer.working_dir.clone(),
+        })
+    }
+
+    /// Queue or send the typed review remark to the workspace's agent tab
+    pub(super) fn send_hunk_comment(&mut self) -> anyhow::Result<Vec<Effect>> {
+        let comment = self
+            .state
+            .diff_comment_dialog_state
+            .input
+            .value()
+            .trim()
+            .to_string();
+        if comment.is_empty() {
+            return Ok(Vec::new());
+        }
+        let tab_id = self.state.diff_comment_dialog_state.tab_id;
+        self.state.diff_comment_dialog_state.hide();
+        self.state.input_mode = InputMode::FileViewer;
+
+        let Some(viewer) = tab_id.and_then(|id| {
+            let index = self.state.tab_manager.tab_index_by_id(id)?;
+            self.state.tab_manager.tab(index)?.as_diff()
+        }) else {
+            return Ok(Vec::new());
+        };
+        let (Some(prompt), Some(workspace_id)) =
+            (viewer.review_prompt(&comment), viewer.workspace_id)
+        else {
+            return Ok(Vec::new());
+        };
+
+        let Some(agent_index) = self.state.tab_manager.tabs().iter().position(|tab| {
+            tab.as_agent()
+                .is_some_and(|session| session.workspace_id == Some(workspace_id))
+        }) else {
+            self.state.set_timed_footer_message(
+                "Open an agent tab for this workspace to send review notes".to_string(),
+                Duration::from_secs(4),
+            );
+            return Ok(Vec::new());
+        };
+
+        let Some(session) = self.state.tab_manager.session_mut(agent_index) else {
+            return Ok(Vec::new());
+        };
+        if session.is_processing {
+            session.queue_message(QueuedMessage {
+                id: Uuid::new_v4(),
+                mode: QueuedMessageMode::FollowUp,
+                text: prompt,
+                images: Vec::new(),
+                created_at: Utc::now(),
+            });
+            self.state
+                .set_timed_footer_message("Review note queued".to_string(), Duration::from_secs(3));
+            return Ok(vec![Effect::SaveSessionState]);
+        }
+
+        let effects =
+            self.submit_prompt_for_tab(agent_index, prompt, Vec::new(), Vec::new(), false, None)?;
+        self.state
+            .set_timed_footer_message("Review note sent".to_string(), Duration::from_secs(3));
+        Ok(effects)
+    }
+
+    /// Rows of diff content visible in the diff review tab
+    pub(super) fn diff_viewer_visible_height(&self) -> usize {
+        self.state
+            .file_viewer_area
+            .map(|area| DiffViewerView::content_height(area.height))
+            .unwrap_or(20)
+    }
+}
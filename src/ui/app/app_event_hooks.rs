@@ -0,0 +1,104 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::agent::event_hooks::EventHookRun;
+use crate::agent::{AgentEvent, EventHook, MessageDisplay};
+use crate::data::{QueuedMessage, QueuedMessageMode};
+use crate::ui::app::App;
+use crate::ui::effect::Effect;
+
+impl App {
+    /// Run the session's hooks that match an agent event
+    pub(super) fn event_hook_effects(&self, tab_index: usize, event: &AgentEvent) -> Vec<Effect> {
+        let Some(session) = self.state.tab_manager.session(tab_index) else {
+            return Vec::new();
+        };
+        let hooks: Vec<&EventHook> = session.event_hooks.matching(event).collect();
+        if hooks.is_empty() {
+            return Vec::new();
+        }
+        let event_json = match serde_json::to_string(event) {
+            Ok(json) => json,
+            Err(err) => {
+                tracing::warn!(error = %err, "Failed to serialize agent event for hooks");
+                return Vec::new();
+            }
+        };
+        let working_dir = session
+            .working_dir
+            .clone()
+            .unwrap_or_else(|| self.config().working_dir.clone());
+
+        hooks
+            .into_iter()
+            .map(|hook| Effect::RunEventHook {
+                session_id: session.id,
+                hook: hook.clone(),
+                event_json: event_json.clone(),
+                working_dir: working_dir.clone(),
+            })
+            .collect()
+    }
+
+    /// Report a finished hook and, when it asks for it, send its output to
+    /// the agent as a follow-up prompt
+    pub(super) fn handle_event_hook_finished(
+        &mut self,
+        session_id: Uuid,
+        hook: EventHook,
+        run: EventHookRun,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let Some(tab_index) = self.state.tab_manager.session_index_by_id(session_id) else {
+            return Ok(Vec::new());
+        };
+        let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
+            return Ok(Vec::new());
+        };
+
+        if !hook.feeds_back(&run) {
+            if let Some(failure) = &run.failure {
+                tracing::warn!(%session_id, command = %hook.command, %failure, "Event hook failed");
+                session.chat_view.push(
+                    MessageDisplay::System {
+                        content: format!(
+                            "Hook `{}` failed after {}: {}",
+                            hook.command, hook.event, failure
+                        ),
+                    }
+                    .to_chat_message(),
+                );
+            }
+            return Ok(Vec::new());
+        }
+
+        // Hooks that keep failing after every turn would otherwise loop forever
+        let max_rounds = session.event_hooks.max_feedback_rounds;
+        if session.hook_feedback_rounds >= max_rounds {
+            session.chat_view.push(
+                MessageDisplay::System {
+                    content: format!(
+                        "Hook `{}` output not sent: {} hook follow-ups since your last prompt",
+                        hook.command, max_rounds
+                    ),
+                }
+                .to_chat_message(),
+            );
+            return Ok(Vec::new());
+        }
+        session.hook_feedback_rounds += 1;
+        session.queue_message(QueuedMessage {
+            id: Uuid::new_v4(),
+            mode: QueuedMessageMode::FollowUp,
+            text: run.feedback_prompt(&hook),
+            images: Vec::new(),
+            created_at: Utc::now(),
+        });
+
+        // A running turn drains the queue when it ends
+        if session.can_start_queued_prompt() {
+            self.drain_queue_for_tab(tab_index)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::agent::event_hooks::EventHookRun;
+use crate::agent::MessageDisplay;
+use crate::config::NotificationEvent;
+use crate::data::{QueuedMessage, QueuedMessageMode};
+use crate::ui::app::App;
+use crate::ui::effect::Effect;
+use crate::ui::events::InputMode;
+use crate::ui::goal::GoalStatus;
+
+impl App {
+    /// `/goal`: stop the running goal loop, or ask for a verification command
+    pub(super) fn toggle_goal(&mut self) {
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            return;
+        };
+        if let Some(goal) = session.goal.as_mut().filter(|goal| goal.is_active()) {
+            goal.stop();
+            session.chat_view.push(
+                MessageDisplay::System {
+                    content: format!("Goal loop stopped: `{}` not re-run", goal.command),
+                }
+                .to_chat_message(),
+            );
+            self.state
+                .set_timed_footer_message("Goal loop stopped".to_string(), Duration::from_secs(3));
+            return;
+        }
+        if session.pending_goal.take().is_some() {
+            self.state
+                .set_timed_footer_message("Goal loop disarmed".to_string(), Duration::from_secs(3));
+            return;
+        }
+
+        let previous = session.goal.as_ref().map(|goal| goal.command.clone());
+        self.state.close_overlays();
+        self.state.goal_dialog_state.show(previous.as_deref());
+        self.state.input_mode = InputMode::SettingGoal;
+    }
+
+    /// Arm the active session so its next prompt starts the goal loop
+    pub(super) fn confirm_goal_command(&mut self) {
+        let command = self
+            .state
+            .goal_dialog_state
+            .input
+            .value()
+            .trim()
+            .to_string();
+        if command.is_empty() {
+            self.state.set_timed_footer_message(
+                "Verification command is empty".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        }
+        self.state.goal_dialog_state.hide();
+        self.state.input_mode = InputMode::Normal;
+
+        if let Some(session) = self.state.tab_manager.active_session_mut() {
+            session.pending_goal = Some(command);
+        }
+        self.state.set_timed_footer_message(
+            "Goal loop armed · type the goal and press Enter".to_string(),
+            Duration::from_secs(5),
+        );
+    }
+
+    /// Run the verification command after a goal loop turn
+    pub(super) fn start_goal_check(
+        &mut self,
+        tab_index: usize,
+        cost_usd: Option<f64>,
+    ) -> Vec<Effect> {
+        let default_dir = self.config().working_dir.clone();
+        let timeout = self.config().goal.check_timeout;
+        let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
+            return Vec::new();
+        };
+        let Some(goal) = session.goal.as_mut() else {
+            return Vec::new();
+        };
+        if !goal.finish_turn(cost_usd) {
+            return Vec::new();
+        }
+        vec![Effect::RunGoalCheck {
+            session_id: session.id,
+            command: goal.command.clone(),
+            working_dir: session.working_dir.clone().unwrap_or(default_dir),
+            timeout,
+        }]
+    }
+
+    /// Stop on success or a limit, otherwise send the failure back to the agent
+    pub(super) fn handle_goal_check_finished(
+        &mut self,
+        session_id: Uuid,
+        run: EventHookRun,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let Some(tab_index) = self.state.tab_manager.session_index_by_id(session_id) else {
+            return Ok(Vec::new());
+        };
+        let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
+            return Ok(Vec::new());
+        };
+        let Some(goal) = session.goal.as_mut() else {
+            return Ok(Vec::new());
+        };
+
+        let failure = run.failure.clone().unwrap_or_default();
+        let next_prompt = goal.record_check(&run);
+        let command = goal.command.clone();
+        let iteration = goal.iteration;
+        let max_iterations = goal.max_iterations;
+        let (content, notification) = match (&next_prompt, &goal.status) {
+            (Some(_), _) => (
+                format!(
+                    "`{}` failed ({}) · goal attempt {}/{}",
+                    command, failure, iteration, max_iterations
+                ),
+                None,
+            ),
+            (None, GoalStatus::Passed) => {
+                let turns = if iteration == 1 { "turn" } else { "turns" };
+                let summary = format!("`{}` passed after {} {}", command, iteration, turns);
+                (
+                    format!("Goal reached: {}", summary),
+                    Some((NotificationEvent::TurnCompleted, summary)),
+                )
+            }
+            (None, GoalStatus::GaveUp(reason)) => {
+                let summary = format!("`{}` still fails after {}", command, reason);
+                (
+                    format!("Goal loop gave up: {}", summary),
+                    Some((NotificationEvent::TurnFailed, summary)),
+                )
+            }
+            // Stopped while the command ran
+            (None, _) => return Ok(Vec::new()),
+        };
+        session
+            .chat_view
+            .push(MessageDisplay::System { content }.to_chat_message());
+
+        let Some(prompt) = next_prompt else {
+            if let Some((event, summary)) = notification {
+                self.notify_attention(tab_index, event, &summary);
+            }
+            return Ok(Vec::new());
+        };
+        session.queue_message(QueuedMessage {
+            id: Uuid::new_v4(),
+            mode: QueuedMessageMode::FollowUp,
+            text: prompt,
+            images: Vec::new(),
+            created_at: Utc::now(),
+        });
+
+        // The user may have sent something while the command ran
+        if session.can_start_queued_prompt() {
+            self.drain_queue_for_tab(tab_index)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// A failed turn ends the goal loop; the failure is what needs attention
+    pub(super) fn end_goal_on_failure(&mut self, tab_index: usize) {
+        let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
+            return;
+        };
+        if let Some(goal) = session.goal.as_mut().filter(|goal| goal.is_active()) {
+            goal.status = GoalStatus::GaveUp("the turn failed".to_string());
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::data::HistorySearchHit;
+use crate::session::HistoryIndexStats;
+use crate::ui::app::App;
+use crate::ui::effect::Effect;
+use crate::ui::events::InputMode;
+use crate::ui::tab_manager::Tab;
+
+/// Hits fetched per query
+const HISTORY_SEARCH_LIMIT: usize = 50;
+
+impl App {
+    /// Open the history search picker and refresh the index behind it
+    pub(super) fn open_history_search(&mut self) -> Vec<Effect> {
+        self.state.close_overlays();
+        self.state.history_search_state.show();
+        self.state.input_mode = InputMode::SearchingHistory;
+        if self.history_index_dao().is_none() {
+            self.state
+                .history_search_state
+                .set_error("History index unavailable (database not open)".to_string());
+            self.state.history_search_state.indexing = false;
+            return Vec::new();
+        }
+        vec![Effect::RefreshHistoryIndex]
+    }
+
+    /// Re-run the query after it changed
+    pub(super) fn update_history_search(&mut self) {
+        let Some(store) = self.history_index_dao() else {
+            return;
+        };
+        let query = self.state.history_search_state.query().to_string();
+        match store.search(&query, HISTORY_SEARCH_LIMIT) {
+            Ok(hits) => self.state.history_search_state.set_hits(hits),
+            Err(err) => self
+                .state
+                .history_search_state
+                .set_error(format!("Search failed: {}", err)),
+        }
+    }
+
+    pub(super) fn handle_history_index_refreshed(
+        &mut self,
+        result: Result<HistoryIndexStats, String>,
+    ) {
+        self.state.history_search_state.indexing = false;
+        match result {
+            Ok(stats) => {
+                // New messages may match what's already typed
+                if stats.indexed > 0 || stats.removed > 0 {
+                    self.update_history_search();
+                }
+            }
+            Err(err) => {
+                tracing::warn!(error = %err, "Failed to refresh history index");
+                self.state.set_timed_footer_message(
+                    format!("History index refresh failed: {}", err),
+                    Duration::from_secs(5),
+                );
+            }
+        }
+    }
+
+    /// Open the selected hit
+    pub(super) fn confirm_history_search(&mut self) -> Vec<Effect> {
+        let Some(hit) = self.state.history_search_state.selected_hit().cloned() else {
+            return Vec::new();
+        };
+        self.state.history_search_state.hide();
+        self.state.input_mode = InputMode::Normal;
+        vec![Effect::OpenHistoryHit(hit)]
+    }
+
+    /// Switch to the hit's session, or load it into a new tab, then search
+    /// its transcript from the matching message
+    pub(super) async fn open_history_hit(&mut self, hit: HistorySearchHit) -> anyhow::Result<()> {
+        let source = &hit.source;
+        let open_tab = self.state.tab_manager.tabs().iter().position(|tab| {
+            matches!(tab, Tab::Agent(session)
+                if session.agent_type == source.agent_type
+                    && session
+                        .agent_session_id
+                        .as_ref()
+                        .is_some_and(|id| id.as_str() == source.agent_session_id))
+        });
+
+        match open_tab {
+            Some(index) => {
+                self.state.tab_manager.switch_to(index);
+                self.sync_footer_spinner();
+            }
+            None => {
+                let workspace_path = source.workspace_id.and_then(|id| {
+                    self.workspace_dao()
+                        .and_then(|dao| dao.get_by_id(id).ok().flatten())
+                        .map(|workspace| workspace.path)
+                        .filter(|path| path.exists())
+                });
+                let working_dir = workspace_path
+                    .or_else(|| source.project.as_ref().map(PathBuf::from))
+                    .unwrap_or_else(|| self.config().working_dir.clone());
+                if !self.state.tab_manager.can_add_tab() {
+                    self.state.set_timed_footer_message(
+                        "Too many tabs open; close one to open this session".to_string(),
+                        Duration::from_secs(4),
+                    );
+                    return Ok(());
+                }
+                self.create_imported_session_tab(
+                    source.agent_type,
+                    source.file_path.clone(),
+                    working_dir,
+                )
+                .await?;
+            }
+        }
+
+        let query = self.state.history_search_state.query().to_string();
+        let term = hit
+            .matched_term()
+            .or_else(|| query.split_whitespace().next())
+            .unwrap_or_default()
+            .to_string();
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            return Ok(());
+        };
+        session
+            .chat_view
+            .set_search_query_at_message(&term, hit.message_index);
+        self.state.chat_search_input.set(&term);
+        self.state.input_mode = InputMode::SearchingChat;
+        Ok(())
+    }
+}
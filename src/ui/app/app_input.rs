@@ -5,7 +5,7 @@ use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
 use std::io;
 
-use crate::agent::{AgentMode, AgentType, MessageDisplay};
+use crate::agent::{AgentMode, AgentType, MessageDisplay, ToolApprovalChoice};
 use crate::config::{KeyCombo, KeyContext};
 use crate::ui::action::Action;
 use crate::ui::app::App;
@@ -32,7 +32,14 @@ impl App {
                 terminal.autoresize()?;
                 Ok(Vec::new())
             }
-            Event::FocusGained | Event::FocusLost => Ok(Vec::new()),
+            Event::FocusGained => {
+                self.state.terminal_focused = true;
+                Ok(Vec::new())
+            }
+            Event::FocusLost => {
+                self.state.terminal_focused = false;
+                Ok(Vec::new())
+            }
         }
     }
 
@@ -66,8 +73,12 @@ impl App {
             self.state.input_mode = InputMode::SelectingModel;
         } else if self.state.reasoning_selector_state.is_visible() {
             self.state.input_mode = InputMode::SelectingReasoning;
+        } else if self.state.codex_permissions_state.is_visible() {
+            self.state.input_mode = InputMode::SelectingCodexPermissions;
         } else if self.state.provider_selector_state.is_visible() {
             self.state.input_mode = InputMode::SelectingProviders;
+        } else if self.state.race_selector_state.is_visible() {
+            self.state.input_mode = InputMode::SelectingRaceContenders;
         } else if self.state.project_picker_state.is_visible() {
             self.state.input_mode = InputMode::PickingProject;
         } else if self.state.base_dir_dialog_state.path.is_visible() {
@@ -84,12 +95,33 @@ impl App {
             return Ok(effects);
         }
 
-        // Handle inline prompt input (AskUserQuestion, ExitPlanMode)
+        // Handle inline prompt input (AskUserQuestion, ExitPlanMode, tool approvals)
         if let Some(session) = self.state.tab_manager.active_session_mut() {
             if let Some(ref mut prompt) = session.inline_prompt {
-                use crate::ui::components::{PromptAction, PromptResponse};
+                use crate::ui::components::{InlinePromptType, PromptAction, PromptResponse};
 
                 match prompt.handle_key(key) {
+                    PromptAction::Submit(PromptResponse::PermissionSelected {
+                        option_id,
+                        reason,
+                    }) => {
+                        let InlinePromptType::ToolPermission { request_id, .. } =
+                            &prompt.prompt_type
+                        else {
+                            return Ok(Vec::new());
+                        };
+                        let request_id = request_id.clone();
+                        if session.agent_type == AgentType::Claude {
+                            let effects =
+                                self.submit_tool_approval(&request_id, &option_id, reason);
+                            return Ok(effects);
+                        }
+                        session.pending_tool_permissions.remove(&prompt.tool_id);
+                        session.inline_prompt = None;
+                        let effects =
+                            self.send_acp_permission_response(&request_id, Some(option_id));
+                        return Ok(effects);
+                    }
                     PromptAction::Submit(response) => {
                         let tool_id = prompt.tool_id.clone();
                         let response_clone = response.clone();
@@ -137,6 +169,8 @@ impl App {
                                     );
                                     self.send_control_response(request_id, response_payload)
                                 }
+                                // Permission selections are handled above.
+                                PromptResponse::PermissionSelected { .. } => Vec::new(),
                             }
                         } else if agent_type == AgentType::Claude
                             && session.agent_input_tx.is_some()
@@ -169,6 +203,7 @@ impl App {
                                         Some(&tool_id),
                                     )
                                 }
+                                PromptResponse::PermissionSelected { .. } => return Ok(Vec::new()),
                             };
                             session
                                 .pending_tool_permission_responses
@@ -206,6 +241,7 @@ impl App {
                                         );
                                     self.send_tool_result(&tool_id, content, tool_use_result)
                                 }
+                                PromptResponse::PermissionSelected { .. } => Vec::new(),
                             }
                         } else {
                             match response_clone {
@@ -238,11 +274,29 @@ impl App {
                                         );
                                     self.send_tool_result(&tool_id, content, tool_use_result)
                                 }
+                                PromptResponse::PermissionSelected { .. } => Vec::new(),
                             }
                         };
                         return Ok(effects);
                     }
                     PromptAction::Cancel => {
+                        if let InlinePromptType::ToolPermission { request_id, .. } =
+                            &prompt.prompt_type
+                        {
+                            let request_id = request_id.clone();
+                            if session.agent_type == AgentType::Claude {
+                                let effects = self.submit_tool_approval(
+                                    &request_id,
+                                    ToolApprovalChoice::Deny.as_str(),
+                                    None,
+                                );
+                                return Ok(effects);
+                            }
+                            session.pending_tool_permissions.remove(&prompt.tool_id);
+                            session.inline_prompt = None;
+                            let effects = self.send_acp_permission_response(&request_id, None);
+                            return Ok(effects);
+                        }
                         let tool_id = prompt.tool_id.clone();
                         let pending_request_id = session.pending_tool_permissions.remove(&tool_id);
                         let agent_type = session.agent_type;
@@ -412,6 +466,7 @@ impl App {
         let active_session = self.state.tab_manager.active_session();
         let has_active_session = active_session.is_some();
         let has_inline_prompt = active_session.is_some_and(|s| s.inline_prompt.is_some());
+        let at_word_start = active_session.is_some_and(|s| s.input_box.is_cursor_at_word_start());
 
         // Only enter command mode if the input box is empty and not in shell mode
         let (input_is_empty, shell_mode) = active_session
@@ -440,12 +495,27 @@ impl App {
             has_inline_prompt,
             has_active_session,
         ) {
+            let prompts = self.prompt_templates();
+            let user_commands = self.config().user_commands.clone();
             self.state.close_overlays();
-            self.state.slash_menu_state.show();
+            self.state
+                .slash_menu_state
+                .show_with(&user_commands, prompts);
             self.state.input_mode = InputMode::SlashMenu;
             return Ok(Vec::new());
         }
 
+        if Self::should_trigger_mention_picker(
+            key.code,
+            key.modifiers,
+            self.state.input_mode,
+            at_word_start,
+            shell_mode,
+            has_inline_prompt,
+        ) {
+            return Ok(self.open_mention_picker());
+        }
+
         if self.state.input_mode == InputMode::SelectingProviders
             && key.modifiers.is_empty()
             && key.code == KeyCode::Char(' ')
@@ -454,6 +524,14 @@ impl App {
             return Ok(Vec::new());
         }
 
+        if self.state.input_mode == InputMode::SelectingRaceContenders
+            && key.modifiers.is_empty()
+            && key.code == KeyCode::Char(' ')
+        {
+            self.state.race_selector_state.toggle_selected();
+            return Ok(Vec::new());
+        }
+
         // Get the current context from input mode and active tab type
         let context = self.key_context_for_active_tab();
 
@@ -503,7 +581,7 @@ impl App {
             return false;
         }
 
-        if matches!(context, KeyContext::FileViewer) {
+        if matches!(context, KeyContext::FileViewer | KeyContext::DiffViewer) {
             return false;
         }
 
@@ -520,6 +598,7 @@ impl App {
                 | KeyContext::CommandPalette
                 | KeyContext::ThemePicker
                 | KeyContext::ModelSelector
+                | KeyContext::ChatSearch
         )
     }
 
@@ -571,18 +650,49 @@ impl App {
             InputMode::SettingBaseDir => {
                 self.state.base_dir_dialog_state.insert_char(c);
             }
+            InputMode::CommentingHunk => {
+                self.state.diff_comment_dialog_state.input.insert_char(c);
+            }
+            InputMode::SettingGoal => {
+                self.state.goal_dialog_state.input.insert_char(c);
+            }
+            InputMode::FillingPromptVariables => {
+                self.state
+                    .prompt_variables_dialog_state
+                    .input
+                    .insert_char(c);
+            }
+            InputMode::SearchingChat => {
+                self.state.chat_search_input.insert_char(c);
+                self.update_chat_search();
+            }
             InputMode::PickingProject => {
                 self.state.project_picker_state.insert_char(c);
             }
             InputMode::ImportingSession => {
                 self.state.session_import_state.insert_char(c);
             }
+            InputMode::SearchingHistory => {
+                self.state.history_search_state.insert_char(c);
+                self.update_history_search();
+            }
             InputMode::CommandPalette => {
                 self.state.command_palette_state.insert_char(c);
             }
             InputMode::SlashMenu => {
                 self.state.slash_menu_state.insert_char(c);
             }
+            InputMode::PickingMention => {
+                if c.is_whitespace() {
+                    // A space ends the mention; keep what was typed as text
+                    self.cancel_mention_picker();
+                    if let Some(session) = self.state.tab_manager.active_session_mut() {
+                        session.input_box.insert_char(c);
+                    }
+                } else {
+                    self.state.mention_picker_state.insert_char(c);
+                }
+            }
             InputMode::MissingTool => {
                 self.state.missing_tool_dialog_state.insert_char(c);
             }
@@ -595,9 +705,18 @@ impl App {
             InputMode::SelectingReasoning => {
                 self.state.reasoning_selector_state.insert_char(c);
             }
+            InputMode::SelectingCodexPermissions => {
+                self.state.codex_permissions_state.insert_char(c);
+            }
             InputMode::SelectingProviders => {
                 self.state.provider_selector_state.insert_char(c);
             }
+            InputMode::SelectingRaceContenders => {
+                self.state.race_selector_state.insert_char(c);
+            }
+            InputMode::ShowingSchedules => {
+                self.handle_schedule_dialog_char(c);
+            }
             _ => {}
         }
     }
@@ -648,6 +767,34 @@ impl App {
                     self.state.base_dir_dialog_state.insert_char(ch);
                 }
             }
+            InputMode::CommentingHunk => {
+                let sanitized = pasted.replace('\n', " ");
+                for ch in sanitized.chars() {
+                    self.state.diff_comment_dialog_state.input.insert_char(ch);
+                }
+            }
+            InputMode::SettingGoal => {
+                let sanitized = pasted.replace('\n', " ");
+                for ch in sanitized.chars() {
+                    self.state.goal_dialog_state.input.insert_char(ch);
+                }
+            }
+            InputMode::FillingPromptVariables => {
+                let sanitized = pasted.replace('\n', " ");
+                for ch in sanitized.chars() {
+                    self.state
+                        .prompt_variables_dialog_state
+                        .input
+                        .insert_char(ch);
+                }
+            }
+            InputMode::SearchingChat => {
+                let sanitized = pasted.replace('\n', " ");
+                for ch in sanitized.chars() {
+                    self.state.chat_search_input.insert_char(ch);
+                }
+                self.update_chat_search();
+            }
             InputMode::PickingProject => {
                 let sanitized = pasted.replace('\n', " ");
                 for ch in sanitized.chars() {
@@ -660,6 +807,13 @@ impl App {
                     self.state.session_import_state.insert_char(ch);
                 }
             }
+            InputMode::SearchingHistory => {
+                let sanitized = pasted.replace('\n', " ");
+                for ch in sanitized.chars() {
+                    self.state.history_search_state.insert_char(ch);
+                }
+                self.update_history_search();
+            }
             InputMode::CommandPalette => {
                 let sanitized = pasted.replace('\n', " ");
                 for ch in sanitized.chars() {
@@ -672,6 +826,11 @@ impl App {
                     self.state.slash_menu_state.insert_char(ch);
                 }
             }
+            InputMode::PickingMention => {
+                for ch in pasted.chars().filter(|ch| !ch.is_whitespace()) {
+                    self.state.mention_picker_state.insert_char(ch);
+                }
+            }
             InputMode::MissingTool => {
                 let sanitized = pasted.replace('\n', " ");
                 for ch in sanitized.chars() {
@@ -690,10 +849,26 @@ impl App {
                 let sanitized = pasted.replace('\n', " ");
                 self.state.reasoning_selector_state.insert_str(&sanitized);
             }
+            InputMode::SelectingCodexPermissions => {
+                let sanitized = pasted.replace('\n', " ");
+                self.state.codex_permissions_state.insert_str(&sanitized);
+            }
             InputMode::SelectingProviders => {
                 let sanitized = pasted.replace('\n', " ");
                 self.state.provider_selector_state.insert_str(&sanitized);
             }
+            InputMode::SelectingRaceContenders => {
+                let sanitized = pasted.replace('\n', " ");
+                self.state.race_selector_state.insert_str(&sanitized);
+            }
+            InputMode::ShowingSchedules => {
+                if let Some(input) = self.state.schedule_dialog_state.input.as_mut() {
+                    pasted
+                        .chars()
+                        .map(|c| if c == '\n' { ' ' } else { c })
+                        .for_each(|c| input.insert_char(c));
+                }
+            }
             _ => {}
         }
     }
@@ -720,6 +895,10 @@ impl App {
                     && self.state.session_import_state.is_visible()
                 {
                     self.state.session_import_state.select_prev();
+                } else if self.state.input_mode == InputMode::SearchingHistory
+                    && self.state.history_search_state.is_visible()
+                {
+                    self.state.history_search_state.select_prev();
                 } else if self.state.input_mode == InputMode::SelectingTheme
                     && self.state.theme_picker_state.is_visible()
                 {
@@ -728,15 +907,26 @@ impl App {
                     && self.state.reasoning_selector_state.is_visible()
                 {
                     self.state.reasoning_selector_state.select_previous();
+                } else if self.state.input_mode == InputMode::SelectingCodexPermissions
+                    && self.state.codex_permissions_state.is_visible()
+                {
+                    self.state.codex_permissions_state.select_previous();
                 } else if self.state.input_mode == InputMode::SelectingProviders
                     && self.state.provider_selector_state.is_visible()
                 {
                     self.state.provider_selector_state.select_previous();
+                } else if self.state.input_mode == InputMode::SelectingRaceContenders
+                    && self.state.race_selector_state.is_visible()
+                {
+                    self.state.race_selector_state.select_previous();
                 } else if self.handle_tab_bar_wheel(x, y, true) {
                     return Ok(Vec::new());
                 } else if let Some(file_session) = self.state.tab_manager.active_file_viewer_mut() {
                     file_session.scroll_up(1);
                     self.record_scroll(1);
+                } else if let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() {
+                    viewer.scroll_up(1);
+                    self.record_scroll(1);
                 } else if self.state.view_mode == ViewMode::RawEvents {
                     if let Some(session) = self.state.tab_manager.active_session_mut() {
                         if session.raw_events_view.is_detail_visible() {
@@ -764,6 +954,10 @@ impl App {
                     && self.state.session_import_state.is_visible()
                 {
                     self.state.session_import_state.select_next();
+                } else if self.state.input_mode == InputMode::SearchingHistory
+                    && self.state.history_search_state.is_visible()
+                {
+                    self.state.history_search_state.select_next();
                 } else if self.state.input_mode == InputMode::SelectingTheme
                     && self.state.theme_picker_state.is_visible()
                 {
@@ -772,15 +966,29 @@ impl App {
                     && self.state.reasoning_selector_state.is_visible()
                 {
                     self.state.reasoning_selector_state.select_next();
+                } else if self.state.input_mode == InputMode::SelectingCodexPermissions
+                    && self.state.codex_permissions_state.is_visible()
+                {
+                    self.state.codex_permissions_state.select_next();
                 } else if self.state.input_mode == InputMode::SelectingProviders
                     && self.state.provider_selector_state.is_visible()
                 {
                     self.state.provider_selector_state.select_next();
+                } else if self.state.input_mode == InputMode::SelectingRaceContenders
+                    && self.state.race_selector_state.is_visible()
+                {
+                    self.state.race_selector_state.select_next();
                 } else if self.handle_tab_bar_wheel(x, y, false) {
                     return Ok(Vec::new());
                 } else if let Some(file_session) = self.state.tab_manager.active_file_viewer_mut() {
                     file_session.scroll_down(1);
                     self.record_scroll(1);
+                } else if self.state.tab_manager.active_is_diff() {
+                    let visible_height = self.diff_viewer_visible_height();
+                    if let Some(viewer) = self.state.tab_manager.active_diff_viewer_mut() {
+                        viewer.scroll_down_clamped(1, visible_height);
+                    }
+                    self.record_scroll(1);
                 } else if self.state.view_mode == ViewMode::RawEvents {
                     let list_height = self.raw_events_list_visible_height();
                     let detail_height = self.raw_events_detail_visible_height();
@@ -0,0 +1,86 @@
+use std::path::{Path, PathBuf};
+
+use crate::agent::AgentType;
+use crate::session::{expand_mentions, MentionCandidate, MentionExpansion};
+use crate::ui::app::App;
+use crate::ui::effect::Effect;
+use crate::ui::events::InputMode;
+
+impl App {
+    /// Open the `@` picker over the active session's working directory
+    pub(super) fn open_mention_picker(&mut self) -> Vec<Effect> {
+        let working_dir = self
+            .state
+            .tab_manager
+            .active_session()
+            .and_then(|session| session.working_dir.clone())
+            .unwrap_or_else(|| self.config().working_dir.clone());
+        self.state.close_overlays();
+        self.state.mention_picker_state.show(working_dir.clone());
+        self.state.input_mode = InputMode::PickingMention;
+        vec![Effect::LoadMentionCandidates { working_dir }]
+    }
+
+    pub(super) fn handle_mention_candidates_loaded(
+        &mut self,
+        working_dir: PathBuf,
+        result: Result<Vec<MentionCandidate>, String>,
+    ) {
+        if let Err(err) = &result {
+            tracing::debug!(error = %err, working_dir = %working_dir.display(), "Failed to list mention candidates");
+        }
+        self.state
+            .mention_picker_state
+            .set_candidates(&working_dir, result);
+    }
+
+    /// Insert the selected file or symbol as a mention token
+    pub(super) fn confirm_mention_picker(&mut self) {
+        let Some(token) = self
+            .state
+            .mention_picker_state
+            .selected_candidate()
+            .map(MentionCandidate::token)
+        else {
+            self.cancel_mention_picker();
+            return;
+        };
+        self.state.mention_picker_state.hide();
+        self.state.input_mode = InputMode::Normal;
+        if let Some(session) = self.state.tab_manager.active_session_mut() {
+            session.input_box.insert_str(&token);
+            session.input_box.insert_char(' ');
+        }
+    }
+
+    /// Close the picker, keeping the `@` and the query as typed text
+    pub(super) fn cancel_mention_picker(&mut self) {
+        let typed = format!("@{}", self.state.mention_picker_state.query());
+        self.state.mention_picker_state.hide();
+        self.state.input_mode = InputMode::Normal;
+        if let Some(session) = self.state.tab_manager.active_session_mut() {
+            session.input_box.insert_str(&typed);
+        }
+    }
+
+    /// Backspace on an empty query removes the `@` itself
+    pub(super) fn mention_picker_backspace(&mut self) {
+        if self.state.mention_picker_state.query().is_empty() {
+            self.state.mention_picker_state.hide();
+            self.state.input_mode = InputMode::Normal;
+        } else {
+            self.state.mention_picker_state.delete_char();
+        }
+    }
+
+    /// Expand `@` mentions in a prompt the way `agent_type` needs them
+    pub(super) fn expand_prompt_mentions(
+        &self,
+        agent_type: AgentType,
+        working_dir: &Path,
+        prompt: &str,
+    ) -> String {
+        let expansion = MentionExpansion::for_agent(self.config().agent_reads_files(agent_type));
+        expand_mentions(prompt, working_dir, expansion)
+    }
+}
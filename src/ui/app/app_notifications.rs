@@ -0,0 +1,65 @@
+use std::io::Write;
+
+use crate::config::NotificationEvent;
+use crate::core::notifications::{self, Notification};
+use crate::ui::app::App;
+use crate::ui::components::TurnSummary;
+
+impl App {
+    /// Let the user know, outside Conduit, that a session needs them
+    pub(super) fn notify_attention(
+        &self,
+        tab_index: usize,
+        event: NotificationEvent,
+        summary: &str,
+    ) {
+        let config = &self.config().notifications;
+        if !config.notifies(event) {
+            return;
+        }
+        let in_view =
+            self.state.terminal_focused && self.state.tab_manager.active_index() == tab_index;
+        if config.background_only && in_view {
+            return;
+        }
+        let Some(session) = self.state.tab_manager.session(tab_index) else {
+            return;
+        };
+
+        let agent_name = self.config().agent_display_name(session.agent_type);
+        let mut notification =
+            Notification::new(event, session.id, agent_name).with_summary(summary);
+        notification.session_title = session.title.clone();
+        notification.workspace_id = session.workspace_id;
+        notification.workspace_name = session.workspace_name.clone();
+        notification.project_name = session.project_name.clone();
+
+        if let Some(sequence) = notifications::terminal_sequence(config.terminal, &notification) {
+            // Bell and OSC 9 don't touch the screen, so they can go out between frames
+            let mut stdout = std::io::stdout();
+            if let Err(err) = stdout
+                .write_all(sequence.as_bytes())
+                .and_then(|()| stdout.flush())
+            {
+                tracing::debug!(error = %err, "Failed to write terminal notification");
+            }
+        }
+        if config.command.is_some() || config.webhook_url.is_some() {
+            let config = config.clone();
+            tokio::spawn(async move {
+                notifications::send_external(&config, &notification).await;
+            });
+        }
+    }
+
+    pub(super) fn turn_notification_summary(summary: &TurnSummary) -> String {
+        match summary.files_changed.len() {
+            0 => format!("Finished in {}s", summary.duration_secs),
+            1 => format!("Finished in {}s, 1 file changed", summary.duration_secs),
+            files => format!(
+                "Finished in {}s, {} files changed",
+                summary.duration_secs, files
+            ),
+        }
+    }
+}
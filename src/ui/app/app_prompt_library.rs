@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::data::{QueuedMessage, QueuedMessageMode};
+use crate::session::prompts::{FILE_VARIABLE, SELECTION_VARIABLE};
+use crate::session::{last_file_mention, load_prompt_templates, PromptTemplate};
+use crate::ui::app::App;
+use crate::ui::components::MessageRole;
+use crate::ui::effect::Effect;
+use crate::ui::events::InputMode;
+
+impl App {
+    /// Templates for the slash menu: personal ones plus the active workspace's
+    pub(super) fn prompt_templates(&self) -> Vec<PromptTemplate> {
+        let working_dir = self
+            .state
+            .tab_manager
+            .active_session()
+            .and_then(|session| session.working_dir.as_deref());
+        load_prompt_templates(working_dir)
+    }
+
+    /// Fill in what the session knows, starting with the chat selection
+    pub(super) fn start_prompt_template(&mut self, template: PromptTemplate) -> Vec<Effect> {
+        let default_dir = self.config().working_dir.clone();
+        let file = template
+            .variables()
+            .iter()
+            .any(|name| name == FILE_VARIABLE)
+            .then(|| self.active_file())
+            .flatten();
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            return Vec::new();
+        };
+        let mut values = HashMap::new();
+        if let Some(file) = file {
+            values.insert(FILE_VARIABLE.to_string(), file);
+        }
+        if let Some(selection) = session
+            .chat_view
+            .copy_selection()
+            .filter(|selection| !selection.trim().is_empty())
+        {
+            values.insert(SELECTION_VARIABLE.to_string(), selection);
+        }
+        vec![Effect::ResolvePromptTemplate {
+            session_id: session.id,
+            template,
+            working_dir: session.working_dir.clone().unwrap_or(default_dir),
+            values,
+        }]
+    }
+
+    /// File the active session is looking at: the one selected in its
+    /// workspace's diff tab, or else the last one the user mentioned with `@`
+    fn active_file(&self) -> Option<String> {
+        let session = self.state.tab_manager.active_session()?;
+        let from_diff = session
+            .workspace_id
+            .and_then(|id| self.state.tab_manager.diff_tab_index_for_workspace(id))
+            .and_then(|index| self.state.tab_manager.diff_viewer(index))
+            .and_then(|viewer| viewer.selected_file_path())
+            .map(str::to_string);
+        if from_diff.is_some() {
+            return from_diff;
+        }
+        let working_dir = session.working_dir.as_deref()?;
+        session
+            .chat_view
+            .messages()
+            .iter()
+            .rev()
+            .filter(|message| message.role == MessageRole::User)
+            .find_map(|message| last_file_mention(&message.content, working_dir))
+    }
+
+    /// Ask for the variables still missing, or send the prompt right away
+    pub(super) fn handle_prompt_template_resolved(
+        &mut self,
+        session_id: Uuid,
+        template: PromptTemplate,
+        values: HashMap<String, String>,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let missing: Vec<String> = template
+            .variables()
+            .into_iter()
+            .filter(|name| !values.contains_key(name))
+            .collect();
+        if missing.is_empty() {
+            return self.queue_prompt(session_id, template.render(&values), &template.name);
+        }
+        self.state.close_overlays();
+        self.state
+            .prompt_variables_dialog_state
+            .show(session_id, template, values, missing);
+        self.state.input_mode = InputMode::FillingPromptVariables;
+        Ok(Vec::new())
+    }
+
+    /// Take the typed value and move on; the last one sends the prompt
+    pub(super) fn confirm_prompt_variable(&mut self) -> anyhow::Result<Vec<Effect>> {
+        if !self.state.prompt_variables_dialog_state.submit_current() {
+            return Ok(Vec::new());
+        }
+        self.state.input_mode = InputMode::Normal;
+        let Some((session_id, template, values)) = self.state.prompt_variables_dialog_state.take()
+        else {
+            return Ok(Vec::new());
+        };
+        self.queue_prompt(session_id, template.render(&values), &template.name)
+    }
+
+    /// Queue a prompt sent by slash command `name` and start it if the agent
+    /// is idle
+    pub(super) fn queue_prompt(
+        &mut self,
+        session_id: Uuid,
+        text: String,
+        name: &str,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let Some(tab_index) = self.state.tab_manager.session_index_by_id(session_id) else {
+            return Ok(Vec::new());
+        };
+        let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
+            return Ok(Vec::new());
+        };
+        session.queue_message(QueuedMessage {
+            id: Uuid::new_v4(),
+            mode: QueuedMessageMode::FollowUp,
+            text,
+            images: Vec::new(),
+            created_at: Utc::now(),
+        });
+
+        if session.can_start_queued_prompt() {
+            self.drain_queue_for_tab(tab_index)
+        } else {
+            self.state
+                .set_timed_footer_message(format!("Queued /{}", name), Duration::from_secs(3));
+            Ok(Vec::new())
+        }
+    }
+}
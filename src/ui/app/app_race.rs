@@ -0,0 +1,351 @@
+use std::time::Duration;
+
+use crate::agent::ModelRegistry;
+use crate::core::services::UsageService;
+use crate::git::GitDiffStats;
+use crate::ui::app::App;
+use crate::ui::components::{RaceResultRow, RaceSelectorState};
+use crate::ui::effect::Effect;
+use crate::ui::events::{InputMode, RaceWorkspacesCreated};
+use crate::ui::race::{Race, RaceContender, RaceEntry, RaceOutcome};
+use crate::ui::session::AgentSession;
+
+impl App {
+    /// Open the contender picker for the active session (`/race`)
+    pub(super) fn show_race_selector(&mut self) {
+        if self.state.active_race.is_some() {
+            self.refuse_second_race();
+            return;
+        }
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            return;
+        };
+        if session.workspace_id.is_none() {
+            self.state.set_timed_footer_message(
+                "Races need a session in a workspace".to_string(),
+                Duration::from_secs(4),
+            );
+            return;
+        }
+        // Re-running /race replaces any armed race, starting from its picks
+        let previous = session.pending_race.take().unwrap_or_else(|| {
+            let model = session
+                .model
+                .clone()
+                .unwrap_or_else(|| ModelRegistry::default_model(session.agent_type));
+            vec![RaceContender::new(session.agent_type, model)]
+        });
+        session.update_status();
+
+        let providers = self.config().effective_enabled_providers(self.tools());
+        if providers.is_empty() {
+            self.state.set_timed_footer_message(
+                "No enabled providers available. Use /providers.".to_string(),
+                Duration::from_secs(4),
+            );
+            return;
+        }
+
+        self.state.close_overlays();
+        self.state.race_selector_state = RaceSelectorState::configure_for(&providers, &previous);
+        self.state.race_selector_state.show();
+        self.state.input_mode = InputMode::SelectingRaceContenders;
+    }
+
+    /// Arm the active session so its next prompt starts the race
+    pub(super) fn confirm_race_contenders(&mut self) {
+        if !self.state.race_selector_state.validate() {
+            return;
+        }
+        let contenders = self.state.race_selector_state.selected_contenders();
+        let count = contenders.len();
+        self.state.race_selector_state.hide();
+        self.state.input_mode = InputMode::Normal;
+
+        if let Some(session) = self.state.tab_manager.active_session_mut() {
+            session.pending_race = Some(contenders);
+            session.update_status();
+        }
+        self.state.set_timed_footer_message(
+            format!("Racing {} agents · type the prompt and press Enter", count),
+            Duration::from_secs(5),
+        );
+    }
+
+    /// Create the race workspaces off the active session's workspace
+    pub(super) fn start_race(
+        &mut self,
+        prompt: String,
+        contenders: Vec<RaceContender>,
+    ) -> Option<Effect> {
+        let session = self.state.tab_manager.active_session_mut()?;
+        let source_workspace_id = session.workspace_id?;
+        if prompt.trim().is_empty() {
+            session.pending_race = Some(contenders);
+            session.update_status();
+            self.state.set_timed_footer_message(
+                "Race prompt is empty".to_string(),
+                Duration::from_secs(3),
+            );
+            return None;
+        }
+
+        if self.state.active_race.is_some() {
+            // Keep the race armed and the prompt typed for when this one ends
+            session.pending_race = Some(contenders);
+            session.input_box.insert_str(&prompt);
+            session.update_status();
+            self.refuse_second_race();
+            return None;
+        }
+
+        let race = Race::new(prompt, source_workspace_id, contenders);
+        let effect = Effect::CreateRaceWorkspaces {
+            race_id: race.id,
+            source_workspace_id,
+            count: race.entries.len(),
+        };
+        self.state.active_race = Some(race);
+        self.mark_workspace_busy(source_workspace_id);
+        Some(effect)
+    }
+
+    fn refuse_second_race(&mut self) {
+        self.state.set_timed_footer_message(
+            "A race is already running · keep a contender before starting another".to_string(),
+            Duration::from_secs(4),
+        );
+    }
+
+    /// Open a tab per contender in its new workspace and send each the prompt
+    pub(super) fn finish_race_setup(
+        &mut self,
+        race_id: uuid::Uuid,
+        created: RaceWorkspacesCreated,
+    ) -> Vec<Effect> {
+        let mut effects = Vec::new();
+        let Some(mut race) = self.state.active_race.take() else {
+            return effects;
+        };
+        if race.id != race_id {
+            self.state.active_race = Some(race);
+            return effects;
+        }
+        race.base_commit = Some(created.base_commit.clone());
+
+        let project_name = self
+            .repo_dao()
+            .and_then(|dao| dao.get_by_id(created.repo_id).ok().flatten())
+            .map(|repo| repo.name);
+        let mut first_tab = None;
+
+        for (index, workspace_id) in created.workspace_ids.iter().copied().enumerate() {
+            let Some(contender) = race.entries.get(index).map(|e| e.contender.clone()) else {
+                break;
+            };
+            race.entries[index].workspace_id = Some(workspace_id);
+            let workspace = self
+                .workspace_dao()
+                .and_then(|dao| dao.get_by_id(workspace_id).ok().flatten());
+            let Some(workspace) = workspace else {
+                race.fail_entry(index);
+                continue;
+            };
+
+            let mut session =
+                AgentSession::with_working_dir(contender.agent_type, workspace.path.clone());
+            session.workspace_id = Some(workspace_id);
+            session.project_name = project_name.clone();
+            session.workspace_name = Some(workspace.name.clone());
+            session.model = Some(contender.model.clone());
+            session.init_context_for_model();
+            session.update_status();
+            let session_id = session.id;
+
+            let Some(tab_index) = self.state.tab_manager.add_session(session) else {
+                tracing::warn!(%workspace_id, "No room for race tab");
+                race.fail_entry(index);
+                continue;
+            };
+            if let Some(ref tracker) = self.git_tracker {
+                tracker.track_workspace(workspace_id, workspace.path.clone());
+            }
+
+            race.start_entry(index, workspace_id, session_id);
+            match self.submit_prompt_for_tab(
+                tab_index,
+                race.prompt.clone(),
+                Vec::new(),
+                Vec::new(),
+                false,
+                None,
+            ) {
+                // Contenders in a workspace still being set up start when it finishes
+                Ok(tab_effects)
+                    if !tab_effects.is_empty()
+                        || self.state.workspace_setups.contains_key(&workspace_id) =>
+                {
+                    effects.extend(tab_effects);
+                    first_tab.get_or_insert(tab_index);
+                }
+                Ok(_) => race.fail_entry(index),
+                Err(err) => {
+                    tracing::warn!(error = %err, %session_id, "Failed to start race contender");
+                    race.fail_entry(index);
+                }
+            }
+        }
+
+        if let Some(tab_index) = first_tab {
+            self.state.tab_manager.switch_to(tab_index);
+            self.sync_footer_spinner();
+            self.state.sidebar_state.hide();
+            self.state.input_mode = InputMode::Normal;
+        }
+
+        let short_commit: String = created.base_commit.chars().take(7).collect();
+        self.state.set_timed_footer_message(
+            format!(
+                "Race started: {} agents from {}",
+                race.entries.len(),
+                short_commit
+            ),
+            Duration::from_secs(5),
+        );
+
+        let complete = race.is_complete();
+        self.state.active_race = Some(race);
+        if complete {
+            self.show_race_results();
+        }
+        effects
+    }
+
+    /// Note the end of a contender's turn; opens the comparison once every
+    /// contender is done
+    pub(super) fn record_race_turn_end(&mut self, session_id: uuid::Uuid, outcome: RaceOutcome) {
+        let Some(race) = self.state.active_race.as_mut() else {
+            return;
+        };
+        if !race.finish_session(session_id, outcome) {
+            return;
+        }
+        if self.state.has_active_overlay() {
+            self.state.set_timed_footer_message(
+                "Race finished · run \"Show race results\" to compare".to_string(),
+                Duration::from_secs(5),
+            );
+        } else {
+            self.show_race_results();
+        }
+    }
+
+    pub(super) fn show_race_results(&mut self) {
+        let Some(race) = self.state.active_race.clone() else {
+            self.state
+                .set_timed_footer_message("No race to show".to_string(), Duration::from_secs(3));
+            return;
+        };
+        let rows = race
+            .entries
+            .iter()
+            .map(|entry| self.race_result_row(&race, entry))
+            .collect();
+
+        self.state.close_overlays();
+        self.state.race_dialog_state.show(&race.prompt, rows);
+        self.state.input_mode = InputMode::ShowingRaceResults;
+    }
+
+    fn race_result_row(&self, race: &Race, entry: &RaceEntry) -> RaceResultRow {
+        let workspace = entry.workspace_id.and_then(|id| {
+            self.workspace_dao()
+                .and_then(|dao| dao.get_by_id(id).ok().flatten())
+        });
+        let diff = match (&workspace, &race.base_commit) {
+            (Some(workspace), Some(base)) => GitDiffStats::since_commit(&workspace.path, base),
+            _ => GitDiffStats::default(),
+        };
+        let totals = entry
+            .session_id
+            .and_then(|id| match UsageService::session_totals(&self.core, id) {
+                Ok(totals) => Some(totals),
+                Err(err) => {
+                    tracing::warn!(error = %err, session_id = %id, "Failed to load race usage");
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        RaceResultRow {
+            label: entry.contender.label(),
+            workspace_id: entry.workspace_id,
+            workspace_name: workspace.map(|w| w.name),
+            outcome: entry.outcome,
+            duration: entry.elapsed(),
+            diff,
+            total_tokens: totals.total_tokens,
+            cost_usd: totals.cost_usd,
+        }
+    }
+
+    /// Keep the highlighted contender and archive every other race workspace
+    pub(super) fn keep_race_contender(&mut self) -> Vec<Effect> {
+        let mut effects = Vec::new();
+        let Some(kept) = self.state.race_dialog_state.selected_row().cloned() else {
+            return effects;
+        };
+        // A contender whose workspace was never created can't be kept;
+        // archiving "everything else" would take every workspace
+        let Some(kept_workspace_id) = kept.workspace_id else {
+            self.state.set_timed_footer_message(
+                format!("{} has no workspace to keep", kept.label),
+                Duration::from_secs(4),
+            );
+            return effects;
+        };
+        self.state.race_dialog_state.hide();
+        self.state.input_mode = InputMode::Normal;
+        let Some(race) = self.state.active_race.take() else {
+            return effects;
+        };
+
+        let losers: Vec<uuid::Uuid> = race
+            .entries
+            .iter()
+            .filter_map(|entry| entry.workspace_id)
+            .filter(|id| *id != kept_workspace_id)
+            .collect();
+        for workspace_id in &losers {
+            // Stop the agents before their worktrees are removed
+            self.close_tabs_for_workspace(*workspace_id);
+            self.mark_workspace_busy(*workspace_id);
+            effects.push(Effect::ArchiveWorkspace {
+                workspace_id: *workspace_id,
+                delete_remote: false,
+            });
+        }
+
+        let kept_session = race
+            .entries
+            .iter()
+            .find(|entry| entry.workspace_id == Some(kept_workspace_id))
+            .and_then(|entry| entry.session_id);
+        if let Some(index) =
+            kept_session.and_then(|id| self.state.tab_manager.session_index_by_id(id))
+        {
+            self.state.tab_manager.switch_to(index);
+            self.sync_footer_spinner();
+            self.state.sidebar_state.hide();
+        }
+        self.state.set_timed_footer_message(
+            format!(
+                "Kept {} · archiving {} other workspaces",
+                kept.workspace_name.as_deref().unwrap_or(&kept.label),
+                losers.len()
+            ),
+            Duration::from_secs(5),
+        );
+        effects
+    }
+}
@@ -0,0 +1,264 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::agent::{MessageDisplay, ProviderErrorKind};
+use crate::config::RateLimitConfig;
+use crate::data::{QueuedMessage, QueuedMessageMode};
+use crate::ui::app::{send_app_event, App};
+use crate::ui::app_prompt;
+use crate::ui::app_state::PendingHandoffRequest;
+use crate::ui::components::{ConfirmationContext, ConfirmationType};
+use crate::ui::effect::Effect;
+use crate::ui::events::{AppEvent, InputMode};
+use crate::ui::session::{AgentSession, RateLimitWait};
+
+/// Wait before checking again when a retry is due before the stream ended
+const STREAM_END_RETRY: Duration = Duration::from_millis(200);
+
+impl App {
+    /// Record a provider error that ended the running turn. Returns the delay
+    /// before the last prompt is resent, when it should be.
+    pub(super) fn plan_rate_limit_retry(
+        config: &RateLimitConfig,
+        session: &mut AgentSession,
+        kind: ProviderErrorKind,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        let agent_name = session.agent_type.display_name();
+        if !kind.is_transient() {
+            let content = match kind {
+                ProviderErrorKind::AuthExpired => format!(
+                    "{} needs you to log in again. Sign in with its CLI in a terminal, then resend your prompt.",
+                    agent_name
+                ),
+                _ => "The conversation no longer fits in the model's context window. Use /handoff to continue in a fresh session.".to_string(),
+            };
+            session
+                .chat_view
+                .push(MessageDisplay::System { content }.to_chat_message());
+            return None;
+        }
+
+        let attempt = session.rate_limit_retries + 1;
+        let delay = config
+            .retry_delay(retry_after, attempt)
+            .filter(|_| session.last_prompt.is_some());
+        let content = match delay {
+            Some(delay) => {
+                session.rate_limit_retries = attempt;
+                format!(
+                    "{} is {}. Resending your last prompt in {}s (retry {} of {}).",
+                    agent_name,
+                    kind.label().to_lowercase(),
+                    delay.as_secs_f32().ceil() as u64,
+                    attempt,
+                    config.max_retries
+                )
+            }
+            None => {
+                let resets = retry_after
+                    .map(|wait| {
+                        format!(
+                            " The limit resets in about {} min.",
+                            wait.as_secs().div_ceil(60)
+                        )
+                    })
+                    .unwrap_or_default();
+                session.rate_limit_retries = 0;
+                format!(
+                    "{} is {}.{} Not retrying automatically; resend your prompt when ready.",
+                    agent_name,
+                    kind.label().to_lowercase(),
+                    resets
+                )
+            }
+        };
+        session.rate_limit_wait = Some(RateLimitWait {
+            kind,
+            retry_at: delay.map(|delay| Instant::now() + delay),
+        });
+        session
+            .chat_view
+            .push(MessageDisplay::System { content }.to_chat_message());
+        delay
+    }
+
+    pub(super) fn schedule_rate_limit_retry(&self, session_id: Uuid, delay: Duration) {
+        let event_tx = self.event_tx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            send_app_event(
+                &event_tx,
+                AppEvent::RateLimitRetryDue { session_id },
+                "rate_limit_retry_due",
+            );
+        });
+    }
+
+    /// Resend the last prompt once the wait is over, unless the user sent,
+    /// stopped or handed off in the meantime
+    pub(super) fn handle_rate_limit_retry_due(
+        &mut self,
+        session_id: Uuid,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let Some(tab_index) = self.state.tab_manager.session_index_by_id(session_id) else {
+            return Ok(Vec::new());
+        };
+        let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
+            return Ok(Vec::new());
+        };
+        let due = session
+            .rate_limit_wait
+            .and_then(|wait| wait.retry_at)
+            .is_some_and(|retry_at| retry_at <= Instant::now());
+        if !due || session.is_processing {
+            return Ok(Vec::new());
+        }
+        if session.agent_pid.is_some() {
+            // The failed process's stream has not been closed yet
+            self.schedule_rate_limit_retry(session_id, STREAM_END_RETRY);
+            return Ok(Vec::new());
+        }
+        let Some(prompt) = session.last_prompt.clone() else {
+            session.rate_limit_wait = None;
+            return Ok(Vec::new());
+        };
+        let (images, image_placeholders) = session
+            .last_prompt_images
+            .iter()
+            .map(|image| (image.path.clone(), image.placeholder.clone()))
+            .unzip();
+        tracing::info!(
+            %session_id,
+            attempt = session.rate_limit_retries,
+            "Resending prompt after rate limit"
+        );
+        self.submit_prompt_for_tab(tab_index, prompt, images, image_placeholders, true, None)
+    }
+
+    /// Sync status bar countdowns with the sessions' waits. Returns true when
+    /// one changed.
+    pub(super) fn tick_rate_limit_countdowns(&mut self) -> bool {
+        let now = Instant::now();
+        let mut changed = false;
+        for session in self.state.tab_manager.sessions_mut() {
+            let countdown = session.rate_limit_wait.map(|wait| wait.countdown(now));
+            if session.status_bar.rate_limit() != countdown {
+                session.status_bar.set_rate_limit(countdown);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Offer to hand a limited session to the configured fallback agent
+    pub(super) fn offer_rate_limit_fallback(&mut self, session_id: Uuid) {
+        let Some(fallback_agent) = self.config().rate_limit.fallback_agent else {
+            return;
+        };
+        if self.state.input_mode != InputMode::Normal
+            || !self
+                .config()
+                .is_provider_enabled_effective(fallback_agent, self.tools())
+        {
+            return;
+        }
+        let Some(session) = self.state.tab_manager.active_session() else {
+            return;
+        };
+        if session.id != session_id || session.agent_type == fallback_agent {
+            return;
+        }
+        let Some(wait) = session.rate_limit_wait else {
+            return;
+        };
+
+        let waiting = match wait.retry_at {
+            Some(_) => "Otherwise your prompt is resent here when the wait is over.",
+            None => "Otherwise nothing is retried here until you resend.",
+        };
+        let body = format!(
+            "{} is {}.\nHand this conversation to {} and resend your last prompt there?",
+            self.config().agent_display_name(session.agent_type),
+            wait.kind.label().to_lowercase(),
+            self.config().agent_display_name(fallback_agent)
+        );
+        self.state.close_overlays();
+        self.state.confirmation_dialog_state.show(
+            "Switch Agent",
+            body,
+            vec![
+                "The handoff opens in a new tab.".to_string(),
+                waiting.to_string(),
+            ],
+            ConfirmationType::Warning,
+            "Switch",
+            Some(ConfirmationContext::RateLimitFallback { session_id }),
+        );
+        self.state.input_mode = InputMode::Confirming;
+    }
+
+    /// Cancel the pending retry and continue the conversation on the fallback agent
+    pub(super) fn confirm_rate_limit_fallback(
+        &mut self,
+        session_id: Uuid,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let Some(fallback_agent) = self.config().rate_limit.fallback_agent else {
+            return Ok(Vec::new());
+        };
+        let fallback_model = self
+            .config()
+            .rate_limit
+            .fallback_model
+            .clone()
+            .unwrap_or_else(|| self.config().default_model_for(fallback_agent));
+
+        let Some(session) = self.state.tab_manager.session_by_id_mut(session_id) else {
+            return Ok(Vec::new());
+        };
+        if session.is_processing {
+            return Ok(Vec::new());
+        }
+        session.rate_limit_wait = None;
+        session.rate_limit_retries = 0;
+        let last_prompt = session.last_prompt.clone();
+        let last_prompt_images = session.last_prompt_images.clone();
+        self.state.pending_handoff_request = Some(Self::handoff_request(session));
+
+        let effects = self.execute_handoff_session(fallback_agent, fallback_model)?;
+        if let (Some(text), Some(session)) =
+            (last_prompt, self.state.tab_manager.active_session_mut())
+        {
+            // Sent after the handoff context turn completes
+            session.queue_message(QueuedMessage {
+                id: Uuid::new_v4(),
+                mode: QueuedMessageMode::FollowUp,
+                text,
+                images: last_prompt_images,
+                created_at: Utc::now(),
+            });
+            session.update_status();
+        }
+        Ok(effects)
+    }
+
+    /// Handoff source captured from a session
+    pub(super) fn handoff_request(session: &AgentSession) -> PendingHandoffRequest {
+        PendingHandoffRequest {
+            source_agent_type: session.agent_type,
+            agent_mode: session.agent_mode,
+            reasoning_effort: session.reasoning_effort,
+            workspace_id: session.workspace_id,
+            working_dir: session.working_dir.clone(),
+            project_name: session.project_name.clone(),
+            workspace_name: session.workspace_name.clone(),
+            pr_number: session.pr_number,
+            handoff_prompt: Arc::from(app_prompt::build_handoff_prompt(
+                session.chat_view.messages(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{Local, Utc};
+use uuid::Uuid;
+
+use crate::agent::MessageDisplay;
+use crate::core::services::{CreateScheduleParams, ScheduleService, UpdateScheduleParams};
+use crate::data::{QueuedMessage, QueuedMessageMode, ScheduledPrompt};
+use crate::ui::app::{send_app_event, App};
+use crate::ui::components::{ScheduleAddTarget, ScheduleRow};
+use crate::ui::effect::Effect;
+use crate::ui::events::{AppEvent, InputMode, WorkspaceCreated};
+
+/// How often the TUI checks for due scheduled prompts
+const SCHEDULE_TICK_INTERVAL: Duration = Duration::from_secs(30);
+
+impl App {
+    pub(super) fn spawn_schedule_ticker(&self) {
+        let event_tx = self.event_tx.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(SCHEDULE_TICK_INTERVAL);
+            loop {
+                ticker.tick().await;
+                if !send_app_event(&event_tx, AppEvent::ScheduleTick, "schedule_tick") {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Claim the scheduled prompts that are due and queue each one
+    pub(super) fn run_due_schedules(&mut self) -> Vec<Effect> {
+        let claimed = match ScheduleService::claim_due(&self.core, Utc::now()) {
+            Ok(claimed) => claimed,
+            Err(err) => {
+                tracing::warn!(error = %err, "Failed to check scheduled prompts");
+                return Vec::new();
+            }
+        };
+        let mut effects = Vec::new();
+        for entry in claimed {
+            effects.extend(self.run_schedule(entry));
+        }
+        // Next run times moved forward
+        self.refresh_schedule_dialog();
+        effects
+    }
+
+    /// Queue a scheduled prompt on its workspace, creating the workspace
+    /// first when the entry asks for a fresh one
+    fn run_schedule(&mut self, entry: ScheduledPrompt) -> Vec<Effect> {
+        tracing::info!(schedule_id = %entry.id, name = %entry.name, "Running scheduled prompt");
+        let Some(workspace_id) = entry.workspace_id else {
+            return vec![Effect::CreateScheduledWorkspace {
+                schedule_id: entry.id,
+                repo_id: entry.repository_id,
+            }];
+        };
+        let result = self.queue_scheduled_prompt(workspace_id, &entry);
+        self.finish_schedule_run(&entry, result)
+    }
+
+    pub(super) fn handle_scheduled_workspace_created(
+        &mut self,
+        schedule_id: Uuid,
+        result: Result<WorkspaceCreated, String>,
+    ) -> anyhow::Result<Vec<Effect>> {
+        // The prompt is queued right away and starts once setup succeeds
+        let mut effects = match &result {
+            Ok(created) => self.start_workspace_setup(created.workspace_id),
+            Err(_) => Vec::new(),
+        };
+        let entry = match ScheduleService::get(&self.core, schedule_id) {
+            Ok(entry) => entry,
+            Err(err) => {
+                tracing::warn!(%schedule_id, error = %err, "Scheduled prompt disappeared");
+                return Ok(effects);
+            }
+        };
+        let result = result.and_then(|created| {
+            self.refresh_sidebar_data();
+            self.queue_scheduled_prompt(created.workspace_id, &entry)
+        });
+        effects.extend(self.finish_schedule_run(&entry, result));
+        Ok(effects)
+    }
+
+    fn finish_schedule_run(
+        &mut self,
+        entry: &ScheduledPrompt,
+        result: Result<Vec<Effect>, String>,
+    ) -> Vec<Effect> {
+        let error = result.as_ref().err().cloned();
+        ScheduleService::record_run(&self.core, entry.id, Utc::now(), error.as_deref());
+        self.refresh_schedule_dialog();
+        match result {
+            Ok(effects) => effects,
+            Err(err) => {
+                tracing::warn!(schedule_id = %entry.id, error = %err, "Scheduled prompt failed");
+                self.state.set_timed_footer_message(
+                    format!("Scheduled prompt \"{}\" failed: {}", entry.name, err),
+                    Duration::from_secs(5),
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    /// Open (or reuse) the workspace's tab without leaving the current one,
+    /// then queue the prompt and start it if the agent is idle
+    fn queue_scheduled_prompt(
+        &mut self,
+        workspace_id: Uuid,
+        entry: &ScheduledPrompt,
+    ) -> Result<Vec<Effect>, String> {
+        let tab_index = match self.find_tab_for_workspace(workspace_id) {
+            Some(index) => index,
+            None => {
+                let previous = (!self.state.tab_manager.is_empty())
+                    .then(|| self.state.tab_manager.active_index());
+                self.open_workspace_with_options(workspace_id, false);
+                let index = self
+                    .find_tab_for_workspace(workspace_id)
+                    .ok_or_else(|| "Could not open the workspace".to_string())?;
+                if let Some(previous) = previous.filter(|previous| *previous != index) {
+                    self.state.tab_manager.switch_to(previous);
+                    self.sync_footer_spinner();
+                }
+                index
+            }
+        };
+
+        let is_active = self.state.tab_manager.active_index() == tab_index;
+        let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
+            return Err("Workspace tab is not an agent session".to_string());
+        };
+        session.queue_message(QueuedMessage {
+            id: Uuid::new_v4(),
+            mode: QueuedMessageMode::FollowUp,
+            text: entry.prompt.clone(),
+            images: Vec::new(),
+            created_at: Utc::now(),
+        });
+        session.chat_view.push(
+            MessageDisplay::System {
+                content: format!("Scheduled prompt \"{}\" ({})", entry.name, entry.schedule),
+            }
+            .to_chat_message(),
+        );
+        if !is_active {
+            session.needs_attention = true;
+        }
+        session.update_status();
+
+        // Busy sessions pick the prompt up from the queue when their turn ends
+        if !session.can_start_queued_prompt() {
+            return Ok(Vec::new());
+        }
+        self.drain_queue_for_tab(tab_index)
+            .map_err(|err| format!("Failed to start the prompt: {}", err))
+    }
+
+    pub(super) fn open_schedules(&mut self) {
+        let add_target = self.schedule_add_target();
+        let rows = self.schedule_rows();
+        self.state.close_overlays();
+        self.state.schedule_dialog_state.show(rows, add_target);
+        self.state.input_mode = InputMode::ShowingSchedules;
+    }
+
+    fn refresh_schedule_dialog(&mut self) {
+        if self.state.schedule_dialog_state.is_visible() {
+            let rows = self.schedule_rows();
+            self.state.schedule_dialog_state.set_rows(rows);
+        }
+    }
+
+    fn schedule_rows(&self) -> Vec<ScheduleRow> {
+        let entries = match ScheduleService::list(&self.core) {
+            Ok(entries) => entries,
+            Err(err) => {
+                tracing::warn!(error = %err, "Failed to load scheduled prompts");
+                return Vec::new();
+            }
+        };
+        let repo_names: HashMap<Uuid, String> = self
+            .repo_dao()
+            .and_then(|dao| dao.get_all().ok())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|repo| (repo.id, repo.name))
+            .collect();
+
+        entries
+            .into_iter()
+            .map(|entry| {
+                let repo = repo_names
+                    .get(&entry.repository_id)
+                    .cloned()
+                    .unwrap_or_else(|| "unknown repository".to_string());
+                let workspace = match entry.workspace_id {
+                    Some(id) => self
+                        .workspace_dao()
+                        .and_then(|dao| dao.get_by_id(id).ok().flatten())
+                        .map(|workspace| workspace.name)
+                        .unwrap_or_else(|| "missing workspace".to_string()),
+                    None => "new workspace".to_string(),
+                };
+                ScheduleRow {
+                    target: format!("{} / {}", repo, workspace),
+                    entry,
+                }
+            })
+            .collect()
+    }
+
+    /// New entries from the dialog run in the active tab's workspace
+    fn schedule_add_target(&self) -> Option<ScheduleAddTarget> {
+        let session = self.state.tab_manager.active_session()?;
+        let workspace_id = session.workspace_id?;
+        let workspace = self
+            .workspace_dao()?
+            .get_by_id(workspace_id)
+            .ok()
+            .flatten()?;
+        let label = match &session.project_name {
+            Some(project) => format!("{} / {}", project, workspace.name),
+            None => workspace.name.clone(),
+        };
+        Some(ScheduleAddTarget {
+            repository_id: workspace.repository_id,
+            workspace_id,
+            label,
+        })
+    }
+
+    /// Enter: add the typed entry, or run the selected one now
+    pub(super) fn confirm_schedule_dialog(&mut self) -> anyhow::Result<Vec<Effect>> {
+        if self.state.schedule_dialog_state.is_adding() {
+            self.add_schedule_from_dialog();
+            return Ok(Vec::new());
+        }
+        let Some(entry) = self.state.schedule_dialog_state.selected_entry().cloned() else {
+            return Ok(Vec::new());
+        };
+        self.state.set_timed_footer_message(
+            format!("Running \"{}\" now", entry.name),
+            Duration::from_secs(3),
+        );
+        Ok(self.run_schedule(entry))
+    }
+
+    fn add_schedule_from_dialog(&mut self) {
+        let dialog = &mut self.state.schedule_dialog_state;
+        let Some(target) = dialog.add_target().cloned() else {
+            return;
+        };
+        let (schedule, prompt) = match dialog.parse_input() {
+            Ok(parts) => parts,
+            Err(err) => {
+                dialog.set_error(err);
+                return;
+            }
+        };
+        let created = ScheduleService::create(
+            &self.core,
+            CreateScheduleParams {
+                repository_id: target.repository_id,
+                workspace_id: Some(target.workspace_id),
+                name: None,
+                schedule,
+                prompt,
+                enabled: true,
+            },
+        );
+        match created {
+            Ok(entry) => {
+                self.state.schedule_dialog_state.cancel_adding();
+                self.refresh_schedule_dialog();
+                let next = entry
+                    .next_run_at
+                    .map(|next| {
+                        format!(
+                            "next run {}",
+                            next.with_timezone(&Local).format("%a %b %d %H:%M")
+                        )
+                    })
+                    .unwrap_or_else(|| "it never fires".to_string());
+                self.state.set_timed_footer_message(
+                    format!("Scheduled \"{}\", {}", entry.name, next),
+                    Duration::from_secs(4),
+                );
+            }
+            Err(err) => self.state.schedule_dialog_state.set_error(err.to_string()),
+        }
+    }
+
+    fn toggle_selected_schedule(&mut self) {
+        let Some(entry) = self.state.schedule_dialog_state.selected_entry().cloned() else {
+            return;
+        };
+        let params = UpdateScheduleParams {
+            enabled: Some(!entry.enabled),
+            ..Default::default()
+        };
+        if let Err(err) = ScheduleService::update(&self.core, entry.id, params) {
+            self.state.schedule_dialog_state.set_error(err.to_string());
+            return;
+        }
+        self.refresh_schedule_dialog();
+    }
+
+    pub(super) fn delete_selected_schedule(&mut self) {
+        let Some(entry) = self.state.schedule_dialog_state.selected_entry().cloned() else {
+            return;
+        };
+        if let Err(err) = ScheduleService::delete(&self.core, entry.id) {
+            self.state.schedule_dialog_state.set_error(err.to_string());
+            return;
+        }
+        self.refresh_schedule_dialog();
+        self.state.set_timed_footer_message(
+            format!("Deleted scheduled prompt \"{}\"", entry.name),
+            Duration::from_secs(3),
+        );
+    }
+
+    /// Typed characters: text while adding, otherwise single-key actions
+    pub(super) fn handle_schedule_dialog_char(&mut self, c: char) {
+        if let Some(input) = self.state.schedule_dialog_state.input.as_mut() {
+            input.insert_char(c);
+            return;
+        }
+        match c {
+            ' ' => self.toggle_selected_schedule(),
+            'x' => self.delete_selected_schedule(),
+            'j' => self.state.schedule_dialog_state.select_next(),
+            'k' => self.state.schedule_dialog_state.select_previous(),
+            'a' => {
+                if !self.state.schedule_dialog_state.start_adding() {
+                    self.state.set_timed_footer_message(
+                        "Open a workspace tab to schedule prompts for it".to_string(),
+                        Duration::from_secs(3),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -21,18 +21,26 @@ impl App {
                 && self.state.project_picker_state.is_visible())
             && !(self.state.input_mode == InputMode::ImportingSession
                 && self.state.session_import_state.is_visible())
+            && !(self.state.input_mode == InputMode::SearchingHistory
+                && self.state.history_search_state.is_visible())
             && !(self.state.input_mode == InputMode::CommandPalette
                 && self.state.command_palette_state.is_visible())
             && !(self.state.input_mode == InputMode::SlashMenu
                 && self.state.slash_menu_state.is_visible())
+            && !(self.state.input_mode == InputMode::PickingMention
+                && self.state.mention_picker_state.is_visible())
             && !(self.state.input_mode == InputMode::SelectingTheme
                 && self.state.theme_picker_state.is_visible())
             && !(self.state.input_mode == InputMode::SelectingModel
                 && self.state.model_selector_state.is_visible())
             && !(self.state.input_mode == InputMode::SelectingReasoning
                 && self.state.reasoning_selector_state.is_visible())
+            && !(self.state.input_mode == InputMode::SelectingCodexPermissions
+                && self.state.codex_permissions_state.is_visible())
             && !(self.state.input_mode == InputMode::SelectingProviders
                 && self.state.provider_selector_state.is_visible())
+            && !(self.state.input_mode == InputMode::SelectingRaceContenders
+                && self.state.race_selector_state.is_visible())
     }
 
     pub(super) fn raw_events_list_visible_height(&self) -> usize {
@@ -85,6 +93,15 @@ impl App {
             for _ in 0..*pending_down {
                 self.state.session_import_state.select_next();
             }
+        } else if self.state.input_mode == InputMode::SearchingHistory
+            && self.state.history_search_state.is_visible()
+        {
+            for _ in 0..*pending_up {
+                self.state.history_search_state.select_prev();
+            }
+            for _ in 0..*pending_down {
+                self.state.history_search_state.select_next();
+            }
         } else if self.state.input_mode == InputMode::CommandPalette
             && self.state.command_palette_state.is_visible()
         {
@@ -103,6 +120,15 @@ impl App {
             for _ in 0..*pending_down {
                 self.state.slash_menu_state.select_next();
             }
+        } else if self.state.input_mode == InputMode::PickingMention
+            && self.state.mention_picker_state.is_visible()
+        {
+            for _ in 0..*pending_up {
+                self.state.mention_picker_state.select_prev();
+            }
+            for _ in 0..*pending_down {
+                self.state.mention_picker_state.select_next();
+            }
         } else if self.state.input_mode == InputMode::SelectingTheme
             && self.state.theme_picker_state.is_visible()
         {
@@ -121,6 +147,15 @@ impl App {
             for _ in 0..*pending_down {
                 self.state.reasoning_selector_state.select_next();
             }
+        } else if self.state.input_mode == InputMode::SelectingCodexPermissions
+            && self.state.codex_permissions_state.is_visible()
+        {
+            for _ in 0..*pending_up {
+                self.state.codex_permissions_state.select_previous();
+            }
+            for _ in 0..*pending_down {
+                self.state.codex_permissions_state.select_next();
+            }
         } else if self.state.input_mode == InputMode::SelectingProviders
             && self.state.provider_selector_state.is_visible()
         {
@@ -130,6 +165,15 @@ impl App {
             for _ in 0..*pending_down {
                 self.state.provider_selector_state.select_next();
             }
+        } else if self.state.input_mode == InputMode::SelectingRaceContenders
+            && self.state.race_selector_state.is_visible()
+        {
+            for _ in 0..*pending_up {
+                self.state.race_selector_state.select_previous();
+            }
+            for _ in 0..*pending_down {
+                self.state.race_selector_state.select_next();
+            }
         } else if self.state.tab_manager.active_is_file() {
             let visible_height = self
                 .state
@@ -0,0 +1,236 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::agent::MessageDisplay;
+use crate::data::{QueuedImageAttachment, QueuedMessage, QueuedMessageMode};
+use crate::git::{WorkspaceHooks, HOOKS_FILE};
+use crate::ui::app::App;
+use crate::ui::components::{DeferredPrompt, SetupStatus, WorkspaceSetupState};
+use crate::ui::effect::Effect;
+
+impl App {
+    /// Run the repository's setup hooks for a new workspace. Until they
+    /// succeed, prompts for the workspace are held back.
+    pub(super) fn start_workspace_setup(&mut self, workspace_id: Uuid) -> Vec<Effect> {
+        let Some(workspace) = self
+            .workspace_dao()
+            .and_then(|dao| dao.get_by_id(workspace_id).ok().flatten())
+        else {
+            return Vec::new();
+        };
+        let Some(root_path) = self
+            .repo_dao()
+            .and_then(|dao| dao.get_by_id(workspace.repository_id).ok().flatten())
+            .and_then(|repo| repo.base_path)
+        else {
+            return Vec::new();
+        };
+
+        let hooks = match WorkspaceHooks::load(&root_path) {
+            Ok(Some(hooks)) if hooks.has_setup() => hooks,
+            Ok(_) => {
+                // Hooks removed since a failed run: release the held prompts
+                if !self.state.workspace_setups.contains_key(&workspace_id) {
+                    return Vec::new();
+                }
+                return self
+                    .handle_workspace_setup_finished(workspace_id, Ok(()))
+                    .unwrap_or_else(|err| {
+                        tracing::warn!(%workspace_id, error = %err, "Failed to send held prompts");
+                        Vec::new()
+                    });
+            }
+            Err(err) => {
+                tracing::warn!(%workspace_id, error = %err, "Invalid workspace hooks");
+                self.fail_workspace_setup(workspace_id, err);
+                return Vec::new();
+            }
+        };
+
+        self.state
+            .workspace_setups
+            .entry(workspace_id)
+            .and_modify(|setup| setup.restart())
+            .or_insert_with(WorkspaceSetupState::running);
+        vec![Effect::RunWorkspaceSetup {
+            workspace_id,
+            hooks,
+            root_path,
+            workspace_path: workspace.path,
+            workspace_name: workspace.name,
+            branch: workspace.branch,
+        }]
+    }
+
+    fn fail_workspace_setup(&mut self, workspace_id: Uuid, error: String) {
+        let setup = self
+            .state
+            .workspace_setups
+            .entry(workspace_id)
+            .or_insert_with(WorkspaceSetupState::running);
+        setup.push_line(error.clone());
+        setup.status = SetupStatus::Failed(error);
+    }
+
+    pub(super) fn handle_workspace_setup_finished(
+        &mut self,
+        workspace_id: Uuid,
+        result: Result<(), String>,
+    ) -> anyhow::Result<Vec<Effect>> {
+        // Archived or cleaned up while setup was running
+        if !self.state.workspace_setups.contains_key(&workspace_id) {
+            return Ok(Vec::new());
+        }
+        let tab_index = self.find_tab_for_workspace(workspace_id);
+        let is_active = tab_index == Some(self.state.tab_manager.active_index());
+
+        if let Err(err) = result {
+            tracing::warn!(%workspace_id, error = %err, "Workspace setup failed");
+            self.fail_workspace_setup(workspace_id, err.clone());
+            if let Some(session) = tab_index.and_then(|i| self.state.tab_manager.session_mut(i)) {
+                if !is_active {
+                    session.needs_attention = true;
+                }
+            }
+            self.state.set_timed_footer_message(
+                format!("Workspace setup failed: {} · /setup to retry", err),
+                Duration::from_secs(5),
+            );
+            return Ok(Vec::new());
+        }
+
+        let Some(setup) = self.state.workspace_setups.remove(&workspace_id) else {
+            return Ok(Vec::new());
+        };
+        let Some(tab_index) = tab_index else {
+            return Ok(Vec::new());
+        };
+        let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
+            return Ok(Vec::new());
+        };
+        session.chat_view.push(
+            MessageDisplay::System {
+                content: format!("Workspace setup finished in {}s", setup.elapsed().as_secs()),
+            }
+            .to_chat_message(),
+        );
+        if session.is_processing || session.agent_pid.is_some() {
+            return Ok(Vec::new());
+        }
+
+        match setup.deferred_prompt {
+            Some(deferred) => self.submit_prompt_for_tab(
+                tab_index,
+                deferred.prompt,
+                deferred.images,
+                deferred.image_placeholders,
+                true,
+                deferred.stdin_payload,
+            ),
+            None => self.drain_queue_for_tab(tab_index),
+        }
+    }
+
+    /// Whether the tab's workspace is still being set up (or failed to be)
+    pub(super) fn setup_blocks_tab(&self, tab_index: usize) -> bool {
+        self.state
+            .tab_manager
+            .session(tab_index)
+            .and_then(|session| session.workspace_id)
+            .is_some_and(|id| self.state.workspace_setups.contains_key(&id))
+    }
+
+    /// Hold a prompt until the tab's workspace setup succeeds: visible
+    /// prompts join the queue, hidden ones (fork seeds) are kept aside
+    pub(super) fn defer_prompt_until_setup(
+        &mut self,
+        tab_index: usize,
+        prompt: String,
+        images: Vec<PathBuf>,
+        image_placeholders: Vec<String>,
+        hidden: bool,
+        stdin_payload: Option<String>,
+    ) {
+        let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
+            return;
+        };
+        let Some(setup) = session
+            .workspace_id
+            .and_then(|id| self.state.workspace_setups.get_mut(&id))
+        else {
+            return;
+        };
+
+        if hidden {
+            if setup.deferred_prompt.is_some() {
+                tracing::warn!("Replacing a prompt held back for workspace setup");
+            }
+            setup.deferred_prompt = Some(DeferredPrompt {
+                prompt,
+                images,
+                image_placeholders,
+                stdin_payload,
+            });
+        } else {
+            let images = images
+                .into_iter()
+                .zip(image_placeholders)
+                .map(|(path, placeholder)| QueuedImageAttachment { path, placeholder })
+                .collect();
+            session.queue_message(QueuedMessage {
+                id: Uuid::new_v4(),
+                mode: QueuedMessageMode::FollowUp,
+                text: prompt,
+                images,
+                created_at: Utc::now(),
+            });
+        }
+        let message = if setup.is_running() {
+            "Queued until workspace setup finishes"
+        } else {
+            "Workspace setup failed · /setup to retry, then the prompt is sent"
+        };
+        self.state
+            .set_timed_footer_message(message.to_string(), Duration::from_secs(3));
+    }
+
+    /// `/setup`: run the active workspace's setup hooks again
+    pub(super) fn rerun_workspace_setup(&mut self) -> Vec<Effect> {
+        let Some(workspace_id) = self
+            .state
+            .tab_manager
+            .active_session()
+            .and_then(|session| session.workspace_id)
+        else {
+            self.state.set_timed_footer_message(
+                "Open a workspace tab to run its setup".to_string(),
+                Duration::from_secs(3),
+            );
+            return Vec::new();
+        };
+        if self
+            .state
+            .workspace_setups
+            .get(&workspace_id)
+            .is_some_and(|setup| setup.is_running())
+        {
+            self.state.set_timed_footer_message(
+                "Workspace setup is already running".to_string(),
+                Duration::from_secs(3),
+            );
+            return Vec::new();
+        }
+
+        let effects = self.start_workspace_setup(workspace_id);
+        if effects.is_empty() && !self.state.workspace_setups.contains_key(&workspace_id) {
+            self.state.set_timed_footer_message(
+                format!("No setup hooks in {}", HOOKS_FILE),
+                Duration::from_secs(3),
+            );
+        }
+        effects
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::agent::supervisor::{self, ProcessExit, RestartPolicy};
+use crate::agent::MessageDisplay;
+use crate::ui::app::{send_app_event, App};
+use crate::ui::effect::Effect;
+use crate::ui::events::AppEvent;
+use crate::ui::session::AgentSession;
+
+/// Wait before checking again when a restart is due before the stream ended
+const STREAM_END_RETRY: Duration = Duration::from_millis(200);
+
+impl App {
+    /// Decide what follows a process exit reported while a turn was running.
+    /// Returns the backoff delay when the session should be resumed.
+    pub(super) fn plan_crash_restart(
+        policy: &RestartPolicy,
+        session: &mut AgentSession,
+        exit: &ProcessExit,
+    ) -> Option<Duration> {
+        // A user stop clears the PID before the exit is reported
+        if !session.is_processing || session.agent_pid.is_none() {
+            return None;
+        }
+        let kind = exit.classify();
+        if !matches!(
+            kind,
+            supervisor::ExitKind::Crash | supervisor::ExitKind::Unrecoverable
+        ) {
+            return None;
+        }
+
+        let attempt = session.crash_restarts + 1;
+        let delay = policy
+            .next_delay(attempt)
+            .filter(|_| kind.should_restart() && session.agent_session_id.is_some());
+        let content = match delay {
+            Some(delay) => {
+                session.crash_restarts = attempt;
+                session.pending_restart = true;
+                supervisor::restart_notice(session.agent_type, exit, attempt, policy, delay)
+            }
+            None if !policy.enabled => return None,
+            None => {
+                let restarts = session.crash_restarts;
+                session.crash_restarts = 0;
+                supervisor::give_up_notice(session.agent_type, kind, restarts)
+            }
+        };
+        let display = MessageDisplay::System { content };
+        session.chat_view.push(display.to_chat_message());
+        delay
+    }
+
+    pub(super) fn schedule_agent_restart(&self, session_id: Uuid, delay: Duration) {
+        let event_tx = self.event_tx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            send_app_event(
+                &event_tx,
+                AppEvent::AgentRestartDue { session_id },
+                "agent_restart_due",
+            );
+        });
+    }
+
+    /// Resume a crashed session unless the user stopped or re-prompted it
+    /// during the backoff
+    pub(super) fn handle_agent_restart_due(
+        &mut self,
+        session_id: Uuid,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let Some(tab_index) = self.state.tab_manager.session_index_by_id(session_id) else {
+            return Ok(Vec::new());
+        };
+        let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
+            return Ok(Vec::new());
+        };
+        if !session.pending_restart || session.is_processing {
+            return Ok(Vec::new());
+        }
+        if session.agent_pid.is_some() {
+            // The crashed process's stream has not been closed yet
+            self.schedule_agent_restart(session_id, STREAM_END_RETRY);
+            return Ok(Vec::new());
+        }
+        session.pending_restart = false;
+        tracing::info!(
+            %session_id,
+            attempt = session.crash_restarts,
+            "Resuming agent session after crash"
+        );
+        self.submit_prompt_for_tab(
+            tab_index,
+            supervisor::RESUME_PROMPT.to_string(),
+            Vec::new(),
+            Vec::new(),
+            true,
+            None,
+        )
+    }
+}
@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use crate::agent::events::ControlRequestEvent;
+use crate::agent::tool_approval::{self, ToolApprovalChoice};
+use crate::agent::{AgentInput, AgentType};
+use crate::ui::app::App;
+use crate::ui::components::{InlinePromptState, PermissionChoice, ProcessingState};
+use crate::ui::effect::Effect;
+use crate::ui::session::AgentSession;
+
+impl App {
+    /// Queue a Claude tool call or Codex approval request for an approval
+    /// card. Calls to tools already allowed for the session are answered
+    /// without asking.
+    pub(super) fn queue_tool_approval(session: &mut AgentSession, request: ControlRequestEvent) {
+        session.tool_approval_queue.push_back(request);
+        Self::show_next_tool_approval(session);
+    }
+
+    /// Answer the approval card for `request_id` with the chosen option
+    pub(super) fn submit_tool_approval(
+        &mut self,
+        request_id: &str,
+        option_id: &str,
+        reason: Option<String>,
+    ) -> Vec<Effect> {
+        let choice = option_id
+            .parse::<ToolApprovalChoice>()
+            .unwrap_or(ToolApprovalChoice::Deny);
+        let agent_type = self
+            .state
+            .tab_manager
+            .active_session()
+            .map(|session| session.agent_type);
+        if agent_type.is_some_and(|agent_type| !choice.is_offered(agent_type)) {
+            return Vec::new();
+        }
+        let (request, workspace_id, resumed) = {
+            let Some(session) = self.state.tab_manager.active_session_mut() else {
+                return Vec::new();
+            };
+            let Some(index) = session
+                .tool_approval_queue
+                .iter()
+                .position(|request| request.request_id == request_id)
+            else {
+                return Vec::new();
+            };
+            let Some(request) = session.tool_approval_queue.remove(index) else {
+                return Vec::new();
+            };
+            session.inline_prompt = None;
+            session
+                .pending_tool_permissions
+                .retain(|_, pending| pending != request_id);
+            if matches!(
+                choice,
+                ToolApprovalChoice::AllowSession | ToolApprovalChoice::AllowAlways
+            ) {
+                session
+                    .session_allowed_tools
+                    .insert(request.tool_name.clone());
+            }
+            Self::send_tool_approval(session, &request, choice, reason.as_deref());
+            Self::show_next_tool_approval(session);
+            (
+                request,
+                session.workspace_id,
+                session.inline_prompt.is_none(),
+            )
+        };
+
+        // The agent carries on unless another card is waiting
+        if resumed {
+            self.state.start_footer_spinner(None);
+        }
+        if choice == ToolApprovalChoice::AllowAlways {
+            match self
+                .core
+                .allow_tool_for_workspace(workspace_id, &request.tool_name)
+            {
+                Ok(()) => self.state.set_timed_footer_message(
+                    format!(
+                        "{} is now always allowed in this repository",
+                        request.tool_name
+                    ),
+                    Duration::from_secs(3),
+                ),
+                Err(err) => self.show_error("Tool Permission", &err),
+            }
+        }
+        Vec::new()
+    }
+
+    /// The approval card choices the agent supports, in display order
+    fn tool_approval_choices(agent_type: AgentType) -> Vec<PermissionChoice> {
+        ToolApprovalChoice::ALL
+            .into_iter()
+            .filter(|choice| choice.is_offered(agent_type))
+            .map(|choice| PermissionChoice {
+                id: choice.as_str().to_string(),
+                label: choice.label().to_string(),
+                requires_reason: choice == ToolApprovalChoice::Deny,
+            })
+            .collect()
+    }
+
+    /// Show the card for the oldest queued call unless one is already open
+    fn show_next_tool_approval(session: &mut AgentSession) {
+        if session.inline_prompt.is_some() {
+            return;
+        }
+        while let Some(request) = session.tool_approval_queue.pop_front() {
+            if session.session_allowed_tools.contains(&request.tool_name) {
+                Self::send_tool_approval(session, &request, ToolApprovalChoice::AllowOnce, None);
+                continue;
+            }
+            let tool_id = request
+                .tool_use_id
+                .clone()
+                .unwrap_or_else(|| request.request_id.clone());
+            session
+                .pending_tool_permissions
+                .insert(tool_id.clone(), request.request_id.clone());
+            session.inline_prompt = Some(InlinePromptState::new_tool_permission(
+                tool_id,
+                request.request_id.clone(),
+                request.tool_name.clone(),
+                tool_approval::preview(&request.tool_name, &request.input),
+                Self::tool_approval_choices(session.agent_type),
+            ));
+            session.tool_approval_queue.push_front(request);
+            session.chat_view.scroll_to_bottom();
+            return;
+        }
+    }
+
+    fn send_tool_approval(
+        session: &mut AgentSession,
+        request: &ControlRequestEvent,
+        choice: ToolApprovalChoice,
+        reason: Option<&str>,
+    ) {
+        if session.agent_type == AgentType::Codex {
+            let Some(input_tx) = session.agent_input_tx.clone() else {
+                tracing::warn!("Unable to answer Codex approval: missing input channel");
+                return;
+            };
+            let input = AgentInput::CodexApproval {
+                request_id: request.request_id.clone(),
+                choice,
+            };
+            tokio::spawn(async move {
+                if let Err(err) = input_tx.send(input).await {
+                    tracing::warn!("Failed to send Codex approval response: {}", err);
+                }
+            });
+            session.set_processing_state(ProcessingState::Thinking);
+            return;
+        }
+        let payload = tool_approval::permission_response(request, choice, reason);
+        let jsonl = match Self::build_control_response_jsonl(&request.request_id, payload) {
+            Ok(jsonl) => jsonl,
+            Err(err) => {
+                tracing::error!("Failed to build tool approval response: {}", err);
+                return;
+            }
+        };
+        let Some(input_tx) = session.agent_input_tx.clone() else {
+            tracing::warn!("Unable to answer tool approval: missing Claude input channel");
+            return;
+        };
+        tokio::spawn(async move {
+            if let Err(err) = input_tx.send(AgentInput::ClaudeJsonl(jsonl)).await {
+                tracing::warn!("Failed to send tool approval response: {}", err);
+            }
+        });
+        session.set_processing_state(ProcessingState::Thinking);
+    }
+}
@@ -0,0 +1,178 @@
+use std::io;
+use std::time::Duration;
+
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+use uuid::Uuid;
+
+use crate::agent::event_hooks::EventHookRun;
+use crate::config::{
+    expand_user_command, CommandOutput, CommandStep, UserCommand, UserCommandKind,
+};
+use crate::ui::app::App;
+use crate::ui::components::ChatMessage;
+use crate::ui::effect::Effect;
+use crate::ui::terminal_guard::TerminalGuard;
+
+impl App {
+    /// Run a `[[commands]]` entry from the slash menu, palette or a key
+    pub(super) async fn run_user_command(
+        &mut self,
+        name: &str,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+        guard: &mut TerminalGuard,
+    ) -> anyhow::Result<Vec<Effect>> {
+        match expand_user_command(&self.config().user_commands, name) {
+            Ok(steps) => self.run_command_steps(steps, terminal, guard).await,
+            Err(err) => {
+                self.state
+                    .set_timed_footer_message(err, Duration::from_secs(5));
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    /// Run actions up to the next script; the rest wait for it to succeed
+    async fn run_command_steps(
+        &mut self,
+        steps: Vec<CommandStep>,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+        guard: &mut TerminalGuard,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let mut effects = Vec::new();
+        let mut steps = steps.into_iter();
+        while let Some(step) = steps.next() {
+            match step {
+                CommandStep::Action(action) => {
+                    effects.extend(Box::pin(self.execute_action(action, terminal, guard)).await?);
+                }
+                CommandStep::Script(command) => {
+                    effects.push(self.start_user_script(command, steps.collect()));
+                    break;
+                }
+            }
+        }
+        Ok(effects)
+    }
+
+    /// Run a script in the active session's workspace
+    fn start_user_script(&mut self, command: UserCommand, then: Vec<CommandStep>) -> Effect {
+        let default_dir = self.config().working_dir.clone();
+        let (run, output) = match &command.kind {
+            UserCommandKind::Script { run, output, .. } => (run.clone(), *output),
+            UserCommandKind::Macro { .. } => (String::new(), CommandOutput::Show),
+        };
+
+        let mut session_id = None;
+        let mut working_dir = default_dir;
+        let mut message_index = None;
+        if let Some(session) = self.state.tab_manager.active_session_mut() {
+            session_id = Some(session.id);
+            if let Some(dir) = &session.working_dir {
+                working_dir = dir.clone();
+            }
+            if output == CommandOutput::Show {
+                let args = serde_json::json!({ "command": run }).to_string();
+                session.chat_view.push(ChatMessage::tool_with_exit(
+                    "Bash",
+                    args,
+                    "Running...".to_string(),
+                    None,
+                ));
+                message_index = Some(session.chat_view.len().saturating_sub(1));
+            }
+        }
+        if message_index.is_none() {
+            self.state.set_timed_footer_message(
+                format!("Running /{}...", command.name),
+                Duration::from_secs(3),
+            );
+        }
+
+        Effect::RunUserCommand {
+            session_id,
+            command,
+            working_dir,
+            message_index,
+            then,
+        }
+    }
+
+    /// Deliver a script's output, then carry on with the command if it passed
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn handle_user_command_finished(
+        &mut self,
+        session_id: Option<Uuid>,
+        command: UserCommand,
+        message_index: Option<usize>,
+        run: EventHookRun,
+        then: Vec<CommandStep>,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+        guard: &mut TerminalGuard,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let UserCommandKind::Script { output, .. } = command.kind else {
+            return Ok(Vec::new());
+        };
+        let tab_index = session_id
+            .and_then(|session_id| self.state.tab_manager.session_index_by_id(session_id));
+
+        if !run.success {
+            let failure = run.failure.unwrap_or_default();
+            if let (Some(session), Some(message_index)) = (
+                tab_index.and_then(|tab_index| self.state.tab_manager.session_mut(tab_index)),
+                message_index,
+            ) {
+                session.chat_view.update_tool_at(
+                    message_index,
+                    format!("Error: {}\n{}", failure, run.output),
+                    None,
+                );
+            }
+            self.state.set_timed_footer_message(
+                format!("/{} failed: {}", command.name, failure),
+                Duration::from_secs(5),
+            );
+            return Ok(Vec::new());
+        }
+
+        let mut effects = Vec::new();
+        let session = tab_index.and_then(|tab_index| self.state.tab_manager.session_mut(tab_index));
+        match (output, session) {
+            (CommandOutput::Show, Some(session)) => {
+                if let Some(message_index) = message_index {
+                    session
+                        .chat_view
+                        .update_tool_at(message_index, run.output, Some(0));
+                }
+            }
+            (CommandOutput::Show, None) => {
+                self.state.set_timed_footer_message(
+                    format!("/{} finished", command.name),
+                    Duration::from_secs(3),
+                );
+            }
+            _ if run.output.is_empty() => {
+                self.state.set_timed_footer_message(
+                    format!("/{} printed nothing", command.name),
+                    Duration::from_secs(3),
+                );
+            }
+            (CommandOutput::Insert, Some(session)) => {
+                session.input_box.insert_str(&run.output);
+            }
+            (CommandOutput::Submit, Some(session)) => {
+                let session_id = session.id;
+                effects.extend(self.queue_prompt(session_id, run.output, &command.name)?);
+            }
+            (_, None) => {
+                self.state.set_timed_footer_message(
+                    format!("/{} needs an open session", command.name),
+                    Duration::from_secs(3),
+                );
+            }
+        }
+
+        effects.extend(self.run_command_steps(then, terminal, guard).await?);
+        Ok(effects)
+    }
+}
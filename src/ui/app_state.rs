@@ -7,13 +7,17 @@ use ratatui::layout::Rect;
 
 use crate::agent::{AgentMode, AgentType, ReasoningEffort};
 use crate::ui::components::{
-    AddRepoDialogState, AgentSelectorState, BaseDirDialogState, CommandPaletteState,
-    ConfirmationDialogState, ErrorDialogState, HelpDialogState, KnightRiderSpinner,
-    LogoShineAnimation, MissingToolDialogState, ModelSelectorState, ProjectPickerState,
-    ProviderSelectorState, ReasoningSelectorState, SessionImportPickerState, SidebarData,
-    SidebarState, SlashMenuState, ThemePickerState,
+    AddRepoDialogState, AgentSelectorState, BaseDirDialogState, CheckpointDialogState,
+    CodexPermissionsSelectorState, CommandPaletteState, ConfirmationDialogState,
+    DiffCommentDialogState, ErrorDialogState, GoalDialogState, HelpDialogState,
+    HistorySearchPickerState, KnightRiderSpinner, LogoShineAnimation, MentionPickerState,
+    MissingToolDialogState, ModelSelectorState, ProjectPickerState, PromptVariablesDialogState,
+    ProviderSelectorState, RaceDialogState, RaceSelectorState, ReasoningSelectorState,
+    ScheduleDialogState, SessionImportPickerState, SidebarData, SidebarState, SlashMenuState,
+    TextInputState, ThemePickerState, UsageDialogState, WorkspaceSetupState,
 };
 use crate::ui::events::{InputMode, ViewMode};
+use crate::ui::race::Race;
 use crate::ui::tab_manager::TabManager;
 use uuid::Uuid;
 
@@ -167,18 +171,31 @@ pub struct AppState {
     pub add_repo_dialog_state: AddRepoDialogState,
     pub model_selector_state: ModelSelectorState,
     pub reasoning_selector_state: ReasoningSelectorState,
+    pub codex_permissions_state: CodexPermissionsSelectorState,
     pub theme_picker_state: ThemePickerState,
     pub agent_selector_state: AgentSelectorState,
     pub provider_selector_state: ProviderSelectorState,
+    pub race_selector_state: RaceSelectorState,
+    pub race_dialog_state: RaceDialogState,
+    pub diff_comment_dialog_state: DiffCommentDialogState,
+    pub goal_dialog_state: GoalDialogState,
+    pub prompt_variables_dialog_state: PromptVariablesDialogState,
+    /// Query typed in the chat search bar
+    pub chat_search_input: TextInputState,
+    pub checkpoint_dialog_state: CheckpointDialogState,
+    pub schedule_dialog_state: ScheduleDialogState,
     pub base_dir_dialog_state: BaseDirDialogState,
     pub project_picker_state: ProjectPickerState,
     pub session_import_state: SessionImportPickerState,
+    pub history_search_state: HistorySearchPickerState,
     pub confirmation_dialog_state: ConfirmationDialogState,
     pub error_dialog_state: ErrorDialogState,
+    pub usage_dialog_state: UsageDialogState,
     pub help_dialog_state: HelpDialogState,
     pub missing_tool_dialog_state: MissingToolDialogState,
     pub command_palette_state: CommandPaletteState,
     pub slash_menu_state: SlashMenuState,
+    pub mention_picker_state: MentionPickerState,
     pub command_buffer: String,
     pub sidebar_area: Option<Rect>,
     pub tab_bar_area: Option<Rect>,
@@ -215,6 +232,8 @@ pub struct AppState {
     pub pending_fork_request: Option<PendingForkRequest>,
     /// Pending handoff request data (set before agent selection)
     pub pending_handoff_request: Option<PendingHandoffRequest>,
+    /// Most recent race, kept until a contender is chosen
+    pub active_race: Option<Race>,
     /// Workspace IDs with in-flight sidebar operations
     pub busy_workspaces: HashSet<Uuid>,
     /// Repository IDs with in-flight sidebar operations
@@ -227,6 +246,10 @@ pub struct AppState {
     pub busy_footer_message: Option<String>,
     /// Pending branch updates captured while workspaces are busy
     pub pending_branch_updates: HashMap<Uuid, Option<String>>,
+    /// Setup hooks running or failed, by workspace; prompts wait until they succeed
+    pub workspace_setups: HashMap<Uuid, WorkspaceSetupState>,
+    /// Whether the terminal window has focus (assumed until told otherwise)
+    pub terminal_focused: bool,
     /// Pending Ctrl+N flow target while onboarding gates are being completed
     pub pending_new_project_target: Option<NewProjectTarget>,
     /// Current behavior context for the model picker
@@ -345,18 +368,30 @@ impl AppState {
             add_repo_dialog_state: AddRepoDialogState::new(),
             model_selector_state: ModelSelectorState::default(),
             reasoning_selector_state: ReasoningSelectorState::default(),
+            codex_permissions_state: CodexPermissionsSelectorState::default(),
             theme_picker_state: ThemePickerState::default(),
             agent_selector_state: AgentSelectorState::new(),
             provider_selector_state: ProviderSelectorState::new(),
+            race_selector_state: RaceSelectorState::new(),
+            race_dialog_state: RaceDialogState::new(),
+            diff_comment_dialog_state: DiffCommentDialogState::new(),
+            goal_dialog_state: GoalDialogState::new(),
+            prompt_variables_dialog_state: PromptVariablesDialogState::new(),
+            chat_search_input: TextInputState::new(),
+            checkpoint_dialog_state: CheckpointDialogState::new(),
+            schedule_dialog_state: ScheduleDialogState::new(),
             base_dir_dialog_state: BaseDirDialogState::new(),
             project_picker_state: ProjectPickerState::new(),
             session_import_state: SessionImportPickerState::new(),
+            history_search_state: HistorySearchPickerState::new(),
             confirmation_dialog_state: ConfirmationDialogState::new(),
             error_dialog_state: ErrorDialogState::new(),
+            usage_dialog_state: UsageDialogState::new(),
             help_dialog_state: HelpDialogState::new(),
             missing_tool_dialog_state: MissingToolDialogState::default(),
             command_palette_state: CommandPaletteState::new(),
             slash_menu_state: SlashMenuState::new(),
+            mention_picker_state: MentionPickerState::new(),
             command_buffer: String::new(),
             sidebar_area: None,
             tab_bar_area: None,
@@ -384,12 +419,15 @@ impl AppState {
             was_splash_visible: true, // Start on splash screen
             pending_fork_request: None,
             pending_handoff_request: None,
+            active_race: None,
             busy_workspaces: HashSet::new(),
             busy_repos: HashSet::new(),
             busy_repo_actions: HashSet::new(),
             busy_footer_message_active: false,
             busy_footer_message: None,
             pending_branch_updates: HashMap::new(),
+            workspace_setups: HashMap::new(),
+            terminal_focused: true,
             pending_new_project_target: None,
             model_picker_context: ModelPickerContext::SessionSelection,
         }
@@ -400,18 +438,29 @@ impl AppState {
         self.base_dir_dialog_state.hide();
         self.project_picker_state.hide();
         self.session_import_state.hide();
+        self.history_search_state.hide();
         self.model_selector_state.hide();
         self.model_picker_context = ModelPickerContext::SessionSelection;
         self.reasoning_selector_state.hide();
+        self.codex_permissions_state.hide();
         self.theme_picker_state.hide(true); // cancelled=true since we're closing all overlays
         self.agent_selector_state.hide();
         self.provider_selector_state.hide();
+        self.race_selector_state.hide();
+        self.race_dialog_state.hide();
+        self.diff_comment_dialog_state.hide();
+        self.goal_dialog_state.hide();
+        self.prompt_variables_dialog_state.hide();
+        self.checkpoint_dialog_state.hide();
+        self.schedule_dialog_state.hide();
         self.confirmation_dialog_state.hide();
         self.error_dialog_state.hide();
+        self.usage_dialog_state.hide();
         self.help_dialog_state.hide();
         self.missing_tool_dialog_state.hide();
         self.command_palette_state.hide();
         self.slash_menu_state.hide();
+        self.mention_picker_state.hide();
         self.pending_handoff_request = None;
     }
 
@@ -421,16 +470,27 @@ impl AppState {
             || self.add_repo_dialog_state.is_visible()
             || self.model_selector_state.is_visible()
             || self.reasoning_selector_state.is_visible()
+            || self.codex_permissions_state.is_visible()
             || self.theme_picker_state.is_visible()
             || self.agent_selector_state.is_visible()
             || self.provider_selector_state.is_visible()
+            || self.race_selector_state.is_visible()
+            || self.race_dialog_state.is_visible()
+            || self.diff_comment_dialog_state.is_visible()
+            || self.goal_dialog_state.is_visible()
+            || self.prompt_variables_dialog_state.is_visible()
+            || self.checkpoint_dialog_state.is_visible()
+            || self.schedule_dialog_state.is_visible()
             || self.confirmation_dialog_state.visible
             || self.error_dialog_state.is_visible()
+            || self.usage_dialog_state.is_visible()
             || self.help_dialog_state.is_visible()
             || self.missing_tool_dialog_state.is_visible()
             || self.session_import_state.is_visible()
+            || self.history_search_state.is_visible()
             || self.command_palette_state.is_visible()
             || self.slash_menu_state.is_visible()
+            || self.mention_picker_state.is_visible()
     }
 
     /// Start footer spinner with optional message
@@ -483,6 +543,14 @@ impl AppState {
         if self.session_import_state.visible && self.session_import_state.loading {
             return true;
         }
+        // History index refresh spinner is animating
+        if self.history_search_state.visible && self.history_search_state.indexing {
+            return true;
+        }
+        // Mention picker listing spinner is animating
+        if self.mention_picker_state.visible && self.mention_picker_state.loading {
+            return true;
+        }
         // Project picker loading spinner is animating
         if self.project_picker_state.visible && self.project_picker_state.loading {
             return true;
@@ -30,7 +30,7 @@ pub struct AgentSelectorState {
 #[derive(Debug, Clone)]
 struct AgentOption {
     agent_type: AgentType,
-    name: &'static str,
+    name: String,
     description: &'static str,
 }
 
@@ -41,28 +41,34 @@ impl Default for AgentSelectorState {
 }
 
 impl AgentSelectorState {
-    fn option_for(agent_type: AgentType) -> AgentOption {
+    /// Option for an agent; `acp_name` is the configured ACP agent's name
+    fn option_for(agent_type: AgentType, acp_name: &str) -> AgentOption {
         match agent_type {
             AgentType::Codex => AgentOption {
                 agent_type: AgentType::Codex,
-                name: "Codex CLI",
+                name: "Codex CLI".to_string(),
                 description: "OpenAI's code generation model",
             },
             AgentType::Claude => AgentOption {
                 agent_type: AgentType::Claude,
-                name: "Claude Code",
+                name: "Claude Code".to_string(),
                 description: "Anthropic's coding assistant",
             },
             AgentType::Gemini => AgentOption {
                 agent_type: AgentType::Gemini,
-                name: "Gemini CLI",
+                name: "Gemini CLI".to_string(),
                 description: "Google's Gemini coding assistant",
             },
             AgentType::Opencode => AgentOption {
                 agent_type: AgentType::Opencode,
-                name: "OpenCode",
+                name: "OpenCode".to_string(),
                 description: "OpenCode multi-provider assistant",
             },
+            AgentType::Acp => AgentOption {
+                agent_type: AgentType::Acp,
+                name: acp_name.to_string(),
+                description: "Agent Client Protocol agent from config",
+            },
         }
     }
 
@@ -72,6 +78,7 @@ impl AgentSelectorState {
             AgentType::Claude => Tool::Claude,
             AgentType::Gemini => Tool::Gemini,
             AgentType::Opencode => Tool::Opencode,
+            AgentType::Acp => Tool::Acp,
         }
     }
 
@@ -81,18 +88,18 @@ impl AgentSelectorState {
             selected: 0,
             agents: AgentType::preferred_order()
                 .into_iter()
-                .map(Self::option_for)
+                .map(|agent_type| Self::option_for(agent_type, agent_type.display_name()))
                 .collect(),
         }
     }
 
     /// Create a new agent selector with only available agents
-    pub fn with_available_agents(tools: &ToolAvailability) -> Self {
+    pub fn with_available_agents(tools: &ToolAvailability, acp_name: &str) -> Self {
         let mut agents = Vec::new();
 
         for agent_type in AgentType::preferred_order() {
             if tools.is_available(Self::tool_for(agent_type)) {
-                agents.push(Self::option_for(agent_type));
+                agents.push(Self::option_for(agent_type, acp_name));
             }
         }
 
@@ -110,12 +117,12 @@ impl AgentSelectorState {
     }
 
     /// Update the available agents list based on tool availability
-    pub fn update_available_agents(&mut self, tools: &ToolAvailability) {
+    pub fn update_available_agents(&mut self, tools: &ToolAvailability, acp_name: &str) {
         let mut agents = Vec::new();
 
         for agent_type in AgentType::preferred_order() {
             if tools.is_available(Self::tool_for(agent_type)) {
-                agents.push(Self::option_for(agent_type));
+                agents.push(Self::option_for(agent_type, acp_name));
             }
         }
 
@@ -235,7 +242,7 @@ impl AgentSelector {
                     Style::default().fg(Color::Cyan),
                 ),
                 Span::styled(
-                    agent.name,
+                    agent.name.as_str(),
                     if is_selected {
                         Style::default()
                             .fg(selected_fg)
@@ -0,0 +1,84 @@
+//! One-line search bar shown under the chat while searching the transcript
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+use super::{accent_error, accent_primary, bg_elevated, text_muted, text_primary, TextInputState};
+
+/// Chat search bar: query on the left, match count and hints on the right
+pub struct ChatSearchBar<'a> {
+    input: &'a TextInputState,
+    /// Selected match (1-based) and total
+    status: Option<(usize, usize)>,
+}
+
+impl<'a> ChatSearchBar<'a> {
+    pub fn new(input: &'a TextInputState, status: Option<(usize, usize)>) -> Self {
+        Self { input, status }
+    }
+}
+
+impl Widget for ChatSearchBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width < 8 {
+            return;
+        }
+
+        let bg_style = Style::default().bg(bg_elevated());
+        for x in area.x..area.x + area.width {
+            buf[(x, area.y)].set_style(bg_style).set_symbol(" ");
+        }
+
+        // Right side: "3/17 · Enter next · S-Enter prev · Esc close"
+        let count = match self.status {
+            _ if self.input.is_empty() => None,
+            Some((_, 0)) | None => Some(("no matches".to_string(), accent_error())),
+            Some((current, total)) => Some((format!("{}/{}", current, total), text_primary())),
+        };
+        let hints = "  ↓/Enter next · ↑ prev · Esc close";
+        let mut right = Vec::new();
+        if let Some((count, color)) = count {
+            right.push(Span::styled(count, bg_style.fg(color)));
+        }
+        let hints_width = hints.chars().count() as u16;
+        if area.width > hints_width + 30 {
+            right.push(Span::styled(hints, bg_style.fg(text_muted())));
+        }
+        let right_width = right
+            .iter()
+            .map(|span| span.content.chars().count() as u16)
+            .sum::<u16>()
+            + 1;
+
+        let prefix = Line::from(Span::styled(" / ", bg_style.fg(accent_primary())));
+        buf.set_line(area.x, area.y, &prefix, 3);
+
+        let input_area = Rect {
+            x: area.x + 3,
+            y: area.y,
+            width: area.width.saturating_sub(3 + right_width + 1),
+            height: 1,
+        };
+        self.input.render_with_placeholder(
+            input_area,
+            buf,
+            bg_style.fg(text_primary()),
+            "Search chat",
+            bg_style.fg(text_muted()),
+        );
+
+        if right_width < area.width.saturating_sub(8) {
+            buf.set_line(
+                area.x + area.width - right_width,
+                area.y,
+                &Line::from(right),
+                right_width,
+            );
+        }
+    }
+}
@@ -12,14 +12,15 @@ use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 use super::{
     render_minimal_scrollbar,
     theme::{
-        accent_error, accent_primary, accent_success, bg_base, bg_highlight, diff_add, diff_remove,
-        markdown_code_bg, text_muted, theme_revision, tool_block_bg, tool_command, tool_comment,
-        tool_output,
+        accent_error, accent_primary, accent_success, accent_warning, bg_base, bg_highlight,
+        diff_add, diff_remove, markdown_code_bg, text_muted, theme_revision, tool_block_bg,
+        tool_command, tool_comment, tool_output,
     },
     ChatMessage, MarkdownRenderer, MessageRole, ScrollbarMetrics, TurnSummary,
 };
 
 mod chat_view_cache;
+mod chat_view_search;
 
 // =============================================================================
 // Tool Block Builder - Opencode-style tool rendering
@@ -218,6 +219,7 @@ impl UserMessageBlockBuilder {
 }
 
 use self::chat_view_cache::LineCache;
+use self::chat_view_search::ChatSearch;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct SelectionPoint {
@@ -479,6 +481,8 @@ pub struct ChatView {
     last_extra_lines_start: usize,
     /// Label for the agent (e.g. "Claude", "Codex") shown above assistant messages
     agent_label: String,
+    /// Transcript search, while the search bar is open
+    search: Option<ChatSearch>,
 }
 
 /// Information about a hovered file path for rendering
@@ -517,6 +521,7 @@ impl ChatView {
             last_extra_lines: Vec::new(),
             last_extra_lines_start: 0,
             agent_label: "Claude".to_string(),
+            search: None,
         }
     }
 
@@ -559,12 +564,17 @@ impl ChatView {
     }
 
     /// Add a message to the chat
-    pub fn push(&mut self, message: ChatMessage) {
+    pub fn push(&mut self, mut message: ChatMessage) {
         // If we were streaming, finalize it
         if !self.streaming_messages.is_empty() {
             self.finalize_streaming();
         }
 
+        // Open collapsed tool output that matches the active search
+        if self.search.as_ref().is_some_and(|s| s.expands(&message)) {
+            message.is_collapsed = false;
+        }
+
         // Update previous message's spacing if needed (it may have changed)
         if !self.messages.is_empty() {
             if let Some(width) = self.cache_width {
@@ -618,6 +628,9 @@ impl ChatView {
 
         msg.content = content;
         msg.exit_code = exit_code;
+        if self.search.as_ref().is_some_and(|s| s.expands(msg)) {
+            msg.is_collapsed = false;
+        }
 
         // For Read tool on images, cache file size now (while file still exists)
         if msg.file_size.is_none() {
@@ -707,6 +720,7 @@ impl ChatView {
         self.streaming_cache = None;
         self.joiner_before.clear();
         self.streaming_joiner_before = None;
+        self.invalidate_search();
         // Keep cache_width so we don't have to recalculate on next render
     }
 
@@ -797,12 +811,23 @@ impl ChatView {
     }
 
     fn user_message_line_indices(&self) -> Vec<usize> {
-        let mut indices = Vec::new();
+        self.message_start_lines()
+            .into_iter()
+            .zip(&self.messages)
+            .filter(|(_, msg)| msg.role == MessageRole::User)
+            .filter_map(|(line, _)| line)
+            .collect()
+    }
+
+    /// First rendered line of each message, if it has any cached lines
+    fn message_start_lines(&self) -> Vec<Option<usize>> {
+        let mut starts = Vec::with_capacity(self.messages.len());
         let mut flat_index = 0usize;
         let mut last_is_blank = false;
 
-        for (msg_idx, msg) in self.messages.iter().enumerate() {
+        for msg_idx in 0..self.messages.len() {
             let Some(Some(cached)) = self.line_cache.entries.get(msg_idx) else {
+                starts.push(None);
                 continue;
             };
             let mut first_included: Option<usize> = None;
@@ -811,20 +836,16 @@ impl ChatView {
                 if is_blank && last_is_blank {
                     continue;
                 }
-                if msg.role == MessageRole::User && first_included.is_none() {
+                if first_included.is_none() {
                     first_included = Some(flat_index);
                 }
                 flat_index = flat_index.saturating_add(1);
                 last_is_blank = is_blank;
             }
-            if msg.role == MessageRole::User {
-                if let Some(idx) = first_included {
-                    indices.push(idx);
-                }
-            }
+            starts.push(first_included);
         }
 
-        indices
+        starts
     }
 
     pub fn set_scroll_from_top(&mut self, offset_from_top: usize, total: usize, visible: usize) {
@@ -1274,7 +1295,10 @@ impl ChatView {
 
         let mut out = Vec::with_capacity(visible_lines.len());
         for (line, line_index) in visible_lines {
-            let mut result_line = line.clone();
+            let mut result_line = match line_index {
+                Some(idx) => self.apply_search_highlight(line, idx),
+                None => line,
+            };
 
             // Apply selection highlight if applicable
             if let (Some((start, end)), Some(idx)) = (selection, line_index) {
@@ -2302,6 +2326,14 @@ impl ChatView {
 
         self.invalidate_theme_cache_if_needed();
 
+        // Lines about to be rebuilt may have gained or lost matches
+        if self.flat_cache_dirty
+            || self.flat_cache_width != Some(content.width)
+            || (self.streaming_cache.is_none() && !self.streaming_messages.is_empty())
+        {
+            self.invalidate_search();
+        }
+
         // Ensure cache is valid for current width
         self.ensure_cache(content.width);
         self.ensure_flat_cache();
@@ -2343,6 +2375,15 @@ impl ChatView {
 
         // Clamp scroll offset (respect selection lock if active)
         let max_scroll = total_lines.saturating_sub(visible_height);
+
+        // Bring the selected search hit a third of the way down the view
+        self.refresh_search(max_scroll.saturating_sub(self.scroll_offset.min(max_scroll)));
+        if let Some(line) = self.take_search_reveal() {
+            if self.selection_scroll_lock.is_none() {
+                let from_top = line.saturating_sub(visible_height / 3).min(max_scroll);
+                self.scroll_offset = max_scroll - from_top;
+            }
+        }
         let scroll_from_top = if let Some(lock) = self.selection_scroll_lock {
             let locked = lock.min(max_scroll);
             self.scroll_offset = max_scroll.saturating_sub(locked);
@@ -3276,4 +3317,58 @@ mod tests {
         let out = selection_to_copy_text(&lines, &joiners, start, end, 80).unwrap();
         assert_eq!(out, "para1\n\ncode\n\npara2");
     }
+
+    #[test]
+    fn test_search_matches_case_insensitively_across_spans() {
+        let line = Line::from(vec![Span::raw("┃  Error: "), Span::raw("error again")]);
+        let needle: Vec<char> = "error".chars().collect();
+        assert_eq!(
+            chat_view_search::find_in_line(&line, &needle),
+            vec![(3, 8), (10, 15)]
+        );
+        assert!(chat_view_search::find_in_line(&line, &['z']).is_empty());
+    }
+
+    #[test]
+    fn test_search_expands_collapsed_tools_and_wraps() {
+        let mut view = ChatView::new();
+        view.push(ChatMessage::user("find the needle"));
+        let mut tool = ChatMessage::tool("Bash", "{}", "haystack\nNEEDLE here");
+        tool.is_collapsed = true;
+        view.push(tool);
+
+        view.set_search_query("needle");
+        assert!(!view.messages[1].is_collapsed);
+
+        let area = Rect::new(0, 0, 80, 40);
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+        assert_eq!(view.search_status(), Some((1, 2)));
+
+        let rendered: Vec<String> = (0..area.height)
+            .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        let (y, line) = rendered
+            .iter()
+            .enumerate()
+            .find(|(_, line)| line.contains("find the needle"))
+            .unwrap();
+        let x = line[..line.find("needle").unwrap()].chars().count() as u16;
+        assert_eq!(buf[(x, y as u16)].bg, accent_warning());
+
+        view.search_next();
+        assert_eq!(view.search_status(), Some((2, 2)));
+        view.search_next();
+        assert_eq!(view.search_status(), Some((1, 2)));
+        view.search_prev();
+        assert_eq!(view.search_status(), Some((2, 2)));
+
+        // New output is searched as it arrives
+        view.push(ChatMessage::assistant("one more needle"));
+        view.render(area, &mut buf);
+        assert_eq!(view.search_status(), Some((2, 3)));
+
+        view.clear_search();
+        assert!(!view.is_searching());
+    }
 }
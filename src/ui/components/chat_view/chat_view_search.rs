@@ -0,0 +1,325 @@
+//! Transcript search for the chat view.
+//!
+//! Matches are found in the rendered lines, so highlights line up with what
+//! is on screen. Collapsed tool blocks whose output matches are expanded
+//! first, since their output isn't rendered otherwise.
+
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+use unicode_width::UnicodeWidthChar;
+
+use super::{accent_warning, bg_base, bg_highlight, ChatMessage, ChatView, MessageRole};
+
+/// One hit: a column range on a rendered line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct SearchMatch {
+    pub(super) line_index: usize,
+    pub(super) start_col: u16,
+    /// Exclusive
+    pub(super) end_col: u16,
+}
+
+/// Search state for one chat view
+#[derive(Debug, Clone, Default)]
+pub(super) struct ChatSearch {
+    /// Case-folded query
+    needle: Vec<char>,
+    matches: Vec<SearchMatch>,
+    /// Selected hit in `matches`
+    current: Option<usize>,
+    /// Matches must be recomputed before the next render
+    stale: bool,
+    /// Scroll the selected hit into view on the next render
+    reveal: bool,
+    /// Message to select the first hit from, instead of the view
+    anchor_message: Option<usize>,
+}
+
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn fold_str(s: &str) -> String {
+    s.chars().map(fold).collect()
+}
+
+/// Column ranges of non-overlapping hits of `needle` in a line
+pub(super) fn find_in_line(line: &Line<'_>, needle: &[char]) -> Vec<(u16, u16)> {
+    let mut chars: Vec<(char, u16, u16)> = Vec::new();
+    let mut col: u16 = 0;
+    for span in &line.spans {
+        for ch in span.content.chars() {
+            let width = UnicodeWidthChar::width(ch).unwrap_or(0) as u16;
+            chars.push((fold(ch), col, width));
+            col = col.saturating_add(width);
+        }
+    }
+
+    let mut ranges = Vec::new();
+    if needle.is_empty() || chars.len() < needle.len() {
+        return ranges;
+    }
+    let mut i = 0;
+    while i + needle.len() <= chars.len() {
+        let hit = chars[i..i + needle.len()]
+            .iter()
+            .zip(needle)
+            .all(|((c, _, _), n)| c == n);
+        if hit {
+            let (_, start, _) = chars[i];
+            let (_, last, width) = chars[i + needle.len() - 1];
+            ranges.push((start, last.saturating_add(width.max(1))));
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    ranges
+}
+
+/// Restyle the hits on a line; the selected one stands out
+fn highlight_matches(line: &Line<'static>, ranges: &[(u16, u16, bool)]) -> Line<'static> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut buffer = String::new();
+    let mut current_style: Option<Style> = None;
+    let mut col: u16 = 0;
+
+    for span in &line.spans {
+        for ch in span.content.chars() {
+            let width = UnicodeWidthChar::width(ch).unwrap_or(0) as u16;
+            let hit = ranges
+                .iter()
+                .find(|(start, end, _)| col >= *start && col < *end);
+            let style = match hit {
+                Some((_, _, true)) => span.style.bg(accent_warning()).fg(bg_base()),
+                Some((_, _, false)) => span.style.bg(bg_highlight()),
+                None => span.style,
+            };
+
+            if current_style != Some(style) && !buffer.is_empty() {
+                spans.push(Span::styled(
+                    std::mem::take(&mut buffer),
+                    current_style.unwrap_or_default(),
+                ));
+            }
+            current_style = Some(style);
+            buffer.push(ch);
+            col = col.saturating_add(width);
+        }
+    }
+    if !buffer.is_empty() {
+        spans.push(Span::styled(buffer, current_style.unwrap_or_default()));
+    }
+
+    Line::from(spans).style(line.style)
+}
+
+impl ChatSearch {
+    fn message_matches(&self, msg: &ChatMessage) -> bool {
+        if self.needle.is_empty() {
+            return false;
+        }
+        let needle: String = self.needle.iter().collect();
+        fold_str(&msg.content).contains(&needle)
+            || msg
+                .tool_args
+                .as_deref()
+                .is_some_and(|args| fold_str(args).contains(&needle))
+    }
+
+    /// Whether a collapsed tool message should open to show a hit
+    pub(super) fn expands(&self, msg: &ChatMessage) -> bool {
+        msg.role == MessageRole::Tool && msg.is_collapsed && self.message_matches(msg)
+    }
+}
+
+impl ChatView {
+    /// Search the transcript, selecting the hit nearest the current view
+    pub fn set_search_query(&mut self, query: &str) {
+        let search = self.search.get_or_insert_with(ChatSearch::default);
+        search.needle = query.chars().map(fold).collect();
+        search.current = None;
+        search.stale = true;
+        search.reveal = true;
+
+        let expand: Vec<usize> = self
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(_, msg)| search.expands(msg))
+            .map(|(index, _)| index)
+            .collect();
+        for index in expand {
+            self.toggle_tool_at(index);
+        }
+    }
+
+    /// Search and select the first hit from message `message_index` on,
+    /// e.g. after opening a session from history search
+    pub fn set_search_query_at_message(&mut self, query: &str, message_index: usize) {
+        self.set_search_query(query);
+        if let Some(search) = self.search.as_mut() {
+            search.anchor_message = Some(message_index);
+        }
+    }
+
+    /// Select the next hit down the transcript, wrapping around
+    pub fn search_next(&mut self) {
+        self.step_search(true);
+    }
+
+    /// Select the previous hit up the transcript, wrapping around
+    pub fn search_prev(&mut self) {
+        self.step_search(false);
+    }
+
+    fn step_search(&mut self, forward: bool) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        let len = search.matches.len();
+        if len == 0 {
+            return;
+        }
+        search.current = Some(match (search.current, forward) {
+            (Some(current), true) => (current + 1) % len,
+            (Some(current), false) => (current + len - 1) % len,
+            (None, true) => 0,
+            (None, false) => len - 1,
+        });
+        search.reveal = true;
+    }
+
+    /// Drop the search and its highlights
+    pub fn clear_search(&mut self) {
+        self.search = None;
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.search.is_some()
+    }
+
+    /// Selected hit (1-based, 0 when none) and the number of hits
+    pub fn search_status(&self) -> Option<(usize, usize)> {
+        let search = self.search.as_ref()?;
+        Some((
+            search.current.map_or(0, |current| current + 1),
+            search.matches.len(),
+        ))
+    }
+
+    /// Mark matches for recomputation after the transcript changed
+    pub(super) fn invalidate_search(&mut self) {
+        if let Some(search) = self.search.as_mut() {
+            search.stale = true;
+        }
+    }
+
+    /// Recompute hits over the cached and streaming lines. `view_top` is the
+    /// first visible line, used to pick a hit for a new query.
+    pub(super) fn refresh_search(&mut self, view_top: usize) {
+        if !self.search.as_ref().is_some_and(|search| search.stale) {
+            return;
+        }
+        let anchor_line = self
+            .search
+            .as_mut()
+            .and_then(|search| search.anchor_message.take())
+            .and_then(|index| self.message_start_lines().get(index).copied().flatten());
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        search.stale = false;
+        let view_top = anchor_line.unwrap_or(view_top);
+
+        let previous = search
+            .current
+            .and_then(|current| search.matches.get(current).copied());
+        let streaming = self.streaming_cache.as_deref().unwrap_or_default();
+        search.matches = self
+            .flat_cache
+            .iter()
+            .chain(streaming)
+            .enumerate()
+            .flat_map(|(line_index, line)| {
+                find_in_line(line, &search.needle)
+                    .into_iter()
+                    .map(move |(start_col, end_col)| SearchMatch {
+                        line_index,
+                        start_col,
+                        end_col,
+                    })
+            })
+            .collect();
+
+        search.current = if search.matches.is_empty() {
+            None
+        } else if let Some(previous) = previous {
+            // Keep the selection on the same hit while new output streams in
+            Some(
+                search
+                    .matches
+                    .iter()
+                    .position(|m| *m == previous)
+                    .unwrap_or_else(|| {
+                        search
+                            .matches
+                            .iter()
+                            .position(|m| m.line_index >= previous.line_index)
+                            .unwrap_or(search.matches.len() - 1)
+                    }),
+            )
+        } else {
+            Some(
+                search
+                    .matches
+                    .iter()
+                    .position(|m| m.line_index >= view_top)
+                    .unwrap_or(search.matches.len() - 1),
+            )
+        };
+    }
+
+    /// Line to scroll to, once after the selected hit changed
+    pub(super) fn take_search_reveal(&mut self) -> Option<usize> {
+        let search = self.search.as_mut()?;
+        if !std::mem::take(&mut search.reveal) {
+            return None;
+        }
+        let current = search.current?;
+        search.matches.get(current).map(|m| m.line_index)
+    }
+
+    /// Apply hit highlights to a rendered line
+    pub(super) fn apply_search_highlight(
+        &self,
+        line: Line<'static>,
+        line_index: usize,
+    ) -> Line<'static> {
+        let Some(search) = self.search.as_ref() else {
+            return line;
+        };
+        let start = search
+            .matches
+            .partition_point(|m| m.line_index < line_index);
+        let ranges: Vec<(u16, u16, bool)> = search.matches[start..]
+            .iter()
+            .enumerate()
+            .take_while(|(_, m)| m.line_index == line_index)
+            .map(|(offset, m)| {
+                (
+                    m.start_col,
+                    m.end_col,
+                    search.current == Some(start + offset),
+                )
+            })
+            .collect();
+        if ranges.is_empty() {
+            line
+        } else {
+            highlight_matches(&line, &ranges)
+        }
+    }
+}
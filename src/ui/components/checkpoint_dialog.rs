@@ -0,0 +1,236 @@
+//! Checkpoint timeline dialog: one row per agent turn in a workspace, newest
+//! first, with a "restore to before this turn" action.
+
+use chrono::Local;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use uuid::Uuid;
+
+use super::{accent_primary, text_muted, text_primary, DialogFrame};
+use crate::data::Checkpoint;
+
+const DIALOG_WIDTH: u16 = 88;
+/// Rows shown at once before the list scrolls
+const MAX_VISIBLE_ROWS: usize = 12;
+
+/// State for the checkpoint timeline dialog
+#[derive(Debug, Clone, Default)]
+pub struct CheckpointDialogState {
+    visible: bool,
+    selected: usize,
+    scroll_offset: usize,
+    workspace_id: Option<Uuid>,
+    workspace_name: String,
+    checkpoints: Vec<Checkpoint>,
+}
+
+impl CheckpointDialogState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show the timeline for a workspace; `checkpoints` are newest first
+    pub fn show(&mut self, workspace_id: Uuid, workspace_name: &str, checkpoints: Vec<Checkpoint>) {
+        self.visible = true;
+        self.selected = 0;
+        self.scroll_offset = 0;
+        self.workspace_id = Some(workspace_id);
+        self.workspace_name = workspace_name.to_string();
+        self.checkpoints = checkpoints;
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn workspace_id(&self) -> Option<Uuid> {
+        self.workspace_id
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.checkpoints.len() {
+            self.selected += 1;
+            if self.selected >= self.scroll_offset + MAX_VISIBLE_ROWS {
+                self.scroll_offset = self.selected + 1 - MAX_VISIBLE_ROWS;
+            }
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+        if self.selected < self.scroll_offset {
+            self.scroll_offset = self.selected;
+        }
+    }
+
+    pub fn selected_checkpoint(&self) -> Option<&Checkpoint> {
+        self.checkpoints.get(self.selected)
+    }
+}
+
+/// Checkpoint timeline widget
+pub struct CheckpointDialog<'a> {
+    state: &'a CheckpointDialogState,
+}
+
+impl<'a> CheckpointDialog<'a> {
+    pub fn new(state: &'a CheckpointDialogState) -> Self {
+        Self { state }
+    }
+
+    fn files_label(checkpoint: &Checkpoint) -> String {
+        match checkpoint.files_changed.len() {
+            0 => "no files".to_string(),
+            1 => "1 file".to_string(),
+            n => format!("{n} files"),
+        }
+    }
+
+    fn row_line(checkpoint: &Checkpoint, selected: bool, width: usize) -> Line<'static> {
+        let marker = if selected { "› " } else { "  " };
+        let label_style = if selected {
+            Style::default()
+                .fg(accent_primary())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(text_primary())
+        };
+        let head = format!(
+            "Turn {:<4} {}  {:>9}  ",
+            checkpoint.turn,
+            checkpoint
+                .created_at
+                .with_timezone(&Local)
+                .format("%b %d %H:%M"),
+            Self::files_label(checkpoint)
+        );
+        let prompt_width = width.saturating_sub(head.chars().count() + marker.len());
+        let prompt: String = checkpoint
+            .prompt
+            .as_deref()
+            .and_then(|prompt| prompt.lines().next())
+            .unwrap_or("")
+            .chars()
+            .take(prompt_width)
+            .collect();
+
+        Line::from(vec![
+            Span::styled(marker, label_style),
+            Span::styled(head, label_style),
+            Span::styled(prompt, Style::default().fg(text_muted())),
+        ])
+    }
+}
+
+impl Widget for CheckpointDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !self.state.visible {
+            return;
+        }
+
+        let visible_rows = self.state.checkpoints.len().clamp(1, MAX_VISIBLE_ROWS);
+        // Padding, heading, spacing, rows, spacing, hint, bottom spacing
+        let dialog_height = visible_rows as u16 + 9;
+        let frame =
+            DialogFrame::new("Checkpoints", DIALOG_WIDTH, dialog_height).instructions(vec![
+                ("↑↓", "Select"),
+                ("Enter", "Restore"),
+                ("Esc", "Close"),
+            ]);
+        let inner = frame.render(area, buf);
+        if inner.height < 4 {
+            return;
+        }
+
+        let width = inner.width.saturating_sub(4) as usize;
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!("Workspace {}", self.state.workspace_name),
+                Style::default().fg(text_muted()),
+            )),
+            Line::default(),
+        ];
+        if self.state.checkpoints.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No checkpoints yet. One is taken when each agent turn starts.",
+                Style::default().fg(text_muted()),
+            )));
+        }
+        for (index, checkpoint) in self
+            .state
+            .checkpoints
+            .iter()
+            .enumerate()
+            .skip(self.state.scroll_offset)
+            .take(MAX_VISIBLE_ROWS)
+        {
+            lines.push(Self::row_line(
+                checkpoint,
+                index == self.state.selected,
+                width,
+            ));
+        }
+        lines.push(Line::default());
+        let hint = match self.state.selected_checkpoint() {
+            Some(checkpoint) => format!(
+                "Restores files to how they were before turn {}. Current files are backed up first.",
+                checkpoint.turn
+            ),
+            None => String::new(),
+        };
+        lines.push(Line::from(Span::styled(
+            hint,
+            Style::default().fg(text_muted()),
+        )));
+
+        Paragraph::new(lines).render(
+            Rect {
+                x: inner.x + 2,
+                y: inner.y + 1,
+                width: inner.width.saturating_sub(4),
+                height: inner.height.saturating_sub(2),
+            },
+            buf,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_dialog_selection_scrolls() {
+        let workspace_id = Uuid::new_v4();
+        let checkpoints: Vec<Checkpoint> = (1..=20)
+            .rev()
+            .map(|turn| Checkpoint::new(workspace_id, None, turn))
+            .collect();
+        let mut state = CheckpointDialogState::new();
+        state.show(workspace_id, "feature", checkpoints);
+        assert_eq!(state.selected_checkpoint().unwrap().turn, 20);
+
+        state.select_previous();
+        assert_eq!(state.selected_checkpoint().unwrap().turn, 20);
+
+        for _ in 0..MAX_VISIBLE_ROWS {
+            state.select_next();
+        }
+        assert_eq!(state.selected_checkpoint().unwrap().turn, 8);
+        assert_eq!(state.scroll_offset, 1);
+
+        for _ in 0..30 {
+            state.select_next();
+        }
+        assert_eq!(state.selected_checkpoint().unwrap().turn, 1);
+    }
+}
@@ -16,6 +16,8 @@ use super::{
     render_minimal_scrollbar, text_muted, text_primary, DialogFrame, SearchableListState,
 };
 use crate::config::keys::{KeyCombo, KeybindingConfig};
+use crate::config::UserCommand;
+use crate::session::TranscriptFormat;
 use crate::ui::action::Action;
 
 /// A command entry in the palette
@@ -52,8 +54,18 @@ impl CommandPaletteState {
     /// Show the command palette and populate commands from keybindings
     /// `supports_plan_mode` controls whether the Build/Plan mode toggle is shown
     pub fn show(&mut self, keybindings: &KeybindingConfig, supports_plan_mode: bool) {
+        self.show_with_user_commands(keybindings, supports_plan_mode, &[]);
+    }
+
+    /// Show the command palette with `user_commands` listed alongside the built-in ones
+    pub fn show_with_user_commands(
+        &mut self,
+        keybindings: &KeybindingConfig,
+        supports_plan_mode: bool,
+        user_commands: &[UserCommand],
+    ) {
         self.visible = true;
-        self.commands = Self::build_commands(keybindings, supports_plan_mode);
+        self.commands = Self::build_commands(keybindings, supports_plan_mode, user_commands);
         self.list.reset();
         // Initialize filtered list with all commands
         self.list.filtered = (0..self.commands.len()).collect();
@@ -73,22 +85,27 @@ impl CommandPaletteState {
     fn build_commands(
         keybindings: &KeybindingConfig,
         supports_plan_mode: bool,
+        user_commands: &[UserCommand],
     ) -> Vec<CommandPaletteEntry> {
         // Build reverse lookup: Action discriminant -> key display string
         let mut keybinding_cache: HashMap<std::mem::Discriminant<Action>, String> = HashMap::new();
+        // User commands share a discriminant, so they are looked up by name
+        let mut command_keybindings: HashMap<String, String> = HashMap::new();
         let mut cache_binding = |combo: &KeyCombo, action: &Action| {
-            let key = std::mem::discriminant(action);
             let display = combo.to_string();
             let display_width = UnicodeWidthStr::width(display.as_str());
+            let existing = match action {
+                Action::RunCommand(name) => {
+                    command_keybindings.entry(name.to_lowercase()).or_default()
+                }
+                _ => keybinding_cache
+                    .entry(std::mem::discriminant(action))
+                    .or_default(),
+            };
             // Prefer shorter keybindings when multiple exist
-            keybinding_cache
-                .entry(key)
-                .and_modify(|existing| {
-                    if display_width < UnicodeWidthStr::width(existing.as_str()) {
-                        *existing = display.clone();
-                    }
-                })
-                .or_insert(display);
+            if existing.is_empty() || display_width < UnicodeWidthStr::width(existing.as_str()) {
+                *existing = display;
+            }
         };
 
         for (combo, action) in &keybindings.global {
@@ -110,13 +127,29 @@ impl CommandPaletteState {
             Action::OpenPr,
             Action::ForkSession,
             Action::HandoffSession,
+            Action::StartRace,
+            Action::ShowRaceResults,
             Action::InterruptAgent,
             Action::ToggleViewMode,
             Action::ShowModelSelector,
             Action::ShowReasoningSelector,
+            Action::ShowCodexPermissions,
             Action::ShowProvidersSelector,
             Action::ToggleMetrics,
             Action::DumpDebugState,
+            Action::CycleApprovalPolicy,
+            Action::ExportTranscript(TranscriptFormat::Markdown),
+            Action::ExportTranscript(TranscriptFormat::Html),
+            Action::ExportTranscript(TranscriptFormat::Jsonl),
+            Action::ShowUsageDashboard,
+            Action::OpenDiffReview,
+            Action::ShowCheckpoints,
+            Action::RollbackLastTurn,
+            Action::ShowSchedules,
+            Action::RunWorkspaceSetup,
+            Action::StartGoal,
+            Action::SearchChat,
+            Action::SearchHistory,
             Action::OpenQueueEditor,
             Action::CloseTab,
             Action::NextTab,
@@ -155,6 +188,16 @@ impl CommandPaletteState {
             })
             .collect();
 
+        entries.extend(user_commands.iter().map(|command| {
+            CommandPaletteEntry {
+                action: Action::RunCommand(command.name.clone()),
+                description: format!("{} (/{})", command.summary(), command.name),
+                keybinding: command_keybindings
+                    .get(&command.name.to_lowercase())
+                    .cloned(),
+            }
+        }));
+
         // Sort alphabetically by description
         entries.sort_by_cached_key(|e| e.description.to_lowercase());
 
@@ -519,4 +562,31 @@ mod tests {
 
         assert_eq!(entry.keybinding.as_deref(), Some("M-S-h"));
     }
+
+    #[test]
+    fn test_command_palette_lists_user_commands_with_keybinding() {
+        let command = UserCommand {
+            name: "ship".to_string(),
+            description: "Test, then open a PR".to_string(),
+            kind: crate::config::UserCommandKind::Macro {
+                steps: vec![Action::OpenPr],
+            },
+        };
+        let mut keybindings = KeybindingConfig::default();
+        let key_combo: KeyCombo = "C-M-s".parse().expect("Should parse C-M-s");
+        keybindings
+            .global
+            .insert(key_combo, Action::RunCommand("ship".to_string()));
+
+        let mut state = CommandPaletteState::new();
+        state.show_with_user_commands(&keybindings, true, &[command]);
+
+        let entry = state
+            .commands
+            .iter()
+            .find(|cmd| cmd.action == Action::RunCommand("ship".to_string()))
+            .expect("User command should appear in command palette");
+        assert_eq!(entry.description, "Test, then open a PR (/ship)");
+        assert_eq!(entry.keybinding.as_deref(), Some("C-M-s"));
+    }
 }
@@ -64,6 +64,21 @@ pub enum ConfirmationContext {
     RemoveProjectPreflightInProgress { repo_id: Uuid },
     /// Fork-session confirmation preflight is running.
     ForkSessionPreflightInProgress { parent_workspace_id: Uuid },
+    /// Reverting a hunk from the diff review tab
+    RevertDiffHunk {
+        tab_id: Uuid,
+        working_dir: PathBuf,
+        patch: String,
+    },
+    /// Handing a rate-limited session to the fallback agent
+    RateLimitFallback { session_id: Uuid },
+    /// Restoring a workspace to a per-turn checkpoint
+    RestoreCheckpoint {
+        workspace_id: Uuid,
+        working_dir: PathBuf,
+        commit: String,
+        turn: i64,
+    },
 }
 
 impl ConfirmationContext {
@@ -0,0 +1,103 @@
+//! Review remark dialog for sending a diff hunk to the agent
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    symbols::border,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+use uuid::Uuid;
+
+use super::{accent_primary, text_muted, text_primary, DialogFrame, TextInputState};
+
+/// State for the hunk comment dialog
+#[derive(Debug, Clone, Default)]
+pub struct DiffCommentDialogState {
+    /// Whether the dialog is visible
+    pub visible: bool,
+    /// Review remark being typed
+    pub input: TextInputState,
+    /// Diff tab the hunk belongs to
+    pub tab_id: Option<Uuid>,
+    /// Label describing the hunk, e.g. `src/lib.rs @@ -1,3 +1,4 @@`
+    pub target: String,
+}
+
+impl DiffCommentDialogState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show the dialog for a hunk in the given diff tab
+    pub fn show(&mut self, tab_id: Uuid, target: String) {
+        self.visible = true;
+        self.tab_id = Some(tab_id);
+        self.target = target;
+        self.input.clear();
+    }
+
+    /// Hide the dialog
+    pub fn hide(&mut self) {
+        self.visible = false;
+        self.tab_id = None;
+        self.input.clear();
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
+
+/// Hunk comment dialog widget
+pub struct DiffCommentDialog;
+
+impl DiffCommentDialog {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Render the dialog
+    pub fn render(&self, area: Rect, buf: &mut Buffer, state: &DiffCommentDialogState) {
+        if !state.is_visible() {
+            return;
+        }
+
+        let frame = DialogFrame::new("Send Hunk to Agent", 72, 10)
+            .instructions(vec![("Enter", "send"), ("Esc", "cancel")]);
+        let inner = frame.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Hunk label
+            Constraint::Length(1), // Spacing
+            Constraint::Length(3), // Input field
+            Constraint::Min(0),    // Remaining space
+        ])
+        .split(inner);
+
+        Paragraph::new(state.target.as_str())
+            .style(Style::default().fg(text_muted()))
+            .render(chunks[0], buf);
+
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(Style::default().fg(accent_primary()));
+        let input_inner = input_block.inner(chunks[2]);
+        input_block.render(chunks[2], buf);
+
+        state.input.render_with_placeholder(
+            input_inner,
+            buf,
+            Style::default().fg(text_primary()),
+            "What should the agent change?",
+            Style::default().fg(text_muted()),
+        );
+    }
+}
+
+impl Default for DiffCommentDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
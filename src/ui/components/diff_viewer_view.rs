@@ -0,0 +1,246 @@
+//! Diff review component rendering a workspace diff side by side
+//!
+//! Old lines are shown on the left and new lines on the right, with the
+//! selected hunk's heading highlighted.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use super::source_highlighter::truncate_spans_with_ellipsis;
+use super::{
+    accent_error, accent_primary, bg_base, bg_highlight, border_default, diff_add, diff_remove,
+    render_minimal_scrollbar, text_muted, text_primary,
+};
+use crate::git::DiffLineKind;
+use crate::ui::diff_viewer::{DiffRow, DiffSide, DiffViewerSession};
+
+/// Width of the line-number gutter, including the trailing marker column
+const GUTTER_WIDTH: usize = 7;
+
+/// Renders a diff review tab: a summary header followed by the rows
+pub struct DiffViewerView<'a> {
+    session: &'a DiffViewerSession,
+}
+
+impl<'a> DiffViewerView<'a> {
+    pub fn new(session: &'a DiffViewerSession) -> Self {
+        Self { session }
+    }
+
+    /// Rows available for diff content in an area of the given height
+    pub fn content_height(area_height: u16) -> usize {
+        area_height.saturating_sub(1).max(1) as usize
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+
+        let header_area = Rect { height: 1, ..area };
+        Paragraph::new(self.header_line())
+            .style(Style::default().bg(bg_base()))
+            .render(header_area, buf);
+
+        let body = Rect {
+            y: area.y + 1,
+            height: area.height.saturating_sub(1),
+            ..area
+        };
+        if body.height == 0 {
+            return;
+        }
+
+        if let Some(message) = self.placeholder() {
+            Paragraph::new(Line::from(Span::styled(
+                message,
+                Style::default().fg(text_muted()),
+            )))
+            .render(
+                Rect {
+                    x: body.x + 1,
+                    width: body.width.saturating_sub(1),
+                    height: 1,
+                    ..body
+                },
+                buf,
+            );
+            return;
+        }
+
+        let visible_height = body.height as usize;
+        // Reserve 1 column for the scrollbar
+        let width = body.width.saturating_sub(1) as usize;
+        for (i, row) in self.session.visible_rows(visible_height).iter().enumerate() {
+            let line = self.row_line(row, width);
+            Paragraph::new(line).render(
+                Rect {
+                    x: body.x,
+                    y: body.y + i as u16,
+                    width: width as u16,
+                    height: 1,
+                },
+                buf,
+            );
+        }
+
+        render_minimal_scrollbar(
+            Rect {
+                x: body.x + body.width.saturating_sub(1),
+                y: body.y,
+                width: 1,
+                height: body.height,
+            },
+            buf,
+            self.session.total_rows(),
+            visible_height,
+            self.session.scroll_offset,
+        );
+    }
+
+    fn header_line(&self) -> Line<'static> {
+        let mut spans = vec![Span::styled(
+            format!(" {}", self.session.working_dir.display()),
+            Style::default().fg(text_primary()),
+        )];
+        if let Some(diff) = self.session.diff() {
+            let short_commit: String = diff.base_commit.chars().take(7).collect();
+            spans.push(Span::styled(
+                format!(
+                    " vs {} ({}) • {} files ",
+                    diff.base_ref,
+                    short_commit,
+                    diff.files.len()
+                ),
+                Style::default().fg(text_muted()),
+            ));
+            spans.push(Span::styled(
+                format!("+{}", diff.additions()),
+                Style::default().fg(diff_add()),
+            ));
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                format!("-{}", diff.deletions()),
+                Style::default().fg(diff_remove()),
+            ));
+        }
+        if self.session.loading && self.session.diff().is_some() {
+            spans.push(Span::styled(
+                " • refreshing…",
+                Style::default().fg(text_muted()),
+            ));
+        }
+        Line::from(spans)
+    }
+
+    fn placeholder(&self) -> Option<String> {
+        if let Some(error) = &self.session.error {
+            return Some(format!("Failed to load diff: {}", error));
+        }
+        match self.session.diff() {
+            None => Some("Loading diff…".to_string()),
+            Some(diff) if diff.is_empty() => Some(format!("No changes against {}", diff.base_ref)),
+            Some(_) => None,
+        }
+    }
+
+    fn row_line(&self, row: &DiffRow, width: usize) -> Line<'static> {
+        match row {
+            DiffRow::File { file } => {
+                let Some(file) = self.session.diff().and_then(|d| d.files.get(*file)) else {
+                    return Line::default();
+                };
+                let style = Style::default().fg(text_primary()).bg(bg_highlight());
+                let mut spans = vec![
+                    Span::styled(
+                        format!(" {} ", file.display_path()),
+                        style.add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("+{}", file.additions()),
+                        Style::default().fg(diff_add()).bg(bg_highlight()),
+                    ),
+                    Span::styled(" ", style),
+                    Span::styled(
+                        format!("-{}", file.deletions()),
+                        Style::default().fg(diff_remove()).bg(bg_highlight()),
+                    ),
+                ];
+                let used: usize = spans.iter().map(|s| s.width()).sum();
+                spans.push(Span::styled(" ".repeat(width.saturating_sub(used)), style));
+                Line::from(spans)
+            }
+            DiffRow::Hunk { file, hunk } => {
+                let header = self
+                    .session
+                    .diff()
+                    .and_then(|d| d.files.get(*file))
+                    .and_then(|f| f.hunks.get(*hunk))
+                    .map(|h| h.header.clone())
+                    .unwrap_or_default();
+                if self.session.is_selected(*file, *hunk) {
+                    Line::from(vec![
+                        Span::styled(
+                            "› ",
+                            Style::default()
+                                .fg(accent_primary())
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(header, Style::default().fg(accent_primary())),
+                    ])
+                } else {
+                    Line::from(Span::styled(
+                        format!("  {}", header),
+                        Style::default().fg(text_muted()),
+                    ))
+                }
+            }
+            DiffRow::Lines { left, right } => {
+                let half = width.saturating_sub(1) / 2;
+                let mut spans = Self::side_spans(left.as_ref(), half);
+                spans.push(Span::styled("│", Style::default().fg(border_default())));
+                spans.extend(Self::side_spans(
+                    right.as_ref(),
+                    width.saturating_sub(half + 1),
+                ));
+                Line::from(spans)
+            }
+            DiffRow::Note(note) => Line::from(Span::styled(
+                format!("  {}", note),
+                Style::default()
+                    .fg(accent_error())
+                    .add_modifier(Modifier::ITALIC),
+            )),
+        }
+    }
+
+    /// Gutter, marker and highlighted content for one side, padded to `width`
+    fn side_spans(side: Option<&DiffSide>, width: usize) -> Vec<Span<'static>> {
+        let Some(side) = side else {
+            return vec![Span::raw(" ".repeat(width))];
+        };
+        let (marker, color) = match side.kind {
+            DiffLineKind::Added => ("+", diff_add()),
+            DiffLineKind::Removed => ("-", diff_remove()),
+            DiffLineKind::Context => (" ", text_muted()),
+        };
+        let mut spans = vec![
+            Span::styled(
+                format!("{:>5} ", side.line_number),
+                Style::default().fg(text_muted()),
+            ),
+            Span::styled(marker, Style::default().fg(color)),
+        ];
+        let content_width = width.saturating_sub(GUTTER_WIDTH);
+        let content = truncate_spans_with_ellipsis(&side.content.spans, content_width);
+        let used: usize = content.iter().map(|s| s.width()).sum();
+        spans.extend(content);
+        spans.push(Span::raw(" ".repeat(content_width.saturating_sub(used))));
+        spans
+    }
+}
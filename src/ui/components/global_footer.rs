@@ -23,6 +23,8 @@ pub enum FooterContext {
     RawEvents,
     /// File viewer mode
     FileViewer,
+    /// Diff review mode
+    DiffViewer,
 }
 
 impl FooterContext {
@@ -71,6 +73,7 @@ impl<'a> GlobalFooter<'a> {
                 FooterContext::Sidebar => Self::sidebar_hints(),
                 FooterContext::RawEvents => Self::raw_events_hints(),
                 FooterContext::FileViewer => Self::file_viewer_hints(),
+                FooterContext::DiffViewer => Self::diff_viewer_hints(),
             },
             spinner: None,
             message: None,
@@ -130,6 +133,7 @@ impl<'a> GlobalFooter<'a> {
             ("enter", "select"),
             ("h/l", "collapse/expand"),
             ("r", "add repo"),
+            ("t", "checkpoints"),
             ("C-n", "new project"),
             ("esc", "exit"),
         ]
@@ -158,6 +162,18 @@ impl<'a> GlobalFooter<'a> {
         ]
     }
 
+    /// Get hints for diff review mode
+    pub fn diff_viewer_hints() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("n/p", "hunk"),
+            ("s", "stage"),
+            ("x", "revert"),
+            ("c", "comment"),
+            ("r", "refresh"),
+            ("q", "close"),
+        ]
+    }
+
     pub fn render(&self, area: Rect, buf: &mut Buffer) {
         // Build left side content (spinner + message)
         let mut left_spans: Vec<Span> = Vec::new();
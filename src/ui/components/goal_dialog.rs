@@ -0,0 +1,95 @@
+//! Verification command dialog for starting a goal loop
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    symbols::border,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+use super::{accent_primary, text_muted, text_primary, DialogFrame, TextInputState};
+
+/// State for the goal dialog
+#[derive(Debug, Clone, Default)]
+pub struct GoalDialogState {
+    /// Whether the dialog is visible
+    pub visible: bool,
+    /// Verification command being typed
+    pub input: TextInputState,
+}
+
+impl GoalDialogState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show the dialog, prefilled with the last command used
+    pub fn show(&mut self, command: Option<&str>) {
+        self.visible = true;
+        self.input.set(command.unwrap_or_default());
+    }
+
+    /// Hide the dialog
+    pub fn hide(&mut self) {
+        self.visible = false;
+        self.input.clear();
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
+
+/// Goal dialog widget
+pub struct GoalDialog;
+
+impl GoalDialog {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Render the dialog
+    pub fn render(&self, area: Rect, buf: &mut Buffer, state: &GoalDialogState) {
+        if !state.is_visible() {
+            return;
+        }
+
+        let frame = DialogFrame::new("Start Goal Loop", 72, 10)
+            .instructions(vec![("Enter", "arm"), ("Esc", "cancel")]);
+        let inner = frame.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Explanation
+            Constraint::Length(1), // Spacing
+            Constraint::Length(3), // Input field
+            Constraint::Min(0),    // Remaining space
+        ])
+        .split(inner);
+
+        Paragraph::new("Your next prompt is the goal; this command checks it after each turn")
+            .style(Style::default().fg(text_muted()))
+            .render(chunks[0], buf);
+
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(Style::default().fg(accent_primary()));
+        let input_inner = input_block.inner(chunks[2]);
+        input_block.render(chunks[2], buf);
+
+        state.input.render_with_placeholder(
+            input_inner,
+            buf,
+            Style::default().fg(text_primary()),
+            "cargo test",
+            Style::default().fg(text_muted()),
+        );
+    }
+}
+
+impl Default for GoalDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,441 @@
+//! History search picker dialog component
+//!
+//! Full-text search over every indexed session transcript. Each hit shows
+//! the session and a snippet around the match; Enter opens the session at
+//! that message.
+
+use chrono::Local;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use super::{
+    accent_warning, agent_acp, agent_claude, agent_codex, agent_gemini, agent_opencode, dialog_bg,
+    ensure_contrast_bg, ensure_contrast_fg, render_minimal_scrollbar, selected_bg, text_muted,
+    text_primary, DialogFrame, SearchableListState,
+};
+use crate::agent::AgentType;
+use crate::data::HistorySearchHit;
+
+/// Hits shown at once; each takes two rows
+const MAX_VISIBLE_HITS: usize = 8;
+const DIALOG_WIDTH: u16 = 90;
+
+/// State for the history search picker dialog
+#[derive(Debug, Clone)]
+pub struct HistorySearchPickerState {
+    /// Whether the dialog is visible
+    pub visible: bool,
+    /// Hits for the current query, best first
+    pub hits: Vec<HistorySearchHit>,
+    /// Query input and selection
+    pub list: SearchableListState,
+    /// Whether the index is being refreshed in the background
+    pub indexing: bool,
+    /// Error message if the index couldn't be read
+    pub error: Option<String>,
+    /// Spinner frame for the indexing animation
+    pub spinner_frame: usize,
+}
+
+impl Default for HistorySearchPickerState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HistorySearchPickerState {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            hits: Vec::new(),
+            list: SearchableListState::new(MAX_VISIBLE_HITS),
+            indexing: false,
+            error: None,
+            spinner_frame: 0,
+        }
+    }
+
+    /// Show the picker; the index refresh starts alongside
+    pub fn show(&mut self) {
+        self.visible = true;
+        self.list.reset();
+        self.hits.clear();
+        self.list.set_filtered(Vec::new());
+        self.error = None;
+        self.indexing = true;
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Advance the spinner animation
+    pub fn tick(&mut self) {
+        if self.indexing {
+            self.spinner_frame = self.spinner_frame.wrapping_add(1);
+        }
+    }
+
+    pub fn query(&self) -> &str {
+        self.list.search.value()
+    }
+
+    /// Replace the hits for a new query
+    pub fn set_hits(&mut self, hits: Vec<HistorySearchHit>) {
+        self.list.set_filtered((0..hits.len()).collect());
+        self.list.selected = 0;
+        self.hits = hits;
+        self.error = None;
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+        self.hits.clear();
+        self.list.set_filtered(Vec::new());
+    }
+
+    // Search input; the caller re-runs the query after edits
+    pub fn insert_char(&mut self, c: char) {
+        self.list.search.insert_char(c);
+    }
+
+    pub fn delete_char(&mut self) {
+        self.list.search.delete_char();
+    }
+
+    pub fn delete_forward(&mut self) {
+        self.list.search.delete_forward();
+    }
+
+    pub fn move_cursor_left(&mut self) {
+        self.list.search.move_left();
+    }
+
+    pub fn move_cursor_right(&mut self) {
+        self.list.search.move_right();
+    }
+
+    pub fn move_cursor_start(&mut self) {
+        self.list.search.move_start();
+    }
+
+    pub fn move_cursor_end(&mut self) {
+        self.list.search.move_end();
+    }
+
+    pub fn select_prev(&mut self) {
+        self.list.select_prev();
+    }
+
+    pub fn select_next(&mut self) {
+        self.list.select_next();
+    }
+
+    pub fn page_up(&mut self) {
+        self.list.page_up();
+    }
+
+    pub fn page_down(&mut self) {
+        self.list.page_down();
+    }
+
+    pub fn selected_hit(&self) -> Option<&HistorySearchHit> {
+        self.list
+            .filtered
+            .get(self.list.selected)
+            .and_then(|&idx| self.hits.get(idx))
+    }
+}
+
+/// Snippet text as spans, with the matched terms highlighted
+fn snippet_spans(
+    snippet: &str,
+    max_width: usize,
+    base: Style,
+    matched: Style,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut text = String::new();
+    let mut in_match = false;
+    let mut width = 0;
+    for ch in snippet.chars() {
+        match ch {
+            HistorySearchHit::MATCH_START | HistorySearchHit::MATCH_END => {
+                if !text.is_empty() {
+                    let style = if in_match { matched } else { base };
+                    spans.push(Span::styled(std::mem::take(&mut text), style));
+                }
+                in_match = ch == HistorySearchHit::MATCH_START;
+            }
+            _ if width >= max_width => break,
+            _ => {
+                // Keep the snippet on one row
+                let ch = if ch.is_whitespace() { ' ' } else { ch };
+                if ch == ' ' && text.ends_with(' ') {
+                    continue;
+                }
+                text.push(ch);
+                width += 1;
+            }
+        }
+    }
+    if !text.is_empty() {
+        let style = if in_match { matched } else { base };
+        spans.push(Span::styled(text, style));
+    }
+    spans
+}
+
+fn truncate(text: &str, max_width: usize) -> String {
+    if text.chars().count() <= max_width {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// History search picker dialog widget
+pub struct HistorySearchPicker;
+
+impl HistorySearchPicker {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Render the dialog
+    pub fn render(&self, area: Rect, buf: &mut Buffer, state: &HistorySearchPickerState) {
+        if !state.visible {
+            return;
+        }
+
+        // border(2) + top_padding(1) + search(1) + separator(1) + spacing(1) + list
+        let list_height = (state.list.visible_len() * 2) as u16;
+        let dialog_height = 6 + list_height;
+        let frame =
+            DialogFrame::new("Search History", DIALOG_WIDTH, dialog_height).instructions(vec![
+                ("↑↓", "Navigate"),
+                ("Enter", "Open"),
+                ("Esc", "Cancel"),
+            ]);
+        let inner = frame.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Search label
+            Constraint::Length(1), // Separator
+            Constraint::Min(1),    // Hit list
+            Constraint::Length(1), // Spacing
+        ])
+        .split(inner);
+
+        // Search input, with index status on the right
+        let search_display = if state.list.search.is_empty() {
+            "Search: (type to search all sessions)".to_string()
+        } else {
+            format!("Search: {}", state.query())
+        };
+        let search_style = if state.list.search.is_empty() {
+            Style::default().fg(text_muted())
+        } else {
+            Style::default().fg(text_primary())
+        };
+        Paragraph::new(search_display)
+            .style(search_style)
+            .render(chunks[0], buf);
+        if !state.list.search.is_empty() || state.list.search.cursor > 0 {
+            let cursor_x = chunks[0].x + 8 + state.list.search.cursor as u16;
+            if cursor_x < chunks[0].x + chunks[0].width {
+                buf[(cursor_x, chunks[0].y)]
+                    .set_style(Style::default().add_modifier(Modifier::REVERSED));
+            }
+        }
+        if state.indexing {
+            const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+            let spinner = SPINNER_FRAMES[state.spinner_frame % SPINNER_FRAMES.len()];
+            let status = format!("{} Indexing", spinner);
+            let width = status.chars().count() as u16;
+            if chunks[0].width > width + 20 {
+                let x = chunks[0].x + chunks[0].width - width;
+                buf.set_string(x, chunks[0].y, status, Style::default().fg(text_muted()));
+            }
+        }
+
+        let separator = "─".repeat(inner.width as usize);
+        Paragraph::new(separator)
+            .style(Style::default().fg(Color::DarkGray))
+            .render(chunks[1], buf);
+
+        let list_area = chunks[2];
+        if let Some(ref error) = state.error {
+            Paragraph::new(error.as_str())
+                .style(Style::default().fg(Color::Red))
+                .alignment(Alignment::Center)
+                .render(list_area, buf);
+        } else if state.list.filtered.is_empty() {
+            let empty_msg = if state.list.search.is_empty() {
+                "Find the session where something was discussed"
+            } else {
+                "No messages match"
+            };
+            Paragraph::new(empty_msg)
+                .style(Style::default().fg(Color::DarkGray))
+                .alignment(Alignment::Center)
+                .render(list_area, buf);
+        } else {
+            self.render_hits(list_area, buf, state);
+        }
+    }
+
+    fn render_hits(&self, area: Rect, buf: &mut Buffer, state: &HistorySearchPickerState) {
+        let visible_count = (area.height / 2) as usize;
+        let row_selected_bg = ensure_contrast_bg(selected_bg(), dialog_bg(), 3.0);
+        let row_selected_fg = ensure_contrast_fg(text_primary(), row_selected_bg, 4.5);
+        let width = area.width.saturating_sub(1) as usize;
+
+        for (i, &hit_idx) in state
+            .list
+            .filtered
+            .iter()
+            .skip(state.list.scroll_offset)
+            .take(visible_count)
+            .enumerate()
+        {
+            let hit = &state.hits[hit_idx];
+            let source = &hit.source;
+            let is_selected = state.list.scroll_offset + i == state.list.selected;
+            let y = area.y + (i * 2) as u16;
+
+            let bg = if is_selected {
+                Style::default().bg(row_selected_bg)
+            } else {
+                Style::default()
+            };
+            let primary = bg.fg(if is_selected {
+                row_selected_fg
+            } else {
+                text_primary()
+            });
+            let muted = bg.fg(if is_selected {
+                row_selected_fg
+            } else {
+                text_muted()
+            });
+            for row in 0..2 {
+                for x in area.x..area.x + width as u16 {
+                    buf[(x, y + row)].set_char(' ').set_style(bg);
+                }
+            }
+
+            // Row 1: "> [C] title                       project · Mar 14"
+            let (icon, agent_color) = match source.agent_type {
+                AgentType::Claude => ("C", agent_claude()),
+                AgentType::Codex => ("X", agent_codex()),
+                AgentType::Gemini => ("G", agent_gemini()),
+                AgentType::Opencode => ("O", agent_opencode()),
+                AgentType::Acp => ("A", agent_acp()),
+            };
+            let mut meta_parts = Vec::new();
+            if let Some(project) = source.project.as_deref() {
+                if let Some(name) = std::path::Path::new(project).file_name() {
+                    meta_parts.push(name.to_string_lossy().to_string());
+                }
+            }
+            if source.session_tab_id.is_some() {
+                meta_parts.push("tab".to_string());
+            }
+            meta_parts.push(
+                source
+                    .updated_at
+                    .with_timezone(&Local)
+                    .format("%b %d")
+                    .to_string(),
+            );
+            let meta = meta_parts.join(" · ");
+            let meta_width = meta.chars().count();
+            let title_width = width.saturating_sub(meta_width + 8);
+            let title = truncate(
+                source.title.as_deref().unwrap_or(&source.agent_session_id),
+                title_width,
+            );
+            let title_line = Line::from(vec![
+                Span::styled(if is_selected { "> " } else { "  " }, primary),
+                Span::styled("[", muted),
+                Span::styled(
+                    icon,
+                    bg.fg(if is_selected {
+                        row_selected_fg
+                    } else {
+                        agent_color
+                    })
+                    .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("] ", muted),
+                Span::styled(title, primary.add_modifier(Modifier::BOLD)),
+            ]);
+            buf.set_line(area.x, y, &title_line, width as u16);
+            if meta_width + 10 < width {
+                let x = area.x + (width - meta_width) as u16;
+                buf.set_string(x, y, &meta, muted);
+            }
+
+            // Row 2: "      user · …the OAuth redirect loops…"
+            let role = format!("      {} · ", hit.role);
+            let matched = bg.fg(accent_warning()).add_modifier(Modifier::BOLD);
+            let mut spans = vec![Span::styled(role.clone(), muted)];
+            spans.extend(snippet_spans(
+                &hit.snippet,
+                width.saturating_sub(role.chars().count()),
+                muted,
+                matched,
+            ));
+            buf.set_line(area.x, y + 1, &Line::from(spans), width as u16);
+        }
+
+        render_minimal_scrollbar(
+            Rect {
+                x: area.x + area.width - 1,
+                y: area.y,
+                width: 1,
+                height: area.height,
+            },
+            buf,
+            state.list.filtered.len(),
+            visible_count,
+            state.list.scroll_offset,
+        );
+    }
+}
+
+impl Default for HistorySearchPicker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippet_spans_highlight_matches() {
+        let base = Style::default();
+        let matched = Style::default().add_modifier(Modifier::BOLD);
+        let spans = snippet_spans("…fixed the \u{2}OAuth\u{3}\n  redirect…", 80, base, matched);
+        let text: Vec<&str> = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(text, vec!["…fixed the ", "OAuth", " redirect…"]);
+        assert_eq!(spans[1].style, matched);
+        assert_eq!(spans[2].style, base);
+
+        let clipped = snippet_spans("abcdef", 3, base, matched);
+        assert_eq!(clipped[0].content, "abc");
+    }
+}
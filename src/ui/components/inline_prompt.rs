@@ -1,4 +1,4 @@
-//! Inline prompt component for AskUserQuestion and ExitPlanMode tools
+//! Inline prompt component for AskUserQuestion, ExitPlanMode, and tool permission requests
 //!
 //! Emulates Claude Code CLI's inline UI patterns for interactive tool responses.
 
@@ -12,8 +12,8 @@ use ratatui::{
 };
 
 use super::{
-    accent_primary, accent_secondary, text_faint, text_muted, text_primary, text_secondary,
-    InstructionBar, TextInputState,
+    accent_primary, accent_secondary, diff_add, diff_remove, text_faint, text_muted, text_primary,
+    text_secondary, InstructionBar, TextInputState,
 };
 use crate::agent::events::{QuestionOption, UserQuestion};
 
@@ -42,6 +42,9 @@ const HORIZONTAL_LINE: char = '─';
 /// Dashed line character for plan box
 const DASHED_LINE: char = '╌';
 
+/// Lines of tool input shown on a permission prompt
+const MAX_PERMISSION_DETAIL_LINES: usize = 16;
+
 // ============================================================================
 // Types
 // ============================================================================
@@ -56,6 +59,24 @@ pub enum InlinePromptType {
         plan_content: String,
         plan_file_path: String,
     },
+    /// Tool permission request with agent-provided options
+    ToolPermission {
+        request_id: String,
+        tool_name: String,
+        detail: Option<String>,
+        options: Vec<PermissionChoice>,
+    },
+}
+
+/// A selectable answer to a tool permission request
+#[derive(Debug, Clone)]
+pub struct PermissionChoice {
+    /// Agent-defined option identifier
+    pub id: String,
+    /// Label shown to the user
+    pub label: String,
+    /// Ask for a reason before submitting this choice
+    pub requires_reason: bool,
 }
 
 /// User's answer to a single question
@@ -94,6 +115,12 @@ pub enum PromptResponse {
     ExitPlanApprove,
     /// Feedback for ExitPlanMode (stay in plan mode)
     ExitPlanFeedback(String),
+    /// Selected option for a tool permission request
+    PermissionSelected {
+        option_id: String,
+        /// Text typed for choices that ask for a reason
+        reason: Option<String>,
+    },
 }
 
 /// Answer value for AskUserQuestion
@@ -171,6 +198,30 @@ impl InlinePromptState {
         }
     }
 
+    /// Create a new tool permission prompt
+    pub fn new_tool_permission(
+        tool_id: String,
+        request_id: String,
+        tool_name: String,
+        detail: Option<String>,
+        options: Vec<PermissionChoice>,
+    ) -> Self {
+        Self {
+            tool_id,
+            prompt_type: InlinePromptType::ToolPermission {
+                request_id,
+                tool_name,
+                detail,
+                options,
+            },
+            current_option: 0,
+            current_question_idx: 0,
+            answers: vec![],
+            input_mode: false,
+            text_input: TextInputState::new(),
+        }
+    }
+
     /// Get the current question (for AskUserQuestion only)
     fn current_question(&self) -> Option<&UserQuestion> {
         match &self.prompt_type {
@@ -236,6 +287,7 @@ impl InlinePromptState {
                     .unwrap_or(0)
             }
             InlinePromptType::ExitPlanMode { .. } => 2, // "Yes, start building" and "Type here..."
+            InlinePromptType::ToolPermission { options, .. } => options.len(),
         }
     }
 
@@ -253,6 +305,7 @@ impl InlinePromptState {
                 }
             }
             InlinePromptType::ExitPlanMode { .. } => self.current_option == 1,
+            InlinePromptType::ToolPermission { .. } => false,
         }
     }
 
@@ -540,6 +593,20 @@ impl InlinePromptState {
                     PromptAction::Consumed
                 }
             }
+            InlinePromptType::ToolPermission { options, .. } => {
+                match options.get(self.current_option) {
+                    Some(option) if option.requires_reason => {
+                        self.input_mode = true;
+                        self.text_input.clear();
+                        PromptAction::Consumed
+                    }
+                    Some(option) => PromptAction::Submit(PromptResponse::PermissionSelected {
+                        option_id: option.id.clone(),
+                        reason: None,
+                    }),
+                    None => PromptAction::Consumed,
+                }
+            }
         }
     }
 
@@ -564,6 +631,16 @@ impl InlinePromptState {
             InlinePromptType::ExitPlanMode { .. } => {
                 PromptAction::Submit(PromptResponse::ExitPlanFeedback(text))
             }
+            // Only choices that ask for a reason enter text input mode
+            InlinePromptType::ToolPermission { options, .. } => options
+                .get(self.current_option)
+                .map(|option| {
+                    PromptAction::Submit(PromptResponse::PermissionSelected {
+                        option_id: option.id.clone(),
+                        reason: Some(text),
+                    })
+                })
+                .unwrap_or(PromptAction::Consumed),
         }
     }
 
@@ -718,6 +795,59 @@ impl InlinePromptState {
                     ]));
                 }
             }
+            InlinePromptType::ToolPermission {
+                tool_name,
+                detail,
+                options,
+                ..
+            } => {
+                lines.push(self.separator_line(width));
+                lines.push(Line::from(Span::styled(
+                    format!(" Allow {}?", tool_name),
+                    Style::default().fg(text_primary()),
+                )));
+
+                if let Some(detail) = detail {
+                    for line in detail.lines().take(MAX_PERMISSION_DETAIL_LINES) {
+                        // Edit previews are `-`/`+` diffs
+                        let color = if line.starts_with("+ ") {
+                            diff_add()
+                        } else if line.starts_with("- ") {
+                            diff_remove()
+                        } else {
+                            text_secondary()
+                        };
+                        lines.push(Line::from(Span::styled(
+                            format!(" {}", line),
+                            Style::default().fg(color),
+                        )));
+                    }
+                }
+                lines.push(Line::from("")); // blank line
+
+                for (i, option) in options.iter().enumerate() {
+                    lines.push(self.exit_plan_option_line(
+                        i,
+                        &option.label,
+                        self.current_option == i,
+                    ));
+                }
+
+                lines.push(Line::from("")); // blank line
+                if self.input_mode {
+                    lines.push(self.text_input_line());
+                    lines.push(Line::from("")); // blank line
+                    lines.push(
+                        self.instruction_bar_line(&[("Enter", "submit"), ("Esc", "go back")]),
+                    );
+                } else {
+                    lines.push(self.instruction_bar_line(&[
+                        ("Enter", "select"),
+                        ("↑/↓", "navigate"),
+                        ("Esc", "deny"),
+                    ]));
+                }
+            }
         }
 
         lines
@@ -1700,6 +1830,19 @@ impl Widget for InlinePrompt<'_> {
                     );
                 }
             }
+
+            InlinePromptType::ToolPermission { .. } => {
+                let lines = self.state.render_as_lines(area.width as usize);
+                Paragraph::new(lines).render(
+                    Rect {
+                        x: area.x,
+                        y,
+                        width: area.width,
+                        height: area.height,
+                    },
+                    buf,
+                );
+            }
         }
     }
 }
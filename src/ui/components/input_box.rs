@@ -738,6 +738,15 @@ impl InputBox {
         self.input.trim().is_empty()
     }
 
+    /// Whether the cursor is at the start of the input or after whitespace,
+    /// where typing `@` starts a mention
+    pub fn is_cursor_at_word_start(&self) -> bool {
+        self.input[..self.cursor_pos]
+            .chars()
+            .next_back()
+            .is_none_or(char::is_whitespace)
+    }
+
     pub fn handle_paste(&mut self, pasted: String) {
         let char_count = pasted.chars().count();
         if char_count > LARGE_PASTE_CHAR_THRESHOLD {
@@ -0,0 +1,444 @@
+//! `@` mention picker component.
+//!
+//! Opens above the input box when `@` is typed at the start of a word and
+//! fuzzy-matches the workspace's tracked files and tags-file symbols. The
+//! picked item is inserted into the prompt as a mention token.
+
+use std::path::{Path, PathBuf};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use super::{
+    accent_primary, bg_highlight, dialog_bg, ensure_contrast_bg, ensure_contrast_fg,
+    render_minimal_scrollbar, text_muted, text_primary, SearchableListState,
+};
+use crate::session::{rank_mentions, MentionCandidate, MentionKind};
+
+/// Matches kept per query
+const MAX_RESULTS: usize = 200;
+
+#[derive(Debug, Clone)]
+pub struct MentionPickerState {
+    pub visible: bool,
+    /// Directory the candidates were listed in
+    pub working_dir: Option<PathBuf>,
+    pub candidates: Vec<MentionCandidate>,
+    pub list: SearchableListState,
+    /// Whether candidates are being listed in the background
+    pub loading: bool,
+    pub error: Option<String>,
+    pub spinner_frame: usize,
+}
+
+impl MentionPickerState {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            working_dir: None,
+            candidates: Vec::new(),
+            list: SearchableListState::new(8),
+            loading: false,
+            error: None,
+            spinner_frame: 0,
+        }
+    }
+
+    /// Show the picker for `working_dir`. Candidates from an earlier listing
+    /// of the same directory are shown until the reload finishes.
+    pub fn show(&mut self, working_dir: PathBuf) {
+        if self.working_dir.as_deref() != Some(working_dir.as_path()) {
+            self.candidates.clear();
+            self.working_dir = Some(working_dir);
+        }
+        self.visible = true;
+        self.loading = true;
+        self.error = None;
+        self.list.reset();
+        self.filter();
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Advance the spinner animation
+    pub fn tick(&mut self) {
+        if self.loading {
+            self.spinner_frame = self.spinner_frame.wrapping_add(1);
+        }
+    }
+
+    /// Apply a finished listing; listings for another directory are dropped
+    pub fn set_candidates(
+        &mut self,
+        working_dir: &Path,
+        result: Result<Vec<MentionCandidate>, String>,
+    ) {
+        if self.working_dir.as_deref() != Some(working_dir) {
+            return;
+        }
+        self.loading = false;
+        match result {
+            Ok(candidates) => {
+                self.candidates = candidates;
+                self.error = None;
+            }
+            Err(error) => {
+                self.candidates.clear();
+                self.error = Some(error);
+            }
+        }
+        self.filter();
+    }
+
+    pub fn query(&self) -> &str {
+        self.list.search.value()
+    }
+
+    pub fn filtered_len(&self) -> usize {
+        self.list.filtered.len()
+    }
+
+    pub fn set_max_visible(&mut self, max_visible: usize) {
+        let max_visible = max_visible.max(1);
+        self.list.max_visible = max_visible;
+        self.list.clamp_selection();
+        if self.list.selected < self.list.scroll_offset {
+            self.list.scroll_offset = self.list.selected;
+        } else if self.list.selected >= self.list.scroll_offset + self.list.max_visible {
+            self.list.scroll_offset = self
+                .list
+                .selected
+                .saturating_sub(self.list.max_visible.saturating_sub(1));
+        }
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.list.search.insert_char(c);
+        self.filter();
+    }
+
+    pub fn delete_char(&mut self) {
+        self.list.search.delete_char();
+        self.filter();
+    }
+
+    pub fn delete_forward(&mut self) {
+        self.list.search.delete_forward();
+        self.filter();
+    }
+
+    pub fn select_next(&mut self) {
+        self.list.select_next();
+    }
+
+    pub fn select_prev(&mut self) {
+        self.list.select_prev();
+    }
+
+    pub fn page_up(&mut self) {
+        self.list.page_up();
+    }
+
+    pub fn page_down(&mut self) {
+        self.list.page_down();
+    }
+
+    pub fn selected_candidate(&self) -> Option<&MentionCandidate> {
+        let idx = self.list.filtered.get(self.list.selected)?;
+        self.candidates.get(*idx)
+    }
+
+    fn filter(&mut self) {
+        let filtered = rank_mentions(&self.candidates, self.list.search.value(), MAX_RESULTS);
+        self.list.set_filtered(filtered);
+    }
+}
+
+impl Default for MentionPickerState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keep the end of a path, where the file name is
+fn truncate_start(text: &str, max_width: usize) -> String {
+    if UnicodeWidthStr::width(text) <= max_width {
+        return text.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+    let mut kept: Vec<char> = Vec::new();
+    let mut width = 1;
+    for ch in text.chars().rev() {
+        let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
+        if width + ch_width > max_width {
+            break;
+        }
+        width += ch_width;
+        kept.push(ch);
+    }
+    std::iter::once('…').chain(kept.into_iter().rev()).collect()
+}
+
+pub struct MentionPicker;
+
+impl MentionPicker {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer, state: &MentionPickerState) {
+        if !state.visible {
+            return;
+        }
+
+        if area.height < 5 || area.width < 10 {
+            return;
+        }
+
+        Clear.render(area, buf);
+        buf.set_style(area, Style::default().bg(dialog_bg()));
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(Style::default().fg(accent_primary()).bg(dialog_bg()))
+            .style(Style::default().bg(dialog_bg()));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height < 3 || inner.width == 0 {
+            return;
+        }
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ])
+        .split(inner);
+
+        self.render_search(chunks[0], buf, state);
+        Paragraph::new("\u{2500}".repeat(chunks[1].width as usize))
+            .style(Style::default().fg(text_muted()))
+            .render(chunks[1], buf);
+        self.render_list(chunks[2], buf, state);
+    }
+
+    fn render_search(&self, area: Rect, buf: &mut Buffer, state: &MentionPickerState) {
+        let prompt = "@";
+        let input = state.list.search.value();
+
+        if input.is_empty() {
+            Paragraph::new("@ Type to find a file or symbol...")
+                .style(Style::default().fg(text_muted()))
+                .render(area, buf);
+        } else {
+            let line = Line::from(vec![
+                Span::styled(prompt, Style::default().fg(accent_primary())),
+                Span::styled(input, Style::default().fg(text_primary())),
+            ]);
+            Paragraph::new(line).render(area, buf);
+        }
+
+        if state.loading {
+            const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+            let spinner = SPINNER_FRAMES[state.spinner_frame % SPINNER_FRAMES.len()];
+            let status = format!("{} listing files", spinner);
+            let width = UnicodeWidthStr::width(status.as_str()) as u16;
+            if width < area.width {
+                buf.set_string(
+                    area.x + area.width - width,
+                    area.y,
+                    status,
+                    Style::default().fg(text_muted()),
+                );
+            }
+        }
+
+        let prompt_width = UnicodeWidthStr::width(prompt) as u16;
+        let cursor_offset = input
+            .chars()
+            .take(state.list.search.cursor)
+            .map(|ch| UnicodeWidthChar::width(ch).unwrap_or(1) as u16)
+            .sum::<u16>();
+        let cursor_x = area.x + prompt_width + cursor_offset;
+        if cursor_x < area.x + area.width {
+            buf[(cursor_x, area.y)].set_style(Style::default().add_modifier(Modifier::REVERSED));
+        }
+    }
+
+    fn render_list(&self, area: Rect, buf: &mut Buffer, state: &MentionPickerState) {
+        if state.list.filtered.is_empty() {
+            let msg = if let Some(error) = &state.error {
+                error.as_str()
+            } else if state.loading {
+                "Listing files..."
+            } else if state.candidates.is_empty() {
+                "No tracked files"
+            } else {
+                "No matching files or symbols"
+            };
+            Paragraph::new(msg)
+                .style(Style::default().fg(text_muted()))
+                .render(area, buf);
+            return;
+        }
+
+        let visible_count = area.height as usize;
+        let has_scrollbar = state.list.filtered.len() > visible_count;
+        let content_width = if has_scrollbar {
+            area.width.saturating_sub(1)
+        } else {
+            area.width
+        };
+
+        let selected_bg = ensure_contrast_bg(bg_highlight(), dialog_bg(), 2.0);
+        let selected_fg = ensure_contrast_fg(text_primary(), selected_bg, 4.5);
+        let selected_muted = ensure_contrast_fg(text_muted(), selected_bg, 3.0);
+        let selected_accent = ensure_contrast_fg(accent_primary(), selected_bg, 3.0);
+
+        for (i, &candidate_idx) in state
+            .list
+            .filtered
+            .iter()
+            .skip(state.list.scroll_offset)
+            .take(visible_count)
+            .enumerate()
+        {
+            let candidate = &state.candidates[candidate_idx];
+            let is_selected = state.list.scroll_offset + i == state.list.selected;
+            let y = area.y + i as u16;
+
+            let (accent_style, main_style, muted_style) = if is_selected {
+                for x in area.x..area.x + content_width {
+                    buf[(x, y)].set_bg(selected_bg);
+                }
+                (
+                    Style::default().fg(selected_accent).bg(selected_bg),
+                    Style::default().fg(selected_fg).bg(selected_bg),
+                    Style::default().fg(selected_muted).bg(selected_bg),
+                )
+            } else {
+                (
+                    Style::default().fg(text_muted()),
+                    Style::default().fg(text_primary()),
+                    Style::default().fg(text_muted()),
+                )
+            };
+
+            let prefix = if is_selected { "> " } else { "  " };
+            let available = (content_width as usize).saturating_sub(prefix.len());
+            let mut spans = vec![Span::styled(prefix, accent_style)];
+            match candidate.kind {
+                MentionKind::File => {
+                    spans.push(Span::styled(
+                        truncate_start(&candidate.path, available),
+                        main_style,
+                    ));
+                }
+                MentionKind::Symbol => {
+                    let name = candidate.symbol.as_deref().unwrap_or_default();
+                    let location = match candidate.line {
+                        Some(line) => format!("{}:{}", candidate.path, line),
+                        None => candidate.path.clone(),
+                    };
+                    let name = truncate_start(name, available.saturating_sub(2));
+                    let location_width =
+                        available.saturating_sub(2 + UnicodeWidthStr::width(name.as_str()) + 2);
+                    spans.push(Span::styled("ƒ ", accent_style));
+                    spans.push(Span::styled(name, main_style));
+                    if location_width > 0 {
+                        spans.push(Span::styled("  ", muted_style));
+                        spans.push(Span::styled(
+                            truncate_start(&location, location_width),
+                            muted_style,
+                        ));
+                    }
+                }
+            }
+
+            let line_area = Rect {
+                x: area.x,
+                y,
+                width: content_width,
+                height: 1,
+            };
+            Paragraph::new(Line::from(spans)).render(line_area, buf);
+        }
+
+        if has_scrollbar {
+            let scrollbar_area = Rect {
+                x: area.x + area.width - 1,
+                y: area.y,
+                width: 1,
+                height: area.height,
+            };
+            render_minimal_scrollbar(
+                scrollbar_area,
+                buf,
+                state.list.filtered.len(),
+                visible_count,
+                state.list.scroll_offset,
+            );
+        }
+    }
+}
+
+impl Default for MentionPicker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mention_picker_filters_loaded_candidates() {
+        let mut state = MentionPickerState::new();
+        let dir = PathBuf::from("/src/app");
+        state.show(dir.clone());
+        assert!(state.loading);
+
+        state.set_candidates(
+            Path::new("/src/other"),
+            Ok(vec![MentionCandidate::file("stale.rs")]),
+        );
+        assert!(state.candidates.is_empty());
+
+        state.set_candidates(
+            &dir,
+            Ok(vec![
+                MentionCandidate::file("README.md"),
+                MentionCandidate::file("src/ui/components/input_box.rs"),
+            ]),
+        );
+        assert!(!state.loading);
+        assert_eq!(state.filtered_len(), 2);
+
+        for c in "inbox".chars() {
+            state.insert_char(c);
+        }
+        assert_eq!(
+            state.selected_candidate().map(|c| c.path.as_str()),
+            Some("src/ui/components/input_box.rs")
+        );
+        assert_eq!(truncate_start("src/ui/app.rs", 8), "…/app.rs");
+    }
+}
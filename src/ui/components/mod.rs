@@ -2,34 +2,47 @@ mod add_repo_dialog;
 mod agent_selector;
 mod base_dir_dialog;
 mod chat_message;
+mod chat_search_bar;
 mod chat_view;
+mod checkpoint_dialog;
+mod codex_permissions_selector;
 mod command_palette;
 mod confirmation_dialog;
 mod dialog;
+mod diff_comment_dialog;
+mod diff_viewer_view;
 mod error_dialog;
 pub mod file_path_detector;
 mod file_viewer_view;
 mod global_footer;
+mod goal_dialog;
 mod help_dialog;
+mod history_search_picker;
 mod inline_prompt;
 mod input_box;
 mod key_hints;
 mod knight_rider_spinner;
 mod logo_shine;
 mod markdown;
+mod mention_picker;
 mod missing_tool_dialog;
 mod model_selector;
 mod multi_select_dialog;
 mod path_input;
 mod project_picker;
+mod prompt_variables_dialog;
 mod provider_selector;
+mod race_dialog;
+mod race_selector;
 mod raw_events_types;
 mod raw_events_view;
 mod reasoning_selector;
+mod schedule_dialog;
 mod scrollbar;
 mod searchable_list;
 mod session_header;
 mod session_import_picker;
+mod setup_pane;
 mod sidebar;
 mod slash_menu;
 pub(crate) mod source_highlighter;
@@ -42,12 +55,19 @@ mod theme_picker;
 mod thinking_indicator;
 mod tree_view;
 mod turn_summary;
+mod usage_dialog;
 
 pub use add_repo_dialog::{AddRepoDialog, AddRepoDialogState};
 pub use agent_selector::{AgentSelector, AgentSelectorState};
 pub use base_dir_dialog::{BaseDirDialog, BaseDirDialogState};
 pub use chat_message::{ChatMessage, MessageRole};
+pub use chat_search_bar::ChatSearchBar;
 pub use chat_view::{ChatView, HoveredFilePath};
+pub use checkpoint_dialog::{CheckpointDialog, CheckpointDialogState};
+pub use codex_permissions_selector::{
+    CodexPermissionChange, CodexPermissionOption, CodexPermissionsSelector,
+    CodexPermissionsSelectorState,
+};
 pub use command_palette::{CommandPalette, CommandPaletteEntry, CommandPaletteState};
 pub use confirmation_dialog::{
     ConfirmationContext, ConfirmationDialog, ConfirmationDialogState, ConfirmationType,
@@ -56,18 +76,24 @@ pub use dialog::{
     dialog_content_area, DialogFrame, InstructionBar, StatusLine, DIALOG_CONTENT_PADDING_X,
     DIALOG_CONTENT_PADDING_Y,
 };
+pub use diff_comment_dialog::{DiffCommentDialog, DiffCommentDialogState};
+pub use diff_viewer_view::DiffViewerView;
 pub use error_dialog::{ErrorDialog, ErrorDialogState};
 pub use file_viewer_view::FileViewerView;
 pub use global_footer::{FooterContext, GlobalFooter};
+pub use goal_dialog::{GoalDialog, GoalDialogState};
 pub use help_dialog::{HelpCategory, HelpDialog, HelpDialogState, KeybindingEntry};
+pub use history_search_picker::{HistorySearchPicker, HistorySearchPickerState};
 pub use inline_prompt::{
-    InlinePrompt, InlinePromptState, InlinePromptType, PromptAction, PromptAnswer, PromptResponse,
+    InlinePrompt, InlinePromptState, InlinePromptType, PermissionChoice, PromptAction,
+    PromptAnswer, PromptResponse,
 };
 pub use input_box::InputBox;
 pub use key_hints::{render_key_hints, render_key_hints_responsive, KeyHintBarStyle};
 pub use knight_rider_spinner::KnightRiderSpinner;
 pub use logo_shine::LogoShineAnimation;
 pub use markdown::MarkdownRenderer;
+pub use mention_picker::{MentionPicker, MentionPickerState};
 pub use missing_tool_dialog::{
     MissingToolDialog, MissingToolDialogState, MissingToolResult, StartupToolDialog,
 };
@@ -77,16 +103,21 @@ pub use model_selector::{
 pub use multi_select_dialog::{MultiSelectDialog, MultiSelectDialogState, MultiSelectItem};
 pub use path_input::PathInputState;
 pub use project_picker::{ProjectEntry, ProjectPicker, ProjectPickerState};
+pub use prompt_variables_dialog::{PromptVariablesDialog, PromptVariablesDialogState};
 pub use provider_selector::{ProviderSelector, ProviderSelectorState};
+pub use race_dialog::{RaceDialog, RaceDialogState, RaceResultRow};
+pub use race_selector::{RaceSelector, RaceSelectorState};
 pub use raw_events_types::{
     EventDetailState, EventDirection, RawEventEntry, DETAIL_PANEL_BREAKPOINT,
 };
 pub use raw_events_view::{RawEventsClick, RawEventsScrollbarMetrics, RawEventsView};
 pub use reasoning_selector::{ReasoningOption, ReasoningSelector, ReasoningSelectorState};
+pub use schedule_dialog::{ScheduleAddTarget, ScheduleDialog, ScheduleDialogState, ScheduleRow};
 pub use scrollbar::{render_minimal_scrollbar, scrollbar_offset_from_point, ScrollbarMetrics};
 pub use searchable_list::SearchableListState;
 pub use session_header::SessionHeader;
 pub use session_import_picker::{AgentFilter, SessionImportPicker, SessionImportPickerState};
+pub use setup_pane::{DeferredPrompt, SetupPane, SetupStatus, WorkspaceSetupState};
 pub use sidebar::{Sidebar, SidebarState, SIDEBAR_HEADER_ROWS};
 pub use slash_menu::{SlashCommand, SlashCommandEntry, SlashMenu, SlashMenuState};
 pub use spinner::Spinner;
@@ -103,6 +134,7 @@ pub use theme::{
     accent_success,
     accent_warning,
     // Agent colors (functions)
+    agent_acp,
     agent_claude,
     agent_codex,
     agent_gemini,
@@ -198,6 +230,7 @@ pub use tree_view::{
     SIDEBAR_GIT_DISPLAY,
 };
 pub use turn_summary::{FileChange, TurnSummary};
+pub use usage_dialog::{UsageDialog, UsageDialogState, USAGE_DASHBOARD_DAYS};
 
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
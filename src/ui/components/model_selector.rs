@@ -74,6 +74,8 @@ pub struct ModelSelectorState {
     allowed_providers: Option<Vec<AgentType>>,
     /// Dialog title
     title: String,
+    /// Section title for the configured ACP agent
+    acp_title: String,
 }
 
 impl Default for ModelSelectorState {
@@ -107,6 +109,7 @@ impl ModelSelectorState {
             default_model: DefaultModelSelection::default(),
             allowed_providers: None,
             title: "Model".to_string(),
+            acp_title: ModelRegistry::agent_section_title(AgentType::Acp).to_string(),
         }
     }
 
@@ -128,6 +131,7 @@ impl ModelSelectorState {
                 AgentType::Codex => ModelRegistry::codex_models(),
                 AgentType::Gemini => ModelRegistry::gemini_models(),
                 AgentType::Opencode => ModelRegistry::opencode_models(),
+                AgentType::Acp => ModelRegistry::acp_models(),
             };
 
             if models.is_empty() {
@@ -182,6 +186,11 @@ impl ModelSelectorState {
         self.allowed_providers = allowed_providers;
     }
 
+    /// Title the configured ACP agent's section under its own name
+    pub fn set_acp_title(&mut self, title: impl Into<String>) {
+        self.acp_title = title.into();
+    }
+
     /// Hide the dialog
     pub fn hide(&mut self) {
         self.visible = false;
@@ -617,7 +626,10 @@ impl ModelSelector {
 
             if seen_headers.insert(model.agent_type) {
                 if render_index >= scroll && visible_index < area.height as usize {
-                    let title = ModelRegistry::agent_section_title(model.agent_type);
+                    let title = match model.agent_type {
+                        AgentType::Acp => state.acp_title.as_str(),
+                        agent_type => ModelRegistry::agent_section_title(agent_type),
+                    };
                     let header_line = Line::from(Span::styled(
                         title,
                         Style::default()
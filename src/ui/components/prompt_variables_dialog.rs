@@ -0,0 +1,149 @@
+//! Dialog asking for the variables a prompt template still needs
+
+use std::collections::HashMap;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    symbols::border,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+use uuid::Uuid;
+
+use super::{accent_primary, text_muted, text_primary, DialogFrame, TextInputState};
+use crate::session::PromptTemplate;
+
+/// State for the prompt variables dialog
+#[derive(Debug, Clone, Default)]
+pub struct PromptVariablesDialogState {
+    /// Whether the dialog is visible
+    pub visible: bool,
+    /// Session the filled-in prompt is sent to
+    pub session_id: Option<Uuid>,
+    pub template: Option<PromptTemplate>,
+    /// Values collected so far, including the ones Conduit filled in
+    pub values: HashMap<String, String>,
+    /// Variables to ask for, in order
+    pub missing: Vec<String>,
+    /// Index into `missing` of the variable being typed
+    pub current: usize,
+    pub input: TextInputState,
+}
+
+impl PromptVariablesDialogState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show the dialog, asking for each of `missing` in turn
+    pub fn show(
+        &mut self,
+        session_id: Uuid,
+        template: PromptTemplate,
+        values: HashMap<String, String>,
+        missing: Vec<String>,
+    ) {
+        self.visible = true;
+        self.session_id = Some(session_id);
+        self.template = Some(template);
+        self.values = values;
+        self.missing = missing;
+        self.current = 0;
+        self.input.clear();
+    }
+
+    /// Hide the dialog
+    pub fn hide(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Variable being asked for
+    pub fn current_variable(&self) -> Option<&str> {
+        self.missing.get(self.current).map(String::as_str)
+    }
+
+    /// Record the typed value and move to the next variable; true once every
+    /// variable has a value
+    pub fn submit_current(&mut self) -> bool {
+        if let Some(name) = self.missing.get(self.current) {
+            self.values
+                .insert(name.clone(), self.input.value().to_string());
+            self.current += 1;
+            self.input.clear();
+        }
+        self.current >= self.missing.len()
+    }
+
+    /// Hide the dialog, returning the session, template and values
+    pub fn take(&mut self) -> Option<(Uuid, PromptTemplate, HashMap<String, String>)> {
+        let state = std::mem::take(self);
+        Some((state.session_id?, state.template?, state.values))
+    }
+}
+
+/// Prompt variables dialog widget
+pub struct PromptVariablesDialog;
+
+impl PromptVariablesDialog {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Render the dialog
+    pub fn render(&self, area: Rect, buf: &mut Buffer, state: &PromptVariablesDialogState) {
+        if !state.is_visible() {
+            return;
+        }
+        let (Some(template), Some(variable)) = (&state.template, state.current_variable()) else {
+            return;
+        };
+
+        let title = format!("/{}", template.name);
+        let frame = DialogFrame::new(&title, 72, 10)
+            .instructions(vec![("Enter", "next"), ("Esc", "cancel")]);
+        let inner = frame.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Variable name
+            Constraint::Length(1), // Spacing
+            Constraint::Length(3), // Input field
+            Constraint::Min(0),    // Remaining space
+        ])
+        .split(inner);
+
+        Paragraph::new(format!(
+            "{{{{{}}}}} ({}/{})",
+            variable,
+            state.current + 1,
+            state.missing.len()
+        ))
+        .style(Style::default().fg(text_muted()))
+        .render(chunks[0], buf);
+
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(Style::default().fg(accent_primary()));
+        let input_inner = input_block.inner(chunks[2]);
+        input_block.render(chunks[2], buf);
+
+        state.input.render_with_placeholder(
+            input_inner,
+            buf,
+            Style::default().fg(text_primary()),
+            variable,
+            Style::default().fg(text_muted()),
+        );
+    }
+}
+
+impl Default for PromptVariablesDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
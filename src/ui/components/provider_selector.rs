@@ -18,7 +18,7 @@ impl ProviderSelectorState {
         }
     }
 
-    fn all_providers() -> [AgentType; 4] {
+    fn all_providers() -> [AgentType; 5] {
         AgentType::preferred_order()
     }
 
@@ -28,6 +28,7 @@ impl ProviderSelectorState {
             AgentType::Codex => Tool::Codex,
             AgentType::Gemini => Tool::Gemini,
             AgentType::Opencode => Tool::Opencode,
+            AgentType::Acp => Tool::Acp,
         }
     }
 
@@ -47,8 +48,8 @@ impl ProviderSelectorState {
 
                 MultiSelectItem {
                     id: provider.as_str().to_string(),
-                    title: provider.display_name().to_string(),
-                    description: format!("{} provider", provider.display_name()),
+                    title: config.agent_display_name(provider).to_string(),
+                    description: format!("{} provider", config.agent_display_name(provider)),
                     checked,
                     disabled: !installed,
                 }
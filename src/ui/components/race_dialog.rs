@@ -0,0 +1,248 @@
+//! Race comparison dialog: one row per contender with its diff, cost and
+//! duration, and a "keep this one" action.
+
+use std::time::Duration;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use uuid::Uuid;
+
+use super::{
+    accent_error, accent_primary, accent_success, diff_add, diff_remove, text_muted, text_primary,
+    DialogFrame,
+};
+use crate::agent::events::ContextWindowState;
+use crate::git::GitDiffStats;
+use crate::ui::race::RaceOutcome;
+
+const DIALOG_WIDTH: u16 = 96;
+
+/// One contender's result as shown in the comparison
+#[derive(Debug, Clone)]
+pub struct RaceResultRow {
+    pub label: String,
+    pub workspace_id: Option<Uuid>,
+    pub workspace_name: Option<String>,
+    pub outcome: RaceOutcome,
+    pub duration: Option<Duration>,
+    pub diff: GitDiffStats,
+    pub total_tokens: i64,
+    pub cost_usd: f64,
+}
+
+/// State for the race comparison dialog
+#[derive(Debug, Clone, Default)]
+pub struct RaceDialogState {
+    visible: bool,
+    selected: usize,
+    prompt: String,
+    rows: Vec<RaceResultRow>,
+}
+
+impl RaceDialogState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn show(&mut self, prompt: &str, rows: Vec<RaceResultRow>) {
+        self.visible = true;
+        self.prompt = prompt.lines().next().unwrap_or_default().to_string();
+        self.selected = self.selected.min(rows.len().saturating_sub(1));
+        self.rows = rows;
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Move the highlight to the next contender, wrapping around
+    pub fn select_next(&mut self) {
+        if !self.rows.is_empty() {
+            self.selected = (self.selected + 1) % self.rows.len();
+        }
+    }
+
+    pub fn selected_row(&self) -> Option<&RaceResultRow> {
+        self.rows.get(self.selected)
+    }
+}
+
+/// Race comparison widget
+pub struct RaceDialog<'a> {
+    state: &'a RaceDialogState,
+}
+
+impl<'a> RaceDialog<'a> {
+    pub fn new(state: &'a RaceDialogState) -> Self {
+        Self { state }
+    }
+
+    fn format_duration(duration: Option<Duration>) -> String {
+        let Some(duration) = duration else {
+            return "—".to_string();
+        };
+        let secs = duration.as_secs();
+        if secs >= 60 {
+            format!("{}m {:02}s", secs / 60, secs % 60)
+        } else {
+            format!("{}s", secs)
+        }
+    }
+
+    fn row_line(row: &RaceResultRow, selected: bool, label_width: usize) -> Line<'static> {
+        let marker = if selected { "› " } else { "  " };
+        let label: String = row.label.chars().take(label_width).collect();
+        let label_style = if selected {
+            Style::default()
+                .fg(accent_primary())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(text_primary())
+        };
+        let additions = format!("+{}", row.diff.additions);
+        let deletions = format!("-{}", row.diff.deletions);
+        let cost = format!("${:.2}", row.cost_usd);
+        let outcome_color = match row.outcome {
+            RaceOutcome::Finished => accent_success(),
+            RaceOutcome::Failed => accent_error(),
+            RaceOutcome::Pending | RaceOutcome::Running => text_muted(),
+        };
+
+        Line::from(vec![
+            Span::styled(marker, label_style),
+            Span::styled(format!("{label:<label_width$} "), label_style),
+            Span::styled(
+                format!("{:<9}", row.outcome.display_name()),
+                Style::default().fg(outcome_color),
+            ),
+            Span::styled(
+                format!("{:>8} ", Self::format_duration(row.duration)),
+                Style::default().fg(text_primary()),
+            ),
+            Span::styled(format!("{additions:>7}"), Style::default().fg(diff_add())),
+            Span::styled(
+                format!("{deletions:>7} "),
+                Style::default().fg(diff_remove()),
+            ),
+            Span::styled(
+                format!(
+                    "{:>5} {:>8} {cost:>9}",
+                    row.diff.files_changed,
+                    ContextWindowState::format_tokens(row.total_tokens),
+                ),
+                Style::default().fg(text_primary()),
+            ),
+        ])
+    }
+}
+
+impl Widget for RaceDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !self.state.visible {
+            return;
+        }
+
+        // Padding, prompt, spacing, header, rows, spacing, hint, bottom spacing
+        let dialog_height = self.state.rows.len().max(1) as u16 + 10;
+        let frame =
+            DialogFrame::new("Race Results", DIALOG_WIDTH, dialog_height).instructions(vec![
+                ("Tab", "Next"),
+                ("Enter", "Keep this one"),
+                ("Esc", "Close"),
+            ]);
+        let inner = frame.render(area, buf);
+        if inner.height < 4 {
+            return;
+        }
+
+        let width = inner.width.saturating_sub(4) as usize;
+        // Columns around the label take 60 cells, including the marker
+        let label_width = width.saturating_sub(60).max(12);
+        let prompt: String = self.state.prompt.chars().take(width).collect();
+        let mut lines = vec![
+            Line::from(Span::styled(prompt, Style::default().fg(text_muted()))),
+            Line::default(),
+            Line::from(Span::styled(
+                format!(
+                    "  {:<label_width$} {:<9}{:>8} {:>7}{:>7} {:>5} {:>8} {:>9}",
+                    "Contender", "Status", "Time", "Added", "Removed", "Files", "Tokens", "Cost"
+                ),
+                Style::default()
+                    .fg(text_muted())
+                    .add_modifier(Modifier::BOLD),
+            )),
+        ];
+        for (index, row) in self.state.rows.iter().enumerate() {
+            lines.push(Self::row_line(
+                row,
+                index == self.state.selected,
+                label_width,
+            ));
+        }
+        lines.push(Line::default());
+        let hint = match self.state.selected_row() {
+            Some(row) => format!(
+                "Keeping {} archives the other race workspaces.",
+                row.workspace_name.as_deref().unwrap_or("this contender")
+            ),
+            None => "No contenders in this race.".to_string(),
+        };
+        lines.push(Line::from(Span::styled(
+            hint,
+            Style::default().fg(text_muted()),
+        )));
+
+        Paragraph::new(lines).render(
+            Rect {
+                x: inner.x + 2,
+                y: inner.y + 1,
+                width: inner.width.saturating_sub(4),
+                height: inner.height.saturating_sub(2),
+            },
+            buf,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(label: &str) -> RaceResultRow {
+        RaceResultRow {
+            label: label.to_string(),
+            workspace_id: Some(Uuid::new_v4()),
+            workspace_name: Some(label.to_lowercase()),
+            outcome: RaceOutcome::Finished,
+            duration: Some(Duration::from_secs(125)),
+            diff: GitDiffStats::default(),
+            total_tokens: 0,
+            cost_usd: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_race_dialog_selection_wraps() {
+        let mut state = RaceDialogState::new();
+        state.show("Fix the flaky test\nMore detail", vec![row("A"), row("B")]);
+        assert_eq!(state.prompt, "Fix the flaky test");
+        assert_eq!(state.selected_row().unwrap().label, "A");
+        state.select_next();
+        assert_eq!(state.selected_row().unwrap().label, "B");
+        state.select_next();
+        assert_eq!(state.selected_row().unwrap().label, "A");
+        assert_eq!(
+            RaceDialog::format_duration(Some(Duration::from_secs(125))),
+            "2m 05s"
+        );
+    }
+}
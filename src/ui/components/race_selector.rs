@@ -0,0 +1,168 @@
+//! Race contender picker built on the reusable multi-select dialog.
+
+use crate::agent::{AgentType, ModelRegistry};
+use crate::ui::race::RaceContender;
+
+use super::{MultiSelectDialog, MultiSelectDialogState, MultiSelectItem};
+
+/// Fewest contenders that make a race worth running
+const MIN_RACE_CONTENDERS: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct RaceSelectorState {
+    pub dialog: MultiSelectDialogState,
+}
+
+impl RaceSelectorState {
+    pub fn new() -> Self {
+        Self {
+            dialog: MultiSelectDialogState::new(10),
+        }
+    }
+
+    /// List every model of the given providers, pre-checking `selected`
+    pub fn configure_for(providers: &[AgentType], selected: &[RaceContender]) -> Self {
+        let items = AgentType::preferred_order()
+            .into_iter()
+            .filter(|provider| providers.contains(provider))
+            .flat_map(ModelRegistry::models_for)
+            .map(|model| {
+                let contender = RaceContender::new(model.agent_type, model.id.clone());
+                MultiSelectItem {
+                    id: contender.to_string(),
+                    title: contender.label(),
+                    description: model.description,
+                    checked: selected.contains(&contender),
+                    disabled: false,
+                }
+            })
+            .collect();
+
+        let mut state = Self::new();
+        state.dialog.configure(
+            "Race Agents",
+            Some("Pick the agent/model pairs to race. Each gets its own workspace.".to_string()),
+            items,
+        );
+        state
+    }
+
+    pub fn show(&mut self) {
+        self.dialog.show();
+    }
+
+    pub fn hide(&mut self) {
+        self.dialog.hide();
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.dialog.is_visible()
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.dialog.insert_char(c);
+    }
+
+    pub fn insert_str(&mut self, s: &str) {
+        self.dialog.insert_str(s);
+    }
+
+    pub fn delete_char(&mut self) {
+        self.dialog.delete_char();
+    }
+
+    pub fn delete_forward(&mut self) {
+        self.dialog.delete_forward();
+    }
+
+    pub fn move_cursor_left(&mut self) {
+        self.dialog.move_cursor_left();
+    }
+
+    pub fn move_cursor_right(&mut self) {
+        self.dialog.move_cursor_right();
+    }
+
+    pub fn move_cursor_start(&mut self) {
+        self.dialog.move_cursor_start();
+    }
+
+    pub fn move_cursor_end(&mut self) {
+        self.dialog.move_cursor_end();
+    }
+
+    pub fn select_next(&mut self) {
+        self.dialog.select_next();
+    }
+
+    pub fn select_previous(&mut self) {
+        self.dialog.select_previous();
+    }
+
+    pub fn select_at_row(&mut self, row: usize) -> bool {
+        self.dialog.select_at_row(row)
+    }
+
+    pub fn toggle_selected(&mut self) -> bool {
+        self.dialog.toggle_selected()
+    }
+
+    pub fn selected_contenders(&self) -> Vec<RaceContender> {
+        self.dialog
+            .selected_ids()
+            .into_iter()
+            .filter_map(|id| id.parse().ok())
+            .collect()
+    }
+
+    pub fn validate(&mut self) -> bool {
+        if self.selected_contenders().len() < MIN_RACE_CONTENDERS {
+            self.dialog.validation_error = Some(format!(
+                "Select at least {} agent/model pairs.",
+                MIN_RACE_CONTENDERS
+            ));
+            return false;
+        }
+        self.dialog.validation_error = None;
+        true
+    }
+}
+
+impl Default for RaceSelectorState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub type RaceSelector = MultiSelectDialog;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_race_selector_round_trips_contenders() {
+        let opus = RaceContender::new(AgentType::Claude, "opus");
+        let mut state = RaceSelectorState::configure_for(
+            &[AgentType::Claude, AgentType::Codex],
+            &[opus.clone()],
+        );
+        assert!(!state.validate());
+
+        let codex = RaceContender::new(
+            AgentType::Codex,
+            ModelRegistry::default_model(AgentType::Codex),
+        );
+        let item = state
+            .dialog
+            .items
+            .iter_mut()
+            .find(|item| item.id == codex.to_string())
+            .expect("default Codex model should be listed");
+        item.checked = true;
+
+        assert!(state.validate());
+        let selected = state.selected_contenders();
+        assert!(selected.contains(&opus) && selected.contains(&codex));
+    }
+}
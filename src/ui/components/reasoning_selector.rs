@@ -210,7 +210,7 @@ impl ReasoningSelectorState {
                     description: "Maximum reasoning budget",
                 });
             }
-            AgentType::Gemini | AgentType::Opencode => {}
+            AgentType::Gemini | AgentType::Opencode | AgentType::Acp => {}
         }
         options
     }
@@ -387,7 +387,7 @@ impl ReasoningSelector {
         let hint = match state.agent_type {
             Some(AgentType::Claude) => "Claude supports: auto, low, medium, high",
             Some(AgentType::Codex) => "Codex supports: auto, minimal, low, medium, high, xhigh",
-            Some(AgentType::Gemini) | Some(AgentType::Opencode) | None => {
+            Some(AgentType::Gemini) | Some(AgentType::Opencode) | Some(AgentType::Acp) | None => {
                 "Reasoning effort is not available for this agent"
             }
         };
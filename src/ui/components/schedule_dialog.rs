@@ -0,0 +1,388 @@
+//! Scheduled prompts dialog: every cron entry with its target and next run,
+//! plus inline actions to run, toggle, delete and add entries.
+
+use chrono::Local;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+use uuid::Uuid;
+
+use super::{accent_error, accent_primary, text_muted, text_primary, DialogFrame, TextInputState};
+use crate::data::ScheduledPrompt;
+
+const DIALOG_WIDTH: u16 = 96;
+/// Rows shown at once before the list scrolls
+const MAX_VISIBLE_ROWS: usize = 10;
+
+/// A scheduled prompt with its resolved target label
+#[derive(Debug, Clone)]
+pub struct ScheduleRow {
+    pub entry: ScheduledPrompt,
+    /// e.g. `conduit / bold-fox` or `conduit / new workspace`
+    pub target: String,
+}
+
+/// Where a new entry added from the dialog runs
+#[derive(Debug, Clone)]
+pub struct ScheduleAddTarget {
+    pub repository_id: Uuid,
+    pub workspace_id: Uuid,
+    pub label: String,
+}
+
+/// State for the scheduled prompts dialog
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleDialogState {
+    visible: bool,
+    selected: usize,
+    scroll_offset: usize,
+    rows: Vec<ScheduleRow>,
+    /// Target for new entries (the active workspace), if there is one
+    add_target: Option<ScheduleAddTarget>,
+    /// `<cron> | <prompt>` being typed, while adding
+    pub input: Option<TextInputState>,
+    /// Why the last add failed
+    error: Option<String>,
+}
+
+impl ScheduleDialogState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn show(&mut self, rows: Vec<ScheduleRow>, add_target: Option<ScheduleAddTarget>) {
+        self.visible = true;
+        self.selected = 0;
+        self.scroll_offset = 0;
+        self.rows = rows;
+        self.add_target = add_target;
+        self.input = None;
+        self.error = None;
+    }
+
+    /// Replace the rows, keeping the selection on the same entry when possible
+    pub fn set_rows(&mut self, rows: Vec<ScheduleRow>) {
+        let selected_id = self.selected_entry().map(|entry| entry.id);
+        self.rows = rows;
+        self.selected = selected_id
+            .and_then(|id| self.rows.iter().position(|row| row.entry.id == id))
+            .unwrap_or_else(|| self.selected.min(self.rows.len().saturating_sub(1)));
+        self.scroll_offset = self
+            .scroll_offset
+            .min(self.selected)
+            .max((self.selected + 1).saturating_sub(MAX_VISIBLE_ROWS));
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+        self.input = None;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.rows.len() {
+            self.selected += 1;
+            if self.selected >= self.scroll_offset + MAX_VISIBLE_ROWS {
+                self.scroll_offset = self.selected + 1 - MAX_VISIBLE_ROWS;
+            }
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+        if self.selected < self.scroll_offset {
+            self.scroll_offset = self.selected;
+        }
+    }
+
+    pub fn selected_entry(&self) -> Option<&ScheduledPrompt> {
+        self.rows.get(self.selected).map(|row| &row.entry)
+    }
+
+    pub fn add_target(&self) -> Option<&ScheduleAddTarget> {
+        self.add_target.as_ref()
+    }
+
+    pub fn is_adding(&self) -> bool {
+        self.input.is_some()
+    }
+
+    /// Start typing a new entry; false when there is no workspace to add to
+    pub fn start_adding(&mut self) -> bool {
+        if self.add_target.is_none() {
+            return false;
+        }
+        self.input = Some(TextInputState::new());
+        self.error = None;
+        true
+    }
+
+    pub fn cancel_adding(&mut self) {
+        self.input = None;
+        self.error = None;
+    }
+
+    pub fn set_error(&mut self, error: impl Into<String>) {
+        self.error = Some(error.into());
+    }
+
+    /// Split the typed `<cron> | <prompt>` into its parts
+    pub fn parse_input(&self) -> Result<(String, String), String> {
+        let value = self.input.as_ref().map(|input| input.value()).unwrap_or("");
+        let Some((schedule, prompt)) = value.split_once('|') else {
+            return Err("Use <cron> | <prompt>, e.g. 0 8 * * 1-5 | run the tests".to_string());
+        };
+        Ok((schedule.trim().to_string(), prompt.trim().to_string()))
+    }
+}
+
+/// Scheduled prompts widget
+pub struct ScheduleDialog<'a> {
+    state: &'a ScheduleDialogState,
+}
+
+impl<'a> ScheduleDialog<'a> {
+    pub fn new(state: &'a ScheduleDialogState) -> Self {
+        Self { state }
+    }
+
+    fn next_label(entry: &ScheduledPrompt) -> String {
+        if !entry.enabled {
+            return "paused".to_string();
+        }
+        match entry.next_run_at {
+            Some(next) => next
+                .with_timezone(&Local)
+                .format("%a %b %d %H:%M")
+                .to_string(),
+            None => "never".to_string(),
+        }
+    }
+
+    fn row_line(row: &ScheduleRow, selected: bool, width: usize) -> Line<'static> {
+        let marker = if selected { "› " } else { "  " };
+        let label_style = if selected {
+            Style::default()
+                .fg(accent_primary())
+                .add_modifier(Modifier::BOLD)
+        } else if row.entry.enabled {
+            Style::default().fg(text_primary())
+        } else {
+            Style::default().fg(text_muted())
+        };
+        let name: String = row.entry.name.chars().take(24).collect();
+        let head = format!(
+            "{:<24}  {:<14}  {:<16}  ",
+            name,
+            row.entry.schedule,
+            Self::next_label(&row.entry)
+        );
+        let target_width = width.saturating_sub(head.chars().count() + marker.len());
+        let target: String = row.target.chars().take(target_width).collect();
+
+        Line::from(vec![
+            Span::styled(marker, label_style),
+            Span::styled(head, label_style),
+            Span::styled(target, Style::default().fg(text_muted())),
+        ])
+    }
+
+    fn detail_line(&self) -> Line<'static> {
+        if let Some(error) = &self.state.error {
+            return Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(accent_error()),
+            ));
+        }
+        let Some(entry) = self.state.selected_entry() else {
+            return Line::default();
+        };
+        if let Some(error) = &entry.last_error {
+            return Line::from(Span::styled(
+                format!("Last run failed: {}", error),
+                Style::default().fg(accent_error()),
+            ));
+        }
+        let last_run = entry
+            .last_run_at
+            .map(|at| {
+                format!(
+                    "Last ran {}. ",
+                    at.with_timezone(&Local).format("%b %d %H:%M")
+                )
+            })
+            .unwrap_or_default();
+        let prompt = entry.prompt.lines().next().unwrap_or("");
+        Line::from(Span::styled(
+            format!("{}Prompt: {}", last_run, prompt),
+            Style::default().fg(text_muted()),
+        ))
+    }
+}
+
+impl Widget for ScheduleDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !self.state.visible {
+            return;
+        }
+
+        let visible_rows = self.state.rows.len().clamp(1, MAX_VISIBLE_ROWS);
+        // Padding, rows, spacing, detail, bottom spacing, optional input box
+        let input_height = if self.state.is_adding() { 5 } else { 0 };
+        let dialog_height = visible_rows as u16 + 7 + input_height;
+        let instructions = if self.state.is_adding() {
+            vec![("Enter", "Add"), ("Esc", "Cancel")]
+        } else {
+            vec![
+                ("↑↓", "Select"),
+                ("Enter", "Run now"),
+                ("Space", "Pause"),
+                ("x", "Delete"),
+                ("a", "Add"),
+                ("Esc", "Close"),
+            ]
+        };
+        let frame = DialogFrame::new("Scheduled Prompts", DIALOG_WIDTH, dialog_height)
+            .instructions(instructions);
+        let inner = frame.render(area, buf);
+        if inner.height < 4 {
+            return;
+        }
+
+        let width = inner.width.saturating_sub(4) as usize;
+        let mut lines = Vec::new();
+        if self.state.rows.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No scheduled prompts. Press a to add one for this workspace.",
+                Style::default().fg(text_muted()),
+            )));
+        }
+        for (index, row) in self
+            .state
+            .rows
+            .iter()
+            .enumerate()
+            .skip(self.state.scroll_offset)
+            .take(MAX_VISIBLE_ROWS)
+        {
+            lines.push(Self::row_line(row, index == self.state.selected, width));
+        }
+        lines.push(Line::default());
+        lines.push(self.detail_line());
+
+        let content = Rect {
+            x: inner.x + 2,
+            y: inner.y + 1,
+            width: inner.width.saturating_sub(4),
+            height: inner.height.saturating_sub(2),
+        };
+        Paragraph::new(lines).render(content, buf);
+
+        let (Some(input), Some(target)) = (&self.state.input, &self.state.add_target) else {
+            return;
+        };
+        let label_y = content.y + visible_rows as u16 + 3;
+        if label_y + 4 > content.y + content.height {
+            return;
+        }
+        Paragraph::new(format!("New schedule for {}", target.label))
+            .style(Style::default().fg(text_muted()))
+            .render(
+                Rect {
+                    y: label_y,
+                    height: 1,
+                    ..content
+                },
+                buf,
+            );
+        let input_area = Rect {
+            y: label_y + 1,
+            height: 3,
+            ..content
+        };
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(Style::default().fg(accent_primary()));
+        let input_inner = input_block.inner(input_area);
+        input_block.render(input_area, buf);
+        input.render_with_placeholder(
+            input_inner,
+            buf,
+            Style::default().fg(text_primary()),
+            "0 8 * * 1-5 | run the tests and fix flaky failures",
+            Style::default().fg(text_muted()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(count: usize) -> Vec<ScheduleRow> {
+        (0..count)
+            .map(|i| ScheduleRow {
+                entry: ScheduledPrompt::new(
+                    Uuid::new_v4(),
+                    None,
+                    format!("entry {i}"),
+                    "@daily",
+                    "prompt",
+                ),
+                target: "repo / new workspace".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_schedule_dialog_keeps_selection_on_refresh() {
+        let mut state = ScheduleDialogState::new();
+        let initial = rows(3);
+        state.show(initial.clone(), None);
+        state.select_next();
+        let selected = state.selected_entry().unwrap().id;
+
+        // The first entry was deleted
+        state.set_rows(initial[1..].to_vec());
+        assert_eq!(state.selected_entry().unwrap().id, selected);
+
+        state.set_rows(Vec::new());
+        assert!(state.selected_entry().is_none());
+    }
+
+    #[test]
+    fn test_schedule_dialog_add_requires_target() {
+        let mut state = ScheduleDialogState::new();
+        state.show(Vec::new(), None);
+        assert!(!state.start_adding());
+
+        state.show(
+            Vec::new(),
+            Some(ScheduleAddTarget {
+                repository_id: Uuid::new_v4(),
+                workspace_id: Uuid::new_v4(),
+                label: "repo / bold-fox".to_string(),
+            }),
+        );
+        assert!(state.start_adding());
+        for c in "@daily | update deps".chars() {
+            state.input.as_mut().unwrap().insert_char(c);
+        }
+        assert_eq!(
+            state.parse_input().unwrap(),
+            ("@daily".to_string(), "update deps".to_string())
+        );
+
+        state.input.as_mut().unwrap().clear();
+        assert!(state.parse_input().is_err());
+    }
+}
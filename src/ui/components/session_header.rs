@@ -2,7 +2,8 @@
 //!
 //! This component renders a fixed header below the tab bar showing
 //! the session title/description. Shows "New session" in muted text
-//! when no title has been generated yet.
+//! when no title has been generated yet. A running or finished goal loop
+//! shows its progress on the right.
 
 use ratatui::{
     buffer::Buffer,
@@ -12,18 +13,30 @@ use ratatui::{
     widgets::Widget,
 };
 
-use super::{bg_elevated, text_muted, text_secondary};
+use super::{
+    accent_error, accent_primary, accent_success, accent_warning, bg_elevated, text_muted,
+    text_secondary,
+};
+use crate::ui::goal::{GoalLoop, GoalStatus};
 
 /// Session header component
 pub struct SessionHeader<'a> {
     /// The session title (None = new session)
     title: Option<&'a str>,
+    /// Goal loop progress shown on the right
+    goal: Option<&'a GoalLoop>,
 }
 
 impl<'a> SessionHeader<'a> {
     /// Create a new session header
     pub fn new(title: Option<&'a str>) -> Self {
-        Self { title }
+        Self { title, goal: None }
+    }
+
+    /// Show a goal loop's progress
+    pub fn goal(mut self, goal: Option<&'a GoalLoop>) -> Self {
+        self.goal = goal;
+        self
     }
 }
 
@@ -41,10 +54,18 @@ impl Widget for SessionHeader<'_> {
             }
         }
 
+        // Goal progress, right-aligned; dropped when the header is too narrow
+        let goal = self.goal.and_then(|goal| {
+            let label = goal.progress_label();
+            let width = label.chars().count() as u16 + 2;
+            (width + 16 <= area.width).then_some((goal, label, width))
+        });
+        let goal_width = goal.as_ref().map_or(0, |(_, _, width)| *width);
+
         // Display text
         let text = self.title.unwrap_or("New session");
         // Reserve 2 chars for leading padding + 1 for ellipsis safety
-        let max_display_chars = area.width.saturating_sub(4) as usize;
+        let max_display_chars = area.width.saturating_sub(4 + goal_width) as usize;
 
         // UTF-8 safe truncation: count by characters, not bytes
         let display = truncate_utf8(text, max_display_chars);
@@ -62,6 +83,18 @@ impl Widget for SessionHeader<'_> {
         ]);
 
         buf.set_line(area.x, area.y, &line, area.width);
+
+        if let Some((goal, label, width)) = goal {
+            let color = match goal.status {
+                GoalStatus::Working => accent_primary(),
+                GoalStatus::Verifying => accent_warning(),
+                GoalStatus::Passed => accent_success(),
+                GoalStatus::GaveUp(_) => accent_error(),
+                GoalStatus::Stopped => text_muted(),
+            };
+            let line = Line::from(Span::styled(label, bg_style.fg(color)));
+            buf.set_line(area.x + area.width - width, area.y, &line, width);
+        }
     }
 }
 
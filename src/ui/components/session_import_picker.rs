@@ -10,7 +10,7 @@ use ratatui::{
 };
 
 use super::{
-    agent_claude, agent_codex, agent_gemini, agent_opencode, bg_highlight, dialog_bg,
+    agent_acp, agent_claude, agent_codex, agent_gemini, agent_opencode, bg_highlight, dialog_bg,
     dialog_content_area, ensure_contrast_bg, ensure_contrast_fg, render_minimal_scrollbar,
     selected_bg, text_muted, text_primary, DialogFrame, ScrollbarMetrics, SearchableListState,
 };
@@ -559,12 +559,14 @@ impl SessionImportPicker {
                 AgentType::Codex => "X",
                 AgentType::Gemini => "G",
                 AgentType::Opencode => "O",
+                AgentType::Acp => "A",
             };
             let agent_color = match session.agent_type {
                 AgentType::Claude => agent_claude(),
                 AgentType::Codex => agent_codex(),
                 AgentType::Gemini => agent_gemini(),
                 AgentType::Opencode => agent_opencode(),
+                AgentType::Acp => agent_acp(),
             };
 
             // Calculate widths
@@ -0,0 +1,176 @@
+//! Workspace setup pane: live output of a new workspace's setup hook, shown
+//! under the chat until setup succeeds.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+use super::{accent_error, accent_primary, text_muted, text_primary};
+
+/// Output lines kept per workspace
+const MAX_OUTPUT_LINES: usize = 500;
+/// Output lines shown in the pane
+pub const SETUP_PANE_LINES: u16 = 6;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetupStatus {
+    Running,
+    Failed(String),
+}
+
+/// A prompt held back until setup succeeds
+#[derive(Debug, Clone)]
+pub struct DeferredPrompt {
+    pub prompt: String,
+    pub images: Vec<PathBuf>,
+    pub image_placeholders: Vec<String>,
+    pub stdin_payload: Option<String>,
+}
+
+/// Setup progress for one workspace
+#[derive(Debug, Clone)]
+pub struct WorkspaceSetupState {
+    pub status: SetupStatus,
+    output: VecDeque<String>,
+    started_at: Instant,
+    /// Hidden prompt (e.g. a fork seed) to send once setup succeeds
+    pub deferred_prompt: Option<DeferredPrompt>,
+}
+
+impl WorkspaceSetupState {
+    pub fn running() -> Self {
+        Self {
+            status: SetupStatus::Running,
+            output: VecDeque::new(),
+            started_at: Instant::now(),
+            deferred_prompt: None,
+        }
+    }
+
+    pub fn failed(error: impl Into<String>) -> Self {
+        Self {
+            status: SetupStatus::Failed(error.into()),
+            ..Self::running()
+        }
+    }
+
+    /// Start over for a retry, keeping any deferred prompt
+    pub fn restart(&mut self) {
+        self.status = SetupStatus::Running;
+        self.output.clear();
+        self.started_at = Instant::now();
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.status == SetupStatus::Running
+    }
+
+    pub fn push_line(&mut self, line: String) {
+        if self.output.len() == MAX_OUTPUT_LINES {
+            self.output.pop_front();
+        }
+        self.output.push_back(line);
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Height of the pane, borders included
+    pub fn height(&self) -> u16 {
+        SETUP_PANE_LINES + 2
+    }
+}
+
+/// Setup pane widget
+pub struct SetupPane<'a> {
+    state: &'a WorkspaceSetupState,
+}
+
+impl<'a> SetupPane<'a> {
+    pub fn new(state: &'a WorkspaceSetupState) -> Self {
+        Self { state }
+    }
+}
+
+impl Widget for SetupPane<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 3 {
+            return;
+        }
+        let (title, color) = match &self.state.status {
+            SetupStatus::Running => (
+                format!(
+                    " Setting up workspace · {}s · prompts wait until it finishes ",
+                    self.state.elapsed().as_secs()
+                ),
+                accent_primary(),
+            ),
+            SetupStatus::Failed(error) => (
+                format!(" Setup failed: {} · /setup to retry ", error),
+                accent_error(),
+            ),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(Style::default().fg(color))
+            .title(Span::styled(
+                title,
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let visible = inner.height as usize;
+        let skip = self.state.output.len().saturating_sub(visible);
+        let mut lines: Vec<Line> = self
+            .state
+            .output
+            .iter()
+            .skip(skip)
+            .map(|line| {
+                Line::from(Span::styled(
+                    line.clone(),
+                    Style::default().fg(text_primary()),
+                ))
+            })
+            .collect();
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "Waiting for output…",
+                Style::default().fg(text_muted()),
+            )));
+        }
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setup_state_keeps_recent_output() {
+        let mut state = WorkspaceSetupState::running();
+        for i in 0..MAX_OUTPUT_LINES + 10 {
+            state.push_line(format!("line {i}"));
+        }
+        assert_eq!(state.output.len(), MAX_OUTPUT_LINES);
+        assert_eq!(state.output.front().unwrap(), "line 10");
+
+        state.status = SetupStatus::Failed("exit 1".to_string());
+        state.restart();
+        assert!(state.is_running());
+        assert!(state.output.is_empty());
+    }
+}
@@ -14,15 +14,30 @@ use super::{
     accent_primary, bg_highlight, dialog_bg, ensure_contrast_bg, ensure_contrast_fg,
     render_minimal_scrollbar, text_muted, text_primary, SearchableListState,
 };
+use crate::config::UserCommand;
+use crate::session::PromptTemplate;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SlashCommand {
     Model,
     Reasoning,
+    Sandbox,
     Providers,
     NewSession,
     Fork,
     Handoff,
+    Race,
+    Diff,
+    Checkpoints,
+    Schedules,
+    Setup,
+    Goal,
+    Search,
+    History,
+    /// A prompt template, by index into [`SlashMenuState::prompts`]
+    Prompt(usize),
+    /// A `[[commands]]` entry, by index into [`SlashMenuState::user_commands`]
+    User(usize),
 }
 
 impl SlashCommand {
@@ -30,10 +45,21 @@ impl SlashCommand {
         match self {
             SlashCommand::Model => "/model",
             SlashCommand::Reasoning => "/reasoning",
+            SlashCommand::Sandbox => "/sandbox",
             SlashCommand::Providers => "/providers",
             SlashCommand::NewSession => "/new",
             SlashCommand::Fork => "/fork",
             SlashCommand::Handoff => "/handoff",
+            SlashCommand::Race => "/race",
+            SlashCommand::Diff => "/diff",
+            SlashCommand::Checkpoints => "/checkpoints",
+            SlashCommand::Schedules => "/schedules",
+            SlashCommand::Setup => "/setup",
+            SlashCommand::Goal => "/goal",
+            SlashCommand::Search => "/search",
+            SlashCommand::History => "/history",
+            SlashCommand::Prompt(_) => "/prompt",
+            SlashCommand::User(_) => "/command",
         }
     }
 
@@ -41,10 +67,21 @@ impl SlashCommand {
         match self {
             SlashCommand::Model => "Select model",
             SlashCommand::Reasoning => "Set reasoning effort",
+            SlashCommand::Sandbox => "Set Codex sandbox and approvals",
             SlashCommand::Providers => "Select enabled providers",
             SlashCommand::NewSession => "Start a new session",
             SlashCommand::Fork => "Fork current session",
             SlashCommand::Handoff => "Handoff current session",
+            SlashCommand::Race => "Race agents on one prompt",
+            SlashCommand::Diff => "Review workspace diff",
+            SlashCommand::Checkpoints => "Restore an earlier turn",
+            SlashCommand::Schedules => "Manage scheduled prompts",
+            SlashCommand::Setup => "Run workspace setup again",
+            SlashCommand::Goal => "Loop until a command passes",
+            SlashCommand::Search => "Search the chat transcript",
+            SlashCommand::History => "Search all sessions",
+            SlashCommand::Prompt(_) => "Saved prompt",
+            SlashCommand::User(_) => "User command",
         }
     }
 }
@@ -52,16 +89,37 @@ impl SlashCommand {
 #[derive(Debug, Clone)]
 pub struct SlashCommandEntry {
     pub command: SlashCommand,
-    pub label: &'static str,
-    pub description: &'static str,
+    pub label: String,
+    pub description: String,
 }
 
 impl SlashCommandEntry {
     fn new(command: SlashCommand) -> Self {
         Self {
             command,
-            label: command.label(),
-            description: command.description(),
+            label: command.label().to_string(),
+            description: command.description().to_string(),
+        }
+    }
+
+    fn prompt(index: usize, template: &PromptTemplate) -> Self {
+        let description = if template.description.is_empty() {
+            SlashCommand::Prompt(index).description().to_string()
+        } else {
+            template.description.clone()
+        };
+        Self {
+            command: SlashCommand::Prompt(index),
+            label: format!("/{}", template.name),
+            description,
+        }
+    }
+
+    fn user(index: usize, command: &UserCommand) -> Self {
+        Self {
+            command: SlashCommand::User(index),
+            label: format!("/{}", command.name),
+            description: command.summary(),
         }
     }
 }
@@ -70,6 +128,10 @@ impl SlashCommandEntry {
 pub struct SlashMenuState {
     pub visible: bool,
     pub commands: Vec<SlashCommandEntry>,
+    /// Names of the user commands listed after the built-in commands
+    pub user_commands: Vec<String>,
+    /// Prompt templates listed after the user commands
+    pub prompts: Vec<PromptTemplate>,
     pub list: SearchableListState,
 }
 
@@ -78,13 +140,37 @@ impl SlashMenuState {
         Self {
             visible: false,
             commands: Vec::new(),
+            user_commands: Vec::new(),
+            prompts: Vec::new(),
             list: SearchableListState::new(6),
         }
     }
 
     pub fn show(&mut self) {
+        self.show_with(&[], Vec::new());
+    }
+
+    /// Show the built-in commands followed by `user_commands` and `prompts`
+    pub fn show_with(&mut self, user_commands: &[UserCommand], prompts: Vec<PromptTemplate>) {
         self.visible = true;
         self.commands = Self::build_commands();
+        self.commands.extend(
+            user_commands
+                .iter()
+                .enumerate()
+                .map(|(index, command)| SlashCommandEntry::user(index, command)),
+        );
+        self.user_commands = user_commands
+            .iter()
+            .map(|command| command.name.clone())
+            .collect();
+        self.commands.extend(
+            prompts
+                .iter()
+                .enumerate()
+                .map(|(index, template)| SlashCommandEntry::prompt(index, template)),
+        );
+        self.prompts = prompts;
         self.list.reset();
         self.list.filtered = (0..self.commands.len()).collect();
     }
@@ -146,14 +232,31 @@ impl SlashMenuState {
         self.commands.get(*idx)
     }
 
+    pub fn prompt(&self, index: usize) -> Option<&PromptTemplate> {
+        self.prompts.get(index)
+    }
+
+    pub fn user_command(&self, index: usize) -> Option<&str> {
+        self.user_commands.get(index).map(String::as_str)
+    }
+
     fn build_commands() -> Vec<SlashCommandEntry> {
         vec![
             SlashCommandEntry::new(SlashCommand::Model),
             SlashCommandEntry::new(SlashCommand::Reasoning),
+            SlashCommandEntry::new(SlashCommand::Sandbox),
             SlashCommandEntry::new(SlashCommand::Providers),
             SlashCommandEntry::new(SlashCommand::NewSession),
             SlashCommandEntry::new(SlashCommand::Fork),
             SlashCommandEntry::new(SlashCommand::Handoff),
+            SlashCommandEntry::new(SlashCommand::Race),
+            SlashCommandEntry::new(SlashCommand::Diff),
+            SlashCommandEntry::new(SlashCommand::Checkpoints),
+            SlashCommandEntry::new(SlashCommand::Schedules),
+            SlashCommandEntry::new(SlashCommand::Setup),
+            SlashCommandEntry::new(SlashCommand::Goal),
+            SlashCommandEntry::new(SlashCommand::Search),
+            SlashCommandEntry::new(SlashCommand::History),
         ]
     }
 
@@ -308,14 +411,14 @@ impl SlashMenu {
             let prefix = if is_selected { "> " } else { "  " };
             let prefix_width = UnicodeWidthStr::width(prefix);
             let available_cmd_width = (content_width as usize).saturating_sub(prefix_width);
-            let cmd_display = truncate_to_width(entry.label, available_cmd_width);
+            let cmd_display = truncate_to_width(&entry.label, available_cmd_width);
             let cmd_width = UnicodeWidthStr::width(cmd_display.as_str());
             let has_desc = !entry.description.is_empty();
             let gap = if has_desc { 3 } else { 0 };
             let available_desc_width =
                 (content_width as usize).saturating_sub(prefix_width + cmd_width + gap);
             let desc_display = if has_desc && available_desc_width > 0 {
-                truncate_to_width(entry.description, available_desc_width)
+                truncate_to_width(&entry.description, available_desc_width)
             } else {
                 String::new()
             };
@@ -460,4 +563,61 @@ mod tests {
         let entry = state.selected_entry().expect("Should match /handoff");
         assert_eq!(entry.command, SlashCommand::Handoff);
     }
+
+    #[test]
+    fn test_slash_menu_filters_race_command() {
+        let mut state = SlashMenuState::new();
+        state.show();
+        state.insert_char('r');
+        state.insert_char('a');
+        state.insert_char('c');
+        state.insert_char('e');
+
+        let entry = state.selected_entry().expect("Should match /race");
+        assert_eq!(entry.command, SlashCommand::Race);
+        assert_eq!(entry.label, "/race");
+    }
+
+    #[test]
+    fn test_slash_menu_lists_prompt_templates() {
+        let template = PromptTemplate {
+            name: "security-review".to_string(),
+            description: String::new(),
+            body: "Audit {{diff}}".to_string(),
+            path: std::path::PathBuf::from("security-review.md"),
+        };
+        let mut state = SlashMenuState::new();
+        state.show_with(&[], vec![template.clone()]);
+        for c in "secur".chars() {
+            state.insert_char(c);
+        }
+
+        let entry = state.selected_entry().expect("Should match the template");
+        assert_eq!(entry.command, SlashCommand::Prompt(0));
+        assert_eq!(entry.label, "/security-review");
+        assert_eq!(entry.description, "Saved prompt");
+        assert_eq!(state.prompt(0), Some(&template));
+    }
+
+    #[test]
+    fn test_slash_menu_lists_user_commands() {
+        let command = UserCommand {
+            name: "ship".to_string(),
+            description: "Test, then open a PR".to_string(),
+            kind: crate::config::UserCommandKind::Macro {
+                steps: vec![crate::ui::action::Action::OpenPr],
+            },
+        };
+        let mut state = SlashMenuState::new();
+        state.show_with(&[command], Vec::new());
+        for c in "ship".chars() {
+            state.insert_char(c);
+        }
+
+        let entry = state.selected_entry().expect("Should match the command");
+        assert_eq!(entry.command, SlashCommand::User(0));
+        assert_eq!(entry.label, "/ship");
+        assert_eq!(entry.description, "Test, then open a PR");
+        assert_eq!(state.user_command(0), Some("ship"));
+    }
 }
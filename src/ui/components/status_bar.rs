@@ -24,9 +24,13 @@ const RIPPLE_FRAMES: &[&str] = &["·", "∙", "•", "●", "•", "∙"];
 /// Status bar component showing session info
 pub struct StatusBar {
     agent_type: AgentType,
+    /// Name shown for the agent
+    agent_name: String,
     agent_mode: AgentMode,
     model: Option<String>,
     shell_mode: bool,
+    /// Contenders waiting for a race prompt (0 when not in race mode)
+    race_contenders: usize,
     session_id: Option<SessionId>,
     token_usage: TokenUsage,
     estimated_cost: f64,
@@ -62,6 +66,8 @@ pub struct StatusBar {
     context_state: Option<ContextWindowState>,
     /// Number of queued messages
     queue_count: usize,
+    /// Provider limit label and seconds until the automatic retry
+    rate_limit: Option<(&'static str, Option<u64>)>,
     /// Whether plan mode is supported for this agent
     supports_plan_mode: bool,
     /// Spinner frame index (shared animation tick)
@@ -72,9 +78,11 @@ impl StatusBar {
     pub fn new(agent_type: AgentType) -> Self {
         Self {
             agent_type,
+            agent_name: agent_type.display_name().to_string(),
             agent_mode: AgentMode::default(),
             model: None,
             shell_mode: false,
+            race_contenders: 0,
             session_id: None,
             token_usage: TokenUsage::default(),
             estimated_cost: 0.0,
@@ -94,6 +102,7 @@ impl StatusBar {
             scroll_active: false,
             context_state: None,
             queue_count: 0,
+            rate_limit: None,
             supports_plan_mode: false,
             spinner_frame: 0,
         }
@@ -107,6 +116,12 @@ impl StatusBar {
         self.agent_type = agent_type;
     }
 
+    pub fn set_agent_name(&mut self, name: &str) {
+        if self.agent_name != name {
+            self.agent_name = name.to_string();
+        }
+    }
+
     pub fn set_agent_mode(&mut self, mode: AgentMode) {
         self.agent_mode = mode;
     }
@@ -119,6 +134,10 @@ impl StatusBar {
         self.shell_mode = shell_mode;
     }
 
+    pub fn set_race_contenders(&mut self, count: usize) {
+        self.race_contenders = count;
+    }
+
     pub fn set_token_usage(&mut self, usage: TokenUsage) {
         self.token_usage = usage;
         self.update_cost();
@@ -132,6 +151,14 @@ impl StatusBar {
         self.queue_count = count;
     }
 
+    pub fn set_rate_limit(&mut self, rate_limit: Option<(&'static str, Option<u64>)>) {
+        self.rate_limit = rate_limit;
+    }
+
+    pub fn rate_limit(&self) -> Option<(&'static str, Option<u64>)> {
+        self.rate_limit
+    }
+
     pub fn set_supports_plan_mode(&mut self, supports: bool) {
         self.supports_plan_mode = supports;
     }
@@ -232,6 +259,11 @@ impl StatusBar {
                 "Shell",
                 Style::default().fg(accent_secondary()),
             ));
+        } else if self.race_contenders > 0 {
+            spans.push(Span::styled(
+                format!("Race · {} agents", self.race_contenders),
+                Style::default().fg(accent_secondary()),
+            ));
         } else {
             // Mode indicator - only when plan mode is supported
             if self.supports_plan_mode {
@@ -265,9 +297,18 @@ impl StatusBar {
                 ));
             }
 
+            if let Some((label, remaining)) = self.rate_limit {
+                let text = match remaining {
+                    Some(secs) => format!("{} · retry in {}", label, format_wait(secs)),
+                    None => label.to_string(),
+                };
+                spans.push(Span::raw("  "));
+                spans.push(Span::styled(text, Style::default().fg(accent_warning())));
+            }
+
             // Agent name - muted color
             spans.push(Span::styled(
-                format!(" {}", self.agent_type.display_name()),
+                format!(" {}", self.agent_name),
                 Style::default().fg(text_muted()),
             ));
         }
@@ -535,3 +576,14 @@ impl StatusBar {
         }
     }
 }
+
+/// Countdown such as `42s`, `3m05s` or `1h20m`
+fn format_wait(secs: u64) -> String {
+    if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
@@ -314,6 +314,11 @@ pub fn agent_opencode() -> Color {
     current_theme().agent_opencode
 }
 
+#[inline]
+pub fn agent_acp() -> Color {
+    current_theme().accent_primary
+}
+
 // PR State Colors
 #[inline]
 pub fn pr_open_bg() -> Color {
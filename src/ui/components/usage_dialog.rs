@@ -0,0 +1,184 @@
+//! Usage dashboard dialog showing recorded token usage and estimated spend.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use super::{accent_primary, text_muted, text_primary, text_secondary, DialogFrame};
+use crate::agent::events::ContextWindowState;
+use crate::data::{UsageGroupBy, UsageSummaryRow, UsageTotals};
+
+const DIALOG_WIDTH: u16 = 78;
+const MAX_VISIBLE_ROWS: usize = 14;
+
+/// Days of history shown in the dashboard
+pub const USAGE_DASHBOARD_DAYS: i64 = 30;
+
+/// State for the usage dashboard dialog
+#[derive(Debug, Clone, Default)]
+pub struct UsageDialogState {
+    visible: bool,
+    pub group_by: UsageGroupBy,
+    rows: Vec<UsageSummaryRow>,
+    error: Option<String>,
+}
+
+impl UsageDialogState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show the dialog with freshly loaded rows (or the error that prevented loading)
+    pub fn show(&mut self, result: Result<Vec<UsageSummaryRow>, String>) {
+        self.visible = true;
+        match result {
+            Ok(rows) => {
+                self.rows = rows;
+                self.error = None;
+            }
+            Err(error) => {
+                self.rows.clear();
+                self.error = Some(error);
+            }
+        }
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Advance to the next grouping; the caller reloads rows afterwards
+    pub fn cycle_group_by(&mut self) {
+        let all = UsageGroupBy::ALL;
+        let index = all.iter().position(|g| *g == self.group_by).unwrap_or(0);
+        self.group_by = all[(index + 1) % all.len()];
+    }
+
+    fn totals(&self) -> UsageTotals {
+        UsageTotals::sum(self.rows.iter().map(|row| &row.totals))
+    }
+}
+
+/// Usage dashboard widget
+pub struct UsageDialog<'a> {
+    state: &'a UsageDialogState,
+}
+
+impl<'a> UsageDialog<'a> {
+    pub fn new(state: &'a UsageDialogState) -> Self {
+        Self { state }
+    }
+
+    fn format_row(label: &str, totals: &UsageTotals, width: usize) -> String {
+        let label_width = width.saturating_sub(40).max(8);
+        let label: String = label.chars().take(label_width).collect();
+        let cost = format!("${:.2}", totals.cost_usd);
+        format!(
+            "{label:<label_width$} {:>6} {:>8} {:>8} {cost:>12}",
+            totals.turns,
+            ContextWindowState::format_tokens(totals.input_tokens),
+            ContextWindowState::format_tokens(totals.output_tokens),
+        )
+    }
+}
+
+impl Widget for UsageDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !self.state.visible {
+            return;
+        }
+
+        let visible_rows = self.state.rows.len().clamp(1, MAX_VISIBLE_ROWS) as u16;
+        // Padding, heading, header, rows, separator, total, bottom spacing
+        let dialog_height = visible_rows + 9;
+        let title = format!("Usage · last {} days", USAGE_DASHBOARD_DAYS);
+        let frame = DialogFrame::new(&title, DIALOG_WIDTH, dialog_height)
+            .instructions(vec![("Tab", "Group by"), ("Esc", "Close")]);
+        let inner = frame.render(area, buf);
+        if inner.height < 4 {
+            return;
+        }
+
+        let width = inner.width.saturating_sub(4) as usize;
+        let mut lines = vec![Line::from(vec![
+            Span::styled("Group by: ", Style::default().fg(text_muted())),
+            Span::styled(
+                self.state.group_by.display_name(),
+                Style::default()
+                    .fg(accent_primary())
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ])];
+        lines.push(Line::default());
+
+        if let Some(error) = &self.state.error {
+            lines.push(Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(text_secondary()),
+            )));
+        } else if self.state.rows.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No usage recorded yet",
+                Style::default().fg(text_muted()),
+            )));
+        } else {
+            let header = {
+                let label_width = width.saturating_sub(40).max(8);
+                format!(
+                    "{:<label_width$} {:>6} {:>8} {:>8} {:>12}",
+                    self.state.group_by.display_name(),
+                    "Turns",
+                    "Input",
+                    "Output",
+                    "Est. cost",
+                )
+            };
+            lines.push(Line::from(Span::styled(
+                header,
+                Style::default()
+                    .fg(text_muted())
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for row in self.state.rows.iter().take(MAX_VISIBLE_ROWS) {
+                lines.push(Line::from(Span::styled(
+                    Self::format_row(&row.label, &row.totals, width),
+                    Style::default().fg(text_primary()),
+                )));
+            }
+            if self.state.rows.len() > MAX_VISIBLE_ROWS {
+                lines.push(Line::from(Span::styled(
+                    format!("… {} more", self.state.rows.len() - MAX_VISIBLE_ROWS),
+                    Style::default().fg(text_muted()),
+                )));
+            }
+            lines.push(Line::from(Span::styled(
+                "─".repeat(width),
+                Style::default().fg(text_muted()),
+            )));
+            lines.push(Line::from(Span::styled(
+                Self::format_row("Total", &self.state.totals(), width),
+                Style::default()
+                    .fg(text_primary())
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+
+        Paragraph::new(lines).render(
+            Rect {
+                x: inner.x + 2,
+                y: inner.y + 1,
+                width: inner.width.saturating_sub(4),
+                height: inner.height.saturating_sub(2),
+            },
+            buf,
+        );
+    }
+}
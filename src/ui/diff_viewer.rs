@@ -0,0 +1,415 @@
+//! Diff review session for reviewing a workspace's changes in a tab
+//!
+//! This module provides the DiffViewerSession struct which holds the
+//! workspace diff against its base branch, laid out as side-by-side rows.
+
+use std::path::{Path, PathBuf};
+
+use ratatui::text::Line;
+use uuid::Uuid;
+
+use crate::git::{DiffHunk, DiffLineKind, FileDiff, WorkspaceDiff};
+use crate::ui::components::source_highlighter::highlight_source_lines;
+use crate::ui::file_viewer::FileKind;
+
+/// One side of a side-by-side row
+#[derive(Debug, Clone)]
+pub struct DiffSide {
+    pub line_number: usize,
+    pub kind: DiffLineKind,
+    pub content: Line<'static>,
+}
+
+/// A display row of the diff
+#[derive(Debug, Clone)]
+pub enum DiffRow {
+    /// File heading with its path and line counts
+    File { file: usize },
+    /// `@@` hunk heading
+    Hunk { file: usize, hunk: usize },
+    /// Old line on the left, new line on the right
+    Lines {
+        left: Option<DiffSide>,
+        right: Option<DiffSide>,
+    },
+    /// Informational line, e.g. for binary files
+    Note(String),
+}
+
+/// Location of a hunk in the row list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HunkRef {
+    file: usize,
+    hunk: usize,
+    row: usize,
+}
+
+/// State for a diff review tab
+#[derive(Debug)]
+pub struct DiffViewerSession {
+    /// Unique identifier for this session
+    pub id: Uuid,
+    /// Workspace whose changes are shown
+    pub workspace_id: Option<Uuid>,
+    /// Working directory the diff is taken in
+    pub working_dir: PathBuf,
+    /// Workspace name shown in the tab bar
+    name: String,
+    diff: Option<WorkspaceDiff>,
+    rows: Vec<DiffRow>,
+    hunks: Vec<HunkRef>,
+    selected_hunk: usize,
+    /// Current scroll offset (in rows)
+    pub scroll_offset: usize,
+    /// Whether a (re)load is in flight
+    pub loading: bool,
+    /// Last load error
+    pub error: Option<String>,
+}
+
+impl DiffViewerSession {
+    /// Create an empty session; the diff is loaded in the background
+    pub fn new(workspace_id: Option<Uuid>, working_dir: PathBuf, name: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            workspace_id,
+            working_dir,
+            name: name.into(),
+            diff: None,
+            rows: Vec::new(),
+            hunks: Vec::new(),
+            selected_hunk: 0,
+            scroll_offset: 0,
+            loading: true,
+            error: None,
+        }
+    }
+
+    /// Get display name for the tab
+    pub fn tab_name(&self) -> String {
+        format!("Diff: {}", self.name)
+    }
+
+    pub fn diff(&self) -> Option<&WorkspaceDiff> {
+        self.diff.as_ref()
+    }
+
+    pub fn rows(&self) -> &[DiffRow] {
+        &self.rows
+    }
+
+    pub fn total_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Replace the diff, keeping the selection near where it was
+    pub fn set_diff(&mut self, diff: WorkspaceDiff) {
+        self.loading = false;
+        self.error = None;
+        self.rows.clear();
+        self.hunks.clear();
+
+        for (file_index, file) in diff.files.iter().enumerate() {
+            self.rows.push(DiffRow::File { file: file_index });
+            if file.is_binary {
+                self.rows
+                    .push(DiffRow::Note("Binary file not shown".to_string()));
+            }
+            let path = Path::new(file.display_path());
+            for (hunk_index, hunk) in file.hunks.iter().enumerate() {
+                self.hunks.push(HunkRef {
+                    file: file_index,
+                    hunk: hunk_index,
+                    row: self.rows.len(),
+                });
+                self.rows.push(DiffRow::Hunk {
+                    file: file_index,
+                    hunk: hunk_index,
+                });
+                self.rows.extend(side_by_side_rows(path, hunk));
+            }
+        }
+
+        self.diff = Some(diff);
+        self.selected_hunk = self.selected_hunk.min(self.hunks.len().saturating_sub(1));
+        let max_scroll = self.rows.len().saturating_sub(1);
+        self.scroll_offset = self.scroll_offset.min(max_scroll);
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.loading = false;
+        self.error = Some(error);
+    }
+
+    /// Whether the given hunk is the one hunk actions apply to
+    pub fn is_selected(&self, file: usize, hunk: usize) -> bool {
+        self.hunks
+            .get(self.selected_hunk)
+            .is_some_and(|h| h.file == file && h.hunk == hunk)
+    }
+
+    /// The selected hunk and the file it belongs to
+    pub fn selected_hunk(&self) -> Option<(&FileDiff, &DiffHunk)> {
+        let hunk_ref = self.hunks.get(self.selected_hunk)?;
+        let file = self.diff.as_ref()?.files.get(hunk_ref.file)?;
+        Some((file, file.hunks.get(hunk_ref.hunk)?))
+    }
+
+    /// Path of the file the selected hunk belongs to
+    pub fn selected_file_path(&self) -> Option<&str> {
+        self.selected_hunk().map(|(file, _)| file.display_path())
+    }
+
+    /// Single-hunk patch for the selected hunk
+    pub fn selected_patch(&self) -> Option<String> {
+        let hunk_ref = self.hunks.get(self.selected_hunk)?;
+        self.diff
+            .as_ref()?
+            .files
+            .get(hunk_ref.file)?
+            .hunk_patch(hunk_ref.hunk)
+    }
+
+    /// Patch staging the selected hunk's unstaged changes, if it has any
+    pub fn selected_stage_patch(&self) -> Option<String> {
+        let hunk_ref = self.hunks.get(self.selected_hunk)?;
+        self.diff
+            .as_ref()?
+            .stage_patch(hunk_ref.file, hunk_ref.hunk)
+    }
+
+    /// Build the follow-up prompt for a review remark on the selected hunk
+    pub fn review_prompt(&self, comment: &str) -> Option<String> {
+        let (file, hunk) = self.selected_hunk()?;
+        Some(format!(
+            "Review note on `{}`:\n\n```diff\n{}\n```\n\n{}",
+            file.display_path(),
+            hunk.text(),
+            comment.trim()
+        ))
+    }
+
+    /// Select the next hunk and scroll it to the top
+    pub fn select_next_hunk(&mut self, visible_height: usize) {
+        if self.selected_hunk + 1 < self.hunks.len() {
+            self.selected_hunk += 1;
+        }
+        self.scroll_to_selected(visible_height);
+    }
+
+    /// Select the previous hunk and scroll it to the top
+    pub fn select_prev_hunk(&mut self, visible_height: usize) {
+        self.selected_hunk = self.selected_hunk.saturating_sub(1);
+        self.scroll_to_selected(visible_height);
+    }
+
+    fn scroll_to_selected(&mut self, visible_height: usize) {
+        if let Some(hunk_ref) = self.hunks.get(self.selected_hunk) {
+            // Keep the file heading in view when jumping to its first hunk
+            let row = if hunk_ref.hunk == 0 {
+                hunk_ref.row.saturating_sub(1)
+            } else {
+                hunk_ref.row
+            };
+            self.set_scroll_offset(row, visible_height);
+        }
+    }
+
+    /// Follow the scroll position: select the last hunk starting at or above
+    /// the top of the view
+    fn sync_selection_to_scroll(&mut self) {
+        let top = self.scroll_offset + 1;
+        self.selected_hunk = self.hunks.iter().rposition(|h| h.row <= top).unwrap_or(0);
+    }
+
+    /// Scroll up by N rows
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(lines);
+        self.sync_selection_to_scroll();
+    }
+
+    /// Scroll down by N rows, clamped to visible height
+    pub fn scroll_down_clamped(&mut self, lines: usize, visible_height: usize) {
+        self.set_scroll_offset(self.scroll_offset + lines, visible_height);
+        self.sync_selection_to_scroll();
+    }
+
+    /// Page up with exact visible height
+    pub fn page_up_exact(&mut self, visible_height: usize) {
+        self.scroll_up(visible_height.saturating_sub(2).max(1));
+    }
+
+    /// Page down with exact visible height
+    pub fn page_down_exact(&mut self, visible_height: usize) {
+        self.scroll_down_clamped(visible_height.saturating_sub(2).max(1), visible_height);
+    }
+
+    /// Scroll to the first row
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_offset = 0;
+        self.sync_selection_to_scroll();
+    }
+
+    /// Scroll to bottom with exact visible height
+    pub fn scroll_to_bottom_exact(&mut self, visible_height: usize) {
+        self.set_scroll_offset(usize::MAX, visible_height);
+        self.sync_selection_to_scroll();
+    }
+
+    /// Set scroll offset explicitly, clamped to the row range
+    pub fn set_scroll_offset(&mut self, offset: usize, visible_height: usize) {
+        let max_scroll = self.rows.len().saturating_sub(visible_height.max(1));
+        self.scroll_offset = offset.min(max_scroll);
+    }
+
+    /// Rows visible from the current scroll offset
+    pub fn visible_rows(&self, visible_height: usize) -> &[DiffRow] {
+        let start = self.scroll_offset.min(self.rows.len());
+        let end = (start + visible_height).min(self.rows.len());
+        &self.rows[start..end]
+    }
+}
+
+/// Lay out a hunk side by side: context lines on both sides, runs of removed
+/// lines paired with the added lines that follow them
+fn side_by_side_rows(path: &Path, hunk: &DiffHunk) -> Vec<DiffRow> {
+    let file_kind = FileKind::from_path(path);
+    let old_text: Vec<String> = hunk
+        .lines
+        .iter()
+        .filter(|line| line.kind != DiffLineKind::Added)
+        .map(|line| line.content.clone())
+        .collect();
+    let new_text: Vec<String> = hunk
+        .lines
+        .iter()
+        .filter(|line| line.kind != DiffLineKind::Removed)
+        .map(|line| line.content.clone())
+        .collect();
+    let mut old_highlighted = highlight_source_lines(file_kind, path, &old_text).into_iter();
+    let mut new_highlighted = highlight_source_lines(file_kind, path, &new_text).into_iter();
+
+    let mut rows = Vec::new();
+    let mut removed: Vec<DiffSide> = Vec::new();
+    let mut added: Vec<DiffSide> = Vec::new();
+    let flush =
+        |rows: &mut Vec<DiffRow>, removed: &mut Vec<DiffSide>, added: &mut Vec<DiffSide>| {
+            let count = removed.len().max(added.len());
+            let mut removed = removed.drain(..);
+            let mut added = added.drain(..);
+            for _ in 0..count {
+                rows.push(DiffRow::Lines {
+                    left: removed.next(),
+                    right: added.next(),
+                });
+            }
+        };
+
+    for line in &hunk.lines {
+        match line.kind {
+            DiffLineKind::Removed => removed.push(DiffSide {
+                line_number: line.old_line.unwrap_or_default(),
+                kind: line.kind,
+                content: old_highlighted.next().unwrap_or_default(),
+            }),
+            DiffLineKind::Added => added.push(DiffSide {
+                line_number: line.new_line.unwrap_or_default(),
+                kind: line.kind,
+                content: new_highlighted.next().unwrap_or_default(),
+            }),
+            DiffLineKind::Context => {
+                flush(&mut rows, &mut removed, &mut added);
+                rows.push(DiffRow::Lines {
+                    left: Some(DiffSide {
+                        line_number: line.old_line.unwrap_or_default(),
+                        kind: line.kind,
+                        content: old_highlighted.next().unwrap_or_default(),
+                    }),
+                    right: Some(DiffSide {
+                        line_number: line.new_line.unwrap_or_default(),
+                        kind: line.kind,
+                        content: new_highlighted.next().unwrap_or_default(),
+                    }),
+                });
+            }
+        }
+    }
+    flush(&mut rows, &mut removed, &mut added);
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,4 @@
+ fn main() {
+-    old();
++    new();
++    extra();
+ }
+diff --git a/README.md b/README.md
+--- a/README.md
++++ b/README.md
+@@ -5 +5 @@
+-Old title
++New title
+";
+
+    fn session() -> DiffViewerSession {
+        let mut session = DiffViewerSession::new(None, PathBuf::from("/tmp"), "ws");
+        session.set_diff(WorkspaceDiff::parse(
+            "origin/main".to_string(),
+            "abc".to_string(),
+            SAMPLE,
+        ));
+        session
+    }
+
+    #[test]
+    fn test_rows_pair_removed_with_added_lines() {
+        let session = session();
+        assert!(!session.loading);
+        // File, hunk, context, paired change, added-only, context, then README
+        assert_eq!(session.total_rows(), 9);
+        match &session.rows()[3] {
+            DiffRow::Lines {
+                left: Some(left),
+                right: Some(right),
+            } => {
+                assert_eq!(left.kind, DiffLineKind::Removed);
+                assert_eq!(right.kind, DiffLineKind::Added);
+                assert_eq!((left.line_number, right.line_number), (2, 2));
+            }
+            other => panic!("unexpected row {other:?}"),
+        }
+        assert!(matches!(
+            &session.rows()[4],
+            DiffRow::Lines {
+                left: None,
+                right: Some(_)
+            }
+        ));
+    }
+
+    #[test]
+    fn test_hunk_navigation_and_review_prompt() {
+        let mut session = session();
+        assert!(session.is_selected(0, 0));
+
+        session.select_next_hunk(3);
+        assert!(session.is_selected(1, 0));
+        let prompt = session.review_prompt("  Keep the old title  ").unwrap();
+        assert!(prompt.starts_with("Review note on `README.md`:"));
+        assert!(prompt.contains("-Old title\n+New title"));
+        assert!(prompt.ends_with("Keep the old title"));
+
+        session.scroll_to_top();
+        assert!(session.is_selected(0, 0));
+        assert!(session.selected_patch().unwrap().contains("+    extra();"));
+    }
+}
@@ -1,7 +1,12 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use crate::agent::{AgentStartConfig, AgentType};
-use crate::session::ExternalSession;
+use crate::agent::{AgentStartConfig, AgentType, EventHook};
+use crate::config::{CommandStep, UserCommand};
+use crate::data::HistorySearchHit;
+use crate::git::{HunkAction, WorkspaceHooks};
+use crate::session::{ExternalSession, PromptTemplate, TranscriptFormat};
 use uuid::Uuid;
 
 /// Side effects that should be executed outside the reducer.
@@ -19,14 +24,68 @@ pub enum Effect {
     OpenPrInBrowser {
         working_dir: PathBuf,
     },
+    /// Load the workspace diff for a diff review tab
+    LoadWorkspaceDiff {
+        tab_id: Uuid,
+        working_dir: PathBuf,
+    },
+    /// Stage or revert a single hunk from a diff review tab
+    ApplyDiffHunk {
+        tab_id: Uuid,
+        working_dir: PathBuf,
+        patch: String,
+        action: HunkAction,
+    },
+    /// Put a workspace's files back to a checkpoint snapshot
+    RestoreCheckpoint {
+        workspace_id: Uuid,
+        working_dir: PathBuf,
+        commit: String,
+        turn: i64,
+    },
     DumpDebugState,
+    ExportTranscript(TranscriptFormat),
     CreateWorkspace {
         repo_id: Uuid,
     },
+    /// Create a fresh workspace for a scheduled prompt run
+    CreateScheduledWorkspace {
+        schedule_id: Uuid,
+        repo_id: Uuid,
+    },
+    /// Copy files and run the setup script for a new workspace
+    RunWorkspaceSetup {
+        workspace_id: Uuid,
+        hooks: WorkspaceHooks,
+        root_path: PathBuf,
+        workspace_path: PathBuf,
+        workspace_name: String,
+        branch: String,
+    },
+    /// Run an agent event hook with the event's JSON on stdin
+    RunEventHook {
+        session_id: Uuid,
+        hook: EventHook,
+        event_json: String,
+        working_dir: PathBuf,
+    },
+    /// Run a goal loop's verification command
+    RunGoalCheck {
+        session_id: Uuid,
+        command: String,
+        working_dir: PathBuf,
+        timeout: Duration,
+    },
     ForkWorkspace {
         parent_workspace_id: Uuid,
         base_branch: String,
     },
+    /// Create one workspace per race contender from the source workspace's commit
+    CreateRaceWorkspaces {
+        race_id: Uuid,
+        source_workspace_id: Uuid,
+        count: usize,
+    },
     ArchiveWorkspacePreflight {
         workspace_id: Uuid,
     },
@@ -42,6 +101,30 @@ pub enum Effect {
     DiscoverSessions,
     /// Import an external session
     ImportSession(ExternalSession),
+    /// Bring the cross-session history index up to date
+    RefreshHistoryIndex,
+    /// Open the session of a history search hit at the matching message
+    OpenHistoryHit(HistorySearchHit),
+    /// List the files and symbols `@` can mention in a working directory
+    LoadMentionCandidates {
+        working_dir: PathBuf,
+    },
+    /// Fill in the session variables of a prompt template, on top of `values`
+    ResolvePromptTemplate {
+        session_id: Uuid,
+        template: PromptTemplate,
+        working_dir: PathBuf,
+        values: HashMap<String, String>,
+    },
+    /// Run a user command's script, then the steps after it
+    RunUserCommand {
+        session_id: Option<Uuid>,
+        command: UserCommand,
+        working_dir: PathBuf,
+        /// Chat message showing the output, for `output = "show"`
+        message_index: Option<usize>,
+        then: Vec<CommandStep>,
+    },
     /// Generate session title and branch name from first message
     GenerateTitleAndBranch {
         /// Stable session ID for correlation (avoids stale tab_index after close/reorder)
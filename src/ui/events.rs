@@ -1,7 +1,9 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-use crate::agent::{AgentEvent, AgentInput, AgentType};
-use crate::git::PrPreflightResult;
+use crate::agent::event_hooks::EventHookRun;
+use crate::agent::{AgentEvent, AgentInput, AgentType, EventHook};
+use crate::git::{HunkAction, PrPreflightResult, WorkspaceDiff};
 use crate::ui::git_tracker::GitTrackerUpdate;
 use tokio::sync::mpsc;
 use uuid::Uuid;
@@ -13,10 +15,15 @@ pub enum AppEvent {
     Input(crossterm::event::Event),
 
     /// Agent event from a session (identified by stable session ID)
-    Agent { session_id: Uuid, event: AgentEvent },
+    Agent {
+        session_id: Uuid,
+        event: AgentEvent,
+    },
 
     /// Agent event stream ended (process exited)
-    AgentStreamEnded { session_id: Uuid },
+    AgentStreamEnded {
+        session_id: Uuid,
+    },
 
     /// Agent subprocess started with given PID
     AgentStarted {
@@ -25,7 +32,10 @@ pub enum AppEvent {
         input_tx: Option<mpsc::Sender<AgentInput>>,
     },
     /// Agent failed to start for a specific session
-    AgentStartFailed { session_id: Uuid, error: String },
+    AgentStartFailed {
+        session_id: Uuid,
+        error: String,
+    },
     /// Agent termination result (used for async termination feedback)
     AgentTerminationResult {
         session_id: Option<Uuid>,
@@ -33,9 +43,22 @@ pub enum AppEvent {
         context: String,
         success: bool,
     },
+    /// Backoff elapsed for a crashed agent; resume its session
+    AgentRestartDue {
+        session_id: Uuid,
+    },
+    /// Rate-limit wait elapsed; resend the session's last prompt
+    RateLimitRetryDue {
+        session_id: Uuid,
+    },
+    /// Time to check for due scheduled prompts
+    ScheduleTick,
 
     /// User submitted a prompt
-    PromptSubmit { tab_index: usize, prompt: String },
+    PromptSubmit {
+        tab_index: usize,
+        prompt: String,
+    },
 
     /// Request to create a new tab
     NewTab(AgentType),
@@ -81,21 +104,78 @@ pub enum AppEvent {
     },
 
     /// Open PR in browser completed
-    OpenPrCompleted { result: Result<(), String> },
+    OpenPrCompleted {
+        result: Result<(), String>,
+    },
+
+    /// Workspace diff for a diff review tab loaded
+    WorkspaceDiffLoaded {
+        tab_id: Uuid,
+        result: Result<WorkspaceDiff, String>,
+    },
+    /// Hunk stage/revert from a diff review tab completed
+    DiffHunkApplied {
+        tab_id: Uuid,
+        action: HunkAction,
+        result: Result<(), String>,
+    },
+    /// Checkpoint restore finished
+    CheckpointRestored {
+        working_dir: PathBuf,
+        turn: i64,
+        result: Result<(), String>,
+    },
 
     /// Debug export completed
-    DebugDumped { result: Result<String, String> },
+    DebugDumped {
+        result: Result<String, String>,
+    },
+    TranscriptExported {
+        result: Result<String, String>,
+    },
 
     /// Workspace creation completed
     WorkspaceCreated {
         repo_id: Uuid,
         result: Result<WorkspaceCreated, String>,
     },
+    /// Workspace creation for a scheduled prompt run completed
+    ScheduledWorkspaceCreated {
+        schedule_id: Uuid,
+        result: Result<WorkspaceCreated, String>,
+    },
+    /// A line of output from a workspace's setup hook
+    WorkspaceSetupOutput {
+        workspace_id: Uuid,
+        line: String,
+    },
+    /// A workspace's setup hook finished
+    WorkspaceSetupFinished {
+        workspace_id: Uuid,
+        result: Result<(), String>,
+    },
+    /// An agent event hook finished
+    EventHookFinished {
+        session_id: Uuid,
+        hook: EventHook,
+        run: EventHookRun,
+    },
+    /// A goal loop's verification command finished
+    GoalCheckFinished {
+        session_id: Uuid,
+        run: EventHookRun,
+    },
     /// Fork workspace creation completed
     ForkWorkspaceCreated {
         parent_workspace_id: Uuid,
         result: Result<ForkWorkspaceCreated, String>,
     },
+    /// Race workspace creation completed
+    RaceWorkspacesCreated {
+        race_id: Uuid,
+        source_workspace_id: Uuid,
+        result: Result<RaceWorkspacesCreated, String>,
+    },
 
     /// Workspace archive completed
     WorkspaceArchived {
@@ -134,7 +214,9 @@ pub enum AppEvent {
     },
 
     /// Project removal completed
-    ProjectRemoved { result: RemoveProjectResult },
+    ProjectRemoved {
+        result: RemoveProjectResult,
+    },
 
     /// Cached sessions loaded (fast path from disk cache)
     SessionsCacheLoaded {
@@ -147,11 +229,41 @@ pub enum AppEvent {
     },
 
     /// Session removed (file no longer exists)
-    SessionRemoved { file_path: PathBuf },
+    SessionRemoved {
+        file_path: PathBuf,
+    },
 
     /// Background session discovery complete
     SessionDiscoveryComplete,
 
+    /// History index refresh finished
+    HistoryIndexRefreshed {
+        result: Result<crate::session::HistoryIndexStats, String>,
+    },
+
+    /// Files and symbols for the `@` mention picker were listed
+    MentionCandidatesLoaded {
+        working_dir: PathBuf,
+        result: Result<Vec<crate::session::MentionCandidate>, String>,
+    },
+
+    /// Session values for a prompt template's variables were worked out
+    PromptTemplateResolved {
+        session_id: Uuid,
+        template: crate::session::PromptTemplate,
+        values: HashMap<String, String>,
+    },
+
+    /// A user command's script finished
+    UserCommandFinished {
+        session_id: Option<Uuid>,
+        command: crate::config::UserCommand,
+        message_index: Option<usize>,
+        run: EventHookRun,
+        /// Steps still to run if the script succeeded
+        then: Vec<crate::config::CommandStep>,
+    },
+
     /// Git tracker update (PR status, git stats, branch changes)
     GitTracker(GitTrackerUpdate),
 
@@ -209,6 +321,15 @@ pub struct ForkWorkspaceCreated {
     pub workspace_id: Uuid,
 }
 
+#[derive(Debug, Clone)]
+pub struct RaceWorkspacesCreated {
+    pub repo_id: Uuid,
+    /// Commit all race workspaces were branched from
+    pub base_commit: String,
+    /// One workspace per contender, in contender order
+    pub workspace_ids: Vec<Uuid>,
+}
+
 #[derive(Debug, Clone)]
 pub struct WorkspaceArchived {
     pub workspace_id: Uuid,
@@ -277,12 +398,24 @@ pub enum InputMode {
     SelectingModel,
     /// Selecting reasoning effort for current session
     SelectingReasoning,
+    /// Selecting Codex sandbox and approval modes for current session
+    SelectingCodexPermissions,
     /// Selecting theme
     SelectingTheme,
     /// Selecting enabled providers
     SelectingProviders,
+    /// Picking agent/model pairs for a race
+    SelectingRaceContenders,
     /// Setting base projects directory
     SettingBaseDir,
+    /// Typing a review remark for a diff hunk
+    CommentingHunk,
+    /// Typing the verification command for a goal loop
+    SettingGoal,
+    /// Filling in the variables of a prompt template
+    FillingPromptVariables,
+    /// Typing a chat transcript search
+    SearchingChat,
     /// Picking a project from the list
     PickingProject,
     /// Showing a confirmation dialog
@@ -291,16 +424,28 @@ pub enum InputMode {
     RemovingProject,
     /// Showing an error dialog
     ShowingError,
+    /// Showing the usage dashboard
+    ShowingUsage,
+    /// Showing the race comparison
+    ShowingRaceResults,
+    /// Showing the checkpoint timeline
+    ShowingCheckpoints,
+    /// Showing scheduled prompts
+    ShowingSchedules,
     /// Command mode (typing :command)
     Command,
     /// Showing help dialog
     ShowingHelp,
     /// Importing a session from external agent
     ImportingSession,
+    /// Searching the history of every session
+    SearchingHistory,
     /// Command palette is open
     CommandPalette,
     /// Slash command menu is open
     SlashMenu,
+    /// Picking a file or symbol to mention with `@`
+    PickingMention,
     /// Missing tool dialog is open
     MissingTool,
     /// Editing queued messages inline
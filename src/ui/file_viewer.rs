@@ -21,6 +21,25 @@ pub enum FileKind {
     PlainText,
 }
 
+impl FileKind {
+    /// Detect the kind from the file extension
+    pub fn from_path(file_path: &Path) -> Self {
+        let ext = file_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+
+        match ext.as_deref() {
+            Some("md" | "markdown") => FileKind::Markdown,
+            Some("json" | "jsonl") => FileKind::Json,
+            Some("rs") => FileKind::Rust,
+            Some("toml") => FileKind::Toml,
+            Some("yaml" | "yml") => FileKind::Yaml,
+            _ => FileKind::PlainText,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FileViewMode {
     Raw,
@@ -95,19 +114,7 @@ impl FileViewerSession {
     }
 
     fn detect_file_kind(file_path: &Path) -> FileKind {
-        let ext = file_path
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .map(|ext| ext.to_ascii_lowercase());
-
-        match ext.as_deref() {
-            Some("md" | "markdown") => FileKind::Markdown,
-            Some("json" | "jsonl") => FileKind::Json,
-            Some("rs") => FileKind::Rust,
-            Some("toml") => FileKind::Toml,
-            Some("yaml" | "yml") => FileKind::Yaml,
-            _ => FileKind::PlainText,
-        }
+        FileKind::from_path(file_path)
     }
 
     fn active_total_lines(&self) -> usize {
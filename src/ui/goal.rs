@@ -0,0 +1,200 @@
+//! Goal loop: keep prompting an agent until a verification command passes.
+//!
+//! The user's prompt is the goal. After every turn the command (e.g.
+//! `cargo test`) runs in the workspace; while it fails, the end of its output
+//! goes back to the agent as the next prompt, up to an iteration or spend
+//! limit.
+
+use crate::agent::event_hooks::EventHookRun;
+use crate::config::GoalConfig;
+
+/// Output lines sent back to the agent after a failed check
+const FEEDBACK_LINES: usize = 60;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GoalStatus {
+    /// The agent is working on the goal
+    Working,
+    /// The verification command is running
+    Verifying,
+    Passed,
+    /// A limit was reached; holds the reason
+    GaveUp(String),
+    /// Stopped by the user
+    Stopped,
+}
+
+impl GoalStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GoalStatus::Working => "working",
+            GoalStatus::Verifying => "verifying",
+            GoalStatus::Passed => "passed",
+            GoalStatus::GaveUp(_) => "gave up",
+            GoalStatus::Stopped => "stopped",
+        }
+    }
+}
+
+/// One goal loop on a session
+#[derive(Debug, Clone)]
+pub struct GoalLoop {
+    pub goal: String,
+    pub command: String,
+    /// Agent turns so far, counting the one working now
+    pub iteration: u32,
+    pub max_iterations: u32,
+    pub max_cost_usd: Option<f64>,
+    pub spent_usd: f64,
+    pub status: GoalStatus,
+}
+
+impl GoalLoop {
+    pub fn new(goal: String, command: String, config: &GoalConfig) -> Self {
+        Self {
+            goal,
+            command,
+            iteration: 1,
+            max_iterations: config.max_iterations.max(1),
+            max_cost_usd: config.max_cost_usd,
+            spent_usd: 0.0,
+            status: GoalStatus::Working,
+        }
+    }
+
+    /// Still working or verifying
+    pub fn is_active(&self) -> bool {
+        matches!(self.status, GoalStatus::Working | GoalStatus::Verifying)
+    }
+
+    /// Record a finished turn; returns true when the check should run now
+    pub fn finish_turn(&mut self, cost_usd: Option<f64>) -> bool {
+        if self.status != GoalStatus::Working {
+            return false;
+        }
+        self.spent_usd += cost_usd.unwrap_or(0.0);
+        self.status = GoalStatus::Verifying;
+        true
+    }
+
+    /// Record the verification result. Returns the next prompt while the
+    /// loop goes on; the status says why it ended otherwise.
+    pub fn record_check(&mut self, run: &EventHookRun) -> Option<String> {
+        if self.status != GoalStatus::Verifying {
+            return None;
+        }
+        if run.success {
+            self.status = GoalStatus::Passed;
+            return None;
+        }
+        if self.iteration >= self.max_iterations {
+            self.status = GoalStatus::GaveUp(format!("{} attempts", self.max_iterations));
+            return None;
+        }
+        if let Some(max_cost) = self.max_cost_usd {
+            if self.spent_usd >= max_cost {
+                self.status =
+                    GoalStatus::GaveUp(format!("${:.2} of ${:.2} spent", self.spent_usd, max_cost));
+                return None;
+            }
+        }
+        self.iteration += 1;
+        self.status = GoalStatus::Working;
+        Some(self.feedback_prompt(run))
+    }
+
+    pub fn stop(&mut self) {
+        if self.is_active() {
+            self.status = GoalStatus::Stopped;
+        }
+    }
+
+    /// Header text, e.g. "Goal 2/5 · cargo test · verifying"
+    pub fn progress_label(&self) -> String {
+        format!(
+            "Goal {}/{} · {} · {}",
+            self.iteration,
+            self.max_iterations,
+            self.command,
+            self.status.label()
+        )
+    }
+
+    fn feedback_prompt(&self, run: &EventHookRun) -> String {
+        let failure = run.failure.as_deref().unwrap_or("failed");
+        let lines: Vec<&str> = run.output.lines().collect();
+        let tail = lines[lines.len().saturating_sub(FEEDBACK_LINES)..].join("\n");
+        let mut prompt = format!(
+            "`{}` still fails ({}). Keep working until it passes.\n\nGoal: {}",
+            self.command, failure, self.goal
+        );
+        if !tail.is_empty() {
+            prompt.push_str(&format!("\n\nEnd of its output:\n```\n{}\n```", tail));
+        }
+        prompt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failed_run(output: &str) -> EventHookRun {
+        EventHookRun {
+            success: false,
+            output: output.to_string(),
+            failure: Some("exit status 101".to_string()),
+        }
+    }
+
+    fn goal(max_iterations: u32, max_cost_usd: Option<f64>) -> GoalLoop {
+        let config = GoalConfig {
+            max_iterations,
+            max_cost_usd,
+            ..GoalConfig::default()
+        };
+        GoalLoop::new(
+            "make tests pass".to_string(),
+            "cargo test".to_string(),
+            &config,
+        )
+    }
+
+    #[test]
+    fn test_failed_check_feeds_back_until_limit() {
+        let mut goal = goal(2, None);
+        assert!(goal.finish_turn(Some(0.1)));
+        assert_eq!(goal.progress_label(), "Goal 1/2 · cargo test · verifying");
+
+        let output: String = (1..=100).map(|i| format!("line {}\n", i)).collect();
+        let prompt = goal.record_check(&failed_run(&output)).unwrap();
+        assert!(prompt.contains("exit status 101"));
+        assert!(prompt.contains("Goal: make tests pass"));
+        assert!(prompt.contains("line 100"));
+        assert!(!prompt.contains("line 40\n"));
+        assert_eq!(goal.status, GoalStatus::Working);
+
+        assert!(goal.finish_turn(None));
+        assert_eq!(goal.record_check(&failed_run("boom")), None);
+        assert_eq!(goal.status, GoalStatus::GaveUp("2 attempts".to_string()));
+        assert!(!goal.is_active());
+    }
+
+    #[test]
+    fn test_check_pass_and_budget_end_the_loop() {
+        let mut goal = goal(5, Some(1.0));
+        goal.finish_turn(Some(0.4));
+        let passed = EventHookRun {
+            success: true,
+            output: String::new(),
+            failure: None,
+        };
+        assert_eq!(goal.record_check(&passed), None);
+        assert_eq!(goal.status, GoalStatus::Passed);
+
+        let mut goal = self::goal(5, Some(1.0));
+        goal.finish_turn(Some(1.2));
+        assert_eq!(goal.record_check(&failed_run("boom")), None);
+        assert!(matches!(goal.status, GoalStatus::GaveUp(_)));
+    }
+}
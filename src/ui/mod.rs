@@ -6,10 +6,13 @@ pub mod app_state;
 pub mod capabilities;
 pub mod clipboard_paste;
 pub mod components;
+pub mod diff_viewer;
 pub mod effect;
 pub mod events;
 pub mod file_viewer;
 pub mod git_tracker;
+pub mod goal;
+pub mod race;
 pub mod session;
 pub mod tab;
 pub mod tab_manager;
@@ -19,6 +22,7 @@ pub use action::Action;
 pub use app::App;
 pub use app_state::{AppState, PerformanceMetrics};
 pub use capabilities::AgentCapabilities;
+pub use diff_viewer::DiffViewerSession;
 pub use effect::Effect;
 pub use events::{AppEvent, InputMode};
 pub use file_viewer::FileViewerSession;
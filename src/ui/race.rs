@@ -0,0 +1,224 @@
+//! Race mode: one prompt run by several agent/model pairs in sibling
+//! workspaces created from the same base commit.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use crate::agent::{AgentType, ModelRegistry};
+
+/// One agent/model pairing taking part in a race
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaceContender {
+    pub agent_type: AgentType,
+    pub model: String,
+}
+
+impl RaceContender {
+    pub fn new(agent_type: AgentType, model: impl Into<String>) -> Self {
+        Self {
+            agent_type,
+            model: model.into(),
+        }
+    }
+
+    /// Human-readable label, e.g. "GPT-5.3 Codex · Codex"
+    pub fn label(&self) -> String {
+        let model = ModelRegistry::find_model(self.agent_type, &self.model)
+            .map(|m| m.display_name)
+            .unwrap_or_else(|| self.model.clone());
+        format!("{} · {}", model, self.agent_type.display_name())
+    }
+}
+
+/// Formats as `agent:model`, the same form [`FromStr`] accepts
+impl fmt::Display for RaceContender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.agent_type.as_str(), self.model)
+    }
+}
+
+/// Parses `agent:model`, or a bare `agent` for its default model
+impl FromStr for RaceContender {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (agent, model) = match s.trim().split_once(':') {
+            Some((agent, model)) => (agent.trim(), Some(model.trim())),
+            None => (s.trim(), None),
+        };
+        let agent_type = AgentType::preferred_order()
+            .into_iter()
+            .find(|a| a.as_str().eq_ignore_ascii_case(agent))
+            .ok_or_else(|| format!("Unknown agent '{}'", agent))?;
+
+        let model = match model.filter(|m| !m.is_empty()) {
+            Some(model) => ModelRegistry::find_model(agent_type, model)
+                .map(|m| m.id)
+                .ok_or_else(|| {
+                    format!(
+                        "Unknown model '{}' for {}",
+                        model,
+                        agent_type.display_name()
+                    )
+                })?,
+            None => ModelRegistry::default_model(agent_type),
+        };
+        Ok(Self::new(agent_type, model))
+    }
+}
+
+/// Progress of a single contender
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RaceOutcome {
+    /// Waiting for its workspace to be created
+    Pending,
+    Running,
+    Finished,
+    Failed,
+}
+
+impl RaceOutcome {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            RaceOutcome::Pending => "Starting",
+            RaceOutcome::Running => "Running",
+            RaceOutcome::Finished => "Finished",
+            RaceOutcome::Failed => "Failed",
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        matches!(self, RaceOutcome::Finished | RaceOutcome::Failed)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RaceEntry {
+    pub contender: RaceContender,
+    pub workspace_id: Option<Uuid>,
+    pub session_id: Option<Uuid>,
+    pub outcome: RaceOutcome,
+    started_at: Option<Instant>,
+    duration: Option<Duration>,
+}
+
+impl RaceEntry {
+    /// Wall-clock time so far, or the final duration once the turn ended
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.duration
+            .or_else(|| self.started_at.map(|started| started.elapsed()))
+    }
+}
+
+/// A race in progress (or finished but not yet decided)
+#[derive(Debug, Clone)]
+pub struct Race {
+    pub id: Uuid,
+    pub prompt: String,
+    pub source_workspace_id: Uuid,
+    /// Commit every contender's workspace was branched from
+    pub base_commit: Option<String>,
+    pub entries: Vec<RaceEntry>,
+}
+
+impl Race {
+    pub fn new(prompt: String, source_workspace_id: Uuid, contenders: Vec<RaceContender>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            prompt,
+            source_workspace_id,
+            base_commit: None,
+            entries: contenders
+                .into_iter()
+                .map(|contender| RaceEntry {
+                    contender,
+                    workspace_id: None,
+                    session_id: None,
+                    outcome: RaceOutcome::Pending,
+                    started_at: None,
+                    duration: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Record that a contender's agent has been given the prompt
+    pub fn start_entry(&mut self, index: usize, workspace_id: Uuid, session_id: Uuid) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.workspace_id = Some(workspace_id);
+            entry.session_id = Some(session_id);
+            entry.outcome = RaceOutcome::Running;
+            entry.started_at = Some(Instant::now());
+        }
+    }
+
+    /// Mark a contender that never got going
+    pub fn fail_entry(&mut self, index: usize) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.outcome = RaceOutcome::Failed;
+        }
+    }
+
+    /// Record the end of a contender's turn. Returns true when this was the
+    /// last contender still running.
+    pub fn finish_session(&mut self, session_id: Uuid, outcome: RaceOutcome) -> bool {
+        let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|e| e.session_id == Some(session_id) && e.outcome == RaceOutcome::Running)
+        else {
+            return false;
+        };
+        entry.outcome = outcome;
+        entry.duration = entry.started_at.map(|started| started.elapsed());
+        self.is_complete()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.entries.iter().all(|e| e.outcome.is_done())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contender_parses_agent_and_model() {
+        let contender: RaceContender = "claude:sonnet".parse().unwrap();
+        assert_eq!(contender.agent_type, AgentType::Claude);
+        assert_eq!(contender.to_string(), "claude:sonnet");
+
+        let default: RaceContender = "Codex".parse().unwrap();
+        assert_eq!(default.agent_type, AgentType::Codex);
+        assert_eq!(
+            default.model,
+            ModelRegistry::default_model(AgentType::Codex)
+        );
+
+        assert!("cursor:fast".parse::<RaceContender>().is_err());
+        assert!("claude:not-a-model".parse::<RaceContender>().is_err());
+    }
+
+    #[test]
+    fn test_race_completes_when_every_entry_is_done() {
+        let contenders = vec![
+            RaceContender::new(AgentType::Claude, "opus"),
+            RaceContender::new(AgentType::Codex, "gpt-5.3-codex"),
+        ];
+        let mut race = Race::new("fix it".to_string(), Uuid::new_v4(), contenders);
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        race.start_entry(0, Uuid::new_v4(), first);
+        race.start_entry(1, Uuid::new_v4(), second);
+
+        assert!(!race.finish_session(first, RaceOutcome::Finished));
+        // A second turn end for the same session is ignored
+        assert!(!race.finish_session(first, RaceOutcome::Failed));
+        assert!(race.finish_session(second, RaceOutcome::Failed));
+        assert_eq!(race.entries[0].outcome, RaceOutcome::Finished);
+        assert!(race.entries[0].elapsed().is_some());
+    }
+}
@@ -1,22 +1,30 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
+use std::time::Instant;
 
 use serde_json::Value;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use crate::agent::{
-    events::{ContextCompactionEvent, ContextWarningLevel, ContextWindowState, TokenUsageEvent},
+    events::{
+        ContextCompactionEvent, ContextWarningLevel, ContextWindowState, ControlRequestEvent,
+        TokenUsageEvent,
+    },
     models::ModelRegistry,
-    AgentHandle, AgentInput, AgentMode, AgentType, ReasoningEffort, SessionId, TokenUsage,
+    AgentHandle, AgentInput, AgentMode, AgentType, CodexApprovalMode, CodexSandboxMode, EventHooks,
+    ProviderErrorKind, ReasoningEffort, SessionId, TokenUsage,
 };
-use crate::data::{QueuedMessage, QueuedMessageMode};
+use crate::core::services::InFlightUsage;
+use crate::data::{QueuedImageAttachment, QueuedMessage, QueuedMessageMode};
 use crate::git::PrManager;
 use crate::ui::capabilities::AgentCapabilities;
 use crate::ui::components::{
     ChatView, EventDirection, InlinePromptState, InputBox, ProcessingState, RawEventsView,
     StatusBar, ThinkingIndicator, TurnSummary,
 };
+use crate::ui::goal::GoalLoop;
+use crate::ui::race::RaceContender;
 
 /// Represents a single agent session (one tab)
 pub struct AgentSession {
@@ -24,6 +32,8 @@ pub struct AgentSession {
     pub id: Uuid,
     /// Type of agent (Claude, Codex, or Gemini)
     pub agent_type: AgentType,
+    /// Configured name of the ACP agent, shown when the session runs it
+    acp_name: Option<String>,
     /// Agent mode (Build vs Plan)
     pub agent_mode: AgentMode,
     /// Last agent mode that received a mode prompt (Plan/Build switch)
@@ -34,6 +44,10 @@ pub struct AgentSession {
     pub model_invalid: bool,
     /// Optional reasoning effort override for this session
     pub reasoning_effort: Option<ReasoningEffort>,
+    /// Codex approval mode override (None = repository default)
+    pub codex_approval: Option<CodexApprovalMode>,
+    /// Codex sandbox mode override (None = repository default)
+    pub codex_sandbox: Option<CodexSandboxMode>,
     /// Associated workspace ID (for project context)
     pub workspace_id: Option<Uuid>,
     /// Working directory for the agent (workspace path)
@@ -110,6 +124,57 @@ pub struct AgentSession {
     pub pending_tool_permissions: HashMap<String, String>,
     /// Pending control responses waiting for a permission request
     pub pending_tool_permission_responses: HashMap<String, serde_json::Value>,
+    /// Claude tool calls waiting on an approval card; the front one is shown
+    pub tool_approval_queue: VecDeque<ControlRequestEvent>,
+    /// Tools the user allowed for the rest of this session
+    pub session_allowed_tools: HashSet<String>,
+    /// Running-turn usage, for interrupting at a hard budget limit
+    pub in_flight_usage: InFlightUsage,
+    /// Whether the soft budget warning was already shown
+    pub budget_warning_shown: bool,
+    /// Contenders picked with /race; the next submitted prompt starts the race
+    pub pending_race: Option<Vec<RaceContender>>,
+    /// Checkpoint taken when the running turn started
+    pub current_checkpoint_id: Option<Uuid>,
+    /// Automatic restarts after crashes in the current turn
+    pub crash_restarts: u32,
+    /// A crashed agent is waiting out its backoff before resuming
+    pub pending_restart: bool,
+    /// Text of the last prompt the user sent, resent after a rate limit
+    pub last_prompt: Option<String>,
+    /// Images attached to `last_prompt`, resent with it
+    pub last_prompt_images: Vec<QueuedImageAttachment>,
+    /// Automatic resends of the last prompt after rate limits
+    pub rate_limit_retries: u32,
+    /// Rate limit or overload the session is waiting out
+    pub rate_limit_wait: Option<RateLimitWait>,
+    /// Event hooks for the running agent, resolved when it starts
+    pub event_hooks: EventHooks,
+    /// Follow-up prompts event hooks sent since the user last typed one
+    pub hook_feedback_rounds: u32,
+    /// Verification command picked with /goal; the next submitted prompt
+    /// becomes the goal
+    pub pending_goal: Option<String>,
+    /// Current or last goal loop
+    pub goal: Option<GoalLoop>,
+}
+
+/// A provider limit that stopped the last turn
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitWait {
+    pub kind: ProviderErrorKind,
+    /// When the last prompt is sent again (None = no automatic retry)
+    pub retry_at: Option<Instant>,
+}
+
+impl RateLimitWait {
+    /// Status bar label and whole seconds left until the retry
+    pub fn countdown(&self, now: Instant) -> (&'static str, Option<u64>) {
+        let remaining = self
+            .retry_at
+            .map(|retry_at| retry_at.saturating_duration_since(now).as_secs_f32().ceil() as u64);
+        (self.kind.label(), remaining)
+    }
 }
 
 /// Context warning notification
@@ -125,11 +190,14 @@ impl AgentSession {
         let mut session = Self {
             id: Uuid::new_v4(),
             agent_type,
+            acp_name: None,
             agent_mode: AgentMode::default(),
             last_mode_prompt: None,
             model: None,
             model_invalid: false,
             reasoning_effort: None,
+            codex_approval: None,
+            codex_sandbox: None,
             workspace_id: None,
             working_dir: None,
             project_name: None,
@@ -168,6 +236,22 @@ impl AgentSession {
             inline_prompt: None,
             pending_tool_permissions: HashMap::new(),
             pending_tool_permission_responses: HashMap::new(),
+            tool_approval_queue: VecDeque::new(),
+            session_allowed_tools: HashSet::new(),
+            in_flight_usage: InFlightUsage::default(),
+            budget_warning_shown: false,
+            pending_race: None,
+            current_checkpoint_id: None,
+            crash_restarts: 0,
+            pending_restart: false,
+            last_prompt: None,
+            last_prompt_images: Vec::new(),
+            rate_limit_retries: 0,
+            rate_limit_wait: None,
+            event_hooks: EventHooks::default(),
+            hook_feedback_rounds: 0,
+            pending_goal: None,
+            goal: None,
         };
         session
             .chat_view
@@ -213,16 +297,24 @@ impl AgentSession {
     /// Update status bar with current state
     pub fn update_status(&mut self) {
         self.status_bar.set_agent_type(self.agent_type);
+        self.status_bar
+            .set_agent_name(agent_name(self.agent_type, self.acp_name.as_deref()));
         self.status_bar.set_agent_mode(self.agent_mode);
         self.status_bar.set_model(self.model.clone());
         self.status_bar
             .set_shell_mode(self.input_box.is_shell_mode());
+        self.status_bar
+            .set_race_contenders(self.pending_race.as_ref().map_or(0, Vec::len));
         self.status_bar
             .set_session_id(self.agent_session_id.clone());
         self.status_bar.set_token_usage(self.total_usage.clone());
         self.status_bar
             .set_context_state(self.context_state.clone());
         self.status_bar.set_queue_count(self.queued_messages.len());
+        self.status_bar.set_rate_limit(
+            self.rate_limit_wait
+                .map(|wait| wait.countdown(Instant::now())),
+        );
         self.status_bar
             .set_supports_plan_mode(self.capabilities.supports_plan_mode);
 
@@ -246,6 +338,31 @@ impl AgentSession {
         self.raw_events_view.set_session_id(session_id);
     }
 
+    /// Name shown for the session's agent
+    pub fn agent_name(&self) -> &str {
+        agent_name(self.agent_type, self.acp_name.as_deref())
+    }
+
+    /// Role label for the agent's chat messages
+    fn agent_label(&self) -> &str {
+        match self.agent_type {
+            AgentType::Acp => self.agent_name(),
+            agent_type => agent_type.short_name(),
+        }
+    }
+
+    /// Use the ACP agent's configured name
+    pub fn set_acp_name(&mut self, name: &str) {
+        if self.acp_name.as_deref() == Some(name) {
+            return;
+        }
+        self.acp_name = Some(name.to_string());
+        self.chat_view
+            .set_agent_label(self.agent_label().to_string());
+        self.status_bar
+            .set_agent_name(agent_name(self.agent_type, self.acp_name.as_deref()));
+    }
+
     /// Change agent type and/or model, updating all related state.
     /// Returns true if the agent type changed.
     pub fn set_agent_and_model(&mut self, agent_type: AgentType, model: Option<String>) -> bool {
@@ -260,7 +377,7 @@ impl AgentSession {
         if agent_changed {
             self.reasoning_effort = None;
             self.chat_view
-                .set_agent_label(agent_type.short_name().to_string());
+                .set_agent_label(self.agent_label().to_string());
         }
         self.last_mode_prompt = None;
 
@@ -405,6 +522,18 @@ impl AgentSession {
         }
     }
 
+    /// Whether a queued prompt can be started now rather than when the
+    /// running turn ends. A rate limit that gave up retrying doesn't hold the
+    /// queue; one counting down to a resend does.
+    pub fn can_start_queued_prompt(&self) -> bool {
+        !self.is_processing
+            && self.inline_prompt.is_none()
+            && !self.pending_restart
+            && !self
+                .rate_limit_wait
+                .is_some_and(|wait| wait.retry_at.is_some())
+    }
+
     pub fn queue_message(&mut self, message: QueuedMessage) {
         self.queued_messages.push(message);
         self.update_status();
@@ -513,6 +642,14 @@ impl AgentSession {
     }
 }
 
+/// Name shown for an agent, given the ACP agent's configured name
+fn agent_name(agent_type: AgentType, acp_name: Option<&str>) -> &str {
+    match (agent_type, acp_name) {
+        (AgentType::Acp, Some(name)) => name,
+        (agent_type, _) => agent_type.display_name(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -586,4 +723,37 @@ mod tests {
         assert!(session.capabilities.supports_plan_mode);
         assert_eq!(session.agent_type, AgentType::Claude);
     }
+
+    #[test]
+    fn test_acp_session_uses_configured_name() {
+        let mut session = AgentSession::new(AgentType::Claude);
+        session.set_acp_name("Goose");
+        assert_eq!(session.agent_name(), "Claude Code");
+
+        session.set_agent_and_model(AgentType::Acp, None);
+        assert_eq!(session.agent_name(), "Goose");
+    }
+
+    #[test]
+    fn test_rate_limit_give_up_does_not_hold_queue() {
+        let mut session = AgentSession::new(AgentType::Claude);
+        assert!(session.can_start_queued_prompt());
+
+        // Counting down to a resend: the resend goes first
+        session.rate_limit_wait = Some(RateLimitWait {
+            kind: ProviderErrorKind::RateLimited,
+            retry_at: Some(Instant::now()),
+        });
+        assert!(!session.can_start_queued_prompt());
+
+        // Gave up retrying: queued prompts run
+        session.rate_limit_wait = Some(RateLimitWait {
+            kind: ProviderErrorKind::RateLimited,
+            retry_at: None,
+        });
+        assert!(session.can_start_queued_prompt());
+
+        session.is_processing = true;
+        assert!(!session.can_start_queued_prompt());
+    }
 }
@@ -1,10 +1,11 @@
 //! Tab abstraction for supporting different tab types
 //!
 //! This module defines the Tab enum which allows the TabManager to store
-//! both agent sessions and file viewers in the same collection.
+//! agent sessions, file viewers and diff reviews in the same collection.
 
 use uuid::Uuid;
 
+use crate::ui::diff_viewer::DiffViewerSession;
 use crate::ui::file_viewer::FileViewerSession;
 use crate::ui::session::AgentSession;
 
@@ -18,6 +19,8 @@ pub enum Tab {
     Agent(AgentSession),
     /// A file viewer tab
     File(FileViewerSession),
+    /// A workspace diff review tab
+    Diff(DiffViewerSession),
 }
 
 impl Tab {
@@ -26,6 +29,7 @@ impl Tab {
         match self {
             Tab::Agent(session) => session.id,
             Tab::File(viewer) => viewer.id,
+            Tab::Diff(viewer) => viewer.id,
         }
     }
 
@@ -34,6 +38,7 @@ impl Tab {
         match self {
             Tab::Agent(session) => session.tab_name(),
             Tab::File(viewer) => viewer.tab_name(),
+            Tab::Diff(viewer) => viewer.tab_name(),
         }
     }
 
@@ -41,7 +46,7 @@ impl Tab {
     pub fn needs_attention(&self) -> bool {
         match self {
             Tab::Agent(session) => session.needs_attention,
-            Tab::File(_) => false, // Files don't have notifications
+            Tab::File(_) | Tab::Diff(_) => false, // Files don't have notifications
         }
     }
 
@@ -49,7 +54,7 @@ impl Tab {
     pub fn is_processing(&self) -> bool {
         match self {
             Tab::Agent(session) => session.is_processing,
-            Tab::File(_) => false,
+            Tab::File(_) | Tab::Diff(_) => false,
         }
     }
 
@@ -57,7 +62,7 @@ impl Tab {
     pub fn is_awaiting_response(&self) -> bool {
         match self {
             Tab::Agent(session) => session.inline_prompt.is_some(),
-            Tab::File(_) => false,
+            Tab::File(_) | Tab::Diff(_) => false,
         }
     }
 
@@ -65,7 +70,7 @@ impl Tab {
     pub fn as_agent(&self) -> Option<&AgentSession> {
         match self {
             Tab::Agent(session) => Some(session),
-            Tab::File(_) => None,
+            Tab::File(_) | Tab::Diff(_) => None,
         }
     }
 
@@ -73,14 +78,14 @@ impl Tab {
     pub fn as_agent_mut(&mut self) -> Option<&mut AgentSession> {
         match self {
             Tab::Agent(session) => Some(session),
-            Tab::File(_) => None,
+            Tab::File(_) | Tab::Diff(_) => None,
         }
     }
 
     /// Get the file viewer if this is a file tab
     pub fn as_file(&self) -> Option<&FileViewerSession> {
         match self {
-            Tab::Agent(_) => None,
+            Tab::Agent(_) | Tab::Diff(_) => None,
             Tab::File(viewer) => Some(viewer),
         }
     }
@@ -88,8 +93,24 @@ impl Tab {
     /// Get the file viewer mutably if this is a file tab
     pub fn as_file_mut(&mut self) -> Option<&mut FileViewerSession> {
         match self {
-            Tab::Agent(_) => None,
+            Tab::Agent(_) | Tab::Diff(_) => None,
             Tab::File(viewer) => Some(viewer),
         }
     }
+
+    /// Get the diff review if this is a diff tab
+    pub fn as_diff(&self) -> Option<&DiffViewerSession> {
+        match self {
+            Tab::Diff(viewer) => Some(viewer),
+            _ => None,
+        }
+    }
+
+    /// Get the diff review mutably if this is a diff tab
+    pub fn as_diff_mut(&mut self) -> Option<&mut DiffViewerSession> {
+        match self {
+            Tab::Diff(viewer) => Some(viewer),
+            _ => None,
+        }
+    }
 }
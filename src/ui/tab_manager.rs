@@ -1,12 +1,14 @@
 //! Tab manager for handling multiple tabs of different types
 //!
-//! This module manages all tabs (agent sessions and file viewers) in the application.
+//! This module manages all tabs (agent sessions, file viewers and diff reviews) in the
+//! application.
 
 use std::path::PathBuf;
 
 use uuid::Uuid;
 
 use crate::agent::AgentType;
+use crate::ui::diff_viewer::DiffViewerSession;
 use crate::ui::file_viewer::FileViewerSession;
 use crate::ui::session::AgentSession;
 use crate::ui::tab::Tab;
@@ -19,6 +21,8 @@ pub struct TabManager {
     active_tab: usize,
     /// Maximum number of tabs allowed
     max_tabs: usize,
+    /// Configured name of the ACP agent, given to every session
+    acp_name: String,
 }
 
 impl TabManager {
@@ -27,6 +31,17 @@ impl TabManager {
             tabs: Vec::new(),
             active_tab: 0,
             max_tabs,
+            acp_name: AgentType::Acp.display_name().to_string(),
+        }
+    }
+
+    /// Name ACP sessions, open and future, after the configured agent
+    pub fn set_acp_name(&mut self, name: &str) {
+        self.acp_name = name.to_string();
+        for tab in &mut self.tabs {
+            if let Tab::Agent(session) = tab {
+                session.set_acp_name(name);
+            }
         }
     }
 
@@ -36,7 +51,8 @@ impl TabManager {
             return None;
         }
 
-        let session = AgentSession::new(agent_type);
+        let mut session = AgentSession::new(agent_type);
+        session.set_acp_name(&self.acp_name);
         self.tabs.push(Tab::Agent(session));
         let new_index = self.tabs.len() - 1;
         self.active_tab = new_index;
@@ -53,7 +69,8 @@ impl TabManager {
             return None;
         }
 
-        let session = AgentSession::with_working_dir(agent_type, working_dir);
+        let mut session = AgentSession::with_working_dir(agent_type, working_dir);
+        session.set_acp_name(&self.acp_name);
         self.tabs.push(Tab::Agent(session));
         let new_index = self.tabs.len() - 1;
         self.active_tab = new_index;
@@ -73,6 +90,18 @@ impl TabManager {
         Ok(new_index)
     }
 
+    /// Open a diff review in a new tab
+    pub fn open_diff(&mut self, viewer: DiffViewerSession) -> Option<usize> {
+        if self.tabs.len() >= self.max_tabs {
+            return None;
+        }
+
+        self.tabs.push(Tab::Diff(viewer));
+        let new_index = self.tabs.len() - 1;
+        self.active_tab = new_index;
+        Some(new_index)
+    }
+
     /// Close a tab by index
     pub fn close_tab(&mut self, index: usize) -> bool {
         if index >= self.tabs.len() {
@@ -192,6 +221,24 @@ impl TabManager {
         }
     }
 
+    /// Get a reference to the active diff review
+    /// Returns None if active tab is not a diff review
+    pub fn active_diff_viewer(&self) -> Option<&DiffViewerSession> {
+        match self.tabs.get(self.active_tab) {
+            Some(Tab::Diff(viewer)) => Some(viewer),
+            _ => None,
+        }
+    }
+
+    /// Get a mutable reference to the active diff review
+    /// Returns None if active tab is not a diff review
+    pub fn active_diff_viewer_mut(&mut self) -> Option<&mut DiffViewerSession> {
+        match self.tabs.get_mut(self.active_tab) {
+            Some(Tab::Diff(viewer)) => Some(viewer),
+            _ => None,
+        }
+    }
+
     /// Get a reference to an agent session by index
     pub fn session(&self, index: usize) -> Option<&AgentSession> {
         match self.tabs.get(index) {
@@ -265,10 +312,11 @@ impl TabManager {
     }
 
     /// Add an existing session (used for session restoration)
-    pub fn add_session(&mut self, session: AgentSession) -> Option<usize> {
+    pub fn add_session(&mut self, mut session: AgentSession) -> Option<usize> {
         if self.tabs.len() >= self.max_tabs {
             return None;
         }
+        session.set_acp_name(&self.acp_name);
 
         self.tabs.push(Tab::Agent(session));
         let new_index = self.tabs.len() - 1;
@@ -283,6 +331,29 @@ impl TabManager {
         })
     }
 
+    /// Get a reference to a diff review by index
+    pub fn diff_viewer(&self, index: usize) -> Option<&DiffViewerSession> {
+        match self.tabs.get(index) {
+            Some(Tab::Diff(viewer)) => Some(viewer),
+            _ => None,
+        }
+    }
+
+    /// Find a diff review by its UUID and return a mutable reference
+    pub fn diff_viewer_by_id_mut(&mut self, id: Uuid) -> Option<&mut DiffViewerSession> {
+        self.tabs.iter_mut().find_map(|t| match t {
+            Tab::Diff(viewer) if viewer.id == id => Some(viewer),
+            _ => None,
+        })
+    }
+
+    /// Find the diff review tab open for a workspace
+    pub fn diff_tab_index_for_workspace(&self, workspace_id: Uuid) -> Option<usize> {
+        self.tabs
+            .iter()
+            .position(|t| matches!(t, Tab::Diff(v) if v.workspace_id == Some(workspace_id)))
+    }
+
     /// Check if the active tab is a file viewer
     pub fn active_is_file(&self) -> bool {
         matches!(self.tabs.get(self.active_tab), Some(Tab::File(_)))
@@ -292,4 +363,9 @@ impl TabManager {
     pub fn active_is_agent(&self) -> bool {
         matches!(self.tabs.get(self.active_tab), Some(Tab::Agent(_)))
     }
+
+    /// Check if the active tab is a diff review
+    pub fn active_is_diff(&self) -> bool {
+        matches!(self.tabs.get(self.active_tab), Some(Tab::Diff(_)))
+    }
 }
@@ -0,0 +1,269 @@
+//! Minimal cron expression parsing for scheduled prompts
+//!
+//! Supports the standard five fields (minute, hour, day of month, month,
+//! day of week) with `*`, lists, ranges, `/step`, month and weekday names,
+//! plus the `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` aliases.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike};
+
+/// How far ahead to look for the next matching minute before giving up
+const SEARCH_YEARS: i32 = 5;
+
+const MONTH_NAMES: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// A parsed five-field cron schedule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u32,
+    days_of_month: u32,
+    months: u16,
+    days_of_week: u8,
+    /// Whether the day-of-month field was restricted (not `*`)
+    dom_restricted: bool,
+    /// Whether the day-of-week field was restricted (not `*`)
+    dow_restricted: bool,
+}
+
+impl CronSchedule {
+    /// The first matching minute strictly after `after`, in its timezone
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = after.timezone();
+        let start = after.naive_local() + Duration::minutes(1);
+        let mut date = start.date();
+        let mut first_day = true;
+        let last_year = date.year() + SEARCH_YEARS;
+
+        while date.year() <= last_year {
+            if !self.matches_month(date.month()) {
+                date = first_of_next_month(date)?;
+                first_day = false;
+                continue;
+            }
+            if !self.matches_day(date) {
+                date = date.succ_opt()?;
+                first_day = false;
+                continue;
+            }
+
+            let (start_hour, start_minute) = if first_day {
+                (start.hour(), start.minute())
+            } else {
+                (0, 0)
+            };
+            for hour in start_hour..24 {
+                if self.hours & (1 << hour) == 0 {
+                    continue;
+                }
+                let from_minute = if hour == start_hour { start_minute } else { 0 };
+                for minute in from_minute..60 {
+                    if self.minutes & (1 << minute) == 0 {
+                        continue;
+                    }
+                    let Some(naive) = date.and_hms_opt(hour, minute, 0) else {
+                        continue;
+                    };
+                    // Times skipped by a DST change have no local mapping
+                    if let Some(local) = tz.from_local_datetime(&naive).earliest() {
+                        return Some(local);
+                    }
+                }
+            }
+
+            date = date.succ_opt()?;
+            first_day = false;
+        }
+        None
+    }
+
+    fn matches_month(&self, month: u32) -> bool {
+        self.months & (1 << month) != 0
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let dom = self.days_of_month & (1 << date.day()) != 0;
+        let dow = self.days_of_week & (1 << date.weekday().num_days_from_sunday()) != 0;
+        // Like cron: when both fields are restricted, either one matching is enough
+        if self.dom_restricted && self.dow_restricted {
+            dom || dow
+        } else {
+            dom && dow
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expr = match s.trim().to_ascii_lowercase().as_str() {
+            "@hourly" => "0 * * * *".to_string(),
+            "@daily" | "@midnight" => "0 0 * * *".to_string(),
+            "@weekly" => "0 0 * * 0".to_string(),
+            "@monthly" => "0 0 1 * *".to_string(),
+            "@yearly" | "@annually" => "0 0 1 1 *".to_string(),
+            other => other.to_string(),
+        };
+
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "expected 5 fields (minute hour day month weekday), got {}",
+                fields.len()
+            ));
+        }
+
+        let minutes = parse_field(fields[0], "minute", 0, 59, &[])?;
+        let hours = parse_field(fields[1], "hour", 0, 23, &[])?;
+        let days_of_month = parse_field(fields[2], "day of month", 1, 31, &[])?;
+        let months = parse_field(fields[3], "month", 1, 12, MONTH_NAMES)?;
+        let mut days_of_week = parse_field(fields[4], "weekday", 0, 7, WEEKDAY_NAMES)?;
+        // 7 is an alias for Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            minutes,
+            hours: hours as u32,
+            days_of_month: days_of_month as u32,
+            months: months as u16,
+            days_of_week: days_of_week as u8,
+            dom_restricted: fields[2] != "*",
+            dow_restricted: fields[4] != "*",
+        })
+    }
+}
+
+/// Parse one cron field into a bit set of allowed values
+fn parse_field(field: &str, name: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("invalid step '{}' in {} field", step, name))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                parse_value(start, name, min, max, names)?,
+                parse_value(end, name, min, max, names)?,
+            )
+        } else {
+            let start = parse_value(range, name, min, max, names)?;
+            // "5/15" means every 15 starting at 5
+            (start, if step > 1 { max } else { start })
+        };
+        if start > end {
+            return Err(format!(
+                "range {}-{} is backwards in {} field",
+                start, end, name
+            ));
+        }
+
+        let mut value = start;
+        while value <= end {
+            bits |= 1 << value;
+            value += step;
+        }
+    }
+    Ok(bits)
+}
+
+fn parse_value(value: &str, name: &str, min: u32, max: u32, names: &[&str]) -> Result<u32, String> {
+    if let Some(index) = names.iter().position(|n| *n == value) {
+        // Month names start at 1, weekday names at 0
+        return Ok(index as u32 + min);
+    }
+    let parsed: u32 = value
+        .parse()
+        .map_err(|_| format!("invalid value '{}' in {} field", value, name))?;
+    if parsed < min || parsed > max {
+        return Err(format!(
+            "{} is out of range for {} field ({}-{})",
+            parsed, name, min, max
+        ));
+    }
+    Ok(parsed)
+}
+
+fn first_of_next_month(date: NaiveDate) -> Option<NaiveDate> {
+    if date.month() == 12 {
+        NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_weekday_mornings() {
+        let schedule: CronSchedule = "0 8 * * mon-fri".parse().unwrap();
+        // Friday 2024-06-07 09:00 -> Monday 08:00
+        let next = schedule.next_after(&at("2024-06-07T09:00:00Z")).unwrap();
+        assert_eq!(next, at("2024-06-10T08:00:00Z"));
+        // Strictly after: an exact match moves on to the next day
+        let next = schedule.next_after(&at("2024-06-10T08:00:00Z")).unwrap();
+        assert_eq!(next, at("2024-06-11T08:00:00Z"));
+    }
+
+    #[test]
+    fn test_steps_lists_and_aliases() {
+        let schedule: CronSchedule = "*/15 9,17 * * *".parse().unwrap();
+        let next = schedule.next_after(&at("2024-06-07T09:50:00Z")).unwrap();
+        assert_eq!(next, at("2024-06-07T17:00:00Z"));
+
+        let nightly: CronSchedule = "@daily".parse().unwrap();
+        let next = nightly.next_after(&at("2024-12-31T23:59:30Z")).unwrap();
+        assert_eq!(next, at("2025-01-01T00:00:00Z"));
+
+        let sundays: CronSchedule = "30 6 * * 7".parse().unwrap();
+        let next = sundays.next_after(&at("2024-06-07T00:00:00Z")).unwrap();
+        assert_eq!(next, at("2024-06-09T06:30:00Z"));
+    }
+
+    #[test]
+    fn test_day_of_month_or_weekday() {
+        // The 1st of the month or any Monday
+        let schedule: CronSchedule = "0 0 1 * mon".parse().unwrap();
+        let next = schedule.next_after(&at("2024-06-25T12:00:00Z")).unwrap();
+        assert_eq!(next, at("2024-07-01T00:00:00Z"));
+        let next = schedule.next_after(&at("2024-07-01T00:00:00Z")).unwrap();
+        assert_eq!(next, at("2024-07-08T00:00:00Z"));
+
+        // Feb 30 never happens
+        let never: CronSchedule = "0 0 30 feb *".parse().unwrap();
+        assert!(never.next_after(&at("2024-01-01T00:00:00Z")).is_none());
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        assert!("0 8 * *".parse::<CronSchedule>().is_err());
+        assert!("60 * * * *".parse::<CronSchedule>().is_err());
+        assert!("*/0 * * * *".parse::<CronSchedule>().is_err());
+        assert!("0 17-9 * * *".parse::<CronSchedule>().is_err());
+        assert!("0 8 * * someday".parse::<CronSchedule>().is_err());
+    }
+}
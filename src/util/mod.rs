@@ -1,11 +1,13 @@
 //! Utility modules
 
+pub mod cron;
 pub mod names;
 pub mod paths;
 pub mod project_folders;
 pub mod title_generator;
 pub mod tools;
 
+pub use cron::CronSchedule;
 pub use names::{generate_branch_name, generate_workspace_name, get_git_username};
 pub use paths::{
     data_dir, database_path, init_data_dir, log_file_path, logs_dir,
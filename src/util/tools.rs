@@ -1,7 +1,8 @@
 //! Tool availability detection and management
 //!
 //! This module provides functionality to detect and track the availability
-//! of external tools required by Conduit (git, gh, claude, codex, gemini, opencode).
+//! of external tools required by Conduit (git, gh, claude, codex, gemini, opencode,
+//! and an optional ACP agent).
 
 use std::path::{Path, PathBuf};
 
@@ -22,6 +23,8 @@ pub enum Tool {
     Gemini,
     /// OpenCode CLI agent
     Opencode,
+    /// Agent Client Protocol agent configured in config.toml
+    Acp,
 }
 
 impl Tool {
@@ -34,6 +37,7 @@ impl Tool {
             Tool::Codex => "codex",
             Tool::Gemini => "gemini",
             Tool::Opencode => "opencode",
+            Tool::Acp => "acp-agent",
         }
     }
 
@@ -46,6 +50,7 @@ impl Tool {
             Tool::Codex => "Codex CLI",
             Tool::Gemini => "Gemini CLI",
             Tool::Opencode => "OpenCode",
+            Tool::Acp => crate::agent::AgentType::Acp.display_name(),
         }
     }
 
@@ -58,6 +63,7 @@ impl Tool {
             Tool::Codex => "npm install -g @openai/codex\nhttps://github.com/openai/codex-cli",
             Tool::Gemini => "npm install -g @google/gemini-cli\nhttps://github.com/google-gemini/gemini-cli",
             Tool::Opencode => "brew install anomalyco/tap/opencode\nhttps://opencode.ai/docs",
+            Tool::Acp => "Add an [acp_agent] section with the agent command to config.toml\nhttps://agentclientprotocol.com",
         }
     }
 
@@ -70,6 +76,7 @@ impl Tool {
             Tool::Codex => "Codex is an AI coding assistant from OpenAI.",
             Tool::Gemini => "Gemini CLI is an AI coding assistant from Google.",
             Tool::Opencode => "OpenCode is a multi-provider AI coding assistant.",
+            Tool::Acp => "Any coding agent that speaks the Agent Client Protocol.",
         }
     }
 
@@ -82,7 +89,7 @@ impl Tool {
     pub fn is_agent(&self) -> bool {
         matches!(
             self,
-            Tool::Claude | Tool::Codex | Tool::Gemini | Tool::Opencode
+            Tool::Claude | Tool::Codex | Tool::Gemini | Tool::Opencode | Tool::Acp
         )
     }
 
@@ -95,6 +102,7 @@ impl Tool {
             Tool::Codex,
             Tool::Gemini,
             Tool::Opencode,
+            Tool::Acp,
         ]
     }
 }
@@ -141,6 +149,9 @@ pub struct ToolPaths {
     pub codex: Option<PathBuf>,
     pub gemini: Option<PathBuf>,
     pub opencode: Option<PathBuf>,
+    /// Resolved from the `[acp_agent]` command rather than `[tools]`
+    #[serde(skip)]
+    pub acp: Option<PathBuf>,
 }
 
 impl ToolPaths {
@@ -153,6 +164,7 @@ impl ToolPaths {
             Tool::Codex => self.codex.as_ref(),
             Tool::Gemini => self.gemini.as_ref(),
             Tool::Opencode => self.opencode.as_ref(),
+            Tool::Acp => self.acp.as_ref(),
         }
     }
 
@@ -165,6 +177,7 @@ impl ToolPaths {
             Tool::Codex => self.codex = Some(path),
             Tool::Gemini => self.gemini = Some(path),
             Tool::Opencode => self.opencode = Some(path),
+            Tool::Acp => self.acp = Some(path),
         }
     }
 }
@@ -178,6 +191,7 @@ pub struct ToolAvailability {
     codex: ToolStatus,
     gemini: ToolStatus,
     opencode: ToolStatus,
+    acp: ToolStatus,
 }
 
 impl ToolAvailability {
@@ -195,6 +209,7 @@ impl ToolAvailability {
             codex: Self::detect_tool(Tool::Codex, configured_paths.codex.as_ref()),
             gemini: Self::detect_tool(Tool::Gemini, configured_paths.gemini.as_ref()),
             opencode: Self::detect_tool(Tool::Opencode, configured_paths.opencode.as_ref()),
+            acp: Self::detect_tool(Tool::Acp, configured_paths.acp.as_ref()),
         }
     }
 
@@ -209,6 +224,11 @@ impl ToolAvailability {
             }
         }
 
+        // The ACP agent has no well-known binary; it must come from config
+        if tool == Tool::Acp {
+            return ToolStatus::NotFound;
+        }
+
         // Otherwise, try to find it in PATH using `which`
         match which::which(tool.binary_name()) {
             Ok(path) => ToolStatus::Available(path),
@@ -258,6 +278,7 @@ impl ToolAvailability {
             Tool::Codex => &self.codex,
             Tool::Gemini => &self.gemini,
             Tool::Opencode => &self.opencode,
+            Tool::Acp => &self.acp,
         }
     }
 
@@ -294,6 +315,7 @@ impl ToolAvailability {
             || self.is_available(Tool::Codex)
             || self.is_available(Tool::Gemini)
             || self.is_available(Tool::Opencode)
+            || self.is_available(Tool::Acp)
     }
 
     /// Get list of available agents
@@ -324,6 +346,7 @@ impl ToolAvailability {
             Tool::Codex => self.codex = status,
             Tool::Gemini => self.gemini = status,
             Tool::Opencode => self.opencode = status,
+            Tool::Acp => self.acp = status,
         }
 
         is_available
@@ -389,6 +412,7 @@ mod tests {
         assert!(Tool::Codex.is_agent());
         assert!(Tool::Gemini.is_agent());
         assert!(Tool::Opencode.is_agent());
+        assert!(Tool::Acp.is_agent());
     }
 
     #[test]
@@ -410,4 +434,13 @@ mod tests {
             ToolStatus::Available(_) | ToolStatus::NotFound
         ));
     }
+
+    #[test]
+    fn test_acp_requires_configured_command() {
+        let availability = ToolAvailability::detect(&ToolPaths::default());
+        assert!(matches!(
+            availability.status(Tool::Acp),
+            ToolStatus::NotFound
+        ));
+    }
 }
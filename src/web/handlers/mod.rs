@@ -6,7 +6,9 @@ pub mod models;
 pub mod onboarding;
 pub mod queue;
 pub mod repositories;
+pub mod schedules;
 pub mod sessions;
 pub mod themes;
 pub mod ui_state;
+pub mod usage;
 pub mod workspaces;
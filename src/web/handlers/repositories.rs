@@ -9,9 +9,10 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use uuid::Uuid;
 
-use crate::core::resolve_repo_workspace_settings;
+use crate::agent::{ApprovalPolicy, CodexApprovalMode, CodexSandboxMode};
+use crate::core::{resolve_repo_approval_policy, resolve_repo_workspace_settings};
 use crate::data::Repository;
-use crate::git::WorkspaceMode;
+use crate::git::{run_workspace_teardown, HookContext, WorkspaceMode};
 use crate::web::error::WebError;
 use crate::web::state::WebAppState;
 
@@ -28,6 +29,10 @@ pub struct RepositoryResponse {
     pub archive_delete_branch_effective: bool,
     pub archive_remote_prompt: Option<bool>,
     pub archive_remote_prompt_effective: bool,
+    pub approval_policy: Option<ApprovalPolicy>,
+    pub approval_policy_effective: ApprovalPolicy,
+    pub codex_approval_mode: Option<CodexApprovalMode>,
+    pub codex_sandbox_mode: Option<CodexSandboxMode>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -35,6 +40,7 @@ pub struct RepositoryResponse {
 impl RepositoryResponse {
     pub(crate) fn from_repo(repo: Repository, config: &crate::config::Config) -> Self {
         let settings = resolve_repo_workspace_settings(config, &repo);
+        let approval_policy_effective = resolve_repo_approval_policy(config, Some(&repo));
         Self {
             id: repo.id,
             name: repo.name,
@@ -46,6 +52,10 @@ impl RepositoryResponse {
             archive_delete_branch_effective: settings.archive_delete_branch,
             archive_remote_prompt: repo.archive_remote_prompt,
             archive_remote_prompt_effective: settings.archive_remote_prompt,
+            approval_policy: repo.approval_policy,
+            approval_policy_effective,
+            codex_approval_mode: repo.codex_approval_mode,
+            codex_sandbox_mode: repo.codex_sandbox_mode,
             created_at: repo.created_at.to_rfc3339(),
             updated_at: repo.updated_at.to_rfc3339(),
         }
@@ -72,6 +82,9 @@ pub struct UpdateRepositorySettingsRequest {
     pub workspace_mode: Option<WorkspaceMode>,
     pub archive_delete_branch: Option<bool>,
     pub archive_remote_prompt: Option<bool>,
+    pub approval_policy: Option<ApprovalPolicy>,
+    pub codex_approval_mode: Option<CodexApprovalMode>,
+    pub codex_sandbox_mode: Option<CodexSandboxMode>,
 }
 
 /// List all repositories.
@@ -193,6 +206,7 @@ pub async fn update_repository_settings(
     let workspace_mode = req.workspace_mode.or(repo.workspace_mode);
     let archive_delete_branch = req.archive_delete_branch.or(repo.archive_delete_branch);
     let archive_remote_prompt = req.archive_remote_prompt.or(repo.archive_remote_prompt);
+    let approval_policy = req.approval_policy.or(repo.approval_policy);
 
     repo_store
         .update_settings(
@@ -200,8 +214,14 @@ pub async fn update_repository_settings(
             workspace_mode,
             archive_delete_branch,
             archive_remote_prompt,
+            approval_policy,
         )
         .map_err(|e| WebError::Internal(format!("Failed to update repository: {}", e)))?;
+    if req.codex_approval_mode.is_some() || req.codex_sandbox_mode.is_some() {
+        repo_store
+            .update_codex_defaults(id, req.codex_approval_mode, req.codex_sandbox_mode)
+            .map_err(|e| WebError::Internal(format!("Failed to update repository: {}", e)))?;
+    }
 
     let updated = repo_store
         .get_by_id(id)
@@ -374,6 +394,19 @@ pub async fn remove_repository(
         let mut archived_commit_sha = None;
 
         if let Some(ref base_path) = repo.base_path {
+            // Run the repository's teardown hook while the workspace still exists
+            if let Err(e) = run_workspace_teardown(HookContext {
+                root_path: base_path,
+                workspace_path: &ws.path,
+                workspace_name: &ws.name,
+                branch: &ws.branch,
+            }) {
+                errors.push(format!(
+                    "Teardown hook failed for workspace '{}': {}",
+                    ws.name, e
+                ));
+            }
+
             // Get branch SHA
             match worktree_manager.get_branch_sha(settings.mode, base_path, &ws.path, &ws.branch) {
                 Ok(sha) => {
@@ -0,0 +1,182 @@
+//! Scheduled prompt handlers for the Conduit web API.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::services::{
+    CreateScheduleParams, ScheduleService, ServiceError, UpdateScheduleParams,
+};
+use crate::data::ScheduledPrompt;
+use crate::web::error::WebError;
+use crate::web::state::WebAppState;
+
+#[derive(Debug, Serialize)]
+pub struct ScheduleResponse {
+    pub id: Uuid,
+    pub repository_id: Uuid,
+    pub workspace_id: Option<Uuid>,
+    pub name: String,
+    pub schedule: String,
+    pub prompt: String,
+    pub enabled: bool,
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ScheduledPrompt> for ScheduleResponse {
+    fn from(entry: ScheduledPrompt) -> Self {
+        Self {
+            id: entry.id,
+            repository_id: entry.repository_id,
+            workspace_id: entry.workspace_id,
+            name: entry.name,
+            schedule: entry.schedule,
+            prompt: entry.prompt,
+            enabled: entry.enabled,
+            next_run_at: entry.next_run_at,
+            last_run_at: entry.last_run_at,
+            last_error: entry.last_error,
+            created_at: entry.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListSchedulesResponse {
+    pub schedules: Vec<ScheduleResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateScheduleRequest {
+    pub repository_id: Uuid,
+    /// Omit to create a new workspace for every run
+    pub workspace_id: Option<Uuid>,
+    pub name: Option<String>,
+    pub schedule: String,
+    pub prompt: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateScheduleRequest {
+    pub name: Option<String>,
+    pub schedule: Option<String>,
+    pub prompt: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RunScheduleResponse {
+    pub workspace_id: Uuid,
+    pub session_id: Uuid,
+}
+
+/// List all scheduled prompts.
+pub async fn list_schedules(
+    State(state): State<WebAppState>,
+) -> Result<Json<ListSchedulesResponse>, WebError> {
+    let core = state.core().await;
+    let schedules = ScheduleService::list(&core).map_err(map_service_error)?;
+    Ok(Json(ListSchedulesResponse {
+        schedules: schedules.into_iter().map(ScheduleResponse::from).collect(),
+    }))
+}
+
+/// Get a single scheduled prompt.
+pub async fn get_schedule(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ScheduleResponse>, WebError> {
+    let core = state.core().await;
+    let entry = ScheduleService::get(&core, id).map_err(map_service_error)?;
+    Ok(Json(ScheduleResponse::from(entry)))
+}
+
+/// Create a scheduled prompt.
+pub async fn create_schedule(
+    State(state): State<WebAppState>,
+    Json(req): Json<CreateScheduleRequest>,
+) -> Result<(StatusCode, Json<ScheduleResponse>), WebError> {
+    let core = state.core().await;
+    let entry = ScheduleService::create(
+        &core,
+        CreateScheduleParams {
+            repository_id: req.repository_id,
+            workspace_id: req.workspace_id,
+            name: req.name,
+            schedule: req.schedule,
+            prompt: req.prompt,
+            enabled: req.enabled,
+        },
+    )
+    .map_err(map_service_error)?;
+    Ok((StatusCode::CREATED, Json(ScheduleResponse::from(entry))))
+}
+
+/// Update a scheduled prompt.
+pub async fn update_schedule(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateScheduleRequest>,
+) -> Result<Json<ScheduleResponse>, WebError> {
+    let core = state.core().await;
+    let entry = ScheduleService::update(
+        &core,
+        id,
+        UpdateScheduleParams {
+            name: req.name,
+            schedule: req.schedule,
+            prompt: req.prompt,
+            enabled: req.enabled,
+        },
+    )
+    .map_err(map_service_error)?;
+    Ok(Json(ScheduleResponse::from(entry)))
+}
+
+/// Delete a scheduled prompt.
+pub async fn delete_schedule(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, WebError> {
+    let core = state.core().await;
+    ScheduleService::delete(&core, id).map_err(map_service_error)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Run a scheduled prompt now, without moving its next run.
+pub async fn run_schedule(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<RunScheduleResponse>, WebError> {
+    let entry = ScheduleService::get(&*state.core().await, id).map_err(map_service_error)?;
+    let delivery = state
+        .run_schedule(&entry, Utc::now())
+        .await
+        .map_err(map_service_error)?;
+    Ok(Json(RunScheduleResponse {
+        workspace_id: delivery.workspace_id,
+        session_id: delivery.session_id,
+    }))
+}
+
+fn map_service_error(error: ServiceError) -> WebError {
+    match error {
+        ServiceError::InvalidInput(message) => WebError::BadRequest(message),
+        ServiceError::NotFound(message) => WebError::NotFound(message),
+        ServiceError::Internal(message) => WebError::Internal(message),
+    }
+}
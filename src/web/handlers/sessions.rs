@@ -2,7 +2,8 @@
 
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
@@ -10,7 +11,8 @@ use uuid::Uuid;
 
 use crate::agent::{
     load_claude_history_with_debug, load_codex_history_with_debug,
-    load_opencode_history_with_debug, AgentMode, AgentType, ModelRegistry,
+    load_opencode_history_with_debug, AgentMode, AgentType, CodexApprovalMode, CodexSandboxMode,
+    ModelRegistry,
 };
 use crate::core::resolve_repo_workspace_settings;
 use crate::core::services::session_service::CreateForkedSessionParams;
@@ -18,6 +20,7 @@ use crate::core::services::{
     ContextWindowService, CreateSessionParams, ServiceError, SessionService, UpdateSessionParams,
 };
 use crate::data::{ForkSeed, SessionTab, Workspace};
+use crate::session::{render_transcript, TranscriptFormat, TranscriptMeta};
 use crate::ui::app_prompt;
 use crate::ui::components::{ChatMessage, MessageRole};
 use crate::util::names::{generate_branch_name, generate_workspace_name, get_git_username};
@@ -40,6 +43,8 @@ pub struct SessionResponse {
     pub pr_number: Option<i32>,
     pub created_at: String,
     pub title: Option<String>,
+    pub codex_approval_mode: Option<CodexApprovalMode>,
+    pub codex_sandbox_mode: Option<CodexSandboxMode>,
 }
 
 impl From<SessionTab> for SessionResponse {
@@ -62,6 +67,8 @@ impl From<SessionTab> for SessionResponse {
             pr_number: session.pr_number,
             created_at: session.created_at.to_rfc3339(),
             title: session.title,
+            codex_approval_mode: session.codex_approval,
+            codex_sandbox_mode: session.codex_sandbox,
         }
     }
 }
@@ -86,6 +93,8 @@ pub struct UpdateSessionRequest {
     pub model: Option<String>,
     pub agent_type: Option<String>,
     pub agent_mode: Option<String>,
+    pub codex_approval_mode: Option<CodexApprovalMode>,
+    pub codex_sandbox_mode: Option<CodexSandboxMode>,
 }
 
 /// List all sessions.
@@ -122,9 +131,10 @@ pub async fn create_session(
         "claude" => AgentType::Claude,
         "gemini" => AgentType::Gemini,
         "opencode" => AgentType::Opencode,
+        "acp" => AgentType::Acp,
         _ => {
             return Err(WebError::BadRequest(format!(
-                "Invalid agent type: {}. Must be one of: codex, claude, gemini, opencode",
+                "Invalid agent type: {}. Must be one of: codex, claude, gemini, opencode, acp",
                 req.agent_type
             )));
         }
@@ -171,8 +181,9 @@ pub async fn update_session(
                 "claude" => Ok(AgentType::Claude),
                 "gemini" => Ok(AgentType::Gemini),
                 "opencode" => Ok(AgentType::Opencode),
+                "acp" => Ok(AgentType::Acp),
                 _ => Err(WebError::BadRequest(format!(
-                    "Invalid agent type: {}. Must be one of: codex, claude, gemini, opencode",
+                    "Invalid agent type: {}. Must be one of: codex, claude, gemini, opencode, acp",
                     agent_type_str
                 ))),
             },
@@ -199,6 +210,8 @@ pub async fn update_session(
             model: req.model,
             agent_type,
             agent_mode,
+            codex_approval: req.codex_approval_mode,
+            codex_sandbox: req.codex_sandbox_mode,
         },
     )
     .map_err(map_service_error)?;
@@ -232,7 +245,7 @@ fn load_history_for_session(session: &SessionTab) -> Vec<ChatMessage> {
                 tracing::warn!("Failed to load Codex history: {}", e);
                 Vec::new()
             }),
-        AgentType::Gemini => Vec::new(),
+        AgentType::Gemini | AgentType::Acp => Vec::new(),
         AgentType::Opencode => load_opencode_history_with_debug(agent_session_id)
             .map(|(messages, _, _)| messages)
             .unwrap_or_else(|e| {
@@ -393,8 +406,8 @@ pub async fn get_session_events(
                 vec![]
             }
         },
-        AgentType::Gemini => {
-            // Gemini history loading not supported yet
+        AgentType::Gemini | AgentType::Acp => {
+            // ACP history loading not supported yet
             vec![]
         }
         AgentType::Opencode => match load_opencode_history_with_debug(&agent_session_id) {
@@ -500,6 +513,60 @@ pub async fn get_session_history(
     Ok(Json(InputHistoryResponse { history }))
 }
 
+#[derive(Debug, Deserialize, Default)]
+pub struct SessionExportQuery {
+    pub format: Option<String>,
+}
+
+/// Export a session transcript as Markdown, HTML or JSONL.
+pub async fn export_session(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<SessionExportQuery>,
+) -> Result<Response, WebError> {
+    let format = match query.format.as_deref() {
+        Some(value) => value
+            .parse::<TranscriptFormat>()
+            .map_err(WebError::BadRequest)?,
+        None => TranscriptFormat::Markdown,
+    };
+
+    let core = state.core().await;
+    let session = SessionService::get_session(&core, id).map_err(map_service_error)?;
+    let workspace = session.workspace_id.and_then(|workspace_id| {
+        core.workspace_store()?
+            .get_by_id(workspace_id)
+            .ok()
+            .flatten()
+    });
+
+    let messages = load_history_for_session(&session);
+    let title = session
+        .title
+        .clone()
+        .or_else(|| workspace.as_ref().map(|w| w.name.clone()))
+        .unwrap_or_else(|| "Conduit session".to_string());
+    let mut meta = TranscriptMeta::new(title, session.agent_type);
+    meta.model = session.model.clone();
+    meta.workspace = workspace.as_ref().map(|w| w.name.clone());
+    meta.branch = workspace.as_ref().map(|w| w.branch.clone());
+
+    let body = render_transcript(&messages, &meta, format);
+    let filename = format!("session-{}.{}", session.id, format.extension());
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
 /// Fork a session into a new workspace and return the seed prompt.
 pub async fn fork_session(
     State(state): State<WebAppState>,
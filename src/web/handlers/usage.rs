@@ -0,0 +1,65 @@
+//! Usage ledger handler for the Conduit web API.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::services::{ServiceError, UsageService};
+use crate::data::{UsageGroupBy, UsageSummaryRow, UsageTotals};
+use crate::web::error::WebError;
+use crate::web::state::WebAppState;
+
+#[derive(Debug, Deserialize, Default)]
+pub struct UsageQuery {
+    pub since: Option<String>,
+    pub group_by: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsageResponse {
+    pub since: Option<DateTime<Utc>>,
+    pub group_by: UsageGroupBy,
+    pub rows: Vec<UsageSummaryRow>,
+    pub totals: UsageTotals,
+}
+
+/// Summarize recorded token usage and estimated cost.
+pub async fn get_usage(
+    State(state): State<WebAppState>,
+    Query(query): Query<UsageQuery>,
+) -> Result<Json<UsageResponse>, WebError> {
+    let group_by = match query.group_by.as_deref() {
+        Some(value) => value
+            .parse::<UsageGroupBy>()
+            .map_err(WebError::BadRequest)?,
+        None => UsageGroupBy::default(),
+    };
+    let since = query
+        .since
+        .as_deref()
+        .map(|value| UsageService::parse_since(value, Utc::now()))
+        .transpose()
+        .map_err(map_service_error)?;
+
+    let core = state.core().await;
+    let rows = UsageService::summarize(&core, since, group_by).map_err(map_service_error)?;
+    let totals = UsageTotals::sum(rows.iter().map(|row| &row.totals));
+
+    Ok(Json(UsageResponse {
+        since,
+        group_by,
+        rows,
+        totals,
+    }))
+}
+
+fn map_service_error(error: ServiceError) -> WebError {
+    match error {
+        ServiceError::InvalidInput(message) => WebError::BadRequest(message),
+        ServiceError::NotFound(message) => WebError::NotFound(message),
+        ServiceError::Internal(message) => WebError::Internal(message),
+    }
+}
@@ -1,7 +1,7 @@
 //! Workspace handlers for the Conduit web API.
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
@@ -12,12 +12,14 @@ use uuid::Uuid;
 use crate::core::resolve_repo_workspace_settings;
 use crate::core::services::{ServiceError, SessionService};
 use crate::data::Workspace;
-use crate::git::PrManager;
+use crate::git::{run_workspace_teardown, HookContext, PrManager, HOOKS_FILE};
+use crate::session::{list_mention_candidates, rank_mentions, MentionCandidate};
 use crate::util::names::{generate_branch_name, generate_workspace_name, get_git_username};
 use crate::web::error::WebError;
 use crate::web::handlers::sessions::SessionResponse;
 use crate::web::state::WebAppState;
 use crate::web::status_types::{PrStatusResponse, WorkspaceStatusResponse};
+use crate::web::SetupRun;
 
 /// Response for a single workspace.
 #[derive(Debug, Serialize)]
@@ -397,6 +399,15 @@ pub async fn archive_workspace(
     let mut archived_commit_sha = None;
 
     if let Some(base_path) = repo.base_path {
+        if let Err(err) = run_workspace_teardown(HookContext {
+            root_path: &base_path,
+            workspace_path: &workspace.path,
+            workspace_name: &workspace.name,
+            branch: &workspace.branch,
+        }) {
+            warnings.push(format!("Teardown hook failed: {}", err));
+        }
+
         match worktree_manager.get_branch_sha(
             settings.mode,
             &base_path,
@@ -453,6 +464,7 @@ pub async fn archive_workspace(
     }
 
     state.status_manager().remove_workspace(id);
+    state.workspace_setups().remove(id);
 
     if !warnings.is_empty() {
         tracing::warn!(
@@ -576,9 +588,56 @@ pub async fn auto_create_workspace(
         .register_workspace(workspace.id, workspace.path.clone());
     state.status_manager().refresh_workspace(workspace.id);
 
+    // Agents for the workspace wait until the repository's setup hooks succeed
+    if let Err(err) = state.workspace_setups().start(&workspace, repo_path) {
+        tracing::warn!(workspace_id = %workspace.id, error = %err, "Workspace setup not started");
+    }
+
     Ok((StatusCode::CREATED, Json(response)))
 }
 
+/// Get the latest setup hook run for a workspace.
+pub async fn get_workspace_setup(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SetupRun>, WebError> {
+    state
+        .workspace_setups()
+        .get(id)
+        .map(Json)
+        .ok_or_else(|| WebError::NotFound(format!("No setup run for workspace {}", id)))
+}
+
+/// Run a workspace's setup hooks again, e.g. after a failure.
+pub async fn run_workspace_setup(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, WebError> {
+    let core = state.core().await;
+    let workspace = core
+        .workspace_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?
+        .get_by_id(id)
+        .map_err(|e| WebError::Internal(format!("Failed to get workspace: {}", e)))?
+        .ok_or_else(|| WebError::NotFound(format!("Workspace {} not found", id)))?;
+    let repo_path = core
+        .repo_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?
+        .get_by_id(workspace.repository_id)
+        .map_err(|e| WebError::Internal(format!("Failed to get repository: {}", e)))?
+        .and_then(|repo| repo.base_path)
+        .ok_or_else(|| WebError::BadRequest("Repository has no base path".to_string()))?;
+
+    match state.workspace_setups().start(&workspace, repo_path) {
+        Ok(true) => Ok(StatusCode::ACCEPTED),
+        Ok(false) => Err(WebError::BadRequest(format!(
+            "Repository has no setup hooks in {}",
+            HOOKS_FILE
+        ))),
+        Err(err) => Err(WebError::BadRequest(err)),
+    }
+}
+
 /// Get workspace git status and PR info.
 pub async fn get_workspace_status(
     State(state): State<WebAppState>,
@@ -826,3 +885,70 @@ pub async fn read_workspace_file(
         exists: true,
     }))
 }
+
+/// Default and maximum number of mention completions returned.
+const DEFAULT_MENTION_LIMIT: usize = 50;
+const MAX_MENTION_LIMIT: usize = 500;
+
+/// Query for `@` mention completion.
+#[derive(Debug, Deserialize, Default)]
+pub struct MentionQuery {
+    /// Text typed after the `@`
+    #[serde(default)]
+    pub q: String,
+    pub limit: Option<usize>,
+}
+
+/// A completion for an `@` mention.
+#[derive(Debug, Serialize)]
+pub struct MentionResponse {
+    #[serde(flatten)]
+    pub candidate: MentionCandidate,
+    /// Text to insert into the prompt
+    pub token: String,
+}
+
+/// Response for mention completion.
+#[derive(Debug, Serialize)]
+pub struct ListMentionsResponse {
+    pub mentions: Vec<MentionResponse>,
+}
+
+/// Complete an `@` mention with the workspace's tracked files and symbols,
+/// best matches first.
+pub async fn list_workspace_mentions(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<MentionQuery>,
+) -> Result<Json<ListMentionsResponse>, WebError> {
+    let workspace_path = {
+        let core = state.core().await;
+        core.workspace_store()
+            .ok_or_else(|| WebError::Internal("Database not available".to_string()))?
+            .get_by_id(id)
+            .map_err(|e| WebError::Internal(format!("Failed to get workspace: {}", e)))?
+            .ok_or_else(|| WebError::NotFound(format!("Workspace {} not found", id)))?
+            .path
+    };
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_MENTION_LIMIT)
+        .min(MAX_MENTION_LIMIT);
+    let candidates = tokio::task::spawn_blocking(move || list_mention_candidates(&workspace_path))
+        .await
+        .map_err(|e| WebError::Internal(format!("Mention listing failed: {}", e)))?
+        .map_err(WebError::BadRequest)?;
+
+    let mentions = rank_mentions(&candidates, &query.q, limit)
+        .into_iter()
+        .map(|index| {
+            let candidate = candidates[index].clone();
+            MentionResponse {
+                token: candidate.token(),
+                candidate,
+            }
+        })
+        .collect();
+    Ok(Json(ListMentionsResponse { mentions }))
+}
@@ -12,6 +12,7 @@ mod server;
 mod state;
 mod status_manager;
 mod status_types;
+mod workspace_setup;
 pub mod ws;
 
 pub use error::WebError;
@@ -19,4 +20,5 @@ pub use server::{run_server, ServerConfig};
 pub use state::WebAppState;
 pub use status_manager::{StatusManager, StatusManagerConfig};
 pub use status_types::{GitDiffStatsResponse, PrStatusResponse, WorkspaceStatusResponse};
+pub use workspace_setup::{SetupRun, SetupRunStatus, WorkspaceSetupTracker};
 pub use ws::{ClientMessage, ServerMessage, SessionManager};
@@ -6,8 +6,8 @@ use axum::{
 };
 
 use crate::web::handlers::{
-    bootstrap, external_sessions, models, onboarding, queue, repositories, sessions, themes,
-    ui_state, workspaces,
+    bootstrap, external_sessions, models, onboarding, queue, repositories, schedules, sessions,
+    themes, ui_state, usage, workspaces,
 };
 use crate::web::state::WebAppState;
 
@@ -77,6 +77,18 @@ pub fn api_routes() -> Router<WebAppState> {
             "/workspaces/{id}/files/read",
             post(workspaces::read_workspace_file),
         )
+        .route(
+            "/workspaces/{id}/mentions",
+            get(workspaces::list_workspace_mentions),
+        )
+        .route(
+            "/workspaces/{id}/setup",
+            get(workspaces::get_workspace_setup),
+        )
+        .route(
+            "/workspaces/{id}/setup",
+            post(workspaces::run_workspace_setup),
+        )
         // Session routes
         .route("/sessions", get(sessions::list_sessions))
         .route("/sessions", post(sessions::create_session))
@@ -85,6 +97,7 @@ pub fn api_routes() -> Router<WebAppState> {
         .route("/sessions/{id}", delete(sessions::close_session))
         .route("/sessions/{id}/events", get(sessions::get_session_events))
         .route("/sessions/{id}/history", get(sessions::get_session_history))
+        .route("/sessions/{id}/export", get(sessions::export_session))
         .route("/sessions/{id}/fork", post(sessions::fork_session))
         .route("/sessions/{id}/queue", get(queue::list_queue))
         .route("/sessions/{id}/queue", post(queue::add_queue_message))
@@ -96,6 +109,13 @@ pub fn api_routes() -> Router<WebAppState> {
             "/sessions/{id}/queue/{message_id}",
             delete(queue::delete_queue_message),
         )
+        // Scheduled prompt routes
+        .route("/schedules", get(schedules::list_schedules))
+        .route("/schedules", post(schedules::create_schedule))
+        .route("/schedules/{id}", get(schedules::get_schedule))
+        .route("/schedules/{id}", patch(schedules::update_schedule))
+        .route("/schedules/{id}", delete(schedules::delete_schedule))
+        .route("/schedules/{id}/run", post(schedules::run_schedule))
         // Onboarding routes
         .route("/onboarding/base-dir", get(onboarding::get_base_dir))
         .route("/onboarding/base-dir", post(onboarding::set_base_dir))
@@ -120,4 +140,6 @@ pub fn api_routes() -> Router<WebAppState> {
         // UI state routes
         .route("/ui/state", get(ui_state::get_ui_state))
         .route("/ui/state", post(ui_state::update_ui_state))
+        // Usage ledger routes
+        .route("/usage", get(usage::get_usage))
 }
@@ -63,7 +63,7 @@ struct AgentsResponse {
 #[derive(Serialize)]
 struct AgentInfo {
     id: &'static str,
-    name: &'static str,
+    name: String,
     available: bool,
 }
 
@@ -78,24 +78,32 @@ async fn list_agents(State(state): State<WebAppState>) -> Json<AgentsResponse> {
         agents: vec![
             AgentInfo {
                 id: "codex",
-                name: "Codex CLI",
+                name: "Codex CLI".to_string(),
                 available: tools.is_available(Tool::Codex),
             },
             AgentInfo {
                 id: "claude",
-                name: "Claude Code",
+                name: "Claude Code".to_string(),
                 available: tools.is_available(Tool::Claude),
             },
             AgentInfo {
                 id: "gemini",
-                name: "Gemini CLI",
+                name: "Gemini CLI".to_string(),
                 available: tools.is_available(Tool::Gemini),
             },
             AgentInfo {
                 id: "opencode",
-                name: "OpenCode",
+                name: "OpenCode".to_string(),
                 available: tools.is_available(Tool::Opencode),
             },
+            AgentInfo {
+                id: "acp",
+                name: core
+                    .config()
+                    .agent_display_name(crate::agent::AgentType::Acp)
+                    .to_string(),
+                available: tools.is_available(Tool::Acp),
+            },
         ],
     })
 }
@@ -146,6 +154,7 @@ fn build_router(state: WebAppState, cors_permissive: bool) -> Router {
 pub async fn run_server(state: WebAppState, config: ServerConfig) -> anyhow::Result<()> {
     let addr: SocketAddr = format!("{}:{}", config.host, config.port).parse()?;
     state.start_status_manager().await;
+    state.start_scheduler();
     let app = build_router(state, config.cors_permissive);
 
     tracing::info!("Starting web server at http://{}", addr);
@@ -1,12 +1,20 @@
 //! Web application state for the Conduit web server.
 
 use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
 use tokio::sync::RwLock;
 
+use crate::core::services::{ScheduleDelivery, ScheduleService, ServiceError};
 use crate::core::ConduitCore;
+use crate::data::ScheduledPrompt;
 
 use super::ws::SessionManager;
-use super::{StatusManager, StatusManagerConfig};
+use super::{StatusManager, StatusManagerConfig, WorkspaceSetupTracker};
+
+/// How often the server checks for due scheduled prompts
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(30);
 
 /// Shared state for the web application.
 ///
@@ -20,6 +28,8 @@ pub struct WebAppState {
     session_manager: Arc<SessionManager>,
     /// Background workspace status manager.
     status_manager: Arc<StatusManager>,
+    /// Setup hook runs for new workspaces.
+    workspace_setups: Arc<WorkspaceSetupTracker>,
 }
 
 impl WebAppState {
@@ -27,12 +37,15 @@ impl WebAppState {
     pub fn new(core: ConduitCore) -> Self {
         let status_config = StatusManagerConfig::from_config(core.config());
         let inner = Arc::new(RwLock::new(core));
-        let session_manager = Arc::new(SessionManager::new(inner.clone()));
+        let workspace_setups = Arc::new(WorkspaceSetupTracker::new());
+        let session_manager =
+            Arc::new(SessionManager::new(inner.clone(), workspace_setups.clone()));
         let status_manager = Arc::new(StatusManager::new(status_config));
         Self {
             inner,
             session_manager,
             status_manager,
+            workspace_setups,
         }
     }
 
@@ -56,6 +69,11 @@ impl WebAppState {
         &self.status_manager
     }
 
+    /// Get the setup hook runs for new workspaces.
+    pub fn workspace_setups(&self) -> &Arc<WorkspaceSetupTracker> {
+        &self.workspace_setups
+    }
+
     /// Kick the initial status scan for all workspaces.
     pub async fn start_status_manager(&self) {
         let core = self.core().await;
@@ -77,4 +95,108 @@ impl WebAppState {
 
         self.status_manager.kick_initial_scan(workspaces);
     }
+
+    /// Run due scheduled prompts every `SCHEDULER_INTERVAL`.
+    pub fn start_scheduler(&self) {
+        let state = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(SCHEDULER_INTERVAL);
+            loop {
+                ticker.tick().await;
+                let now = Utc::now();
+                let claimed = ScheduleService::claim_due(&*state.core().await, now);
+                match claimed {
+                    Ok(claimed) => {
+                        if !claimed.is_empty() {
+                            tracing::info!(count = claimed.len(), "Running scheduled prompts");
+                        }
+                        for entry in &claimed {
+                            // Failures are logged and recorded on the entry
+                            let _ = state.run_schedule(entry, now).await;
+                        }
+                    }
+                    Err(err) => tracing::warn!(error = %err, "Failed to run scheduled prompts"),
+                }
+            }
+        });
+    }
+
+    /// Queue a scheduled prompt and start the session's agent on it when
+    /// idle, recording the run as failed if either step fails.
+    ///
+    /// A workspace created for the run gets its setup hooks first; the prompt
+    /// stays queued and starts once setup succeeds.
+    pub async fn run_schedule(
+        &self,
+        entry: &ScheduledPrompt,
+        ran_at: DateTime<Utc>,
+    ) -> Result<ScheduleDelivery, ServiceError> {
+        let delivery = ScheduleService::deliver(&*self.core().await, entry);
+        let result = match delivery {
+            Ok(delivery) if delivery.created_workspace && self.start_setup(&delivery).await => {
+                self.start_after_setup(delivery);
+                Ok(delivery)
+            }
+            Ok(delivery) => self
+                .session_manager
+                .start_queued_prompt(delivery.session_id)
+                .await
+                .map(|_| delivery)
+                .map_err(ServiceError::Internal),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = &result {
+            tracing::warn!(schedule_id = %entry.id, error = %err, "Scheduled prompt failed");
+        }
+        let error = result.as_ref().err().map(|e| e.to_string());
+        ScheduleService::record_run(&*self.core().await, entry.id, ran_at, error.as_deref());
+        result
+    }
+
+    /// Start setup hooks for a workspace created by a scheduled run.
+    /// Returns false when there is nothing to wait for.
+    async fn start_setup(&self, delivery: &ScheduleDelivery) -> bool {
+        let core = self.core().await;
+        let Some(workspace) = core
+            .workspace_store()
+            .and_then(|store| store.get_by_id(delivery.workspace_id).ok().flatten())
+        else {
+            return false;
+        };
+        let Some(root_path) = core
+            .repo_store()
+            .and_then(|store| store.get_by_id(workspace.repository_id).ok().flatten())
+            .and_then(|repo| repo.base_path)
+        else {
+            return false;
+        };
+        drop(core);
+        self.status_manager
+            .register_workspace(workspace.id, workspace.path.clone());
+        match self.workspace_setups.start(&workspace, root_path) {
+            Ok(started) => started,
+            Err(err) => {
+                tracing::warn!(workspace_id = %workspace.id, error = %err, "Workspace setup not started");
+                false
+            }
+        }
+    }
+
+    /// Start the queued prompt once the workspace's setup succeeds.
+    fn start_after_setup(&self, delivery: ScheduleDelivery) {
+        let state = self.clone();
+        tokio::spawn(async move {
+            if let Err(reason) = state.workspace_setups.wait(delivery.workspace_id).await {
+                tracing::warn!(workspace_id = %delivery.workspace_id, %reason, "Scheduled prompt held");
+                return;
+            }
+            if let Err(err) = state
+                .session_manager
+                .start_queued_prompt(delivery.session_id)
+                .await
+            {
+                tracing::warn!(session_id = %delivery.session_id, error = %err, "Scheduled prompt failed");
+            }
+        });
+    }
 }
@@ -0,0 +1,168 @@
+//! Setup hook runs for workspaces created through the web API.
+//!
+//! Runs `.conduit/hooks.toml` setup in the background, keeps its output for
+//! the `/workspaces/{id}/setup` endpoint, and tells the WebSocket handler to
+//! hold off starting agents until setup succeeds.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::data::Workspace;
+use crate::git::{HookContext, WorkspaceHooks};
+
+/// Output lines kept per workspace
+const MAX_OUTPUT_LINES: usize = 500;
+
+/// How often `wait` checks on a running setup
+const SETUP_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SetupRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// One workspace's latest setup run
+#[derive(Debug, Clone, Serialize)]
+pub struct SetupRun {
+    pub status: SetupRunStatus,
+    pub output: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Setup runs by workspace
+#[derive(Debug, Default)]
+pub struct WorkspaceSetupTracker {
+    runs: RwLock<HashMap<Uuid, SetupRun>>,
+}
+
+impl WorkspaceSetupTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start the repository's setup hooks for a workspace in the background.
+    /// Returns false when the repository has nothing to set up.
+    pub fn start(
+        self: &Arc<Self>,
+        workspace: &Workspace,
+        root_path: PathBuf,
+    ) -> Result<bool, String> {
+        let hooks = match WorkspaceHooks::load(&root_path)? {
+            Some(hooks) if hooks.has_setup() => hooks,
+            _ => {
+                self.write().remove(&workspace.id);
+                return Ok(false);
+            }
+        };
+        {
+            let mut runs = self.write();
+            if runs
+                .get(&workspace.id)
+                .is_some_and(|run| run.status == SetupRunStatus::Running)
+            {
+                return Err("Workspace setup is already running".to_string());
+            }
+            runs.insert(
+                workspace.id,
+                SetupRun {
+                    status: SetupRunStatus::Running,
+                    output: Vec::new(),
+                    error: None,
+                },
+            );
+        }
+
+        let tracker = Arc::clone(self);
+        let workspace = workspace.clone();
+        tokio::task::spawn_blocking(move || {
+            let ctx = HookContext {
+                root_path: &root_path,
+                workspace_path: &workspace.path,
+                workspace_name: &workspace.name,
+                branch: &workspace.branch,
+            };
+            let result = hooks.run_setup(ctx, |line| tracker.push_line(workspace.id, line));
+            tracker.finish(workspace.id, result);
+        });
+        Ok(true)
+    }
+
+    pub fn get(&self, workspace_id: Uuid) -> Option<SetupRun> {
+        self.read().get(&workspace_id).cloned()
+    }
+
+    /// Why prompts for the workspace must wait, if they must
+    pub fn blocking_reason(&self, workspace_id: Uuid) -> Option<String> {
+        match self.read().get(&workspace_id)?.status {
+            SetupRunStatus::Running => Some("Workspace setup is still running".to_string()),
+            SetupRunStatus::Failed => Some(format!(
+                "Workspace setup failed; run it again with POST /api/workspaces/{}/setup",
+                workspace_id
+            )),
+            SetupRunStatus::Succeeded => None,
+        }
+    }
+
+    /// Wait for the workspace's setup run to finish, returning why prompts
+    /// must still wait if it did not succeed
+    pub async fn wait(&self, workspace_id: Uuid) -> Result<(), String> {
+        while self
+            .get(workspace_id)
+            .is_some_and(|run| run.status == SetupRunStatus::Running)
+        {
+            tokio::time::sleep(SETUP_POLL_INTERVAL).await;
+        }
+        match self.blocking_reason(workspace_id) {
+            Some(reason) => Err(reason),
+            None => Ok(()),
+        }
+    }
+
+    pub fn remove(&self, workspace_id: Uuid) {
+        self.write().remove(&workspace_id);
+    }
+
+    fn push_line(&self, workspace_id: Uuid, line: String) {
+        if let Some(run) = self.write().get_mut(&workspace_id) {
+            if run.output.len() == MAX_OUTPUT_LINES {
+                run.output.remove(0);
+            }
+            run.output.push(line);
+        }
+    }
+
+    fn finish(&self, workspace_id: Uuid, result: Result<(), String>) {
+        if let Err(err) = &result {
+            tracing::warn!(%workspace_id, error = %err, "Workspace setup failed");
+        }
+        if let Some(run) = self.write().get_mut(&workspace_id) {
+            match result {
+                Ok(()) => run.status = SetupRunStatus::Succeeded,
+                Err(err) => {
+                    run.status = SetupRunStatus::Failed;
+                    run.error = Some(err);
+                }
+            }
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<Uuid, SetupRun>> {
+        self.runs
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<Uuid, SetupRun>> {
+        self.runs
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
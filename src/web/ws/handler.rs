@@ -1,6 +1,6 @@
 //! WebSocket connection handler for real-time agent communication.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -12,13 +12,25 @@ use futures::{SinkExt, StreamExt};
 use tokio::sync::{broadcast, mpsc, RwLock};
 use uuid::Uuid;
 
-use crate::agent::events::AgentEvent;
-use crate::agent::runner::{AgentInput, AgentRunner, AgentStartConfig, AgentType};
+use crate::agent::event_hooks::{
+    run_event_hook, EventHook, EventHookContext, EventHooks, EVENT_HOOK_FAILED, EVENT_HOOK_FEEDBACK,
+};
+use crate::agent::events::{AgentEvent, ControlRequestEvent, ErrorEvent, TokenUsage};
+use crate::agent::runner::{AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType};
 use crate::agent::session::SessionId;
-use crate::core::services::{SessionService, UpdateSessionParams};
+use crate::agent::supervisor::{self, ExitKind, ProcessExit};
+use crate::agent::tool_approval::{self, ToolApprovalChoice};
+use crate::config::NotificationEvent;
+use crate::core::notifications::{self, Notification};
+use crate::core::services::{
+    BudgetService, BudgetStatus, InFlightUsage, SessionService, TurnUsageContext,
+    UpdateSessionParams, UsageService,
+};
 use crate::core::ConduitCore;
+use crate::session::{expand_mentions, MentionExpansion};
 use crate::ui::app_prompt;
 use crate::util::{generate_title_and_branch, get_git_username, sanitize_branch_suffix};
+use crate::web::WorkspaceSetupTracker;
 use serde_json::json;
 
 use super::messages::{ClientMessage, ImageAttachment, ServerMessage};
@@ -26,18 +38,26 @@ use super::messages::{ClientMessage, ImageAttachment, ServerMessage};
 /// Active session state tracked by the WebSocket handler.
 struct ActiveSession {
     agent_type: AgentType,
+    /// Workspace directory `@` mentions in follow-up input resolve against
+    working_dir: Option<PathBuf>,
     /// Process ID for stopping the agent
     pid: Option<u32>,
     /// Sender to broadcast events to all subscribers
     event_tx: broadcast::Sender<AgentEvent>,
     /// Input sender for sending follow-up messages
     input_tx: Option<mpsc::Sender<AgentInput>>,
+    /// Claude tool calls waiting on an approval card, keyed by request ID
+    pending_approvals: HashMap<String, ControlRequestEvent>,
 }
 
 /// Manages active agent sessions and their event streams.
 pub struct SessionManager {
     sessions: Arc<RwLock<HashMap<Uuid, ActiveSession>>>,
+    /// Tools allowed for the rest of a session, kept across agent restarts
+    allowed_tools: Arc<RwLock<HashMap<Uuid, HashSet<String>>>>,
     core: Arc<RwLock<ConduitCore>>,
+    /// Agents wait for a new workspace's setup hooks to succeed
+    workspace_setups: Arc<WorkspaceSetupTracker>,
 }
 
 struct StartSessionArgs {
@@ -85,6 +105,208 @@ async fn persist_agent_session_id(
     Ok(())
 }
 
+fn usage_context(session_id: Uuid, tab: &crate::data::SessionTab) -> TurnUsageContext {
+    TurnUsageContext {
+        session_id: Some(session_id),
+        agent_session_id: tab.agent_session_id.clone(),
+        agent_type: tab.agent_type,
+        model: tab.model.clone(),
+        workspace_id: tab.workspace_id,
+    }
+}
+
+fn load_usage_context(core: &ConduitCore, session_id: Uuid) -> Result<TurnUsageContext, String> {
+    let tab = SessionService::get_session(core, session_id)
+        .map_err(|e| format!("Failed to get session {}: {}", session_id, e))?;
+    Ok(usage_context(session_id, &tab))
+}
+
+fn record_turn_usage(
+    core: &ConduitCore,
+    session_id: Uuid,
+    usage: &TokenUsage,
+) -> Result<TurnUsageContext, String> {
+    let context = load_usage_context(core, session_id)?;
+    UsageService::record_turn(core, context.clone(), usage)
+        .map_err(|e| format!("Failed to record usage: {}", e))?;
+    Ok(context)
+}
+
+/// Error event refusing a prompt while a hard budget limit is reached.
+fn budget_block_event(core: &ConduitCore, context: &TurnUsageContext) -> Option<AgentEvent> {
+    let status = BudgetService::status(core, context, None);
+    if status.is_exceeded() {
+        budget_event(status)
+    } else {
+        None
+    }
+}
+
+/// Error event explaining a budget warning or hard stop to the client.
+fn budget_event(status: BudgetStatus) -> Option<AgentEvent> {
+    let code = match status {
+        BudgetStatus::Ok => return None,
+        BudgetStatus::Warning(_) => "budget_warning",
+        BudgetStatus::Exceeded(_) => "budget_exceeded",
+    };
+    Some(AgentEvent::Error(ErrorEvent {
+        message: status.message()?,
+        is_fatal: false,
+        code: Some(code.to_string()),
+        details: None,
+    }))
+}
+
+/// Start a crashed agent again, resuming its session with the continuation prompt
+async fn resume_crashed_agent(
+    runner: &Arc<dyn AgentRunner>,
+    base: &AgentStartConfig,
+    agent_type: AgentType,
+    agent_session_id: SessionId,
+) -> Result<AgentHandle, String> {
+    let mut config = base.clone().with_resume(agent_session_id);
+    config.images.clear();
+    if agent_type == AgentType::Claude {
+        config.prompt = String::new();
+        config.stdin_payload = Some(build_claude_prompt_jsonl(supervisor::RESUME_PROMPT, &[])?);
+    } else {
+        config.prompt = supervisor::RESUME_PROMPT.to_string();
+        config.stdin_payload = None;
+    }
+    runner
+        .start(config)
+        .await
+        .map_err(|e| format!("Failed to restart agent: {}", e))
+}
+
+fn notice_event(message: String, code: Option<&str>) -> AgentEvent {
+    AgentEvent::Error(ErrorEvent {
+        message,
+        is_fatal: false,
+        code: code.map(str::to_string),
+        details: None,
+    })
+}
+
+/// Run the hooks matching an agent event in the background. Failures and
+/// output meant for the agent go out as notices; the client decides whether
+/// to send the output on as a follow-up prompt.
+fn spawn_event_hooks(
+    hooks: &EventHooks,
+    event: &AgentEvent,
+    ctx: &EventHookContext,
+    event_tx: &broadcast::Sender<AgentEvent>,
+) {
+    let matching: Vec<EventHook> = hooks.matching(event).cloned().collect();
+    if matching.is_empty() {
+        return;
+    }
+    let event_json = match serde_json::to_string(event) {
+        Ok(json) => json,
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to serialize agent event for hooks");
+            return;
+        }
+    };
+    for hook in matching {
+        let ctx = ctx.clone();
+        let event_json = event_json.clone();
+        let event_tx = event_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let run = run_event_hook(&hook, &event_json, &ctx);
+            let notice = if hook.feeds_back(&run) {
+                AgentEvent::Error(ErrorEvent {
+                    message: format!("Hook `{}` has output for the agent", hook.command),
+                    is_fatal: false,
+                    code: Some(EVENT_HOOK_FEEDBACK.to_string()),
+                    details: Some(json!({
+                        "command": hook.command,
+                        "event": hook.event,
+                        "prompt": run.feedback_prompt(&hook),
+                    })),
+                })
+            } else if let Some(failure) = &run.failure {
+                notice_event(
+                    format!(
+                        "Hook `{}` failed after {}: {}",
+                        hook.command, hook.event, failure
+                    ),
+                    Some(EVENT_HOOK_FAILED),
+                )
+            } else {
+                return;
+            };
+            if let Err(error) = event_tx.send(notice) {
+                tracing::debug!(error = %error, "No active subscribers for hook notices");
+            }
+        });
+    }
+}
+
+/// Send an attention notification for a web session through the command
+/// and webhook sinks
+fn spawn_notification(
+    core: Arc<RwLock<ConduitCore>>,
+    session_id: Uuid,
+    agent_type: AgentType,
+    event: NotificationEvent,
+    summary: String,
+) {
+    tokio::spawn(async move {
+        let (config, notification) = {
+            let core = core.read().await;
+            let config = core.config().notifications.clone();
+            if !config.notifies(event) {
+                return;
+            }
+            let mut notification = Notification::new(
+                event,
+                session_id,
+                core.config().agent_display_name(agent_type),
+            )
+            .with_summary(&summary);
+            if let Ok(tab) = SessionService::get_session(&core, session_id) {
+                notification.session_title = tab.title;
+                notification.workspace_id = tab.workspace_id;
+            }
+            let workspace = notification
+                .workspace_id
+                .and_then(|id| core.workspace_store()?.get_by_id(id).ok().flatten());
+            if let Some(workspace) = workspace {
+                notification.project_name = core
+                    .repo_store()
+                    .and_then(|store| store.get_by_id(workspace.repository_id).ok().flatten())
+                    .map(|repo| repo.name);
+                notification.workspace_name = Some(workspace.name);
+            }
+            (config, notification)
+        };
+        notifications::send_external(&config, &notification).await;
+    });
+}
+
+async fn send_claude_control_response(
+    input_tx: &mpsc::Sender<AgentInput>,
+    request_id: &str,
+    response: serde_json::Value,
+) -> Result<(), String> {
+    let payload = json!({
+        "type": "control_response",
+        "response": {
+            "subtype": "success",
+            "request_id": request_id,
+            "response": response,
+        }
+    });
+    let json_payload = serde_json::to_string(&payload)
+        .map_err(|e| format!("Failed to serialize control response: {}", e))?;
+
+    input_tx
+        .send(AgentInput::ClaudeJsonl(format!("{json_payload}\n")))
+        .await
+        .map_err(|e| format!("Failed to send control response: {}", e))
+}
+
 async fn append_input_history(
     core: &Arc<RwLock<ConduitCore>>,
     session_id: Uuid,
@@ -125,10 +347,15 @@ async fn persist_pending_user_message(
 }
 
 impl SessionManager {
-    pub fn new(core: Arc<RwLock<ConduitCore>>) -> Self {
+    pub fn new(
+        core: Arc<RwLock<ConduitCore>>,
+        workspace_setups: Arc<WorkspaceSetupTracker>,
+    ) -> Self {
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
+            allowed_tools: Arc::new(RwLock::new(HashMap::new())),
             core,
+            workspace_setups,
         }
     }
 
@@ -161,18 +388,20 @@ impl SessionManager {
 
         // Get the appropriate runner
         let core = self.core.read().await;
-        let runner: Arc<dyn AgentRunner> = match agent_type {
-            AgentType::Claude => core.claude_runner().clone(),
-            AgentType::Codex => core.codex_runner().clone(),
-            AgentType::Gemini => core.gemini_runner().clone(),
-            AgentType::Opencode => core.opencode_runner().clone(),
-        };
+        let runner: Arc<dyn AgentRunner> = core.runner_for(agent_type);
 
         if !runner.is_available() {
-            return Err(format!("{} is not available", agent_type.display_name()));
+            return Err(format!(
+                "{} is not available",
+                core.config().agent_display_name(agent_type)
+            ));
         }
 
         // Build start config
+        let hook_context = EventHookContext {
+            working_dir: working_dir.clone(),
+            session_id: session_id.to_string(),
+        };
         let mut config = AgentStartConfig::new(prompt, working_dir);
         if let Some(m) = model {
             config = config.with_model(m);
@@ -187,6 +416,27 @@ impl SessionManager {
             config = config.with_stdin_payload(payload);
         }
 
+        let session_tab = SessionService::get_session(&core, session_id).ok();
+        let workspace_id = session_tab
+            .as_ref()
+            .and_then(|session_tab| session_tab.workspace_id);
+        if agent_type == AgentType::Codex {
+            let (approval, sandbox) = core.codex_permissions_for_workspace(workspace_id);
+            let (session_approval, session_sandbox) = session_tab
+                .as_ref()
+                .map_or((None, None), |tab| (tab.codex_approval, tab.codex_sandbox));
+            config = config
+                .with_codex_permissions(session_approval.or(approval), session_sandbox.or(sandbox));
+        }
+        config = config.with_tool_policy(core.tool_policy_for_workspace(workspace_id));
+        let event_hooks = core.event_hooks_for_workspace(workspace_id);
+        if agent_type == AgentType::Claude || agent_type.uses_acp() {
+            let approval_policy = core.approval_policy_for_workspace(workspace_id);
+            config = config
+                .with_tools(core.claude_allowed_tools_for_workspace(workspace_id, approval_policy))
+                .with_approval_policy(approval_policy);
+        }
+
         if agent_type == AgentType::Opencode {
             match SessionService::get_session(&core, session_id) {
                 Ok(session_tab) => {
@@ -204,7 +454,8 @@ impl SessionManager {
             }
         }
 
-        // Start the agent
+        // Start the agent; the config is kept to resume it after a crash
+        let restart_config = config.clone();
         let mut handle = runner
             .start(config)
             .await
@@ -223,7 +474,8 @@ impl SessionManager {
             }
         }
 
-        let pid = handle.pid;
+        let mut pid = handle.pid;
+        let mut agent_session_id = handle.session_id.clone();
         let input_tx = handle.input_tx.take();
 
         // Reuse an existing event channel if we already have one (e.g. if the UI subscribed
@@ -237,6 +489,7 @@ impl SessionManager {
                     return Err(format!("Session {} is already running", session_id));
                 }
                 existing.agent_type = agent_type;
+                existing.working_dir = Some(restart_config.working_dir.clone());
                 existing.pid = Some(pid);
                 existing.input_tx = input_tx;
                 (existing.event_tx.clone(), existing.event_tx.subscribe())
@@ -246,9 +499,11 @@ impl SessionManager {
                     session_id,
                     ActiveSession {
                         agent_type,
+                        working_dir: Some(restart_config.working_dir.clone()),
                         pid: Some(pid),
                         event_tx: event_tx.clone(),
                         input_tx,
+                        pending_approvals: HashMap::new(),
                     },
                 );
                 (event_tx, event_rx)
@@ -257,10 +512,120 @@ impl SessionManager {
 
         // Spawn task to forward events from agent to broadcast channel
         let sessions_ref = self.sessions.clone();
+        let allowed_tools_ref = self.allowed_tools.clone();
         let core_ref = self.core.clone();
+        let interrupt_on_budget = core.config().budget.interrupts_running_turn();
+        let restart_policy = core.config().supervisor;
+        drop(core);
         tokio::spawn(async move {
-            while let Some(event) = handle.events.recv().await {
+            let mut in_flight_usage = InFlightUsage::default();
+            let mut in_flight_context: Option<TurnUsageContext> = None;
+            let mut budget_warning_sent = false;
+            let mut budget_stopped = false;
+            // The session starts with a prompt, so a turn is running
+            let mut turn_active = true;
+            let mut crash_restarts = 0;
+            while let Some(mut event) = handle.events.recv().await {
+                let mut budget_notice = None;
+                let mut crash_notice = None;
+                let mut restart_delay = None;
+                if let AgentEvent::ControlRequest(request) = &event {
+                    if matches!(agent_type, AgentType::Claude | AgentType::Codex)
+                        && !tool_approval::is_interactive_tool(&request.tool_name)
+                    {
+                        let allowed = allowed_tools_ref
+                            .read()
+                            .await
+                            .get(&session_id)
+                            .is_some_and(|tools| tools.contains(&request.tool_name));
+                        let input_tx = {
+                            let mut sessions = sessions_ref.write().await;
+                            let Some(active) = sessions.get_mut(&session_id) else {
+                                continue;
+                            };
+                            if !allowed {
+                                active
+                                    .pending_approvals
+                                    .insert(request.request_id.clone(), request.clone());
+                            }
+                            active.input_tx.clone()
+                        };
+                        // Tools allowed for the session never reach the client
+                        if let (true, Some(input_tx)) = (allowed, input_tx) {
+                            if agent_type == AgentType::Codex {
+                                let input = AgentInput::CodexApproval {
+                                    request_id: request.request_id.clone(),
+                                    choice: ToolApprovalChoice::AllowOnce,
+                                };
+                                if let Err(error) = input_tx.send(input).await {
+                                    tracing::warn!(%session_id, error = %error, "Failed to auto-approve Codex request");
+                                }
+                                continue;
+                            }
+                            let response = tool_approval::permission_response(
+                                request,
+                                ToolApprovalChoice::AllowOnce,
+                                None,
+                            );
+                            if let Err(error) = send_claude_control_response(
+                                &input_tx,
+                                &request.request_id,
+                                response,
+                            )
+                            .await
+                            {
+                                tracing::warn!(%session_id, error = %error, "Failed to auto-approve tool");
+                            }
+                            continue;
+                        }
+                    }
+                }
+                match &event {
+                    AgentEvent::TurnCompleted(_) => {
+                        turn_active = false;
+                        crash_restarts = 0;
+                    }
+                    AgentEvent::TurnFailed(_) => turn_active = false,
+                    AgentEvent::TurnStarted
+                    | AgentEvent::AssistantMessage(_)
+                    | AgentEvent::AssistantReasoning(_)
+                    | AgentEvent::ToolStarted(_) => turn_active = true,
+                    _ => {}
+                }
+                spawn_event_hooks(&event_hooks, &event, &hook_context, &event_tx);
+                let attention = match &event {
+                    AgentEvent::TurnCompleted(_) => Some((
+                        NotificationEvent::TurnCompleted,
+                        "Turn finished".to_string(),
+                    )),
+                    AgentEvent::TurnFailed(failed) => {
+                        Some((NotificationEvent::TurnFailed, failed.error.clone()))
+                    }
+                    // Auto-approved requests were answered above; questions
+                    // and plans are reported when their tool starts
+                    AgentEvent::ControlRequest(request)
+                        if !tool_approval::is_interactive_tool(&request.tool_name) =>
+                    {
+                        Some((
+                            NotificationEvent::InputNeeded,
+                            format!("{} is waiting for you", request.tool_name),
+                        ))
+                    }
+                    AgentEvent::ToolStarted(tool)
+                        if tool_approval::is_interactive_tool(&tool.tool_name) =>
+                    {
+                        Some((
+                            NotificationEvent::InputNeeded,
+                            format!("{} is waiting for you", tool.tool_name),
+                        ))
+                    }
+                    _ => None,
+                };
+                if let Some((kind, summary)) = attention {
+                    spawn_notification(core_ref.clone(), session_id, agent_type, kind, summary);
+                }
                 if let AgentEvent::SessionInit(init) = &event {
+                    agent_session_id = Some(init.session_id.clone());
                     if let Err(error) =
                         persist_agent_session_id(&core_ref, session_id, init.session_id.as_str())
                             .await
@@ -273,7 +638,127 @@ impl SessionManager {
                         );
                     }
                 }
-                if let AgentEvent::Error(err) = &event {
+                if matches!(event, AgentEvent::TurnStarted) {
+                    in_flight_usage.start_turn();
+                }
+                // A turn stopped at the budget limit was recorded when it was
+                // stopped; a late completion must not count it again
+                let completed_turn = match &event {
+                    AgentEvent::TurnCompleted(completed) if !budget_stopped => Some(completed),
+                    _ => None,
+                };
+                if let Some(completed) = completed_turn {
+                    let core = core_ref.read().await;
+                    match record_turn_usage(&core, session_id, &completed.usage) {
+                        Ok(context) => {
+                            let status = BudgetService::status(&core, &context, None);
+                            match status {
+                                BudgetStatus::Ok => budget_warning_sent = false,
+                                BudgetStatus::Warning(_) if budget_warning_sent => {}
+                                BudgetStatus::Warning(_) => {
+                                    budget_warning_sent = true;
+                                    budget_notice = budget_event(status);
+                                }
+                                BudgetStatus::Exceeded(_) => budget_notice = budget_event(status),
+                            }
+                        }
+                        Err(error) => {
+                            tracing::warn!(
+                                %session_id,
+                                error = %error,
+                                "Failed to record turn usage"
+                            );
+                        }
+                    }
+                }
+                if let AgentEvent::TokenUsage(usage_event) = &event {
+                    let turn_usage = in_flight_usage.observe(&usage_event.usage);
+                    if interrupt_on_budget && !budget_stopped {
+                        let core = core_ref.read().await;
+                        if in_flight_context.is_none() {
+                            in_flight_context = load_usage_context(&core, session_id)
+                                .inspect_err(|error| {
+                                    tracing::warn!(
+                                        %session_id,
+                                        error = %error,
+                                        "Failed to load session for budget check"
+                                    );
+                                })
+                                .ok();
+                        }
+                        if let Some(context) = &in_flight_context {
+                            let status = BudgetService::status(&core, context, Some(&turn_usage));
+                            if status.is_exceeded() {
+                                // The stopped turn never completes, so record
+                                // what it used so far
+                                if let Err(error) =
+                                    UsageService::record_turn(&core, context.clone(), &turn_usage)
+                                {
+                                    tracing::warn!(
+                                        %session_id,
+                                        error = %error,
+                                        "Failed to record turn usage"
+                                    );
+                                }
+                                if let Err(error) = runner.stop(&handle).await {
+                                    tracing::warn!(
+                                        %session_id,
+                                        error = %error,
+                                        "Failed to stop agent at budget limit"
+                                    );
+                                }
+                                budget_stopped = true;
+                                budget_notice = budget_event(status);
+                            }
+                        }
+                    }
+                }
+                if let AgentEvent::Error(err) = &mut event {
+                    if let Some(exit) = ProcessExit::from_error(err) {
+                        let kind = exit.classify();
+                        // A stop request removes the session before the exit is reported
+                        let running = sessions_ref
+                            .read()
+                            .await
+                            .get(&session_id)
+                            .is_some_and(|active| active.pid == Some(pid));
+                        if matches!(kind, ExitKind::Crash | ExitKind::Unrecoverable)
+                            && turn_active
+                            && running
+                            && !budget_stopped
+                            && restart_policy.enabled
+                        {
+                            let attempt = crash_restarts + 1;
+                            restart_delay = restart_policy
+                                .next_delay(attempt)
+                                .filter(|_| kind.should_restart() && agent_session_id.is_some());
+                            let notice = match restart_delay {
+                                Some(delay) => {
+                                    crash_restarts = attempt;
+                                    // The turn goes on once the agent is resumed
+                                    err.is_fatal = false;
+                                    supervisor::restart_notice(
+                                        agent_type,
+                                        &exit,
+                                        attempt,
+                                        &restart_policy,
+                                        delay,
+                                    )
+                                }
+                                None => {
+                                    let notice = supervisor::give_up_notice(
+                                        agent_type,
+                                        kind,
+                                        crash_restarts,
+                                    );
+                                    crash_restarts = 0;
+                                    notice
+                                }
+                            };
+                            let code = restart_delay.map(|_| supervisor::AGENT_RESTARTING);
+                            crash_notice = Some(notice_event(notice, code));
+                        }
+                    }
                     if err.code.as_deref() == Some("model_not_found") {
                         let core = core_ref.read().await;
                         if let Err(error) =
@@ -288,12 +773,68 @@ impl SessionManager {
                     }
                 }
 
-                if let Err(error) = event_tx.send(event) {
-                    tracing::debug!(
+                // Budget notices go out first so clients hold queued prompts
+                // before the turn's completion lets them drain the queue.
+                for event in budget_notice
+                    .into_iter()
+                    .chain(std::iter::once(event))
+                    .chain(crash_notice)
+                {
+                    if let Err(error) = event_tx.send(event) {
+                        tracing::debug!(
+                            %session_id,
+                            error = %error,
+                            "No active subscribers for agent events"
+                        );
+                    }
+                }
+
+                if let (Some(delay), Some(resume_id)) = (restart_delay, agent_session_id.clone()) {
+                    tokio::time::sleep(delay).await;
+                    let still_ours = sessions_ref
+                        .read()
+                        .await
+                        .get(&session_id)
+                        .is_some_and(|active| active.pid == Some(pid));
+                    if !still_ours {
+                        // Stopped during the backoff; the entry is gone or belongs to a new run
+                        return;
+                    }
+                    tracing::info!(
                         %session_id,
-                        error = %error,
-                        "No active subscribers for agent events"
+                        attempt = crash_restarts,
+                        "Resuming agent session after crash"
                     );
+                    match resume_crashed_agent(&runner, &restart_config, agent_type, resume_id)
+                        .await
+                    {
+                        Ok(mut new_handle) => {
+                            pid = new_handle.pid;
+                            let input_tx = new_handle.input_tx.take();
+                            if let Some(active) = sessions_ref.write().await.get_mut(&session_id) {
+                                active.pid = Some(pid);
+                                active.input_tx = input_tx;
+                                active.pending_approvals.clear();
+                            }
+                            handle = new_handle;
+                        }
+                        Err(error) => {
+                            let event = AgentEvent::Error(ErrorEvent {
+                                message: error,
+                                is_fatal: true,
+                                code: None,
+                                details: None,
+                            });
+                            if let Err(error) = event_tx.send(event) {
+                                tracing::debug!(
+                                    %session_id,
+                                    error = %error,
+                                    "No active subscribers for agent events"
+                                );
+                            }
+                            break;
+                        }
+                    }
                 }
             }
             // Session ended, remove from map
@@ -341,9 +882,11 @@ impl SessionManager {
             session_id,
             ActiveSession {
                 agent_type: tab.agent_type,
+                working_dir: None,
                 pid: None,
                 event_tx,
                 input_tx: None,
+                pending_approvals: HashMap::new(),
             },
         );
 
@@ -435,7 +978,12 @@ impl SessionManager {
         // Send as appropriate input type based on agent
         let agent_input = match agent_type {
             AgentType::Claude => AgentInput::ClaudeJsonl(input),
-            AgentType::Codex | AgentType::Gemini | AgentType::Opencode => AgentInput::CodexPrompt {
+            // ACP input channels only carry permission responses; follow-up
+            // prompts start a new process.
+            AgentType::Gemini | AgentType::Acp => {
+                return Err("Session does not support input".to_string())
+            }
+            AgentType::Codex | AgentType::Opencode => AgentInput::CodexPrompt {
                 text: input,
                 images,
                 model,
@@ -457,7 +1005,7 @@ impl SessionManager {
         request_id: String,
         response: serde_json::Value,
     ) -> Result<(), String> {
-        let input_tx = {
+        let (input_tx, agent_type) = {
             let sessions = self.sessions.read().await;
             let session = sessions
                 .get(&session_id)
@@ -466,29 +1014,121 @@ impl SessionManager {
                 .input_tx
                 .clone()
                 .ok_or_else(|| "Session does not support control responses".to_string())?;
-            if session.agent_type != AgentType::Claude {
-                return Err("Control responses are only supported for Claude sessions".to_string());
-            }
-            input_tx
+            (input_tx, session.agent_type)
         };
 
-        let payload = json!({
-            "type": "control_response",
-            "response": {
-                "subtype": "success",
-                "request_id": request_id,
-                "response": response,
+        match agent_type {
+            AgentType::Claude => {
+                let response = match self
+                    .approval_response(session_id, &request_id, &response)
+                    .await?
+                {
+                    Some(approval) => approval,
+                    None => response,
+                };
+                send_claude_control_response(&input_tx, &request_id, response).await
             }
-        });
-        let json_payload = serde_json::to_string(&payload)
-            .map_err(|e| format!("Failed to serialize control response: {}", e))?;
+            AgentType::Gemini | AgentType::Acp => {
+                // ACP agents expect the selected permission option; a missing
+                // option_id cancels the tool call.
+                let option_id = response
+                    .get("option_id")
+                    .and_then(|value| value.as_str())
+                    .map(str::to_string);
+                input_tx
+                    .send(AgentInput::AcpPermission {
+                        request_id,
+                        option_id,
+                    })
+                    .await
+                    .map_err(|e| format!("Failed to send control response: {}", e))
+            }
+            AgentType::Codex => {
+                let choice = response
+                    .get("option_id")
+                    .and_then(|value| value.as_str())
+                    .and_then(|value| value.parse::<ToolApprovalChoice>().ok())
+                    .unwrap_or(ToolApprovalChoice::Deny);
+                if !choice.is_offered(AgentType::Codex) {
+                    return Err(format!(
+                        "\"{}\" is not available for Codex approvals",
+                        choice.label()
+                    ));
+                }
+                let request = self
+                    .sessions
+                    .write()
+                    .await
+                    .get_mut(&session_id)
+                    .and_then(|session| session.pending_approvals.remove(&request_id))
+                    .ok_or_else(|| format!("No approval pending for request {}", request_id))?;
+                if choice == ToolApprovalChoice::AllowSession {
+                    self.allowed_tools
+                        .write()
+                        .await
+                        .entry(session_id)
+                        .or_default()
+                        .insert(request.tool_name);
+                }
+                input_tx
+                    .send(AgentInput::CodexApproval { request_id, choice })
+                    .await
+                    .map_err(|e| format!("Failed to send control response: {}", e))
+            }
+            AgentType::Opencode => Err(
+                "Control responses are only supported for Claude, Codex and ACP sessions"
+                    .to_string(),
+            ),
+        }
+    }
 
-        input_tx
-            .send(AgentInput::ClaudeJsonl(format!("{json_payload}\n")))
+    /// Turn an approval card answer (`{"option_id": "allow_session"}`, with an
+    /// optional `message` for denials) into Claude's permission response.
+    /// Returns `None` for raw responses, which are sent unchanged.
+    async fn approval_response(
+        &self,
+        session_id: Uuid,
+        request_id: &str,
+        response: &serde_json::Value,
+    ) -> Result<Option<serde_json::Value>, String> {
+        let Some(choice) = response
+            .get("option_id")
+            .and_then(|value| value.as_str())
+            .and_then(|value| value.parse::<ToolApprovalChoice>().ok())
+        else {
+            return Ok(None);
+        };
+        let request = self
+            .sessions
+            .write()
             .await
-            .map_err(|e| format!("Failed to send control response: {}", e))?;
+            .get_mut(&session_id)
+            .and_then(|session| session.pending_approvals.remove(request_id))
+            .ok_or_else(|| format!("No tool approval pending for request {}", request_id))?;
+
+        if matches!(
+            choice,
+            ToolApprovalChoice::AllowSession | ToolApprovalChoice::AllowAlways
+        ) {
+            self.allowed_tools
+                .write()
+                .await
+                .entry(session_id)
+                .or_default()
+                .insert(request.tool_name.clone());
+        }
+        if choice == ToolApprovalChoice::AllowAlways {
+            let core = self.core.read().await;
+            let workspace_id = SessionService::get_session(&core, session_id)
+                .ok()
+                .and_then(|session_tab| session_tab.workspace_id);
+            core.allow_tool_for_workspace(workspace_id, &request.tool_name)?;
+        }
 
-        Ok(())
+        let reason = response.get("message").and_then(|value| value.as_str());
+        Ok(Some(tool_approval::permission_response(
+            &request, choice, reason,
+        )))
     }
 
     /// Get the agent type for a session.
@@ -496,6 +1136,99 @@ impl SessionManager {
         let sessions = self.sessions.read().await;
         sessions.get(&session_id).map(|s| s.agent_type)
     }
+
+    /// Get the working directory of a session.
+    pub async fn get_working_dir(&self, session_id: Uuid) -> Option<PathBuf> {
+        let sessions = self.sessions.read().await;
+        sessions
+            .get(&session_id)
+            .and_then(|s| s.working_dir.clone())
+    }
+
+    /// Start an idle session's agent on its first queued prompt, without a
+    /// client. Returns false when the agent is already running.
+    pub async fn start_queued_prompt(&self, session_id: Uuid) -> Result<bool, String> {
+        if self
+            .sessions
+            .read()
+            .await
+            .get(&session_id)
+            .is_some_and(|active| active.pid.is_some())
+        {
+            return Ok(false);
+        }
+
+        let core = self.core.read().await;
+        let session_tab =
+            SessionService::get_session(&core, session_id).map_err(|e| e.to_string())?;
+        let workspace_id = session_tab
+            .workspace_id
+            .ok_or_else(|| "Session has no workspace".to_string())?;
+        if let Some(reason) = self.workspace_setups.blocking_reason(workspace_id) {
+            return Err(reason);
+        }
+        let working_dir = core
+            .workspace_store()
+            .ok_or_else(|| "Database not available".to_string())?
+            .get_by_id(workspace_id)
+            .map_err(|e| format!("Failed to get workspace: {}", e))?
+            .ok_or_else(|| format!("Workspace {} not found", workspace_id))?
+            .path;
+        let Some(model) = session_tab
+            .model
+            .clone()
+            .filter(|_| !session_tab.model_invalid)
+        else {
+            return Err("Session has no model selected".to_string());
+        };
+        let status = BudgetService::status(&core, &usage_context(session_id, &session_tab), None);
+        if status.is_exceeded() {
+            return Err(status.message().unwrap_or_default());
+        }
+        let Some(message) = session_tab.queued_messages.first().cloned() else {
+            return Ok(false);
+        };
+        if !message.images.is_empty() {
+            return Err("Queued prompt has images; open the session to send it".to_string());
+        }
+
+        let agent_type = session_tab.agent_type;
+        let expansion = MentionExpansion::for_agent(core.config().agent_reads_files(agent_type));
+        drop(core);
+        let agent_prompt = expand_mentions(&message.text, &working_dir, expansion);
+        let (prompt, input_format, stdin_payload) = if agent_type == AgentType::Claude {
+            let payload = build_claude_prompt_jsonl(&agent_prompt, &[])?;
+            (
+                String::new(),
+                Some("stream-json".to_string()),
+                Some(payload),
+            )
+        } else {
+            (agent_prompt, None, None)
+        };
+
+        self.start_session(StartSessionArgs {
+            session_id,
+            agent_type,
+            prompt,
+            working_dir,
+            model: Some(model),
+            images: Vec::new(),
+            input_format,
+            stdin_payload,
+        })
+        .await?;
+
+        let core = self.core.read().await;
+        if let Err(error) = SessionService::remove_queue_message(&core, session_id, message.id) {
+            tracing::warn!(%session_id, error = %error, "Failed to remove started queued prompt");
+        }
+        drop(core);
+        if let Err(error) = append_input_history(&self.core, session_id, &message.text).await {
+            tracing::warn!(%session_id, error = %error, "Failed to persist input history");
+        }
+        Ok(true)
+    }
 }
 
 fn should_generate_title(hidden: bool, session: &crate::data::SessionTab) -> bool {
@@ -990,6 +1723,23 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                     }
                     continue;
                 };
+                if let Some(reason) = session_tab
+                    .workspace_id
+                    .and_then(|id| session_manager.workspace_setups.blocking_reason(id))
+                {
+                    if let Err(send_err) = tx
+                        .send(ServerMessage::session_error(session_id, reason))
+                        .await
+                    {
+                        tracing::debug!(
+                            %session_id,
+                            error = ?send_err,
+                            "Failed to send session error"
+                        );
+                        break 'ws_loop;
+                    }
+                    continue;
+                }
                 if session_tab.model_invalid || session_tab.model.is_none() {
                     if let Some(model_id) = model.clone() {
                         if let Err(error) = SessionService::update_session(
@@ -997,8 +1747,7 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                             session_id,
                             UpdateSessionParams {
                                 model: Some(model_id),
-                                agent_type: None,
-                                agent_mode: None,
+                                ..Default::default()
                             },
                         ) {
                             if let Err(send_err) = tx
@@ -1032,17 +1781,39 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                         continue;
                     }
                 }
+                if let Some(event) =
+                    budget_block_event(&core, &usage_context(session_id, &session_tab))
+                {
+                    if let Err(send_err) =
+                        tx.send(ServerMessage::agent_event(session_id, event)).await
+                    {
+                        tracing::debug!(
+                            %session_id,
+                            error = ?send_err,
+                            "Failed to send budget error"
+                        );
+                        break 'ws_loop;
+                    }
+                    continue;
+                }
                 let agent_type = session_tab.agent_type;
                 let should_generate = should_generate_title(hidden, &session_tab);
+                let mention_expansion =
+                    MentionExpansion::for_agent(core.config().agent_reads_files(agent_type));
                 drop(core);
 
                 let mut input_format: Option<String> = None;
                 let mut stdin_payload: Option<String> = None;
                 let working_dir_path = PathBuf::from(working_dir);
+                let agent_prompt = if hidden {
+                    prompt.clone()
+                } else {
+                    expand_mentions(&prompt, &working_dir_path, mention_expansion)
+                };
                 let prompt_for_agent = if agent_type == AgentType::Claude {
                     String::new()
                 } else {
-                    prompt.clone()
+                    agent_prompt.clone()
                 };
 
                 let image_paths = if images.is_empty() {
@@ -1067,7 +1838,7 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                             }
                         },
                         AgentType::Claude => {
-                            match build_claude_prompt_jsonl(&prompt, &images) {
+                            match build_claude_prompt_jsonl(&agent_prompt, &images) {
                                 Ok(payload) => {
                                     input_format = Some("stream-json".to_string());
                                     stdin_payload = Some(payload);
@@ -1089,11 +1860,14 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                             }
                             Vec::new()
                         }
-                        AgentType::Gemini => {
+                        AgentType::Gemini | AgentType::Acp => {
                             if let Err(send_err) = tx
                                 .send(ServerMessage::session_error(
                                     session_id,
-                                    "Image attachments are not supported for Gemini sessions",
+                                    format!(
+                                        "Image attachments are not supported for {} sessions",
+                                        agent_type.display_name()
+                                    ),
                                 ))
                                 .await
                             {
@@ -1127,7 +1901,7 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                 };
 
                 if agent_type == AgentType::Claude && stdin_payload.is_none() {
-                    match build_claude_prompt_jsonl(&prompt, &[]) {
+                    match build_claude_prompt_jsonl(&agent_prompt, &[]) {
                         Ok(payload) => {
                             input_format = Some("stream-json".to_string());
                             stdin_payload = Some(payload);
@@ -1285,6 +2059,7 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                 images,
             } => {
                 let agent_type = session_manager.get_agent_type(session_id).await;
+                let working_dir = session_manager.get_working_dir(session_id).await;
                 let core = session_manager.core.read().await;
                 let session_tab = match SessionService::get_session(&core, session_id) {
                     Ok(session) => session,
@@ -1320,9 +2095,33 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                     }
                     continue;
                 }
+                if let Some(event) =
+                    budget_block_event(&core, &usage_context(session_id, &session_tab))
+                {
+                    if let Err(send_err) =
+                        tx.send(ServerMessage::agent_event(session_id, event)).await
+                    {
+                        tracing::debug!(
+                            %session_id,
+                            error = ?send_err,
+                            "Failed to send budget error"
+                        );
+                        break 'ws_loop;
+                    }
+                    continue;
+                }
                 let model = session_tab.model.clone();
+                let mention_expansion = agent_type.map(|agent| {
+                    MentionExpansion::for_agent(core.config().agent_reads_files(agent))
+                });
                 drop(core);
-                let mut input_payload = input.clone();
+                let agent_input = match (&working_dir, mention_expansion) {
+                    (Some(dir), Some(expansion)) if !hidden => {
+                        expand_mentions(&input, dir, expansion)
+                    }
+                    _ => input.clone(),
+                };
+                let mut input_payload = agent_input.clone();
                 let image_paths = if images.is_empty() {
                     Vec::new()
                 } else {
@@ -1345,7 +2144,7 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                             }
                         },
                         Some(AgentType::Claude) => {
-                            match build_claude_prompt_jsonl(&input, &images) {
+                            match build_claude_prompt_jsonl(&agent_input, &images) {
                                 Ok(payload) => {
                                     input_payload = payload;
                                     Vec::new()
@@ -1366,11 +2165,14 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                                 }
                             }
                         }
-                        Some(AgentType::Gemini) => {
+                        Some(agent @ (AgentType::Gemini | AgentType::Acp)) => {
                             if let Err(send_err) = tx
                                 .send(ServerMessage::session_error(
                                     session_id,
-                                    "Image attachments are not supported for Gemini sessions",
+                                    format!(
+                                        "Image attachments are not supported for {} sessions",
+                                        agent.display_name()
+                                    ),
                                 ))
                                 .await
                             {
@@ -1405,7 +2207,7 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                 };
 
                 if matches!(agent_type, Some(AgentType::Claude)) && images.is_empty() {
-                    match build_claude_prompt_jsonl(&input, &[]) {
+                    match build_claude_prompt_jsonl(&agent_input, &[]) {
                         Ok(payload) => {
                             input_payload = payload;
                         }
@@ -63,7 +63,9 @@ pub enum ClientMessage {
         session_id: Uuid,
         /// The control request ID to respond to
         request_id: String,
-        /// Response payload for the control request
+        /// Response payload for the control request. Claude tool approval
+        /// cards answer with `{"option_id": "allow_once" | "allow_session" |
+        /// "allow_always" | "deny", "message"?: string}`.
         response: serde_json::Value,
     },
 
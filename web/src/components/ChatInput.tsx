@@ -21,7 +21,7 @@ interface ChatInputProps {
   notice?: string | null;
   // Session/workspace info for status line
   modelDisplayName?: string | null;
-  agentType?: 'claude' | 'codex' | 'gemini' | 'opencode' | null;
+  agentType?: 'claude' | 'codex' | 'gemini' | 'opencode' | 'acp' | null;
   agentMode?: string | null;
   gitStats?: { additions: number; deletions: number } | null;
   branch?: string | null;
@@ -409,7 +409,9 @@ export function ChatInput({
                   ? 'Codex CLI'
                   : agentType === 'opencode'
                     ? 'OpenCode'
-                    : 'Gemini CLI'}
+                    : agentType === 'acp'
+                      ? 'ACP Agent'
+                      : 'Gemini CLI'}
             </span>
           )}
           {!modelDisplayName && !agentType && !canChangeModel && (
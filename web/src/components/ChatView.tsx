@@ -5,7 +5,12 @@ import { ToolRunMessage } from './ToolRunMessage';
 import { SessionStatusIndicator } from './SessionStatusIndicator';
 import { ChatInput } from './ChatInput';
 import { QueuePanel } from './QueuePanel';
-import { InlinePrompt, type InlinePromptData, type InlinePromptResponse } from './InlinePrompt';
+import {
+  InlinePrompt,
+  type InlinePromptData,
+  type InlinePromptResponse,
+  type ToolPermissionOption,
+} from './InlinePrompt';
 import { RawEventsPanel } from './RawEventsPanel';
 import { ModelSelectorDialog } from './ModelSelectorDialog';
 import {
@@ -34,7 +39,7 @@ import type {
   QueuedMessage,
   ImageAttachment,
 } from '../types';
-import { MessageSquarePlus, Loader2, Bug, GitBranch, GitPullRequest } from 'lucide-react';
+import { AlertTriangle, MessageSquarePlus, Loader2, Bug, GitBranch, GitPullRequest } from 'lucide-react';
 import { cn } from '../lib/cn';
 
 interface ChatViewProps {
@@ -46,6 +51,13 @@ interface ChatViewProps {
 }
 
 const INLINE_PROMPT_TOOLS = new Set(['AskUserQuestion', 'ExitPlanMode']);
+// Same choices as the TUI approval card; the server maps them to Claude's response.
+const TOOL_APPROVAL_OPTIONS: ToolPermissionOption[] = [
+  { optionId: 'allow_once', name: 'Allow once', kind: 'allow_once' },
+  { optionId: 'allow_session', name: 'Allow for this session', kind: 'allow_session' },
+  { optionId: 'allow_always', name: 'Always allow in this repository', kind: 'allow_always' },
+  { optionId: 'deny', name: 'Deny', kind: 'reject_once', requiresReason: true },
+];
 const PROCESSING_WORDS = ['Thinking', 'Working', 'Whirring', 'Computing', 'Processing', 'Pondering', 'Mulling', 'Synthesizing', 'Calculating', 'Planning', 'Crafting', 'Noodling', 'Ruminating', 'Scheming', 'Tinkering', 'Brewing', 'Cooking', 'Conjuring', 'Hustling', 'Wandering'];
 const pickProcessingWord = () =>
   PROCESSING_WORDS[Math.floor(Math.random() * PROCESSING_WORDS.length)];
@@ -147,6 +159,49 @@ function parseExitPlan(args: unknown): string | null {
   return typeof plan === 'string' ? plan : null;
 }
 
+function parseToolPermissionOptions(input: unknown): ToolPermissionOption[] | null {
+  if (!input || typeof input !== 'object') return null;
+  const options = (input as { options?: unknown }).options;
+  if (!Array.isArray(options)) return null;
+  return options.flatMap((option) => {
+    if (!option || typeof option !== 'object') return [];
+    const { option_id, name, kind } = option as { option_id?: unknown; name?: unknown; kind?: unknown };
+    if (typeof option_id !== 'string') return [];
+    return [{
+      optionId: option_id,
+      name: typeof name === 'string' ? name : option_id,
+      kind: typeof kind === 'string' ? kind : '',
+    }];
+  });
+}
+
+function toolApprovalPreview(toolName: string, input: unknown): string | null {
+  if (!input || typeof input !== 'object') return null;
+  const fields = input as Record<string, unknown>;
+  const text = (key: string) => (typeof fields[key] === 'string' ? (fields[key] as string) : '');
+  const diff = (oldText: string, newText: string) => [
+    ...(oldText ? oldText.split('\n').map((line) => `- ${line}`) : []),
+    ...(newText ? newText.split('\n').map((line) => `+ ${line}`) : []),
+  ];
+  switch (toolName) {
+    case 'Bash':
+      return text('command') ? `$ ${text('command')}` : null;
+    case 'Edit':
+      return [text('file_path'), ...diff(text('old_string'), text('new_string'))].join('\n');
+    case 'Write':
+      return [text('file_path'), ...diff('', text('content'))].join('\n');
+    case 'ApplyPatch': {
+      const files = Array.isArray(fields.files) ? (fields.files as string[]) : [];
+      return [...(text('description') ? [`# ${text('description')}`] : []), ...files].join('\n');
+    }
+    default:
+      return (
+        ['file_path', 'path', 'url', 'pattern', 'query'].map(text).find(Boolean) ??
+        JSON.stringify(input, null, 2)
+      );
+  }
+}
+
 function buildPermissionAllowResponse(updatedInput: unknown, toolUseId?: string | null) {
   return {
     behavior: 'allow',
@@ -197,7 +252,8 @@ export function ChatView({
   const forceScrollToBottom = useRef(false);
   const scrollStateBySession = useRef<Record<string, { top: number; pinned: boolean }>>({});
   const scrollSessionId = useRef<string | null>(null);
-  const { sendPrompt, respondToControl, stopSession } = useWebSocket();
+  const { sendPrompt, respondToControl, stopSession, budgetBlockedSessions } = useWebSocket();
+  const budgetBlockReason = session ? budgetBlockedSessions.get(session.id) : undefined;
   const wsEvents = useSessionEvents(session?.id ?? null);
   const updateSessionMutation = useUpdateSession();
   const setDefaultModelMutation = useSetDefaultModel();
@@ -229,6 +285,7 @@ export function ChatView({
   const [hasInitiallyScrolled, setHasInitiallyScrolled] = useState(false);
   const [inlinePrompt, setInlinePrompt] = useState<InlinePromptData | null>(null);
   const [pendingControlResponse, setPendingControlResponse] = useState<unknown | null>(null);
+  const [answeredApprovals, setAnsweredApprovals] = useState<Set<string>>(() => new Set());
   const [showRawEvents, setShowRawEvents] = useState(false);
   const [showModelSelector, setShowModelSelector] = useState(false);
   const [escHint, setEscHint] = useState<string | null>(null);
@@ -409,7 +466,7 @@ export function ChatView({
     if (wsEvents.length === 0) return;
 
     const lastEvent = wsEvents[wsEvents.length - 1];
-    if (lastEvent.type === 'TurnStarted') {
+    if (lastEvent.type === 'TurnStarted' || (lastEvent.type === 'Error' && lastEvent.code === 'agent_restarting')) {
       setIsProcessing(true);
       setIsAwaitingResponse(false);
     } else if (
@@ -431,6 +488,7 @@ export function ChatView({
   useEffect(() => {
     setInlinePrompt(null);
     setPendingControlResponse(null);
+    setAnsweredApprovals(new Set());
     setShowRawEvents(false);
     setIsAwaitingResponse(false);
     setEscHint(null);
@@ -707,6 +765,20 @@ export function ChatView({
       }
     }
 
+    if (lastEvent.type === 'ControlRequest' && session.agent_type === 'gemini') {
+      const options = parseToolPermissionOptions(lastEvent.input);
+      if (options && inlinePrompt?.requestId !== lastEvent.request_id) {
+        setInlinePrompt({
+          type: 'tool_permission',
+          toolUseId: lastEvent.tool_use_id ?? lastEvent.request_id,
+          toolName: lastEvent.tool_name,
+          options,
+          requestId: lastEvent.request_id,
+        });
+        return;
+      }
+    }
+
     if (lastEvent.type === 'ControlRequest' && inlinePrompt) {
       if (lastEvent.tool_use_id && lastEvent.tool_use_id === inlinePrompt.toolUseId) {
         setInlinePrompt({ ...inlinePrompt, requestId: lastEvent.request_id });
@@ -861,6 +933,10 @@ export function ChatView({
         setShowModelSelector(true);
         return;
       }
+      if (budgetBlockReason) {
+        onNotify?.(budgetBlockReason, 'error');
+        return;
+      }
       if (queued.text.trim().length > 0) {
         setOptimisticMessages((prev) => ({
           ...prev,
@@ -905,7 +981,7 @@ export function ChatView({
         }
       );
     },
-    [session, workspace, onNotify, deleteQueueMutation, sendPrompt]
+    [session, workspace, onNotify, budgetBlockReason, deleteQueueMutation, sendPrompt]
   );
 
   const autoQueueInFlightRef = useRef<string | null>(null);
@@ -922,6 +998,7 @@ export function ChatView({
       return;
     }
     if (session.model_invalid || !session.model) return;
+    if (budgetBlockReason) return;
     const nextQueued = queuedMessages[0];
     if (autoQueueInFlightRef.current === nextQueued.id) return;
     autoQueueInFlightRef.current = nextQueued.id;
@@ -932,6 +1009,7 @@ export function ChatView({
     queuedMessages,
     session,
     workspace,
+    budgetBlockReason,
     handleSendQueued,
   ]);
 
@@ -950,7 +1028,48 @@ export function ChatView({
     setDrafts((prev) => ({ ...prev, [session.id]: value }));
   };
 
+  // Claude tool calls and Codex approval requests waiting on an approval card,
+  // oldest first. Derived from the event list so parallel tool calls queue up
+  // instead of replacing each other.
+  const pendingApproval = useMemo<InlinePromptData | null>(() => {
+    const agentType = session?.agent_type;
+    if (agentType !== 'claude' && agentType !== 'codex') return null;
+    // Codex has no repository allow list, so it can't be always allowed
+    const options =
+      agentType === 'codex'
+        ? TOOL_APPROVAL_OPTIONS.filter((option) => option.optionId !== 'allow_always')
+        : TOOL_APPROVAL_OPTIONS;
+    const completed = new Set(
+      wsEvents.flatMap((event) => (event.type === 'ToolCompleted' ? [event.tool_id] : []))
+    );
+    for (const event of wsEvents) {
+      if (event.type !== 'ControlRequest' || INLINE_PROMPT_TOOLS.has(event.tool_name)) continue;
+      if (answeredApprovals.has(event.request_id)) continue;
+      if (event.tool_use_id && completed.has(event.tool_use_id)) continue;
+      return {
+        type: 'tool_permission',
+        toolUseId: event.tool_use_id ?? event.request_id,
+        toolName: event.tool_name,
+        options,
+        detail: toolApprovalPreview(event.tool_name, event.input),
+        requestId: event.request_id,
+      };
+    }
+    return null;
+  }, [session?.agent_type, wsEvents, answeredApprovals]);
+
+  const answerApproval = (optionId: string, message?: string) => {
+    if (!session || !pendingApproval?.requestId) return;
+    const requestId = pendingApproval.requestId;
+    respondToControl(session.id, requestId, { option_id: optionId, ...(message ? { message } : {}) });
+    setAnsweredApprovals((prev) => new Set(prev).add(requestId));
+  };
+
   const handlePromptSubmit = (response: InlinePromptResponse) => {
+    if (!inlinePrompt && pendingApproval && response.type === 'tool_permission') {
+      answerApproval(response.optionId, response.message);
+      return;
+    }
     if (!session || !inlinePrompt) return;
 
     let controlResponse: unknown;
@@ -958,6 +1077,8 @@ export function ChatView({
     if (response.type === 'ask_user' && inlinePrompt.type === 'ask_user') {
       const updatedInput = buildAskUserUpdatedInput(inlinePrompt.questions, response.answers);
       controlResponse = buildPermissionAllowResponse(updatedInput, inlinePrompt.toolUseId);
+    } else if (response.type === 'tool_permission' && inlinePrompt.type === 'tool_permission') {
+      controlResponse = { option_id: response.optionId };
     } else if (response.type === 'exit_plan' && inlinePrompt.type === 'exit_plan') {
       if (response.approved) {
         const updatedInput = buildExitPlanUpdatedInput(inlinePrompt.plan);
@@ -983,8 +1104,16 @@ export function ChatView({
   };
 
   const handlePromptCancel = () => {
+    if (!inlinePrompt && pendingApproval) {
+      answerApproval('deny');
+      return;
+    }
     if (!session || !inlinePrompt) return;
-    const controlResponse = buildPermissionDenyResponse('User cancelled the prompt.', inlinePrompt.toolUseId);
+    // Gemini treats a response without option_id as a cancelled tool call.
+    const controlResponse =
+      inlinePrompt.type === 'tool_permission'
+        ? {}
+        : buildPermissionDenyResponse('User cancelled the prompt.', inlinePrompt.toolUseId);
     if (inlinePrompt.requestId) {
       respondToControl(session.id, inlinePrompt.requestId, controlResponse);
       setInlinePrompt(null);
@@ -1119,7 +1248,8 @@ export function ChatView({
   // Check if we have content to display
   const hasHistory = historyEvents.length > 0;
   const hasWsEvents = renderableWsEvents.length > 0;
-  const showStatusIndicator = !inlinePrompt && (isProcessing || isAwaitingResponse);
+  const activePrompt = inlinePrompt ?? pendingApproval;
+  const showStatusIndicator = !activePrompt && (isProcessing || isAwaitingResponse);
   const statusLabel = activeToolName
     ? `Running ${activeToolName}…`
     : `${processingWord}…`;
@@ -1184,10 +1314,10 @@ export function ChatView({
   const currentAttachments = session ? attachmentsBySession[session.id] ?? [] : [];
   const canStop = isProcessing || isAwaitingResponse;
 
-  const handleModelSelect = useCallback((modelId: string, newAgentType: 'claude' | 'codex' | 'gemini' | 'opencode') => {
+  const handleModelSelect = useCallback((modelId: string, newAgentType: 'claude' | 'codex' | 'gemini' | 'opencode' | 'acp') => {
     if (!session) return;
     // Only include agent_type in the request if it's different from current
-    const data: { model: string; agent_type?: 'claude' | 'codex' | 'gemini' | 'opencode' } = { model: modelId };
+    const data: { model: string; agent_type?: 'claude' | 'codex' | 'gemini' | 'opencode' | 'acp' } = { model: modelId };
     if (newAgentType !== session.agent_type) {
       data.agent_type = newAgentType;
     }
@@ -1206,7 +1336,7 @@ export function ChatView({
   }, [session, updateSessionMutation, onNotify]);
 
   const handleSetDefaultModel = useCallback(
-    (modelId: string, newAgentType: 'claude' | 'codex' | 'gemini' | 'opencode') => {
+    (modelId: string, newAgentType: 'claude' | 'codex' | 'gemini' | 'opencode' | 'acp') => {
       setDefaultModelMutation.mutate({ agent_type: newAgentType, model_id: modelId });
     },
     [setDefaultModelMutation]
@@ -1408,12 +1538,13 @@ export function ChatView({
                 <ChatMessage key={`ws-${index}`} event={event} />
               )
             ))}
-            {inlinePrompt && (
+            {activePrompt && (
               <InlinePrompt
-                prompt={inlinePrompt}
+                key={activePrompt.toolUseId}
+                prompt={activePrompt}
                 onSubmit={handlePromptSubmit}
                 onCancel={handlePromptCancel}
-                isPending={!inlinePrompt.requestId}
+                isPending={!activePrompt.requestId}
               />
             )}
             {showStatusIndicator && (
@@ -1434,6 +1565,13 @@ export function ChatView({
         />
       )}
 
+      {budgetBlockReason && (
+        <div className="mx-4 mb-2 flex items-start gap-2 rounded-lg border border-red-500/30 bg-red-500/10 px-3 py-2 text-sm text-red-400">
+          <AlertTriangle className="mt-0.5 h-4 w-4 shrink-0" />
+          <span className="min-w-0 break-words">{budgetBlockReason}</span>
+        </div>
+      )}
+
       <QueuePanel
         messages={queuedMessages}
         canSend={canSendQueued}
@@ -60,6 +60,8 @@ export function Header({
                   ? 'bg-green-400'
                   : activeSession.agent_type === 'opencode'
                   ? 'bg-teal-400'
+                  : activeSession.agent_type === 'acp'
+                  ? 'bg-purple-400'
                   : 'bg-blue-400'
               )}
             />
@@ -70,6 +72,8 @@ export function Header({
                 ? 'Codex'
                 : activeSession.agent_type === 'opencode'
                 ? 'OpenCode'
+                : activeSession.agent_type === 'acp'
+                ? 'ACP'
                 : 'Gemini'}
             </span>
             {supportsPlanMode(activeSession.agent_type) && (
@@ -1,5 +1,5 @@
 import { useMemo, useState } from 'react';
-import { CheckCircle2, MessageSquarePlus, XCircle } from 'lucide-react';
+import { CheckCircle2, MessageSquarePlus, ShieldQuestion, XCircle } from 'lucide-react';
 import type { UserQuestion } from '../types';
 import { cn } from '../lib/cn';
 
@@ -15,8 +15,25 @@ export type InlinePromptData =
       toolUseId: string;
       plan: string;
       requestId: string | null;
+    }
+  | {
+      type: 'tool_permission';
+      toolUseId: string;
+      toolName: string;
+      options: ToolPermissionOption[];
+      /** Command or -/+ diff preview of the tool call */
+      detail?: string | null;
+      requestId: string | null;
     };
 
+export interface ToolPermissionOption {
+  optionId: string;
+  name: string;
+  kind: string;
+  /** Ask for a reason before submitting this option */
+  requiresReason?: boolean;
+}
+
 export type InlinePromptResponse =
   | {
       type: 'ask_user';
@@ -26,6 +43,11 @@ export type InlinePromptResponse =
       type: 'exit_plan';
       approved: boolean;
       feedback?: string;
+    }
+  | {
+      type: 'tool_permission';
+      optionId: string;
+      message?: string;
     };
 
 interface InlinePromptProps {
@@ -47,6 +69,17 @@ export function InlinePrompt({ prompt, onSubmit, onCancel, isPending }: InlinePr
     );
   }
 
+  if (prompt.type === 'tool_permission') {
+    return (
+      <ToolPermissionPrompt
+        prompt={prompt}
+        onSubmit={onSubmit}
+        onCancel={onCancel}
+        isPending={isPending}
+      />
+    );
+  }
+
   return (
     <ExitPlanPrompt
       prompt={prompt}
@@ -272,3 +305,102 @@ function ExitPlanPrompt({
     </div>
   );
 }
+
+function ToolPermissionPrompt({
+  prompt,
+  onSubmit,
+  onCancel,
+  isPending,
+}: {
+  prompt: Extract<InlinePromptData, { type: 'tool_permission' }>;
+  onSubmit: (response: InlinePromptResponse) => void;
+  onCancel: () => void;
+  isPending?: boolean;
+}) {
+  const [reasonOption, setReasonOption] = useState<ToolPermissionOption | null>(null);
+  const [reason, setReason] = useState('');
+
+  return (
+    <div className="rounded-xl border border-border bg-surface p-4">
+      <div className="mb-3 flex items-center justify-between">
+        <div className="flex items-center gap-2 text-sm font-medium text-text">
+          <ShieldQuestion className="h-4 w-4 text-accent" />
+          <span>Allow tool?</span>
+        </div>
+        <button
+          onClick={onCancel}
+          className="text-xs text-text-muted hover:text-text"
+          disabled={isPending}
+        >
+          Cancel
+        </button>
+      </div>
+
+      <div className="rounded-lg border border-border bg-surface-elevated p-3 text-sm text-text">
+        {prompt.toolName}
+        {prompt.detail && (
+          <pre className="mt-2 max-h-64 overflow-auto whitespace-pre-wrap text-xs">
+            {prompt.detail.split('\n').map((line, index) => (
+              <div
+                key={index}
+                className={cn(
+                  line.startsWith('+ ') && 'text-success',
+                  line.startsWith('- ') && 'text-error',
+                  !line.startsWith('+ ') && !line.startsWith('- ') && 'text-text-muted'
+                )}
+              >
+                {line}
+              </div>
+            ))}
+          </pre>
+        )}
+      </div>
+
+      {reasonOption && (
+        <div className="mt-3">
+          <label className="text-xs text-text-muted">Reason (optional)</label>
+          <textarea
+            value={reason}
+            onChange={(event) => setReason(event.target.value)}
+            rows={2}
+            className="mt-1 w-full rounded-lg border border-border bg-surface-elevated px-3 py-2 text-sm text-text focus:border-accent focus:outline-none focus:ring-1 focus:ring-accent"
+            placeholder="Tell the agent what to do instead"
+          />
+        </div>
+      )}
+
+      <div className="mt-4 flex flex-wrap items-center justify-end gap-2">
+        {prompt.options.map((option) => {
+          const isAllow = option.kind.startsWith('allow');
+          const submit = () => {
+            if (option.requiresReason && reasonOption?.optionId !== option.optionId) {
+              setReasonOption(option);
+              return;
+            }
+            onSubmit({
+              type: 'tool_permission',
+              optionId: option.optionId,
+              ...(option.requiresReason && reason.trim() ? { message: reason.trim() } : {}),
+            });
+          };
+          return (
+            <button
+              key={option.optionId}
+              onClick={submit}
+              disabled={isPending}
+              className={cn(
+                'flex items-center gap-2 rounded-lg px-3 py-1.5 text-sm disabled:cursor-not-allowed disabled:opacity-60',
+                isAllow
+                  ? 'bg-accent text-white transition-colors hover:bg-accent-hover'
+                  : 'border border-border text-text-muted hover:bg-surface-elevated'
+              )}
+            >
+              {isAllow ? <CheckCircle2 className="h-4 w-4" /> : <XCircle className="h-4 w-4" />}
+              {option.name}
+            </button>
+          );
+        })}
+      </div>
+    </div>
+  );
+}
@@ -4,7 +4,7 @@ import { useExternalSessions, useImportExternalSession } from '../hooks';
 import type { ExternalSession, Session } from '../types';
 import { cn } from '../lib/cn';
 
-type AgentFilter = 'all' | 'claude' | 'codex' | 'gemini' | 'opencode';
+type AgentFilter = 'all' | 'claude' | 'codex' | 'gemini' | 'opencode' | 'acp';
 
 interface SessionImportDialogProps {
   isOpen: boolean;
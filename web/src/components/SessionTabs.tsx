@@ -133,6 +133,8 @@ export function SessionTabs({
                   ? 'bg-green-400'
                   : session.agent_type === 'opencode'
                   ? 'bg-teal-400'
+                  : session.agent_type === 'acp'
+                  ? 'bg-purple-400'
                   : 'bg-blue-400'
               )}
             />
@@ -12,6 +12,7 @@ interface WebSocketContextValue {
   connectionState: ConnectionState;
   processingSessionIds: Set<string>;
   unseenSessionIds: Set<string>;
+  budgetBlockedSessions: Map<string, string>;
   clearUnseenSession: (sessionId: string) => void;
   sendInput: (
     sessionId: string,
@@ -51,6 +52,10 @@ export function WebSocketProvider({ children }: WebSocketProviderProps) {
   const [connectionState, setConnectionState] = useState<ConnectionState>('disconnected');
   const [processingSessionIds, setProcessingSessionIds] = useState<Set<string>>(new Set());
   const [unseenSessionIds, setUnseenSessionIds] = useState<Set<string>>(new Set());
+  // Sessions held at a hard budget limit, with the server's explanation
+  const [budgetBlockedSessions, setBudgetBlockedSessions] = useState<Map<string, string>>(
+    new Map()
+  );
   const activeSessionIdRef = useRef<string | null>(null);
   const runningSessionsRef = useRef(new Set<string>());
   const pendingPromptsRef = useRef(
@@ -80,12 +85,26 @@ export function WebSocketProvider({ children }: WebSocketProviderProps) {
       if (message.type === 'agent_event') {
         runningSessionsRef.current.add(message.session_id);
         const event = message.event;
-        if (event.type === 'TurnStarted') {
+        if (event.type === 'Error' && event.code === 'agent_restarting') {
+          // A crashed agent being resumed keeps the turn running
+          setProcessingSessionIds((prev) => new Set(prev).add(message.session_id));
+        } else if (
+          event.type === 'Error' &&
+          (event.code === 'event_hook_feedback' || event.code === 'event_hook_failed')
+        ) {
+          // Event hook notices can arrive mid-turn and don't end it
+        } else if (event.type === 'TurnStarted') {
           setProcessingSessionIds((prev) => {
             const next = new Set(prev);
             next.add(message.session_id);
             return next;
           });
+          setBudgetBlockedSessions((prev) => {
+            if (!prev.has(message.session_id)) return prev;
+            const next = new Map(prev);
+            next.delete(message.session_id);
+            return next;
+          });
         } else if (event.type === 'TurnCompleted' || event.type === 'TurnFailed' || event.type === 'Error') {
           setProcessingSessionIds((prev) => {
             const next = new Set(prev);
@@ -100,6 +119,9 @@ export function WebSocketProvider({ children }: WebSocketProviderProps) {
               return next;
             });
           }
+          if (event.type === 'Error' && event.code === 'budget_exceeded') {
+            setBudgetBlockedSessions((prev) => new Map(prev).set(message.session_id, event.message));
+          }
           if (event.type === 'Error' && event.code === 'model_not_found') {
             queryClient.setQueryData<Session>(queryKeys.session(message.session_id), (prev) =>
               prev ? { ...prev, model: null, model_display_name: null, model_invalid: true } : prev
@@ -274,6 +296,7 @@ export function WebSocketProvider({ children }: WebSocketProviderProps) {
     connectionState,
     processingSessionIds,
     unseenSessionIds,
+    budgetBlockedSessions,
     clearUnseenSession,
     sendInput,
     sendPrompt,
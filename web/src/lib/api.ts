@@ -264,6 +264,12 @@ export async function getSessionHistory(id: string): Promise<InputHistoryRespons
   return request(`/sessions/${id}/history`);
 }
 
+export type TranscriptFormat = 'markdown' | 'html' | 'jsonl';
+
+export function sessionExportUrl(id: string, format: TranscriptFormat = 'markdown'): string {
+  return `${API_BASE}/sessions/${id}/export?format=${format}`;
+}
+
 export async function getSessionQueue(id: string): Promise<SessionQueueResponse> {
   return request(`/sessions/${id}/queue`);
 }
@@ -2,6 +2,12 @@
 
 export type WorkspaceMode = 'worktree' | 'checkout';
 
+export type ApprovalPolicy = 'ask' | 'allow-reads' | 'allow-all';
+
+export type CodexApprovalMode = 'untrusted' | 'on-failure' | 'on-request' | 'never';
+
+export type CodexSandboxMode = 'read-only' | 'workspace-write' | 'danger-full-access';
+
 export interface Repository {
   id: string;
   name: string;
@@ -13,6 +19,10 @@ export interface Repository {
   archive_delete_branch_effective: boolean;
   archive_remote_prompt: boolean | null;
   archive_remote_prompt_effective: boolean;
+  approval_policy: ApprovalPolicy | null;
+  approval_policy_effective: ApprovalPolicy;
+  codex_approval_mode: CodexApprovalMode | null;
+  codex_sandbox_mode: CodexSandboxMode | null;
   created_at: string;
   updated_at: string;
 }
@@ -57,7 +67,7 @@ export interface Session {
   id: string;
   tab_index: number;
   workspace_id: string | null;
-  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode';
+  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode' | 'acp';
   agent_mode: string | null;
   agent_session_id: string | null;
   model: string | null;
@@ -66,6 +76,8 @@ export interface Session {
   pr_number: number | null;
   created_at: string;
   title: string | null;
+  codex_approval_mode: CodexApprovalMode | null;
+  codex_sandbox_mode: CodexSandboxMode | null;
 }
 
 export interface Agent {
@@ -105,6 +117,9 @@ export interface UpdateRepositorySettingsRequest {
   workspace_mode?: WorkspaceMode;
   archive_delete_branch?: boolean;
   archive_remote_prompt?: boolean;
+  approval_policy?: ApprovalPolicy;
+  codex_approval_mode?: CodexApprovalMode;
+  codex_sandbox_mode?: CodexSandboxMode;
 }
 
 export interface CreateWorkspaceRequest {
@@ -120,7 +135,7 @@ export interface ArchiveWorkspaceRequest {
 
 export interface CreateSessionRequest {
   workspace_id?: string;
-  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode';
+  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode' | 'acp';
   model?: string;
 }
 
@@ -176,7 +191,7 @@ export interface BootstrapResponse {
 
 export interface ExternalSession {
   id: string;
-  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode';
+  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode' | 'acp';
   display: string;
   project?: string | null;
   project_name?: string | null;
@@ -313,7 +328,7 @@ export interface ModelInfo {
   display_name: string;
   description: string;
   is_default: boolean;
-  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode';
+  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode' | 'acp';
   context_window: number;
 }
 
@@ -330,12 +345,14 @@ export interface ListModelsResponse {
 
 export interface UpdateSessionRequest {
   model?: string;
-  agent_type?: 'claude' | 'codex' | 'gemini' | 'opencode';
+  agent_type?: 'claude' | 'codex' | 'gemini' | 'opencode' | 'acp';
   agent_mode?: 'build' | 'plan';
+  codex_approval_mode?: CodexApprovalMode;
+  codex_sandbox_mode?: CodexSandboxMode;
 }
 
 export interface SetDefaultModelRequest {
-  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode';
+  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode' | 'acp';
   model_id: string;
 }
 